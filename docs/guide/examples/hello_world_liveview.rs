@@ -1,6 +1,7 @@
 // ANCHOR: all
-use axum::{extract::ws::WebSocketUpgrade, response::Html, routing::get, Router};
+use axum::{response::Html, routing::get, Router};
 use dioxus::prelude::*;
+use dioxus_liveview::LiveViewUpgrade;
 
 // ANCHOR: glue
 #[tokio::main]
@@ -31,11 +32,9 @@ async fn main() {
         // The WebSocket route is what Dioxus uses to communicate with the browser
         .route(
             "/ws",
-            get(move |ws: WebSocketUpgrade| async move {
-                ws.on_upgrade(move |socket| async move {
-                    // When the WebSocket is upgraded, launch the LiveView with the app component
-                    _ = view.launch(dioxus_liveview::axum_socket(socket), app).await;
-                })
+            get(move |ws: LiveViewUpgrade| async move {
+                // When the WebSocket is upgraded, launch the LiveView with the app component
+                view.launch(ws, app)
             }),
         );
 
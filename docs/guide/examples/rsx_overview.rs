@@ -144,6 +144,7 @@ cx.render(rsx!{
         for i in 0..3 {
             // NOTE: the body of the loop is RSX not a rust statement
             div {
+                key: "{i}",
                 "{i}"
             }
         }
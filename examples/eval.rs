@@ -5,7 +5,7 @@ fn main() {
 }
 
 fn app(cx: Scope) -> Element {
-    let eval = dioxus_desktop::use_eval(cx);
+    let eval = use_eval(cx);
     let script = use_state(cx, String::new);
     let output = use_state(cx, String::new);
 
@@ -20,14 +20,41 @@ fn app(cx: Scope) -> Element {
             button {
                 onclick: move |_| {
                     to_owned![script, eval, output];
-                    cx.spawn(async move {
-                        if let Ok(res) = eval(script.to_string()).await {
-                            output.set(res.to_string());
+                    async move {
+                        let result = match eval(&format!("return {script};")) {
+                            Ok(running) => running.join::<serde_json::Value>().await,
+                            Err(err) => Err(err),
+                        };
+                        match result {
+                            Ok(value) => output.set(value.to_string()),
+                            Err(err) => output.set(err.to_string()),
                         }
-                    });
+                    }
                 },
                 "Execute"
             }
+            button {
+                onclick: move |_| {
+                    to_owned![eval, output];
+                    async move {
+                        // the script and Rust can keep talking while it runs
+                        let running = match eval(r#"
+                            const name = await dioxus.recv();
+                            dioxus.send(`hello ${name}`);
+                            return name.length;
+                        "#) {
+                            Ok(running) => running,
+                            Err(err) => return output.set(err.to_string()),
+                        };
+
+                        let _ = running.send("dioxus");
+                        let greeting = running.recv::<String>().await;
+                        let length = running.join::<usize>().await;
+                        output.set(format!("{greeting:?} {length:?}"));
+                    }
+                },
+                "Say hello"
+            }
         }
     })
 }
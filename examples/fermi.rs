@@ -48,7 +48,7 @@ fn ChildWithRef(cx: Scope) -> Element {
                     let names = names.clone();
                     cx.spawn(async move {
                         names.write().push("asd".to_string());
-                    })
+                    });
                 },
                 "Add name"
             }
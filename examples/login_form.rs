@@ -13,8 +13,8 @@ fn app(cx: Scope) -> Element {
             let resp = reqwest::Client::new()
                 .post("http://localhost:8080/login")
                 .form(&[
-                    ("username", &evt.values["username"]),
-                    ("password", &evt.values["password"]),
+                    ("username", evt.value_of("username").unwrap_or_default()),
+                    ("password", evt.value_of("password").unwrap_or_default()),
                 ])
                 .send()
                 .await;
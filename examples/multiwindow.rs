@@ -1,4 +1,5 @@
 use dioxus::prelude::*;
+use dioxus_desktop::use_window_close_handler;
 
 fn main() {
     dioxus_desktop::launch(app);
@@ -6,6 +7,13 @@ fn main() {
 
 fn app(cx: Scope) -> Element {
     let window = dioxus_desktop::use_window(cx);
+    let windows = use_state(cx, || window.windows());
+
+    use_window_close_handler(cx, {
+        let windows = windows.clone();
+        let window = window.clone();
+        move |_| windows.set(window.windows())
+    });
 
     cx.render(rsx! {
         div {
@@ -13,9 +21,18 @@ fn app(cx: Scope) -> Element {
                 onclick: move |_| {
                     let dom = VirtualDom::new(popup);
                     window.new_window(dom, Default::default());
+                    windows.set(window.windows());
                 },
                 "New Window"
             }
+            ul {
+                windows.iter().enumerate().map(|(idx, id)| rsx! {
+                    li {
+                        key: "{idx}",
+                        button { onclick: move |_| window.focus_window(*id), "Focus window {idx}" }
+                    }
+                })
+            }
         }
     })
 }
@@ -7,8 +7,9 @@ fn main() {
     let mut vdom = VirtualDom::new(example);
     _ = vdom.rebuild();
 
-    let mut renderer = dioxus_ssr::Renderer::new();
-    renderer.pretty = true;
+    let mut renderer = dioxus_ssr::Renderer::with_config(
+        dioxus_ssr::SsrConfig::new().whitespace(dioxus_ssr::Whitespace::Pretty),
+    );
     renderer.render(&vdom);
 }
 
@@ -104,29 +104,28 @@ fn app(cx: Scope) -> Element {
             }
 
             // Matching
+            // Every arm is rendered as its own template, so arms don't need rsx! or to share a type
             match true {
-                true => rsx!( h1 {"Top text"}),
-                false => rsx!( h1 {"Bottom text"})
+                true => h1 {"Top text"},
+                false => {
+                    h1 {"Bottom text"}
+                    p {"with a subtitle"}
+                }
             }
 
             // Conditional rendering
-            // Dioxus conditional rendering is based around None/Some. We have no special syntax for conditionals.
-            // You can convert a bool condition to rsx! with .then and .or
-            true.then(|| rsx!(div {})),
-
-            // Alternatively, you can use the "if" syntax - but both branches must be resolve to Element
+            // The branches of an "if" are rsx, and a missing "else" renders nothing
             if false {
-                rsx!(h1 {"Top text"})
+                h1 {"Top text"}
+            } else if true {
+                h2 {"Middle text"}
             } else {
-                rsx!(h1 {"Bottom text"})
+                "Bottom text"
             }
 
-            // Using optionals for diverging branches
-            if true {
-                Some(rsx!(h1 {"Top text"}))
-            } else {
-                None
-            }
+            // Dioxus conditional rendering is also based around None/Some.
+            // You can convert a bool condition to rsx! with .then and .or
+            true.then(|| rsx!(div {})),
 
             // returning "None" without a diverging branch is a bit noisy... but rare in practice
             None as Option<()>,
@@ -177,6 +176,12 @@ fn app(cx: Scope) -> Element {
             // Can take children too!
             Taller { a: "asd", div {"hello world!"} }
 
+            // Other Element props are named slots, which are filled with rsx! right in place
+            Card {
+                header: rsx! { h2 { "A card" } },
+                p { "The body of the card" }
+            }
+
             // This component's props are defined *inline* with the `inline_props` macro
             WithInline { text: "using functionc all syntax" }
 
@@ -260,6 +265,23 @@ pub fn Taller<'a>(cx: Scope<'a, TallerProps<'a>>) -> Element {
     })
 }
 
+#[derive(Props)]
+pub struct CardProps<'a> {
+    /// Slots that aren't passed are left empty
+    header: Element<'a>,
+    footer: Element<'a>,
+    children: Element<'a>,
+}
+
+#[allow(non_snake_case)]
+pub fn Card<'a>(cx: Scope<'a, CardProps<'a>>) -> Element {
+    cx.render(rsx! {
+        div { &cx.props.header }
+        div { &cx.props.children }
+        div { &cx.props.footer }
+    })
+}
+
 #[derive(Props, PartialEq, Eq)]
 pub struct TypedInputProps<T> {
     #[props(optional, default)]
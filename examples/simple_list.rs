@@ -21,8 +21,9 @@ fn app(cx: Scope) -> Element {
             Some(rsx! { "Some" }),
 
             // use a for loop where the body itself is RSX
+            // every item needs a key, which lets dioxus move items instead of recreating them
             for name in 0..10 {
-                div {"{name}"}
+                div { key: "{name}", "{name}" }
             }
 
             // Or even use an unterminated conditional
@@ -0,0 +1,211 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use std::path::{Path, PathBuf};
+use syn::{
+    parse::{Parse, ParseStream},
+    Ident, LitByteStr, LitInt, LitStr, Result, Token,
+};
+
+pub struct AssetInput {
+    path: LitStr,
+    image: ImageOptions,
+}
+
+/// How an image is changed while the app is built
+#[derive(Default)]
+struct ImageOptions {
+    width: Option<u32>,
+    height: Option<u32>,
+    format: Option<LitStr>,
+    span: Option<Span>,
+}
+
+impl Parse for AssetInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let path = input.parse()?;
+        let mut image = ImageOptions::default();
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let name: Ident = input.parse()?;
+            input.parse::<Token![:]>()?;
+            match name.to_string().as_str() {
+                "width" => image.width = Some(input.parse::<LitInt>()?.base10_parse()?),
+                "height" => image.height = Some(input.parse::<LitInt>()?.base10_parse()?),
+                "format" => image.format = Some(input.parse()?),
+                _ => {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        "assets only take a `width`, `height` or `format`",
+                    ))
+                }
+            }
+            image.span.get_or_insert(name.span());
+        }
+
+        Ok(Self { path, image })
+    }
+}
+
+pub fn expand(input: AssetInput) -> Result<TokenStream> {
+    let path = resolve(&input.path.value());
+    let data = std::fs::read(&path).map_err(|err| {
+        syn::Error::new(
+            input.path.span(),
+            format!("failed to read {}: {}", path.display(), err),
+        )
+    })?;
+
+    let mut extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase());
+    let full_path = path.to_string_lossy().into_owned();
+
+    // the file is always included, so cargo builds the app again when it changes
+    let bytes = match input.image.span {
+        None => quote! { ::std::include_bytes!(#full_path) },
+        Some(span) => {
+            let (processed, format) =
+                process_image(&data, &input.image).map_err(|err| syn::Error::new(span, err))?;
+            extension = Some(format);
+            let processed = LitByteStr::new(&processed, Span::call_site());
+            quote! {{
+                const _: &[u8] = ::std::include_bytes!(#full_path);
+                #processed
+            }}
+        }
+    };
+
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let hash = format!("{:016x}", fnv1a(&data, &input.image));
+    let name = match &extension {
+        Some(extension) => format!("{}-{}.{}", stem, hash, extension),
+        None => format!("{}-{}", stem, hash),
+    };
+    let mime = mime(extension.as_deref().unwrap_or_default());
+
+    Ok(quote! {{
+        static ASSET: ::dioxus_assets::Asset = ::dioxus_assets::Asset::new(#name, #bytes, #mime);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        const _: () = {
+            ::dioxus_assets::__private::inventory::submit! {
+                ::dioxus_assets::__private::AssetEntry(&ASSET)
+            }
+        };
+
+        ASSET.url()
+    }})
+}
+
+/// Paths are relative to the crate the macro is used in, like `./assets/logo.png`
+fn resolve(path: &str) -> PathBuf {
+    let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    Path::new(&root).join(path.trim_start_matches("./"))
+}
+
+/// A hash of the asset that stays the same between builds, unlike the hasher of std
+///
+/// Processed images hash the options too, since the same file can be turned into several images.
+fn fnv1a(data: &[u8], image: &ImageOptions) -> u64 {
+    let options = format!(
+        "{:?}{:?}{:?}",
+        image.width,
+        image.height,
+        image.format.as_ref().map(LitStr::value)
+    );
+    let options = match image.span {
+        Some(_) => options.into_bytes(),
+        None => Vec::new(),
+    };
+
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in data.iter().chain(&options) {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+fn mime(extension: &str) -> &'static str {
+    match extension {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "svg" => "image/svg+xml",
+        "ico" => "image/x-icon",
+        "bmp" => "image/bmp",
+        "css" => "text/css",
+        "js" | "mjs" => "text/javascript",
+        "json" => "application/json",
+        "wasm" => "application/wasm",
+        "html" | "htm" => "text/html",
+        "txt" => "text/plain",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "ogg" => "audio/ogg",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Resize an image and convert it to another format, giving back the new image and its extension
+#[cfg(feature = "image")]
+fn process_image(
+    data: &[u8],
+    options: &ImageOptions,
+) -> std::result::Result<(Vec<u8>, String), String> {
+    use image::{imageops::FilterType, ImageFormat};
+
+    let format = match &options.format {
+        Some(format) => ImageFormat::from_extension(format.value()).ok_or_else(|| {
+            format!(
+                "{} isn't a format images can be converted to",
+                format.value()
+            )
+        })?,
+        None => image::guess_format(data).map_err(|err| err.to_string())?,
+    };
+
+    let mut image = image::load_from_memory(data).map_err(|err| err.to_string())?;
+    if options.width.is_some() || options.height.is_some() {
+        image = image.resize(
+            options.width.unwrap_or(u32::MAX),
+            options.height.unwrap_or(u32::MAX),
+            FilterType::Lanczos3,
+        );
+    }
+
+    let mut processed = std::io::Cursor::new(Vec::new());
+    image
+        .write_to(&mut processed, format)
+        .map_err(|err| err.to_string())?;
+
+    let extension = format.extensions_str().first().copied().unwrap_or_default();
+    Ok((processed.into_inner(), extension.to_string()))
+}
+
+#[cfg(not(feature = "image"))]
+fn process_image(
+    _data: &[u8],
+    _options: &ImageOptions,
+) -> std::result::Result<(Vec<u8>, String), String> {
+    Err(
+        "changing images while the app is built needs the `image` feature of dioxus-assets"
+            .to_string(),
+    )
+}
@@ -0,0 +1,24 @@
+use proc_macro::TokenStream;
+use syn::parse_macro_input;
+
+mod asset;
+
+/// Include a file in the app under a name with a hash of its contents, and evaluate to the url it's served at
+///
+/// The path is relative to the root of the crate. Options after it turn images into another size or format while the
+/// app is built, which needs the `image` feature:
+///
+/// ```rust, ignore
+/// img { src: asset!("./assets/logo.png") }
+/// img { src: asset!("./assets/photo.jpg", width: 640, format: "webp") }
+/// ```
+///
+/// Images keep their aspect ratio, fitting inside the `width` and `height` that are given.
+#[proc_macro]
+pub fn asset(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as asset::AssetInput);
+    match asset::expand(input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,121 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+
+use once_cell::sync::{Lazy, OnceCell};
+use std::{collections::HashMap, sync::Mutex};
+
+pub use dioxus_assets_macro::asset;
+
+/// The path assets are served under, unless [`set_asset_base`] moves them somewhere else
+pub const ASSET_PREFIX: &str = "/_dioxus/assets";
+
+/// A file of the app that [`asset!`] included
+#[derive(Debug)]
+pub struct Asset {
+    name: &'static str,
+    bytes: &'static [u8],
+    mime: &'static str,
+}
+
+impl Asset {
+    #[doc(hidden)]
+    pub const fn new(name: &'static str, bytes: &'static [u8], mime: &'static str) -> Self {
+        Self { name, bytes, mime }
+    }
+
+    /// The name of the file, with a hash of the contents in it like `logo-3f8a5c0e1d2b4a69.png`
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The contents of the file
+    pub fn bytes(&self) -> &'static [u8] {
+        self.bytes
+    }
+
+    /// The mime type of the file, like `image/png`
+    pub fn mime(&self) -> &'static str {
+        self.mime
+    }
+
+    /// The url the asset is served at
+    ///
+    /// That's a path under [`ASSET_PREFIX`] by default, which desktop apps and fullstack servers serve. In the browser,
+    /// the asset is served from the wasm binary unless [`set_asset_base`] was called.
+    pub fn url(&'static self) -> &'static str {
+        let mut urls = URLS.lock().unwrap();
+        *urls.entry(self.name).or_insert_with(|| {
+            #[cfg(target_arch = "wasm32")]
+            let object_url = match ASSET_BASE.get() {
+                Some(_) => None,
+                None => web::object_url(self),
+            };
+            #[cfg(not(target_arch = "wasm32"))]
+            let object_url = None;
+
+            let url = object_url.unwrap_or_else(|| {
+                let base = ASSET_BASE.get().map_or(ASSET_PREFIX, String::as_str);
+                format!("{}/{}", base, self.name)
+            });
+            Box::leak(url.into_boxed_str())
+        })
+    }
+}
+
+// every asset gets one url, so leaking them is bounded by the number of assets
+static URLS: Lazy<Mutex<HashMap<&'static str, &'static str>>> = Lazy::new(Default::default);
+
+static ASSET_BASE: OnceCell<String> = OnceCell::new();
+
+/// Serve assets from another url, like a CDN at `https://cdn.example.com/assets`, or a server that renders the page
+///
+/// It has to be set before the url of an asset is used, and can only be set once. Fullstack apps call it with
+/// [`ASSET_PREFIX`] in the browser, so the client uses the assets the server serves instead of the ones in the wasm
+/// binary.
+pub fn set_asset_base(base: impl Into<String>) {
+    let base = base.into();
+    ASSET_BASE
+        .set(base.trim_end_matches('/').to_string())
+        .expect("the asset base can only be set once");
+}
+
+/// Every asset of the app, from every crate it's built from
+#[cfg(not(target_arch = "wasm32"))]
+pub fn assets() -> impl Iterator<Item = &'static Asset> {
+    inventory::iter::<__private::AssetEntry>
+        .into_iter()
+        .map(|entry| entry.0)
+}
+
+/// Find the asset with a name, which is how servers look up the asset of a request
+#[cfg(not(target_arch = "wasm32"))]
+pub fn find_asset(name: &str) -> Option<&'static Asset> {
+    assets().find(|asset| asset.name == name)
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use super::Asset;
+    use web_sys::{Blob, BlobPropertyBag, Url};
+
+    /// A url for the bytes of the asset that are already in the wasm binary
+    pub(super) fn object_url(asset: &Asset) -> Option<String> {
+        let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(asset.bytes));
+        let mut options = BlobPropertyBag::new();
+        options.type_(asset.mime);
+        let blob = Blob::new_with_u8_array_sequence_and_options(&parts, &options).ok()?;
+        Url::create_object_url_with_blob(&blob).ok()
+    }
+}
+
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use inventory;
+
+    /// An asset registered with inventory, so servers can find it before anything asked for its url
+    pub struct AssetEntry(pub &'static super::Asset);
+
+    #[cfg(not(target_arch = "wasm32"))]
+    inventory::collect!(AssetEntry);
+}
@@ -0,0 +1,20 @@
+use dioxus_assets::{asset, find_asset, ASSET_PREFIX};
+
+#[test]
+fn assets_get_hashed_names() {
+    let url = asset!("./tests/logo.svg");
+
+    let name = url
+        .strip_prefix(ASSET_PREFIX)
+        .unwrap()
+        .trim_start_matches('/');
+    assert!(name.starts_with("logo-"));
+    assert!(name.ends_with(".svg"));
+
+    // the same file gets the same name everywhere
+    assert_eq!(asset!("tests/logo.svg"), url);
+
+    let asset = find_asset(name).unwrap();
+    assert_eq!(asset.bytes(), include_bytes!("logo.svg"));
+    assert_eq!(asset.mime(), "image/svg+xml");
+}
@@ -13,6 +13,9 @@ use dioxus_rsx::IfmtInput;
 pub struct Buffer {
     pub buf: String,
     pub indent: usize,
+
+    /// A `//` comment that ended the line of the last node, which is written at the next line break
+    pub trailing_comment: Option<String>,
 }
 
 impl Buffer {
@@ -44,9 +47,18 @@ impl Buffer {
     }
 
     pub fn new_line(&mut self) -> Result {
+        if let Some(comment) = self.trailing_comment.take() {
+            write!(self.buf, " {comment}")?;
+        }
         writeln!(self.buf)
     }
 
+    // The indentation of the line that's being written
+    pub fn current_indent(&self) -> usize {
+        let line = self.buf.rsplit('\n').next().unwrap_or_default();
+        line.chars().take_while(|c| *c == ' ').count()
+    }
+
     pub fn write_text(&mut self, text: &IfmtInput) -> Result {
         write!(self.buf, "\"{}\"", text.source.as_ref().unwrap().value())
     }
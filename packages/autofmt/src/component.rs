@@ -158,11 +158,22 @@ impl Writer {
 
         while let Some(field) = field_iter.next() {
             if !sameline {
+                if self.current_span_is_primary(field.name.span()) {
+                    self.out.indent += 1;
+                    self.write_comments(field.name.span())?;
+                    self.out.indent -= 1;
+                }
                 self.out.indented_tabbed_line()?;
             }
 
             let name = &field.name;
             match &field.content {
+                ContentField::ManExpr(exp) | ContentField::OnHandlerRaw(exp)
+                    if self.is_verbatim_expr(exp.span()) =>
+                {
+                    write!(self.out, "{}: ", name)?;
+                    self.write_raw_expr(exp.span())?;
+                }
                 ContentField::ManExpr(exp) => {
                     let out = prettyplease::unparse_expr(exp);
                     write!(self.out, "{}: {}", name, out)?;
@@ -175,6 +186,12 @@ impl Writer {
                         s.source.as_ref().unwrap().value()
                     )?;
                 }
+                ContentField::Slot { mac, nodes } => {
+                    write!(self.out, "{}: {}! {{", name, mac)?;
+                    self.out.indent += 1;
+                    self.write_block(nodes)?;
+                    self.out.indent -= 1;
+                }
                 ContentField::OnHandlerRaw(exp) => {
                     let out = prettyplease::unparse_expr(exp);
                     let mut lines = out.split('\n').peekable();
@@ -188,6 +205,10 @@ impl Writer {
                 }
             }
 
+            if let Some(end) = field_end(field) {
+                self.write_trailing_comment(end);
+            }
+
             if field_iter.peek().is_some() || manual_props.is_some() {
                 write!(self.out, ",")?;
 
@@ -212,9 +233,28 @@ impl Writer {
         fields: &[ComponentField],
         manual_props: &Option<syn::Expr>,
     ) -> usize {
+        for field in fields {
+            if self.current_span_is_primary(field.name.span()) {
+                'line: for line in self.src[..field.name.span().start().line - 1].iter().rev() {
+                    match (line.trim().starts_with("//"), line.is_empty()) {
+                        (true, _) => return 100000,
+                        (_, true) => continue 'line,
+                        _ => break 'line,
+                    }
+                }
+            }
+
+            if field_end(field).map_or(false, |end| self.trailing_comment(end).is_some()) {
+                return 100000;
+            }
+        }
+
         let attr_len = fields
             .iter()
             .map(|field| match &field.content {
+                // multiline exprs that are copied from the source bump everything down
+                ContentField::OnHandlerRaw(exp) | ContentField::ManExpr(exp)
+                    if self.is_verbatim_expr(exp.span()) && exp.span().start().line != exp.span().end().line => 10000,
                 ContentField::Formatted(s) => s.source.as_ref().unwrap().value().len() ,
                 ContentField::OnHandlerRaw(exp) | ContentField::ManExpr(exp) => {
                     let formatted = prettyplease::unparse_expr(exp);
@@ -226,6 +266,9 @@ impl Writer {
                     self.cached_formats.insert(Location::new(exp.span().start()) , formatted);
                     len
                 },
+                // slots with a body are always written over multiple lines
+                ContentField::Slot { nodes, .. } if nodes.is_empty() => 0,
+                ContentField::Slot { .. } => 10000,
             } + 10)
             .sum::<usize>();
 
@@ -268,3 +311,12 @@ impl Writer {
         Ok(())
     }
 }
+
+/// Where a field ends, for the fields that a comment can follow on their last line
+fn field_end(field: &ComponentField) -> Option<proc_macro2::Span> {
+    match &field.content {
+        ContentField::ManExpr(exp) | ContentField::OnHandlerRaw(exp) => Some(exp.span()),
+        ContentField::Formatted(s) => Some(s.source.span()),
+        ContentField::Slot { .. } => None,
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Writer;
+use crate::{writer::attr_end, Writer};
 use dioxus_rsx::*;
 use proc_macro2::Span;
 use std::{
@@ -200,6 +200,7 @@ impl Writer {
             }
 
             self.write_attribute(attr)?;
+            self.write_trailing_comment(attr_end(&attr.attr));
 
             if attr_iter.peek().is_some() {
                 write!(self.out, ",")?;
@@ -222,6 +223,10 @@ impl Writer {
                     value = value.source.as_ref().unwrap().value()
                 )?;
             }
+            ElementAttr::AttrExpression { name, value } if self.is_verbatim_expr(value.span()) => {
+                write!(self.out, "{}: ", name)?;
+                self.write_raw_expr(value.span())?;
+            }
             ElementAttr::AttrExpression { name, value } => {
                 let out = prettyplease::unparse_expr(value);
                 write!(self.out, "{}: {}", name, out)?;
@@ -236,11 +241,26 @@ impl Writer {
                 )?;
             }
 
+            ElementAttr::CustomAttrExpression { name, value }
+                if self.is_verbatim_expr(value.span()) =>
+            {
+                write!(self.out, "\"{}\": ", name.value())?;
+                self.write_raw_expr(value.span())?;
+            }
             ElementAttr::CustomAttrExpression { name, value } => {
                 let out = prettyplease::unparse_expr(value);
                 write!(self.out, "\"{}\": {}", name.value(), out)?;
             }
 
+            ElementAttr::Spread { value, .. } => {
+                let out = prettyplease::unparse_expr(value);
+                write!(self.out, "..{}", out)?;
+            }
+
+            ElementAttr::EventTokens { name, tokens } if self.is_verbatim_expr(tokens.span()) => {
+                write!(self.out, "{}: ", name)?;
+                self.write_raw_expr(tokens.span())?;
+            }
             ElementAttr::EventTokens { name, tokens } => {
                 let out = self.retrieve_formatted_expr(tokens).to_string();
 
@@ -367,6 +387,7 @@ impl Writer {
                         },
                         BodyNode::ForLoop(_forloop) => return None,
                         BodyNode::IfChain(_chain) => return None,
+                        BodyNode::Match(_expr) => return None,
                     }
                 }
 
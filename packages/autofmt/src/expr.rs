@@ -25,7 +25,7 @@ impl Writer {
         }
 
         // If the expr is multiline, we want to collect all of its lines together and write them out properly
-        // This involves unshifting the first line if it's aligned
+        // The lines after the first keep their indentation relative to the line the expr starts on
         let first_line = &self.src[start.line - 1];
         write!(
             self.out,
@@ -33,37 +33,43 @@ impl Writer {
             &first_line[start.column - 1..first_line.len()].trim()
         )?;
 
-        let first_prefix = &self.src[start.line - 1][..start.column];
-        let offset = match first_prefix.trim() {
-            "" => 0,
-            _ => first_prefix
-                .chars()
-                .rev()
-                .take_while(|c| c.is_whitespace())
-                .count() as isize,
-        };
+        let offset = leading_whitespace(first_line);
+        let indent = self.out.current_indent();
 
         for (id, line) in self.src[start.line..end.line].iter().enumerate() {
             writeln!(self.out)?;
-            // trim the leading whitespace
+
             let line = match id {
                 x if x == (end.line - start.line) - 1 => &line[..end.column],
                 _ => line,
             };
 
-            if offset < 0 {
-                for _ in 0..-offset {
-                    write!(self.out, " ")?;
-                }
-
-                write!(self.out, "{}", line)?;
-            } else {
-                let offset = offset as usize;
-                let right = &line[offset..];
-                write!(self.out, "{}", right)?;
+            // trim the leading whitespace
+            let right = &line[leading_whitespace(line).min(offset)..];
+            if !right.trim().is_empty() {
+                write!(self.out, "{:indent$}{}", "", right)?;
             }
         }
 
         Ok(())
     }
+
+    /// Expressions with comments or nested rsx are copied from the source instead of going through prettyplease,
+    /// which drops the comments and can't format the rsx. The nested rsx is formatted in a pass of its own.
+    pub fn is_verbatim_expr(&self, placement: Span) -> bool {
+        let (start, end) = (placement.start(), placement.end());
+        let lines = match self.src.get(start.line.saturating_sub(1)..end.line) {
+            Some(lines) => lines,
+            None => return false,
+        };
+
+        lines
+            .iter()
+            .any(|line| line.contains("rsx!") || line.contains("render!"))
+            || (start.line != end.line && lines.iter().any(|line| line.contains("//")))
+    }
+}
+
+fn leading_whitespace(line: &str) -> usize {
+    line.len() - line.trim_start().len()
 }
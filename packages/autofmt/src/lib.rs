@@ -73,7 +73,10 @@ pub fn fmt_file(contents: &str) -> Vec<FormattedBlock> {
         };
 
         let remaining = &contents[open - 1..];
-        let close = find_bracket_end(remaining).unwrap();
+        let close = match find_bracket_end(remaining) {
+            Some(close) => close,
+            None => continue,
+        };
         // Move the last bracket end to the end of this block to avoid nested blocks
         last_bracket_end = close + open - 1;
 
@@ -83,7 +86,14 @@ pub fn fmt_file(contents: &str) -> Vec<FormattedBlock> {
         // make sure to add back whatever weird whitespace there was at the end
         let mut remaining_whitespace = substring.chars().rev().take_while(|c| *c == ' ').count();
 
-        let mut new = fmt_block(substring, indent_level).unwrap();
+        // Blocks that aren't valid rsx are left as they are
+        let mut new = match fmt_block(substring, indent_level) {
+            Some(new) => new,
+            None => continue,
+        };
+
+        // Expressions with nested rsx are copied as they are, so format the nested blocks once they're in place
+        new = apply_formats(&new, fmt_file(&new));
 
         // if the new string is not multiline, don't try to adjust the marker ending
         // We want to trim off any indentation that there might be
@@ -122,8 +132,9 @@ pub fn write_block_out(body: CallBody) -> Option<String> {
     buf.consume()
 }
 
+/// Format the contents of an rsx! block, or return `None` if they aren't valid rsx
 pub fn fmt_block(block: &str, indent_level: usize) -> Option<String> {
-    let body = syn::parse_str::<dioxus_rsx::CallBody>(block).unwrap();
+    let body = syn::parse_str::<dioxus_rsx::CallBody>(block).ok()?;
 
     let mut buf = Writer {
         src: block.lines().map(|f| f.to_string()).collect(),
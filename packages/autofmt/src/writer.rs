@@ -1,11 +1,11 @@
-use dioxus_rsx::{BodyNode, ElementAttr, ElementAttrNamed, ForLoop};
+use dioxus_rsx::{BodyNode, ElementAttr, ElementAttrNamed, ForLoop, IfChain, MatchExpr};
 use proc_macro2::{LineColumn, Span};
 use quote::ToTokens;
 use std::{
     collections::{HashMap, VecDeque},
     fmt::{Result, Write},
 };
-use syn::{spanned::Spanned, Expr, ExprIf};
+use syn::{spanned::Spanned, Expr};
 
 use crate::buffer::Buffer;
 
@@ -41,6 +41,7 @@ impl Writer {
             BodyNode::RawExpr(exp) => self.write_raw_expr(exp.span()),
             BodyNode::ForLoop(forloop) => self.write_for_loop(forloop),
             BodyNode::IfChain(ifchain) => self.write_if_chain(ifchain),
+            BodyNode::Match(expr) => self.write_match(expr),
         }
     }
 
@@ -81,6 +82,28 @@ impl Writer {
         Ok(())
     }
 
+    /// Keep a `//` comment that follows a node on its line, since the line itself is rewritten
+    pub fn write_trailing_comment(&mut self, node_end: Span) {
+        if let Some(comment) = self.trailing_comment(node_end) {
+            self.out.trailing_comment = Some(comment.to_string());
+        }
+    }
+
+    pub fn trailing_comment(&self, node_end: Span) -> Option<&str> {
+        let end = node_end.end();
+        let line = self.src.get(end.line.checked_sub(1)?)?;
+        let (at, _) = line.char_indices().nth(end.column)?;
+
+        let rest = line[at..].trim_start();
+        let rest = rest.strip_prefix(',').unwrap_or(rest).trim();
+
+        if rest.starts_with("//") {
+            Some(rest)
+        } else {
+            None
+        }
+    }
+
     // Push out the indent level and write each component, line by line
     pub fn write_body_indented(&mut self, children: &[BodyNode]) -> Result {
         self.out.indent += 1;
@@ -114,6 +137,10 @@ impl Writer {
                     self.write_ident(child)?;
                 }
             }
+
+            if let Some(end) = node_end(child) {
+                self.write_trailing_comment(end);
+            }
         }
 
         Ok(())
@@ -133,7 +160,19 @@ impl Writer {
                 }
             }
 
+            if self.trailing_comment(attr_end(&attr.attr)).is_some() {
+                return 100000;
+            }
+
             total += match &attr.attr {
+                // multiline exprs that are copied from the source bump everything down
+                ElementAttr::AttrExpression { value, .. }
+                | ElementAttr::CustomAttrExpression { value, .. }
+                | ElementAttr::EventTokens { tokens: value, .. }
+                    if self.is_verbatim_expr(value.span()) && value.span().line_length() > 0 =>
+                {
+                    10000
+                }
                 ElementAttr::AttrText { value, name } => {
                     value.source.as_ref().unwrap().value().len() + name.span().line_length() + 3
                 }
@@ -146,6 +185,7 @@ impl Writer {
                 ElementAttr::CustomAttrExpression { name, value } => {
                     name.value().len() + value.span().line_length() + 3
                 }
+                ElementAttr::Spread { value, .. } => value.span().line_length() + 2,
                 ElementAttr::EventTokens { tokens, name } => {
                     let location = Location::new(tokens.span().start());
 
@@ -187,21 +227,115 @@ impl Writer {
             prettyplease::unparse_expr(&forloop.expr)
         )?;
 
-        if forloop.body.is_empty() {
+        self.write_block(&forloop.body)
+    }
+
+    fn write_if_chain(&mut self, ifchain: &IfChain) -> std::fmt::Result {
+        let mut chain = Some(ifchain);
+
+        while let Some(IfChain {
+            cond,
+            then_branch,
+            else_if_branch,
+            else_branch,
+            ..
+        }) = chain
+        {
+            write!(self.out, "if {} {{", prettyplease::unparse_expr(cond))?;
+            self.write_block(then_branch)?;
+
+            chain = else_if_branch.as_deref();
+            if chain.is_some() {
+                write!(self.out, " else ")?;
+            } else if let Some(else_branch) = else_branch {
+                write!(self.out, " else {{")?;
+                self.write_block(else_branch)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_match(&mut self, expr: &MatchExpr) -> std::fmt::Result {
+        write!(
+            self.out,
+            "match {} {{",
+            prettyplease::unparse_expr(&expr.expr)
+        )?;
+
+        self.out.indent += 1;
+        for arm in &expr.arms {
+            self.write_comments(arm.pat.span())?;
+            self.out.tabbed_line()?;
+            self.write_raw_expr(arm.pat.span())?;
+            if let Some((_, guard)) = &arm.guard {
+                write!(self.out, " if {}", prettyplease::unparse_expr(guard))?;
+            }
+
+            // A single expression or text stays on the line of its pattern
+            match arm.body.as_slice() {
+                [BodyNode::RawExpr(expr)] => {
+                    write!(self.out, " => ")?;
+                    self.write_raw_expr(expr.span())?;
+                    write!(self.out, ",")?;
+                    self.write_trailing_comment(expr.span());
+                }
+                [BodyNode::Text(text)] => {
+                    write!(self.out, " => ")?;
+                    self.out.write_text(text)?;
+                    write!(self.out, ",")?;
+                    self.write_trailing_comment(text.source.span());
+                }
+                body => {
+                    write!(self.out, " => {{")?;
+                    self.write_block(body)?;
+                }
+            }
+        }
+        self.out.indent -= 1;
+
+        self.out.tabbed_line()?;
+        write!(self.out, "}}")?;
+
+        Ok(())
+    }
+
+    /// Write the body of a block whose opening brace was already written, and close it
+    pub fn write_block(&mut self, body: &[BodyNode]) -> std::fmt::Result {
+        if body.is_empty() {
             write!(self.out, "}}")?;
             return Ok(());
         }
 
-        self.write_body_indented(&forloop.body)?;
+        self.write_body_indented(body)?;
 
         self.out.tabbed_line()?;
         write!(self.out, "}}")?;
 
         Ok(())
     }
+}
+
+/// Where a node ends, for the nodes that a comment can follow on their last line
+pub fn node_end(node: &BodyNode) -> Option<Span> {
+    match node {
+        BodyNode::Element(el) => Some(el.brace.span),
+        BodyNode::Component(component) => Some(component.brace.span),
+        BodyNode::Text(text) => Some(text.source.span()),
+        BodyNode::RawExpr(exp) => Some(exp.span()),
+        BodyNode::ForLoop(_) | BodyNode::IfChain(_) | BodyNode::Match(_) => None,
+    }
+}
 
-    fn write_if_chain(&mut self, ifchain: &ExprIf) -> std::fmt::Result {
-        self.write_raw_expr(ifchain.span())
+pub fn attr_end(attr: &ElementAttr) -> Span {
+    match attr {
+        ElementAttr::AttrText { value, .. } | ElementAttr::CustomAttrText { value, .. } => {
+            value.source.span()
+        }
+        ElementAttr::AttrExpression { value, .. }
+        | ElementAttr::CustomAttrExpression { value, .. }
+        | ElementAttr::Spread { value, .. } => value.span(),
+        ElementAttr::EventTokens { tokens, .. } => tokens.span(),
     }
 }
 
@@ -35,5 +35,9 @@ twoway![
     emoji,
     messy_indent,
     long_exprs,
-    ifchain_forloop
+    ifchain_forloop,
+    ifchain_match,
+    slots,
+    trailing_comments,
+    nested_rsx
 ];
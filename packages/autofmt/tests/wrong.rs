@@ -16,3 +16,5 @@ twoway!("comments" => comments);
 twoway!("multi" => multi);
 
 twoway!("multiexpr" => multiexpr);
+
+twoway!("nested" => nested);
@@ -163,7 +163,7 @@ mod util {
 }
 
 mod field_info {
-    use crate::props::type_from_inside_option;
+    use crate::props::{type_from_inside_option, type_is_element};
     use proc_macro2::TokenStream;
     use quote::quote;
     use syn::parse::Error;
@@ -197,6 +197,12 @@ mod field_info {
                         Some(syn::parse(quote!(Default::default()).into()).unwrap());
                 }
 
+                // other Element fields are named slots, which are left empty unless they're passed
+                if builder_attr.default.is_none() && type_is_element(&field.ty) {
+                    builder_attr.default =
+                        Some(syn::parse(quote!(Default::default()).into()).unwrap());
+                }
+
                 // auto detect optional
                 let strip_option_auto = builder_attr.strip_option
                     || !builder_attr.ignore_option
@@ -457,6 +463,18 @@ fn type_from_inside_option(ty: &syn::Type, check_option_name: bool) -> Option<&s
     }
 }
 
+/// Whether the type is an `Element<'a>`, which is how components take rsx from their parent
+fn type_is_element(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => type_path
+            .path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == "Element"),
+        _ => false,
+    }
+}
+
 mod struct_info {
     use proc_macro2::TokenStream;
     use quote::quote;
@@ -528,10 +546,20 @@ mod struct_info {
             } = *self;
 
             // we're generating stuff that goes into unsafe code here
-            // we use the heuristic: are there *any* generic parameters?
-            // If so, then they might have non-static lifetimes and we can't compare two generic things that *might borrow*
+            // we use the heuristic: are there any lifetime parameters?
+            // If so, then the props borrow from the parent and we can't compare two things that *might borrow*
             // Therefore, we will generate code that shortcircuits the "comparison" in memoization
-            let are_there_generics = !self.generics.params.is_empty();
+            // Type parameters could be filled with types that borrow or can't be compared, so props that are generic over
+            // types are only memoized when they opt in with `#[props(memoize)]`, which requires them to be PartialEq and 'static
+            let borrows = self.generics.lifetimes().next().is_some();
+            let are_there_type_generics = !borrows && !self.generics.params.is_empty();
+            if borrows && self.builder_attr.memoize {
+                return Err(Error::new_spanned(
+                    name,
+                    "Props that borrow from the parent can't be memoized",
+                ));
+            }
+            let memoize = !borrows && (!are_there_type_generics || self.builder_attr.memoize);
 
             let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
             let all_fields_param = syn::GenericParam::Type(
@@ -614,16 +642,31 @@ Finally, call `.build()` to create the instance of `{name}`.
                     .extend(predicates.predicates.clone());
             }
 
-            let can_memoize = match are_there_generics {
-                true => quote! { false  },
-                false => quote! { self == other },
+            let fields_name = syn::Ident::new(
+                &format!("{}_Fields", builder_name),
+                proc_macro2::Span::call_site(),
+            );
+            let field_names = self.included_fields().map(|f| f.name);
+
+            let can_memoize = match memoize {
+                false => quote! { false  },
+                true => quote! { self == other },
             };
 
-            let is_static = match are_there_generics {
-                true => quote! { false  },
-                false => quote! { true },
+            let is_static = match memoize {
+                false => quote! { false  },
+                true => quote! { true },
             };
 
+            let mut props_where = where_clause
+                .cloned()
+                .unwrap_or_else(|| syn::parse_quote!(where));
+            if are_there_type_generics && memoize {
+                props_where
+                    .predicates
+                    .push(syn::parse_quote!(Self: PartialEq + 'static));
+            }
+
             Ok(quote! {
                 impl #impl_generics #name #ty_generics #where_clause {
                     #[doc = #builder_method_doc]
@@ -653,8 +696,30 @@ Finally, call `.build()` to create the instance of `{name}`.
                     }
                 }
 
+                #[doc(hidden)]
+                #[allow(dead_code, non_camel_case_types, non_snake_case)]
+                #vis struct #fields_name;
+
+                #[doc(hidden)]
+                #[allow(dead_code, non_camel_case_types, missing_docs)]
+                impl #fields_name {
+                    #(
+                        pub fn #field_names(&self) {}
+                    )*
+                }
+
+                #[allow(dead_code, non_camel_case_types, missing_docs)]
+                impl #b_generics_impl #builder_name #b_generics_ty #b_generics_where_extras_predicates {
+                    // rsx! calls the marker of every prop it's given with the span of that prop, so a misspelled prop
+                    // is reported where it's written along with the props that are close to it
+                    #[doc(hidden)]
+                    pub fn __dioxus_fields(&self) -> #fields_name {
+                        #fields_name
+                    }
+                }
+
                 impl #impl_generics dioxus::prelude::Properties for #name #ty_generics
-                #b_generics_where_extras_predicates
+                #props_where
                 {
                     type Builder = #builder_name #generics_with_empty;
                     const IS_STATIC: bool = #is_static;
@@ -664,6 +729,12 @@ Finally, call `.build()` to create the instance of `{name}`.
                     unsafe fn memoize(&self, other: &Self) -> bool {
                         #can_memoize
                     }
+                    fn debug_props(&self) -> ::core::option::Option<::std::string::String> {
+                        // only one of them is picked, depending on whether the props are Debug
+                        #[allow(unused_imports)]
+                        use dioxus::core::{PropsViaDebug, PropsWithoutDebug};
+                        (&&dioxus::core::DebugProps(self)).debug_props()
+                    }
                 }
 
             })
@@ -793,13 +864,24 @@ Finally, call `.build()` to create the instance of `{name}`.
                     quote!(impl core::convert::Into<#arg_type>),
                     quote!(#field_name.into()),
                 )
+            } else if field.builder_attr.strip_option {
+                // an optional prop can also be given an Option, so it can be left out conditionally
+                (
+                    quote!(impl dioxus::prelude::IntoOptionalProp<#arg_type>),
+                    quote!(#field_name),
+                )
             } else {
                 (quote!(#arg_type), quote!(#field_name))
             };
-            let arg_expr = if field.builder_attr.strip_option {
-                quote!(Some(#arg_expr))
-            } else {
-                arg_expr
+            let arg_expr = match (
+                field.builder_attr.strip_option,
+                field.builder_attr.auto_into,
+            ) {
+                (true, true) => quote!(Some(#arg_expr)),
+                (true, false) => {
+                    quote!(dioxus::prelude::IntoOptionalProp::into_optional_prop(#arg_expr))
+                }
+                (false, _) => arg_expr,
             };
 
             let repeated_fields_error_type_name = syn::Ident::new(
@@ -1076,6 +1158,10 @@ Finally, call `.build()` to create the instance of `{name}`.
         /// specify `doc` instead and a default value will be filled in here.
         pub build_method_doc: Option<syn::Expr>,
 
+        /// Whether props that are generic over types are compared to skip rendering the component again, which
+        /// requires them to be `PartialEq` and `'static`.
+        pub memoize: bool,
+
         pub field_defaults: FieldBuilderAttr,
     }
 
@@ -1083,7 +1169,10 @@ Finally, call `.build()` to create the instance of `{name}`.
         pub fn new(attrs: &[syn::Attribute]) -> Result<TypeBuilderAttr, Error> {
             let mut result = TypeBuilderAttr::default();
             for attr in attrs {
-                if path_to_single_string(&attr.path).as_deref() != Some("builder") {
+                if !matches!(
+                    path_to_single_string(&attr.path).as_deref(),
+                    Some("builder") | Some("props")
+                ) {
                     continue;
                 }
 
@@ -1144,6 +1233,10 @@ Finally, call `.build()` to create the instance of `{name}`.
                             self.doc = true;
                             Ok(())
                         }
+                        "memoize" => {
+                            self.memoize = true;
+                            Ok(())
+                        }
                         _ => Err(Error::new_spanned(
                             &path,
                             format!("Unknown parameter {:?}", name),
@@ -1,7 +1,7 @@
 use std::{marker::PhantomData, panic::AssertUnwindSafe};
 
 use crate::{
-    innerlude::Scoped,
+    innerlude::{CapturedPanic, Scoped},
     nodes::{ComponentReturn, RenderReturn},
     scopes::{Scope, ScopeState},
     Element,
@@ -16,11 +16,19 @@ pub(crate) unsafe trait AnyProps<'a> {
     fn props_ptr(&self) -> *const ();
     fn render(&'a self, bump: &'a ScopeState) -> RenderReturn<'a>;
     unsafe fn memoize(&self, other: &dyn AnyProps) -> bool;
+    fn debug(&self) -> Option<String>;
+    fn render_fn(&self) -> *const ();
+
+    /// # Safety
+    ///
+    /// The function must take the same props and return the same type as the one it replaces
+    unsafe fn set_render_fn(&mut self, render_fn: *const ());
 }
 
 pub(crate) struct VProps<'a, P, A, F: ComponentReturn<'a, A> = Element<'a>> {
     pub render_fn: fn(Scope<'a, P>) -> F,
     pub memo: unsafe fn(&P, &P) -> bool,
+    pub debug: fn(&P) -> Option<String>,
     pub props: P,
     _marker: PhantomData<A>,
 }
@@ -32,11 +40,13 @@ where
     pub(crate) fn new(
         render_fn: fn(Scope<'a, P>) -> F,
         memo: unsafe fn(&P, &P) -> bool,
+        debug: fn(&P) -> Option<String>,
         props: P,
     ) -> Self {
         Self {
             render_fn,
             memo,
+            debug,
             props,
             _marker: PhantomData,
         }
@@ -61,6 +71,18 @@ where
         (self.memo)(real_us, real_other)
     }
 
+    fn debug(&self) -> Option<String> {
+        (self.debug)(&self.props)
+    }
+
+    fn render_fn(&self) -> *const () {
+        self.render_fn as *const ()
+    }
+
+    unsafe fn set_render_fn(&mut self, render_fn: *const ()) {
+        self.render_fn = std::mem::transmute::<*const (), fn(Scope<'a, P>) -> F>(render_fn);
+    }
+
     fn render(&'a self, cx: &'a ScopeState) -> RenderReturn<'a> {
         let res = std::panic::catch_unwind(AssertUnwindSafe(move || {
             // Call the render function directly
@@ -74,7 +96,11 @@ where
 
         match res {
             Ok(e) => e,
-            Err(_) => RenderReturn::default(),
+            Err(payload) => {
+                // Let the nearest error boundary know that the component panicked
+                cx.throw(CapturedPanic::from_payload(payload, cx));
+                RenderReturn::default()
+            }
         }
     }
 }
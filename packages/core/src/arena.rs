@@ -1,8 +1,8 @@
-use std::ptr::NonNull;
+use std::{collections::BTreeSet, ptr::NonNull};
 
 use crate::{
-    nodes::RenderReturn, nodes::VNode, virtual_dom::VirtualDom, AttributeValue, DynamicNode,
-    ScopeId,
+    innerlude::ComponentStack, nodes::RenderReturn, nodes::VNode, virtual_dom::VirtualDom,
+    AttributeValue, DynamicNode, Mutation, ScopeId, Template, TemplateNode,
 };
 use bumpalo::boxed::Box as BumpBox;
 
@@ -10,6 +10,9 @@ use bumpalo::boxed::Box as BumpBox;
 ///
 /// `ElementId` is a `usize` that is unique across the entire VirtualDOM - but not unique across time. If a component is
 /// unmounted, then the `ElementId` will be reused for a new component.
+///
+/// To tell a reused id apart from the element it used to point to, pair it with its generation from
+/// [`VirtualDom::element_generation`]. The generation is bumped every time the id is reclaimed.
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub struct ElementId(pub usize);
@@ -20,19 +23,141 @@ pub(crate) struct ElementRef {
 
     // The actual template
     pub template: Option<NonNull<VNode<'static>>>,
+
+    // Whether the element is on its way out of the dom (IE running an exit animation)
+    pub leaving: bool,
+
+    // The scope that was rendering when the element was allocated
+    pub scope: Option<ScopeId>,
+}
+
+/// A keyed child that was removed, but stays mounted until its exit transition finishes
+pub(crate) struct Exit {
+    // The scope that stopped rendering the child
+    pub scope: ScopeId,
+
+    // The root elements of the child, which the renderer removes once the exit is done
+    pub roots: Vec<ElementId>,
+
+    // Every element that was parked instead of reclaimed when the child was removed, roots included
+    pub elements: Vec<ElementId>,
 }
 
-#[derive(Clone, Copy)]
+/// The location of an element inside of its template.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ElementPath {
+    /// A path into the template, starting with the index of the root
     Deep(&'static [u8]),
+
+    /// A path into the template that was computed at runtime, IE after the element was moved
+    Owned(Box<[u8]>),
+
+    /// The index of one of the template's roots
     Root(usize),
 }
 
+/// The differences between the elements a renderer believes are live and the elements in the VirtualDom's arena
+///
+/// Produced by [`VirtualDom::diff_against`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Elements that are live in the VirtualDom but that the renderer does not know about
+    pub missing: Vec<ElementId>,
+
+    /// Elements the renderer still holds but that the VirtualDom has already reclaimed
+    pub stale: Vec<ElementId>,
+}
+
+impl SyncReport {
+    /// Check if the renderer and the VirtualDom agree on every live element
+    pub fn is_synced(&self) -> bool {
+        self.missing.is_empty() && self.stale.is_empty()
+    }
+}
+
+/// The reason a subtree could not be reparented with [`VirtualDom::reparent_subtree`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReparentError {
+    /// The root of the subtree is not a live element
+    MissingElement(ElementId),
+
+    /// The root of the subtree does not live inside of a template, so it has no path to rewrite
+    Detached(ElementId),
+
+    /// The new prefix was empty. Every path in a template starts with the index of its root
+    EmptyPrefix,
+
+    /// The root of the subtree comes after the 256th root of its template, so its path can't be written as bytes
+    RootOutOfRange(ElementId),
+}
+
+impl std::fmt::Display for ReparentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReparentError::MissingElement(id) => write!(f, "{:?} is not a live element", id),
+            ReparentError::Detached(id) => write!(f, "{:?} is not part of a template", id),
+            ReparentError::EmptyPrefix => write!(f, "the new path prefix is empty"),
+            ReparentError::RootOutOfRange(id) => {
+                write!(f, "{:?} is a root past the 256th of its template", id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReparentError {}
+
+/// The index of a root that can't be the first byte of an [`ElementPath`], because the template has more than 256 roots
+///
+/// Returned by [`ElementPath::as_bytes`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RootOutOfRange(pub usize);
+
+impl std::fmt::Display for RootOutOfRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "root {} doesn't fit in the first byte of a path", self.0)
+    }
+}
+
+impl std::error::Error for RootOutOfRange {}
+
+/// A live element whose stored [`ElementPath`] does not resolve within its current template.
+///
+/// Produced by [`VirtualDom::audit_paths`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathAudit {
+    /// The element with the invalid path
+    pub id: ElementId,
+
+    /// The path stored for the element
+    pub path: ElementPath,
+
+    /// The name of the template the path was checked against
+    pub template: &'static str,
+}
+
+/// Where the last element in a slot of the arena was allocated
+///
+/// Only recorded after [`VirtualDom::track_element_origins`] is turned on. Origins outlive the element they describe,
+/// so they can explain an id that has already been reclaimed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ElementOrigin {
+    /// The scope that was rendering when the element was allocated
+    pub scope: Option<ScopeId>,
+
+    /// The name of the template the element belongs to, if any
+    pub template: Option<&'static str>,
+
+    /// The path of the element inside of its template
+    pub path: ElementPath,
+}
+
 impl ElementRef {
     pub(crate) fn none() -> Self {
         Self {
             template: None,
             path: ElementPath::Root(0),
+            leaving: false,
+            scope: None,
         }
     }
 }
@@ -47,14 +172,20 @@ impl VirtualDom {
     }
 
     pub(crate) fn next_null(&mut self) -> ElementId {
+        let scope = self.scope_stack.last().copied();
         let entry = self.elements.vacant_entry();
         let id = entry.key();
 
-        entry.insert(ElementRef::none());
-        ElementId(id)
+        entry.insert(ElementRef {
+            scope,
+            ..ElementRef::none()
+        });
+        self.record_origin(ElementId(id));
+        self.record_generation(ElementId(id))
     }
 
     fn next_reference(&mut self, template: &VNode, path: ElementPath) -> ElementId {
+        let scope = self.scope_stack.last().copied();
         let entry = self.elements.vacant_entry();
         let id = entry.key();
 
@@ -62,13 +193,70 @@ impl VirtualDom {
             // We know this is non-null because it comes from a reference
             template: Some(unsafe { NonNull::new_unchecked(template as *const _ as *mut _) }),
             path,
+            leaving: false,
+            scope,
         });
-        ElementId(id)
+        self.record_origin(ElementId(id));
+        self.record_generation(ElementId(id))
+    }
+
+    /// Remember where the element was allocated, if origins are being tracked
+    fn record_origin(&mut self, id: ElementId) {
+        let origins = match self.element_origins.as_mut() {
+            Some(origins) => origins,
+            None => return,
+        };
+
+        let el_ref = &self.elements[id.0];
+        let origin = ElementOrigin {
+            scope: el_ref.scope,
+            // safety: we maintain references of all vnodes in the element slab
+            template: el_ref
+                .template
+                .map(|node| unsafe { node.as_ref() }.template.get().name),
+            path: el_ref.path.clone(),
+        };
+
+        if origins.len() <= id.0 {
+            origins.resize(id.0 + 1, None);
+        }
+        origins[id.0] = Some(origin);
+    }
+
+    /// Make sure the slot for this id has a generation, and in debug builds, hand it to the renderer
+    fn record_generation(&mut self, id: ElementId) -> ElementId {
+        if self.element_generations.len() <= id.0 {
+            self.element_generations.resize(id.0 + 1, 0);
+        }
+
+        if cfg!(debug_assertions) {
+            let generation = self.element_generations[id.0];
+            self.mutations.generations.push((id, generation));
+        }
+
+        id
     }
 
     pub(crate) fn reclaim(&mut self, el: ElementId) {
-        self.try_reclaim(el)
-            .unwrap_or_else(|| panic!("cannot reclaim {:?}", el));
+        if self.park_element(el) || self.try_reclaim(el).is_some() {
+            return;
+        }
+
+        // The component that allocated the element might not be around anymore
+        let stack = self
+            .element_origin(el)
+            .and_then(|origin| origin.scope)
+            .and_then(|scope| self.scopes.get(scope.0))
+            .map(|scope| ComponentStack::debug_note(scope))
+            .unwrap_or_default();
+
+        match self.element_origin(el) {
+            Some(origin) => panic!(
+                "cannot reclaim {:?}, it was last allocated by {:?} for template {:?} at {:?}{}",
+                el, origin.scope, origin.template, origin.path, stack
+            ),
+            None => panic!("cannot reclaim {:?}{}", el, stack),
+        }
     }
 
     pub(crate) fn try_reclaim(&mut self, el: ElementId) -> Option<ElementRef> {
@@ -79,7 +267,76 @@ impl VirtualDom {
             );
         }
 
-        self.elements.try_remove(el.0)
+        if self.park_element(el) {
+            return None;
+        }
+
+        let removed = self.elements.try_remove(el.0);
+
+        // Any references to the old element are now stale
+        if removed.is_some() {
+            let generation = &mut self.element_generations[el.0];
+            *generation = generation.wrapping_add(1);
+            if !self.patched_live.is_empty() {
+                self.patched_live.retain(|(id, _)| *id != el);
+            }
+            if !self.fired_listeners.is_empty() {
+                self.fired_listeners.retain(|(id, _)| *id != el);
+            }
+        }
+
+        // Only the root is left, so the app has no content
+        if removed.is_some() && self.elements.len() == 1 {
+            if let Some(on_empty) = self.on_arena_empty.as_mut() {
+                on_empty();
+            }
+        }
+
+        removed
+    }
+
+    /// Keep an element of an exiting child alive instead of reclaiming it, so the renderer can still refer to it
+    fn park_element(&mut self, el: ElementId) -> bool {
+        let parked = match self.parked_elements.as_mut() {
+            Some(parked) => parked,
+            None => return false,
+        };
+
+        match self.elements.get_mut(el.0) {
+            Some(el_ref) => {
+                el_ref.leaving = true;
+                // The node the element belonged to is dropped along with the old frame
+                el_ref.template = None;
+                parked.push(el);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Take the elements of an exit out of the dom and reclaim them
+    pub(crate) fn remove_exit(&mut self, exit: Exit, gen_muts: bool) {
+        if gen_muts {
+            for &id in &exit.roots {
+                self.mutations.push(Mutation::Remove { id });
+            }
+        }
+
+        for id in exit.elements.into_iter().rev() {
+            self.try_reclaim(id);
+        }
+    }
+
+    /// Finish the exits of the children a scope removed, since nothing will report them once the scope is gone
+    pub(crate) fn remove_scope_exits(&mut self, scope: ScopeId, gen_muts: bool) {
+        let (finished, exits) = std::mem::take(&mut self.exits)
+            .into_iter()
+            .partition(|exit| exit.scope == scope);
+        self.exits = exits;
+
+        for exit in finished {
+            self.remove_exit(exit, gen_muts);
+        }
     }
 
     pub(crate) fn update_template(&mut self, el: ElementId, node: &VNode) {
@@ -102,6 +359,18 @@ impl VirtualDom {
             }
         }
 
+        let live = self.scopes[id.0].live.take();
+        self.sync_live(id, live);
+
+        self.run_drop_callbacks(id);
+        self.drop_scope_tasks(id);
+        self.remove_scope_exits(id, false);
+
+        // The children of a portal were reclaimed above, but the placeholder it left behind isn't part of any node
+        if let Some(placeholder) = self.portal(id).and_then(|portal| portal.placeholder.take()) {
+            self.try_reclaim(placeholder);
+        }
+
         self.scopes[id.0].props.take();
 
         let scope = &mut self.scopes[id.0];
@@ -111,6 +380,32 @@ impl VirtualDom {
         for hook in scope.hook_list.get_mut().drain(..) {
             drop(unsafe { BumpBox::from_raw(hook) });
         }
+        scope.hook_names.get_mut().clear();
+    }
+
+    /// Cancel the tasks spawned by a scope that's about to be torn down, since they can't outlive it
+    pub(crate) fn drop_scope_tasks(&self, id: ScopeId) {
+        let spawned = self.scopes[id.0].spawned_tasks.take();
+
+        let mut tasks = self.scheduler.tasks.borrow_mut();
+        let mut removed = Vec::new();
+        for task in spawned {
+            if matches!(tasks.get(task.0), Some(task) if task.scope == id) {
+                removed.push(tasks.remove(task.0));
+            }
+        }
+
+        // Drop the futures outside of the borrow in case they touch the scheduler
+        drop(tasks);
+        drop(removed);
+    }
+
+    /// Run the [`crate::ScopeState::push_on_drop`] callbacks of a scope that's about to be torn down
+    pub(crate) fn run_drop_callbacks(&self, id: ScopeId) {
+        let callbacks = self.scopes[id.0].on_drop.take();
+        for callback in callbacks {
+            callback();
+        }
     }
 
     fn drop_scope_inner(&mut self, node: &VNode) {
@@ -144,6 +439,303 @@ impl VirtualDom {
         }
     }
 
+    /// Get the generation of a live element
+    ///
+    /// Every time an [`ElementId`] is reclaimed its generation is bumped, so an id paired with the generation it had
+    /// when it was created can be checked against the arena to see if it still refers to the same element.
+    ///
+    /// Returns [`None`] if the element is not live.
+    pub fn element_generation(&self, el: ElementId) -> Option<u32> {
+        match self.elements.contains(el.0) {
+            true => Some(self.element_generations.get(el.0).copied().unwrap_or(0)),
+            false => None,
+        }
+    }
+
+    /// Get the number of live elements, not counting the root
+    pub fn live_element_count(&self) -> usize {
+        self.elements.len() - 1
+    }
+
+    /// Get the number of live scopes, including the root
+    pub fn live_scope_count(&self) -> usize {
+        self.scopes.len()
+    }
+
+    /// Count the live elements allocated while each scope was rendering, sorted by scope
+    ///
+    /// Scopes without any elements are left out. Elements that don't belong to any scope, like the root or anchors
+    /// reserved by the renderer, aren't counted.
+    pub fn elements_per_scope(&self) -> Vec<(ScopeId, usize)> {
+        let mut counts = std::collections::BTreeMap::new();
+
+        for (_, el_ref) in &self.elements {
+            if let Some(scope) = el_ref.scope {
+                *counts.entry(scope).or_insert(0) += 1;
+            }
+        }
+
+        counts.into_iter().collect()
+    }
+
+    /// Start or stop recording where each element is allocated
+    ///
+    /// Tracking costs an allocation per element, so it's off by default. Turning it off throws out every recorded
+    /// origin. While it's on, the panic for reclaiming an id twice includes where the id was last allocated.
+    pub fn track_element_origins(&mut self, track: bool) {
+        self.element_origins = match track {
+            true => Some(self.element_origins.take().unwrap_or_default()),
+            false => None,
+        };
+    }
+
+    /// Get where the last element in a slot was allocated, even if it's been reclaimed since
+    ///
+    /// Returns [`None`] unless [`VirtualDom::track_element_origins`] was on when the element was allocated.
+    pub fn element_origin(&self, el: ElementId) -> Option<&ElementOrigin> {
+        self.element_origins.as_ref()?.get(el.0)?.as_ref()
+    }
+
+    /// Check if an id still refers to the element it was created for, given the generation it was created with
+    pub fn is_element_current(&self, el: ElementId, generation: u32) -> bool {
+        self.element_generation(el) == Some(generation)
+    }
+
+    /// Mark an element as leaving the dom, or clear the mark
+    ///
+    /// Leaving elements are still live - their ids are not reclaimed - but they are no longer considered part of the
+    /// interactive tree. Events targeting a leaving element are ignored by [`VirtualDom::handle_event`], and events
+    /// passing through one skip its listeners. [`VirtualDom::element_ancestors`] and [`VirtualDom::element_siblings`]
+    /// leave it out. This is meant to coordinate exit animations, where the element stays on screen for a while after
+    /// it has been removed.
+    ///
+    /// The mark is dropped along with the element when it is reclaimed.
+    pub fn set_element_leaving(&mut self, el: ElementId, leaving: bool) {
+        if let Some(el_ref) = self.elements.get_mut(el.0) {
+            el_ref.leaving = leaving;
+        }
+    }
+
+    /// Check if an element has been marked as leaving with [`VirtualDom::set_element_leaving`]
+    ///
+    /// Returns false if the element does not exist.
+    pub fn element_is_leaving(&self, el: ElementId) -> bool {
+        self.elements
+            .get(el.0)
+            .map(|el_ref| el_ref.leaving)
+            .unwrap_or(false)
+    }
+
+    /// Get the ancestors of an element that have an id, starting with its parent
+    ///
+    /// Ancestors marked as leaving with [`VirtualDom::set_element_leaving`] are skipped, but the walk carries on past
+    /// them. Returns an empty list if the element does not exist.
+    pub fn element_ancestors(&self, el: ElementId) -> Vec<ElementId> {
+        let mut ancestors = vec![];
+        let mut current = self.elements.get(el.0);
+
+        while let Some(el_ref) = current {
+            let template = match el_ref.template {
+                Some(template) => template,
+                None => break,
+            };
+
+            ancestors.extend(
+                el_ref
+                    .path
+                    .ancestors()
+                    .skip(1)
+                    .filter_map(|path| self.element_at(template, &path)),
+            );
+
+            // safety: we maintain references of all vnodes in the element slab
+            let parent = unsafe { template.as_ref() }.parent;
+            ancestors.extend(parent);
+            current = parent.and_then(|id| self.elements.get(id.0));
+        }
+
+        ancestors.retain(|id| !self.element_is_leaving(*id));
+        ancestors
+    }
+
+    /// Get the elements with an id that share a parent with an element inside of its template
+    ///
+    /// The roots of a template are siblings of each other. Siblings marked as leaving with
+    /// [`VirtualDom::set_element_leaving`] are skipped. Returns an empty list if the element does not exist.
+    pub fn element_siblings(&self, el: ElementId) -> Vec<ElementId> {
+        let el_ref = match self.elements.get(el.0) {
+            Some(el_ref) => el_ref,
+            None => return vec![],
+        };
+        let parent = match (el_ref.template, parent_bytes(&el_ref.path)) {
+            (Some(_), Some(parent)) => parent,
+            _ => return vec![],
+        };
+
+        self.elements
+            .iter()
+            .filter(|(id, other)| {
+                *id != el.0
+                    && !other.leaving
+                    && other.template == el_ref.template
+                    && parent_bytes(&other.path) == Some(parent)
+            })
+            .map(|(id, _)| ElementId(id))
+            .collect()
+    }
+
+    /// Find the element with an id at a path inside of a template
+    fn element_at(
+        &self,
+        template: NonNull<VNode<'static>>,
+        path: &ElementPath,
+    ) -> Option<ElementId> {
+        let path = path.as_bytes().ok()?;
+
+        self.elements
+            .iter()
+            .find(|(_, other)| {
+                other.template == Some(template) && other.path.as_bytes().ok() == Some(path)
+            })
+            .map(|(id, _)| ElementId(id))
+    }
+
+    /// Report that the exit transition of an element finished, removing the keyed child it belongs to from the dom
+    ///
+    /// Only components that called [`crate::ScopeState::set_exit_transitions`] keep their removed children around.
+    /// A child with several root elements is removed along with all of them. The removal shows up in the next batch
+    /// of edits.
+    ///
+    /// Returns false if the element isn't exiting, IE because the transition of another root already finished it.
+    pub fn finish_exit(&mut self, el: ElementId) -> bool {
+        // The renderer only knows the shifted ids
+        let el = match el.0.checked_sub(self.root_element.0) {
+            Some(id) => ElementId(id),
+            None => return false,
+        };
+
+        match self.exits.iter().position(|exit| exit.roots.contains(&el)) {
+            Some(idx) => {
+                let exit = self.exits.remove(idx);
+                self.remove_exit(exit, true);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The root elements that are running an exit transition, for renderers that finish exits after a timeout
+    pub fn exiting_elements(&self) -> Vec<ElementId> {
+        self.exits
+            .iter()
+            .flat_map(|exit| &exit.roots)
+            .map(|id| ElementId(id.0 + self.root_element.0))
+            .collect()
+    }
+
+    /// Compare the elements a renderer believes are live against the elements the VirtualDom has allocated
+    ///
+    /// The root element is always live and is ignored on both sides. Both lists in the report are sorted by id.
+    pub fn diff_against(&self, renderer_ids: &[ElementId]) -> SyncReport {
+        let renderer_ids: BTreeSet<ElementId> = renderer_ids
+            .iter()
+            .copied()
+            .filter(|id| id.0 != 0)
+            .collect();
+
+        let missing = self
+            .elements
+            .iter()
+            .map(|(id, _)| ElementId(id))
+            .filter(|id| id.0 != 0 && !renderer_ids.contains(id))
+            .collect();
+
+        let stale = renderer_ids
+            .into_iter()
+            .filter(|id| !self.elements.contains(id.0))
+            .collect();
+
+        SyncReport { missing, stale }
+    }
+
+    /// Check that every live element's stored path still resolves within its current template.
+    ///
+    /// Returns a [`PathAudit`] for each element whose path points past the roots or children of its template. This can
+    /// happen if the template of an element is swapped out (IE through hot reloading) for one with a different layout.
+    ///
+    /// Elements without a template (the root and placeholders) are skipped.
+    pub fn audit_paths(&self) -> Vec<PathAudit> {
+        self.elements
+            .iter()
+            .filter_map(|(id, el_ref)| {
+                // safety: we maintain references of all vnodes in the element slab
+                let template = unsafe { el_ref.template?.as_ref() }.template.get();
+
+                match el_ref.path.resolves_in(&template) {
+                    true => None,
+                    false => Some(PathAudit {
+                        id: ElementId(id),
+                        path: el_ref.path.clone(),
+                        template: template.name,
+                    }),
+                }
+            })
+            .collect()
+    }
+
+    /// Rewrite the paths of an element and the elements below it in its template after it has been moved
+    ///
+    /// The stored path of `root` is replaced with `new_prefix`, and every element in the same template whose path
+    /// starts with the old path of `root` has that prefix swapped out for `new_prefix`. Elements in nested templates
+    /// are relative to their own template, so they are left alone.
+    ///
+    /// Since the new paths are not known at compile time, they are stored as [`ElementPath::Owned`].
+    pub fn reparent_subtree(
+        &mut self,
+        root: ElementId,
+        new_prefix: &[u8],
+    ) -> Result<(), ReparentError> {
+        if new_prefix.is_empty() {
+            return Err(ReparentError::EmptyPrefix);
+        }
+
+        let root_ref = self
+            .elements
+            .get(root.0)
+            .ok_or(ReparentError::MissingElement(root))?;
+
+        let template = root_ref.template.ok_or(ReparentError::Detached(root))?;
+        let old_prefix: Box<[u8]> = root_ref
+            .path
+            .as_bytes()
+            .map_err(|_| ReparentError::RootOutOfRange(root))?
+            .into();
+
+        if old_prefix.is_empty() {
+            return Err(ReparentError::Detached(root));
+        }
+
+        for (_, el_ref) in self.elements.iter_mut() {
+            if el_ref.template != Some(template) {
+                continue;
+            }
+            let path = match el_ref.path.as_bytes() {
+                Ok(path) if path.starts_with(&old_prefix) => path,
+                _ => continue,
+            };
+
+            let new_path = new_prefix
+                .iter()
+                .chain(&path[old_prefix.len()..])
+                .copied()
+                .collect();
+
+            el_ref.path = ElementPath::Owned(new_path);
+        }
+
+        Ok(())
+    }
+
     /// Descend through the tree, removing any borrowed props and listeners
     pub(crate) fn ensure_drop_safety(&self, scope_id: ScopeId) {
         let scope = &self.scopes[scope_id.0];
@@ -170,6 +762,8 @@ impl VirtualDom {
             match &listener.value {
                 AttributeValue::Listener(l) => {
                     _ = l.take();
+                    self.listener_count
+                        .set(self.listener_count.get().saturating_sub(1));
                 }
                 AttributeValue::Any(a) => {
                     _ = a.take();
@@ -180,20 +774,118 @@ impl VirtualDom {
     }
 }
 
+/// The path of the parent of an element inside of its template, which is empty for the roots
+///
+/// Returns [`None`] for placeholders, which are not part of the template.
+fn parent_bytes(path: &ElementPath) -> Option<&[u8]> {
+    match path {
+        ElementPath::Root(_) => Some(&[]),
+        path => path.as_bytes().ok()?.split_last().map(|(_, parent)| parent),
+    }
+}
+
+/// A byte for every possible root index, so root paths can be viewed as a slice without allocating
+static ROOT_BYTES: [u8; 256] = {
+    let mut bytes = [0; 256];
+    let mut idx = 0;
+    while idx < 256 {
+        bytes[idx] = idx as u8;
+        idx += 1;
+    }
+    bytes
+};
+
 impl ElementPath {
-    pub(crate) fn is_ascendant(&self, big: &&[u8]) -> bool {
+    /// Create a path from a list of child indices, IE one read back off the wire. This is the inverse of [`ElementPath::as_bytes`].
+    ///
+    /// The path is converted into its canonical form: a single index becomes [`ElementPath::Root`] and anything longer
+    /// is copied into an [`ElementPath::Owned`]. [`ElementPath::Deep`] can only be built from a `&'static` slice, so
+    /// paths known at compile time should be made with it directly to avoid the allocation.
+    pub fn from_bytes(bytes: &[u8]) -> ElementPath {
+        match bytes {
+            [] => ElementPath::Deep(&[]),
+            [root] => ElementPath::Root(*root as usize),
+            path => ElementPath::Owned(path.into()),
+        }
+    }
+
+    /// View this path as a list of child indices, starting with the index of the root
+    ///
+    /// Templates can have any number of roots, but only the first 256 can be written as a byte. The path of any later
+    /// root is an error.
+    pub fn as_bytes(&self) -> Result<&[u8], RootOutOfRange> {
         match *self {
-            ElementPath::Deep(small) => small.len() <= big.len() && small == &big[..small.len()],
-            ElementPath::Root(r) => big.len() == 1 && big[0] == r as u8,
+            ElementPath::Deep(path) => Ok(path),
+            ElementPath::Owned(ref path) => Ok(path),
+            ElementPath::Root(r) => ROOT_BYTES
+                .get(r)
+                .map(std::slice::from_ref)
+                .ok_or(RootOutOfRange(r)),
+        }
+    }
+
+    /// Get the path of the parent of this element within the template
+    ///
+    /// Returns [`None`] if this path points at a root of the template.
+    pub fn parent(&self) -> Option<ElementPath> {
+        match *self {
+            ElementPath::Deep(path) if path.len() > 1 => {
+                Some(ElementPath::Deep(&path[..path.len() - 1]))
+            }
+            ElementPath::Owned(ref path) if path.len() > 1 => {
+                Some(ElementPath::Owned(path[..path.len() - 1].into()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Iterate through this path and the paths of each of its ancestors within the template
+    ///
+    /// Like [`std::path::Path::ancestors`], the first item is this path itself. Each following item is one segment
+    /// shorter than the last, ending with the path of the root this element lives under.
+    pub fn ancestors(&self) -> impl Iterator<Item = ElementPath> {
+        std::iter::successors(Some(self.clone()), |path| path.parent())
+    }
+
+    /// Check if this path points at a node that exists in the given template
+    ///
+    /// An empty path is used for placeholders that are not part of the template and always resolves.
+    pub fn resolves_in(&self, template: &Template) -> bool {
+        self.as_bytes().map_or(false, |path| path.is_empty()) || self.node_in(template).is_some()
+    }
+
+    /// Find the node this path points at in the given template
+    pub(crate) fn node_in<'a>(&self, template: &Template<'a>) -> Option<&'a TemplateNode<'a>> {
+        if let ElementPath::Root(r) = *self {
+            return template.roots.get(r);
+        }
+
+        let (first, rest) = self.as_bytes().ok()?.split_first()?;
+        let mut node = template.roots.get(*first as usize)?;
+
+        for idx in rest {
+            node = match node {
+                TemplateNode::Element { children, .. } => children.get(*idx as usize)?,
+                _ => return None,
+            };
+        }
+
+        Some(node)
+    }
+
+    pub(crate) fn is_ascendant(&self, big: &&[u8]) -> bool {
+        match self {
+            ElementPath::Root(r) => big.len() == 1 && big[0] == *r as u8,
+            path => match path.as_bytes() {
+                Ok(small) => small.len() <= big.len() && small == &big[..small.len()],
+                Err(_) => false,
+            },
         }
     }
 }
 
 impl PartialEq<&[u8]> for ElementPath {
     fn eq(&self, other: &&[u8]) -> bool {
-        match *self {
-            ElementPath::Deep(deep) => deep.eq(*other),
-            ElementPath::Root(r) => other.len() == 1 && other[0] == r as u8,
-        }
+        self.as_bytes().map_or(false, |path| path == *other)
     }
 }
@@ -2,9 +2,29 @@ use crate::nodes::RenderReturn;
 use bumpalo::Bump;
 use std::cell::{Cell, UnsafeCell};
 
+/// A limit on how much memory each frame of a scope keeps around between renders
+///
+/// Bump arenas only ever grow, so a component that rendered a huge tree once would otherwise hold onto that memory for
+/// as long as it lives. With a budget set through [`VirtualDom::set_memory_budget`], a frame that is larger than
+/// `max_frame_bytes` but has only needed less than that for `frames` renders in a row is thrown out and reallocated
+/// at the size it actually needs.
+///
+/// [`VirtualDom::set_memory_budget`]: crate::VirtualDom::set_memory_budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryBudget {
+    /// The size above which a frame counts as oversized
+    pub max_frame_bytes: usize,
+
+    /// The number of renders in a row a frame has to stay oversized but underused before it's shrunk
+    pub frames: usize,
+}
+
 pub(crate) struct BumpFrame {
     pub bump: UnsafeCell<Bump>,
     pub node: Cell<*const RenderReturn<'static>>,
+
+    // The number of renders in a row this frame has been larger than the memory budget but used less than it
+    pub underused_renders: Cell<usize>,
 }
 
 impl BumpFrame {
@@ -13,6 +33,7 @@ impl BumpFrame {
         Self {
             bump: UnsafeCell::new(bump),
             node: Cell::new(std::ptr::null()),
+            underused_renders: Cell::new(0),
         }
     }
 
@@ -35,4 +56,31 @@ impl BumpFrame {
     pub(crate) unsafe fn bump_mut(&self) -> &mut Bump {
         unsafe { &mut *self.bump.get() }
     }
+
+    /// Reset the frame for the next render, shrinking it if it has been oversized for long enough
+    ///
+    /// # Safety
+    /// Nothing may reference the nodes in this frame anymore
+    pub(crate) unsafe fn recycle(&self, budget: Option<MemoryBudget>) {
+        let bump = self.bump_mut();
+
+        if let Some(budget) = budget {
+            let used: usize = bump.iter_allocated_chunks().map(|chunk| chunk.len()).sum();
+
+            if bump.allocated_bytes() > budget.max_frame_bytes && used <= budget.max_frame_bytes {
+                let underused = self.underused_renders.get() + 1;
+                self.underused_renders.set(underused);
+
+                if underused >= budget.frames {
+                    self.underused_renders.set(0);
+                    *bump = Bump::with_capacity(used);
+                    return;
+                }
+            } else {
+                self.underused_renders.set(0);
+            }
+        }
+
+        bump.reset();
+    }
 }
@@ -0,0 +1,54 @@
+use crate::{ScopeId, ScopeState};
+use std::fmt::{Display, Formatter};
+
+/// The components a scope was rendered under, from the scope itself up to the root of the VirtualDom
+///
+/// With the `component-stack` feature, panics while rendering or in event handlers, and the panics of the VirtualDom
+/// itself (like a broken rule of hooks), come with the stack of the component involved.
+///
+/// ```rust, ignore
+/// println!("{}", cx.component_stack());
+/// // component stack:
+/// //     in Row (ScopeId(4))
+/// //     in Table (ScopeId(2))
+/// //     in App (ScopeId(0))
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentStack {
+    /// The name and scope of every component, the innermost first
+    pub frames: Vec<(&'static str, ScopeId)>,
+}
+
+impl ComponentStack {
+    pub(crate) fn new(scope: &ScopeState) -> Self {
+        let mut frames = vec![(scope.name, scope.id)];
+
+        // safety: the pointer to a parent is always valid while the child is alive
+        let mut parent = scope.parent;
+        while let Some(scope) = parent.map(|p| unsafe { &*p }) {
+            frames.push((scope.name, scope.id));
+            parent = scope.parent;
+        }
+
+        Self { frames }
+    }
+
+    /// The stack to add to a panic message, if the `component-stack` feature is on
+    pub(crate) fn debug_note(scope: &ScopeState) -> String {
+        if cfg!(feature = "component-stack") {
+            format!("\n\n{}", Self::new(scope))
+        } else {
+            String::new()
+        }
+    }
+}
+
+impl Display for ComponentStack {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "component stack:")?;
+        for (name, scope) in &self.frames {
+            write!(f, "\n    in {} ({:?})", name, scope)?;
+        }
+        Ok(())
+    }
+}
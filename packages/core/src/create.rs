@@ -1,5 +1,5 @@
 use crate::any_props::AnyProps;
-use crate::innerlude::{BorrowedAttributeValue, VComponent, VPlaceholder, VText};
+use crate::innerlude::{BorrowedAttributeValue, ListenerOptions, VComponent, VPlaceholder, VText};
 use crate::mutations::Mutation;
 use crate::mutations::Mutation::*;
 use crate::nodes::VNode;
@@ -65,11 +65,11 @@ impl<'b> VirtualDom {
         // check for a overriden template
         #[cfg(debug_assertions)]
         {
-            let (path, byte_index) = node.template.get().name.rsplit_once(':').unwrap();
+            let (path, index) = node.template.get().name.rsplit_once(':').unwrap();
             if let Some(template) = self
                 .templates
                 .get(path)
-                .and_then(|map| map.get(&byte_index.parse().unwrap()))
+                .and_then(|map| map.get(&index.parse().unwrap()))
             {
                 node.template.set(*template);
             }
@@ -173,7 +173,7 @@ impl<'b> VirtualDom {
                 self.mutations.push(CreatePlaceholder { id });
                 1
             }
-            Text(VText { id, value }) => {
+            Text(VText { id, value, .. }) => {
                 let id = self.set_slot(template, id, idx);
                 self.create_static_text(value, id);
                 1
@@ -292,12 +292,23 @@ impl<'b> VirtualDom {
 
         match &attribute.value {
             AttributeValue::Listener(_) => {
+                // all listeners start with "on", and listeners with options listen for the same event as plain ones
+                let (name, options) = ListenerOptions::parse(unbounded_name)
+                    .unwrap_or((&unbounded_name[2..], ListenerOptions::default()));
+
                 self.mutations.push(NewEventListener {
-                    // all listeners start with "on"
-                    name: &unbounded_name[2..],
+                    name,
                     id,
+                    passive: options.passive,
                 })
             }
+            AttributeValue::Spread(spread) => {
+                for attribute in spread.iter() {
+                    self.write_attribute(attribute, id);
+                }
+            }
+            // A new element doesn't have the attribute yet, so there's nothing to remove
+            AttributeValue::None => {}
             _ => {
                 // Safety: we promise not to re-alias this text later on after committing it to the mutation
                 let value: BorrowedAttributeValue<'b> = (&attribute.value).into();
@@ -357,27 +368,17 @@ impl<'b> VirtualDom {
         id
     }
 
-    /// Insert a new template into the VirtualDom's template registry
-    pub(crate) fn register_template_first_byte_index(&mut self, mut template: Template<'static>) {
-        // First, make sure we mark the template as seen, regardless if we process it
-        let (path, _) = template.name.rsplit_once(':').unwrap();
-        if let Some((_, old_template)) = self
-            .templates
+    /// Replace a template in the VirtualDom's template registry with a hot reloaded version of it
+    pub(crate) fn register_hot_reloaded_template(&mut self, template: Template<'static>) {
+        let (path, index) = template
+            .location()
+            .expect("template names must follow the `file:line:column:index` scheme");
+
+        // The template is used in place of the compiled one from now on, even if it wasn't rendered yet
+        self.templates
             .entry(path)
             .or_default()
-            .iter_mut()
-            .min_by_key(|(byte_index, _)| **byte_index)
-        {
-            // the byte index of the hot reloaded template could be different
-            template.name = old_template.name;
-            *old_template = template;
-        } else {
-            // This is a template without any current instances
-            self.templates
-                .entry(path)
-                .or_default()
-                .insert(usize::MAX, template);
-        }
+            .insert(index, template);
 
         // If it's all dynamic nodes, then we don't need to register it
         if !template.is_completely_dynamic() {
@@ -386,32 +387,21 @@ impl<'b> VirtualDom {
     }
 
     /// Insert a new template into the VirtualDom's template registry
-    pub(crate) fn register_template(&mut self, mut template: Template<'static>) {
-        let (path, byte_index) = template.name.rsplit_once(':').unwrap();
-        let byte_index = byte_index.parse::<usize>().unwrap();
+    pub(crate) fn register_template(&mut self, template: Template<'static>) {
+        let (path, index) = template
+            .location()
+            .expect("template names must follow the `file:line:column:index` scheme");
         // First, check if we've already seen this template
         if self
             .templates
             .get(&path)
-            .filter(|set| set.contains_key(&byte_index))
+            .filter(|set| set.contains_key(&index))
             .is_none()
         {
-            // if hot reloading is enabled, then we need to check for a template that has overriten this one
-            #[cfg(debug_assertions)]
-            if let Some(mut new_template) = self
-                .templates
-                .get_mut(path)
-                .and_then(|map| map.remove(&usize::MAX))
-            {
-                // the byte index of the hot reloaded template could be different
-                new_template.name = template.name;
-                template = new_template;
-            }
-
             self.templates
                 .entry(path)
                 .or_default()
-                .insert(byte_index, template);
+                .insert(index, template);
 
             // If it's all dynamic nodes, then we don't need to register it
             if !template.is_completely_dynamic() {
@@ -525,12 +515,29 @@ impl<'b> VirtualDom {
         // Keep track of how many mutations are in the buffer in case we need to split them out if a suspense boundary
         // is encountered
         let mutations_to_this_point = self.mutations.edits.len();
+        let leaves_to_this_point = self.collected_leaves.len();
 
         // Create the component's root element
         let created = self.create_scope(scope, new);
 
+        // Portals mount their children under the target and leave a placeholder in the slot
+        if let Some(portal) = self.portal(scope) {
+            let target = portal.target.get();
+            self.mutations.push(AppendChildren {
+                id: target,
+                m: created,
+            });
+            portal.mounted_target.set(Some(target));
+
+            let id = self.next_element(parent, parent.template.get().node_paths[idx]);
+            portal.placeholder.set(Some(id));
+            self.mutations.push(CreatePlaceholder { id });
+
+            return 1;
+        }
+
         // If there are no suspense leaves below us, then just don't bother checking anything suspense related
-        if self.collected_leaves.is_empty() {
+        if self.collected_leaves.len() == leaves_to_this_point {
             return created;
         }
 
@@ -540,12 +547,6 @@ impl<'b> VirtualDom {
             _ => return created,
         };
 
-        // Since this is a boundary, use its placeholder within the template as the placeholder for the suspense tree
-        let new_id = self.next_element(new, parent.template.get().node_paths[idx]);
-
-        // Now connect everything to the boundary
-        self.scopes[scope.0].placeholder.set(Some(new_id));
-
         // This involves breaking off the mutations to this point, and then creating a new placeholder for the boundary
         // Note that we break off dynamic mutations only - since static mutations aren't rendered immediately
         let split_off = unsafe {
@@ -558,15 +559,24 @@ impl<'b> VirtualDom {
         boundary
             .waiting_on
             .borrow_mut()
-            .extend(self.collected_leaves.drain(..));
+            .extend(self.collected_leaves.drain(leaves_to_this_point..));
 
-        // Now assign the placeholder in the DOM
-        self.mutations.push(AssignId {
-            id: new_id,
-            path: &parent.template.get().node_paths[idx][1..],
-        });
+        let path = parent.template.get().node_paths[idx];
 
-        0
+        // If the boundary has a fallback, show it in the slot until the suspended tree resolves
+        // Like any other nodes of a component, they replace the placeholder in the template or stay on the stack
+        if boundary.fallback.borrow().is_some() {
+            return self.create_fallback(&boundary);
+        }
+
+        // Since this is a boundary, use its placeholder within the template as the placeholder for the suspense tree
+        let new_id = self.next_element(new, path);
+
+        // Now connect everything to the boundary
+        self.scopes[scope.0].placeholder.set(Some(new_id));
+        boundary.placeholder.set(Some(new_id));
+
+        self.assign_placeholder(new_id, path)
     }
 
     fn mount_aborted(&mut self, parent: &'b VNode<'b>, placeholder: &VPlaceholder) -> usize {
@@ -580,15 +590,27 @@ impl<'b> VirtualDom {
     ///
     /// IE simply assign an ID to the placeholder
     fn mount_async(&mut self, template: &VNode, idx: usize, scope: ScopeId) -> usize {
-        let new_id = self.next_element(template, template.template.get().node_paths[idx]);
+        let path = template.template.get().node_paths[idx];
+        let new_id = self.next_element(template, path);
 
         // Set the placeholder of the scope
         self.scopes[scope.0].placeholder.set(Some(new_id));
 
+        self.assign_placeholder(new_id, path)
+    }
+
+    /// Put a placeholder in the slot of a dynamic node, returning how many nodes that left on the stack
+    fn assign_placeholder(&mut self, id: ElementId, path: &'static [u8]) -> usize {
+        // The roots of a template are created on the stack rather than loaded with it, so there's nothing to assign
+        if path.len() == 1 {
+            self.mutations.push(CreatePlaceholder { id });
+            return 1;
+        }
+
         // Since the placeholder is already in the DOM, we don't create any new nodes
         self.mutations.push(AssignId {
-            id: new_id,
-            path: &template.template.get().node_paths[idx][1..],
+            id,
+            path: &path[1..],
         });
 
         0
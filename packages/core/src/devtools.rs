@@ -0,0 +1,155 @@
+//! What an inspector sees of a running VirtualDom, and the messages it trades with the renderer
+//!
+//! Renderers that support inspectors serve the protocol over a WebSocket as JSON, with the `serialize` feature. The
+//! inspector sends a [`DevtoolsRequest`] and the renderer answers every one with a [`DevtoolsResponse`].
+
+use crate::innerlude::*;
+
+/// A component that's mounted in the VirtualDom
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InspectedScope {
+    pub id: ScopeId,
+
+    /// The component that rendered this one, which is None for the root of the app
+    pub parent: Option<ScopeId>,
+
+    /// The name of the component function
+    pub name: String,
+
+    /// The props of the component formatted with Debug, if they implement it
+    pub props: Option<String>,
+
+    /// The types of the hooks of the component, in the order it calls them
+    pub hooks: Vec<String>,
+
+    /// How many times the component rendered
+    pub render_count: usize,
+
+    /// What made the component render the last time
+    pub last_render_reason: Option<RenderReason>,
+
+    /// The nodes the component rendered at its root, which an inspector highlights
+    pub elements: Vec<ElementId>,
+}
+
+/// A message from an inspector to the renderer of an app
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename_all = "snake_case")
+)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DevtoolsRequest {
+    /// Send every component that's mounted, in the order they are in the tree
+    Tree,
+
+    /// Outline the nodes of a component on the page, like when it's hovered in the inspector
+    Highlight { scope: ScopeId },
+
+    /// Take the outline away
+    ClearHighlight,
+}
+
+/// The answer of a renderer to a [`DevtoolsRequest`]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename_all = "snake_case")
+)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DevtoolsResponse {
+    Tree {
+        scopes: Vec<InspectedScope>,
+    },
+
+    /// The nodes that were outlined, which are none if the component isn't mounted anymore
+    Highlighted {
+        elements: Vec<ElementId>,
+    },
+
+    Cleared,
+}
+
+impl VirtualDom {
+    /// Every component that's mounted, with its parents before its children
+    pub fn inspect(&self) -> Vec<InspectedScope> {
+        let mut scopes = Vec::new();
+        let mut stack = vec![ScopeId(0)];
+        while let Some(id) = stack.pop() {
+            let scope = match self.get_scope(id) {
+                Some(scope) => scope,
+                None => continue,
+            };
+
+            let mut children = Vec::new();
+            if let RenderReturn::Ready(node) = scope.root_node() {
+                self.child_scopes(node, &mut children);
+            }
+            stack.extend(children.into_iter().rev());
+
+            scopes.push(InspectedScope {
+                id,
+                parent: scope.parent.map(|parent| unsafe { (*parent).id }),
+                name: scope.name.to_string(),
+                props: scope.props.as_ref().and_then(|props| props.debug()),
+                hooks: scope
+                    .hook_names
+                    .borrow()
+                    .iter()
+                    .map(|name| name.to_string())
+                    .collect(),
+                render_count: scope.render_cnt.get(),
+                last_render_reason: scope.last_render_reason(),
+                elements: self.scope_elements(id),
+            });
+        }
+        scopes
+    }
+
+    /// The nodes a component rendered at its root, including the ones of the components it rendered there
+    pub fn scope_elements(&self, id: ScopeId) -> Vec<ElementId> {
+        let mut elements = Vec::new();
+        if let Some(scope) = self.get_scope(id) {
+            if let RenderReturn::Ready(node) = scope.root_node() {
+                self.root_elements(node, &mut elements);
+            }
+        }
+        elements
+    }
+
+    // the components rendered anywhere inside of the node, but not inside of those components
+    fn child_scopes(&self, node: &VNode, scopes: &mut Vec<ScopeId>) {
+        for dynamic in node.dynamic_nodes {
+            match dynamic {
+                DynamicNode::Component(component) => scopes.extend(component.scope.get()),
+                DynamicNode::Fragment(nodes) => {
+                    for node in *nodes {
+                        self.child_scopes(node, scopes);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn root_elements(&self, node: &VNode, elements: &mut Vec<ElementId>) {
+        for idx in 0..node.template.get().roots.len() {
+            match node.dynamic_root(idx) {
+                None => elements.extend(node.root_ids.get(idx)),
+                Some(DynamicNode::Text(text)) => elements.extend(text.id.get()),
+                Some(DynamicNode::Placeholder(_)) => {}
+                Some(DynamicNode::Fragment(nodes)) => {
+                    for node in *nodes {
+                        self.root_elements(node, elements);
+                    }
+                }
+                Some(DynamicNode::Component(component)) => {
+                    if let Some(scope) = component.scope.get() {
+                        elements.extend(self.scope_elements(scope));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use crate::{
     any_props::AnyProps,
-    arena::ElementId,
-    innerlude::{BorrowedAttributeValue, DirtyScope, VComponent, VPlaceholder, VText},
+    arena::{ElementId, Exit},
+    innerlude::{unmark_dirty, BorrowedAttributeValue, VComponent, VPlaceholder, VText},
+    instrument::span,
     mutations::Mutation,
     nodes::RenderReturn,
     nodes::{DynamicNode, VNode},
@@ -15,9 +16,16 @@ use DynamicNode::*;
 
 impl<'b> VirtualDom {
     pub(super) fn diff_scope(&mut self, scope: ScopeId) {
+        // The children of a boundary that's still mounting aren't in the dom, so there's nothing to diff them against
+        if let Some(boundary) = self.mounting_boundary(scope) {
+            return self.remount_boundary(scope, &boundary);
+        }
+
         let scope_state = &mut self.scopes[scope.0];
+        let _span = span!("diff", scope = scope.0, component = scope_state.name);
 
         self.scope_stack.push(scope);
+        self.start_diff_stats();
         unsafe {
             // Load the old and new bump arenas
             let old = scope_state
@@ -42,30 +50,49 @@ impl<'b> VirtualDom {
                 // Just move over the placeholder
                 (Aborted(l), Aborted(r)) => r.id.set(l.id.get()),
 
-                // Becomes async, do nothing while we wait
+                // Becomes async, keep the old nodes mounted until the future resolves and gets diffed against them
                 (Ready(_nodes), Pending(_fut)) => self.diff_ok_to_async(_nodes, scope),
 
+                // The error placeholder stays mounted until the future resolves
+                (Aborted(_), Pending(_)) => {}
+
                 // Placeholder becomes something
                 // We should also clear the error now
                 (Aborted(l), Ready(r)) => self.replace_placeholder(l, [r]),
 
-                (Aborted(_), Pending(_)) => todo!("async should not resolve here"),
-                (Pending(_), Ready(_)) => todo!("async should not resolve here"),
-                (Pending(_), Aborted(_)) => todo!("async should not resolve here"),
-                (Pending(_), Pending(_)) => {
-                    // All suspense should resolve before we diff it again
-                    panic!("Should not roll from suspense to suspense.");
-                }
+                // A scope created suspended rendered again and didn't need to wait this time
+                (Pending(_), Ready(_) | Aborted(_)) => self.mount_suspended(scope, new),
+
+                // Rendering again restarted the future, so the placeholder waits for the new one
+                (Pending(_), Pending(_)) => {}
             };
         }
+
+        // A portal that was rendered with a new target takes its children along with it
+        if let Some(portal) = self.portal(scope) {
+            let target = portal.target.get();
+            if portal.mounted_target.get() != Some(target) {
+                if let RenderReturn::Ready(node) =
+                    unsafe { self.scopes[scope.0].root_node().extend_lifetime_ref() }
+                {
+                    let m = self.push_all_real_nodes(node);
+                    self.mutations
+                        .push(Mutation::AppendChildren { id: target, m });
+                }
+                portal.mounted_target.set(Some(target));
+            }
+        }
+
+        self.finish_diff_stats(scope);
         self.scope_stack.pop();
     }
 
-    fn diff_ok_to_async(&mut self, _new: &'b VNode<'b>, _scope: ScopeId) {
-        //
-    }
+    /// The nodes stay in the previous frame while the scope waits on its future
+    ///
+    /// Nothing is removed here. Once the future resolves, the scheduler diffs its output against these nodes.
+    fn diff_ok_to_async(&mut self, _old: &'b VNode<'b>, _scope: ScopeId) {}
 
-    fn diff_ok_to_err(&mut self, l: &'b VNode<'b>, p: &'b VPlaceholder) {
+    pub(crate) fn diff_ok_to_err(&mut self, l: &'b VNode<'b>, p: &'b VPlaceholder) {
         let id = self.next_null();
         p.id.set(Some(id));
         self.mutations.push(Mutation::CreatePlaceholder { id });
@@ -91,10 +118,10 @@ impl<'b> VirtualDom {
         // If hot reloading is enabled, we need to make sure we're using the latest template
         #[cfg(debug_assertions)]
         {
-            let (path, byte_index) = right_template.template.get().name.rsplit_once(':').unwrap();
+            let (path, index) = right_template.template.get().name.rsplit_once(':').unwrap();
             if let Some(map) = self.templates.get(path) {
-                let byte_index = byte_index.parse::<usize>().unwrap();
-                if let Some(&template) = map.get(&byte_index) {
+                let index = index.parse::<usize>().unwrap();
+                if let Some(&template) = map.get(&index) {
                     right_template.template.set(template);
                     if template != left_template.template.get() {
                         return self.replace(left_template, [right_template]);
@@ -119,19 +146,37 @@ impl<'b> VirtualDom {
             .dynamic_attrs
             .iter()
             .zip(right_template.dynamic_attrs.iter())
-            .for_each(|(left_attr, right_attr)| {
+            .enumerate()
+            .for_each(|(idx, (left_attr, right_attr))| {
                 // Move over the ID from the old to the new
                 right_attr
                     .mounted_element
                     .set(left_attr.mounted_element.get());
 
                 // We want to make sure anything listener that gets pulled is valid
-                if let AttributeValue::Listener(_) = right_attr.value {
+                if let AttributeValue::Listener(right_listener) = &right_attr.value {
                     self.update_template(left_attr.mounted_element.get(), right_template);
+
+                    // A listener that only runs once stays disarmed after it ran, even if it was rendered again
+                    let fired = (left_attr.mounted_element.get(), idx);
+                    if self.fired_listeners.contains(&fired) {
+                        right_listener.borrow_mut().take();
+                    }
+                }
+
+                // Spread attributes are diffed by name, since they can come and go
+                if let (AttributeValue::Spread(left), AttributeValue::Spread(right)) =
+                    (&left_attr.value, &right_attr.value)
+                {
+                    return self.diff_spread(left_attr.mounted_element.get(), left, right);
                 }
 
-                // If the attributes are different (or volatile), we need to update them
-                if left_attr.value != right_attr.value || left_attr.volatile {
+                // If the attributes are different (or volatile, or patched with a live value), we need to update them
+                let patched = left_attr.live.map_or(false, |live| {
+                    self.patched_live
+                        .remove(&(left_attr.mounted_element.get(), live))
+                });
+                if left_attr.value != right_attr.value || left_attr.volatile || patched {
                     self.update_attribute(right_attr, left_attr);
                 }
             });
@@ -180,6 +225,58 @@ impl<'b> VirtualDom {
         });
     }
 
+    fn diff_spread(
+        &mut self,
+        id: ElementId,
+        left: &'b [Attribute<'b>],
+        right: &'b [Attribute<'b>],
+    ) {
+        for right_attr in right {
+            right_attr.mounted_element.set(id);
+
+            let left_attr = left.iter().find(|left| {
+                left.name == right_attr.name && left.namespace == right_attr.namespace
+            });
+
+            let patched = left_attr
+                .and_then(|left_attr| left_attr.live)
+                .map_or(false, |live| self.patched_live.remove(&(id, live)));
+
+            match left_attr {
+                Some(left_attr)
+                    if left_attr.value == right_attr.value && !left_attr.volatile && !patched => {}
+                _ => {
+                    let name = unsafe { std::mem::transmute(right_attr.name) };
+                    let value: BorrowedAttributeValue<'b> = (&right_attr.value).into();
+                    let value = unsafe { std::mem::transmute(value) };
+                    self.mutations.push(Mutation::SetAttribute {
+                        id,
+                        ns: right_attr.namespace,
+                        name,
+                        value,
+                    });
+                }
+            }
+        }
+
+        // Attributes that aren't spread anymore are removed
+        for left_attr in left {
+            let removed = !right.iter().any(|right| {
+                left_attr.name == right.name && left_attr.namespace == right.namespace
+            });
+
+            if removed {
+                let name = unsafe { std::mem::transmute(left_attr.name) };
+                self.mutations.push(Mutation::SetAttribute {
+                    id,
+                    ns: left_attr.namespace,
+                    name,
+                    value: BorrowedAttributeValue::None,
+                });
+            }
+        }
+    }
+
     fn diff_vcomponent(
         &mut self,
         left: &'b VComponent<'b>,
@@ -192,7 +289,7 @@ impl<'b> VirtualDom {
         }
 
         // Replace components that have different render fns
-        if left.render_fn != right.render_fn {
+        if self.reloaded_component(left.render_fn) != self.reloaded_component(right.render_fn) {
             return self.replace_vcomponent(right_template, right, idx, left);
         }
 
@@ -220,10 +317,11 @@ impl<'b> VirtualDom {
         self.run_scope(scope_id);
         self.diff_scope(scope_id);
 
-        self.dirty_scopes.remove(&DirtyScope {
-            height: self.scopes[scope_id.0].height,
-            id: scope_id,
-        });
+        unmark_dirty(
+            &mut self.dirty_scopes,
+            self.scopes[scope_id.0].height,
+            scope_id,
+        );
     }
 
     fn replace_vcomponent(
@@ -308,7 +406,10 @@ impl<'b> VirtualDom {
             .unwrap_or_else(|| self.next_element(node, &[0]));
 
         right.id.set(Some(id));
-        if left.value != right.value {
+        let patched = left
+            .live
+            .map_or(false, |live| self.patched_live.remove(&(id, live)));
+        if left.value != right.value || patched {
             let value = unsafe { std::mem::transmute(right.value) };
             self.mutations.push(Mutation::SetText { id, value });
         }
@@ -422,7 +523,7 @@ impl<'b> VirtualDom {
 
         if new_middle.is_empty() {
             // remove the old elements
-            self.remove_nodes(old_middle);
+            self.remove_keyed_nodes(old_middle);
         } else if old_middle.is_empty() {
             // there were no old elements, so just create the new elements
             // we need to find the right "foothold" though - we shouldn't use the "append" at all
@@ -475,7 +576,7 @@ impl<'b> VirtualDom {
         // And if that was all of the new children, then remove all of the remaining
         // old children and we're finished.
         if left_offset == new.len() {
-            self.remove_nodes(&old[left_offset..]);
+            self.remove_keyed_nodes(&old[left_offset..]);
             return None;
         }
 
@@ -562,7 +663,11 @@ impl<'b> VirtualDom {
         // If none of the old keys are reused by the new children, then we remove all the remaining old children and
         // create the new children afresh.
         if shared_keys.is_empty() {
-            if old.get(0).is_some() {
+            if self.exit_transitions() {
+                // The old children stay in place while they exit, so the new children go in front of them
+                self.create_and_insert_before(new, &old[0]);
+                self.remove_keyed_nodes(old);
+            } else if old.get(0).is_some() {
                 self.remove_nodes(&old[1..]);
                 self.replace(&old[0], new);
             } else {
@@ -580,33 +685,44 @@ impl<'b> VirtualDom {
         for child in old {
             let key = child.key.unwrap();
             if !shared_keys.contains(&key) {
-                self.remove_node(child, true);
+                self.remove_keyed_node(child);
             }
         }
 
         // 4. Compute the LIS of this list
-        let mut lis_sequence = Vec::default();
-        lis_sequence.reserve(new_index_to_old_index.len());
+        // Only the shared children are considered - new children have to be created and inserted no matter what, so
+        // letting them into the LIS would just push out children that could have stayed in place
+        let shared_new_indices = new_index_to_old_index
+            .iter()
+            .enumerate()
+            .filter(|(_, old_index)| **old_index != u32::MAX as usize)
+            .map(|(new_index, _)| new_index)
+            .collect::<Vec<_>>();
+
+        let shared_old_indices = shared_new_indices
+            .iter()
+            .map(|new_index| new_index_to_old_index[*new_index])
+            .collect::<Vec<_>>();
 
-        let mut predecessors = vec![0; new_index_to_old_index.len()];
-        let mut starts = vec![0; new_index_to_old_index.len()];
+        let mut lis_sequence = Vec::with_capacity(shared_old_indices.len());
+        let mut predecessors = vec![0; shared_old_indices.len()];
+        let mut starts = vec![0; shared_old_indices.len()];
 
         longest_increasing_subsequence::lis_with(
-            &new_index_to_old_index,
+            &shared_old_indices,
             &mut lis_sequence,
             |a, b| a < b,
             &mut predecessors,
             &mut starts,
         );
 
-        // the lis comes out backwards, I think. can't quite tell.
+        // Map the LIS back into indices of the new children - it comes out backwards, so sort it too
+        let mut lis_sequence = lis_sequence
+            .into_iter()
+            .map(|shared_index| shared_new_indices[shared_index])
+            .collect::<Vec<_>>();
         lis_sequence.sort_unstable();
 
-        // if a new node gets u32 max and is at the end, then it might be part of our LIS (because u32 max is a valid LIS)
-        if lis_sequence.last().map(|f| new_index_to_old_index[*f]) == Some(u32::MAX as usize) {
-            lis_sequence.pop();
-        }
-
         for idx in &lis_sequence {
             self.diff_node(&old[new_index_to_old_index[*idx]], &new[*idx]);
         }
@@ -683,6 +799,89 @@ impl<'b> VirtualDom {
         }
     }
 
+    /// Check if the scope being diffed keeps the keyed children it removes mounted while they exit
+    fn exit_transitions(&self) -> bool {
+        self.scope_stack
+            .last()
+            .map_or(false, |scope| self.scopes[scope.0].exit_transitions.get())
+    }
+
+    fn remove_keyed_nodes(&mut self, nodes: &'b [VNode<'b>]) {
+        nodes
+            .iter()
+            .rev()
+            .for_each(|node| self.remove_keyed_node(node));
+    }
+
+    /// Remove a keyed child, or flag its root elements as exiting if its scope asked for exit transitions
+    ///
+    /// An exiting child is torn down like any other, but its elements are parked instead of reclaimed until the
+    /// renderer reports the exit finished.
+    fn remove_keyed_node(&mut self, node: &'b VNode<'b>) {
+        let scope = match self.scope_stack.last() {
+            Some(&scope) if self.exit_transitions() => scope,
+            _ => return self.remove_node(node, true),
+        };
+
+        // Text can't be animated, so children without an element at their root are removed right away
+        let mut roots = Vec::new();
+        self.exit_roots(node, &mut roots);
+        if roots.is_empty() {
+            return self.remove_node(node, true);
+        }
+
+        let pre_edits = self.mutations.edits.len();
+        self.parked_elements = Some(Vec::new());
+        self.remove_node(node, true);
+        let elements = self.parked_elements.take().unwrap_or_default();
+
+        for edit in &mut self.mutations.edits[pre_edits..] {
+            if let Mutation::Remove { id } = *edit {
+                if roots.contains(&id) {
+                    *edit = Mutation::SetAttribute {
+                        name: "data-exiting",
+                        value: BorrowedAttributeValue::Text("true"),
+                        id,
+                        ns: None,
+                    };
+                }
+            }
+        }
+
+        self.exits.push(Exit {
+            scope,
+            roots,
+            elements,
+        });
+    }
+
+    /// Collect the root elements of a node, which run the exit transition when it's removed
+    fn exit_roots(&self, node: &'b VNode<'b>, roots: &mut Vec<ElementId>) {
+        for (idx, root) in node.template.get().roots.iter().enumerate() {
+            match node.dynamic_root(idx) {
+                None => {
+                    if let TemplateNode::Element { .. } = root {
+                        roots.extend(node.root_ids.get(idx));
+                    }
+                }
+                Some(Fragment(nodes)) => nodes.iter().for_each(|node| self.exit_roots(node, roots)),
+                Some(Component(comp)) => {
+                    let scope = self.mounted_scope(comp.scope.get().unwrap());
+                    // The children of a portal live under its target, so they leave with the portal
+                    if self.portal(scope).is_some() || self.suspense_placeholder(scope).is_some() {
+                        continue;
+                    }
+                    if let RenderReturn::Ready(node) =
+                        unsafe { self.scopes[scope.0].root_node().extend_lifetime_ref() }
+                    {
+                        self.exit_roots(node, roots);
+                    }
+                }
+                Some(Text(_)) | Some(Placeholder(_)) => {}
+            }
+        }
+    }
+
     /// Push all the real nodes on the stack
     fn push_all_real_nodes(&mut self, node: &'b VNode<'b>) -> usize {
         node.template
@@ -717,10 +916,17 @@ impl<'b> VirtualDom {
                     Fragment(nodes) => nodes
                         .iter()
                         .map(|node| self.push_all_real_nodes(node))
-                        .count(),
+                        .sum(),
 
                     Component(comp) => {
-                        let scope = comp.scope.get().unwrap();
+                        let scope = self.mounted_scope(comp.scope.get().unwrap());
+                        if let Some(id) = self
+                            .portal_placeholder(scope)
+                            .or_else(|| self.suspense_placeholder(scope))
+                        {
+                            self.mutations.push(Mutation::PushRoot { id });
+                            return 1;
+                        }
                         match unsafe { self.scopes[scope.0].root_node().extend_lifetime_ref() } {
                             RenderReturn::Ready(node) => self.push_all_real_nodes(node),
                             RenderReturn::Aborted(_node) => todo!(),
@@ -729,7 +935,7 @@ impl<'b> VirtualDom {
                     }
                 }
             })
-            .count()
+            .sum()
     }
 
     fn create_children(&mut self, nodes: impl IntoIterator<Item = &'b VNode<'b>>) -> usize {
@@ -811,7 +1017,7 @@ impl<'b> VirtualDom {
             .for_each(|node| self.remove_node(node, true));
     }
 
-    fn remove_node(&mut self, node: &'b VNode<'b>, gen_muts: bool) {
+    pub(crate) fn remove_node(&mut self, node: &'b VNode<'b>, gen_muts: bool) {
         // Clean up any attributes that have claimed a static node as dynamic for mount/unmounta
         // Will not generate mutations!
         self.reclaim_attributes(node);
@@ -921,24 +1127,111 @@ impl<'b> VirtualDom {
             .take()
             .expect("VComponents to always have a scope");
 
+        let props = self.remove_scope(scope, gen_muts);
+
+        *comp.props.borrow_mut() = unsafe { std::mem::transmute(props) };
+    }
+
+    /// Remove a scope and its nodes, handing back the props it was rendered with
+    pub(crate) fn remove_scope(
+        &mut self,
+        scope: ScopeId,
+        gen_muts: bool,
+    ) -> Option<Box<dyn AnyProps<'static>>> {
         match unsafe { self.scopes[scope.0].root_node().extend_lifetime_ref() } {
-            RenderReturn::Ready(t) => self.remove_node(t, gen_muts),
+            RenderReturn::Ready(t) => match (self.portal(scope), self.mounting_boundary(scope)) {
+                // The children live under the target, so removing the parent won't take them out of the dom
+                (Some(portal), _) => {
+                    self.remove_node(t, true);
+                    if let Some(id) = portal.placeholder.take() {
+                        if gen_muts {
+                            self.mutations.push(Mutation::Remove { id });
+                        }
+                        self.reclaim(id);
+                    }
+                }
+                // The children never made it into the dom, only the fallback or the placeholder standing in for them
+                (None, Some(boundary)) => {
+                    self.remove_node(t, false);
+                    boundary.mutations.borrow_mut().edits.clear();
+                    boundary.resolved.borrow_mut().edits.clear();
+                    self.finished_fibers.retain(|id| *id != scope);
+
+                    match boundary.fallback_scope.take() {
+                        Some(fallback) => drop(self.remove_scope(fallback, gen_muts)),
+                        None => {
+                            let id = boundary.placeholder.take().unwrap();
+                            if gen_muts {
+                                self.mutations.push(Mutation::Remove { id });
+                            }
+                            self.reclaim(id);
+                        }
+                    }
+                }
+                (None, None) => self.remove_node(t, gen_muts),
+            },
             RenderReturn::Aborted(placeholder) => self.remove_placeholder(placeholder, gen_muts),
-            _ => todo!(),
+            RenderReturn::Pending(_) => self.remove_pending_scope(scope, gen_muts),
         };
 
-        let props = self.scopes[scope.0].props.take();
+        self.run_drop_callbacks(scope);
+        self.drop_scope_tasks(scope);
+        self.remove_scope_exits(scope, gen_muts);
+        self.scopes[scope.0].unsubscribe_from_contexts();
 
-        self.dirty_scopes.remove(&DirtyScope {
-            height: self.scopes[scope.0].height,
-            id: scope,
-        });
+        let props = self.scopes[scope.0].props.take();
 
-        *comp.props.borrow_mut() = unsafe { std::mem::transmute(props) };
+        unmark_dirty(&mut self.dirty_scopes, self.scopes[scope.0].height, scope);
 
         // make sure to wipe any of its props and listeners
         self.ensure_drop_safety(scope);
         self.scopes.remove(scope.0);
+
+        props
+    }
+
+    /// Put the nodes a scope resolved to in place of the placeholder it was created with while suspended
+    ///
+    /// An error keeps the placeholder around instead.
+    pub(crate) fn mount_suspended(&mut self, scope: ScopeId, new: &'b RenderReturn<'b>) {
+        let id = match self.scopes[scope.0].placeholder.take() {
+            Some(id) => id,
+            None => return,
+        };
+
+        match new {
+            RenderReturn::Ready(template) => {
+                self.scope_stack.push(scope);
+                let m = self.create(template);
+                self.scope_stack.pop();
+
+                self.mutations.push(Mutation::ReplaceWith { id, m });
+                self.reclaim(id);
+            }
+            RenderReturn::Aborted(placeholder) => placeholder.id.set(Some(id)),
+            RenderReturn::Pending(_) => self.scopes[scope.0].placeholder.set(Some(id)),
+        }
+    }
+
+    /// Remove a scope that's waiting on its future, along with whatever it left in the dom meanwhile
+    fn remove_pending_scope(&mut self, scope: ScopeId, gen_muts: bool) {
+        self.remove_suspense_leaf(scope);
+
+        // A scope that rendered before keeps its old nodes mounted while it waits, otherwise only its placeholder is
+        match unsafe { self.scopes[scope.0].previous_frame().try_load_node() } {
+            Some(RenderReturn::Ready(t)) => self.remove_node(t, gen_muts),
+            Some(RenderReturn::Aborted(placeholder)) => {
+                self.remove_placeholder(placeholder, gen_muts)
+            }
+            _ => {
+                if let Some(id) = self.scopes[scope.0].placeholder.take() {
+                    if gen_muts {
+                        self.mutations.push(Mutation::Remove { id });
+                    }
+                    self.reclaim(id);
+                }
+            }
+        }
     }
 
     fn find_first_element(&self, node: &'b VNode<'b>) -> ElementId {
@@ -948,7 +1241,13 @@ impl<'b> VirtualDom {
             Some(Fragment(t)) => self.find_first_element(&t[0]),
             Some(Placeholder(t)) => t.id.get().unwrap(),
             Some(Component(comp)) => {
-                let scope = comp.scope.get().unwrap();
+                let scope = self.mounted_scope(comp.scope.get().unwrap());
+                if let Some(id) = self
+                    .portal_placeholder(scope)
+                    .or_else(|| self.suspense_placeholder(scope))
+                {
+                    return id;
+                }
                 match unsafe { self.scopes[scope.0].root_node().extend_lifetime_ref() } {
                     RenderReturn::Ready(t) => self.find_first_element(t),
                     _ => todo!("cannot handle nonstandard nodes"),
@@ -964,7 +1263,13 @@ impl<'b> VirtualDom {
             Some(Fragment(t)) => self.find_last_element(t.last().unwrap()),
             Some(Placeholder(t)) => t.id.get().unwrap(),
             Some(Component(comp)) => {
-                let scope = comp.scope.get().unwrap();
+                let scope = self.mounted_scope(comp.scope.get().unwrap());
+                if let Some(id) = self
+                    .portal_placeholder(scope)
+                    .or_else(|| self.suspense_placeholder(scope))
+                {
+                    return id;
+                }
                 match unsafe { self.scopes[scope.0].root_node().extend_lifetime_ref() } {
                     RenderReturn::Ready(t) => self.find_last_element(t),
                     _ => todo!("cannot handle nonstandard nodes"),
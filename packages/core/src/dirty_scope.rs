@@ -1,9 +1,32 @@
+use std::collections::BTreeSet;
 use std::hash::Hash;
 
 use crate::ScopeId;
 
+/// How urgently a dirty scope should be rendered
+///
+/// Dirty scopes are rendered lane by lane, starting with [`Priority::UserInput`]. When rendering with a deadline, the
+/// lower lanes give way once the deadline passes and are picked up again on the next render.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Updates in response to the user - these are always rendered, even past a deadline
+    UserInput,
+
+    /// Updates that can lag a frame behind without being noticed, IE switching between views
+    Transition,
+
+    /// Background updates that should only be rendered when there's time to spare
+    Idle,
+}
+
+impl Priority {
+    pub(crate) const LANES: [Priority; 3] =
+        [Priority::UserInput, Priority::Transition, Priority::Idle];
+}
+
 #[derive(Debug, Clone, Eq, PartialOrd, Ord)]
 pub struct DirtyScope {
+    pub priority: Priority,
     pub height: u32,
     pub id: ScopeId,
 }
@@ -19,3 +42,14 @@ impl Hash for DirtyScope {
         self.id.hash(state);
     }
 }
+
+/// Remove a scope from the dirty set, whichever lane it's in
+pub(crate) fn unmark_dirty(dirty_scopes: &mut BTreeSet<DirtyScope>, height: u32, id: ScopeId) {
+    for priority in Priority::LANES {
+        dirty_scopes.remove(&DirtyScope {
+            priority,
+            height,
+            id,
+        });
+    }
+}
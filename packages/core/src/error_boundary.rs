@@ -1,13 +1,16 @@
-use crate::{ScopeId, ScopeState};
+use crate::{innerlude::*, ScopeId, ScopeState};
 use std::{
     any::{Any, TypeId},
     cell::RefCell,
     fmt::Debug,
+    rc::Rc,
+    sync::Arc,
 };
 
 /// A boundary that will capture any errors from child components
 pub struct ErrorBoundary {
     error: RefCell<Option<CapturedError>>,
+    rerender: Option<Arc<dyn Fn() + Send + Sync>>,
     _id: ScopeId,
 }
 
@@ -32,10 +35,46 @@ impl CapturedError {
     }
 }
 
+/// The error thrown to the nearest error boundary when a component panics while rendering
+#[derive(Debug)]
+pub struct CapturedPanic {
+    /// The message the component panicked with, if it was a string
+    pub message: Option<String>,
+
+    /// The components the panic happened under. This is only filled in with the `component-stack` feature.
+    pub component_stack: Option<ComponentStack>,
+}
+
+impl CapturedPanic {
+    pub(crate) fn from_payload(payload: Box<dyn Any + Send>, scope: &ScopeState) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => Some(*message),
+            Err(payload) => payload.downcast_ref::<&str>().map(|s| s.to_string()),
+        };
+
+        let component_stack = cfg!(feature = "component-stack").then(|| ComponentStack::new(scope));
+
+        Self {
+            message,
+            component_stack,
+        }
+    }
+}
+
 impl ErrorBoundary {
     pub fn new(id: ScopeId) -> Self {
         Self {
             error: RefCell::new(None),
+            rerender: None,
+            _id: id,
+        }
+    }
+
+    /// Create a new Error Boundary that calls `rerender` whenever an error is inserted or cleared
+    pub fn new_with_rerender(id: ScopeId, rerender: Arc<dyn Fn() + Send + Sync>) -> Self {
+        Self {
+            error: RefCell::new(None),
+            rerender: Some(rerender),
             _id: id,
         }
     }
@@ -43,6 +82,144 @@ impl ErrorBoundary {
     /// Push an error into this Error Boundary
     pub fn insert_error(&self, scope: ScopeId, error: Box<dyn Debug + 'static>) {
         self.error.replace(Some(CapturedError { error, scope }));
+
+        if let Some(rerender) = &self.rerender {
+            rerender();
+        }
+    }
+
+    /// Check if this is the boundary the VirtualDom provides at the root, which never renders the errors it captures
+    pub(crate) fn is_root_default(&self) -> bool {
+        self._id == ScopeId(0) && self.rerender.is_none()
+    }
+
+    /// Check if this Error Boundary has captured an error
+    pub fn has_error(&self) -> bool {
+        self.error.borrow().is_some()
+    }
+
+    /// Take the captured error out of this Error Boundary without rerendering it
+    pub fn take_error(&self) -> Option<CapturedError> {
+        self.error.take()
+    }
+
+    /// Clear the captured error and rerender the boundary, retrying its children
+    pub fn reset(&self) {
+        if self.error.take().is_some() {
+            if let Some(rerender) = &self.rerender {
+                rerender();
+            }
+        }
+    }
+}
+
+/// Catch errors thrown by any components below this one and render a fallback instead
+///
+/// ## Details
+///
+/// Errors thrown with [`ScopeState::throw`] or the [`Throw`] trait, as well as panics while rendering, are captured by
+/// the nearest boundary. While an error is captured, the boundary renders its fallback in place of its children. The
+/// fallback is given the error and the boundary itself so it can call [`ErrorBoundary::reset`] to retry.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// rsx!{
+///     ErrorBoundary {
+///         fallback: |err: &CapturedError, boundary: &Rc<ErrorBoundary>| {
+///             let boundary = boundary.clone();
+///             cx.render(rsx! {
+///                 "Something went wrong: {err.error:?}"
+///                 button { onclick: move |_| boundary.reset(), "Retry" }
+///             })
+///         },
+///         ThrowingChild {}
+///     }
+/// }
+/// ```
+///
+/// Without a fallback, the boundary renders nothing while an error is captured.
+#[allow(non_upper_case_globals, non_snake_case)]
+pub fn ErrorBoundary<'a>(cx: Scope<'a, ErrorBoundaryProps<'a>>) -> Element {
+    let boundary: &Rc<ErrorBoundary> = cx.use_hook(|| {
+        cx.provide_context(Rc::new(ErrorBoundary::new_with_rerender(
+            cx.scope_id(),
+            cx.schedule_update(),
+        )))
+    });
+
+    let error = boundary.error.borrow();
+    match error.as_ref() {
+        Some(error) => cx.props.fallback.as_ref()?(error, boundary),
+        None => {
+            let children = cx.props.children.as_ref()?;
+            Some(VNode {
+                key: children.key,
+                parent: children.parent,
+                template: children.template.clone(),
+                root_ids: children.root_ids.clone(),
+                dynamic_nodes: children.dynamic_nodes,
+                dynamic_attrs: children.dynamic_attrs,
+            })
+        }
+    }
+}
+
+type ErrorFallback<'a> = Box<dyn Fn(&CapturedError, &Rc<ErrorBoundary>) -> Element<'a> + 'a>;
+
+/// The props for the [`ErrorBoundary`] component
+pub struct ErrorBoundaryProps<'a> {
+    fallback: Option<ErrorFallback<'a>>,
+    children: Element<'a>,
+}
+
+/// The builder for [`ErrorBoundaryProps`]
+pub struct ErrorBoundaryBuilder<'a> {
+    fallback: Option<ErrorFallback<'a>>,
+    children: Element<'a>,
+}
+
+impl<'a> ErrorBoundaryBuilder<'a> {
+    /// Set the function that renders the captured error
+    pub fn fallback(
+        mut self,
+        fallback: impl Fn(&CapturedError, &Rc<ErrorBoundary>) -> Element<'a> + 'a,
+    ) -> Self {
+        self.fallback = Some(Box::new(fallback));
+        self
+    }
+
+    /// Set the children that might throw errors
+    pub fn children(mut self, children: Element<'a>) -> Self {
+        self.children = children;
+        self
+    }
+
+    /// Finish building the props
+    pub fn build(self) -> ErrorBoundaryProps<'a> {
+        ErrorBoundaryProps {
+            fallback: self.fallback,
+            children: self.children,
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn __dioxus_fields(&self) -> BuiltinFields {
+        BuiltinFields
+    }
+}
+
+impl<'a> Properties for ErrorBoundaryProps<'a> {
+    type Builder = ErrorBoundaryBuilder<'a>;
+    const IS_STATIC: bool = false;
+    fn builder() -> Self::Builder {
+        ErrorBoundaryBuilder {
+            fallback: None,
+            children: None,
+        }
+    }
+    unsafe fn memoize(&self, _other: &Self) -> bool {
+        false
     }
 }
 
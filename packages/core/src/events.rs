@@ -1,4 +1,6 @@
+use crate::{ElementId, VirtualDom};
 use std::{
+    any::Any,
     cell::{Cell, RefCell},
     rc::Rc,
 };
@@ -24,9 +26,25 @@ pub struct Event<T: 'static + ?Sized> {
     /// The data associated with this event
     pub data: Rc<T>,
     pub(crate) propagates: Rc<Cell<bool>>,
+    pub(crate) propagates_immediately: Rc<Cell<bool>>,
+    pub(crate) default_prevented: Rc<Cell<bool>>,
 }
 
 impl<T> Event<T> {
+    /// Make an event out of its data, for calling handlers outside of the VirtualDom like tests and replays do
+    ///
+    /// ```rust, ignore
+    /// cx.props.onclick.call(Event::new(MouseData::default()));
+    /// ```
+    pub fn new(data: T) -> Self {
+        Self {
+            data: Rc::new(data),
+            propagates: Rc::new(Cell::new(true)),
+            propagates_immediately: Rc::new(Cell::new(true)),
+            default_prevented: Rc::new(Cell::new(false)),
+        }
+    }
+
     /// Prevent this event from continuing to bubble up the tree to parent elements.
     ///
     /// # Example
@@ -62,6 +80,40 @@ impl<T> Event<T> {
         self.propagates.set(false);
     }
 
+    /// Prevent this event from reaching any other listeners, including the other listeners on the current element.
+    ///
+    /// # Example
+    ///
+    /// ```rust, ignore
+    /// rsx! {
+    ///     button {
+    ///         onclick_capture: move |evt: Event<MouseData>| {
+    ///             // The button's onclick listener won't run either
+    ///             evt.stop_immediate_propagation();
+    ///         },
+    ///         onclick: move |_| {}
+    ///     }
+    /// }
+    /// ```
+    pub fn stop_immediate_propagation(&self) {
+        self.propagates.set(false);
+        self.propagates_immediately.set(false);
+    }
+
+    /// Ask the renderer to skip the platform's default behavior for this event.
+    ///
+    /// [`crate::VirtualDom::handle_event`] reports if any listener called this, so renderers that dispatch events
+    /// synchronously can act on it. Renderers that receive events after the platform has already handled them can't
+    /// undo the default behavior - use the `prevent_default` attribute for those instead.
+    pub fn prevent_default(&self) {
+        self.default_prevented.set(true);
+    }
+
+    /// Check if any listener has called [`Event::prevent_default`] on this event so far
+    pub fn default_prevented(&self) -> bool {
+        self.default_prevented.get()
+    }
+
     /// Get a reference to the inner data from this event
     ///
     /// ```rust, ignore
@@ -85,6 +137,8 @@ impl<T: ?Sized> Clone for Event<T> {
     fn clone(&self) -> Self {
         Self {
             propagates: self.propagates.clone(),
+            propagates_immediately: self.propagates_immediately.clone(),
+            default_prevented: self.default_prevented.clone(),
             data: self.data.clone(),
         }
     }
@@ -101,11 +155,186 @@ impl<T: std::fmt::Debug> std::fmt::Debug for Event<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("UiEvent")
             .field("bubble_state", &self.propagates)
+            .field("default_prevented", &self.default_prevented)
             .field("data", &self.data)
             .finish()
     }
 }
 
+/// An event that didn't come from a renderer, like the ones test harnesses and macro recorders inject
+///
+/// The data is type-erased for [`VirtualDom::handle_event`] when the event is dispatched, so it has to be the type
+/// the listeners expect, IE `MouseData` for a click. Events bubble unless told otherwise.
+///
+/// ```rust, ignore
+/// SyntheticEvent::new("click", MouseData::default())
+///     .bubbles(true)
+///     .dispatch(&mut dom, ElementId(1));
+/// ```
+pub struct SyntheticEvent {
+    name: String,
+    data: Rc<dyn Any>,
+    bubbles: bool,
+}
+
+impl SyntheticEvent {
+    /// Make an event with the data its listeners take
+    pub fn new<T: 'static>(name: impl Into<String>, data: T) -> Self {
+        Self {
+            name: name.into(),
+            data: Rc::new(data),
+            bubbles: true,
+        }
+    }
+
+    /// Set whether the event bubbles up to the listeners of the parents of its target
+    pub fn bubbles(mut self, bubbles: bool) -> Self {
+        self.bubbles = bubbles;
+        self
+    }
+
+    /// Send the event to an element, returning true if a listener called [`Event::prevent_default`]
+    pub fn dispatch(&self, dom: &mut VirtualDom, element: ElementId) -> bool {
+        dom.handle_event(&self.name, self.data.clone(), element, self.bubbles)
+    }
+}
+
+/// When a renderer has to hand an event type to the VirtualDom
+///
+/// Renderers ask [`VirtualDom::event_dispatch`] before sending an event, and the app can change the answer for any
+/// event with [`VirtualDom::set_event_dispatch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventDispatch {
+    /// Handle the event as soon as it fires, after the deferred events waiting before it
+    ///
+    /// The web renderer handles it while the browser is still dispatching it, so a listener calling
+    /// [`Event::prevent_default`] can stop the default action (IE a submit reloading the page). Renderers whose page
+    /// talks to the VirtualDom over a channel send it right away.
+    Synchronous,
+
+    /// The event may wait for the next frame, and consecutive events of this type on the same element may be merged
+    /// into the last one. The default action of the event can't be prevented.
+    Deferred,
+}
+
+impl EventDispatch {
+    /// The events that fire many times a frame, which are deferred unless the app says otherwise
+    pub const DEFERRED_BY_DEFAULT: &'static [&'static str] = &[
+        "mousemove",
+        "pointermove",
+        "touchmove",
+        "drag",
+        "dragover",
+        "scroll",
+        "wheel",
+        "input",
+    ];
+
+    /// How an event is dispatched if the app hasn't set a policy for it
+    pub fn default_for(event: &str) -> Self {
+        if Self::DEFERRED_BY_DEFAULT.contains(&event) {
+            Self::Deferred
+        } else {
+            Self::Synchronous
+        }
+    }
+}
+
+/// How a listener is attached to its element
+///
+/// The options are part of the name of the listener attribute, after the event name: `onclick_capture`,
+/// `onclick_once` and `onscroll_capture_once_passive` are all listeners for their event. Use
+/// [`ListenerOptions::attribute_name`] to build the name of a listener with options.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ListenerOptions {
+    /// Run the listener while the event travels down to its target, before the bubbling listeners
+    pub capture: bool,
+
+    /// Only run the listener the first time the event reaches it. The listener stays disarmed across rerenders until
+    /// its element is removed.
+    pub once: bool,
+
+    /// Promise the renderer that the listener never calls [`Event::prevent_default`], so the platform can handle the
+    /// event (IE scrolling) without waiting on it
+    pub passive: bool,
+}
+
+impl ListenerOptions {
+    /// Set whether the listener runs in the capture phase
+    pub const fn capture(mut self, capture: bool) -> Self {
+        self.capture = capture;
+        self
+    }
+
+    /// Set whether the listener only runs once
+    pub const fn once(mut self, once: bool) -> Self {
+        self.once = once;
+        self
+    }
+
+    /// Set whether the listener is passive
+    pub const fn passive(mut self, passive: bool) -> Self {
+        self.passive = passive;
+        self
+    }
+
+    /// Split the name of a listener attribute (IE `onclick_once`) into the event it listens for and its options
+    ///
+    /// Returns `None` if the name isn't the name of a listener.
+    pub fn parse(name: &str) -> Option<(&str, Self)> {
+        let mut event = name.strip_prefix("on")?;
+        let mut options = Self::default();
+
+        if let Some(rest) = event.strip_suffix("_passive") {
+            options.passive = true;
+            event = rest;
+        }
+        if let Some(rest) = event.strip_suffix("_once") {
+            options.once = true;
+            event = rest;
+        }
+        if let Some(rest) = event.strip_suffix("_capture") {
+            options.capture = true;
+            event = rest;
+        }
+
+        Some((event, options))
+    }
+
+    /// Add these options to the name of a listener attribute (IE `onclick`), allocating the new name in the bump
+    /// arena of the scope if it changes
+    pub fn attribute_name<'a>(self, cx: &'a crate::ScopeState, name: &'a str) -> &'a str {
+        let (event, options) = match Self::parse(name) {
+            Some(parsed) => parsed,
+            None => return name,
+        };
+
+        let options = Self {
+            capture: self.capture || options.capture,
+            once: self.once || options.once,
+            passive: self.passive || options.passive,
+        };
+
+        let mut full = String::from("on");
+        full.push_str(event);
+        if options.capture {
+            full.push_str("_capture");
+        }
+        if options.once {
+            full.push_str("_once");
+        }
+        if options.passive {
+            full.push_str("_passive");
+        }
+
+        if full == name {
+            name
+        } else {
+            cx.bump().alloc_str(&full)
+        }
+    }
+}
+
 /// The callback type generated by the `rsx!` macro when an `on` field is specified for components.
 ///
 /// This makes it possible to pass `move |evt| {}` style closures into components as property fields.
@@ -49,6 +49,11 @@ impl<'a, const A: bool> FragmentBuilder<'a, A> {
     pub fn build(self) -> FragmentProps<'a> {
         FragmentProps(self.0)
     }
+
+    #[doc(hidden)]
+    pub fn __dioxus_fields(&self) -> BuiltinFields {
+        BuiltinFields
+    }
 }
 
 /// Access the children elements passed into the component
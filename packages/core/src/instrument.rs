@@ -0,0 +1,25 @@
+//! Spans around the work of the VirtualDom, for profiling apps with `tracing` subscribers like tracing-chrome or tracy
+//!
+//! The spans are only created with the `profiling` feature. Without it, [`span!`] expands to a guard that does nothing,
+//! and the fields of the span aren't evaluated at all.
+
+/// Enter a trace level span until the guard it returns is dropped, with the same arguments as `tracing::trace_span!`
+#[cfg(feature = "profiling")]
+macro_rules! span {
+    ($($args:tt)*) => {
+        tracing::trace_span!($($args)*).entered()
+    };
+}
+
+#[cfg(not(feature = "profiling"))]
+macro_rules! span {
+    ($($args:tt)*) => {
+        $crate::instrument::NoSpan
+    };
+}
+
+pub(crate) use span;
+
+/// What [`span!`] returns without the `profiling` feature
+#[cfg(not(feature = "profiling"))]
+pub(crate) struct NoSpan;
@@ -4,34 +4,54 @@
 mod any_props;
 mod arena;
 mod bump_frame;
+mod component_stack;
 mod create;
+mod devtools;
 mod diff;
 mod dirty_scope;
 mod error_boundary;
 mod events;
 mod fragment;
+mod instrument;
 mod lazynodes;
+mod live;
 mod mutations;
 mod nodes;
+mod portal;
 mod properties;
+mod recording;
+mod render_reason;
+mod render_stats;
 mod scheduler;
 mod scope_arena;
 mod scopes;
+mod semantics;
+mod suspense_boundary;
 mod virtual_dom;
 
 pub(crate) mod innerlude {
     pub use crate::arena::*;
+    pub use crate::bump_frame::MemoryBudget;
+    pub use crate::component_stack::*;
+    pub use crate::devtools::*;
     pub use crate::dirty_scope::*;
     pub use crate::error_boundary::*;
     pub use crate::events::*;
     pub use crate::fragment::*;
     pub use crate::lazynodes::*;
+    pub use crate::live::*;
     pub use crate::mutations::*;
     pub use crate::nodes::RenderReturn;
     pub use crate::nodes::*;
+    pub use crate::portal::*;
     pub use crate::properties::*;
+    pub use crate::recording::*;
+    pub use crate::render_reason::*;
+    pub use crate::render_stats::*;
     pub use crate::scheduler::*;
     pub use crate::scopes::*;
+    pub use crate::semantics::*;
+    pub use crate::suspense_boundary::*;
     pub use crate::virtual_dom::*;
 
     /// An [`Element`] is a possibly-none [`VNode`] created by calling `render` on [`Scope`] or [`ScopeState`].
@@ -70,10 +90,17 @@ pub(crate) mod innerlude {
 }
 
 pub use crate::innerlude::{
-    fc_to_builder, AnyValue, Attribute, AttributeValue, BorrowedAttributeValue, CapturedError,
-    Component, DynamicNode, Element, ElementId, Event, Fragment, IntoDynNode, LazyNodes, Mutation,
-    Mutations, Properties, RenderReturn, Scope, ScopeId, ScopeState, Scoped, SuspenseContext,
-    TaskId, Template, TemplateAttribute, TemplateNode, VComponent, VNode, VText, VirtualDom,
+    fc_to_builder, implicit_role, rendering_scope, AnyValue, Attribute, AttributeValue,
+    BorrowedAttributeValue, CapturedError, CapturedPanic, Component, ComponentStack, DebugProps,
+    DevtoolsRequest, DevtoolsResponse, DynamicNode, Element, ElementId, ElementOrigin, ElementPath,
+    ErrorBoundary, ErrorBoundaryProps, Event, EventDispatch, Fragment, HookReload, InspectedScope,
+    IntoAttribute, IntoDynNode, IntoOptionalProp, LazyNodes, ListenerOptions, Live, LiveId,
+    MemoryBudget, Mutation, Mutations, PathAudit, Portal, PortalProps, Priority, Properties,
+    PropsViaDebug, PropsWithoutDebug, RecordedEvent, RecordedStep, Recording, RenderReason,
+    RenderReturn, ReparentError, RootOutOfRange, Scope, ScopeId, ScopeState, ScopeStats, Scoped,
+    SemanticsUpdate, Spawner, SuspenseBoundary, SuspenseBoundaryProps, SuspenseContext, SyncReport,
+    SyntheticEvent, TaskHandle, TaskId, Template, TemplateAttribute, TemplateNode, VComponent,
+    VNode, VText, VirtualDom, WriteMutations,
 };
 
 /// The purpose of this module is to alleviate imports of many common types
@@ -81,9 +108,10 @@ pub use crate::innerlude::{
 /// This includes types like [`Scope`], [`Element`], and [`Component`].
 pub mod prelude {
     pub use crate::innerlude::{
-        fc_to_builder, AnyValue, Component, Element, Event, EventHandler, Fragment,
-        IntoAttributeValue, LazyNodes, Properties, Scope, ScopeId, ScopeState, Scoped, TaskId,
-        Template, TemplateAttribute, TemplateNode, Throw, VNode, VirtualDom,
+        fc_to_builder, AnyValue, Component, Element, ErrorBoundary, Event, EventHandler, Fragment,
+        IntoAttributeValue, IntoOptionalProp, LazyNodes, Portal, Properties, Scope, ScopeId,
+        ScopeState, Scoped, SuspenseBoundary, TaskHandle, TaskId, Template, TemplateAttribute,
+        TemplateNode, Throw, VNode, VirtualDom,
     };
 }
 
@@ -0,0 +1,228 @@
+//! Text and attributes that are patched in place when the state they display changes
+//!
+//! Reactive state like `dioxus-signals` renders through [`Live`] to update the text and attributes showing it without
+//! running or diffing the component that rendered them.
+
+use crate::innerlude::*;
+use bumpalo::Bump;
+use rustc_hash::FxHashSet;
+use std::{
+    cell::Cell,
+    fmt::Display,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+thread_local! {
+    // The scope whose component is running, so reactive state knows who read it
+    static RENDERING: Cell<Option<ScopeId>> = Cell::new(None);
+
+    // The live value that the attribute value converted last came from
+    static LIVE_ATTRIBUTE: Cell<Option<LiveId>> = Cell::new(None);
+}
+
+static NEXT_LIVE: AtomicUsize = AtomicUsize::new(0);
+
+pub(crate) fn set_rendering(scope: Option<ScopeId>) {
+    RENDERING.with(|rendering| rendering.set(scope));
+}
+
+/// The scope whose component is rendering on this thread right now, if any
+///
+/// Reactive state subscribes this scope when it's read, so the component renders again when the state changes.
+pub fn rendering_scope() -> Option<ScopeId> {
+    RENDERING.with(|rendering| rendering.get())
+}
+
+/// Convert an attribute value, along with the live value it came from if there is one
+pub(crate) fn into_live_value<'a>(
+    value: impl IntoAttributeValue<'a>,
+    bump: &'a Bump,
+) -> (AttributeValue<'a>, Option<LiveId>) {
+    LIVE_ATTRIBUTE.with(|live| live.set(None));
+    let value = value.into_value(bump);
+    (value, LIVE_ATTRIBUTE.with(|live| live.take()))
+}
+
+/// What identifies a piece of state displayed with [`Live`], to patch everything that displays it with
+/// [`ScopeState::schedule_live_update`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LiveId(usize);
+
+impl LiveId {
+    /// Create an id that's different from any other id created before
+    pub fn new() -> Self {
+        Self(NEXT_LIVE.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl Default for LiveId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A value rendered as text or as the value of an attribute, that's patched in place when it changes
+///
+/// ## Details
+///
+/// The text node or attribute remembers the [`LiveId`]. Sending a new value for the id through
+/// [`ScopeState::schedule_live_update`] patches every text node and attribute that displays it with a
+/// [`Mutation::SetText`] or [`Mutation::SetAttribute`], without running or diffing the components that rendered them.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// fn Counter(cx: Scope) -> Element {
+///     let id = *cx.use_hook(LiveId::new);
+///     let update = cx.use_hook(|| cx.schedule_live_update()).clone();
+///
+///     cx.render(rsx! {
+///         button {
+///             title: Live { id, value: 0 },
+///             onclick: move |_| update(id, "1".to_string()),
+///             Live { id, value: 0 }
+///         }
+///     })
+/// }
+/// ```
+pub struct Live<T> {
+    /// The state the value is displayed from
+    pub id: LiveId,
+
+    /// The value to display until the state changes
+    pub value: T,
+}
+
+impl<'a, T: Display> IntoDynNode<'a> for Live<T> {
+    fn into_vnode(self, cx: &'a ScopeState) -> DynamicNode<'a> {
+        cx.live.borrow_mut().insert(self.id);
+
+        DynamicNode::Text(VText {
+            value: cx.raw_text(format_args!("{}", self.value)),
+            id: Default::default(),
+            live: Some(self.id),
+        })
+    }
+}
+
+impl<'a, T: Display> IntoAttributeValue<'a> for Live<T> {
+    fn into_value(self, bump: &'a Bump) -> AttributeValue<'a> {
+        LIVE_ATTRIBUTE.with(|live| live.set(Some(self.id)));
+        AttributeValue::Text(bumpalo::format!(in bump, "{}", self.value).into_bump_str())
+    }
+}
+
+impl ScopeState {
+    /// Get a function that patches every text node and attribute displaying a [`Live`] value with a new value
+    ///
+    /// The function can be sent to other threads. Only the last value sent for an id before the VirtualDom renders is
+    /// displayed.
+    pub fn schedule_live_update(&self) -> Arc<dyn Fn(LiveId, String) + Send + Sync> {
+        let chan = self.tasks.sender.clone();
+        Arc::new(move |id, value| {
+            _ = chan.unbounded_send(SchedulerMsg::LiveUpdate(id, value));
+        })
+    }
+}
+
+impl VirtualDom {
+    /// Remember which live values a scope displays now that it rendered, forgetting the ones it displayed before
+    pub(crate) fn sync_live(&mut self, scope: ScopeId, before: FxHashSet<LiveId>) {
+        let now = match self.scopes.get(scope.0) {
+            Some(state) => state.live.borrow().clone(),
+            None => FxHashSet::default(),
+        };
+
+        for id in before.difference(&now) {
+            if let Some(scopes) = self.live_scopes.get_mut(id) {
+                scopes.remove(&scope);
+                if scopes.is_empty() {
+                    self.live_scopes.remove(id);
+                }
+            }
+        }
+
+        for id in now.difference(&before) {
+            self.live_scopes.entry(*id).or_default().insert(scope);
+        }
+    }
+
+    /// Patch the text nodes and attributes displaying the live values that changed
+    pub(crate) fn patch_live_values(&mut self) {
+        for (id, value) in std::mem::take(&mut self.live_updates) {
+            let scopes = match self.live_scopes.get(&id) {
+                Some(scopes) => scopes,
+                None => continue,
+            };
+
+            // The text stays around until the next render, after the renderer applied the edits pointing at it
+            let value = self.live_text.alloc_str(&value);
+            let value: &'static str = unsafe { std::mem::transmute(&*value) };
+
+            for scope in scopes {
+                if let Some(RenderReturn::Ready(node)) = self
+                    .scopes
+                    .get(scope.0)
+                    .and_then(|scope| scope.try_root_node())
+                {
+                    let node: &VNode<'static> = unsafe { std::mem::transmute(node) };
+                    find_live(
+                        node,
+                        id,
+                        value,
+                        &mut self.mutations.edits,
+                        &mut self.patched_live,
+                    );
+                }
+            }
+        }
+    }
+}
+
+// Set the text nodes and attributes of a node displaying a live value, not descending into other components
+fn find_live(
+    node: &VNode<'static>,
+    live: LiveId,
+    value: &'static str,
+    edits: &mut Vec<Mutation<'static>>,
+    patched: &mut FxHashSet<(ElementId, LiveId)>,
+) {
+    for attr in node.dynamic_attrs {
+        // Spread attributes are set on the element of the attribute they were spread through
+        let id = attr.mounted_element.get();
+        let spread = match attr.value {
+            AttributeValue::Spread(spread) => spread,
+            _ => std::slice::from_ref(attr),
+        };
+
+        for attr in spread.iter().filter(|attr| attr.live == Some(live)) {
+            patched.insert((id, live));
+            edits.push(Mutation::SetAttribute {
+                name: attr.name,
+                value: BorrowedAttributeValue::Text(value),
+                id,
+                ns: attr.namespace,
+            });
+        }
+    }
+
+    for dynamic in node.dynamic_nodes {
+        match dynamic {
+            DynamicNode::Text(text) if text.live == Some(live) => {
+                if let Some(id) = text.id.get() {
+                    patched.insert((id, live));
+                    edits.push(Mutation::SetText { value, id });
+                }
+            }
+            DynamicNode::Fragment(nodes) => {
+                for node in nodes.iter() {
+                    find_live(node, live, value, edits, patched);
+                }
+            }
+            _ => {}
+        }
+    }
+}
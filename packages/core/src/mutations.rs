@@ -1,6 +1,10 @@
 use rustc_hash::FxHashSet;
 
-use crate::{arena::ElementId, innerlude::BorrowedAttributeValue, ScopeId, Template};
+use crate::{
+    arena::ElementId,
+    innerlude::{BorrowedAttributeValue, SemanticsUpdate},
+    ScopeId, Template,
+};
 
 /// A container for all the relevant steps to modify the Real DOM
 ///
@@ -13,7 +17,11 @@ use crate::{arena::ElementId, innerlude::BorrowedAttributeValue, ScopeId, Templa
 /// Templates, however, apply to all subtrees, not just target subtree.
 ///
 /// Mutations are the only link between the RealDOM and the VirtualDOM.
-#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+///
+/// With the `serialize` feature, a whole set of mutations can be sent to a renderer in another process. Templates are
+/// identified only by their [`Template::name`], so the receiving side can cache them by name without ever seeing the
+/// original statics.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Default)]
 #[must_use = "not handling edits can lead to visual inconsistencies in UI"]
 pub struct Mutations<'a> {
@@ -29,7 +37,19 @@ pub struct Mutations<'a> {
     pub templates: Vec<Template<'a>>,
 
     /// Any mutations required to patch the renderer to match the layout of the VirtualDom
+    #[cfg_attr(feature = "serialize", serde(borrow))]
     pub edits: Vec<Mutation<'a>>,
+
+    /// The generation of every [`ElementId`] handed out while producing these mutations.
+    ///
+    /// Renderers can hold onto these to assert they never act on an id that has since been reclaimed and reused. This
+    /// is only filled in debug builds.
+    pub generations: Vec<(ElementId, u32)>,
+
+    /// How the accessibility information of the elements changed with these edits
+    ///
+    /// This is only filled in after [`crate::VirtualDom::track_semantics`] is turned on.
+    pub semantics: Vec<SemanticsUpdate>,
 }
 
 impl<'a> Mutations<'a> {
@@ -61,7 +81,7 @@ impl<'a> Mutations<'a> {
     derive(serde::Serialize, serde::Deserialize),
     serde(tag = "type")
 )]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Mutation<'a> {
     /// Add these m children to the target element
     AppendChildren {
@@ -221,6 +241,11 @@ pub enum Mutation<'a> {
 
         /// The ID of the node to attach the listener to.
         id: ElementId,
+
+        /// The listener promised to never prevent the default behavior of the event, so the renderer can let the
+        /// platform handle it without waiting. See [`crate::ListenerOptions`]
+        #[cfg_attr(feature = "serialize", serde(default))]
+        passive: bool,
     },
 
     /// Remove an existing Event Listener.
@@ -244,3 +269,217 @@ pub enum Mutation<'a> {
         id: ElementId,
     },
 }
+
+/// Something that can handle the edits produced by the VirtualDom in batches
+///
+/// This is the alternative to taking a [`Mutations`] out of the VirtualDom every frame: the diff still collects its
+/// edits into a buffer, but the buffer belongs to the VirtualDom and is emptied into the writer after every scope it
+/// diffs, so it keeps its allocation between frames instead of growing a new one each time. Each method corresponds to
+/// a variant of [`Mutation`] - see its docs for the meaning of each edit.
+///
+/// The text of the edits borrows the VirtualDom they come from for `'a`, like the edits of a [`Mutations`] do.
+///
+/// Pass an implementation to [`crate::VirtualDom::rebuild_to`] or [`crate::VirtualDom::render_immediate_to`].
+pub trait WriteMutations<'a> {
+    /// Register a template with the renderer. This is always called before any edits that load the template.
+    fn register_template(&mut self, template: Template<'static>);
+
+    /// Add these m children to the target element. See [`Mutation::AppendChildren`]
+    fn append_children(&mut self, id: ElementId, m: usize);
+
+    /// Assign the element at the given path the target ElementId. See [`Mutation::AssignId`]
+    fn assign_id(&mut self, path: &'static [u8], id: ElementId);
+
+    /// Create a placeholder in the DOM that we will use later. See [`Mutation::CreatePlaceholder`]
+    fn create_placeholder(&mut self, id: ElementId);
+
+    /// Create a node specifically for text with the given value. See [`Mutation::CreateTextNode`]
+    fn create_text_node(&mut self, value: &'a str, id: ElementId);
+
+    /// Hydrate an existing text node at the given path with the given text. See [`Mutation::HydrateText`]
+    fn hydrate_text(&mut self, path: &'static [u8], value: &'a str, id: ElementId);
+
+    /// Load and clone an existing node from a template. See [`Mutation::LoadTemplate`]
+    fn load_template(&mut self, name: &'static str, index: usize, id: ElementId);
+
+    /// Replace the target element with the topmost m nodes on the stack. See [`Mutation::ReplaceWith`]
+    fn replace_with(&mut self, id: ElementId, m: usize);
+
+    /// Replace the element at the given path with the m nodes on the stack. See [`Mutation::ReplacePlaceholder`]
+    fn replace_placeholder(&mut self, path: &'static [u8], m: usize);
+
+    /// Insert a number of nodes after a given node. See [`Mutation::InsertAfter`]
+    fn insert_after(&mut self, id: ElementId, m: usize);
+
+    /// Insert a number of nodes before a given node. See [`Mutation::InsertBefore`]
+    fn insert_before(&mut self, id: ElementId, m: usize);
+
+    /// Set the value of a node's attribute. See [`Mutation::SetAttribute`]
+    fn set_attribute(
+        &mut self,
+        name: &'a str,
+        ns: Option<&'a str>,
+        value: BorrowedAttributeValue<'a>,
+        id: ElementId,
+    );
+
+    /// Set the textcontent of a node. See [`Mutation::SetText`]
+    fn set_text(&mut self, value: &'a str, id: ElementId);
+
+    /// Create a new Event Listener. See [`Mutation::NewEventListener`]
+    fn new_event_listener(&mut self, name: &'a str, id: ElementId, passive: bool);
+
+    /// Remove an existing Event Listener. See [`Mutation::RemoveEventListener`]
+    fn remove_event_listener(&mut self, name: &'a str, id: ElementId);
+
+    /// Remove a particular node from the DOM. See [`Mutation::Remove`]
+    fn remove(&mut self, id: ElementId);
+
+    /// Push the given root node onto our stack. See [`Mutation::PushRoot`]
+    fn push_root(&mut self, id: ElementId);
+
+    /// Update the accessibility information of an element. This is called after the edits it describes.
+    ///
+    /// Only called after [`crate::VirtualDom::track_semantics`] is turned on.
+    fn update_semantics(&mut self, _update: SemanticsUpdate) {}
+}
+
+impl<'a> Mutation<'a> {
+    /// The element this mutation targets or creates, if it refers to one by id
+    pub(crate) fn id_mut(&mut self) -> Option<&mut ElementId> {
+        match self {
+            Mutation::AppendChildren { id, .. }
+            | Mutation::AssignId { id, .. }
+            | Mutation::CreatePlaceholder { id }
+            | Mutation::CreateTextNode { id, .. }
+            | Mutation::HydrateText { id, .. }
+            | Mutation::LoadTemplate { id, .. }
+            | Mutation::ReplaceWith { id, .. }
+            | Mutation::InsertAfter { id, .. }
+            | Mutation::InsertBefore { id, .. }
+            | Mutation::SetAttribute { id, .. }
+            | Mutation::SetText { id, .. }
+            | Mutation::NewEventListener { id, .. }
+            | Mutation::RemoveEventListener { id, .. }
+            | Mutation::Remove { id }
+            | Mutation::PushRoot { id } => Some(id),
+            Mutation::ReplacePlaceholder { .. } => None,
+        }
+    }
+
+    /// Hand this mutation off to the matching method of a [`WriteMutations`] implementation
+    pub fn write_to(self, to: &mut (impl WriteMutations<'a> + ?Sized)) {
+        match self {
+            Mutation::AppendChildren { id, m } => to.append_children(id, m),
+            Mutation::AssignId { path, id } => to.assign_id(path, id),
+            Mutation::CreatePlaceholder { id } => to.create_placeholder(id),
+            Mutation::CreateTextNode { value, id } => to.create_text_node(value, id),
+            Mutation::HydrateText { path, value, id } => to.hydrate_text(path, value, id),
+            Mutation::LoadTemplate { name, index, id } => to.load_template(name, index, id),
+            Mutation::ReplaceWith { id, m } => to.replace_with(id, m),
+            Mutation::ReplacePlaceholder { path, m } => to.replace_placeholder(path, m),
+            Mutation::InsertAfter { id, m } => to.insert_after(id, m),
+            Mutation::InsertBefore { id, m } => to.insert_before(id, m),
+            Mutation::SetAttribute {
+                name,
+                value,
+                id,
+                ns,
+            } => to.set_attribute(name, ns, value, id),
+            Mutation::SetText { value, id } => to.set_text(value, id),
+            Mutation::NewEventListener { name, id, passive } => {
+                to.new_event_listener(name, id, passive)
+            }
+            Mutation::RemoveEventListener { name, id } => to.remove_event_listener(name, id),
+            Mutation::Remove { id } => to.remove(id),
+            Mutation::PushRoot { id } => to.push_root(id),
+        }
+    }
+}
+
+/// Collect the edits back into a [`Mutations`], IE to compare the edits written out against the ones returned
+impl<'a> WriteMutations<'a> for Mutations<'a> {
+    fn register_template(&mut self, template: Template<'static>) {
+        self.templates.push(template);
+    }
+
+    fn append_children(&mut self, id: ElementId, m: usize) {
+        self.edits.push(Mutation::AppendChildren { id, m });
+    }
+
+    fn assign_id(&mut self, path: &'static [u8], id: ElementId) {
+        self.edits.push(Mutation::AssignId { path, id });
+    }
+
+    fn create_placeholder(&mut self, id: ElementId) {
+        self.edits.push(Mutation::CreatePlaceholder { id });
+    }
+
+    fn create_text_node(&mut self, value: &'a str, id: ElementId) {
+        self.edits.push(Mutation::CreateTextNode { value, id });
+    }
+
+    fn hydrate_text(&mut self, path: &'static [u8], value: &'a str, id: ElementId) {
+        self.edits.push(Mutation::HydrateText { path, value, id });
+    }
+
+    fn load_template(&mut self, name: &'static str, index: usize, id: ElementId) {
+        self.edits.push(Mutation::LoadTemplate { name, index, id });
+    }
+
+    fn replace_with(&mut self, id: ElementId, m: usize) {
+        self.edits.push(Mutation::ReplaceWith { id, m });
+    }
+
+    fn replace_placeholder(&mut self, path: &'static [u8], m: usize) {
+        self.edits.push(Mutation::ReplacePlaceholder { path, m });
+    }
+
+    fn insert_after(&mut self, id: ElementId, m: usize) {
+        self.edits.push(Mutation::InsertAfter { id, m });
+    }
+
+    fn insert_before(&mut self, id: ElementId, m: usize) {
+        self.edits.push(Mutation::InsertBefore { id, m });
+    }
+
+    fn set_attribute(
+        &mut self,
+        name: &'a str,
+        ns: Option<&'a str>,
+        value: BorrowedAttributeValue<'a>,
+        id: ElementId,
+    ) {
+        self.edits.push(Mutation::SetAttribute {
+            name,
+            value,
+            id,
+            ns,
+        });
+    }
+
+    fn set_text(&mut self, value: &'a str, id: ElementId) {
+        self.edits.push(Mutation::SetText { value, id });
+    }
+
+    fn new_event_listener(&mut self, name: &'a str, id: ElementId, passive: bool) {
+        self.edits
+            .push(Mutation::NewEventListener { name, id, passive });
+    }
+
+    fn remove_event_listener(&mut self, name: &'a str, id: ElementId) {
+        self.edits.push(Mutation::RemoveEventListener { name, id });
+    }
+
+    fn remove(&mut self, id: ElementId) {
+        self.edits.push(Mutation::Remove { id });
+    }
+
+    fn push_root(&mut self, id: ElementId) {
+        self.edits.push(Mutation::PushRoot { id });
+    }
+
+    fn update_semantics(&mut self, update: SemanticsUpdate) {
+        self.semantics.push(update);
+    }
+}
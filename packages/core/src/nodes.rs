@@ -1,5 +1,8 @@
 use crate::{
-    any_props::AnyProps, arena::ElementId, Element, Event, LazyNodes, ScopeId, ScopeState,
+    any_props::AnyProps,
+    arena::ElementId,
+    innerlude::{into_live_value, LiveId},
+    Element, Event, LazyNodes, ScopeId, ScopeState,
 };
 use bumpalo::boxed::Box as BumpBox;
 use bumpalo::Bump;
@@ -219,6 +222,12 @@ pub struct Template<'a> {
     /// The name of the template. This must be unique across your entire program for template diffing to work properly
     ///
     /// If two templates have the same name, it's likely that Dioxus will panic when diffing.
+    ///
+    /// Names take the form `file:line:column:index`, where the first three parts are the location of the `rsx!` call and
+    /// the last is the index of the template within that call. The body of the call is template 0, and the bodies of the
+    /// loops, conditionals and components nested in it follow in the order they're expanded. The name only depends on
+    /// the source, so it is the same in every process built from the same code and can be used to identify a template
+    /// across the wire.
     #[cfg_attr(
         feature = "serialize",
         serde(deserialize_with = "deserialize_string_leaky")
@@ -302,6 +311,14 @@ impl<'a> Template<'a> {
             .iter()
             .all(|root| matches!(root, Dynamic { .. } | DynamicText { .. }))
     }
+
+    /// Split the name of the template into the location of its `rsx!` call and its index within that call
+    ///
+    /// Returns `None` if the name doesn't follow the `file:line:column:index` scheme described on [`Template::name`].
+    pub fn location(&self) -> Option<(&'a str, usize)> {
+        let (path, index) = self.name.rsplit_once(':')?;
+        Some((path, index.parse().ok()?))
+    }
 }
 
 /// A statically known node in a layout.
@@ -439,6 +456,9 @@ pub struct VText<'a> {
 
     /// The ID of this node in the real DOM
     pub id: Cell<Option<ElementId>>,
+
+    /// The [`crate::Live`] value the text displays, if it's patched in place when the value changes
+    pub live: Option<LiveId>,
 }
 
 /// A placeholder node, used by suspense and fragments
@@ -500,6 +520,9 @@ pub struct Attribute<'a> {
 
     /// An indication of we should always try and set the attribute. Used in controlled components to ensure changes are propagated
     pub volatile: bool,
+
+    /// The [`crate::Live`] value the attribute displays, if it's patched in place when the value changes
+    pub live: Option<LiveId>,
 }
 
 /// Any of the built-in values that the Dioxus VirtualDom supports as dynamic attributes on elements
@@ -527,6 +550,12 @@ pub enum AttributeValue<'a> {
 
     /// A "none" value, resulting in the removal of an attribute from the dom
     None,
+
+    /// A group of attributes spread onto an element with `..attributes` in rsx, created with
+    /// [`ScopeState::spread`](crate::ScopeState::spread)
+    ///
+    /// Spread attributes can't be listeners.
+    Spread(&'a [Attribute<'a>]),
 }
 
 pub type ListenerCb<'a> = BumpBox<'a, dyn FnMut(Event<dyn Any>) + 'a>;
@@ -573,6 +602,9 @@ impl<'a> From<&'a AttributeValue<'a>> for BorrowedAttributeValue<'a> {
             AttributeValue::Listener(_) => {
                 panic!("A listener cannot be turned into a borrowed value")
             }
+            AttributeValue::Spread(_) => {
+                panic!("Spread attributes are set one by one, and cannot be turned into a borrowed value")
+            }
             AttributeValue::Any(value) => {
                 let value = value.borrow();
                 BorrowedAttributeValue::Any(std::cell::Ref::map(value, |value| {
@@ -597,6 +629,19 @@ impl Debug for BorrowedAttributeValue<'_> {
     }
 }
 
+impl Clone for BorrowedAttributeValue<'_> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Text(arg0) => Self::Text(*arg0),
+            Self::Float(arg0) => Self::Float(*arg0),
+            Self::Int(arg0) => Self::Int(*arg0),
+            Self::Bool(arg0) => Self::Bool(*arg0),
+            Self::Any(arg0) => Self::Any(std::cell::Ref::clone(arg0)),
+            Self::None => Self::None,
+        }
+    }
+}
+
 impl PartialEq for BorrowedAttributeValue<'_> {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -615,7 +660,9 @@ fn serialize_any_value<S>(_: &std::cell::Ref<'_, dyn AnyValue>, _: S) -> Result<
 where
     S: serde::Serializer,
 {
-    panic!("Any cannot be serialized")
+    Err(serde::ser::Error::custom(
+        "Any attributes only live in the VirtualDom and cannot be serialized",
+    ))
 }
 
 #[cfg(feature = "serialize")]
@@ -623,7 +670,9 @@ fn deserialize_any_value<'de, 'a, D>(_: D) -> Result<std::cell::Ref<'a, dyn AnyV
 where
     D: serde::Deserializer<'de>,
 {
-    panic!("Any cannot be deserialized")
+    Err(serde::de::Error::custom(
+        "Any attributes only live in the VirtualDom and cannot be deserialized",
+    ))
 }
 
 impl<'a> std::fmt::Debug for AttributeValue<'a> {
@@ -636,6 +685,7 @@ impl<'a> std::fmt::Debug for AttributeValue<'a> {
             Self::Listener(_) => f.debug_tuple("Listener").finish(),
             Self::Any(_) => f.debug_tuple("Any").finish(),
             Self::None => write!(f, "None"),
+            Self::Spread(attributes) => f.debug_tuple("Spread").field(attributes).finish(),
         }
     }
 }
@@ -653,6 +703,12 @@ impl<'a> PartialEq for AttributeValue<'a> {
                 let r0 = r0.borrow();
                 l0.as_ref().unwrap().any_cmp(&**r0.as_ref().unwrap())
             }
+            (Self::Spread(l0), Self::Spread(r0)) => {
+                l0.len() == r0.len()
+                    && l0.iter().zip(r0.iter()).all(|(l, r)| {
+                        l.name == r.name && l.namespace == r.namespace && l.value == r.value
+                    })
+            }
             _ => false,
         }
     }
@@ -837,6 +893,34 @@ where
     }
 }
 
+/// A value that can be spread onto an element as an attribute, with [`ScopeState::spread`](crate::ScopeState::spread)
+pub trait IntoAttribute<'a> {
+    /// Convert into an attribute
+    fn into_attribute(self, bump: &'a Bump) -> Attribute<'a>;
+}
+
+impl<'a> IntoAttribute<'a> for Attribute<'a> {
+    fn into_attribute(self, _: &'a Bump) -> Attribute<'a> {
+        self
+    }
+}
+
+/// A name and a value, like `("aria-label", "Close")`
+impl<'a, N: AsRef<str>, V: IntoAttributeValue<'a>> IntoAttribute<'a> for (N, V) {
+    fn into_attribute(self, bump: &'a Bump) -> Attribute<'a> {
+        let (name, value) = self;
+        let (value, live) = into_live_value(value, bump);
+        Attribute {
+            name: bump.alloc_str(name.as_ref()),
+            value,
+            namespace: None,
+            mounted_element: Default::default(),
+            volatile: false,
+            live,
+        }
+    }
+}
+
 /// A value that can be converted into an attribute value
 pub trait IntoAttributeValue<'a> {
     /// Convert into an attribute value
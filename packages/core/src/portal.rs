@@ -0,0 +1,161 @@
+use crate::innerlude::*;
+use std::{cell::Cell, rc::Rc};
+
+/// Render the children of the portal under a different element than the portal itself
+///
+/// ## Details
+///
+/// The children are mounted under `target` while the portal leaves an empty placeholder where it was rendered. This is
+/// mostly useful for modals and tooltips that need to escape the layout of their parent.
+///
+/// Only the elements move. The children are still owned by the component that rendered the portal: they can read its
+/// contexts, events bubble up through the component tree, and removing the portal removes its children from the target.
+///
+/// The target can be any live element, or an anchor reserved by the renderer with [`VirtualDom::create_anchor`].
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// rsx!{
+///     Portal {
+///         target: *modal_root,
+///         div { class: "modal", "Are you sure?" }
+///     }
+/// }
+/// ```
+#[allow(non_upper_case_globals, non_snake_case)]
+pub fn Portal<'a>(cx: Scope<'a, PortalProps<'a>>) -> Element {
+    let portal = cx.use_hook(|| cx.provide_context(Rc::new(PortalContext::new(cx.props.target))));
+
+    // The VirtualDom moves the children over if the target changed since they were mounted
+    portal.target.set(cx.props.target);
+
+    let children = match cx.props.children.as_ref() {
+        Some(children) => children,
+
+        // Always render a node so there's something in the target to diff the next children against
+        None => {
+            return Some(VNode {
+                key: None,
+                parent: None,
+                template: Cell::new(EMPTY_PORTAL),
+                root_ids: Default::default(),
+                dynamic_nodes: cx
+                    .bump()
+                    .alloc([DynamicNode::Placeholder(Default::default())]),
+                dynamic_attrs: &[],
+            })
+        }
+    };
+
+    Some(VNode {
+        key: children.key,
+        parent: children.parent,
+        template: children.template.clone(),
+        root_ids: children.root_ids.clone(),
+        dynamic_nodes: children.dynamic_nodes,
+        dynamic_attrs: children.dynamic_attrs,
+    })
+}
+
+static EMPTY_PORTAL: Template<'static> = Template {
+    name: "portal.rs:0:0:0",
+    roots: &[TemplateNode::Dynamic { id: 0 }],
+    node_paths: &[&[0]],
+    attr_paths: &[],
+};
+
+/// Where the children of a [`Portal`] live in the dom
+pub(crate) struct PortalContext {
+    /// The element the portal was last rendered with
+    pub(crate) target: Cell<ElementId>,
+
+    /// The element the children are actually mounted under
+    pub(crate) mounted_target: Cell<Option<ElementId>>,
+
+    /// The placeholder left behind where the portal was rendered
+    pub(crate) placeholder: Cell<Option<ElementId>>,
+}
+
+impl PortalContext {
+    fn new(target: ElementId) -> Self {
+        Self {
+            target: Cell::new(target),
+            mounted_target: Cell::new(None),
+            placeholder: Cell::new(None),
+        }
+    }
+}
+
+impl VirtualDom {
+    /// Reserve an [`ElementId`] for an element that belongs to the renderer, usually to be used as the target of a
+    /// [`Portal`]
+    ///
+    /// The VirtualDom never creates or removes the anchor itself. The renderer needs to bind the id to its own element
+    /// before applying any mutations that mount children under it.
+    pub fn create_anchor(&mut self) -> ElementId {
+        self.next_null()
+    }
+
+    pub(crate) fn portal(&self, scope: ScopeId) -> Option<Rc<PortalContext>> {
+        self.scopes[scope.0].has_context()
+    }
+
+    /// The placeholder standing in for the children of the portal, if the scope is a portal
+    pub(crate) fn portal_placeholder(&self, scope: ScopeId) -> Option<ElementId> {
+        self.portal(scope)?.placeholder.get()
+    }
+}
+
+/// The props for the [`Portal`] component
+pub struct PortalProps<'a> {
+    target: ElementId,
+    children: Element<'a>,
+}
+
+/// The builder for [`PortalProps`]
+pub struct PortalBuilder<'a> {
+    target: ElementId,
+    children: Element<'a>,
+}
+
+impl<'a> PortalBuilder<'a> {
+    /// Set the element to mount the children under
+    pub fn target(mut self, target: ElementId) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Set the children to render under the target
+    pub fn children(mut self, children: Element<'a>) -> Self {
+        self.children = children;
+        self
+    }
+
+    /// Finish building the props
+    pub fn build(self) -> PortalProps<'a> {
+        PortalProps {
+            target: self.target,
+            children: self.children,
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn __dioxus_fields(&self) -> BuiltinFields {
+        BuiltinFields
+    }
+}
+
+impl<'a> Properties for PortalProps<'a> {
+    type Builder = PortalBuilder<'a>;
+    const IS_STATIC: bool = false;
+    fn builder() -> Self::Builder {
+        PortalBuilder {
+            target: ElementId(0),
+            children: None,
+        }
+    }
+    unsafe fn memoize(&self, _other: &Self) -> bool {
+        false
+    }
+}
@@ -8,6 +8,9 @@ use crate::innerlude::*;
 /// if your props borrow data, then the memoization strategy will simply default to "false" and the PartialEq will be ignored.
 /// This tends to be useful when props borrow something that simply cannot be compared (IE a reference to a closure);
 ///
+/// Props that are generic over types aren't memoized, since the types they're filled with might not be comparable. Adding
+/// `#[props(memoize)]` memoizes them like other 'static props, requiring them to be PartialEq and 'static.
+///
 /// By default, the memoization strategy is very conservative, but can be tuned to be more aggressive manually. However,
 /// this is only safe if the props are 'static - otherwise you might borrow references after-free.
 ///
@@ -35,6 +38,9 @@ use crate::innerlude::*;
 pub trait Properties: Sized {
     /// The type of the builder for this component.
     /// Used to create "in-progress" versions of the props.
+    ///
+    /// rsx! checks the props it's given against the hidden `__dioxus_fields` markers of the builder, which the derive
+    /// macro generates.
     type Builder;
 
     /// An indication if these props are can be memoized automatically.
@@ -49,6 +55,13 @@ pub trait Properties: Sized {
     /// The user must know if their props are static, but if they make a mistake, UB happens
     /// Therefore it's unsafe to memoize.
     unsafe fn memoize(&self, other: &Self) -> bool;
+
+    /// The props formatted with [`Debug`](std::fmt::Debug), for inspectors to show. None if they can't be.
+    ///
+    /// The derive macro fills this in for props that implement Debug.
+    fn debug_props(&self) -> Option<String> {
+        None
+    }
 }
 
 impl Properties for () {
@@ -66,6 +79,59 @@ impl Properties for () {
 pub struct EmptyBuilder;
 impl EmptyBuilder {
     pub fn build(self) {}
+
+    #[doc(hidden)]
+    pub fn __dioxus_fields(&self) -> EmptyFields {
+        EmptyFields
+    }
+}
+
+/// The markers rsx! checks the props of a component against, which a component without props has none of
+///
+/// Builders made by the Props derive have a marker method for every prop instead.
+#[doc(hidden)]
+pub struct EmptyFields;
+
+/// The markers of the props of the components that are built in, like [`crate::Fragment`] and
+/// [`crate::SuspenseBoundary`]
+#[doc(hidden)]
+pub struct BuiltinFields;
+
+#[doc(hidden)]
+impl BuiltinFields {
+    pub fn children(&self) {}
+    pub fn fallback(&self) {}
+    pub fn target(&self) {}
+}
+
+/// Formats props that implement Debug for [`Properties::debug_props`], and gives None for the ones that don't
+///
+/// The derive macro calls `(&&DebugProps(self)).debug_props()` with both traits in scope. Props that are Debug pick
+/// the method of [`PropsViaDebug`], since it takes the double reference as it is, and the others fall back to the one
+/// of [`PropsWithoutDebug`], which takes a single reference.
+#[doc(hidden)]
+pub struct DebugProps<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait PropsViaDebug {
+    fn debug_props(&self) -> Option<String>;
+}
+
+impl<T: std::fmt::Debug> PropsViaDebug for &DebugProps<'_, T> {
+    fn debug_props(&self) -> Option<String> {
+        Some(format!("{:?}", self.0))
+    }
+}
+
+#[doc(hidden)]
+pub trait PropsWithoutDebug {
+    fn debug_props(&self) -> Option<String>;
+}
+
+impl<T> PropsWithoutDebug for DebugProps<'_, T> {
+    fn debug_props(&self) -> Option<String> {
+        None
+    }
 }
 
 /// This utility function launches the builder method so rsx! and html! macros can use the typed-builder pattern
@@ -73,3 +139,24 @@ impl EmptyBuilder {
 pub fn fc_to_builder<'a, A, T: Properties + 'a>(_: fn(Scope<'a, T>) -> A) -> T::Builder {
     T::builder()
 }
+
+/// A value for an optional prop, which is either the value itself or an [`Option`] of it
+///
+/// The setters of `Option<T>` props take this, so a component can be given `value`, `Some(value)`, `None`, or an `if`
+/// without an `else` in rsx.
+pub trait IntoOptionalProp<T> {
+    /// Convert into the value of the prop
+    fn into_optional_prop(self) -> Option<T>;
+}
+
+impl<T> IntoOptionalProp<T> for T {
+    fn into_optional_prop(self) -> Option<T> {
+        Some(self)
+    }
+}
+
+impl<T> IntoOptionalProp<T> for Option<T> {
+    fn into_optional_prop(self) -> Option<T> {
+        self
+    }
+}
@@ -0,0 +1,228 @@
+use crate::innerlude::*;
+use bumpalo::Bump;
+
+/// Every batch of [`Mutations`] the VirtualDom produced while recording, along with the events that caused them
+///
+/// Recordings make it possible to step back through the history of the UI, IE for time-travel debugging or to attach
+/// a reproducible bug report. Replaying the first N steps into a fresh renderer with [`Recording::replay_to`] rebuilds
+/// the UI exactly as it was after step N.
+///
+/// Start recording with [`VirtualDom::start_recording`] before the first [`VirtualDom::rebuild`] so the recording
+/// contains every template and element a fresh renderer needs.
+///
+/// Recorded edits own their text, so [`BorrowedAttributeValue::Any`] attributes are recorded as
+/// [`BorrowedAttributeValue::None`].
+#[derive(Default)]
+pub struct Recording {
+    // The text of every recorded edit lives here for as long as the recording
+    bump: Bump,
+    steps: Vec<RecordedStep>,
+    pending_events: Vec<RecordedEvent>,
+
+    // The step being written out in parts, when the edits are streamed
+    open_step: Option<RecordedStep>,
+}
+
+/// A single batch of mutations in a [`Recording`]
+#[derive(Debug)]
+pub struct RecordedStep {
+    events: Vec<RecordedEvent>,
+    templates: Vec<Template<'static>>,
+    edits: Vec<Mutation<'static>>,
+}
+
+/// An event handled by the VirtualDom while recording
+///
+/// The data of the event is type-erased, so only what's needed to find the listeners is kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedEvent {
+    /// The name of the event, IE "click"
+    pub name: String,
+
+    /// The element the event was dispatched to
+    pub element: ElementId,
+
+    /// Whether the event bubbled
+    pub bubbles: bool,
+}
+
+impl Recording {
+    /// The steps recorded so far, in the order the VirtualDom produced them
+    pub fn steps(&self) -> &[RecordedStep] {
+        &self.steps
+    }
+
+    /// The number of steps recorded so far
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Check if nothing has been recorded yet
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Write the templates and edits of the first `steps` steps into a renderer, in order
+    ///
+    /// Asking for more steps than were recorded replays the whole recording.
+    pub fn replay_to<'a>(&'a self, steps: usize, to: &mut impl WriteMutations<'a>) {
+        for step in self.steps.iter().take(steps) {
+            for template in &step.templates {
+                to.register_template(*template);
+            }
+
+            for edit in &step.edits {
+                edit.clone().write_to(to);
+            }
+        }
+    }
+
+    pub(crate) fn record_event(&mut self, name: &str, element: ElementId, bubbles: bool) {
+        self.pending_events.push(RecordedEvent {
+            name: name.to_string(),
+            element,
+            bubbles,
+        });
+    }
+
+    pub(crate) fn record(&mut self, mutations: &Mutations) {
+        self.record_part(mutations);
+        self.finish_step();
+    }
+
+    /// Add the mutations to the step in progress, which is finished by [`Recording::finish_step`]
+    pub(crate) fn record_part(&mut self, mutations: &Mutations) {
+        if mutations.edits.is_empty() && mutations.templates.is_empty() {
+            return;
+        }
+
+        let edits: Vec<_> = mutations.edits.iter().map(|edit| self.copy(edit)).collect();
+
+        match self.open_step.as_mut() {
+            Some(step) => {
+                step.templates.extend_from_slice(&mutations.templates);
+                step.edits.extend(edits);
+            }
+            None => {
+                self.open_step = Some(RecordedStep {
+                    events: std::mem::take(&mut self.pending_events),
+                    templates: mutations.templates.clone(),
+                    edits,
+                })
+            }
+        }
+    }
+
+    pub(crate) fn finish_step(&mut self) {
+        // Frames where nothing happened would only make the recording harder to step through
+        let step = match self.open_step.take() {
+            Some(step) => step,
+            None if self.pending_events.is_empty() => return,
+            None => RecordedStep {
+                events: std::mem::take(&mut self.pending_events),
+                templates: Vec::new(),
+                edits: Vec::new(),
+            },
+        };
+
+        self.steps.push(step);
+    }
+
+    fn copy(&self, edit: &Mutation) -> Mutation<'static> {
+        // safety: the steps are dropped with the bump, and the edits given out never outlive a borrow of the recording
+        let text =
+            |text: &str| -> &'static str { unsafe { &*(self.bump.alloc_str(text) as *const str) } };
+
+        match *edit {
+            Mutation::AppendChildren { id, m } => Mutation::AppendChildren { id, m },
+            Mutation::AssignId { path, id } => Mutation::AssignId { path, id },
+            Mutation::CreatePlaceholder { id } => Mutation::CreatePlaceholder { id },
+            Mutation::CreateTextNode { value, id } => Mutation::CreateTextNode {
+                value: text(value),
+                id,
+            },
+            Mutation::HydrateText { path, value, id } => Mutation::HydrateText {
+                path,
+                value: text(value),
+                id,
+            },
+            Mutation::LoadTemplate { name, index, id } => {
+                Mutation::LoadTemplate { name, index, id }
+            }
+            Mutation::ReplaceWith { id, m } => Mutation::ReplaceWith { id, m },
+            Mutation::ReplacePlaceholder { path, m } => Mutation::ReplacePlaceholder { path, m },
+            Mutation::InsertAfter { id, m } => Mutation::InsertAfter { id, m },
+            Mutation::InsertBefore { id, m } => Mutation::InsertBefore { id, m },
+            Mutation::SetAttribute {
+                name,
+                ref value,
+                id,
+                ns,
+            } => Mutation::SetAttribute {
+                name: text(name),
+                value: match *value {
+                    BorrowedAttributeValue::Text(value) => {
+                        BorrowedAttributeValue::Text(text(value))
+                    }
+                    BorrowedAttributeValue::Float(f) => BorrowedAttributeValue::Float(f),
+                    BorrowedAttributeValue::Int(i) => BorrowedAttributeValue::Int(i),
+                    BorrowedAttributeValue::Bool(b) => BorrowedAttributeValue::Bool(b),
+                    BorrowedAttributeValue::Any(_) | BorrowedAttributeValue::None => {
+                        BorrowedAttributeValue::None
+                    }
+                },
+                id,
+                ns: ns.map(text),
+            },
+            Mutation::SetText { value, id } => Mutation::SetText {
+                value: text(value),
+                id,
+            },
+            Mutation::NewEventListener { name, id, passive } => Mutation::NewEventListener {
+                name: text(name),
+                id,
+                passive,
+            },
+            Mutation::RemoveEventListener { name, id } => Mutation::RemoveEventListener {
+                name: text(name),
+                id,
+            },
+            Mutation::Remove { id } => Mutation::Remove { id },
+            Mutation::PushRoot { id } => Mutation::PushRoot { id },
+        }
+    }
+}
+
+impl RecordedStep {
+    /// The events handled since the previous step
+    pub fn events(&self) -> &[RecordedEvent] {
+        &self.events
+    }
+
+    /// The templates first seen in this step
+    pub fn templates(&self) -> &[Template<'static>] {
+        &self.templates
+    }
+
+    /// The edits of this step
+    pub fn edits(&self) -> &[Mutation<'_>] {
+        &self.edits
+    }
+}
+
+impl VirtualDom {
+    /// Start recording every batch of mutations and the events that caused them, throwing out any previous recording
+    pub fn start_recording(&mut self) {
+        self.recording = Some(Recording::default());
+    }
+
+    /// Stop recording, handing back what was recorded
+    pub fn stop_recording(&mut self) -> Option<Recording> {
+        self.recording.take()
+    }
+
+    /// Get the recording in progress, if any
+    pub fn recording(&self) -> Option<&Recording> {
+        self.recording.as_ref()
+    }
+}
@@ -0,0 +1,97 @@
+//! Remembering why each component rendered, to find the hook or state that keeps rendering it
+
+use crate::innerlude::*;
+use std::cell::RefCell;
+
+/// What made a component render, from [`ScopeState::last_render_reason`]
+///
+/// When several things ask for the same render before it happens, the reason is the first of them.
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename_all = "snake_case")
+)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenderReason {
+    /// The component rendered for the first time
+    Created,
+
+    /// A listener of the event scheduled the render, like by setting a `use_state`
+    Event { name: String },
+
+    /// A context the component consumed was replaced, or a shared state it reads was written
+    Context,
+
+    /// A signal or an atom the component reads was written
+    Signal,
+
+    /// The parent rendered the component again with new props
+    Parent { scope: ScopeId },
+
+    /// A task scheduled the render while it was polled, like a `use_future` that resolved
+    Task { task: TaskId },
+
+    /// The code or the rsx of the component was hot reloaded
+    HotReload,
+
+    /// Something outside of any event or task scheduled the render, like another thread or
+    /// [`VirtualDom::mark_dirty`]
+    Scheduled,
+}
+
+thread_local! {
+    // What the VirtualDom is running on this thread, which the updates scheduled meanwhile are blamed on
+    static CAUSE: RefCell<Option<RenderReason>> = RefCell::new(None);
+}
+
+// Puts back the cause that was there before, even if the code that ran panicked
+struct RestoreCause(Option<RenderReason>);
+
+impl Drop for RestoreCause {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        _ = CAUSE.try_with(|cause| *cause.borrow_mut() = previous);
+    }
+}
+
+/// Run `f`, blaming every update it schedules on `reason`
+pub(crate) fn with_cause<O>(reason: RenderReason, f: impl FnOnce() -> O) -> O {
+    let _restore = RestoreCause(CAUSE.with(|cause| cause.replace(Some(reason))));
+    f()
+}
+
+/// The reason of an update scheduled right now, on any thread
+pub(crate) fn current_cause() -> RenderReason {
+    CAUSE
+        .try_with(|cause| cause.borrow().clone())
+        .ok()
+        .flatten()
+        .unwrap_or(RenderReason::Scheduled)
+}
+
+impl VirtualDom {
+    /// Remember why a scope is about to render, unless something else asked for the render first
+    pub(crate) fn note_render_reason(&self, id: ScopeId, reason: RenderReason) {
+        if let Some(scope) = self.scopes.get(id.0) {
+            scope
+                .pending_render_reason
+                .borrow_mut()
+                .get_or_insert(reason);
+        }
+    }
+
+    /// Record the reason of the render a scope is starting
+    ///
+    /// Renders nothing asked for come from the parent diffing the component again with new props.
+    pub(crate) fn start_render_reason(&self, id: ScopeId) {
+        let scope = &self.scopes[id.0];
+        let pending = scope.pending_render_reason.take();
+        let reason = match (scope.render_cnt.get(), pending, scope.parent()) {
+            (0, _, _) => RenderReason::Created,
+            (_, Some(pending), _) => pending,
+            (_, None, Some(parent)) => RenderReason::Parent { scope: parent },
+            (_, None, None) => RenderReason::Scheduled,
+        };
+        *scope.render_reason.borrow_mut() = Some(reason);
+    }
+}
@@ -0,0 +1,146 @@
+//! Counting how each component renders, to find the ones that render without changing anything
+
+use crate::innerlude::*;
+use rustc_hash::FxHashMap;
+use std::time::Duration;
+
+/// How a component rendered since [`VirtualDom::track_render_stats`] was turned on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeStats {
+    /// The scope of the component
+    pub scope: ScopeId,
+
+    /// The name of the component function
+    pub name: &'static str,
+
+    /// How many times the component rendered again after it was created
+    pub renders: usize,
+
+    /// The renders whose output diffed into no edits at all, which memoizing the component could have skipped
+    pub wasted_renders: usize,
+
+    /// How long diffing the output of the component took, not counting the components it rendered
+    pub diff_time: Duration,
+}
+
+#[derive(Default)]
+pub(crate) struct RenderStats {
+    scopes: FxHashMap<ScopeId, ScopeStats>,
+
+    // The diffs in progress, innermost last
+    diffs: Vec<DiffFrame>,
+}
+
+struct DiffFrame {
+    started: Duration,
+    edits: usize,
+
+    // What the diffs of nested components took, so they aren't counted against this one
+    nested_edits: usize,
+    nested_time: Duration,
+}
+
+/// A clock that starts with the first diff
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn default_clock() -> Duration {
+    thread_local! {
+        static START: std::time::Instant = std::time::Instant::now();
+    }
+    START.with(|start| start.elapsed())
+}
+
+/// std has no clock in the browser, so renderers pass one to [`VirtualDom::set_render_clock`]
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn default_clock() -> Duration {
+    Duration::ZERO
+}
+
+impl VirtualDom {
+    /// Start or stop counting how each component renders
+    ///
+    /// Every time a component renders again, its diff is timed and checked for edits. Renders that produce no edits
+    /// are counted as wasted: the component could have been memoized, or its state could have been split up so it
+    /// doesn't rerender. Tracking reads the clock twice per diff, so it's off by default. Turning it off throws out the
+    /// stats.
+    pub fn track_render_stats(&mut self, track: bool) {
+        self.render_stats = match track {
+            true => Some(self.render_stats.take().unwrap_or_default()),
+            false => None,
+        };
+    }
+
+    /// Time diffs with another clock, like `performance.now()` in the browser, where std has no clock
+    ///
+    /// The clock gives the time since any point, as long as it's the same point every time.
+    pub fn set_render_clock(&mut self, clock: fn() -> Duration) {
+        self.render_clock = clock;
+    }
+
+    /// The stats of every component that rendered again, the ones that wasted the most renders first
+    ///
+    /// Returns nothing unless [`VirtualDom::track_render_stats`] is on.
+    pub fn render_stats(&self) -> Vec<ScopeStats> {
+        let mut stats: Vec<_> = match &self.render_stats {
+            Some(stats) => stats.scopes.values().cloned().collect(),
+            None => return Vec::new(),
+        };
+        stats.sort_by(|a, b| {
+            b.wasted_renders
+                .cmp(&a.wasted_renders)
+                .then(b.renders.cmp(&a.renders))
+                .then(a.scope.0.cmp(&b.scope.0))
+        });
+        stats
+    }
+
+    pub(crate) fn start_diff_stats(&mut self) {
+        if let Some(stats) = self.render_stats.as_mut() {
+            stats.diffs.push(DiffFrame {
+                started: (self.render_clock)(),
+                edits: self.mutations.edits.len(),
+                nested_edits: 0,
+                nested_time: Duration::ZERO,
+            });
+        }
+    }
+
+    pub(crate) fn finish_diff_stats(&mut self, scope: ScopeId) {
+        let stats = match self.render_stats.as_mut() {
+            Some(stats) => stats,
+            None => return,
+        };
+        let frame = match stats.diffs.pop() {
+            Some(frame) => frame,
+            None => return,
+        };
+
+        let edits = self.mutations.edits.len().saturating_sub(frame.edits);
+        let time = (self.render_clock)().saturating_sub(frame.started);
+
+        let name = self.scopes[scope.0].name;
+        let scope_stats = stats.scopes.entry(scope).or_insert_with(|| ScopeStats {
+            scope,
+            name,
+            renders: 0,
+            wasted_renders: 0,
+            diff_time: Duration::ZERO,
+        });
+        scope_stats.renders += 1;
+        if edits == frame.nested_edits {
+            scope_stats.wasted_renders += 1;
+        }
+        scope_stats.diff_time += time.saturating_sub(frame.nested_time);
+
+        if let Some(parent) = stats.diffs.last_mut() {
+            parent.nested_edits += edits;
+            parent.nested_time += time;
+        }
+    }
+
+    /// Forget the stats of a scope whose slot is handed to a new component
+    pub(crate) fn clear_scope_stats(&mut self, scope: ScopeId) {
+        if let Some(stats) = self.render_stats.as_mut() {
+            stats.scopes.remove(&scope);
+        }
+    }
+}
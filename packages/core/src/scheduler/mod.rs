@@ -1,4 +1,7 @@
-use crate::ScopeId;
+use crate::{
+    innerlude::{CapturedError, LiveId, Priority, RenderReason},
+    ScopeId,
+};
 use slab::Slab;
 
 mod suspense;
@@ -14,13 +17,19 @@ pub use task::*;
 #[derive(Debug)]
 pub(crate) enum SchedulerMsg {
     /// Immediate updates from Components that mark them as dirty
-    Immediate(ScopeId),
+    Immediate(ScopeId, RenderReason),
+
+    /// Updates from Components that mark them as dirty in a specific priority lane
+    ImmediateWithPriority(ScopeId, Priority, RenderReason),
 
     /// A task has woken and needs to be progressed
     TaskNotified(TaskId),
 
     /// A task has woken and needs to be progressed
     SuspenseNotified(SuspenseId),
+
+    /// A value displayed with [`crate::Live`] changed, so the text and attributes displaying it need to be patched
+    LiveUpdate(LiveId, String),
 }
 
 use std::{cell::RefCell, rc::Rc};
@@ -33,14 +42,24 @@ pub(crate) struct Scheduler {
 
     /// Async components
     pub leaves: RefCell<Slab<SuspenseLeaf>>,
+
+    /// Called with errors that no error boundary caught
+    pub error_handler: RefCell<Option<ErrorHandler>>,
+
+    /// Runs tasks instead of the VirtualDom, if the app set one
+    pub spawner: RefCell<Option<Rc<dyn Spawner>>>,
 }
 
+pub(crate) type ErrorHandler = Rc<dyn Fn(ScopeId, CapturedError)>;
+
 impl Scheduler {
     pub fn new(sender: futures_channel::mpsc::UnboundedSender<SchedulerMsg>) -> Rc<Self> {
         Rc::new(Scheduler {
             sender,
             tasks: RefCell::new(Slab::new()),
             leaves: RefCell::new(Slab::new()),
+            error_handler: RefCell::new(None),
+            spawner: RefCell::new(None),
         })
     }
 }
@@ -2,8 +2,9 @@ use futures_util::task::ArcWake;
 
 use super::SchedulerMsg;
 use crate::ElementId;
-use crate::{innerlude::Mutations, Element, ScopeId};
+use crate::{innerlude::Mutations, Element, Scope, ScopeId};
 use std::future::Future;
+use std::rc::Rc;
 use std::sync::Arc;
 use std::task::Waker;
 use std::{
@@ -20,8 +21,18 @@ pub struct SuspenseContext {
     pub(crate) id: ScopeId,
     pub(crate) waiting_on: RefCell<HashSet<SuspenseId>>,
     pub(crate) mutations: RefCell<Mutations<'static>>,
+
+    /// The edits for the leaves that resolved while the tree was held back, which replace their placeholders once
+    /// the tree is in the dom
+    pub(crate) resolved: RefCell<Mutations<'static>>,
     pub(crate) placeholder: Cell<Option<ElementId>>,
     pub(crate) created_on_stack: Cell<usize>,
+
+    /// Renders the nodes to show while the boundary is suspended, refreshed every time the boundary renders
+    pub(crate) fallback: RefCell<Option<Rc<dyn Fn(Scope) -> Element>>>,
+
+    /// The scope rendering the fallback in place of the suspended tree while it's mounted
+    pub(crate) fallback_scope: Cell<Option<ScopeId>>,
 }
 
 impl SuspenseContext {
@@ -31,10 +42,33 @@ impl SuspenseContext {
             id,
             waiting_on: Default::default(),
             mutations: RefCell::new(Mutations::default()),
+            resolved: RefCell::new(Mutations::default()),
             placeholder: Cell::new(None),
             created_on_stack: Cell::new(0),
+            fallback: RefCell::new(None),
+            fallback_scope: Cell::new(None),
         }
     }
+
+    /// Check if any components below this boundary are still waiting on their futures
+    pub fn is_suspended(&self) -> bool {
+        !self.waiting_on.borrow().is_empty()
+    }
+
+    /// The scope rendering the fallback while the boundary's children are still being mounted, if it has one
+    ///
+    /// This is for renderers that walk the VirtualDom themselves, like server-side rendering. The fallback is a scope
+    /// of its own, so it can be rendered like any other component.
+    pub fn fallback_scope(&self) -> Option<ScopeId> {
+        self.fallback_scope.get()
+    }
+
+    /// Check if the tree below this boundary is still waiting to be mounted for the first time
+    ///
+    /// Once the boundary is mounted, suspended re-renders below it are diffed straight into the dom when they resolve.
+    pub(crate) fn is_mounting(&self) -> bool {
+        self.placeholder.get().is_some() || self.fallback_scope.get().is_some()
+    }
 }
 
 pub(crate) struct SuspenseLeaf {
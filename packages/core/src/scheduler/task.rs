@@ -1,10 +1,13 @@
+use futures_util::future::{abortable, poll_fn, AbortHandle};
 use futures_util::task::ArcWake;
 
 use super::{Scheduler, SchedulerMsg};
+use crate::innerlude::{with_cause, RenderReason};
 use crate::ScopeId;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::future::Future;
 use std::pin::Pin;
+use std::rc::Rc;
 use std::sync::Arc;
 use std::task::Waker;
 
@@ -21,6 +24,68 @@ pub(crate) struct LocalTask {
     pub scope: ScopeId,
     pub(super) task: RefCell<Pin<Box<dyn Future<Output = ()> + 'static>>>,
     pub waker: Waker,
+
+    // Shared with every handle to the task, set once the future is dropped
+    finished: Rc<Cell<bool>>,
+
+    // Cancels the future if a custom spawner is running it
+    abort: Option<AbortHandle>,
+}
+
+impl Drop for LocalTask {
+    fn drop(&mut self) {
+        if let Some(abort) = &self.abort {
+            abort.abort();
+        }
+        self.finished.set(true);
+    }
+}
+
+/// Runs the futures components spawn on an executor of the app's choosing, instead of inside the VirtualDom
+///
+/// Set with [`crate::VirtualDom::with_spawner`]. The tasks still belong to the component that spawned them, so they are
+/// cancelled when it is dropped, but the VirtualDom never polls them. Closures taking the future implement this.
+pub trait Spawner {
+    /// Start running a future on the same thread as the VirtualDom
+    fn spawn_local(&self, task: Pin<Box<dyn Future<Output = ()>>>);
+}
+
+impl<F: Fn(Pin<Box<dyn Future<Output = ()>>>)> Spawner for F {
+    fn spawn_local(&self, task: Pin<Box<dyn Future<Output = ()>>>) {
+        self(task)
+    }
+}
+
+/// A handle to a spawned task
+///
+/// Dropping the handle doesn't cancel the task.
+#[derive(Clone)]
+pub struct TaskHandle {
+    id: TaskId,
+    finished: Rc<Cell<bool>>,
+    scheduler: Rc<Scheduler>,
+}
+
+impl TaskHandle {
+    /// Get the id of the task
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    /// Cancel the task, dropping its future
+    ///
+    /// Does nothing if the task already finished.
+    pub fn cancel(&self) {
+        // The id might have been handed to another task since this one finished
+        if !self.finished.get() {
+            self.scheduler.remove(self.id);
+        }
+    }
+
+    /// Check if the task ran to completion or was cancelled
+    pub fn is_finished(&self) -> bool {
+        self.finished.get()
+    }
 }
 
 impl Scheduler {
@@ -33,35 +98,84 @@ impl Scheduler {
     ///
     /// Spawning a future onto the root scope will cause it to be dropped when the root component is dropped - which
     /// will only occur when the VirtuaalDom itself has been dropped.
-    pub fn spawn(&self, scope: ScopeId, task: impl Future<Output = ()> + 'static) -> TaskId {
+    pub fn spawn(
+        self: &Rc<Self>,
+        scope: ScopeId,
+        task: impl Future<Output = ()> + 'static,
+    ) -> TaskId {
+        // A custom spawner runs the future itself, so the VirtualDom only keeps a way to cancel it
+        let spawner = self.spawner.borrow().clone();
+        let (local, remote): (Pin<Box<dyn Future<Output = ()>>>, _) = match spawner {
+            Some(spawner) => {
+                let (task, abort) = abortable(task);
+                (
+                    Box::pin(std::future::pending()),
+                    Some((spawner, task, abort)),
+                )
+            }
+            None => (Box::pin(task), None),
+        };
+
         let mut tasks = self.tasks.borrow_mut();
 
         let entry = tasks.vacant_entry();
         let task_id = TaskId(entry.key());
 
         let task = LocalTask {
-            task: RefCell::new(Box::pin(task)),
+            task: RefCell::new(local),
             scope,
             waker: futures_util::task::waker(Arc::new(LocalTaskHandle {
                 id: task_id,
                 tx: self.sender.clone(),
             })),
+            finished: Default::default(),
+            abort: remote.as_ref().map(|(_, _, abort)| abort.clone()),
         };
 
         entry.insert(task);
+        drop(tasks);
 
-        self.sender
-            .unbounded_send(SchedulerMsg::TaskNotified(task_id))
-            .expect("Scheduler should exist");
+        match remote {
+            Some((spawner, task, _)) => {
+                let scheduler = self.clone();
+                let mut task = Box::pin(task);
+                spawner.spawn_local(Box::pin(async move {
+                    // The updates the task schedules while it runs are blamed on it, like when the VirtualDom polls it
+                    let reason = RenderReason::Task { task: task_id };
+                    let finished =
+                        poll_fn(|cx| with_cause(reason.clone(), || task.as_mut().poll(cx))).await;
+
+                    // A cancelled task was already removed, and its id might belong to another task by now
+                    if finished.is_ok() {
+                        scheduler.remove(task_id);
+                    }
+                }));
+            }
+            None => self
+                .sender
+                .unbounded_send(SchedulerMsg::TaskNotified(task_id))
+                .expect("Scheduler should exist"),
+        }
 
         task_id
     }
 
+    /// Get a handle to a task that was just spawned
+    pub(crate) fn handle(self: &Rc<Self>, id: TaskId) -> TaskHandle {
+        TaskHandle {
+            id,
+            finished: self.tasks.borrow()[id.0].finished.clone(),
+            scheduler: self.clone(),
+        }
+    }
+
     /// Drop the future with the given TaskId
     ///
     /// This does not abort the task, so you'll want to wrap it in an aborthandle if that's important to you
     pub fn remove(&self, id: TaskId) {
-        self.tasks.borrow_mut().try_remove(id.0);
+        // Drop the future outside of the borrow in case it touches the scheduler
+        let task = self.tasks.borrow_mut().try_remove(id.0);
+        drop(task);
     }
 }
 
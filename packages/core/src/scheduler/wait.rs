@@ -5,7 +5,8 @@ use std::{
 };
 
 use crate::{
-    innerlude::{Mutation, Mutations, SuspenseContext},
+    innerlude::{with_cause, Mutation, Mutations, RenderReason, SuspenseContext},
+    instrument::span,
     nodes::RenderReturn,
     ScopeId, TaskId, VNode, VirtualDom,
 };
@@ -26,10 +27,21 @@ impl VirtualDom {
             None => return,
         };
 
+        let _span = span!(
+            "poll task",
+            task = id.0,
+            scope = task.scope.0,
+            component = self.scopes[task.scope.0].name
+        );
         let mut cx = Context::from_waker(&task.waker);
 
+        // The updates the task schedules while it runs are blamed on it
+        let poll = with_cause(RenderReason::Task { task: id }, || {
+            task.task.borrow_mut().as_mut().poll(&mut cx)
+        });
+
         // If the task completes...
-        if task.task.borrow_mut().as_mut().poll(&mut cx).is_ready() {
+        if poll.is_ready() {
             // Remove it from the scope so we dont try to double drop it when the scope dropes
             let scope = &self.scopes[task.scope.0];
             scope.spawned_tasks.borrow_mut().remove(&id);
@@ -47,9 +59,19 @@ impl VirtualDom {
 
     pub(crate) fn handle_suspense_wakeup(&mut self, id: SuspenseId) {
         let leaves = self.scheduler.leaves.borrow_mut();
-        let leaf = leaves.get(id.0).unwrap();
+
+        let leaf = match leaves.get(id.0) {
+            Some(leaf) => leaf,
+            // The leaf already resolved, so this is a stale wakeup
+            None => return,
+        };
 
         let scope_id = leaf.scope_id;
+        let _span = span!(
+            "poll suspense",
+            scope = scope_id.0,
+            component = self.scopes[scope_id.0].name
+        );
 
         // todo: cache the waker
         let mut cx = Context::from_waker(&leaf.waker);
@@ -62,7 +84,14 @@ impl VirtualDom {
         // we should attach them to that component and then render its children
         // continue rendering the tree until we hit yet another suspended component
         if let Poll::Ready(new_nodes) = as_pinned_mut.poll_unpin(&mut cx) {
-            let fiber = self.acquire_suspense_boundary(leaf.scope_id);
+            // The leaf is done, so the scheduler no longer needs to track it
+            drop(leaves);
+            self.scheduler.leaves.borrow_mut().remove(id.0);
+
+            // The future rendered the rest of the live values the scope displays
+            self.sync_live(scope_id, Default::default());
+
+            let fiber = self.acquire_suspense_boundary(scope_id);
 
             let scope = &self.scopes[scope_id.0];
             let arena = scope.current_frame();
@@ -76,36 +105,104 @@ impl VirtualDom {
 
             fiber.waiting_on.borrow_mut().remove(&id);
 
-            if let RenderReturn::Ready(template) = ret {
-                let mutations_ref = &mut fiber.mutations.borrow_mut();
-                let mutations = &mut **mutations_ref;
-                let template: &VNode = unsafe { std::mem::transmute(template) };
-                let mutations: &mut Mutations = unsafe { std::mem::transmute(mutations) };
+            // The boundary is already in the dom, so the resolved nodes can go straight into the mutations
+            if !fiber.is_mounting() {
+                self.resolve_mounted_leaf(scope_id);
+                return;
+            }
+
+            match ret {
+                // The nodes replace the placeholder of the scope once the boundary puts the tree in the dom
+                RenderReturn::Ready(template) => {
+                    let mutations_ref = &mut fiber.resolved.borrow_mut();
+                    let mutations = &mut **mutations_ref;
+                    let template: &VNode = unsafe { std::mem::transmute(template) };
+                    let mutations: &mut Mutations = unsafe { std::mem::transmute(mutations) };
 
-                std::mem::swap(&mut self.mutations, mutations);
+                    std::mem::swap(&mut self.mutations, mutations);
 
-                let place_holder_id = scope.placeholder.get().unwrap();
-                self.scope_stack.push(scope_id);
+                    let place_holder_id = scope.placeholder.get().unwrap();
+                    self.scope_stack.push(scope_id);
 
-                drop(leaves);
+                    let created = self.create(template);
+                    self.scope_stack.pop();
+                    self.mutations.push(Mutation::ReplaceWith {
+                        id: place_holder_id,
+                        m: created,
+                    });
 
-                let created = self.create(template);
-                self.scope_stack.pop();
-                mutations.push(Mutation::ReplaceWith {
-                    id: place_holder_id,
-                    m: created,
-                });
+                    for leaf in self.collected_leaves.drain(..) {
+                        fiber.waiting_on.borrow_mut().insert(leaf);
+                    }
 
-                for leaf in self.collected_leaves.drain(..) {
-                    fiber.waiting_on.borrow_mut().insert(leaf);
+                    std::mem::swap(&mut self.mutations, mutations);
                 }
 
-                std::mem::swap(&mut self.mutations, mutations);
+                // The placeholder the scope was created with stays around for the error
+                RenderReturn::Aborted(placeholder) => placeholder.id.set(scope.placeholder.get()),
 
-                if fiber.waiting_on.borrow().is_empty() {
-                    self.finished_fibers.push(fiber.id);
-                }
+                RenderReturn::Pending(_) => unreachable!("futures resolve to nodes"),
+            }
+
+            if fiber.waiting_on.borrow().is_empty() {
+                self.finished_fibers.push(fiber.id);
             }
         }
     }
+
+    /// Mount the nodes of a suspended scope below a boundary that has already been mounted
+    ///
+    /// A scope that was re-rendered kept its old nodes mounted while it waited, so they get diffed against the new
+    /// ones. A scope created while suspended only has its placeholder in the dom, which gets replaced.
+    fn resolve_mounted_leaf(&mut self, scope_id: ScopeId) {
+        // Safety: rendering a suspended scope again restarts its future and keeps the mounted frame as the previous
+        // one, so the previous frame always holds what's in the dom
+        let mounted = unsafe { self.scopes[scope_id.0].previous_frame().try_load_node() };
+
+        match mounted {
+            Some(RenderReturn::Ready(_) | RenderReturn::Aborted(_)) => self.diff_scope(scope_id),
+            _ => {
+                let new = unsafe { self.scopes[scope_id.0].root_node().extend_lifetime_ref() };
+                self.mount_suspended(scope_id, new);
+            }
+        }
+
+        self.attach_collected_leaves();
+    }
+
+    /// Make the boundaries of the leaves collected while rendering wait on them
+    pub(crate) fn attach_collected_leaves(&mut self) {
+        let leaves = self.scheduler.leaves.borrow();
+        for id in std::mem::take(&mut self.collected_leaves) {
+            if let Some(leaf) = leaves.get(id.0) {
+                self.acquire_suspense_boundary(leaf.scope_id)
+                    .waiting_on
+                    .borrow_mut()
+                    .insert(id);
+            }
+        }
+    }
+
+    /// Stop waiting on the future of a suspended scope and drop it
+    pub(crate) fn remove_suspense_leaf(&mut self, scope: ScopeId) {
+        let leaf = {
+            let mut leaves = self.scheduler.leaves.borrow_mut();
+            let id = leaves
+                .iter()
+                .find(|(_, leaf)| leaf.scope_id == scope)
+                .map(|(id, _)| id);
+            id.map(|id| (SuspenseId(id), leaves.remove(id)))
+        };
+
+        if let Some((id, leaf)) = leaf {
+            self.acquire_suspense_boundary(scope)
+                .waiting_on
+                .borrow_mut()
+                .remove(&id);
+            self.collected_leaves.retain(|collected| *collected != id);
+
+            // safety: the future is pinned in the bump arena, which never drops it, and the leaf was its only handle
+            unsafe { std::ptr::drop_in_place(leaf.task) };
+        }
+    }
 }
@@ -1,11 +1,13 @@
 use crate::{
     any_props::AnyProps,
     bump_frame::BumpFrame,
-    innerlude::DirtyScope,
-    innerlude::{SuspenseHandle, SuspenseId, SuspenseLeaf},
+    innerlude::unmark_dirty,
+    innerlude::{set_rendering, SuspenseHandle, SuspenseId, SuspenseLeaf},
+    instrument::span,
     nodes::RenderReturn,
     scopes::{ScopeId, ScopeState},
-    virtual_dom::VirtualDom,
+    virtual_dom::{HookReload, VirtualDom},
+    AttributeValue,
 };
 use futures_util::FutureExt;
 use std::{
@@ -22,6 +24,7 @@ impl VirtualDom {
         name: &'static str,
     ) -> &ScopeState {
         let parent = self.acquire_current_scope_raw();
+        self.clear_scope_stats(ScopeId(self.scopes.vacant_key()));
         let entry = self.scopes.vacant_entry();
         let height = unsafe { parent.map(|f| (*f).height + 1).unwrap_or(0) };
         let id = ScopeId(entry.key());
@@ -32,6 +35,8 @@ impl VirtualDom {
             height,
             name,
             props: Some(props),
+            render_reason: Default::default(),
+            pending_render_reason: Default::default(),
             tasks: self.scheduler.clone(),
             placeholder: Default::default(),
             node_arena_1: BumpFrame::new(0),
@@ -40,10 +45,18 @@ impl VirtualDom {
             render_cnt: Default::default(),
             hook_arena: Default::default(),
             hook_list: Default::default(),
+            hook_names: Default::default(),
             hook_idx: Default::default(),
+            reloading: Default::default(),
             shared_contexts: Default::default(),
+            context_subscribers: Default::default(),
+            context_subscriptions: Default::default(),
             borrowed_props: Default::default(),
             attributes_to_drop: Default::default(),
+            on_mount: Default::default(),
+            on_drop: Default::default(),
+            live: Default::default(),
+            exit_transitions: Default::default(),
         }))
     }
 
@@ -54,13 +67,41 @@ impl VirtualDom {
     }
 
     pub(crate) fn run_scope(&mut self, scope_id: ScopeId) -> &RenderReturn {
+        let _span = span!(
+            "render",
+            scope = scope_id.0,
+            component = self.scopes[scope_id.0].name
+        );
+
+        // A scope that's still waiting on its future gives it up and starts over with the new render
+        if let Some(RenderReturn::Pending(_)) =
+            unsafe { self.scopes[scope_id.0].current_frame().try_load_node() }
+        {
+            self.restart_suspended(scope_id);
+        }
+
         // Cycle to the next frame and then reset it
         // This breaks any latent references, invalidating every pointer referencing into it.
         // Remove all the outdated listeners
         self.ensure_drop_safety(scope_id);
+        self.start_render_reason(scope_id);
+
+        // Props can be made with the old code of a hot reloaded component, like by a parent that wasn't reloaded
+        if !self.reloaded_components.is_empty() {
+            if let Some(props) = self.scopes[scope_id.0].props.as_mut() {
+                if let Some(&render_fn) = self.reloaded_components.get(&props.render_fn()) {
+                    // safety: replace_component only maps between components with the same props
+                    unsafe { props.set_render_fn(render_fn) };
+                }
+            }
+        }
+
+        let live = self.scopes[scope_id.0].live.take();
 
         let mut new_nodes = unsafe {
-            self.scopes[scope_id.0].previous_frame().bump_mut().reset();
+            self.scopes[scope_id.0]
+                .previous_frame()
+                .recycle(self.memory_budget);
 
             let scope = &self.scopes[scope_id.0];
 
@@ -70,7 +111,21 @@ impl VirtualDom {
             let props: &dyn AnyProps = scope.props.as_ref().unwrap().as_ref();
             let props: &dyn AnyProps = mem::transmute(props);
 
-            props.render(scope).extend_lifetime()
+            if scope.reloading.get() == Some(HookReload::Reset) {
+                scope.reloading.set(None);
+                scope.drop_hooks_from(0);
+            }
+
+            set_rendering(Some(scope_id));
+            let nodes = props.render(scope).extend_lifetime();
+            set_rendering(None);
+
+            // The new code may call fewer hooks than the old one did
+            if !matches!(nodes, RenderReturn::Pending(_)) && scope.reloading.take().is_some() {
+                scope.drop_hooks_from(scope.hook_idx.get());
+            }
+
+            nodes
         };
 
         // immediately resolve futures that can be resolved
@@ -127,8 +182,23 @@ impl VirtualDom {
             }
         };
 
+        self.sync_live(scope_id, live);
+
         let scope = &self.scopes[scope_id.0];
 
+        // Any listeners from the last render were released above, so every listener in the list is new
+        let new_listeners = scope
+            .attributes_to_drop
+            .borrow()
+            .iter()
+            .filter(|attr| matches!(unsafe { &(***attr).value }, AttributeValue::Listener(_)))
+            .count();
+        let listener_count = self.listener_count.get() + new_listeners;
+        self.listener_count.set(listener_count);
+        if listener_count > self.peak_listener_count.get() {
+            self.peak_listener_count.set(listener_count);
+        }
+
         // We write on top of the previous frame and then make it the current by pushing the generation forward
         let frame = scope.previous_frame();
 
@@ -140,12 +210,30 @@ impl VirtualDom {
         scope.render_cnt.set(scope.render_cnt.get() + 1);
 
         // remove this scope from dirty scopes
-        self.dirty_scopes.remove(&DirtyScope {
-            height: scope.height,
-            id: scope.id,
-        });
+        unmark_dirty(&mut self.dirty_scopes, scope.height, scope.id);
+
+        // The mount callbacks run once the renderer has applied the edits we're about to generate
+        if !scope.on_mount.borrow().is_empty() {
+            self.pending_mounts.push(scope_id);
+        }
 
         // rebind the lifetime now that its stored internally
         unsafe { allocated.extend_lifetime_ref() }
     }
+
+    /// Drop the future of a suspended scope that's about to render again
+    ///
+    /// The previous frame of a suspended scope holds the nodes that stay mounted while it waits. Rendering recycles
+    /// the previous frame, so the frames are swapped first: the new render goes on top of the abandoned future, and
+    /// the mounted nodes stay around to be diffed against.
+    fn restart_suspended(&mut self, scope_id: ScopeId) {
+        self.remove_suspense_leaf(scope_id);
+
+        let scope = &mut self.scopes[scope_id.0];
+        if let Some(RenderReturn::Ready(_) | RenderReturn::Aborted(_)) =
+            unsafe { scope.previous_frame().try_load_node() }
+        {
+            mem::swap(&mut scope.node_arena_1, &mut scope.node_arena_2);
+        }
+    }
 }
@@ -3,10 +3,13 @@ use crate::{
     any_props::VProps,
     arena::ElementId,
     bump_frame::BumpFrame,
-    innerlude::{DynamicNode, EventHandler, VComponent, VText},
-    innerlude::{ErrorBoundary, Scheduler, SchedulerMsg},
+    innerlude::{current_cause, into_live_value, LiveId, RenderReason},
+    innerlude::{
+        CapturedError, ComponentStack, ErrorBoundary, Priority, Scheduler, SchedulerMsg, TaskHandle,
+    },
+    innerlude::{DynamicNode, EventHandler, HookReload, VComponent, VText},
     lazynodes::LazyNodes,
-    nodes::{ComponentReturn, IntoAttributeValue, IntoDynNode, RenderReturn},
+    nodes::{ComponentReturn, IntoAttribute, IntoAttributeValue, IntoDynNode, RenderReturn},
     AnyValue, Attribute, AttributeValue, Element, Event, Properties, TaskId,
 };
 use bumpalo::{boxed::Box as BumpBox, Bump};
@@ -80,10 +83,19 @@ pub struct ScopeState {
 
     pub(crate) hook_arena: Bump,
     pub(crate) hook_list: RefCell<Vec<*mut dyn Any>>,
+    // the types of the hooks, for inspectors
+    pub(crate) hook_names: RefCell<Vec<&'static str>>,
     pub(crate) hook_idx: Cell<usize>,
 
+    // Set until the scope renders with the code of a hot reloaded component, so the hooks that changed start over
+    pub(crate) reloading: Cell<Option<HookReload>>,
+
     pub(crate) shared_contexts: RefCell<FxHashMap<TypeId, Box<dyn Any>>>,
 
+    // The scopes that consumed each context this scope provides, and the providers of every context this scope consumed
+    pub(crate) context_subscribers: RefCell<FxHashMap<TypeId, FxHashSet<ScopeId>>>,
+    pub(crate) context_subscriptions: RefCell<Vec<(*const ScopeState, TypeId)>>,
+
     pub(crate) tasks: Rc<Scheduler>,
     pub(crate) spawned_tasks: RefCell<FxHashSet<TaskId>>,
 
@@ -91,7 +103,21 @@ pub struct ScopeState {
     pub(crate) attributes_to_drop: RefCell<Vec<*const Attribute<'static>>>,
 
     pub(crate) props: Option<Box<dyn AnyProps<'static>>>,
+
+    // Why the scope rendered the last time, and why it will render next time
+    pub(crate) render_reason: RefCell<Option<RenderReason>>,
+    pub(crate) pending_render_reason: RefCell<Option<RenderReason>>,
+
     pub(crate) placeholder: Cell<Option<ElementId>>,
+
+    pub(crate) on_mount: RefCell<Vec<Box<dyn FnOnce()>>>,
+    pub(crate) on_drop: RefCell<Vec<Box<dyn FnOnce()>>>,
+
+    // The live values displayed by the last render
+    pub(crate) live: RefCell<FxHashSet<LiveId>>,
+
+    // Whether the keyed children this scope stops rendering stay mounted until their exit transition finishes
+    pub(crate) exit_transitions: Cell<bool>,
 }
 
 impl<'src> ScopeState {
@@ -123,6 +149,21 @@ impl<'src> ScopeState {
         self.render_cnt.get()
     }
 
+    /// Why the component rendered the last time, or `None` if it hasn't rendered yet
+    ///
+    /// This is the place to start when a component renders more than it should: the reason points at the event,
+    /// context, signal, task or parent that asked for the render.
+    ///
+    /// ```rust, ignore
+    /// fn Counter(cx: Scope) -> Element {
+    ///     log::debug!("Counter rendered because of {:?}", cx.last_render_reason());
+    ///     // ...
+    /// }
+    /// ```
+    pub fn last_render_reason(&self) -> Option<RenderReason> {
+        self.render_reason.borrow().clone()
+    }
+
     /// Get a handle to the currently active bump arena for this Scope
     ///
     /// This is a bump memory allocator. Be careful using this directly since the contents will be wiped on the next render.
@@ -200,6 +241,11 @@ impl<'src> ScopeState {
         self.parent.map(|p| unsafe { &*p }.id)
     }
 
+    /// Get the components this scope was rendered under, from this one up to the root
+    pub fn component_stack(&self) -> ComponentStack {
+        ComponentStack::new(self)
+    }
+
     /// Get the ID of this Scope within this Dioxus [`crate::VirtualDom`].
     ///
     /// This ID is not unique across Dioxus [`crate::VirtualDom`]s or across time. IDs will be reused when components are unmounted.
@@ -222,7 +268,25 @@ impl<'src> ScopeState {
     /// ## Notice: you should prefer using [`Self::schedule_update_any`] and [`Self::scope_id`]
     pub fn schedule_update(&self) -> Arc<dyn Fn() + Send + Sync + 'static> {
         let (chan, id) = (self.tasks.sender.clone(), self.scope_id());
-        Arc::new(move || drop(chan.unbounded_send(SchedulerMsg::Immediate(id))))
+        Arc::new(move || drop(chan.unbounded_send(SchedulerMsg::Immediate(id, current_cause()))))
+    }
+
+    /// Create a subscription that schedules a future render for the reference component in the given priority lane
+    ///
+    /// Use a lower lane like [`Priority::Idle`] for background updates that shouldn't hold up rendering the response
+    /// to user input.
+    pub fn schedule_update_with_priority(
+        &self,
+        priority: Priority,
+    ) -> Arc<dyn Fn() + Send + Sync + 'static> {
+        let (chan, id) = (self.tasks.sender.clone(), self.scope_id());
+        Arc::new(move || {
+            drop(chan.unbounded_send(SchedulerMsg::ImmediateWithPriority(
+                id,
+                priority,
+                current_cause(),
+            )))
+        })
     }
 
     /// Schedule an update for any component given its [`ScopeId`].
@@ -233,7 +297,23 @@ impl<'src> ScopeState {
     pub fn schedule_update_any(&self) -> Arc<dyn Fn(ScopeId) + Send + Sync> {
         let chan = self.tasks.sender.clone();
         Arc::new(move |id| {
-            chan.unbounded_send(SchedulerMsg::Immediate(id)).unwrap();
+            chan.unbounded_send(SchedulerMsg::Immediate(id, current_cause()))
+                .unwrap();
+        })
+    }
+
+    /// Schedule an update for any component like [`Self::schedule_update_any`], blaming it on `reason`
+    ///
+    /// Hooks that keep state outside of the components, like shared state or atoms, use this so
+    /// [`Self::last_render_reason`] tells their writes apart from updates the component scheduled itself.
+    pub fn schedule_update_any_with_reason(
+        &self,
+        reason: RenderReason,
+    ) -> Arc<dyn Fn(ScopeId) + Send + Sync> {
+        let chan = self.tasks.sender.clone();
+        Arc::new(move |id| {
+            chan.unbounded_send(SchedulerMsg::Immediate(id, reason.clone()))
+                .unwrap();
         })
     }
 
@@ -246,9 +326,13 @@ impl<'src> ScopeState {
     ///
     /// `ScopeId` is not unique for the lifetime of the [`crate::VirtualDom`] - a [`ScopeId`] will be reused if a component is unmounted.
     pub fn needs_update_any(&self, id: ScopeId) {
+        self.needs_update_any_with_reason(id, current_cause());
+    }
+
+    fn needs_update_any_with_reason(&self, id: ScopeId, reason: RenderReason) {
         self.tasks
             .sender
-            .unbounded_send(SchedulerMsg::Immediate(id))
+            .unbounded_send(SchedulerMsg::Immediate(id, reason))
             .expect("Scheduler to exist if scope exists");
     }
 
@@ -263,17 +347,31 @@ impl<'src> ScopeState {
 
     /// Try to retrieve a shared state with type `T` from any parent scope.
     ///
-    /// Clones the state if it exists.
+    /// Clones the state if it exists. If the state comes from a parent, this scope is re-rendered whenever the parent
+    /// replaces it with [`Self::replace_context`].
     pub fn consume_context<T: 'static + Clone>(&self) -> Option<T> {
         if let Some(this_ctx) = self.has_context() {
             return Some(this_ctx);
         }
 
+        let type_id = TypeId::of::<T>();
         let mut search_parent = self.parent;
         while let Some(parent_ptr) = search_parent {
             // safety: all parent pointers are valid thanks to the bump arena
             let parent = unsafe { &*parent_ptr };
-            if let Some(shared) = parent.shared_contexts.borrow().get(&TypeId::of::<T>()) {
+            if let Some(shared) = parent.shared_contexts.borrow().get(&type_id) {
+                let subscribed = parent
+                    .context_subscribers
+                    .borrow_mut()
+                    .entry(type_id)
+                    .or_default()
+                    .insert(self.id);
+                if subscribed {
+                    self.context_subscriptions
+                        .borrow_mut()
+                        .push((parent_ptr, type_id));
+                }
+
                 return shared.downcast_ref::<T>().cloned();
             }
             search_parent = parent.parent;
@@ -281,6 +379,57 @@ impl<'src> ScopeState {
         None
     }
 
+    /// Retrieve a shared state with type `T` from any parent scope like [`Self::consume_context`]
+    ///
+    /// # Panics
+    ///
+    /// Panics with the name of the type if no parent provides the state.
+    pub fn expect_context<T: 'static + Clone>(&self) -> T {
+        match self.consume_context() {
+            Some(value) => value,
+            None => panic!(
+                "Could not find context `{}` in `{}` or any of its parents. Make sure a parent component provides it with `provide_context`{}",
+                std::any::type_name::<T>(),
+                self.name,
+                ComponentStack::debug_note(self)
+            ),
+        }
+    }
+
+    /// Replace a shared state provided by this scope, returning the old value
+    ///
+    /// Unlike reading the state again, replacing it notifies every scope below that consumed it with
+    /// [`Self::consume_context`]. They are all marked dirty and pick up the new value on their next render.
+    pub fn replace_context<T: 'static + Clone>(&self, value: T) -> Option<T> {
+        let type_id = TypeId::of::<T>();
+
+        let old = self
+            .shared_contexts
+            .borrow_mut()
+            .insert(type_id, Box::new(value))
+            .and_then(|old| old.downcast::<T>().ok())
+            .map(|old| *old);
+
+        if let Some(subscribers) = self.context_subscribers.borrow().get(&type_id) {
+            for id in subscribers {
+                self.needs_update_any_with_reason(*id, RenderReason::Context);
+            }
+        }
+
+        old
+    }
+
+    /// Stop listening for replacements of the contexts this scope consumed
+    pub(crate) fn unsubscribe_from_contexts(&self) {
+        for (provider, type_id) in self.context_subscriptions.borrow_mut().drain(..) {
+            // safety: providers are ancestors of this scope, so they outlive it
+            let provider = unsafe { &*provider };
+            if let Some(subscribers) = provider.context_subscribers.borrow_mut().get_mut(&type_id) {
+                subscribers.remove(&self.id);
+            }
+        }
+    }
+
     /// Expose state to children further down the [`crate::VirtualDom`] Tree. Does not require `clone` on the context,
     /// though we do recommend it.
     ///
@@ -318,35 +467,63 @@ impl<'src> ScopeState {
     }
 
     /// Pushes the future onto the poll queue to be polled after the component renders.
+    ///
+    /// The future is dropped when this component is unmounted.
     pub fn push_future(&self, fut: impl Future<Output = ()> + 'static) -> TaskId {
-        self.tasks.spawn(self.id, fut)
+        let id = self.tasks.spawn(self.id, fut);
+        self.spawned_tasks.borrow_mut().insert(id);
+        id
     }
 
-    /// Spawns the future but does not return the [`TaskId`]
-    pub fn spawn(&self, fut: impl Future<Output = ()> + 'static) {
-        self.push_future(fut);
+    /// Spawn a future owned by this component, returning a handle that can cancel it
+    ///
+    /// The future is dropped when this component is unmounted.
+    pub fn spawn(&self, fut: impl Future<Output = ()> + 'static) -> TaskHandle {
+        let id = self.push_future(fut);
+        self.tasks.handle(id)
     }
 
     /// Spawn a future that Dioxus won't clean up when this component is unmounted
     ///
     /// This is good for tasks that need to be run after the component has been dropped.
-    pub fn spawn_forever(&self, fut: impl Future<Output = ()> + 'static) -> TaskId {
+    pub fn spawn_forever(&self, fut: impl Future<Output = ()> + 'static) -> TaskHandle {
         // The root scope will never be unmounted so we can just add the task at the top of the app
         let id = self.tasks.spawn(ScopeId(0), fut);
+        self.tasks.handle(id)
+    }
 
-        // wake up the scheduler if it is sleeping
-        self.tasks
-            .sender
-            .unbounded_send(SchedulerMsg::TaskNotified(id))
-            .expect("Scheduler should exist");
+    /// Run a callback once the edits from this render have been applied by the renderer
+    ///
+    /// Callbacks pushed during the first render run once the component is in the dom. Callbacks pushed during a later
+    /// render run after the edits of that render are applied. Either way, each callback runs exactly once.
+    ///
+    /// Children run their callbacks before their parents.
+    pub fn push_on_mount(&self, f: impl FnOnce() + 'static) {
+        self.on_mount.borrow_mut().push(Box::new(f));
+    }
 
-        id
+    /// Run a callback right before this component is torn down
+    ///
+    /// The callback runs after the children of the component have been dropped, but while the hooks of this component are
+    /// still alive. Callbacks run in the order they were pushed.
+    pub fn push_on_drop(&self, f: impl FnOnce() + 'static) {
+        self.on_drop.borrow_mut().push(Box::new(f));
+    }
+
+    /// Keep the keyed children this component stops rendering in the dom until their exit transition finishes
+    ///
+    /// Instead of being removed right away, the root elements of a removed keyed child get a `data-exiting` attribute
+    /// that CSS can animate. They stay in place and stop receiving events until the renderer reports the transition
+    /// finished with [`crate::VirtualDom::finish_exit`], usually from `ontransitionend` or after a timeout.
+    pub fn set_exit_transitions(&self, enabled: bool) {
+        self.exit_transitions.set(enabled);
     }
 
     /// Informs the scheduler that this task is no longer needed and should be removed.
     ///
     /// This drops the task immediately.
     pub fn remove_future(&self, id: TaskId) {
+        self.spawned_tasks.borrow_mut().remove(&id);
         self.tasks.remove(id);
     }
 
@@ -368,13 +545,21 @@ impl<'src> ScopeState {
 
         let mut listeners = self.attributes_to_drop.borrow_mut();
         for attr in element.dynamic_attrs {
-            match attr.value {
-                AttributeValue::Any(_) | AttributeValue::Listener(_) => {
-                    let unbounded = unsafe { std::mem::transmute(attr as *const Attribute) };
-                    listeners.push(unbounded);
+            // Spread attributes are allocated in the bump too, so their values need to be dropped the same way
+            let spread = match attr.value {
+                AttributeValue::Spread(spread) => spread,
+                _ => std::slice::from_ref(attr),
+            };
+
+            for attr in spread {
+                match attr.value {
+                    AttributeValue::Any(_) | AttributeValue::Listener(_) => {
+                        let unbounded = unsafe { std::mem::transmute(attr as *const Attribute) };
+                        listeners.push(unbounded);
+                    }
+
+                    _ => (),
                 }
-
-                _ => (),
             }
         }
 
@@ -394,6 +579,7 @@ impl<'src> ScopeState {
         DynamicNode::Text(VText {
             value: self.raw_text(args),
             id: Default::default(),
+            live: None,
         })
     }
 
@@ -425,13 +611,59 @@ impl<'src> ScopeState {
         namespace: Option<&'static str>,
         volatile: bool,
     ) -> Attribute<'src> {
+        let (value, live) = into_live_value(value, self.bump());
+        if let Some(live) = live {
+            self.live.borrow_mut().insert(live);
+        }
+
         Attribute {
             name,
             namespace,
             volatile,
             mounted_element: Default::default(),
-            value: value.into_value(self.bump()),
+            value,
+            live,
+        }
+    }
+
+    /// Create the value of the attributes spread onto an element with `..attributes` in rsx
+    ///
+    /// The attributes in `written` are left out, so attributes written on the element take precedence over the ones
+    /// that are spread.
+    ///
+    /// # Panics
+    ///
+    /// Spread attributes can't be listeners, since events are only dispatched to listeners written in rsx.
+    pub fn spread<A: IntoAttribute<'src>>(
+        &'src self,
+        attributes: impl IntoIterator<Item = A>,
+        written: &[&str],
+    ) -> AttributeValue<'src> {
+        let bump = self.bump();
+        let mut spread = bumpalo::collections::Vec::new_in(bump);
+
+        for attribute in attributes {
+            let attribute = attribute.into_attribute(bump);
+            if written.contains(&attribute.name) {
+                continue;
+            }
+
+            if let Some(live) = attribute.live {
+                self.live.borrow_mut().insert(live);
+            }
+
+            assert!(
+                !matches!(
+                    attribute.value,
+                    AttributeValue::Listener(_) | AttributeValue::Spread(_)
+                ),
+                "The attribute {} can't be spread onto an element",
+                attribute.name
+            );
+            spread.push(attribute);
         }
+
+        AttributeValue::Spread(spread.into_bump_slice())
     }
 
     /// Create a new [`DynamicNode::Component`] variant
@@ -457,7 +689,7 @@ impl<'src> ScopeState {
     where
         P: Properties + 'src,
     {
-        let vcomp = VProps::new(component, P::memoize, props);
+        let vcomp = VProps::new(component, P::memoize, P::debug_props, props);
 
         // cast off the lifetime of the render return
         let as_dyn: Box<dyn AnyProps<'src> + '_> = Box::new(vcomp);
@@ -496,6 +728,8 @@ impl<'src> ScopeState {
                 if let Ok(data) = event.data.downcast::<T>() {
                     callback(Event {
                         propagates: event.propagates,
+                        propagates_immediately: event.propagates_immediately,
+                        default_prevented: event.default_prevented,
                         data,
                     })
                 }
@@ -520,9 +754,30 @@ impl<'src> ScopeState {
     ///
     /// The error doesn't need to implement Error or any specific traits since the boundary
     /// itself will downcast the error into a trait object.
+    ///
+    /// If the only boundary above this scope is the default one at the root, the error goes to the handler set with
+    /// [`crate::VirtualDom::set_error_handler`] instead.
     pub fn throw(&self, error: impl Debug + 'static) -> Option<()> {
-        if let Some(cx) = self.consume_context::<Rc<ErrorBoundary>>() {
-            cx.insert_error(self.scope_id(), Box::new(error));
+        let boundary = self.consume_context::<Rc<ErrorBoundary>>();
+
+        let handler = match &boundary {
+            Some(boundary) if !boundary.is_root_default() => None,
+            _ => self.tasks.error_handler.borrow().clone(),
+        };
+
+        match (handler, boundary) {
+            (Some(handler), _) => {
+                let scope = self.scope_id();
+                handler(
+                    scope,
+                    CapturedError {
+                        error: Box::new(error),
+                        scope,
+                    },
+                );
+            }
+            (None, Some(boundary)) => boundary.insert_error(self.scope_id(), Box::new(error)),
+            (None, None) => {}
         }
 
         // Always return none during a throw
@@ -548,10 +803,26 @@ impl<'src> ScopeState {
     #[allow(clippy::mut_from_ref)]
     pub fn use_hook<State: 'static>(&self, initializer: impl FnOnce() -> State) -> &mut State {
         let cur_hook = self.hook_idx.get();
-        let mut hook_list = self.hook_list.try_borrow_mut().expect("The hook list is already borrowed: This error is likely caused by trying to use a hook inside a hook which violates the rules of hooks.");
+
+        // Hot reloaded code may call different hooks from here on, so they start over instead of panicking
+        if self.reloading.get().is_some() {
+            let changed = self
+                .hook_list
+                .borrow()
+                .get(cur_hook)
+                .map_or(false, |hook| unsafe { !(**hook).is::<State>() });
+            if changed {
+                self.drop_hooks_from(cur_hook);
+            }
+        }
+
+        let mut hook_list = self.hook_list.try_borrow_mut().unwrap_or_else(|_| panic!("The hook list is already borrowed: This error is likely caused by trying to use a hook inside a hook which violates the rules of hooks.{}", ComponentStack::debug_note(self)));
 
         if cur_hook >= hook_list.len() {
             hook_list.push(self.hook_arena.alloc(initializer()));
+            self.hook_names
+                .borrow_mut()
+                .push(std::any::type_name::<State>());
         }
 
         hook_list
@@ -561,14 +832,26 @@ impl<'src> ScopeState {
                 let raw_box = unsafe { &mut **inn };
                 raw_box.downcast_mut::<State>()
             })
-            .expect(
-                r###"
+            .unwrap_or_else(|| {
+                panic!(
+                    r###"
                 Unable to retrieve the hook that was initialized at this index.
                 Consult the `rules of hooks` to understand how to use hooks properly.
 
                 You likely used the hook in a conditional. Hooks rely on consistent ordering between renders.
                 Functions prefixed with "use" should never be called conditionally.
-                "###,
-            )
+                {}"###,
+                    ComponentStack::debug_note(self)
+                )
+            })
+    }
+
+    // Drop the hooks from an index on, in the order they were created
+    pub(crate) fn drop_hooks_from(&self, idx: usize) {
+        let dropped: Vec<_> = self.hook_list.borrow_mut().drain(idx..).collect();
+        self.hook_names.borrow_mut().truncate(idx);
+        for hook in dropped {
+            drop(unsafe { BumpBox::from_raw(hook) });
+        }
     }
 }
@@ -0,0 +1,253 @@
+use crate::{
+    arena::ElementId, innerlude::DynamicNode, mutations::Mutation, AttributeValue,
+    TemplateAttribute, TemplateNode, VirtualDom,
+};
+
+/// A change to the accessibility information of an element, derived from its html tag and attributes
+///
+/// Only produced after [`VirtualDom::track_semantics`] is turned on. The updates come with the [`crate::Mutations`]
+/// that caused them, so renderers like desktop or the TUI can keep an accessibility tree next to their real tree
+/// without reading the attributes back themselves.
+///
+/// Only elements the renderer has been given an id for are described. Removing an element removes the semantics of
+/// everything below it.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SemanticsUpdate {
+    /// The element was created or its attributes changed
+    Set {
+        /// The element being described
+        id: ElementId,
+
+        /// The `role` attribute of the element, or the role implied by its tag
+        role: Option<String>,
+
+        /// The accessible name of the element: its `aria-label`, `alt` or `title`, falling back to the text directly
+        /// inside of it
+        name: Option<String>,
+
+        /// The states of the element, IE `disabled` or the `aria-*` attributes other than `aria-label`
+        ///
+        /// Aria attributes are listed without their `aria-` prefix.
+        states: Vec<(String, String)>,
+    },
+
+    /// The element was removed from the tree
+    Remove {
+        /// The element that was removed
+        id: ElementId,
+    },
+}
+
+impl SemanticsUpdate {
+    /// The element this update describes
+    pub fn id(&self) -> ElementId {
+        match self {
+            SemanticsUpdate::Set { id, .. } | SemanticsUpdate::Remove { id } => *id,
+        }
+    }
+
+    fn describes_anything(&self) -> bool {
+        !matches!(
+            self,
+            SemanticsUpdate::Set { role: None, name: None, states, .. } if states.is_empty()
+        )
+    }
+
+    pub(crate) fn id_mut(&mut self) -> &mut ElementId {
+        match self {
+            SemanticsUpdate::Set { id, .. } | SemanticsUpdate::Remove { id } => id,
+        }
+    }
+}
+
+/// Attributes that describe the state of an element without the `aria-` prefix
+const STATE_ATTRIBUTES: &[&str] = &[
+    "checked", "disabled", "hidden", "readonly", "required", "selected", "open",
+];
+
+impl VirtualDom {
+    /// Start or stop annotating mutations with [`SemanticsUpdate`]s
+    ///
+    /// The updates are collected into [`crate::Mutations::semantics`], or handed to
+    /// [`crate::WriteMutations::update_semantics`] when writing edits out directly.
+    pub fn track_semantics(&mut self, track: bool) {
+        self.track_semantics = track;
+    }
+
+    /// Describe every element touched by the pending mutations
+    pub(crate) fn collect_semantics(&mut self) {
+        if !self.track_semantics {
+            return;
+        }
+
+        let mut updates = Vec::new();
+
+        for edit in self.mutations.edits.iter() {
+            let update = match *edit {
+                Mutation::Remove { id } | Mutation::ReplaceWith { id, .. } => {
+                    Some(SemanticsUpdate::Remove { id })
+                }
+
+                // Changing an attribute can take away the semantics of an element, so the renderer always hears about it
+                Mutation::SetAttribute { id, .. } => self.element_semantics(id),
+
+                Mutation::LoadTemplate { id, .. } | Mutation::AssignId { id, .. } => self
+                    .element_semantics(id)
+                    .filter(SemanticsUpdate::describes_anything),
+
+                // Text changes the name of the element it's in, if the renderer knows about that element
+                Mutation::HydrateText { id, .. } | Mutation::SetText { id, .. } => self
+                    .text_parent(id)
+                    .and_then(|parent| self.element_semantics(parent)),
+
+                _ => None,
+            };
+
+            // Only the latest description of an element matters
+            if let Some(update) = update {
+                updates.retain(|old: &SemanticsUpdate| {
+                    old.id() != update.id() || matches!(old, SemanticsUpdate::Remove { .. })
+                });
+                updates.push(update);
+            }
+        }
+
+        self.mutations.semantics.extend(updates);
+    }
+
+    fn element_semantics(&self, id: ElementId) -> Option<SemanticsUpdate> {
+        let el_ref = self.elements.get(id.0)?;
+
+        // safety: we maintain references of all vnodes in the element slab
+        let vnode = unsafe { el_ref.template?.as_ref() };
+
+        let (tag, attrs, children) = match el_ref.path.node_in(&vnode.template.get())? {
+            TemplateNode::Element {
+                tag,
+                attrs,
+                children,
+                ..
+            } => (*tag, *attrs, *children),
+            _ => return None,
+        };
+
+        let attributes = attrs.iter().filter_map(|attr| match attr {
+            TemplateAttribute::Static { name, value, .. } => Some((*name, value.to_string())),
+            TemplateAttribute::Dynamic { id } => {
+                let attr = &vnode.dynamic_attrs[*id];
+                let value = match &attr.value {
+                    AttributeValue::Text(text) => text.to_string(),
+                    AttributeValue::Float(f) => f.to_string(),
+                    AttributeValue::Int(i) => i.to_string(),
+                    AttributeValue::Bool(b) => b.to_string(),
+                    _ => return None,
+                };
+                Some((attr.name, value))
+            }
+        });
+
+        let mut role = None;
+        let mut label = None;
+        let mut input_type = None;
+        let mut states = Vec::new();
+
+        for (name, value) in attributes {
+            match name {
+                "role" => role = Some(value),
+                "aria-label" => label = Some(value),
+                "alt" | "title" if label.is_none() => label = Some(value),
+                "type" => input_type = Some(value),
+                _ => match name.strip_prefix("aria-") {
+                    Some(state) => states.push((state.to_string(), value)),
+                    None if STATE_ATTRIBUTES.contains(&name) => {
+                        states.push((name.to_string(), value))
+                    }
+                    None => {}
+                },
+            }
+        }
+
+        let role = role.or_else(|| implicit_role(tag, input_type.as_deref()).map(String::from));
+
+        let name = label.or_else(|| {
+            let text = children
+                .iter()
+                .filter_map(|child| match child {
+                    TemplateNode::Text { text } => Some(*text),
+                    TemplateNode::DynamicText { id } | TemplateNode::Dynamic { id } => {
+                        match &vnode.dynamic_nodes[*id] {
+                            DynamicNode::Text(text) => Some(text.value),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                })
+                .collect::<String>();
+
+            let text = text.trim();
+            (!text.is_empty()).then(|| text.to_string())
+        });
+
+        Some(SemanticsUpdate::Set {
+            id,
+            role,
+            name,
+            states,
+        })
+    }
+
+    /// Find the element a text node lives in, if that element has an id
+    fn text_parent(&self, text: ElementId) -> Option<ElementId> {
+        let el_ref = self.elements.get(text.0)?;
+        let template = el_ref.template?;
+        let parent = el_ref.path.parent()?;
+
+        self.elements
+            .iter()
+            .find(|(_, other)| {
+                other.template == Some(template) && other.path.as_bytes() == parent.as_bytes()
+            })
+            .map(|(id, _)| ElementId(id))
+    }
+}
+
+/// The role an element has without an explicit `role` attribute
+///
+/// `input_type` is the `type` attribute of the element, which decides the role of an `input`.
+pub fn implicit_role(tag: &str, input_type: Option<&str>) -> Option<&'static str> {
+    Some(match tag {
+        "a" => "link",
+        "article" => "article",
+        "aside" => "complementary",
+        "button" => "button",
+        "dialog" => "dialog",
+        "footer" => "contentinfo",
+        "form" => "form",
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+        "header" => "banner",
+        "img" => "img",
+        "input" => match input_type.unwrap_or("text") {
+            "button" | "submit" | "reset" => "button",
+            "checkbox" => "checkbox",
+            "radio" => "radio",
+            "range" => "slider",
+            "search" => "searchbox",
+            "hidden" => return None,
+            _ => "textbox",
+        },
+        "li" => "listitem",
+        "main" => "main",
+        "nav" => "navigation",
+        "ol" | "ul" => "list",
+        "option" => "option",
+        "progress" => "progressbar",
+        "select" => "combobox",
+        "table" => "table",
+        "td" => "cell",
+        "textarea" => "textbox",
+        "th" => "columnheader",
+        "tr" => "row",
+        _ => return None,
+    })
+}
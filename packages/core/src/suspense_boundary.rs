@@ -0,0 +1,231 @@
+use crate::any_props::VProps;
+use crate::innerlude::*;
+use std::rc::Rc;
+
+/// A boundary that shows a fallback while any of its children are suspended
+///
+/// ## Details
+///
+/// Any async component rendered below the boundary will suspend the whole boundary. Until every suspended component
+/// has resolved, the fallback is rendered in place of the children. Once the last component resolves, the children
+/// are swapped in a single edit.
+///
+/// The fallback renders as a component of its own, so it can use hooks and keeps its state while the boundary waits.
+/// If the boundary renders again before its children were ever mounted, the children are created again from scratch.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// rsx!{
+///     SuspenseBoundary {
+///         fallback: |cx| cx.render(rsx!("Loading...")),
+///         AsyncProfile {}
+///     }
+/// }
+/// ```
+///
+/// Without a fallback, the boundary leaves an empty placeholder in the DOM while suspended.
+#[allow(non_upper_case_globals, non_snake_case)]
+pub fn SuspenseBoundary<'a>(cx: Scope<'a, SuspenseBoundaryProps<'a>>) -> Element {
+    let boundary = cx.use_hook(|| cx.provide_context(Rc::new(SuspenseContext::new(cx.scope_id()))));
+
+    // The VirtualDom mounts the fallback itself if the children suspend while being created
+    *boundary.fallback.borrow_mut() = cx.props.fallback.clone();
+
+    let children = cx.props.children.as_ref()?;
+    Some(VNode {
+        key: children.key,
+        parent: children.parent,
+        template: children.template.clone(),
+        root_ids: children.root_ids.clone(),
+        dynamic_nodes: children.dynamic_nodes,
+        dynamic_attrs: children.dynamic_attrs,
+    })
+}
+
+/// The props of the component rendering the fallback of a boundary
+struct SuspenseFallbackProps {
+    boundary: Rc<SuspenseContext>,
+}
+
+/// Render the latest fallback the boundary was given
+#[allow(non_snake_case)]
+fn SuspenseFallback(cx: Scope<SuspenseFallbackProps>) -> Element {
+    let fallback = cx.props.boundary.fallback.borrow().clone()?;
+    fallback(cx.scope.bump().alloc(Scoped {
+        scope: cx.scope,
+        props: &(),
+    }))
+}
+
+/// The props for the [`SuspenseBoundary`] component
+pub struct SuspenseBoundaryProps<'a> {
+    fallback: Option<Rc<dyn Fn(Scope) -> Element>>,
+    children: Element<'a>,
+}
+
+/// The builder for [`SuspenseBoundaryProps`]
+pub struct SuspenseBoundaryBuilder<'a> {
+    fallback: Option<Rc<dyn Fn(Scope) -> Element>>,
+    children: Element<'a>,
+}
+
+impl<'a> SuspenseBoundaryBuilder<'a> {
+    /// Set the component to render while the children are suspended
+    pub fn fallback(mut self, fallback: impl Fn(Scope) -> Element + 'static) -> Self {
+        self.fallback = Some(Rc::new(fallback));
+        self
+    }
+
+    /// Set the children that might suspend
+    pub fn children(mut self, children: Element<'a>) -> Self {
+        self.children = children;
+        self
+    }
+
+    /// Finish building the props
+    pub fn build(self) -> SuspenseBoundaryProps<'a> {
+        SuspenseBoundaryProps {
+            fallback: self.fallback,
+            children: self.children,
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn __dioxus_fields(&self) -> BuiltinFields {
+        BuiltinFields
+    }
+}
+
+impl<'a> Properties for SuspenseBoundaryProps<'a> {
+    type Builder = SuspenseBoundaryBuilder<'a>;
+    const IS_STATIC: bool = false;
+    fn builder() -> Self::Builder {
+        SuspenseBoundaryBuilder {
+            fallback: None,
+            children: None,
+        }
+    }
+    unsafe fn memoize(&self, _other: &Self) -> bool {
+        false
+    }
+}
+
+impl VirtualDom {
+    /// The boundary of the scope, if the scope is a boundary whose children are still waiting to be mounted
+    pub(crate) fn mounting_boundary(&self, scope: ScopeId) -> Option<Rc<SuspenseContext>> {
+        self.scopes[scope.0]
+            .has_context::<Rc<SuspenseContext>>()
+            .filter(|boundary| boundary.is_mounting())
+    }
+
+    /// The scope whose nodes are in the dom in place of this one
+    ///
+    /// That's the fallback of a boundary whose children are still being mounted, and the scope itself otherwise.
+    pub(crate) fn mounted_scope(&self, scope: ScopeId) -> ScopeId {
+        self.mounting_boundary(scope)
+            .and_then(|boundary| boundary.fallback_scope.get())
+            .unwrap_or(scope)
+    }
+
+    /// The placeholder standing in for the children of a boundary without a fallback while they're being mounted
+    pub(crate) fn suspense_placeholder(&self, scope: ScopeId) -> Option<ElementId> {
+        self.mounting_boundary(scope)?.placeholder.get()
+    }
+
+    /// Create the fallback of a boundary whose children just suspended, leaving its nodes on the stack
+    ///
+    /// The fallback is a sibling of the boundary rather than its child, so it isn't suspended along with the children.
+    pub(crate) fn create_fallback(&mut self, boundary: &Rc<SuspenseContext>) -> usize {
+        let props = VProps::new(
+            SuspenseFallback,
+            |_, _| false,
+            |_| None,
+            SuspenseFallbackProps {
+                boundary: boundary.clone(),
+            },
+        );
+        let scope = self.new_scope(Box::new(props), "SuspenseFallback").id;
+        boundary.fallback_scope.set(Some(scope));
+
+        match unsafe { self.run_scope(scope).extend_lifetime_ref() } {
+            RenderReturn::Ready(node) => self.create_scope(scope, node),
+            RenderReturn::Aborted(placeholder) => {
+                let id = self.next_null();
+                placeholder.id.set(Some(id));
+                self.mutations.push(Mutation::CreatePlaceholder { id });
+                1
+            }
+            RenderReturn::Pending(_) => unreachable!("fallbacks are never async"),
+        }
+    }
+
+    /// Create the children of a boundary that rendered again before they were ever mounted
+    ///
+    /// They only exist in the edits the boundary is holding back, so they're thrown away along with those edits and
+    /// created again from the new props. The fallback stays in the dom and is diffed like any other child.
+    pub(crate) fn remount_boundary(&mut self, scope: ScopeId, boundary: &SuspenseContext) {
+        self.scope_stack.push(scope);
+        self.start_diff_stats();
+
+        let (old, new) = unsafe {
+            let scope = &self.scopes[scope.0];
+            (
+                scope.previous_frame().try_load_node(),
+                scope
+                    .current_frame()
+                    .try_load_node()
+                    .expect("Call rebuild before diffing"),
+            )
+        };
+
+        match old {
+            Some(RenderReturn::Ready(old)) => self.remove_node(old, false),
+            Some(RenderReturn::Aborted(placeholder)) => {
+                if let Some(id) = placeholder.id.take() {
+                    self.reclaim(id);
+                }
+            }
+            _ => {}
+        }
+        boundary.mutations.borrow_mut().edits.clear();
+        boundary.resolved.borrow_mut().edits.clear();
+        boundary.waiting_on.borrow_mut().clear();
+        self.finished_fibers.retain(|id| *id != scope);
+
+        // Create the new children into the held back edits, keeping the leaves collected outside of the boundary
+        let outer_leaves = std::mem::take(&mut self.collected_leaves);
+        std::mem::swap(&mut self.mutations, &mut *boundary.mutations.borrow_mut());
+
+        let created = match new {
+            RenderReturn::Ready(new) => self.create(new),
+            RenderReturn::Aborted(placeholder) => {
+                let id = self.next_null();
+                placeholder.id.set(Some(id));
+                self.mutations.push(Mutation::CreatePlaceholder { id });
+                1
+            }
+            RenderReturn::Pending(_) => unreachable!("boundaries are never async"),
+        };
+
+        std::mem::swap(&mut self.mutations, &mut *boundary.mutations.borrow_mut());
+        boundary.created_on_stack.set(created);
+        boundary
+            .waiting_on
+            .borrow_mut()
+            .extend(std::mem::replace(&mut self.collected_leaves, outer_leaves));
+
+        if boundary.waiting_on.borrow().is_empty() {
+            self.finished_fibers.push(scope);
+        }
+
+        // The fallback renders again with whatever the boundary was just given
+        if let Some(fallback) = boundary.fallback_scope.get() {
+            self.run_scope(fallback);
+            self.diff_scope(fallback);
+        }
+
+        self.finish_diff_stats(scope);
+        self.scope_stack.pop();
+    }
+}
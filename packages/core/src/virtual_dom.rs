@@ -4,19 +4,31 @@
 
 use crate::{
     any_props::VProps,
-    arena::{ElementId, ElementRef},
-    innerlude::{DirtyScope, ErrorBoundary, Mutations, Scheduler, SchedulerMsg},
+    arena::{ElementId, ElementOrigin, ElementRef, Exit},
+    innerlude::{
+        current_cause, default_clock, with_cause, CapturedError, CapturedPanic, DirtyScope,
+        DynamicNode, ErrorBoundary, EventDispatch, ListenerOptions, LiveId, MemoryBudget,
+        Mutations, Priority, Recording, RenderReason, RenderStats, Scheduler, SchedulerMsg,
+        Spawner, VNode, VPlaceholder, WriteMutations,
+    },
+    instrument::span,
     mutations::Mutation,
     nodes::RenderReturn,
     nodes::{Template, TemplateId},
     scheduler::SuspenseId,
     scopes::{ScopeId, ScopeState},
-    AttributeValue, Element, Event, Scope, SuspenseContext,
+    AttributeValue, Component, Element, Event, Scope, SuspenseContext,
 };
+use bumpalo::Bump;
 use futures_util::{pin_mut, StreamExt};
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use slab::Slab;
-use std::{any::Any, borrow::BorrowMut, cell::Cell, collections::BTreeSet, future::Future, rc::Rc};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use std::{
+    any::Any, borrow::BorrowMut, cell::Cell, collections::BTreeSet, future::Future,
+    panic::AssertUnwindSafe, rc::Rc,
+};
 
 /// A virtual node system that progresses user events and diffs UI trees.
 ///
@@ -175,7 +187,7 @@ use std::{any::Any, borrow::BorrowMut, cell::Cell, collections::BTreeSet, future
 /// }
 /// ```
 pub struct VirtualDom {
-    // Maps a template path to a map of byteindexes to templates
+    // Maps the path of an rsx! call to the templates of the call by their index
     pub(crate) templates: FxHashMap<TemplateId, FxHashMap<usize, Template<'static>>>,
     pub(crate) scopes: Slab<Box<ScopeState>>,
     pub(crate) dirty_scopes: BTreeSet<DirtyScope>,
@@ -184,6 +196,26 @@ pub struct VirtualDom {
     // Every element is actually a dual reference - one to the template and the other to the dynamic node in that template
     pub(crate) elements: Slab<ElementRef>,
 
+    // The generation of each slot in the element slab, bumped whenever the slot is reclaimed
+    pub(crate) element_generations: Vec<u32>,
+
+    // Where each slot of the element arena was last allocated, if the app asked to track it
+    pub(crate) element_origins: Option<Vec<Option<ElementOrigin>>>,
+
+    // The scopes displaying each live value, and the values sent for them since the last render
+    pub(crate) live_scopes: FxHashMap<LiveId, FxHashSet<ScopeId>>,
+    pub(crate) live_updates: FxHashMap<LiveId, String>,
+
+    // The text of the live values patched in the last render, kept until the renderer applied it
+    pub(crate) live_text: Bump,
+
+    // Text nodes and attributes patched with a live value since their scope last rendered, so their old value can't be
+    // trusted while diffing
+    pub(crate) patched_live: FxHashSet<(ElementId, LiveId)>,
+
+    // Listeners that only run once and already ran, by their element and the index of their dynamic attribute
+    pub(crate) fired_listeners: FxHashSet<(ElementId, usize)>,
+
     // While diffing we need some sort of way of breaking off a stream of suspended mutations.
     pub(crate) scope_stack: Vec<ScopeId>,
     pub(crate) collected_leaves: Vec<SuspenseId>,
@@ -192,9 +224,46 @@ pub struct VirtualDom {
     // When "render_with_deadline" is called, we pop the stack and return the mutations
     pub(crate) finished_fibers: Vec<ScopeId>,
 
+    // Scopes that rendered with mount callbacks, in the order they rendered
+    pub(crate) pending_mounts: Vec<ScopeId>,
+
     pub(crate) rx: futures_channel::mpsc::UnboundedReceiver<SchedulerMsg>,
 
     pub(crate) mutations: Mutations<'static>,
+
+    // The number of listeners currently live across every scope, and the most that have ever been live at once
+    pub(crate) listener_count: Cell<usize>,
+    pub(crate) peak_listener_count: Cell<usize>,
+
+    // Called whenever the last element other than the root is reclaimed
+    pub(crate) on_arena_empty: Option<Box<dyn FnMut()>>,
+
+    // How much memory each scope's frames may keep around between renders
+    pub(crate) memory_budget: Option<MemoryBudget>,
+    // Every batch of mutations and the events that caused them, if the app asked to record them
+    pub(crate) recording: Option<Recording>,
+
+    // The renderer's element standing in for the root, which every element id in the edits is shifted by
+    pub(crate) root_element: ElementId,
+
+    // Whether the mutations are annotated with how the accessibility information of each element changed
+    pub(crate) track_semantics: bool,
+
+    // The code every hot reloaded component runs now, by the code it ran before
+    pub(crate) reloaded_components: FxHashMap<*const (), *const ()>,
+
+    // Keyed children that were removed but stay mounted until their exit transition finishes
+    pub(crate) exits: Vec<Exit>,
+
+    // Set while removing a keyed child that's exiting, collecting the elements that would have been reclaimed
+    pub(crate) parked_elements: Option<Vec<ElementId>>,
+
+    // How each component rendered, if the app asked to track it, and the clock its diffs are timed with
+    pub(crate) render_stats: Option<RenderStats>,
+    pub(crate) render_clock: fn() -> Duration,
+
+    // The dispatch policies the app set, overriding the default of their event
+    pub(crate) event_dispatch: FxHashMap<String, EventDispatch>,
 }
 
 impl VirtualDom {
@@ -260,15 +329,41 @@ impl VirtualDom {
             templates: Default::default(),
             scopes: Slab::default(),
             elements: Default::default(),
+            element_generations: Default::default(),
+            live_scopes: Default::default(),
+            live_updates: Default::default(),
+            live_text: Bump::new(),
+            patched_live: Default::default(),
+            fired_listeners: Default::default(),
+            element_origins: None,
             scope_stack: Vec::new(),
             dirty_scopes: BTreeSet::new(),
             collected_leaves: Vec::new(),
             finished_fibers: Vec::new(),
+            pending_mounts: Vec::new(),
             mutations: Mutations::default(),
+            listener_count: Cell::new(0),
+            peak_listener_count: Cell::new(0),
+            on_arena_empty: None,
+            memory_budget: None,
+            recording: None,
+            root_element: ElementId(0),
+            track_semantics: false,
+            reloaded_components: Default::default(),
+            exits: Vec::new(),
+            parked_elements: None,
+            render_stats: None,
+            render_clock: default_clock,
+            event_dispatch: FxHashMap::default(),
         };
 
         let root = dom.new_scope(
-            Box::new(VProps::new(root, |_, _| unreachable!(), root_props)),
+            Box::new(VProps::new(
+                root,
+                |_, _| unreachable!(),
+                |_| None,
+                root_props,
+            )),
             "app",
         );
 
@@ -283,6 +378,7 @@ impl VirtualDom {
 
         // the root element is always given element ID 0 since it's the container for the entire tree
         dom.elements.insert(ElementRef::none());
+        dom.element_generations.push(0);
 
         dom
     }
@@ -309,14 +405,112 @@ impl VirtualDom {
         self
     }
 
+    /// Build the virtualdom to mount under an element the renderer already has, instead of the root of a fresh tree
+    ///
+    /// Every element id in the edits is shifted by `root`, so `root` itself stands in for [`ElementId(0)`](ElementId)
+    /// and the rest of the tree is given the ids after it. This lets several VirtualDoms share one renderer, as long
+    /// as each is given a root far enough from the others that their ids don't overlap.
+    ///
+    /// Events are handled with the shifted ids too. Events for elements before `root` are ignored.
+    ///
+    /// ```rust, ignore
+    /// // The renderer binds its existing element to id 10_000 before applying any edits
+    /// let mut dom = VirtualDom::new(app).with_root_element(ElementId(10_000));
+    /// ```
+    pub fn with_root_element(mut self, root: ElementId) -> Self {
+        self.root_element = root;
+        self
+    }
+
+    /// Run the tasks components spawn on another executor, instead of polling them in [`VirtualDom::wait_for_work`]
+    ///
+    /// The VirtualDom still owns the tasks: they are cancelled when their component is dropped and their
+    /// [`TaskHandle`](crate::TaskHandle)s work as usual. Only tasks spawned after this is set use the spawner.
+    ///
+    /// ```rust, ignore
+    /// let mut dom = VirtualDom::new(app).with_spawner(|task| {
+    ///     executor.spawn_local(task);
+    /// });
+    /// ```
+    pub fn with_spawner(self, spawner: impl Spawner + 'static) -> Self {
+        *self.scheduler.spawner.borrow_mut() = Some(Rc::new(spawner));
+        self
+    }
+
     /// Manually mark a scope as requiring a re-render
     ///
     /// Whenever the VirtualDom "works", it will re-render this scope
     pub fn mark_dirty(&mut self, id: ScopeId) {
-        if let Some(scope) = self.scopes.get(id.0) {
-            let height = scope.height;
-            self.dirty_scopes.insert(DirtyScope { height, id });
+        self.mark_dirty_with_priority(id, Priority::UserInput);
+    }
+
+    /// Mark a scope as dirty in the given priority lane
+    ///
+    /// A scope can only be in one lane at a time. If it's already dirty in a more urgent lane, then it stays there.
+    pub fn mark_dirty_with_priority(&mut self, id: ScopeId, priority: Priority) {
+        let height = match self.scopes.get(id.0) {
+            Some(scope) => scope.height,
+            None => return,
+        };
+        self.note_render_reason(id, current_cause());
+
+        for existing in Priority::LANES {
+            let dirty = DirtyScope {
+                priority: existing,
+                height,
+                id,
+            };
+
+            if self.dirty_scopes.contains(&dirty) {
+                if existing <= priority {
+                    return;
+                }
+                self.dirty_scopes.remove(&dirty);
+            }
         }
+
+        self.dirty_scopes.insert(DirtyScope {
+            priority,
+            height,
+            id,
+        });
+    }
+
+    /// Remove all the elements created by a scope while keeping the scope itself mounted
+    ///
+    /// The scope's rendered nodes are swapped out for a placeholder and their ElementIds are reclaimed. The scope's
+    /// hooks and props are left untouched, so the next render will recreate its elements from scratch without losing
+    /// any component state. Child components are unmounted along with the elements.
+    ///
+    /// The scope is marked dirty, so the fresh elements will be created the next time the VirtualDom is rendered.
+    pub fn clear_scope_elements(&mut self, id: ScopeId) {
+        let frame = match self.scopes.get(id.0) {
+            Some(scope) => scope.current_frame(),
+            None => return,
+        };
+
+        // Only scopes that have rendered nodes own any elements
+        let old = match unsafe { frame.try_load_node() } {
+            Some(RenderReturn::Ready(node)) => node,
+            _ => return,
+        };
+
+        // Make the placeholder the new head of the frame so the next diff creates the nodes afresh
+        let allocated = &*frame
+            .bump()
+            .alloc(RenderReturn::Aborted(VPlaceholder::default()));
+        frame.node.set(allocated);
+
+        let placeholder = match unsafe { allocated.extend_lifetime_ref() } {
+            RenderReturn::Aborted(placeholder) => placeholder,
+            _ => unreachable!(),
+        };
+
+        self.scope_stack.push(id);
+        self.diff_ok_to_err(old, placeholder);
+        self.scope_stack.pop();
+
+        self.mark_dirty(id);
     }
 
     /// Determine whether or not a scope is currently in a suspended state
@@ -338,13 +532,133 @@ impl VirtualDom {
         !self.scheduler.leaves.borrow().is_empty()
     }
 
+    /// Get the total number of bytes allocated by the bump arenas that back each scope's rendered nodes
+    ///
+    /// Every scope keeps two frames - the current and previous render - and both are counted. This tracks the memory
+    /// used by VNode trees, separate from the element arena. A number that keeps growing between identical renders
+    /// points at a component producing ever-larger trees.
+    pub fn total_frame_arena_bytes(&self) -> usize {
+        self.scopes
+            .iter()
+            .map(|(_, scope)| {
+                scope.node_arena_1.bump().allocated_bytes()
+                    + scope.node_arena_2.bump().allocated_bytes()
+            })
+            .sum()
+    }
+
+    /// Get the number of bytes allocated by the bump arenas backing the rendered nodes of a single scope
+    ///
+    /// Like [`VirtualDom::total_frame_arena_bytes`], this counts both the current and previous frame.
+    pub fn scope_frame_arena_bytes(&self, id: ScopeId) -> Option<usize> {
+        let scope = self.scopes.get(id.0)?;
+        Some(
+            scope.node_arena_1.bump().allocated_bytes()
+                + scope.node_arena_2.bump().allocated_bytes(),
+        )
+    }
+
+    /// Limit how much memory the frames of each scope keep around after rendering a large tree
+    ///
+    /// See [`MemoryBudget`] for how frames are shrunk. Passing `None` lets frames keep everything they've allocated,
+    /// which is the default.
+    pub fn set_memory_budget(&mut self, budget: Option<MemoryBudget>) {
+        self.memory_budget = budget;
+    }
+
+    /// Set a handler for errors that aren't caught by any error boundary
+    ///
+    /// The handler is called with the scope the error came from whenever a thrown error, or a panic while rendering or
+    /// handling an event, isn't caught by an [`ErrorBoundary`] component. This is the place to report crashes or show
+    /// an overlay instead of rendering nothing.
+    ///
+    /// ```rust, ignore
+    /// dom.set_error_handler(|scope, error| log::error!("{:?} crashed: {:?}", scope, error.error));
+    /// ```
+    pub fn set_error_handler(&mut self, handler: impl Fn(ScopeId, CapturedError) + 'static) {
+        *self.scheduler.error_handler.borrow_mut() = Some(Rc::new(handler));
+    }
+
+    /// Get the most listeners that have been live at once since the VirtualDom was created or the count was last reset
+    ///
+    /// If this number keeps climbing while cycling through the same set of views, then listeners are leaking.
+    pub fn peak_listener_count(&self) -> usize {
+        self.peak_listener_count.get()
+    }
+
+    /// Reset the peak listener count down to the number of listeners that are currently live
+    pub fn reset_peak_listener_count(&mut self) {
+        self.peak_listener_count.set(self.listener_count.get());
+    }
+
+    /// Set a callback to run whenever the last element other than the root is reclaimed
+    ///
+    /// This signals that the app has no content left in the dom, which renderers can use to show a blank-state
+    /// placeholder or release their native surface. The callback runs every time the arena becomes empty, so it will
+    /// fire again once new content has been created and removed.
+    ///
+    /// The callback is not run when the VirtualDom itself is dropped.
+    pub fn on_arena_empty(&mut self, f: impl FnMut() + 'static) {
+        self.on_arena_empty = Some(Box::new(f));
+    }
+
+    /// Set whether renderers have to dispatch an event type synchronously or may defer it
+    ///
+    /// Without a policy, the events in [`EventDispatch::DEFERRED_BY_DEFAULT`] are deferred and every other event is
+    /// synchronous. Make an event synchronous if a listener for it calls [`Event::prevent_default`], or deferred if it
+    /// fires so often that handling every one of them would slow the app down.
+    ///
+    /// ```rust, ignore
+    /// dom.set_event_dispatch("keydown", EventDispatch::Deferred);
+    /// dom.set_event_dispatch("wheel", EventDispatch::Synchronous);
+    /// ```
+    pub fn set_event_dispatch(&mut self, event: impl Into<String>, dispatch: EventDispatch) {
+        self.event_dispatch.insert(event.into(), dispatch);
+    }
+
+    /// Get how renderers should dispatch an event type, by its name without the `on` prefix (IE `click`)
+    pub fn event_dispatch(&self, event: &str) -> EventDispatch {
+        self.event_dispatch
+            .get(event)
+            .copied()
+            .unwrap_or_else(|| EventDispatch::default_for(event))
+    }
+
+    /// Get every event type that may be deferred, for renderers that have to tell a platform layer ahead of time
+    pub fn deferred_events(&self) -> Vec<String> {
+        let defaults = EventDispatch::DEFERRED_BY_DEFAULT
+            .iter()
+            .filter(|event| !self.event_dispatch.contains_key(**event))
+            .map(|event| event.to_string());
+        let overrides = self
+            .event_dispatch
+            .iter()
+            .filter(|(_, dispatch)| **dispatch == EventDispatch::Deferred)
+            .map(|(event, _)| event.clone());
+        let mut events: Vec<_> = defaults.chain(overrides).collect();
+        events.sort();
+        events
+    }
+
     /// Call a listener inside the VirtualDom with data from outside the VirtualDom.
     ///
     /// This method will identify the appropriate element. The data must match up with the listener delcared. Note that
     /// this method does not give any indication as to the success of the listener call. If the listener is not found,
     /// nothing will happen.
     ///
-    /// It is up to the listeners themselves to mark nodes as dirty.
+    /// It is up to the listeners themselves to mark nodes as dirty. Every scope they mark dirty, in any part of the
+    /// tree, is diffed into the next batch of [`Mutations`] together with the child components it re-renders, even
+    /// when that batch is rendered past its deadline. That way the renderer never shows one half of an update without
+    /// the other. Updates the listeners schedule in a lane below [`Priority::UserInput`] still wait for their turn.
+    ///
+    /// Events targeting an element that has been marked as leaving with [`VirtualDom::set_element_leaving`] are
+    /// ignored. Events targeting an element inside of a leaving element still run, but skip the listeners of the
+    /// leaving element.
+    ///
+    /// Capturing listeners (IE `onclick_capture`) run first, from the root down to the target. Bubbling listeners run
+    /// after, from the target back up to the root. Events that don't bubble only run the target's bubbling listener.
+    ///
+    /// Returns true if any listener called [`Event::prevent_default`].
     ///
     /// If you have multiple events, you can call this method multiple times before calling "render_with_deadline"
     pub fn handle_event(
@@ -353,16 +667,15 @@ impl VirtualDom {
         data: Rc<dyn Any>,
         element: ElementId,
         bubbles: bool,
-    ) {
+    ) -> bool {
         /*
         ------------------------
-        The algorithm works by walking through the list of dynamic attributes, checking their paths, and breaking when
-        we find the target path.
+        The algorithm works by walking through the list of dynamic attributes, checking their paths, and collecting the
+        listeners on the target path.
 
-        With the target path, we try and move up to the parent until there is no parent.
-        Due to how bubbling works, we call the listeners before walking to the parent.
-
-        If we wanted to do capturing, then we would accumulate all the listeners and call them in reverse order.
+        With the target path, we try and move up to the parent until there is no parent. Bubbling listeners are
+        collected from the target upwards, and capturing listeners are collected the same way and then reversed so they
+        run from the root downwards.
         ----------------------
 
         For a visual demonstration, here we present a tree on the left and whether or not a listener is collected on the
@@ -371,64 +684,141 @@ impl VirtualDom {
         |           <-- yes (is ascendant)
         | | |       <-- no  (is not direct ascendant)
         | |         <-- yes (is ascendant)
-        | | | | |   <--- target element, break early, don't check other listeners
-        | | |       <-- no, broke early
-        |           <-- no, broke early
+        | | | | |   <--- target element
+        | | |       <-- no, not ascendant
+        |           <-- no, not ascendant
         */
+        let _span = span!("event", event = name, element = element.0, bubbles);
+
+        self.mutations_applied();
+
+        if let Some(recording) = self.recording.as_mut() {
+            recording.record_event(name, element, bubbles);
+        }
+
+        // The renderer only knows the shifted ids
+        let element = match element.0.checked_sub(self.root_element.0) {
+            Some(id) => ElementId(id),
+            None => return false,
+        };
+
         let mut parent_path = self.elements.get(element.0);
-        let mut listeners = vec![];
+
+        // Elements on their way out of the dom don't receive events
+        if matches!(parent_path, Some(el_ref) if el_ref.leaving) {
+            return false;
+        }
 
         // We will clone this later. The data itself is wrapped in RC to be used in callbacks if required
         let uievent = Event {
-            propagates: Rc::new(Cell::new(bubbles)),
+            propagates: Rc::new(Cell::new(true)),
+            propagates_immediately: Rc::new(Cell::new(true)),
+            default_prevented: Rc::new(Cell::new(false)),
             data,
         };
 
+        // Each listener is paired with the element it's attached to, so stopping propagation can finish that element
+        let mut capturing = vec![];
+        let mut bubbling = vec![];
+        let mut is_target_template = true;
+
         // Loop through each dynamic attribute in this template before moving up to the template's parent.
         while let Some(el_ref) = parent_path {
             // safety: we maintain references of all vnodes in the element slab
             let template = unsafe { el_ref.template.unwrap().as_ref() };
             let node_template = template.template.get();
-            let target_path = el_ref.path;
+            let target_path = &el_ref.path;
+
+            let mut template_capturing = vec![];
+            let mut template_bubbling = vec![];
 
             for (idx, attr) in template.dynamic_attrs.iter().enumerate() {
                 let this_path = node_template.attr_paths[idx];
 
+                if !target_path.is_ascendant(&this_path) {
+                    continue;
+                }
+
                 // listeners are required to be prefixed with "on", but they come back to the virtualdom with that missing
                 // we should fix this so that we look for "onclick" instead of "click"
-                if &attr.name[2..] == name && target_path.is_ascendant(&this_path) {
-                    listeners.push(&attr.value);
+                let (event_name, options) = ListenerOptions::parse(attr.name)
+                    .unwrap_or((&attr.name[2..], ListenerOptions::default()));
 
-                    // Break if the event doesn't bubble anyways
-                    if !bubbles {
-                        break;
-                    }
+                if event_name != name {
+                    continue;
+                }
 
-                    // Break if this is the exact target element.
-                    // This means we won't call two listeners with the same name on the same element. This should be
-                    // documented, or be rejected from the rsx! macro outright
-                    if target_path == this_path {
-                        break;
-                    }
+                // Elements on their way out of the dom are not part of the path the event takes
+                if self.element_is_leaving(attr.mounted_element.get()) {
+                    continue;
                 }
+
+                let element = (template as *const VNode, this_path);
+                let fired = options.once.then(|| (attr.mounted_element.get(), idx));
+                let listener = (element, el_ref.scope, &attr.value, fired);
+
+                if options.capture {
+                    template_capturing.push(listener);
+                } else if bubbles || (is_target_template && template_bubbling.is_empty()) {
+                    // Events that don't bubble only reach the first listener on the target
+                    template_bubbling.push(listener);
+                }
+            }
+
+            // The attributes come in tree order, so the ones closest to the target are last
+            capturing.extend(template_capturing.into_iter().rev());
+            bubbling.extend(template_bubbling.into_iter().rev());
+
+            parent_path = template.parent.and_then(|id| self.elements.get(id.0));
+            is_target_template = false;
+        }
+
+        capturing.reverse();
+
+        // Once propagation stops, only the remaining listeners on the same element are called
+        let mut stopped_at = None;
+        for (element, scope, listener, fired) in capturing.into_iter().chain(bubbling) {
+            if matches!(stopped_at, Some(stopped) if stopped != element) {
+                break;
             }
 
-            // Now that we've accumulated all the parent attributes for the target element, call them in reverse order
-            // We check the bubble state between each call to see if the event has been stopped from bubbling
-            for listener in listeners.drain(..).rev() {
-                if let AttributeValue::Listener(listener) = listener {
-                    if let Some(cb) = listener.borrow_mut().as_deref_mut() {
-                        cb(uievent.clone());
+            if let AttributeValue::Listener(listener) = listener {
+                let mut listener = listener.borrow_mut();
+
+                if let Some(cb) = listener.as_deref_mut() {
+                    let reason = RenderReason::Event {
+                        name: name.to_string(),
+                    };
+                    let res = with_cause(reason, || {
+                        std::panic::catch_unwind(AssertUnwindSafe(|| cb(uievent.clone())))
+                    });
+
+                    // Listeners that only run once are disarmed by dropping their callback
+                    if let Some(fired) = fired {
+                        *listener = None;
+                        self.fired_listeners.insert(fired);
                     }
 
-                    if !uievent.propagates.get() {
-                        return;
+                    // A panicking handler goes to the nearest error boundary and the event stops there
+                    if let Err(payload) = res {
+                        let scope = scope.unwrap_or(ScopeId(0));
+                        let scope = &self.scopes[scope.0];
+                        scope.throw(CapturedPanic::from_payload(payload, scope));
+                        break;
                     }
                 }
             }
 
-            parent_path = template.parent.and_then(|id| self.elements.get(id.0));
+            if !uievent.propagates_immediately.get() {
+                break;
+            }
+
+            if stopped_at.is_none() && !uievent.propagates.get() {
+                stopped_at = Some(element);
+            }
         }
+
+        uievent.default_prevented.get()
     }
 
     /// Wait for the scheduler to have any work.
@@ -448,32 +838,41 @@ impl VirtualDom {
     /// let sender = dom.get_scheduler_channel();
     /// ```
     pub async fn wait_for_work(&mut self) {
-        let mut some_msg = None;
+        futures_util::future::poll_fn(|cx| self.poll_tasks(cx)).await
+    }
+
+    /// Poll the tasks and suspended components that woke up, without an async runtime
+    ///
+    /// This is the synchronous half of [`VirtualDom::wait_for_work`], for event loops that drive the VirtualDom
+    /// themselves. It returns [`Poll::Ready`] once there's work to render. Otherwise, the waker of `cx` is woken when a
+    /// task or scope might have made some, so the loop can sleep until then.
+    ///
+    /// ```rust, ignore
+    /// // Once per frame of a game loop
+    /// if dom.poll_tasks(&mut Context::from_waker(&waker)).is_ready() {
+    ///     let edits = dom.render_immediate();
+    /// }
+    /// ```
+    pub fn poll_tasks(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        self.mutations_applied();
 
         loop {
-            match some_msg.take() {
-                // If a bunch of messages are ready in a sequence, try to pop them off synchronously
-                Some(msg) => match msg {
-                    SchedulerMsg::Immediate(id) => self.mark_dirty(id),
-                    SchedulerMsg::TaskNotified(task) => self.handle_task_wakeup(task),
-                    SchedulerMsg::SuspenseNotified(id) => self.handle_suspense_wakeup(id),
-                },
-
-                // If they're not ready, then we should wait for them to be ready
-                None => {
-                    match self.rx.try_next() {
-                        Ok(Some(val)) => some_msg = Some(val),
-                        Ok(None) => return,
-                        Err(_) => {
-                            // If we have any dirty scopes, or finished fiber trees then we should exit
-                            if !self.dirty_scopes.is_empty() || !self.finished_fibers.is_empty() {
-                                return;
-                            }
-
-                            some_msg = self.rx.next().await
-                        }
-                    }
-                }
+            // If a bunch of messages are ready in a sequence, pop them off synchronously
+            self.process_events();
+
+            // If we have any dirty scopes, finished fiber trees, or live values to patch then we should exit
+            if !self.dirty_scopes.is_empty()
+                || !self.finished_fibers.is_empty()
+                || !self.live_updates.is_empty()
+            {
+                return Poll::Ready(());
+            }
+
+            // Otherwise wait for the next message, registering the waker
+            match self.rx.poll_next_unpin(cx) {
+                Poll::Ready(Some(msg)) => self.handle_scheduler_msg(msg),
+                Poll::Ready(None) => return Poll::Ready(()),
+                Poll::Pending => return Poll::Pending,
             }
         }
     }
@@ -481,10 +880,24 @@ impl VirtualDom {
     /// Process all events in the queue until there are no more left
     pub fn process_events(&mut self) {
         while let Ok(Some(msg)) = self.rx.try_next() {
-            match msg {
-                SchedulerMsg::Immediate(id) => self.mark_dirty(id),
-                SchedulerMsg::TaskNotified(task) => self.handle_task_wakeup(task),
-                SchedulerMsg::SuspenseNotified(id) => self.handle_suspense_wakeup(id),
+            self.handle_scheduler_msg(msg);
+        }
+    }
+
+    fn handle_scheduler_msg(&mut self, msg: SchedulerMsg) {
+        match msg {
+            SchedulerMsg::Immediate(id, reason) => {
+                self.note_render_reason(id, reason);
+                self.mark_dirty(id)
+            }
+            SchedulerMsg::ImmediateWithPriority(id, priority, reason) => {
+                self.note_render_reason(id, reason);
+                self.mark_dirty_with_priority(id, priority)
+            }
+            SchedulerMsg::TaskNotified(task) => self.handle_task_wakeup(task),
+            SchedulerMsg::SuspenseNotified(id) => self.handle_suspense_wakeup(id),
+            SchedulerMsg::LiveUpdate(id, value) => {
+                self.live_updates.insert(id, value);
             }
         }
     }
@@ -492,19 +905,25 @@ impl VirtualDom {
     /// Replace a template at runtime. This will re-render all components that use this template.
     /// This is the primitive that enables hot-reloading.
     ///
-    /// The caller must ensure that the template refrences the same dynamic attributes and nodes as the original template.
+    /// The new template may add, remove, or rearrange static elements, as long as it refrences the same dynamic
+    /// attributes and nodes as the original template. Live instances are recreated with the new structure the next
+    /// time the VirtualDom renders, including instances nested inside the output of other templates.
+    ///
+    /// This will only replace the the given template, not any templates nested inside of it.
     ///
-    /// This will only replace the the parent template, not any nested templates.
+    /// Templates are only swapped out while diffing in debug builds.
     pub fn replace_template(&mut self, template: Template<'static>) {
-        self.register_template_first_byte_index(template);
+        self.register_hot_reloaded_template(template);
+        let (path, _) = template.name.rsplit_once(':').unwrap();
+
         // iterating a slab is very inefficient, but this is a rare operation that will only happen during development so it's fine
         for (_, scope) in &self.scopes {
             if let Some(RenderReturn::Ready(sync)) = scope.try_root_node() {
-                if sync.template.get().name.rsplit_once(':').unwrap().0
-                    == template.name.rsplit_once(':').unwrap().0
-                {
+                if renders_template(sync, path) {
                     let height = scope.height;
+                    self.note_render_reason(scope.id, RenderReason::HotReload);
                     self.dirty_scopes.insert(DirtyScope {
+                        priority: Priority::UserInput,
                         height,
                         id: scope.id,
                     });
@@ -513,6 +932,70 @@ impl VirtualDom {
         }
     }
 
+    /// Swap out the code of a component at runtime, like after reloading it from a freshly compiled library. This is
+    /// the primitive that enables hot-reloading the bodies of components, where [`VirtualDom::replace_template`] only
+    /// covers their rsx.
+    ///
+    /// Every instance of the old component renders the new code the next time the VirtualDom renders, and so does every
+    /// instance created with the old one later on, like by a parent that wasn't reloaded. The instances keep their
+    /// place in the tree and their children, and `hooks` decides what happens to the state of their hooks.
+    ///
+    /// Replacing a component that was already replaced before replaces the code it runs now.
+    pub fn replace_component<P>(
+        &mut self,
+        old: Component<P>,
+        new: Component<P>,
+        hooks: HookReload,
+    ) {
+        let (old, new) = (old as *const (), new as *const ());
+        if self.reloaded_component(old) == new {
+            return;
+        }
+
+        // Components that were reloaded into the old code run the new code too, and reloading back to earlier code
+        // means it isn't replaced anymore
+        for render_fn in self.reloaded_components.values_mut() {
+            if *render_fn == old {
+                *render_fn = new;
+            }
+        }
+        self.reloaded_components.remove(&new);
+        self.reloaded_components.insert(old, new);
+
+        let reloaded: Vec<_> = self
+            .scopes
+            .iter()
+            .filter(|(_, scope)| {
+                scope.props.as_ref().map_or(false, |props| {
+                    self.reloaded_component(props.render_fn()) == new
+                })
+            })
+            .map(|(_, scope)| (scope.id, scope.height))
+            .collect();
+
+        for (id, height) in reloaded {
+            // the hooks are dropped once the scope renders, since its nodes can still use them until then
+            if hooks == HookReload::Reset {
+                self.drop_scope_tasks(id);
+            }
+            self.scopes[id.0].reloading.set(Some(hooks));
+            self.note_render_reason(id, RenderReason::HotReload);
+            self.dirty_scopes.insert(DirtyScope {
+                priority: Priority::UserInput,
+                height,
+                id,
+            });
+        }
+    }
+
+    // The code a component runs now, which is only different if it was hot reloaded
+    pub(crate) fn reloaded_component(&self, render_fn: *const ()) -> *const () {
+        self.reloaded_components
+            .get(&render_fn)
+            .copied()
+            .unwrap_or(render_fn)
+    }
+
     /// Performs a *full* rebuild of the virtual dom, returning every edit required to generate the actual dom from scratch.
     ///
     /// The mutations item expects the RealDom's stack to be the root of the application.
@@ -534,6 +1017,65 @@ impl VirtualDom {
     /// apply_edits(edits);
     /// ```
     pub fn rebuild(&mut self) -> Mutations {
+        self.build_root();
+        self.finalize()
+    }
+
+    /// Perform a *full* rebuild of the virtual dom like [`VirtualDom::rebuild`], writing the edits out to a
+    /// [`WriteMutations`] implementation instead of returning them as [`Mutations`].
+    ///
+    /// The edits are still buffered before they are written, but the VirtualDom reuses its internal edit buffer between
+    /// calls, so renderers that apply edits through this method don't pay for a fresh allocation every frame.
+    pub fn rebuild_to<'a>(&'a mut self, to: &mut impl WriteMutations<'a>) {
+        self.build_root();
+        self.flush_to(to);
+    }
+
+    /// Recreate every element of an app that's already running, for a renderer that lost its dom
+    ///
+    /// Unlike [`VirtualDom::rebuild`], the state of the components is kept: the elements are cleared like with
+    /// [`VirtualDom::clear_scope_elements`] and rendered again. The mutations register every template the VirtualDom
+    /// knows about, and expect the renderer to start out with nothing but the root element, like a fresh one.
+    pub fn rebuild_in_place(&mut self) -> Mutations {
+        self.build_in_place(None);
+        self.finalize()
+    }
+
+    /// Recreate every element of the app like [`VirtualDom::rebuild_in_place`], writing the edits out to a
+    /// [`WriteMutations`] implementation instead of returning them as [`Mutations`].
+    pub fn rebuild_in_place_to<'a>(&'a mut self, to: &mut impl WriteMutations<'a>) {
+        self.build_in_place(Some(&mut *to));
+        self.flush_to(to);
+    }
+
+    fn build_in_place(&mut self, to: Option<&mut dyn WriteMutations<'_>>) {
+        self.clear_scope_elements(ScopeId(0));
+
+        // The renderer doesn't have any of the elements being removed
+        self.mutations.edits.clear();
+        self.mutations.templates = self
+            .templates
+            .values()
+            .flat_map(|set| set.values().copied())
+            .filter(|template| !template.is_completely_dynamic())
+            .collect();
+
+        let frame = self.scopes[0].current_frame();
+        if let Some(RenderReturn::Aborted(placeholder)) = unsafe { frame.try_load_node() } {
+            if let Some(id) = placeholder.id.get() {
+                self.mutations.push(Mutation::CreatePlaceholder { id });
+                self.mutations.push(Mutation::AppendChildren {
+                    id: ElementId(0),
+                    m: 1,
+                });
+            }
+        }
+
+        self.mark_dirty(ScopeId(0));
+        self.work_immediate(to);
+    }
+
+    fn build_root(&mut self) {
         match unsafe { self.run_scope(ScopeId(0)).extend_lifetime_ref() } {
             // Rebuilding implies we append the created elements to the root
             RenderReturn::Ready(node) => {
@@ -542,30 +1084,46 @@ impl VirtualDom {
                     id: ElementId(0),
                     m,
                 });
+
+                // The root is a boundary too, for the leaves no other boundary took
+                self.attach_collected_leaves();
             }
             // If an error occurs, we should try to render the default error component and context where the error occured
             RenderReturn::Aborted(_placeholder) => panic!("Cannot catch errors during rebuild"),
             RenderReturn::Pending(_) => unreachable!("Root scope cannot be an async component"),
         }
-
-        self.finalize()
     }
 
     /// Render whatever the VirtualDom has ready as fast as possible without requiring an executor to progress
     /// suspended subtrees.
     pub fn render_immediate(&mut self) -> Mutations {
+        self.work_immediate(None);
+        self.finalize()
+    }
+
+    /// Render whatever the VirtualDom has ready like [`VirtualDom::render_immediate`], writing the edits out to a
+    /// [`WriteMutations`] implementation instead of returning them as [`Mutations`].
+    ///
+    /// The edits of every component are buffered while it's diffed and written out in one batch once it's done, so the
+    /// buffer only ever holds the edits of one component. The trees a [`crate::SuspenseBoundary`] holds back until they
+    /// resolve are the exception and are kept around in between.
+    pub fn render_immediate_to<'a>(&'a mut self, to: &mut impl WriteMutations<'a>) {
+        self.work_immediate(Some(&mut *to));
+        self.flush_to(to);
+    }
+
+    fn work_immediate(&mut self, to: Option<&mut dyn WriteMutations<'_>>) {
         // Build a waker that won't wake up since our deadline is already expired when it's polled
         let waker = futures_util::task::noop_waker();
         let mut cx = std::task::Context::from_waker(&waker);
 
         // Now run render with deadline but dont even try to poll any async tasks
-        let fut = self.render_with_deadline(std::future::ready(()));
+        let fut = self.work_with_deadline(std::future::ready(()), false, to);
         pin_mut!(fut);
 
         // The root component is not allowed to be async
-        match fut.poll(&mut cx) {
-            std::task::Poll::Ready(mutations) => mutations,
-            std::task::Poll::Pending => panic!("render_immediate should never return pending"),
+        if fut.poll(&mut cx).is_pending() {
+            panic!("render_immediate should never return pending");
         }
     }
 
@@ -573,15 +1131,50 @@ impl VirtualDom {
     ///
     /// It's generally a good idea to put some sort of limit on the suspense process in case a future is having issues.
     ///
-    /// If no suspense trees are present
+    /// Once the deadline passes, dirty scopes in lanes below [`Priority::UserInput`] wait for the next render. Urgent
+    /// scopes always render, along with the child components they re-render.
     pub async fn render_with_deadline(&mut self, deadline: impl Future<Output = ()>) -> Mutations {
-        pin_mut!(deadline);
+        self.work_with_deadline(deadline, true, None).await;
+        self.finalize()
+    }
 
-        self.process_events();
+    /// Render what you can given the timeline like [`VirtualDom::render_with_deadline`], writing the edits out to a
+    /// [`WriteMutations`] implementation instead of returning them as [`Mutations`].
+    pub async fn render_with_deadline_to<'a>(
+        &'a mut self,
+        deadline: impl Future<Output = ()>,
+        to: &mut impl WriteMutations<'a>,
+    ) {
+        self.work_with_deadline(deadline, true, Some(&mut *to))
+            .await;
+        self.flush_to(to);
+    }
+
+    /// Render dirty scopes lane by lane until there's no work left or the deadline is reached
+    ///
+    /// If `yield_lanes` is set, lanes below [`Priority::UserInput`] stop rendering once the deadline passes and stay
+    /// dirty for the next render.
+    ///
+    /// With a writer, the edits are written out after every scope instead of being collected for the caller.
+    async fn work_with_deadline(
+        &mut self,
+        deadline: impl Future<Output = ()>,
+        yield_lanes: bool,
+        mut to: Option<&mut dyn WriteMutations<'_>>,
+    ) {
+        self.mutations_applied();
+
+        // The renderer applied the edits of the last render, so the live text they pointed at isn't needed anymore
+        self.live_text.reset();
+
+        pin_mut!(deadline);
 
         loop {
+            // Pick up any updates that came in since the last scope so more urgent lanes can jump the queue
+            self.process_events();
+
             // first, unload any complete suspense trees
-            for finished_fiber in self.finished_fibers.drain(..) {
+            for finished_fiber in std::mem::take(&mut self.finished_fibers) {
                 let scope = &self.scopes[finished_fiber.0];
                 let context = scope.has_context::<Rc<SuspenseContext>>().unwrap();
 
@@ -593,16 +1186,51 @@ impl VirtualDom {
                     .edits
                     .append(&mut context.mutations.borrow_mut().edits);
 
-                // TODO: count how many nodes are on the stack?
-                self.mutations.push(Mutation::ReplaceWith {
-                    id: context.placeholder.get().unwrap(),
-                    m: 1,
-                })
+                let created = context.created_on_stack.get();
+
+                match context.fallback_scope.take() {
+                    // Swap the fallback out for the resolved tree
+                    Some(fallback) => {
+                        drop(self.remove_scope(fallback, true));
+
+                        // The first root of the fallback is removed last, so replace it instead of removing it
+                        match self.mutations.edits.pop() {
+                            Some(Mutation::Remove { id }) => self
+                                .mutations
+                                .push(Mutation::ReplaceWith { id, m: created }),
+                            _ => panic!("Expected remove mutation from remove_node"),
+                        }
+                    }
+                    None => {
+                        let id = context.placeholder.take().unwrap();
+                        self.mutations
+                            .push(Mutation::ReplaceWith { id, m: created });
+                        self.reclaim(id);
+                    }
+                }
+
+                // The leaves that resolved meanwhile replace their placeholders, which are in the dom now
+                self.mutations
+                    .templates
+                    .append(&mut context.resolved.borrow_mut().templates);
+
+                self.mutations
+                    .edits
+                    .append(&mut context.resolved.borrow_mut().edits);
             }
 
             // Next, diff any dirty scopes
             // We choose not to poll the deadline since we complete pretty quickly anyways
             if let Some(dirty) = self.dirty_scopes.iter().next().cloned() {
+                // Less urgent work gives way once the deadline passes, so the frame isn't held up
+                if yield_lanes && dirty.priority > Priority::UserInput {
+                    let waker = futures_util::task::noop_waker();
+                    let mut cx = std::task::Context::from_waker(&waker);
+                    if deadline.as_mut().poll(&mut cx).is_ready() {
+                        return;
+                    }
+                }
+
                 self.dirty_scopes.remove(&dirty);
 
                 // If the scope doesn't exist for whatever reason, then we should skip it
@@ -615,45 +1243,31 @@ impl VirtualDom {
                     continue;
                 }
 
-                // Save the current mutations length so we can split them into boundary
-                let mutations_to_this_point = self.mutations.edits.len();
-
                 // Run the scope and get the mutations
                 self.run_scope(dirty.id);
                 self.diff_scope(dirty.id);
 
-                // If suspended leaves are present, then we should find the boundary for this scope and attach things
-                // No placeholder necessary since this is a diff
-                if !self.collected_leaves.is_empty() {
-                    let mut boundary = self.scopes[dirty.id.0]
-                        .consume_context::<Rc<SuspenseContext>>()
-                        .unwrap();
-
-                    let boundary_mut = boundary.borrow_mut();
-
-                    // Attach mutations
-                    boundary_mut
-                        .mutations
-                        .borrow_mut()
-                        .edits
-                        .extend(self.mutations.edits.split_off(mutations_to_this_point));
-
-                    // Attach suspended leaves
-                    boundary
-                        .waiting_on
-                        .borrow_mut()
-                        .extend(self.collected_leaves.drain(..));
-                }
+                // If suspended leaves are present, then their boundaries wait on them
+                // No placeholder necessary since this is a diff: the old nodes stay mounted until the leaves resolve
+                self.attach_collected_leaves();
+            }
+
+            self.patch_live_values();
+
+            // Nothing refers back to the edits of the scopes that are done, so they can go out right away
+            if let Some(to) = to.as_deref_mut() {
+                self.write_through(to);
             }
 
             // If there's more work, then just continue, plenty of work to do
-            if !self.dirty_scopes.is_empty() {
+            // That includes boundaries whose children finished mounting while a parent rendered them again
+            if !self.dirty_scopes.is_empty() || !self.finished_fibers.is_empty() {
                 continue;
             }
 
             // If there's no pending suspense, then we have no reason to wait for anything
             if self.scheduler.leaves.borrow().is_empty() {
-                return self.finalize();
+                return;
             }
 
             // Poll the suspense leaves in the meantime
@@ -667,20 +1281,137 @@ impl VirtualDom {
             if let Either::Left((_, _)) = select(&mut deadline, pinned).await {
                 // release the borrowed
                 drop(work);
-                return self.finalize();
+                return;
             }
         }
     }
 
     /// Swap the current mutations with a new
     fn finalize(&mut self) -> Mutations {
+        self.collect_semantics();
+        self.shift_element_ids();
+
+        if let Some(recording) = self.recording.as_mut() {
+            recording.record(&self.mutations);
+        }
+
         std::mem::take(&mut self.mutations)
     }
+
+    /// Move the ids in the pending mutations past the root element the renderer gave us
+    fn shift_element_ids(&mut self) {
+        let offset = self.root_element.0;
+        if offset == 0 {
+            return;
+        }
+
+        for edit in self.mutations.edits.iter_mut() {
+            if let Some(id) = edit.id_mut() {
+                id.0 += offset;
+            }
+        }
+
+        for (id, _) in self.mutations.generations.iter_mut() {
+            id.0 += offset;
+        }
+
+        for update in self.mutations.semantics.iter_mut() {
+            update.id_mut().0 += offset;
+        }
+    }
+
+    /// Write out the pending mutations, keeping the buffers around for the next frame
+    fn flush_to(&mut self, to: &mut dyn WriteMutations<'_>) {
+        self.write_through(to);
+
+        if let Some(recording) = self.recording.as_mut() {
+            recording.finish_step();
+        }
+
+        self.mutations_applied();
+    }
+
+    /// Write out the batch of mutations collected so far while the VirtualDom is still rendering
+    fn write_through(&mut self, to: &mut dyn WriteMutations<'_>) {
+        self.collect_semantics();
+        self.shift_element_ids();
+
+        if let Some(recording) = self.recording.as_mut() {
+            recording.record_part(&self.mutations);
+        }
+
+        for template in self.mutations.templates.drain(..) {
+            to.register_template(template);
+        }
+
+        for edit in self.mutations.edits.drain(..) {
+            edit.write_to(to);
+        }
+
+        for update in self.mutations.semantics.drain(..) {
+            to.update_semantics(update);
+        }
+
+        self.mutations.dirty_scopes.clear();
+        self.mutations.generations.clear();
+    }
+
+    /// Run the [`ScopeState::push_on_mount`] callbacks of every scope rendered by the edits the renderer just applied
+    ///
+    /// The VirtualDom calls this itself the next time it handles an event or renders, and after writing edits with one
+    /// of the `_to` methods. Renderers applying [`Mutations`] only need to call it to run the callbacks sooner.
+    pub fn mutations_applied(&mut self) {
+        // Parents render before their children, so running the queue backwards mounts children first
+        while let Some(id) = self.pending_mounts.pop() {
+            let callbacks = match self.scopes.get(id.0) {
+                Some(scope) => scope.on_mount.take(),
+                None => continue,
+            };
+
+            for callback in callbacks {
+                callback();
+            }
+        }
+    }
 }
 
 impl Drop for VirtualDom {
     fn drop(&mut self) {
+        // Tearing down the VirtualDom empties the arena, but that isn't the app running out of content
+        self.on_arena_empty.take();
+
         // Simply drop this scope which drops all of its children
         self.drop_scope(ScopeId(0));
     }
 }
+
+/// What happens to the hook state of a component when its code is swapped out with [`VirtualDom::replace_component`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookReload {
+    /// Keep every hook up to the first one the new code calls with a different type, and start over from there
+    ///
+    /// Editing the body of a component without touching its hooks keeps all of its state, and adding a hook at the end
+    /// keeps the state of the ones before it. Hooks the new code doesn't call anymore are dropped.
+    KeepMatching,
+
+    /// Drop every hook and cancel the tasks of the component, like it was mounted again
+    Reset,
+}
+
+impl Default for HookReload {
+    fn default() -> Self {
+        Self::KeepMatching
+    }
+}
+
+/// Check if a node or any of the fragments it rendered inline came from the given rsx! call
+fn renders_template(node: &VNode, path: &str) -> bool {
+    if node.template.get().name.rsplit_once(':').unwrap().0 == path {
+        return true;
+    }
+
+    node.dynamic_nodes.iter().any(|dynamic| match dynamic {
+        DynamicNode::Fragment(nodes) => nodes.iter().any(|node| renders_template(node, path)),
+        _ => false,
+    })
+}
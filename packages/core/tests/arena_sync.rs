@@ -0,0 +1,49 @@
+//! Renderers can check that they agree with the VirtualDom about which elements are live
+
+use dioxus::core::{ElementId, SyncReport};
+use dioxus::prelude::*;
+
+#[test]
+fn report_desync() {
+    let mut dom = VirtualDom::new(|cx| {
+        let gen = cx.generation();
+        cx.render(rsx!( div { "{gen}" } ))
+    });
+    _ = dom.rebuild();
+
+    // The root is ignored, and the div and its text are live
+    assert!(dom.diff_against(&[ElementId(1), ElementId(2)]).is_synced());
+    assert!(dom
+        .diff_against(&[ElementId(0), ElementId(1), ElementId(2)])
+        .is_synced());
+
+    assert_eq!(
+        dom.diff_against(&[ElementId(2), ElementId(7)]),
+        SyncReport { missing: vec![ElementId(1)], stale: vec![ElementId(7)] }
+    );
+}
+
+#[test]
+fn reused_ids_bump_generation() {
+    let mut dom = VirtualDom::new(|cx| match cx.generation() % 2 {
+        0 => cx.render(rsx!( div {} )),
+        _ => cx.render(rsx!( p {} )),
+    });
+
+    let edits = dom.rebuild();
+    let first = dom.element_generation(ElementId(1)).unwrap();
+    if cfg!(debug_assertions) {
+        assert_eq!(edits.generations, [(ElementId(1), first)]);
+    }
+
+    // Swapping the div for the p reclaims the div's id
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(dom.element_generation(ElementId(1)), None);
+
+    // The next swap reuses the id under a new generation
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert!(!dom.is_element_current(ElementId(1), first));
+    assert!(dom.is_element_current(ElementId(1), first + 1));
+}
@@ -0,0 +1,35 @@
+//! Event handlers that return futures should have them spawned on the scope
+
+use dioxus::core::ElementId;
+use dioxus::prelude::*;
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+thread_local! {
+    static CLICKS: Cell<usize> = Cell::new(0);
+}
+
+#[tokio::test]
+async fn async_handlers_are_spawned() {
+    let mut dom = VirtualDom::new(|cx| {
+        cx.render(rsx! {
+            button {
+                onclick: move |_| async move {
+                    tokio::time::sleep(Duration::from_millis(1)).await;
+                    CLICKS.with(|clicks| clicks.set(clicks.get() + 1));
+                }
+            }
+        })
+    });
+
+    _ = dom.rebuild();
+
+    dom.handle_event("click", Rc::new(MouseData::default()), ElementId(1), true);
+    assert_eq!(CLICKS.with(Cell::get), 0);
+
+    // The task finishes while the dom waits for work
+    tokio::select! {
+        _ = dom.wait_for_work() => {}
+        _ = tokio::time::sleep(Duration::from_millis(100)) => {}
+    };
+    assert_eq!(CLICKS.with(Cell::get), 1);
+}
@@ -0,0 +1,61 @@
+//! Clearing a scope's elements should recreate them on the next render without touching its hooks
+
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::prelude::*;
+
+#[test]
+fn clear_keeps_hook_state() {
+    let mut dom = VirtualDom::new(|cx| {
+        let count = cx.use_hook(|| 0);
+        *count += 1;
+        cx.render(rsx! { div { "{count}" } })
+    });
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            HydrateText { path: &[0], value: "1", id: ElementId(2) },
+            AppendChildren { m: 1, id: ElementId(0) },
+        ]
+    );
+
+    dom.clear_scope_elements(ScopeId(0));
+
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            CreatePlaceholder { id: ElementId(3) },
+            ReplaceWith { id: ElementId(1), m: 1 },
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            HydrateText { path: &[0], value: "2", id: ElementId(2) },
+            ReplaceWith { id: ElementId(3), m: 1 },
+        ]
+    );
+}
+
+#[test]
+fn rebuild_in_place_keeps_hook_state() {
+    let mut dom = VirtualDom::new(|cx| {
+        let count = cx.use_hook(|| 0);
+        *count += 1;
+        cx.render(rsx! { div { "{count}" } })
+    });
+
+    let first = dom.rebuild();
+    assert_eq!(first.templates.len(), 1);
+
+    // a fresh renderer gets the templates again, and nothing is removed from it
+    let edits = dom.rebuild_in_place();
+    assert_eq!(edits.templates, first.templates);
+    assert_eq!(
+        edits.santize().edits,
+        [
+            CreatePlaceholder { id: ElementId(3) },
+            AppendChildren { id: ElementId(0), m: 1 },
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            HydrateText { path: &[0], value: "2", id: ElementId(2) },
+            ReplaceWith { id: ElementId(3), m: 1 },
+        ]
+    );
+}
@@ -0,0 +1,42 @@
+//! Scopes should know the components they were rendered under
+
+use dioxus::core::ComponentStack;
+use dioxus::prelude::*;
+use std::cell::RefCell;
+
+thread_local! {
+    static STACK: RefCell<Option<ComponentStack>> = RefCell::new(None);
+}
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx!(middle {}))
+}
+
+fn middle(cx: Scope) -> Element {
+    cx.render(rsx!(leaf {}))
+}
+
+fn leaf(cx: Scope) -> Element {
+    STACK.with(|stack| *stack.borrow_mut() = Some(cx.component_stack()));
+    cx.render(rsx!("leaf"))
+}
+
+#[test]
+fn stack_goes_up_to_the_root() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let stack = STACK.with(|stack| stack.take()).unwrap();
+    assert_eq!(
+        stack.frames,
+        [
+            ("leaf", ScopeId(2)),
+            ("middle", ScopeId(1)),
+            ("app", ScopeId(0))
+        ]
+    );
+    assert_eq!(
+        stack.to_string(),
+        "component stack:\n    in leaf (ScopeId(2))\n    in middle (ScopeId(1))\n    in app (ScopeId(0))"
+    );
+}
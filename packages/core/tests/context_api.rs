@@ -49,3 +49,37 @@ fn state_shares() {
         [SetText { value: "Value is 3", id: ElementId(1,) },]
     );
 }
+
+#[test]
+fn replacing_context_rerenders_consumers() {
+    fn app(cx: Scope) -> Element {
+        cx.use_hook(|| cx.provide_context(0));
+        cx.render(rsx!(child_1 {}))
+    }
+
+    fn child_1(cx: Scope) -> Element {
+        cx.render(rsx!(child_2 {}))
+    }
+
+    fn child_2(cx: Scope) -> Element {
+        let value = cx.consume_context::<i32>().unwrap();
+        cx.render(rsx!("Value is {value}"))
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    // Only the consumer re-renders, the provider and the component in between are left alone
+    assert_eq!(dom.base_scope().replace_context(1), Some(0));
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [SetText { value: "Value is 1", id: ElementId(1,) },]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Could not find context `u8`")]
+fn missing_context_names_type() {
+    let dom = VirtualDom::new(|cx| cx.render(rsx!("hello")));
+    dom.base_scope().expect_context::<u8>();
+}
@@ -0,0 +1,69 @@
+//! Tests for what inspectors see of the VirtualDom.
+
+use dioxus::prelude::*;
+
+#[test]
+fn inspects_the_scope_tree() {
+    fn app(cx: Scope) -> Element {
+        let count = use_state(cx, || 0);
+        cx.render(rsx! {
+            div { "{count}" }
+            child { name: "first".to_string() }
+            plain { value: 1 }
+        })
+    }
+
+    #[derive(Props, PartialEq, Debug)]
+    struct ChildProps {
+        name: String,
+    }
+
+    fn child(cx: Scope<ChildProps>) -> Element {
+        cx.render(rsx! { p { "{cx.props.name}" } })
+    }
+
+    #[derive(Props, PartialEq)]
+    struct PlainProps {
+        value: i32,
+    }
+
+    fn plain(cx: Scope<PlainProps>) -> Element {
+        cx.render(rsx! { "{cx.props.value}" })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let scopes = dom.inspect();
+    assert_eq!(scopes.len(), 3);
+
+    let root = &scopes[0];
+    assert_eq!(root.id, ScopeId(0));
+    assert_eq!(root.parent, None);
+    assert_eq!(root.render_count, 1);
+    assert_eq!(root.hooks.len(), 1);
+    assert!(root.hooks[0].contains("UseState"));
+
+    let child = &scopes[1];
+    assert_eq!(child.name, "child");
+    assert_eq!(child.parent, Some(ScopeId(0)));
+    assert_eq!(
+        child.props.as_deref(),
+        Some("ChildProps { name: \"first\" }")
+    );
+    assert_eq!(child.elements.len(), 1);
+
+    // props that aren't Debug can't be shown
+    let plain = &scopes[2];
+    assert_eq!(plain.name, "plain");
+    assert_eq!(plain.props, None);
+
+    // the root has the nodes of its children at its root too
+    let elements = dom.scope_elements(ScopeId(0));
+    assert_eq!(elements.len(), 3);
+    assert_eq!(elements[1..], [child.elements[0], plain.elements[0]]);
+
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(dom.inspect()[0].render_count, 2);
+}
@@ -102,3 +102,83 @@ fn component_swap() {
         ]
     );
 }
+
+/// Generic props that opt into memoizing are compared with PartialEq, so unchanged children don't re-render
+#[test]
+fn generic_props_memoize() {
+    use std::cell::Cell;
+
+    thread_local! {
+        static CHILD_RENDERS: Cell<usize> = Cell::new(0);
+    }
+
+    fn app(cx: Scope) -> Element {
+        let gen = cx.generation();
+        cx.render(rsx! {
+            div { "{gen}" }
+            child { value: 1 }
+            child { value: gen / 2 }
+        })
+    }
+
+    #[derive(Props, PartialEq)]
+    #[props(memoize)]
+    struct ChildProps<T> {
+        value: T,
+    }
+
+    fn child<T: std::fmt::Display>(cx: Scope<ChildProps<T>>) -> Element {
+        CHILD_RENDERS.with(|renders| renders.set(renders.get() + 1));
+        render!("{cx.props.value}")
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    assert_eq!(CHILD_RENDERS.with(|renders| renders.get()), 2);
+
+    // gen goes from 0 to 1, so neither child's props changed
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(CHILD_RENDERS.with(|renders| renders.get()), 2);
+
+    // gen goes to 2, so only the second child re-renders
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(CHILD_RENDERS.with(|renders| renders.get()), 3);
+}
+
+/// Generic props don't have to be comparable unless they opt into memoizing, so they render every time
+#[test]
+fn generic_props_without_partial_eq() {
+    use std::cell::Cell;
+
+    thread_local! {
+        static CHILD_RENDERS: Cell<usize> = Cell::new(0);
+    }
+
+    struct NotComparable(usize);
+
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! {
+            child { value: NotComparable(1) }
+        })
+    }
+
+    #[derive(Props)]
+    struct ChildProps<T> {
+        value: T,
+    }
+
+    fn child(cx: Scope<ChildProps<NotComparable>>) -> Element {
+        CHILD_RENDERS.with(|renders| renders.set(renders.get() + 1));
+        render!("{cx.props.value.0}")
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    assert_eq!(CHILD_RENDERS.with(|renders| renders.get()), 1);
+
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(CHILD_RENDERS.with(|renders| renders.get()), 2);
+}
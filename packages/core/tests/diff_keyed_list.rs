@@ -241,6 +241,35 @@ fn keyed_diffing_additions_and_moves_in_middle() {
     );
 }
 
+/// New children in the middle of a reorder shouldn't stop the biggest run of children from staying in place
+#[test]
+fn keyed_diffing_lis_skips_new_nodes() {
+    let mut dom = VirtualDom::new(|cx| {
+        let order: &[_] = match cx.generation() % 2 {
+            0 => &[/**/ 1, 2, 3, 4, 5 /**/],
+            1 => &[/**/ 4, 5, 9, 1, 2, 3 /**/],
+            _ => unreachable!(),
+        };
+
+        cx.render(rsx!(order.iter().map(|i| rsx!(div { key: "{i}" }))))
+    });
+
+    _ = dom.rebuild();
+
+    // LIS: 1, 2, 3
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            // move 4, 5 and create 9
+            PushRoot { id: ElementId(4) },
+            PushRoot { id: ElementId(5) },
+            LoadTemplate { name: "template", index: 0, id: ElementId(6) },
+            InsertBefore { id: ElementId(1), m: 3 }
+        ]
+    );
+}
+
 #[test]
 fn controlled_keyed_diffing_out_of_order() {
     let mut dom = VirtualDom::new(|cx| {
@@ -0,0 +1,54 @@
+//! The VirtualDom should be able to report who owns the elements in its arena
+
+use dioxus::core::{ElementId, ElementPath};
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx! {
+        div { "hello" }
+        child {}
+    })
+}
+
+fn child(cx: Scope) -> Element {
+    cx.render(rsx! {
+        p {}
+        p {}
+    })
+}
+
+#[test]
+fn counts_live_elements() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(dom.live_scope_count(), 2);
+    assert_eq!(dom.live_element_count(), 3);
+    assert_eq!(dom.elements_per_scope(), [(ScopeId(0), 1), (ScopeId(1), 2)]);
+}
+
+#[test]
+fn origins_outlive_elements() {
+    let mut dom = VirtualDom::new(|cx| {
+        let show = cx.generation() == 0;
+        cx.render(rsx! {
+            show.then(|| rsx!( div {} ))
+        })
+    });
+    dom.track_element_origins(true);
+    _ = dom.rebuild();
+
+    let origin = dom.element_origin(ElementId(1)).cloned().unwrap();
+    assert_eq!(origin.scope, Some(ScopeId(0)));
+    assert_eq!(origin.path, ElementPath::Root(0));
+    assert!(origin.template.unwrap().contains("element_diagnostics.rs"));
+
+    // The div is removed, but the record of where it came from is kept around
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(dom.element_generation(ElementId(1)), None);
+    assert_eq!(dom.element_origin(ElementId(1)), Some(&origin));
+
+    dom.track_element_origins(false);
+    assert_eq!(dom.element_origin(ElementId(1)), None);
+}
@@ -0,0 +1,198 @@
+//! Tests for the paths stored alongside each element in the arena
+
+use dioxus::core::{ElementId, ElementPath, ReparentError, RootOutOfRange, Template, TemplateNode};
+use dioxus::prelude::*;
+
+static TEMPLATE: Template<'static> = Template {
+    name: "element_paths.rs:1:1:0",
+    roots: &[TemplateNode::Element {
+        tag: "div",
+        namespace: None,
+        attrs: &[],
+        children: &[
+            TemplateNode::Text { text: "a" },
+            TemplateNode::Element {
+                tag: "span",
+                namespace: None,
+                attrs: &[],
+                children: &[TemplateNode::Dynamic { id: 0 }],
+            },
+        ],
+    }],
+    node_paths: &[&[0, 1, 0]],
+    attr_paths: &[],
+};
+
+#[test]
+fn paths_resolve() {
+    assert!(ElementPath::Root(0).resolves_in(&TEMPLATE));
+    assert!(ElementPath::Deep(&[]).resolves_in(&TEMPLATE));
+    assert!(ElementPath::Deep(&[0, 1]).resolves_in(&TEMPLATE));
+    assert!(ElementPath::Deep(&[0, 1, 0]).resolves_in(&TEMPLATE));
+
+    assert!(!ElementPath::Root(1).resolves_in(&TEMPLATE));
+    assert!(!ElementPath::Deep(&[1]).resolves_in(&TEMPLATE));
+    assert!(!ElementPath::Deep(&[0, 2]).resolves_in(&TEMPLATE));
+    assert!(!ElementPath::Deep(&[0, 0, 0]).resolves_in(&TEMPLATE));
+}
+
+#[test]
+fn audit_live_tree() {
+    let mut dom = VirtualDom::new(|cx| {
+        let gen = cx.generation();
+        cx.render(rsx! {
+            div {
+                h1 { class: "{gen}" }
+                "{gen}"
+            }
+            (0..3).map(|i| rsx!( p { key: "{i}", "{i}" } ))
+        })
+    });
+
+    _ = dom.rebuild();
+    assert!(dom.audit_paths().is_empty());
+
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert!(dom.audit_paths().is_empty());
+}
+
+#[test]
+fn path_ancestors() {
+    let path = ElementPath::Deep(&[0, 1, 0]);
+
+    assert_eq!(
+        path.ancestors().collect::<Vec<_>>(),
+        [
+            ElementPath::Deep(&[0, 1, 0]),
+            ElementPath::Deep(&[0, 1]),
+            ElementPath::Deep(&[0]),
+        ]
+    );
+    assert!(path.ancestors().all(|p| p.resolves_in(&TEMPLATE)));
+
+    assert_eq!(ElementPath::Deep(&[0]).parent(), None);
+    assert_eq!(
+        ElementPath::Root(0).ancestors().collect::<Vec<_>>(),
+        [ElementPath::Root(0)]
+    );
+}
+
+#[test]
+fn navigation_skips_leaving_elements() {
+    let mut dom = VirtualDom::new(|cx| {
+        let gen = cx.generation();
+        cx.render(rsx! {
+            div {
+                section {
+                    class: "{gen}",
+                    span { class: "{gen}" }
+                    span { class: "{gen}" }
+                }
+            }
+        })
+    });
+
+    _ = dom.rebuild();
+    assert_eq!(
+        dom.element_ancestors(ElementId(3)),
+        [ElementId(2), ElementId(1)]
+    );
+    assert_eq!(dom.element_siblings(ElementId(3)), [ElementId(4)]);
+
+    // A leaving ancestor is skipped, but the walk still reaches the ones above it
+    dom.set_element_leaving(ElementId(2), true);
+    assert_eq!(dom.element_ancestors(ElementId(3)), [ElementId(1)]);
+    assert_eq!(dom.element_ancestors(ElementId(4)), [ElementId(1)]);
+
+    dom.set_element_leaving(ElementId(4), true);
+    assert!(dom.element_siblings(ElementId(3)).is_empty());
+
+    // Clearing the mark brings the element back
+    dom.set_element_leaving(ElementId(2), false);
+    assert_eq!(
+        dom.element_ancestors(ElementId(3)),
+        [ElementId(2), ElementId(1)]
+    );
+}
+
+#[test]
+fn reparent_rewrites_paths() {
+    let mut dom = VirtualDom::new(|cx| {
+        let gen = cx.generation();
+        cx.render(rsx! {
+            div {
+                h1 { class: "{gen}" }
+                "{gen}"
+            }
+            (0..3).map(|i| rsx!( p { key: "{i}", "{i}" } ))
+        })
+    });
+
+    _ = dom.rebuild();
+
+    // The div is moved on top of the list, so the paths below it no longer point at elements
+    dom.reparent_subtree(ElementId(1), &[1]).unwrap();
+
+    let audit = dom.audit_paths();
+    assert_eq!(
+        audit.iter().map(|a| a.id).collect::<Vec<_>>(),
+        [ElementId(2), ElementId(3)]
+    );
+    assert_eq!(audit[0].path, ElementPath::Owned(Box::new([1, 0])));
+    assert_eq!(audit[1].path.as_bytes().unwrap(), &[1, 1]);
+
+    // Moving it back restores the original layout
+    dom.reparent_subtree(ElementId(1), &[0]).unwrap();
+    assert!(dom.audit_paths().is_empty());
+
+    assert_eq!(
+        dom.reparent_subtree(ElementId(1), &[]),
+        Err(ReparentError::EmptyPrefix)
+    );
+    assert_eq!(
+        dom.reparent_subtree(ElementId(100), &[0]),
+        Err(ReparentError::MissingElement(ElementId(100)))
+    );
+    assert_eq!(
+        dom.reparent_subtree(ElementId(0), &[0]),
+        Err(ReparentError::Detached(ElementId(0)))
+    );
+}
+
+#[test]
+fn paths_from_bytes() {
+    assert_eq!(ElementPath::from_bytes(&[]), ElementPath::Deep(&[]));
+    assert_eq!(ElementPath::from_bytes(&[3]), ElementPath::Root(3));
+    assert_eq!(
+        ElementPath::from_bytes(&[0, 1, 0]),
+        ElementPath::Owned(Box::new([0, 1, 0]))
+    );
+
+    for path in [
+        ElementPath::Root(2),
+        ElementPath::Deep(&[0, 1]),
+        ElementPath::Owned(Box::new([1, 2, 3])),
+    ] {
+        assert_eq!(
+            ElementPath::from_bytes(path.as_bytes().unwrap()).as_bytes(),
+            path.as_bytes()
+        );
+    }
+}
+
+#[test]
+fn roots_past_a_byte() {
+    let roots: &'static [TemplateNode<'static>] =
+        Box::leak(vec![TemplateNode::Text { text: "root" }; 300].into_boxed_slice());
+    let template =
+        Template { name: "element_paths.rs:2:1:0", roots, node_paths: &[], attr_paths: &[] };
+
+    assert_eq!(ElementPath::Root(255).as_bytes(), Ok(&[255][..]));
+    assert_eq!(ElementPath::Root(256).as_bytes(), Err(RootOutOfRange(256)));
+
+    // the roots can still be found without their bytes
+    assert!(ElementPath::Root(299).resolves_in(&template));
+    assert!(!ElementPath::Root(300).resolves_in(&template));
+    assert!(ElementPath::Root(256) != &[0][..]);
+}
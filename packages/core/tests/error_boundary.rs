@@ -1,6 +1,10 @@
 #![allow(non_snake_case)]
 
+use dioxus::core::{CapturedError, ElementId, Mutation::*};
 use dioxus::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 #[test]
 fn catches_panic() {
@@ -32,3 +36,64 @@ fn ThrowChild(cx: Scope) -> Element {
         div {}
     })
 }
+
+thread_local! {
+    static BOUNDARY: RefCell<Option<Rc<ErrorBoundary>>> = RefCell::new(None);
+}
+
+static THROWN: AtomicBool = AtomicBool::new(false);
+
+#[test]
+fn boundary_renders_fallback() {
+    let mut dom = VirtualDom::new(|cx| {
+        cx.render(rsx! {
+            ErrorBoundary {
+                fallback: |_err: &CapturedError, boundary: &Rc<ErrorBoundary>| {
+                    BOUNDARY.with(|b| *b.borrow_mut() = Some(boundary.clone()));
+                    cx.render(rsx!( "Oops" ))
+                },
+                ThrowOnce {}
+            }
+        })
+    });
+
+    // The child throws, leaving a placeholder until the boundary rerenders
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            CreatePlaceholder { id: ElementId(1) },
+            AppendChildren { m: 1, id: ElementId(0) },
+        ]
+    );
+
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(2) },
+            ReplaceWith { id: ElementId(1), m: 1 },
+        ]
+    );
+
+    // Resetting the boundary retries the children
+    let boundary = BOUNDARY.with(|b| b.borrow_mut().take()).unwrap();
+    assert!(boundary.has_error());
+    boundary.reset();
+
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            ReplaceWith { id: ElementId(2), m: 1 },
+        ]
+    );
+}
+
+fn ThrowOnce(cx: Scope) -> Element {
+    if !THROWN.swap(true, Ordering::SeqCst) {
+        cx.throw("first render")?;
+    }
+
+    cx.render(rsx! {
+        div {}
+    })
+}
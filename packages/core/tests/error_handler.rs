@@ -0,0 +1,66 @@
+//! Errors that no error boundary catches should reach the handler set on the VirtualDom
+
+use dioxus::core::{CapturedError, CapturedPanic, ElementId};
+use dioxus::prelude::*;
+use std::{cell::RefCell, rc::Rc};
+
+fn record_errors(dom: &mut VirtualDom) -> Rc<RefCell<Vec<(ScopeId, String)>>> {
+    let errors = Rc::new(RefCell::new(Vec::new()));
+    let handler_errors = errors.clone();
+    dom.set_error_handler(move |scope, error| {
+        handler_errors
+            .borrow_mut()
+            .push((scope, format!("{:?}", error.error)));
+    });
+    errors
+}
+
+#[test]
+fn thrown_errors_reach_handler() {
+    let mut dom = VirtualDom::new(|cx| cx.render(rsx!(child {})));
+    let errors = record_errors(&mut dom);
+
+    _ = dom.rebuild();
+
+    assert_eq!(errors.borrow()[..], [(ScopeId(1), "\"oops\"".to_string())]);
+}
+
+fn child(cx: Scope) -> Element {
+    cx.throw("oops")
+}
+
+#[test]
+fn panicking_handlers_reach_handler() {
+    let mut dom = VirtualDom::new(|cx| {
+        cx.render(rsx! {
+            button { onclick: move |_| panic!("clicked") }
+        })
+    });
+    let errors = record_errors(&mut dom);
+
+    _ = dom.rebuild();
+    dom.handle_event("click", Rc::new(MouseData::default()), ElementId(1), true);
+
+    let expected = format!(
+        "{:?}",
+        CapturedPanic { message: Some("clicked".to_string()), component_stack: None }
+    );
+    assert_eq!(errors.borrow()[..], [(ScopeId(0), expected)]);
+}
+
+#[test]
+fn boundaries_catch_first() {
+    let mut dom = VirtualDom::new(|cx| {
+        cx.render(rsx! {
+            ErrorBoundary {
+                fallback: |_err: &CapturedError, _boundary: &Rc<ErrorBoundary>| cx.render(rsx!( "Oops" )),
+                child {}
+            }
+        })
+    });
+    let errors = record_errors(&mut dom);
+
+    _ = dom.rebuild();
+
+    assert!(errors.borrow().is_empty());
+}
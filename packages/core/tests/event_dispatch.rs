@@ -0,0 +1,34 @@
+//! Renderers ask the VirtualDom which events they have to handle synchronously and which ones they may defer
+
+use dioxus::core::EventDispatch;
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx!(div {}))
+}
+
+#[test]
+fn events_that_fire_often_are_deferred() {
+    let dom = VirtualDom::new(app);
+
+    assert_eq!(dom.event_dispatch("click"), EventDispatch::Synchronous);
+    assert_eq!(dom.event_dispatch("submit"), EventDispatch::Synchronous);
+    assert_eq!(dom.event_dispatch("mousemove"), EventDispatch::Deferred);
+    assert_eq!(dom.event_dispatch("scroll"), EventDispatch::Deferred);
+}
+
+#[test]
+fn the_app_can_override_the_policy() {
+    let mut dom = VirtualDom::new(app);
+    dom.set_event_dispatch("keydown", EventDispatch::Deferred);
+    dom.set_event_dispatch("wheel", EventDispatch::Synchronous);
+
+    assert_eq!(dom.event_dispatch("keydown"), EventDispatch::Deferred);
+    assert_eq!(dom.event_dispatch("wheel"), EventDispatch::Synchronous);
+
+    let deferred = dom.deferred_events();
+    assert!(deferred.contains(&"keydown".to_string()));
+    assert!(deferred.contains(&"mousemove".to_string()));
+    assert!(!deferred.contains(&"wheel".to_string()));
+    assert!(!deferred.contains(&"click".to_string()));
+}
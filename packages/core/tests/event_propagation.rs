@@ -0,0 +1,175 @@
+//! Capturing listeners run before bubbling ones, stopping propagation should finish the current element, and listener
+//! options change how listeners are attached
+
+use dioxus::core::{ElementId, Mutation, SyntheticEvent};
+use dioxus::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+#[derive(Clone, Copy)]
+enum Stop {
+    Never,
+    Propagation,
+    Immediate,
+}
+
+thread_local! {
+    static CALLS: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+    static STOP: Cell<Stop> = Cell::new(Stop::Never);
+}
+
+fn record(call: &'static str) {
+    CALLS.with(|calls| calls.borrow_mut().push(call));
+}
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx! {
+        div {
+            onclick: move |_| record("outer bubble"),
+            onclick_capture: move |_| record("outer capture"),
+            button {
+                onclick: move |evt| {
+                    record("inner bubble");
+                    evt.prevent_default();
+                },
+                onclick_capture: move |evt| {
+                    record("inner capture");
+                    match STOP.with(|stop| stop.get()) {
+                        Stop::Never => {}
+                        Stop::Propagation => evt.stop_propagation(),
+                        Stop::Immediate => evt.stop_immediate_propagation(),
+                    }
+                }
+            }
+        }
+    })
+}
+
+fn click(stop: Stop) -> (Vec<&'static str>, bool) {
+    STOP.with(|s| s.set(stop));
+    CALLS.with(|calls| calls.borrow_mut().clear());
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let prevented = dom.handle_event("click", Rc::new(MouseData::default()), ElementId(2), true);
+    (CALLS.with(|calls| calls.take()), prevented)
+}
+
+#[test]
+fn capture_then_bubble() {
+    assert_eq!(
+        click(Stop::Never),
+        (
+            vec![
+                "outer capture",
+                "inner capture",
+                "inner bubble",
+                "outer bubble"
+            ],
+            true
+        )
+    );
+}
+
+#[test]
+fn stop_propagation_finishes_element() {
+    assert_eq!(
+        click(Stop::Propagation),
+        (vec!["outer capture", "inner capture", "inner bubble"], true)
+    );
+}
+
+#[test]
+fn stop_immediate_propagation() {
+    assert_eq!(
+        click(Stop::Immediate),
+        (vec!["outer capture", "inner capture"], false)
+    );
+}
+
+#[test]
+fn synthetic_events() {
+    STOP.with(|s| s.set(Stop::Never));
+    CALLS.with(|calls| calls.borrow_mut().clear());
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    // Injected events reach the same listeners as the ones from a renderer
+    let click = SyntheticEvent::new("click", MouseData::default());
+    assert!(click.dispatch(&mut dom, ElementId(2)));
+    assert_eq!(
+        CALLS.with(|calls| calls.take()),
+        [
+            "outer capture",
+            "inner capture",
+            "inner bubble",
+            "outer bubble"
+        ]
+    );
+
+    // Events that don't bubble only run the target's bubbling listener
+    let click = SyntheticEvent::new("click", MouseData::default()).bubbles(false);
+    click.dispatch(&mut dom, ElementId(2));
+    assert_eq!(CALLS.with(|calls| calls.take()), ["inner bubble"]);
+}
+
+#[test]
+fn once_and_passive_listeners() {
+    fn app(cx: Scope) -> Element {
+        let on_click = move |_: MouseEvent| record("once");
+        let on_scroll = move |_: ScrollEvent| record("scroll");
+        cx.render(rsx! {
+            div { onscroll: on_scroll.passive(), button { onclick: on_click.once() } }
+        })
+    }
+
+    CALLS.with(|calls| calls.borrow_mut().clear());
+
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild().santize();
+    assert!(edits.edits.contains(&Mutation::NewEventListener {
+        name: "scroll",
+        id: ElementId(1),
+        passive: true
+    }));
+    assert!(edits.edits.contains(&Mutation::NewEventListener {
+        name: "click",
+        id: ElementId(2),
+        passive: false
+    }));
+
+    let click = SyntheticEvent::new("click", MouseData::default());
+    click.dispatch(&mut dom, ElementId(2));
+    click.dispatch(&mut dom, ElementId(2));
+    assert_eq!(CALLS.with(|calls| calls.take()), ["once"]);
+
+    // Rendering the listener again doesn't rearm it
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    click.dispatch(&mut dom, ElementId(2));
+    assert_eq!(CALLS.with(|calls| calls.take()), Vec::<&str>::new());
+}
+
+#[test]
+fn leaving_ancestors_are_skipped() {
+    STOP.with(|s| s.set(Stop::Never));
+    CALLS.with(|calls| calls.borrow_mut().clear());
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    // The button is still live, so it gets the event, but the div around it is already leaving
+    dom.set_element_leaving(ElementId(1), true);
+    let prevented = dom.handle_event("click", Rc::new(MouseData::default()), ElementId(2), true);
+    assert!(prevented);
+    assert_eq!(
+        CALLS.with(|calls| calls.take()),
+        ["inner capture", "inner bubble"]
+    );
+
+    // Events targeting the leaving div itself are dropped
+    assert!(!dom.handle_event("click", Rc::new(MouseData::default()), ElementId(1), true));
+    assert_eq!(CALLS.with(|calls| calls.take()), Vec::<&str>::new());
+}
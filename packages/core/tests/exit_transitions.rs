@@ -0,0 +1,82 @@
+//! Keyed children of a component with exit transitions should stay mounted until the renderer finishes their exit
+
+use dioxus::core::{BorrowedAttributeValue, ElementId, Mutation::*};
+use dioxus::prelude::*;
+
+fn exiting(id: usize) -> dioxus::core::Mutation<'static> {
+    SetAttribute {
+        name: "data-exiting",
+        value: BorrowedAttributeValue::Text("true"),
+        id: ElementId(id),
+        ns: None,
+    }
+}
+
+#[test]
+fn removed_children_exit_before_they_are_removed() {
+    let mut dom = VirtualDom::new(|cx| {
+        cx.set_exit_transitions(true);
+
+        let order: &[_] = match cx.generation() {
+            0 => &[0, 1, 2],
+            1 => &[0, 2],
+            _ => &[0, 2, 3],
+        };
+
+        cx.render(rsx!(order.iter().map(|i| rsx!(div { key: "{i}" }))))
+    });
+
+    _ = dom.rebuild();
+
+    // The removed child is flagged instead of removed, and doesn't receive events anymore
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(dom.render_immediate().edits, [exiting(2)]);
+    assert_eq!(dom.exiting_elements(), [ElementId(2)]);
+    assert!(dom.element_is_leaving(ElementId(2)));
+
+    // Its id stays taken while it exits, so new children don't reuse it
+    dom.mark_dirty(ScopeId(0));
+    let edits = dom.render_immediate().santize().edits;
+    assert!(edits.contains(&LoadTemplate { name: "template", index: 0, id: ElementId(4) }));
+    assert!(dom.element_is_leaving(ElementId(2)));
+
+    // Once the renderer reports the exit finished, the child is removed for real
+    assert!(dom.finish_exit(ElementId(2)));
+    assert_eq!(dom.render_immediate().edits, [Remove { id: ElementId(2) }]);
+    assert!(dom.exiting_elements().is_empty());
+    assert!(!dom.finish_exit(ElementId(2)));
+}
+
+#[test]
+fn components_exit_with_all_their_roots() {
+    fn row(cx: Scope) -> Element {
+        cx.render(rsx!( "label" div {} div {} ))
+    }
+
+    let mut dom = VirtualDom::new(|cx| {
+        cx.set_exit_transitions(true);
+
+        let order: &[_] = match cx.generation() {
+            0 => &[0, 1],
+            _ => &[0],
+        };
+
+        cx.render(rsx!(order.iter().map(|i| rsx!(row { key: "{i}" }))))
+    });
+
+    _ = dom.rebuild();
+
+    // The text can't be animated, so it leaves right away
+    dom.mark_dirty(ScopeId(0));
+    let edits = dom.render_immediate().edits;
+    assert!(edits.contains(&Remove { id: ElementId(4) }));
+    assert!(edits.contains(&exiting(5)));
+    assert!(edits.contains(&exiting(6)));
+
+    // Finishing either root finishes the whole child
+    assert!(dom.finish_exit(ElementId(6)));
+    let edits = dom.render_immediate().edits;
+    assert!(edits.contains(&Remove { id: ElementId(5) }));
+    assert!(edits.contains(&Remove { id: ElementId(6) }));
+    assert!(dom.exiting_elements().is_empty());
+}
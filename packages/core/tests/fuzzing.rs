@@ -213,6 +213,7 @@ fn create_random_dynamic_attr(cx: &ScopeState) -> Attribute {
         namespace: random_ns(),
         mounted_element: Default::default(),
         volatile: rand::random(),
+        live: None,
     }
 }
 
@@ -258,6 +259,7 @@ fn create_random_element(cx: Scope<DepthProps>) -> Element {
                                     format!("{}", rand::random::<usize>()).into_boxed_str(),
                                 ),
                                 id: Default::default(),
+                                live: None,
                             }),
                             DynamicNodeType::Other => {
                                 create_random_dynamic_node(cx, cx.props.depth + 1)
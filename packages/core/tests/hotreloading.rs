@@ -1 +1,109 @@
-//! It should be possible to swap out templates at runtime, enabling hotreloading
+//! It should be possible to swap out templates and the code of components at runtime, enabling hotreloading
+
+use dioxus::core::{ElementId, HookReload, Mutation::*};
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx! {
+        div {
+            (0..1).map(|i| rsx!( span { key: "{i}", "hello" } ))
+        }
+    })
+}
+
+#[test]
+fn nested_template_replaced() {
+    let mut dom = VirtualDom::new(app);
+
+    let name: &'static str = {
+        let edits = dom.rebuild();
+        let span = edits
+            .templates
+            .iter()
+            .find(|t| matches!(t.roots, [TemplateNode::Element { tag: "span", .. }]))
+            .unwrap();
+        Box::leak(span.name.to_string().into_boxed_str())
+    };
+
+    // The new structure swaps the element out entirely, not just its text
+    dom.replace_template(Template {
+        name,
+        roots: &[TemplateNode::Element {
+            tag: "p",
+            namespace: None,
+            attrs: &[],
+            children: &[TemplateNode::Text { text: "goodbye" }],
+        }],
+        node_paths: &[],
+        attr_paths: &[],
+    });
+
+    let edits = dom.render_immediate();
+    assert_eq!(edits.templates.len(), 1);
+    assert_eq!(
+        edits.santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(3) },
+            ReplaceWith { id: ElementId(2), m: 1 },
+        ]
+    );
+}
+
+fn show(cx: &ScopeState, text: String) -> Element {
+    cx.render(rsx!("{text}"))
+}
+
+fn counter(cx: Scope) -> Element {
+    let count = cx.use_hook(|| 0);
+    *count += 1;
+    show(cx, count.to_string())
+}
+
+fn edited_counter(cx: Scope) -> Element {
+    let count = cx.use_hook(|| 0);
+    *count += 10;
+    show(cx, count.to_string())
+}
+
+fn greeting(cx: Scope) -> Element {
+    let name = cx.use_hook(|| "fresh".to_string());
+    show(cx, name.clone())
+}
+
+fn counter_app(cx: Scope) -> Element {
+    cx.render(rsx! { counter {} })
+}
+
+#[test]
+fn replaced_components_keep_their_hooks() {
+    let mut dom = VirtualDom::new(counter_app);
+    _ = dom.rebuild();
+
+    // the parent still renders the old code, which runs the new code in the same scope
+    dom.replace_component(counter, edited_counter, HookReload::KeepMatching);
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [SetText { value: "11", id: ElementId(1) }]
+    );
+
+    // hooks that changed type start over
+    dom.replace_component(edited_counter, greeting, HookReload::KeepMatching);
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [SetText { value: "fresh", id: ElementId(1) }]
+    );
+    assert_eq!(dom.inspect()[1].hooks.len(), 1);
+}
+
+#[test]
+fn replaced_components_can_reset() {
+    let mut dom = VirtualDom::new(counter_app);
+    _ = dom.rebuild();
+
+    dom.replace_component(counter, edited_counter, HookReload::Reset);
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [SetText { value: "10", id: ElementId(1) }]
+    );
+}
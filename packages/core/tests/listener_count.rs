@@ -0,0 +1,35 @@
+//! The VirtualDom keeps track of the most listeners that have been live at once
+
+use dioxus::prelude::*;
+
+#[test]
+fn peak_listeners() {
+    let mut dom = VirtualDom::new(|cx| {
+        let count = match cx.generation() % 2 {
+            0 => 3,
+            _ => 1,
+        };
+
+        cx.render(rsx! {
+            (0..count).map(|i| rsx!( button { key: "{i}", onclick: move |_| {} } ))
+        })
+    });
+
+    _ = dom.rebuild();
+    assert_eq!(dom.peak_listener_count(), 3);
+
+    // Cycling down to fewer listeners should not move the peak
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(dom.peak_listener_count(), 3);
+
+    dom.reset_peak_listener_count();
+    assert_eq!(dom.peak_listener_count(), 1);
+
+    // Cycling back up should only ever reach the same peak
+    for _ in 0..4 {
+        dom.mark_dirty(ScopeId(0));
+        _ = dom.render_immediate();
+    }
+    assert_eq!(dom.peak_listener_count(), 3);
+}
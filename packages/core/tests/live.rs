@@ -0,0 +1,115 @@
+//! Live values should patch the text and attributes displaying them without running the component again
+
+use dioxus::core::{BorrowedAttributeValue, ElementId, Live, LiveId, Mutation::*};
+use dioxus::prelude::*;
+use std::cell::Cell;
+use std::rc::Rc;
+
+thread_local! {
+    static RENDERS: Cell<usize> = Cell::new(0);
+}
+
+fn renders() -> usize {
+    RENDERS.with(|renders| renders.get())
+}
+
+fn text_id(edits: &[Mutation]) -> ElementId {
+    edits
+        .iter()
+        .find_map(|edit| match edit {
+            HydrateText { id, .. } => Some(*id),
+            _ => None,
+        })
+        .unwrap()
+}
+
+fn set_title(value: &str) -> Mutation {
+    SetAttribute {
+        name: "title",
+        value: BorrowedAttributeValue::Text(value),
+        id: ElementId(1),
+        ns: None,
+    }
+}
+
+// A button that displays a live value, sending the values it's clicked with without changing what it renders
+fn app(cx: Scope) -> Element {
+    RENDERS.with(|renders| renders.set(renders.get() + 1));
+
+    let id = *cx.use_hook(LiveId::new);
+    let update = cx.use_hook(|| cx.schedule_live_update()).clone();
+    let clicks = cx.use_hook(|| Rc::new(Cell::new(0))).clone();
+
+    let title = Live { id, value: 0 };
+    let text = Live { id, value: 0 };
+
+    cx.render(rsx! {
+        button {
+            title: title,
+            onclick: move |_| {
+                clicks.set(clicks.get() + 1);
+                update(id, clicks.get().to_string());
+            },
+            text
+        }
+    })
+}
+
+#[test]
+fn text_and_attributes_patched_in_place() {
+    RENDERS.with(|renders| renders.set(0));
+    let mut dom = VirtualDom::new(app);
+    let text = text_id(&dom.rebuild().edits);
+
+    for value in ["1", "2"] {
+        dom.handle_event("click", Rc::new(MouseData::default()), ElementId(1), true);
+        assert_eq!(
+            dom.render_immediate().edits,
+            [set_title(value), SetText { value, id: text }]
+        );
+    }
+    assert_eq!(renders(), 1);
+
+    // Only the last value sent before rendering is patched in
+    dom.handle_event("click", Rc::new(MouseData::default()), ElementId(1), true);
+    dom.handle_event("click", Rc::new(MouseData::default()), ElementId(1), true);
+    assert_eq!(
+        dom.render_immediate().edits,
+        [set_title("4"), SetText { value: "4", id: text }]
+    );
+
+    // Rendering again diffs against the dom, which has the patched value instead of the one rendered last time
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        dom.render_immediate().edits,
+        [set_title("0"), SetText { value: "0", id: text }]
+    );
+    assert_eq!(renders(), 2);
+
+    // Until it's patched again, the value rendered is the value in the dom
+    dom.mark_dirty(ScopeId(0));
+    assert!(dom.render_immediate().edits.is_empty());
+}
+
+#[test]
+fn patched_text_is_not_kept_in_the_frames() {
+    fn app(cx: Scope) -> Element {
+        let id = *cx.use_hook(LiveId::new);
+        let update = cx.use_hook(|| cx.schedule_live_update()).clone();
+        let text = Live { id, value: "" };
+
+        cx.render(rsx! {
+            button { onclick: move |_| update(id, "patched".repeat(1000)), text }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    let bytes = dom.total_frame_arena_bytes();
+
+    for _ in 0..100 {
+        dom.handle_event("click", Rc::new(MouseData::default()), ElementId(1), true);
+        _ = dom.render_immediate();
+    }
+    assert_eq!(dom.total_frame_arena_bytes(), bytes);
+}
@@ -0,0 +1,54 @@
+//! Frames that rendered a huge tree once should give the memory back once they only need a little
+
+use dioxus::core::MemoryBudget;
+use dioxus::prelude::*;
+
+const BUDGET: usize = 64 * 1024;
+
+fn app(cx: Scope) -> Element {
+    let rows = match cx.generation() {
+        0 => 10_000,
+        _ => 3,
+    };
+
+    cx.render(rsx! {
+        (0..rows).map(|i| rsx!( p { key: "{i}", "{i}" } ))
+    })
+}
+
+fn render_small_frames(dom: &mut VirtualDom) -> usize {
+    _ = dom.rebuild();
+    let after_big = dom.scope_frame_arena_bytes(ScopeId(0)).unwrap();
+    assert!(after_big > BUDGET);
+
+    for _ in 0..8 {
+        dom.mark_dirty(ScopeId(0));
+        _ = dom.render_immediate();
+    }
+
+    dom.scope_frame_arena_bytes(ScopeId(0)).unwrap()
+}
+
+#[test]
+fn oversized_frames_shrink() {
+    let mut dom = VirtualDom::new(app);
+    dom.set_memory_budget(Some(MemoryBudget { max_frame_bytes: BUDGET, frames: 2 }));
+
+    assert!(render_small_frames(&mut dom) < BUDGET);
+    assert_eq!(
+        dom.scope_frame_arena_bytes(ScopeId(0)),
+        Some(dom.total_frame_arena_bytes())
+    );
+}
+
+#[test]
+fn frames_keep_memory_without_budget() {
+    let mut dom = VirtualDom::new(app);
+    assert!(render_small_frames(&mut dom) > BUDGET);
+}
+
+#[test]
+fn missing_scope_has_no_usage() {
+    let dom = VirtualDom::new(app);
+    assert_eq!(dom.scope_frame_arena_bytes(ScopeId(100)), None);
+}
@@ -0,0 +1,37 @@
+//! Attributes that are `None` are left out of the element
+
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::prelude::*;
+
+#[test]
+fn optional_attributes() {
+    let mut dom = VirtualDom::new(|cx| {
+        let active = cx.generation() % 2 == 1;
+
+        cx.render(rsx! {
+            div {
+                class: if active { "selected" },
+                title: None::<&str>,
+            }
+        })
+    });
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            AppendChildren { m: 1, id: ElementId(0) },
+        ]
+    );
+
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [SetAttribute {
+            name: "class",
+            value: BorrowedAttributeValue::Text("selected"),
+            id: ElementId(1),
+            ns: None
+        }]
+    );
+}
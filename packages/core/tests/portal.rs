@@ -0,0 +1,94 @@
+//! Portals mount their children under a different element, but still own them
+
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::prelude::*;
+use std::cell::Cell;
+
+thread_local! {
+    static SHOW: Cell<bool> = Cell::new(true);
+    static TARGET: Cell<ElementId> = Cell::new(ElementId(1));
+}
+
+#[test]
+fn mounts_under_target() {
+    fn app(cx: Scope) -> Element {
+        let show = SHOW.with(|show| show.get());
+        cx.render(rsx! {
+            div {
+                show.then(|| rsx! {
+                    Portal { target: ElementId(1), p { "hello" } }
+                })
+            }
+        })
+    }
+
+    SHOW.with(|show| show.set(true));
+    let mut dom = VirtualDom::new(app);
+    assert_eq!(dom.create_anchor(), ElementId(1));
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(2) },
+            LoadTemplate { name: "template", index: 0, id: ElementId(3) },
+            AppendChildren { id: ElementId(1), m: 1 },
+            CreatePlaceholder { id: ElementId(4) },
+            ReplacePlaceholder { path: &[0], m: 1 },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+
+    // Removing the portal removes its children from the target too
+    SHOW.with(|show| show.set(false));
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            CreatePlaceholder { id: ElementId(5) },
+            Remove { id: ElementId(3) },
+            ReplaceWith { id: ElementId(4), m: 1 },
+        ]
+    );
+}
+
+#[test]
+fn moves_to_new_target() {
+    fn app(cx: Scope) -> Element {
+        let target = TARGET.with(|target| target.get());
+        cx.render(rsx! {
+            div {
+                Portal { target: target, p { "hello" } }
+            }
+        })
+    }
+
+    TARGET.with(|target| target.set(ElementId(1)));
+    let mut dom = VirtualDom::new(app);
+    assert_eq!(dom.create_anchor(), ElementId(1));
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(2) },
+            LoadTemplate { name: "template", index: 0, id: ElementId(3) },
+            AppendChildren { id: ElementId(1), m: 1 },
+            CreatePlaceholder { id: ElementId(4) },
+            ReplacePlaceholder { path: &[0], m: 1 },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+
+    let anchor = dom.create_anchor();
+    TARGET.with(|target| target.set(anchor));
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            PushRoot { id: ElementId(3) },
+            AppendChildren { id: anchor, m: 1 },
+        ]
+    );
+
+    // Dropping the dom with the portal still mounted cleans up the children and the placeholder
+    drop(dom);
+}
@@ -0,0 +1,80 @@
+//! Dirty scopes in lower priority lanes should give way to the deadline, except for the updates of an event
+
+#![allow(non_snake_case)]
+
+use dioxus::core::{ElementId, Mutation::*, Priority};
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+fn app(cx: Scope) -> Element {
+    let gen = cx.generation();
+    cx.render(rsx!( div { "{gen}" } ))
+}
+
+#[tokio::test]
+async fn idle_lane_yields_to_deadline() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    dom.mark_dirty_with_priority(ScopeId(0), Priority::Idle);
+
+    // The deadline has already passed, so the idle update waits for the next render
+    assert!(dom
+        .render_with_deadline(std::future::ready(()))
+        .await
+        .edits
+        .is_empty());
+
+    // render_immediate always flushes every lane
+    assert_eq!(
+        dom.render_immediate().edits,
+        [SetText { value: "1", id: ElementId(2) }]
+    );
+}
+
+#[tokio::test]
+async fn user_input_upgrades_lane() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    dom.mark_dirty_with_priority(ScopeId(0), Priority::Idle);
+    dom.mark_dirty(ScopeId(0));
+
+    // Marking the scope dirty again from user input moves it ahead of the deadline
+    assert_eq!(
+        dom.render_with_deadline(std::future::ready(())).await.edits,
+        [SetText { value: "1", id: ElementId(2) }]
+    );
+}
+
+#[tokio::test]
+async fn event_renders_in_one_batch() {
+    let mut dom = VirtualDom::new(|cx| {
+        let count = use_state(cx, || 0);
+        cx.render(rsx! {
+            button { onclick: move |_| count.set(*count.get() + 1), "{count}" }
+            Child { gen: *count.get() }
+        })
+    });
+    _ = dom.rebuild();
+
+    dom.handle_event("click", Rc::new(MouseData::default()), ElementId(1), true);
+
+    // The child the click re-renders comes along with the parent, even though the deadline already passed
+    let edits = dom.render_with_deadline(std::future::ready(())).await.edits;
+    let texts: Vec<_> = edits
+        .iter()
+        .filter_map(|edit| match edit {
+            SetText { value, .. } => Some(*value),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(texts, ["1", "1"]);
+
+    assert!(dom.render_immediate().edits.is_empty());
+}
+
+#[inline_props]
+fn Child(cx: Scope, gen: usize) -> Element {
+    cx.render(rsx!( p { "{gen}" } ))
+}
@@ -0,0 +1,82 @@
+//! Replaying a recording should write the same edits the VirtualDom produced while it was recorded
+
+use dioxus::core::{ElementId, Mutations, RecordedEvent};
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+fn app(cx: Scope) -> Element {
+    let gen = cx.generation();
+    cx.render(rsx! {
+        button { onclick: move |_| cx.needs_update(), "{gen}" }
+    })
+}
+
+// The edits borrow the VirtualDom, so they're kept around by how they print
+#[derive(Default, Clone, Debug, PartialEq)]
+struct Written {
+    templates: Vec<String>,
+    edits: Vec<String>,
+}
+
+impl Written {
+    fn add(&mut self, mutations: &Mutations) {
+        let templates = mutations.templates.iter().map(|t| format!("{:?}", t));
+        self.templates.extend(templates);
+        let edits = mutations.edits.iter().map(|edit| format!("{:?}", edit));
+        self.edits.extend(edits);
+    }
+
+    fn of(mutations: &Mutations) -> Self {
+        let mut written = Self::default();
+        written.add(mutations);
+        written
+    }
+}
+
+#[test]
+fn replay_matches_live_edits() {
+    let mut dom = VirtualDom::new(app);
+    dom.start_recording();
+
+    let mut live = Mutations::default();
+    dom.rebuild_to(&mut live);
+    let rebuilt = Written::of(&live);
+    let mut all = rebuilt.clone();
+
+    for _ in 0..2 {
+        dom.handle_event("click", Rc::new(MouseData::default()), ElementId(1), true);
+        let mut live = Mutations::default();
+        dom.render_immediate_to(&mut live);
+        all.add(&live);
+    }
+
+    // Nothing is dirty, so this frame doesn't show up in the recording
+    let mut live = Mutations::default();
+    dom.render_immediate_to(&mut live);
+    assert_eq!(Written::of(&live), Written::default());
+
+    let recording = dom.stop_recording().unwrap();
+    assert_eq!(recording.len(), 3);
+    assert!(recording.steps()[0].events().is_empty());
+    assert_eq!(
+        recording.steps()[1].events(),
+        [RecordedEvent { name: "click".to_string(), element: ElementId(1), bubbles: true }]
+    );
+
+    let mut replayed = Mutations::default();
+    recording.replay_to(usize::MAX, &mut replayed);
+    assert_eq!(Written::of(&replayed), all);
+
+    let mut first = Mutations::default();
+    recording.replay_to(1, &mut first);
+    assert_eq!(Written::of(&first), rebuilt);
+}
+
+#[test]
+fn nothing_recorded_unless_asked() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert!(dom.recording().is_none());
+    assert!(dom.stop_recording().is_none());
+}
@@ -0,0 +1,95 @@
+//! Every render remembers what asked for it
+
+use dioxus::core::{ElementId, RenderReason};
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+fn reason(dom: &VirtualDom, id: usize) -> Option<RenderReason> {
+    dom.get_scope(ScopeId(id)).unwrap().last_render_reason()
+}
+
+#[test]
+fn events_and_parents() {
+    fn app(cx: Scope) -> Element {
+        let count = use_state(cx, || 0);
+        cx.render(rsx! {
+            button { onclick: move |_| count.set(**count + 1), "{count}" }
+            child { count: **count }
+        })
+    }
+
+    #[inline_props]
+    fn child(cx: Scope, count: i32) -> Element {
+        cx.render(rsx! { "{count}" })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    assert_eq!(reason(&dom, 0), Some(RenderReason::Created));
+    assert_eq!(reason(&dom, 1), Some(RenderReason::Created));
+
+    dom.handle_event("click", Rc::new(MouseData::default()), ElementId(1), true);
+    _ = dom.render_immediate();
+    assert_eq!(
+        reason(&dom, 0),
+        Some(RenderReason::Event { name: "click".to_string() })
+    );
+    assert_eq!(
+        reason(&dom, 1),
+        Some(RenderReason::Parent { scope: ScopeId(0) })
+    );
+
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(reason(&dom, 0), Some(RenderReason::Scheduled));
+    assert_eq!(dom.inspect()[0].last_render_reason, reason(&dom, 0));
+}
+
+#[test]
+fn contexts_and_signals() {
+    fn app(cx: Scope) -> Element {
+        cx.use_hook(|| cx.provide_context(0));
+        cx.render(rsx! { consumer {} })
+    }
+
+    fn consumer(cx: Scope) -> Element {
+        let value = cx.consume_context::<i32>().unwrap();
+        cx.render(rsx! { "{value}" })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    dom.base_scope().replace_context(1);
+    _ = dom.render_immediate();
+    assert_eq!(reason(&dom, 1), Some(RenderReason::Context));
+
+    // Signals and atoms update the components that read them like this
+    let update = dom
+        .base_scope()
+        .schedule_update_any_with_reason(RenderReason::Signal);
+    update(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(reason(&dom, 0), Some(RenderReason::Signal));
+}
+
+#[tokio::test]
+async fn tasks() {
+    fn app(cx: Scope) -> Element {
+        cx.use_hook(|| {
+            let update = cx.schedule_update();
+            cx.spawn(async move {
+                tokio::task::yield_now().await;
+                update();
+            })
+        });
+        cx.render(rsx!(()))
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    dom.wait_for_work().await;
+    _ = dom.render_immediate();
+    assert!(matches!(reason(&dom, 0), Some(RenderReason::Task { .. })));
+}
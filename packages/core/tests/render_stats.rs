@@ -0,0 +1,48 @@
+//! Render stats should count the renders of each component, and flag the ones that didn't change anything
+
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    let generation = cx.generation();
+    cx.render(rsx! {
+        div { "{generation}" }
+        child {}
+    })
+}
+
+fn child(cx: Scope) -> Element {
+    cx.render(rsx!( div { "always the same" } ))
+}
+
+#[test]
+fn wasted_renders_are_flagged() {
+    let mut dom = VirtualDom::new(app);
+    dom.track_render_stats(true);
+    _ = dom.rebuild();
+
+    // Creating components doesn't count as rendering again
+    assert!(dom.render_stats().is_empty());
+
+    dom.mark_dirty(ScopeId(0));
+    dom.mark_dirty(ScopeId(1));
+    _ = dom.render_immediate();
+
+    dom.mark_dirty(ScopeId(1));
+    _ = dom.render_immediate();
+
+    // The child rendered the same thing every time, so it comes first
+    let stats = dom.render_stats();
+    assert_eq!(stats.len(), 2);
+    assert_eq!(stats[0].scope, ScopeId(1));
+    assert_eq!(stats[0].name, "child");
+    assert_eq!(stats[0].renders, 2);
+    assert_eq!(stats[0].wasted_renders, 2);
+
+    // The app changed its text, so its render wasn't wasted
+    assert_eq!(stats[1].scope, ScopeId(0));
+    assert_eq!(stats[1].renders, 1);
+    assert_eq!(stats[1].wasted_renders, 0);
+
+    dom.track_render_stats(false);
+    assert!(dom.render_stats().is_empty());
+}
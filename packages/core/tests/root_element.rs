@@ -0,0 +1,38 @@
+//! A VirtualDom mounted under an existing element should shift every id it hands to the renderer
+
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+#[test]
+fn ids_are_shifted_past_the_root() {
+    let mut dom = VirtualDom::new(|cx| {
+        let count = cx.use_hook(|| 0);
+        cx.render(rsx! {
+            button { onclick: move |_| *count += 1, "{count}" }
+        })
+    })
+    .with_root_element(ElementId(100));
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(101) },
+            NewEventListener { name: "click", id: ElementId(101), passive: false },
+            HydrateText { path: &[0], value: "0", id: ElementId(102) },
+            AppendChildren { m: 1, id: ElementId(100) },
+        ]
+    );
+
+    // Events use the shifted ids too, so the unshifted id doesn't belong to this dom
+    dom.handle_event("click", Rc::new(MouseData::default()), ElementId(1), true);
+    dom.mark_dirty(ScopeId(0));
+    assert!(dom.render_immediate().edits.is_empty());
+
+    dom.handle_event("click", Rc::new(MouseData::default()), ElementId(101), true);
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [SetText { value: "1", id: ElementId(102) }]
+    );
+}
@@ -0,0 +1,68 @@
+#![allow(non_snake_case)]
+//! Mount callbacks run once the edits are applied, and drop callbacks run right before the scope is torn down
+
+use dioxus::prelude::*;
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    static LOG: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+    static SHOW: Cell<bool> = Cell::new(true);
+}
+
+fn log(event: &'static str) {
+    LOG.with(|log| log.borrow_mut().push(event));
+}
+
+fn take_log() -> Vec<&'static str> {
+    LOG.with(|log| log.take())
+}
+
+fn app(cx: Scope) -> Element {
+    cx.use_hook(|| {
+        cx.push_on_mount(|| log("app mounted"));
+        cx.push_on_drop(|| log("app dropped"));
+    });
+
+    let show = SHOW.with(|show| show.get());
+    cx.render(rsx! {
+        show.then(|| rsx! { Child {} })
+    })
+}
+
+fn Child(cx: Scope) -> Element {
+    cx.use_hook(|| {
+        cx.push_on_mount(|| log("child mounted"));
+        cx.push_on_drop(|| log("child dropped"));
+    });
+
+    cx.render(rsx! { div {} })
+}
+
+#[test]
+fn callbacks_run_once() {
+    SHOW.with(|show| show.set(true));
+    take_log();
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    // Nothing is mounted until the renderer has applied the edits
+    assert!(take_log().is_empty());
+
+    dom.mutations_applied();
+    assert_eq!(take_log(), ["child mounted", "app mounted"]);
+
+    // Mount callbacks only ever run once
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    dom.mutations_applied();
+    assert!(take_log().is_empty());
+
+    SHOW.with(|show| show.set(false));
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(take_log(), ["child dropped"]);
+
+    drop(dom);
+    assert_eq!(take_log(), ["app dropped"]);
+}
@@ -0,0 +1,62 @@
+//! Mutations should carry the accessibility information of the elements they touch once semantics are tracked
+
+use dioxus::core::{ElementId, SemanticsUpdate};
+use dioxus::prelude::*;
+
+fn set(id: usize, role: &str, name: &str, states: &[(&str, &str)]) -> SemanticsUpdate {
+    SemanticsUpdate::Set {
+        id: ElementId(id),
+        role: Some(role.to_string()),
+        name: Some(name.to_string()),
+        states: states
+            .iter()
+            .map(|(state, value)| (state.to_string(), value.to_string()))
+            .collect(),
+    }
+}
+
+#[test]
+fn semantics_follow_attributes() {
+    let mut dom = VirtualDom::new(|cx| {
+        let gen = cx.generation();
+        cx.render(rsx! {
+            div {
+                button { disabled: "{gen}", "Save" }
+            }
+        })
+    });
+    dom.track_semantics(true);
+
+    // The div has nothing to describe, but the button's role and name come from its tag and text
+    assert_eq!(
+        dom.rebuild().semantics,
+        [set(2, "button", "Save", &[("disabled", "0")])]
+    );
+
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        dom.render_immediate().semantics,
+        [set(2, "button", "Save", &[("disabled", "1")])]
+    );
+}
+
+#[test]
+fn explicit_roles_and_aria() {
+    let mut dom = VirtualDom::new(|cx| {
+        cx.render(rsx! {
+            div { role: "alert", aria_live: "polite", "Saved" }
+        })
+    });
+    dom.track_semantics(true);
+
+    assert_eq!(
+        dom.rebuild().semantics,
+        [set(1, "alert", "Saved", &[("live", "polite")])]
+    );
+}
+
+#[test]
+fn untracked_by_default() {
+    let mut dom = VirtualDom::new(|cx| cx.render(rsx!( button { "Save" } )));
+    assert!(dom.rebuild().semantics.is_empty());
+}
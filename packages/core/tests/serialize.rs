@@ -0,0 +1,99 @@
+//! Mutations sent to a renderer in another process should read back as the same templates and edits
+#![cfg(feature = "serialize")]
+
+use dioxus::core::{BorrowedAttributeValue, ElementId, Mutation::*, Mutations};
+use dioxus::prelude::*;
+use std::cell::RefCell;
+
+#[test]
+fn nested_templates_round_trip() {
+    let mut dom = VirtualDom::new(|cx| {
+        cx.render(rsx! {
+            div { class: "outer",
+                (0..2).map(|i| rsx! {
+                    ul { key: "{i}",
+                        li { "item {i}" }
+                        (0..2).map(|j| rsx! { li { key: "{j}", "nested {j}" } })
+                    }
+                })
+                if true {
+                    rsx!( span { "shown" } )
+                }
+            }
+        })
+    });
+
+    let edits = dom.rebuild();
+    assert!(edits.templates.len() > 1);
+
+    let json = serde_json::to_string(&edits).unwrap();
+    let read: Mutations = serde_json::from_str(&json).unwrap();
+    assert_eq!(read.templates, edits.templates);
+    assert_eq!(read.edits, edits.edits);
+}
+
+#[test]
+fn attribute_values_round_trip() {
+    let edits = Mutations {
+        edits: vec![
+            SetAttribute {
+                name: "title",
+                value: BorrowedAttributeValue::Text("big"),
+                id: ElementId(1),
+                ns: None,
+            },
+            SetAttribute {
+                name: "opacity",
+                value: BorrowedAttributeValue::Float(0.5),
+                id: ElementId(1),
+                ns: None,
+            },
+            SetAttribute {
+                name: "tabindex",
+                value: BorrowedAttributeValue::Int(-1),
+                id: ElementId(1),
+                ns: None,
+            },
+            SetAttribute {
+                name: "hidden",
+                value: BorrowedAttributeValue::Bool(true),
+                id: ElementId(1),
+                ns: None,
+            },
+            SetAttribute {
+                name: "alt",
+                value: BorrowedAttributeValue::None,
+                id: ElementId(1),
+                ns: None,
+            },
+            SetAttribute {
+                name: "color",
+                value: BorrowedAttributeValue::Text("red"),
+                id: ElementId(1),
+                ns: Some("style"),
+            },
+        ],
+        ..Default::default()
+    };
+
+    let json = serde_json::to_string(&edits).unwrap();
+    let read: Mutations = serde_json::from_str(&json).unwrap();
+    assert_eq!(read.edits, edits.edits);
+}
+
+#[test]
+fn any_values_are_not_serialized() {
+    let any: RefCell<Box<dyn AnyValue>> = RefCell::new(Box::new(1u8));
+    let edits = Mutations {
+        edits: vec![SetAttribute {
+            name: "data",
+            value: BorrowedAttributeValue::Any(std::cell::Ref::map(any.borrow(), |any| &**any)),
+            id: ElementId(1),
+            ns: None,
+        }],
+        ..Default::default()
+    };
+
+    // any values only live in the VirtualDom, so they fail to serialize instead of panicking
+    assert!(serde_json::to_string(&edits).is_err());
+}
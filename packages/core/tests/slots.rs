@@ -0,0 +1,42 @@
+#![allow(non_snake_case)]
+
+//! Components can take more than one element from their parent as named slots
+
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::prelude::*;
+
+#[test]
+fn named_slots() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! {
+            Layout {
+                header: rsx! { h1 { "Title" } },
+            }
+        })
+    }
+
+    #[derive(Props)]
+    struct LayoutProps<'a> {
+        header: Element<'a>,
+        footer: Element<'a>,
+    }
+
+    fn Layout<'a>(cx: Scope<'a, LayoutProps<'a>>) -> Element {
+        cx.render(rsx! {
+            &cx.props.header,
+            &cx.props.footer,
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+
+    // the footer wasn't passed, so it's left empty
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            CreatePlaceholder { id: ElementId(2) },
+            AppendChildren { m: 2, id: ElementId(0) },
+        ]
+    );
+}
@@ -0,0 +1,77 @@
+//! Attributes spread onto an element with `..attributes` are created and diffed by name
+
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::prelude::*;
+use dioxus_core::BorrowedAttributeValue;
+
+#[test]
+fn spread_attributes() {
+    let mut dom = VirtualDom::new(|cx| {
+        let extra = match cx.generation() {
+            0 => vec![("aria-label", "Close"), ("class", "ignored")],
+            _ => vec![("aria-label", "Close"), ("data-state", "open")],
+        };
+
+        cx.render(rsx! {
+            div { ..extra, class: "button" }
+        })
+    });
+
+    // The class written on the element wins over the spread one
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            SetAttribute {
+                name: "aria-label",
+                value: BorrowedAttributeValue::Text("Close"),
+                id: ElementId(1),
+                ns: None
+            },
+            AppendChildren { m: 1, id: ElementId(0) },
+        ]
+    );
+
+    // Only the attributes that changed are set
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [SetAttribute {
+            name: "data-state",
+            value: BorrowedAttributeValue::Text("open"),
+            id: ElementId(1),
+            ns: None
+        }]
+    );
+
+    // And nothing is set when the spread stays the same
+    dom.mark_dirty(ScopeId(0));
+    assert!(dom.render_immediate().santize().edits.is_empty());
+}
+
+#[test]
+fn removed_spread_attributes() {
+    let mut dom = VirtualDom::new(|cx| {
+        let extra = match cx.generation() % 2 {
+            0 => vec![("title", "Hello")],
+            _ => vec![],
+        };
+
+        cx.render(rsx! {
+            div { ..extra }
+        })
+    });
+
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [SetAttribute {
+            name: "title",
+            value: BorrowedAttributeValue::None,
+            id: ElementId(1),
+            ns: None
+        }]
+    );
+}
@@ -0,0 +1,315 @@
+//! The SuspenseBoundary component should show its fallback until every suspended child resolves
+
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::prelude::*;
+use std::time::Duration;
+
+#[test]
+fn fallback_swaps_for_children() {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let mut dom = VirtualDom::new(app);
+
+            // The fallback fills the boundary's slot while the child is suspended
+            assert_eq!(
+                dom.rebuild().santize().edits,
+                [
+                    LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+                    LoadTemplate { name: "template", index: 0, id: ElementId(4) },
+                    ReplacePlaceholder { path: &[0], m: 1 },
+                    AppendChildren { m: 1, id: ElementId(0) },
+                ]
+            );
+            assert!(dom.has_suspended_work());
+
+            dom.wait_for_work().await;
+
+            // Once the child resolves, the fallback is replaced with the children in one edit
+            assert_eq!(
+                dom.render_immediate().santize().edits,
+                [
+                    LoadTemplate { name: "template", index: 0, id: ElementId(2) },
+                    AssignId { path: &[0], id: ElementId(3) },
+                    ReplaceWith { id: ElementId(4), m: 1 },
+                    LoadTemplate { name: "template", index: 0, id: ElementId(5) },
+                    ReplaceWith { id: ElementId(3), m: 1 },
+                ]
+            );
+            assert!(!dom.has_suspended_work());
+        });
+}
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx!(
+        div {
+            SuspenseBoundary {
+                fallback: |cx| cx.render(rsx!( "Loading..." )),
+                div { async_child {} }
+            }
+        }
+    ))
+}
+
+async fn async_child(cx: Scope<'_>) -> Element {
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    cx.render(rsx!("Done!"))
+}
+
+#[test]
+fn rerender_diffs_resolved_nodes() {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let mut dom = VirtualDom::new(|cx| {
+                cx.render(rsx!(
+                    SuspenseBoundary {
+                        fallback: |cx| cx.render(rsx!( "Loading..." )),
+                        counting_child {}
+                    }
+                ))
+            });
+
+            // A boundary at the root of a template leaves its fallback on the stack
+            assert_eq!(
+                dom.rebuild().santize().edits,
+                [
+                    LoadTemplate { name: "template", index: 0, id: ElementId(2) },
+                    AppendChildren { m: 1, id: ElementId(0) },
+                ]
+            );
+
+            // So does a suspended component at the root of the children, until it resolves
+            dom.wait_for_work().await;
+            assert_eq!(
+                dom.render_immediate().santize().edits,
+                [
+                    CreatePlaceholder { id: ElementId(1) },
+                    ReplaceWith { id: ElementId(2), m: 1 },
+                    CreateTextNode { value: "1", id: ElementId(3) },
+                    ReplaceWith { id: ElementId(1), m: 1 },
+                ]
+            );
+            assert!(!dom.has_suspended_work());
+
+            // While the child waits on its next render, the old nodes stay mounted
+            dom.mark_dirty(ScopeId(2));
+            assert!(dom.render_immediate().santize().edits.is_empty());
+            assert!(dom.has_suspended_work());
+
+            // Once it resolves, the new nodes are diffed against the old ones instead of being recreated
+            dom.wait_for_work().await;
+            assert_eq!(
+                dom.render_immediate().santize().edits,
+                [SetText { value: "2", id: ElementId(3) }]
+            );
+            assert!(!dom.has_suspended_work());
+        });
+}
+
+async fn counting_child(cx: Scope<'_>) -> Element {
+    let count = cx.use_hook(|| 0);
+    *count += 1;
+    let count = *count;
+
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    cx.render(rsx!("{count}"))
+}
+
+#[test]
+fn parent_rerenders_while_suspended() {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let mut dom = VirtualDom::new(|cx| {
+                let generation = cx.generation();
+                cx.render(rsx!(
+                    div {
+                        SuspenseBoundary {
+                            fallback: move |cx| cx.render(rsx!( "Loading {generation}" )),
+                            div { async_child {} }
+                        }
+                    }
+                ))
+            });
+
+            assert_eq!(
+                dom.rebuild().santize().edits,
+                [
+                    LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+                    CreateTextNode { value: "Loading 0", id: ElementId(4) },
+                    ReplacePlaceholder { path: &[0], m: 1 },
+                    AppendChildren { m: 1, id: ElementId(0) },
+                ]
+            );
+
+            // The children were never mounted, so they're created again offscreen while the fallback is diffed
+            dom.mark_dirty(ScopeId(0));
+            assert_eq!(
+                dom.render_immediate().santize().edits,
+                [SetText { value: "Loading 1", id: ElementId(4) }]
+            );
+            assert!(dom.has_suspended_work());
+
+            // Only the children created by the second render are mounted
+            dom.wait_for_work().await;
+            assert_eq!(
+                dom.render_immediate().santize().edits,
+                [
+                    LoadTemplate { name: "template", index: 0, id: ElementId(2) },
+                    AssignId { path: &[0], id: ElementId(3) },
+                    ReplaceWith { id: ElementId(4), m: 1 },
+                    LoadTemplate { name: "template", index: 0, id: ElementId(5) },
+                    ReplaceWith { id: ElementId(3), m: 1 },
+                ]
+            );
+            assert!(!dom.has_suspended_work());
+        });
+}
+
+#[test]
+fn parent_rerenders_resolved_child_while_suspended() {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let mut dom = VirtualDom::new(|cx| {
+                let generation = cx.generation();
+                cx.render(rsx!(
+                    div {
+                        SuspenseBoundary {
+                            fallback: |cx| cx.render(rsx!( "Loading..." )),
+                            slow_value { value: generation }
+                        }
+                    }
+                ))
+            });
+
+            _ = dom.rebuild();
+            dom.wait_for_work().await;
+            _ = dom.render_immediate();
+            assert!(!dom.has_suspended_work());
+
+            // The resolved nodes stay mounted while the child waits on its new props
+            dom.mark_dirty(ScopeId(0));
+            assert!(dom.render_immediate().santize().edits.is_empty());
+            assert!(dom.has_suspended_work());
+
+            // Rendering it again before it resolves starts over without touching the mounted nodes
+            dom.mark_dirty(ScopeId(0));
+            assert!(dom.render_immediate().santize().edits.is_empty());
+            assert!(dom.has_suspended_work());
+
+            // Only the last render resolves, and it's diffed against the nodes that were mounted all along
+            dom.wait_for_work().await;
+            assert_eq!(
+                dom.render_immediate().santize().edits,
+                [SetText { value: "2", id: ElementId(4) }]
+            );
+            assert!(!dom.has_suspended_work());
+        });
+}
+
+#[test]
+fn rerendered_before_resolving() {
+    let mut dom = VirtualDom::new(|cx| {
+        let generation = cx.generation();
+        cx.render(rsx!(
+            SuspenseBoundary {
+                (generation > 0).then(|| rsx!( ready_after_first { value: generation } ))
+            }
+        ))
+    });
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            CreatePlaceholder { id: ElementId(1) },
+            AppendChildren { m: 1, id: ElementId(0) },
+        ]
+    );
+
+    // The child is created suspended below a boundary that's already mounted
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            CreatePlaceholder { id: ElementId(2) },
+            ReplaceWith { id: ElementId(1), m: 1 },
+        ]
+    );
+    assert!(dom.has_suspended_work());
+
+    // Its next render doesn't wait, so the nodes replace its placeholder right away
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            CreateTextNode { value: "2", id: ElementId(1) },
+            ReplaceWith { id: ElementId(2), m: 1 },
+        ]
+    );
+    assert!(!dom.has_suspended_work());
+}
+
+#[derive(Props, PartialEq)]
+struct ValueProps {
+    value: usize,
+}
+
+async fn slow_value(cx: Scope<'_, ValueProps>) -> Element {
+    let value = cx.props.value;
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    cx.render(rsx!("{value}"))
+}
+
+async fn ready_after_first(cx: Scope<'_, ValueProps>) -> Element {
+    let value = cx.props.value;
+    if value == 1 {
+        std::future::pending::<()>().await;
+    }
+    cx.render(rsx!("{value}"))
+}
+
+#[test]
+fn removed_while_suspended() {
+    let mut dom = VirtualDom::new(|cx| {
+        let show = cx.generation() == 0;
+        cx.render(rsx!(
+            div {
+                show.then(|| rsx!(
+                    SuspenseBoundary {
+                        fallback: |cx| cx.render(rsx!( "Loading..." )),
+                        div { forever {} }
+                    }
+                ))
+            }
+        ))
+    });
+
+    _ = dom.rebuild();
+    assert!(dom.has_suspended_work());
+
+    // Only the fallback was ever in the dom, and the suspended child is dropped along with the boundary
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            CreatePlaceholder { id: ElementId(5) },
+            ReplaceWith { id: ElementId(4), m: 1 },
+        ]
+    );
+    assert!(!dom.has_suspended_work());
+}
+
+async fn forever(_cx: Scope<'_>) -> Element {
+    std::future::pending::<()>().await;
+    None
+}
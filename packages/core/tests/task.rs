@@ -1,6 +1,9 @@
 //! Verify that tasks get polled by the virtualdom properly, and that we escape wait_for_work safely
 
 use dioxus::prelude::*;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 use std::time::Duration;
 
 static mut POLL_COUNT: usize = 0;
@@ -40,3 +43,111 @@ fn app(cx: Scope) -> Element {
 
     cx.render(rsx!(()))
 }
+
+#[test]
+fn cancel_task() {
+    let dom = VirtualDom::new(|cx| cx.render(rsx!(())));
+
+    let task = dom.base_scope().spawn(std::future::pending());
+    assert!(!task.is_finished());
+
+    task.cancel();
+    assert!(task.is_finished());
+
+    // Cancelling again is a no-op, even if the id was handed to a new task
+    let other = dom.base_scope().spawn(std::future::pending());
+    task.cancel();
+    assert!(!other.is_finished());
+}
+
+#[test]
+fn tasks_dropped_with_scope() {
+    thread_local! {
+        static SHOW: std::cell::Cell<bool> = std::cell::Cell::new(true);
+        static TASK: std::cell::RefCell<Option<TaskHandle>> = std::cell::RefCell::new(None);
+    }
+
+    fn app(cx: Scope) -> Element {
+        let show = SHOW.with(|show| show.get());
+        cx.render(rsx! {
+            show.then(|| rsx! { child {} })
+        })
+    }
+
+    fn child(cx: Scope) -> Element {
+        cx.use_hook(|| {
+            let task = cx.spawn(std::future::pending());
+            TASK.with(|slot| *slot.borrow_mut() = Some(task));
+        });
+        cx.render(rsx!(div {}))
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let task = TASK.with(|slot| slot.borrow().clone().unwrap());
+    assert!(!task.is_finished());
+
+    SHOW.with(|show| show.set(false));
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert!(task.is_finished());
+}
+
+#[test]
+fn poll_tasks_without_a_runtime() {
+    thread_local! {
+        static WAKE: std::cell::RefCell<Option<futures_channel::oneshot::Sender<()>>> = std::cell::RefCell::new(None);
+    }
+
+    let mut dom = VirtualDom::new(|cx| {
+        cx.use_hook(|| {
+            let (tx, rx) = futures_channel::oneshot::channel();
+            WAKE.with(|slot| *slot.borrow_mut() = Some(tx));
+
+            let update = cx.schedule_update();
+            cx.spawn(async move {
+                _ = rx.await;
+                update();
+            });
+        });
+        cx.render(rsx!(()))
+    });
+    _ = dom.rebuild();
+
+    let waker = futures_util::task::noop_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    // The task is waiting on the channel, so there's nothing to render
+    assert_eq!(dom.poll_tasks(&mut cx), Poll::Pending);
+
+    // Once it wakes up and marks the component dirty, there is
+    WAKE.with(|slot| slot.borrow_mut().take().unwrap().send(()).unwrap());
+    assert_eq!(dom.poll_tasks(&mut cx), Poll::Ready(()));
+}
+
+#[test]
+fn custom_spawner_runs_tasks() {
+    type Task = Pin<Box<dyn Future<Output = ()>>>;
+
+    let spawned = std::rc::Rc::new(std::cell::RefCell::new(Vec::<Task>::new()));
+    let dom = VirtualDom::new(|cx| cx.render(rsx!(()))).with_spawner({
+        let spawned = spawned.clone();
+        move |task: Task| spawned.borrow_mut().push(task)
+    });
+
+    let finishes = dom.base_scope().spawn(async {});
+    let cancelled = dom.base_scope().spawn(std::future::pending());
+    assert_eq!(spawned.borrow().len(), 2);
+
+    cancelled.cancel();
+
+    // The spawner drives the tasks, and the VirtualDom lets go of the ones that finish
+    let waker = futures_util::task::noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    for task in spawned.borrow_mut().iter_mut() {
+        assert!(task.as_mut().poll(&mut cx).is_ready());
+    }
+    assert!(finishes.is_finished());
+    assert!(cancelled.is_finished());
+}
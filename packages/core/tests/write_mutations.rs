@@ -0,0 +1,129 @@
+//! Writing edits out in batches through WriteMutations should produce the same edits as collecting them into Mutations
+
+use dioxus::core::{BorrowedAttributeValue, ElementId, Mutations, WriteMutations};
+use dioxus::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+fn app(cx: Scope) -> Element {
+    let gen = cx.generation();
+    cx.render(rsx! {
+        div { class: "{gen}", hidden: false, "{gen}" }
+        (0..gen).map(|i| rsx!( p { key: "{i}", "{i}" } ))
+    })
+}
+
+#[test]
+fn written_edits_match_returned() {
+    let mut returned = VirtualDom::new(app);
+    let mut written = VirtualDom::new(app);
+
+    let mut out = Mutations::default();
+    written.rebuild_to(&mut out);
+    let expected = returned.rebuild();
+
+    assert_eq!(out.templates.len(), expected.templates.len());
+    assert_eq!(out.santize().edits, expected.santize().edits);
+
+    for _ in 0..3 {
+        returned.mark_dirty(ScopeId(0));
+        written.mark_dirty(ScopeId(0));
+
+        let mut out = Mutations::default();
+        written.render_immediate_to(&mut out);
+        let expected = returned.render_immediate();
+
+        assert_eq!(out.santize().edits, expected.santize().edits);
+    }
+}
+
+// The number of edits the counting writer was handed so far
+static WRITTEN: AtomicUsize = AtomicUsize::new(0);
+
+// What had been written by the time the child rendered
+static SEEN_BY_CHILD: AtomicUsize = AtomicUsize::new(0);
+
+struct Counter;
+
+impl Counter {
+    fn count(&mut self) {
+        WRITTEN.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+impl WriteMutations<'_> for Counter {
+    fn register_template(&mut self, _: Template<'static>) {}
+    fn append_children(&mut self, _: ElementId, _: usize) {
+        self.count()
+    }
+    fn assign_id(&mut self, _: &'static [u8], _: ElementId) {
+        self.count()
+    }
+    fn create_placeholder(&mut self, _: ElementId) {
+        self.count()
+    }
+    fn create_text_node(&mut self, _: &str, _: ElementId) {
+        self.count()
+    }
+    fn hydrate_text(&mut self, _: &'static [u8], _: &str, _: ElementId) {
+        self.count()
+    }
+    fn load_template(&mut self, _: &'static str, _: usize, _: ElementId) {
+        self.count()
+    }
+    fn replace_with(&mut self, _: ElementId, _: usize) {
+        self.count()
+    }
+    fn replace_placeholder(&mut self, _: &'static [u8], _: usize) {
+        self.count()
+    }
+    fn insert_after(&mut self, _: ElementId, _: usize) {
+        self.count()
+    }
+    fn insert_before(&mut self, _: ElementId, _: usize) {
+        self.count()
+    }
+    fn set_attribute(&mut self, _: &str, _: Option<&str>, _: BorrowedAttributeValue, _: ElementId) {
+        self.count()
+    }
+    fn set_text(&mut self, _: &str, _: ElementId) {
+        self.count()
+    }
+    fn new_event_listener(&mut self, _: &str, _: ElementId, _: bool) {
+        self.count()
+    }
+    fn remove_event_listener(&mut self, _: &str, _: ElementId) {
+        self.count()
+    }
+    fn remove(&mut self, _: ElementId) {
+        self.count()
+    }
+    fn push_root(&mut self, _: ElementId) {
+        self.count()
+    }
+}
+
+#[test]
+fn edits_are_written_between_scopes() {
+    fn parent(cx: Scope) -> Element {
+        let gen = cx.generation();
+        cx.render(rsx! {
+            "{gen}"
+            child {}
+        })
+    }
+
+    fn child(cx: Scope) -> Element {
+        SEEN_BY_CHILD.store(WRITTEN.load(Ordering::SeqCst), Ordering::SeqCst);
+        cx.render(rsx!(div {}))
+    }
+
+    let mut dom = VirtualDom::new(parent);
+    dom.rebuild_to(&mut Counter);
+    let rebuilt = WRITTEN.load(Ordering::SeqCst);
+
+    // The parent renders first and its new text goes out before the child renders
+    dom.mark_dirty(ScopeId(0));
+    dom.mark_dirty(ScopeId(1));
+    dom.render_immediate_to(&mut Counter);
+    assert_eq!(SEEN_BY_CHILD.load(Ordering::SeqCst), rebuilt + 1);
+}
@@ -0,0 +1,834 @@
+//! Mirrors the elements of a window into an AccessKit tree, so assistive technology sees the app as a native one
+//!
+//! The webview exposes its own accessibility tree, but only as well as the webview of the platform supports it. With
+//! the `accessibility` feature, every window keeps a copy of its elements next to the webview, updated from the same
+//! mutations, and describes them to the platform with the roles, names and states in their html attributes. The actions
+//! assistive technology takes on the tree, like focusing or activating an element, are done to the element in the
+//! webview, so they fire the same events a user would.
+
+use dioxus_core::Mutations;
+use wry::application::event_loop::EventLoopProxy;
+use wry::application::window::Window;
+
+use crate::desktop_context::UserWindowEvent;
+
+/// Nothing to mirror without the `accessibility` feature
+#[cfg(not(feature = "accessibility"))]
+pub(crate) struct Accessibility;
+
+#[cfg(not(feature = "accessibility"))]
+impl Accessibility {
+    pub(crate) fn new(_window: &Window, _proxy: &EventLoopProxy<UserWindowEvent>) -> Self {
+        Self
+    }
+
+    pub(crate) fn update(&mut self, _edits: &Mutations) {}
+}
+
+#[cfg(feature = "accessibility")]
+pub(crate) use mirror::Accessibility;
+
+#[cfg(feature = "accessibility")]
+mod mirror {
+    use super::*;
+    use crate::desktop_context::EventData;
+    use accesskit::{
+        Action, ActionHandler, ActionRequest, CheckedState, DefaultActionVerb, Node, NodeBuilder,
+        NodeClassSet, NodeId, Role, Tree, TreeUpdate,
+    };
+    use dioxus_core::{
+        BorrowedAttributeValue, ElementId, Mutation, TemplateAttribute, TemplateNode,
+    };
+    use std::cell::RefCell;
+    use std::collections::{HashMap, HashSet};
+    use std::num::NonZeroU128;
+    use std::rc::Rc;
+    use wry::application::window::WindowId;
+    use wry::webview::WebView;
+
+    /// The accessibility tree of a window, and the adapter that hands it to the platform
+    pub(crate) struct Accessibility {
+        tree: Rc<RefCell<MirrorTree>>,
+        adapter: Option<Adapter>,
+    }
+
+    impl Accessibility {
+        pub(crate) fn new(window: &Window, proxy: &EventLoopProxy<UserWindowEvent>) -> Self {
+            let tree = Rc::new(RefCell::new(MirrorTree::new()));
+            let handler = Box::new(ActionForwarder {
+                proxy: proxy.clone(),
+                window: window.id(),
+            });
+            let adapter = Adapter::new(window, tree.clone(), handler);
+            if adapter.is_none() {
+                log::warn!(
+                    "Assistive technology isn't available, so the window has no accessibility tree"
+                );
+            }
+            Self { tree, adapter }
+        }
+
+        /// Apply the mutations the window is about to send to its webview, and describe what they changed
+        pub(crate) fn update(&mut self, edits: &Mutations) {
+            let update = {
+                let mut tree = self.tree.borrow_mut();
+                for template in &edits.templates {
+                    tree.register_template(template.name, template.roots);
+                }
+                for edit in &edits.edits {
+                    tree.apply(edit);
+                }
+                tree.dirty_update()
+            };
+
+            // the platform may ask for the whole tree while it's handed the update, so the tree isn't borrowed here
+            if let (Some(adapter), Some(update)) = (&self.adapter, update) {
+                adapter.update(update);
+            }
+        }
+
+        /// Do what assistive technology asked to the element in the webview
+        pub(crate) fn action(&self, webview: &WebView, request: ActionRequest) {
+            let id = match self.tree.borrow().event_target(request.target) {
+                Some(id) => id,
+                None => return,
+            };
+            let script = match request.action {
+                Action::Focus => format!("window.interpreter.SetFocus({}, true);", id.0),
+                Action::Blur => format!("window.interpreter.SetFocus({}, false);", id.0),
+                Action::Default => format!("window.interpreter.nodes[{}]?.click();", id.0),
+                Action::ScrollIntoView => {
+                    format!("window.interpreter.ScrollTo({}, \"instant\");", id.0)
+                }
+                _ => return,
+            };
+            _ = webview.evaluate_script(&script);
+        }
+    }
+
+    /// Hands the actions of assistive technology to the event loop, since they can come from another thread
+    struct ActionForwarder {
+        proxy: EventLoopProxy<UserWindowEvent>,
+        window: WindowId,
+    }
+
+    impl ActionHandler for ActionForwarder {
+        fn do_action(&self, request: ActionRequest) {
+            let event = UserWindowEvent(EventData::Accessibility(request), self.window);
+            _ = self.proxy.send_event(event);
+        }
+    }
+
+    /// The platform adapters AccessKit has for the windows tao opens
+    enum Adapter {
+        #[cfg(target_os = "windows")]
+        Windows(accesskit_windows::SubclassingAdapter),
+        #[cfg(target_os = "macos")]
+        MacOs(accesskit_macos::SubclassingAdapter),
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ))]
+        Unix(accesskit_unix::Adapter),
+    }
+
+    impl Adapter {
+        #[allow(unused_variables)]
+        fn new(
+            window: &Window,
+            tree: Rc<RefCell<MirrorTree>>,
+            handler: Box<ActionForwarder>,
+        ) -> Option<Self> {
+            // the platform asks for the whole tree the first time assistive technology looks at the window
+            let source = move || tree.borrow().full_update();
+
+            #[cfg(target_os = "windows")]
+            {
+                use wry::application::platform::windows::WindowExtWindows;
+                let hwnd = accesskit_windows::HWND(window.hwnd() as _);
+                return Some(Adapter::Windows(
+                    accesskit_windows::SubclassingAdapter::new(hwnd, source, handler),
+                ));
+            }
+
+            #[cfg(target_os = "macos")]
+            {
+                use wry::application::platform::macos::WindowExtMacOS;
+                return Some(Adapter::MacOs(unsafe {
+                    accesskit_macos::SubclassingAdapter::new(window.ns_view(), source, handler)
+                }));
+            }
+
+            // AT-SPI takes the tree right away, from the thread it runs on
+            #[cfg(any(
+                target_os = "linux",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "netbsd",
+                target_os = "openbsd"
+            ))]
+            {
+                let initial = source();
+                return accesskit_unix::Adapter::new(
+                    window.title(),
+                    "dioxus".to_string(),
+                    env!("CARGO_PKG_VERSION").to_string(),
+                    move || initial,
+                    handler,
+                )
+                .map(Adapter::Unix);
+            }
+
+            #[allow(unreachable_code)]
+            None
+        }
+
+        fn update(&self, update: TreeUpdate) {
+            match self {
+                #[cfg(target_os = "windows")]
+                Adapter::Windows(adapter) => adapter.update(update).raise(),
+                #[cfg(target_os = "macos")]
+                Adapter::MacOs(adapter) => adapter.update(update).raise(),
+                #[cfg(any(
+                    target_os = "linux",
+                    target_os = "dragonfly",
+                    target_os = "freebsd",
+                    target_os = "netbsd",
+                    target_os = "openbsd"
+                ))]
+                Adapter::Unix(adapter) => adapter.update(update),
+                #[cfg(not(any(
+                    target_os = "windows",
+                    target_os = "macos",
+                    target_os = "linux",
+                    target_os = "dragonfly",
+                    target_os = "freebsd",
+                    target_os = "netbsd",
+                    target_os = "openbsd"
+                )))]
+                _ => {}
+            }
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    enum MirrorKind {
+        Element {
+            tag: String,
+            attributes: Vec<(String, String)>,
+            listeners: Vec<String>,
+        },
+        Text(String),
+        Placeholder,
+    }
+
+    #[derive(Debug, Clone)]
+    struct MirrorNode {
+        kind: MirrorKind,
+        parent: Option<usize>,
+        children: Vec<usize>,
+        element: Option<ElementId>,
+    }
+
+    /// A copy of the elements in the webview, kept up to date with the mutations like the interpreter does
+    ///
+    /// Accesskit knows every node by its index in the tree plus one, since its ids can't be zero.
+    struct MirrorTree {
+        // removed nodes leave a hole behind, so assistive technology never confuses a new node for an old one
+        nodes: Vec<Option<MirrorNode>>,
+        elements: HashMap<ElementId, usize>,
+        templates: HashMap<String, Vec<usize>>,
+        stack: Vec<usize>,
+        dirty: HashSet<usize>,
+        classes: NodeClassSet,
+    }
+
+    /// Roles that are named after the text inside of them when they don't have a label
+    const NAMED_BY_CONTENT: &[Role] = &[
+        Role::Button,
+        Role::CheckBox,
+        Role::Link,
+        Role::Heading,
+        Role::ListItem,
+        Role::ListBoxOption,
+        Role::Cell,
+        Role::ColumnHeader,
+        Role::RadioButton,
+        Role::Alert,
+    ];
+
+    impl MirrorTree {
+        fn new() -> Self {
+            let mut tree = Self {
+                nodes: Vec::new(),
+                elements: HashMap::new(),
+                templates: HashMap::new(),
+                stack: Vec::new(),
+                dirty: HashSet::new(),
+                classes: NodeClassSet::new(),
+            };
+
+            // the app is mounted into the element with the first id, which stands in for the window
+            let root = tree.create(MirrorKind::Element {
+                tag: "main".to_string(),
+                attributes: Vec::new(),
+                listeners: Vec::new(),
+            });
+            tree.set_element(root, ElementId(0));
+            tree
+        }
+
+        fn get(&self, id: usize) -> Option<&MirrorNode> {
+            self.nodes.get(id).and_then(Option::as_ref)
+        }
+
+        fn get_mut(&mut self, id: usize) -> Option<&mut MirrorNode> {
+            self.nodes.get_mut(id).and_then(Option::as_mut)
+        }
+
+        fn node(&self, id: ElementId) -> Option<usize> {
+            self.elements.get(&id).copied()
+        }
+
+        fn create(&mut self, kind: MirrorKind) -> usize {
+            self.nodes.push(Some(MirrorNode {
+                kind,
+                parent: None,
+                children: Vec::new(),
+                element: None,
+            }));
+            self.nodes.len() - 1
+        }
+
+        fn set_element(&mut self, id: usize, element: ElementId) {
+            if let Some(node) = self.get_mut(id) {
+                node.element = Some(element);
+                self.elements.insert(element, id);
+            }
+        }
+
+        fn register_template(&mut self, name: &str, roots: &[TemplateNode]) {
+            let roots = roots
+                .iter()
+                .map(|root| self.create_template_node(root))
+                .collect();
+            self.templates.insert(name.to_string(), roots);
+        }
+
+        fn create_template_node(&mut self, node: &TemplateNode) -> usize {
+            match node {
+                TemplateNode::Element {
+                    tag,
+                    attrs,
+                    children,
+                    ..
+                } => {
+                    let attributes = attrs
+                        .iter()
+                        .filter_map(|attr| match attr {
+                            TemplateAttribute::Static {
+                                name,
+                                value,
+                                namespace: None,
+                            } => Some((name.to_string(), value.to_string())),
+                            _ => None,
+                        })
+                        .collect();
+                    let id = self.create(MirrorKind::Element {
+                        tag: tag.to_string(),
+                        attributes,
+                        listeners: Vec::new(),
+                    });
+                    for child in *children {
+                        let child = self.create_template_node(child);
+                        self.add_child(id, child);
+                    }
+                    id
+                }
+                TemplateNode::Text { text } => self.create(MirrorKind::Text(text.to_string())),
+                TemplateNode::DynamicText { .. } => self.create(MirrorKind::Text(String::new())),
+                TemplateNode::Dynamic { .. } => self.create(MirrorKind::Placeholder),
+            }
+        }
+
+        fn clone_node(&mut self, id: usize) -> usize {
+            let (kind, children) = match self.get(id) {
+                Some(node) => (node.kind.clone(), node.children.clone()),
+                None => return self.create(MirrorKind::Placeholder),
+            };
+            let new = self.create(kind);
+            for child in children {
+                let child = self.clone_node(child);
+                self.add_child(new, child);
+            }
+            new
+        }
+
+        fn add_child(&mut self, parent: usize, child: usize) {
+            if let Some(node) = self.get_mut(child) {
+                node.parent = Some(parent);
+            }
+            if let Some(node) = self.get_mut(parent) {
+                node.children.push(child);
+            }
+        }
+
+        fn load_child(&self, path: &[u8]) -> Option<usize> {
+            let mut current = *self.stack.last()?;
+            for index in path {
+                current = *self.get(current)?.children.get(*index as usize)?;
+            }
+            Some(current)
+        }
+
+        fn pop(&mut self, m: usize) -> Vec<usize> {
+            let at = self.stack.len().saturating_sub(m);
+            self.stack.split_off(at)
+        }
+
+        fn insert(&mut self, anchor: usize, new: Vec<usize>, after: bool) {
+            let parent = match self.get(anchor).and_then(|node| node.parent) {
+                Some(parent) => parent,
+                None => return,
+            };
+            for node in &new {
+                self.mark_inserted(*node);
+                if let Some(node) = self.get_mut(*node) {
+                    node.parent = Some(parent);
+                }
+            }
+            if let Some(node) = self.get_mut(parent) {
+                let mut index = node
+                    .children
+                    .iter()
+                    .position(|child| *child == anchor)
+                    .unwrap_or(0);
+                if after {
+                    index += 1;
+                }
+                node.children.splice(index..index, new);
+            }
+            self.mark_changed(parent);
+        }
+
+        fn remove(&mut self, id: usize) {
+            if let Some(parent) = self.get(id).and_then(|node| node.parent) {
+                if let Some(node) = self.get_mut(parent) {
+                    node.children.retain(|child| *child != id);
+                }
+                self.mark_changed(parent);
+            }
+            self.drop_node(id);
+        }
+
+        fn drop_node(&mut self, id: usize) {
+            if let Some(node) = self.nodes.get_mut(id).and_then(Option::take) {
+                self.dirty.remove(&id);
+                // the id may already belong to another node
+                if let Some(element) = node.element {
+                    if self.elements.get(&element) == Some(&id) {
+                        self.elements.remove(&element);
+                    }
+                }
+                for child in node.children {
+                    self.drop_node(child);
+                }
+            }
+        }
+
+        /// Something about the node changed, which can change the names of the elements around it
+        fn mark_changed(&mut self, id: usize) {
+            let mut current = Some(id);
+            while let Some(id) = current {
+                self.dirty.insert(id);
+                current = self.get(id).and_then(|node| node.parent);
+            }
+        }
+
+        /// A new node and everything inside of it needs to be described
+        fn mark_inserted(&mut self, id: usize) {
+            let mut stack = vec![id];
+            while let Some(id) = stack.pop() {
+                self.dirty.insert(id);
+                if let Some(node) = self.get(id) {
+                    stack.extend(node.children.iter().copied());
+                }
+            }
+        }
+
+        fn apply(&mut self, edit: &Mutation) {
+            match *edit {
+                Mutation::AppendChildren { id, m } => {
+                    if let Some(parent) = self.node(id) {
+                        for child in self.pop(m) {
+                            self.mark_inserted(child);
+                            self.add_child(parent, child);
+                        }
+                        self.mark_changed(parent);
+                    }
+                }
+                Mutation::AssignId { path, id } => {
+                    if let Some(node) = self.load_child(path) {
+                        self.set_element(node, id);
+                    }
+                }
+                Mutation::CreatePlaceholder { id } => {
+                    let node = self.create(MirrorKind::Placeholder);
+                    self.set_element(node, id);
+                    self.stack.push(node);
+                }
+                Mutation::CreateTextNode { value, id } => {
+                    let node = self.create(MirrorKind::Text(value.to_string()));
+                    self.set_element(node, id);
+                    self.stack.push(node);
+                }
+                Mutation::HydrateText { path, value, id } => {
+                    if let Some(node) = self.load_child(path) {
+                        self.set_element(node, id);
+                        if let Some(node) = self.get_mut(node) {
+                            node.kind = MirrorKind::Text(value.to_string());
+                        }
+                    }
+                }
+                Mutation::LoadTemplate { name, index, id } => {
+                    if let Some(template) =
+                        self.templates.get(name).and_then(|roots| roots.get(index))
+                    {
+                        let node = self.clone_node(*template);
+                        self.set_element(node, id);
+                        self.stack.push(node);
+                    }
+                }
+                Mutation::ReplaceWith { id, m } => {
+                    let new = self.pop(m);
+                    if let Some(old) = self.node(id) {
+                        self.insert(old, new, false);
+                        self.remove(old);
+                    }
+                }
+                Mutation::ReplacePlaceholder { path, m } => {
+                    let new = self.pop(m);
+                    if let Some(old) = self.load_child(path) {
+                        self.insert(old, new, false);
+                        self.remove(old);
+                    }
+                }
+                Mutation::InsertAfter { id, m } => {
+                    let new = self.pop(m);
+                    if let Some(anchor) = self.node(id) {
+                        self.insert(anchor, new, true);
+                    }
+                }
+                Mutation::InsertBefore { id, m } => {
+                    let new = self.pop(m);
+                    if let Some(anchor) = self.node(id) {
+                        self.insert(anchor, new, false);
+                    }
+                }
+                Mutation::SetAttribute {
+                    name,
+                    ns: None,
+                    ref value,
+                    id,
+                } => {
+                    let value = match value {
+                        BorrowedAttributeValue::Text(text) => Some(text.to_string()),
+                        BorrowedAttributeValue::Float(f) => Some(f.to_string()),
+                        BorrowedAttributeValue::Int(i) => Some(i.to_string()),
+                        BorrowedAttributeValue::Bool(b) => Some(b.to_string()),
+                        _ => None,
+                    };
+                    let node = match self.node(id) {
+                        Some(node) => node,
+                        None => return,
+                    };
+                    if let Some(MirrorNode {
+                        kind: MirrorKind::Element { attributes, .. },
+                        ..
+                    }) = self.get_mut(node)
+                    {
+                        attributes.retain(|(existing, _)| existing != name);
+                        if let Some(value) = value {
+                            attributes.push((name.to_string(), value));
+                        }
+                    }
+                    self.mark_changed(node);
+                }
+                Mutation::SetText { value, id } => {
+                    if let Some(node) = self.node(id) {
+                        if let Some(node) = self.get_mut(node) {
+                            node.kind = MirrorKind::Text(value.to_string());
+                        }
+                        self.mark_changed(node);
+                    }
+                }
+                Mutation::NewEventListener { name, id, .. } => {
+                    if let Some(node) = self.node(id) {
+                        if let Some(MirrorNode {
+                            kind: MirrorKind::Element { listeners, .. },
+                            ..
+                        }) = self.get_mut(node)
+                        {
+                            listeners.push(name.to_string());
+                        }
+                        self.dirty.insert(node);
+                    }
+                }
+                Mutation::RemoveEventListener { name, id } => {
+                    if let Some(node) = self.node(id) {
+                        if let Some(MirrorNode {
+                            kind: MirrorKind::Element { listeners, .. },
+                            ..
+                        }) = self.get_mut(node)
+                        {
+                            listeners.retain(|listener| listener != name);
+                        }
+                        self.dirty.insert(node);
+                    }
+                }
+                Mutation::Remove { id } => {
+                    if let Some(node) = self.node(id) {
+                        self.remove(node);
+                    }
+                }
+                Mutation::PushRoot { id } => {
+                    if let Some(node) = self.node(id) {
+                        self.stack.push(node);
+                    }
+                }
+                Mutation::SetAttribute { .. } => {}
+            }
+        }
+
+        /// The element in the webview an action on the node goes to
+        fn event_target(&self, target: NodeId) -> Option<ElementId> {
+            let mut current = Some(target.0.get() as usize - 1);
+            while let Some(id) = current {
+                let node = self.get(id)?;
+                if let (MirrorKind::Element { .. }, Some(element)) = (&node.kind, node.element) {
+                    if id != 0 {
+                        return Some(element);
+                    }
+                }
+                current = node.parent;
+            }
+            None
+        }
+
+        /// Describe every node that changed since the last update, if any did
+        fn dirty_update(&mut self) -> Option<TreeUpdate> {
+            if self.dirty.is_empty() {
+                return None;
+            }
+            let mut dirty: Vec<_> = self.dirty.drain().collect();
+            dirty.sort_unstable();
+            let nodes = dirty
+                .into_iter()
+                .filter_map(|id| Some((node_id(id), self.describe(id)?)))
+                .collect();
+            Some(TreeUpdate {
+                nodes,
+                tree: None,
+                focus: None,
+            })
+        }
+
+        fn full_update(&self) -> TreeUpdate {
+            let mut classes = NodeClassSet::new();
+            let mut nodes = Vec::new();
+            let mut stack = vec![0];
+            while let Some(id) = stack.pop() {
+                if let Some(node) = self.describe_with(id, &mut classes) {
+                    nodes.push((node_id(id), node));
+                }
+                if let Some(node) = self.get(id) {
+                    stack.extend(node.children.iter().copied());
+                }
+            }
+            TreeUpdate {
+                nodes,
+                tree: Some(Tree::new(node_id(0))),
+                focus: None,
+            }
+        }
+
+        fn describe(&mut self, id: usize) -> Option<Node> {
+            let mut classes = std::mem::take(&mut self.classes);
+            let node = self.describe_with(id, &mut classes);
+            self.classes = classes;
+            node
+        }
+
+        fn describe_with(&self, id: usize, classes: &mut NodeClassSet) -> Option<Node> {
+            let node = self.get(id)?;
+            let (tag, attributes, listeners) = match &node.kind {
+                MirrorKind::Element {
+                    tag,
+                    attributes,
+                    listeners,
+                } => (tag, attributes, listeners),
+                MirrorKind::Text(text) => {
+                    let mut builder = NodeBuilder::new(Role::StaticText);
+                    builder.set_name(text.as_str());
+                    return Some(builder.build(classes));
+                }
+                MirrorKind::Placeholder => return None,
+            };
+            let attribute = |name: &str| {
+                attributes
+                    .iter()
+                    .find(|(existing, _)| existing == name)
+                    .map(|(_, value)| value.as_str())
+            };
+
+            let role = match id {
+                0 => Role::Window,
+                _ => attribute("role")
+                    .or_else(|| dioxus_core::implicit_role(tag, attribute("type")))
+                    .map_or(Role::GenericContainer, role_from_aria),
+            };
+            let mut builder = NodeBuilder::new(role);
+
+            // the children are only the nodes that are described
+            builder.set_children(
+                node.children
+                    .iter()
+                    .filter(|child| {
+                        !matches!(
+                            self.get(**child),
+                            None | Some(MirrorNode {
+                                kind: MirrorKind::Placeholder,
+                                ..
+                            })
+                        )
+                    })
+                    .map(|child| node_id(*child))
+                    .collect::<Vec<_>>(),
+            );
+
+            let label = attribute("aria-label")
+                .or_else(|| attribute("alt"))
+                .or_else(|| attribute("title"))
+                .map(str::to_string)
+                .or_else(|| {
+                    NAMED_BY_CONTENT
+                        .contains(&role)
+                        .then(|| self.text(id).trim().to_string())
+                        .filter(|text| !text.is_empty())
+                });
+            if let Some(label) = label {
+                builder.set_name(label);
+            }
+            if let Some(value) = attribute("value") {
+                builder.set_value(value);
+            }
+
+            let truthy = |name: &str| matches!(attribute(name), Some(value) if value != "false");
+            if truthy("disabled") || truthy("aria-disabled") {
+                builder.set_disabled();
+            }
+            if truthy("hidden") || truthy("aria-hidden") {
+                builder.set_hidden();
+            }
+            match attribute("aria-checked") {
+                Some("mixed") => builder.set_checked_state(CheckedState::Mixed),
+                Some(checked) => builder.set_checked_state(match checked {
+                    "false" => CheckedState::False,
+                    _ => CheckedState::True,
+                }),
+                None if matches!(role, Role::CheckBox | Role::RadioButton) => builder
+                    .set_checked_state(if truthy("checked") {
+                        CheckedState::True
+                    } else {
+                        CheckedState::False
+                    }),
+                None => {}
+            }
+
+            let focusable = attribute("tabindex").is_some()
+                || matches!(tag.as_str(), "button" | "input" | "select" | "textarea")
+                || (tag == "a" && attribute("href").is_some());
+            if focusable && id != 0 {
+                builder.add_action(Action::Focus);
+            }
+            let clickable = listeners.iter().any(|listener| listener == "click")
+                || matches!(
+                    role,
+                    Role::Button | Role::Link | Role::CheckBox | Role::RadioButton
+                );
+            if clickable && id != 0 {
+                builder.add_action(Action::Default);
+                builder.set_default_action_verb(match role {
+                    Role::Link => DefaultActionVerb::Jump,
+                    Role::CheckBox | Role::RadioButton => DefaultActionVerb::Check,
+                    _ => DefaultActionVerb::Click,
+                });
+            }
+
+            Some(builder.build(classes))
+        }
+
+        /// All of the text inside of the node
+        fn text(&self, id: usize) -> String {
+            let mut text = String::new();
+            let mut stack = vec![id];
+            while let Some(id) = stack.pop() {
+                match self.get(id) {
+                    Some(MirrorNode {
+                        kind: MirrorKind::Text(value),
+                        ..
+                    }) => text.push_str(value),
+                    Some(node) => stack.extend(node.children.iter().rev()),
+                    None => {}
+                }
+            }
+            text
+        }
+    }
+
+    fn node_id(id: usize) -> NodeId {
+        NodeId(NonZeroU128::new(id as u128 + 1).unwrap())
+    }
+
+    /// The AccessKit role for a role in html
+    fn role_from_aria(role: &str) -> Role {
+        match role {
+            "alert" => Role::Alert,
+            "article" => Role::Article,
+            "banner" => Role::Banner,
+            "button" => Role::Button,
+            "cell" => Role::Cell,
+            "checkbox" => Role::CheckBox,
+            "columnheader" => Role::ColumnHeader,
+            "combobox" => Role::ComboBox,
+            "complementary" => Role::Complementary,
+            "contentinfo" => Role::ContentInfo,
+            "dialog" => Role::Dialog,
+            "form" => Role::Form,
+            "heading" => Role::Heading,
+            "img" => Role::Image,
+            "link" => Role::Link,
+            "list" => Role::List,
+            "listitem" => Role::ListItem,
+            "main" => Role::Main,
+            "menu" => Role::Menu,
+            "menuitem" => Role::MenuItem,
+            "navigation" => Role::Navigation,
+            "option" => Role::ListBoxOption,
+            "progressbar" => Role::ProgressIndicator,
+            "radio" => Role::RadioButton,
+            "row" => Role::Row,
+            "searchbox" => Role::SearchBox,
+            "slider" => Role::Slider,
+            "tab" => Role::Tab,
+            "tablist" => Role::TabList,
+            "tabpanel" => Role::TabPanel,
+            "table" => Role::Table,
+            "textbox" => Role::TextField,
+            _ => Role::GenericContainer,
+        }
+    }
+}
@@ -1,5 +1,11 @@
+#[cfg(feature = "inspector")]
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
+use crate::protocol::{Asset, AssetHandler};
+
+use dioxus_core::{EventDispatch, ScopeState};
+
 use wry::application::window::Icon;
 use wry::{
     application::window::{Window, WindowBuilder},
@@ -15,13 +21,26 @@ pub struct Config {
     pub(crate) window: WindowBuilder,
     pub(crate) file_drop_handler: Option<DropHandler>,
     pub(crate) protocols: Vec<WryProtocol>,
+    pub(crate) asset_handlers: Vec<(String, Box<AssetHandler>)>,
     pub(crate) pre_rendered: Option<String>,
     // pub(crate) event_handler: Option<Box<DynEventHandlerFn>>,
     pub(crate) disable_context_menu: bool,
     pub(crate) resource_dir: Option<PathBuf>,
+    pub(crate) storage_dir: Option<PathBuf>,
+    pub(crate) window_state_id: Option<String>,
     pub(crate) custom_head: Option<String>,
     pub(crate) custom_index: Option<String>,
     pub(crate) root_name: String,
+    pub(crate) user_agent: Option<String>,
+    pub(crate) menu_bar: Option<crate::MenuBar>,
+    pub(crate) root_contexts: Vec<Box<dyn Fn(&ScopeState)>>,
+    pub(crate) event_dispatch: Vec<(String, EventDispatch)>,
+    #[cfg(feature = "tray")]
+    pub(crate) tray: Option<crate::Tray>,
+    #[cfg(feature = "deep-links")]
+    pub(crate) url_scheme: Option<String>,
+    #[cfg(feature = "inspector")]
+    pub(crate) inspector: Option<SocketAddr>,
 }
 
 type DropHandler = Box<dyn Fn(&Window, FileDropEvent) -> bool>;
@@ -41,13 +60,26 @@ impl Config {
             // event_handler: None,
             window,
             protocols: Vec::new(),
+            asset_handlers: Vec::new(),
             file_drop_handler: None,
             pre_rendered: None,
             disable_context_menu: !cfg!(debug_assertions),
             resource_dir: None,
+            storage_dir: None,
+            window_state_id: None,
             custom_head: None,
             custom_index: None,
             root_name: "main".to_string(),
+            user_agent: None,
+            menu_bar: None,
+            root_contexts: Vec::new(),
+            event_dispatch: Vec::new(),
+            #[cfg(feature = "tray")]
+            tray: None,
+            #[cfg(feature = "deep-links")]
+            url_scheme: None,
+            #[cfg(feature = "inspector")]
+            inspector: None,
         }
     }
 
@@ -57,6 +89,46 @@ impl Config {
         self
     }
 
+    /// Set the directory where `use_persistent` stores its values
+    ///
+    /// Defaults to a folder named after the executable in the config directory of the user.
+    pub fn with_storage_directory(mut self, path: impl Into<PathBuf>) -> Self {
+        self.storage_dir = Some(path.into());
+        self
+    }
+
+    /// Save the size, position and maximized state of the window when it closes, and open it the same way next time
+    ///
+    /// The state is kept under `app_id` in the config directory of the user, or in the
+    /// [storage directory](Config::with_storage_directory) if it's set. Give every kind of window its own id.
+    pub fn with_persistent_window_state(mut self, app_id: impl Into<String>) -> Self {
+        self.window_state_id = Some(app_id.into());
+        self
+    }
+
+    /// Open the app with URLs of a scheme, like `myapp://settings/profile`
+    ///
+    /// The scheme is registered with the operating system when the app launches, and only one instance of the app
+    /// runs: opening it again hands the URL to the running instance and brings it to the front. The main window routes
+    /// to the path of every URL, with the host as the first segment, and
+    /// [`use_deep_link_handler`](crate::use_deep_link_handler) hears about them too.
+    #[cfg(feature = "deep-links")]
+    pub fn with_url_scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.url_scheme = Some(scheme.into());
+        self
+    }
+
+    /// Serve the devtools protocol of the app on an address, like `127.0.0.1:8765`
+    ///
+    /// Inspectors connect over a WebSocket and send [`DevtoolsRequest`](dioxus_core::DevtoolsRequest)s as JSON to get
+    /// the components of the main window, with their props, hooks and how often they rendered, and to outline them in
+    /// the window.
+    #[cfg(feature = "inspector")]
+    pub fn with_inspector(mut self, addr: SocketAddr) -> Self {
+        self.inspector = Some(addr);
+        self
+    }
+
     /// Set whether or not the right-click context menu should be disabled.
     pub fn with_disable_context_menu(mut self, disable: bool) -> Self {
         self.disable_context_menu = disable;
@@ -87,6 +159,9 @@ impl Config {
     // }
 
     /// Set a file drop handler
+    ///
+    /// The handler sees every file drag before the app does. Returning `true` keeps the drag from reaching the app as
+    /// `ondragover` and `ondrop` events.
     pub fn with_file_drop_handler(
         mut self,
         handler: impl Fn(&Window, FileDropEvent) -> bool + 'static,
@@ -104,6 +179,33 @@ impl Config {
         self
     }
 
+    /// Serve the assets under a path, like `/assets`, from Rust
+    ///
+    /// The handler gets the rest of the path, like `images/cat.png` for `/assets/images/cat.png`, and returns the
+    /// [`Asset`](crate::Asset) to serve or `None` if there isn't one. Files, images, fonts and media load from it
+    /// like any other url of the page, and audio and video can seek in them.
+    ///
+    /// ```rust, ignore
+    /// Config::new().with_asset_handler("/user-files", move |path| Asset::from_file(files_dir.join(path)).ok())
+    /// ```
+    pub fn with_asset_handler(
+        mut self,
+        prefix: impl AsRef<str>,
+        handler: impl Fn(&str) -> Option<Asset> + 'static,
+    ) -> Self {
+        let prefix = format!("/{}", prefix.as_ref().trim_matches('/'));
+        self.asset_handlers.push((prefix, Box::new(handler)));
+        self
+    }
+
+    /// Set the user agent the webview sends with its requests and shows as `navigator.userAgent`
+    ///
+    /// The webview only takes it when it's created, so it can't change while the window is open.
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
     /// Set a custom icon for this application
     pub fn with_icon(mut self, icon: Icon) -> Self {
         self.window.window.window_icon = Some(icon);
@@ -136,6 +238,52 @@ impl Config {
         self.root_name = name.into();
         self
     }
+
+    /// Set the menu bar of the window, in place of the one the platform gives it
+    pub fn with_menu_bar(mut self, menu_bar: crate::MenuBar) -> Self {
+        self.menu_bar = Some(menu_bar);
+        self
+    }
+
+    /// Provide a context to the root of the app before it's built, for the global services every component can reach
+    /// with `use_context`, like a database handle or the theme.
+    ///
+    /// The renderer's own contexts are provided first, so a context of the same type replaces them.
+    ///
+    /// ```rust, ignore
+    /// dioxus_desktop::launch_cfg(App, Config::new().with_context(Theme::Dark));
+    /// ```
+    pub fn with_context<T: Clone + 'static>(mut self, context: T) -> Self {
+        self.root_contexts.push(Box::new(move |scope| {
+            scope.provide_context(context.clone());
+        }));
+        self
+    }
+
+    /// Set whether the webview sends an event type as soon as it fires, or once per animation frame
+    ///
+    /// Deferred events are merged, so a listener only sees the last one that fired on its element before each frame.
+    /// By default the events that fire many times a frame, like `mousemove`, `scroll` and `input`, are deferred and
+    /// the rest are sent right away.
+    ///
+    /// ```rust, ignore
+    /// dioxus_desktop::launch_cfg(App, Config::new().with_event_dispatch("wheel", EventDispatch::Synchronous));
+    /// ```
+    pub fn with_event_dispatch(
+        mut self,
+        event: impl Into<String>,
+        dispatch: EventDispatch,
+    ) -> Self {
+        self.event_dispatch.push((event.into(), dispatch));
+        self
+    }
+
+    /// Show an icon in the system tray while the app runs
+    #[cfg(feature = "tray")]
+    pub fn with_tray(mut self, tray: crate::Tray) -> Self {
+        self.tray = Some(tray);
+        self
+    }
 }
 
 impl Default for Config {
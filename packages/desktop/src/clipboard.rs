@@ -0,0 +1,46 @@
+use dioxus_hooks::{ClipboardBackend, ClipboardError, ClipboardFuture};
+
+/// Backs `use_clipboard` with the clipboard of the operating system
+pub(crate) struct SystemClipboard;
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+impl SystemClipboard {
+    /// The clipboard is opened for every call, since some platforms only let one handle hold it at a time
+    fn with_clipboard<T>(
+        f: impl FnOnce(&mut arboard::Clipboard) -> Result<T, arboard::Error>,
+    ) -> Result<T, ClipboardError> {
+        let result = arboard::Clipboard::new().and_then(|mut clipboard| f(&mut clipboard));
+        result.map_err(|err| match err {
+            arboard::Error::ClipboardNotSupported => ClipboardError::Unavailable,
+            err => ClipboardError::Other(err.to_string()),
+        })
+    }
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+impl ClipboardBackend for SystemClipboard {
+    fn read(&self) -> ClipboardFuture<String> {
+        // An empty clipboard, or one that holds something other than text, reads as no text
+        let text = Self::with_clipboard(|clipboard| match clipboard.get_text() {
+            Err(arboard::Error::ContentNotAvailable) => Ok(String::new()),
+            text => text,
+        });
+        Box::pin(async move { text })
+    }
+
+    fn write(&self, text: String) -> ClipboardFuture<()> {
+        let result = Self::with_clipboard(|clipboard| clipboard.set_text(text));
+        Box::pin(async move { result })
+    }
+}
+
+#[cfg(any(target_os = "ios", target_os = "android"))]
+impl ClipboardBackend for SystemClipboard {
+    fn read(&self) -> ClipboardFuture<String> {
+        Box::pin(async { Err(ClipboardError::Unavailable) })
+    }
+
+    fn write(&self, _text: String) -> ClipboardFuture<()> {
+        Box::pin(async { Err(ClipboardError::Unavailable) })
+    }
+}
@@ -0,0 +1,237 @@
+use crate::desktop_context::{EventData, UserWindowEvent};
+use crate::use_window;
+use dioxus_core::ScopeState;
+use dioxus_history::{History, MemoryHistory};
+use interprocess::local_socket::{LocalSocketListener, LocalSocketStream};
+use std::cell::RefCell;
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::rc::Rc;
+use tokio::sync::broadcast::error::RecvError;
+use wry::application::event_loop::EventLoopProxy;
+use wry::application::window::WindowId;
+
+/// The socket the first instance of the app listens on for the URLs the next ones were opened with
+fn socket_path(scheme: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("@dioxus-{}-links", scheme))
+}
+
+/// The URL of the scheme the app was opened with, which the operating system passes as an argument
+pub(crate) fn url_from_args(scheme: &str) -> Option<String> {
+    let prefix = format!("{}:", scheme.to_lowercase());
+    std::env::args()
+        .skip(1)
+        .find(|arg| arg.to_lowercase().starts_with(&prefix))
+}
+
+/// The route of a URL of the scheme, with the host as the first segment: `myapp://settings/profile` is
+/// `/settings/profile`
+pub(crate) fn route(url: &str) -> String {
+    let rest = url.splitn(2, ':').nth(1).unwrap_or_default();
+    format!("/{}", rest.trim_start_matches('/'))
+}
+
+/// Hand the URL over to the instance of the app that's already running, if there is one
+///
+/// An instance that was opened without a URL just brings the running one to the front.
+pub(crate) fn forward_to_running_instance(scheme: &str, url: Option<&str>) -> bool {
+    match LocalSocketStream::connect(socket_path(scheme)) {
+        Ok(mut stream) => {
+            let _ = writeln!(stream, "{}", url.unwrap_or_default());
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Listen for the URLs of the instances that open after this one, forwarding them to the event loop
+pub(crate) fn listen(scheme: &str, proxy: EventLoopProxy<UserWindowEvent>, window: WindowId) {
+    let path = socket_path(scheme);
+    std::thread::spawn(move || {
+        // nothing answered on the socket, so whatever is left of it belongs to an instance that crashed
+        let listener = LocalSocketListener::bind(path.clone()).or_else(|_| {
+            let _ = std::fs::remove_file(&path);
+            LocalSocketListener::bind(path)
+        });
+        let listener = match listener {
+            Ok(listener) => listener,
+            Err(err) => {
+                log::error!("Failed to listen for the links of other instances: {}", err);
+                return;
+            }
+        };
+
+        for connection in listener.incoming().filter_map(Result::ok) {
+            let mut url = String::new();
+            if BufReader::new(connection).read_line(&mut url).is_ok() {
+                let url = url.trim().to_string();
+                _ = proxy.send_event(UserWindowEvent(EventData::DeepLink(url), window));
+            }
+        }
+    });
+}
+
+/// Tell the operating system to open URLs of the scheme with this executable
+///
+/// On Linux this adds a desktop entry for the scheme, and on Windows it adds the scheme to the registry of the user.
+/// The schemes of macOS apps are declared in the `Info.plist` of their bundle instead.
+pub(crate) fn register(scheme: &str) -> std::io::Result<()> {
+    let exe = std::env::current_exe()?;
+
+    #[cfg(target_os = "linux")]
+    {
+        let applications = dirs::data_dir()
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no data directory"))?
+            .join("applications");
+        std::fs::create_dir_all(&applications)?;
+
+        let name = format!("dioxus-{}-handler.desktop", scheme);
+        let entry = format!(
+            "[Desktop Entry]\nType=Application\nName={scheme}\nExec=\"{exe}\" %u\nNoDisplay=true\nMimeType=x-scheme-handler/{scheme};\n",
+            scheme = scheme,
+            exe = exe.display()
+        );
+        std::fs::write(applications.join(&name), entry)?;
+
+        std::process::Command::new("xdg-mime")
+            .args(["default", &name, &format!("x-scheme-handler/{}", scheme)])
+            .status()?;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let key = format!("HKCU\\Software\\Classes\\{}", scheme);
+        let open = format!("{}\\shell\\open\\command", key);
+        let description = format!("URL:{}", scheme);
+        let command = format!("\"{}\" \"%1\"", exe.display());
+        let values: [&[&str]; 3] = [
+            &[key.as_str(), "/ve", "/d", description.as_str()],
+            &[key.as_str(), "/v", "URL Protocol", "/d", ""],
+            &[open.as_str(), "/ve", "/d", command.as_str()],
+        ];
+        for value in values.iter() {
+            std::process::Command::new("reg")
+                .arg("add")
+                .args(value.iter())
+                .arg("/f")
+                .status()?;
+        }
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    let _ = (scheme, exe);
+
+    Ok(())
+}
+
+/// The history of the main window when the app has a URL scheme, which navigates to the links the app is opened with
+///
+/// It's provided as the history of the [`Router`](https://docs.rs/dioxus-router), so a link like
+/// `myapp://settings/profile` routes to `/settings/profile`.
+pub(crate) struct DeepLinkHistory {
+    inner: MemoryHistory,
+    callback: RefCell<Option<Rc<dyn Fn()>>>,
+}
+
+impl DeepLinkHistory {
+    pub(crate) fn new(url: Option<&str>) -> Self {
+        Self {
+            inner: MemoryHistory::with_initial_path(url.map(route).unwrap_or_else(|| "/".into())),
+            callback: RefCell::new(None),
+        }
+    }
+
+    /// Navigate to the route of a link, like it came from outside the app
+    pub(crate) fn open(&self, url: &str) {
+        self.inner.push(&route(url));
+
+        // the router might look at the history again, so it can't be borrowed
+        let callback = self.callback.borrow().clone();
+        if let Some(callback) = callback {
+            callback();
+        }
+    }
+}
+
+impl History for DeepLinkHistory {
+    fn current_path(&self) -> String {
+        self.inner.current_path()
+    }
+
+    fn push(&self, path: &str) {
+        self.inner.push(path)
+    }
+
+    fn replace(&self, path: &str) {
+        self.inner.replace(path)
+    }
+
+    fn go(&self, delta: isize) {
+        self.inner.go(delta)
+    }
+
+    fn subscribe(&self, callback: Box<dyn Fn()>) {
+        let callback: Rc<dyn Fn()> = callback.into();
+        self.inner.subscribe(Box::new({
+            let callback = callback.clone();
+            move || callback()
+        }));
+        *self.callback.borrow_mut() = Some(callback);
+    }
+}
+
+/// Run `handler` with every URL of the scheme of the app that the app is opened with while it's running
+///
+/// The URL the app was launched with is in [`DesktopContext::launch_url`](crate::DesktopContext::launch_url). The
+/// handler is removed when the component is dropped.
+pub fn use_deep_link_handler(cx: &ScopeState, handler: impl FnMut(&str) + 'static) {
+    let desktop = use_window(cx);
+    let current = cx.use_hook(|| Rc::new(RefCell::new(None::<Box<dyn FnMut(&str)>>)));
+    // keep the latest handler so it sees the latest props
+    current.replace(Some(Box::new(handler)));
+
+    cx.use_hook(|| {
+        let mut links = desktop.deep_links();
+        let current = current.clone();
+        cx.push_future(async move {
+            loop {
+                match links.recv().await {
+                    Ok(url) => {
+                        if let Some(handler) = current.borrow_mut().as_mut() {
+                            handler(&url);
+                        }
+                    }
+                    Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    });
+}
+
+#[test]
+fn routes() {
+    assert_eq!(route("myapp://settings/profile"), "/settings/profile");
+    assert_eq!(route("myapp:///settings?tab=1"), "/settings?tab=1");
+    assert_eq!(route("myapp:"), "/");
+}
+
+#[test]
+fn opening_a_link_navigates() {
+    let history = DeepLinkHistory::new(Some("myapp://home"));
+    assert_eq!(history.current_path(), "/home");
+
+    let changes = Rc::new(std::cell::Cell::new(0));
+    history.subscribe(Box::new({
+        let changes = changes.clone();
+        move || changes.set(changes.get() + 1)
+    }));
+
+    history.open("myapp://settings/profile");
+    assert_eq!(history.current_path(), "/settings/profile");
+    assert_eq!(changes.get(), 1);
+
+    history.go(-1);
+    assert_eq!(history.current_path(), "/home");
+    assert_eq!(changes.get(), 2);
+}
@@ -1,35 +1,147 @@
+use std::cell::Cell;
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::rc::Weak;
 
 use crate::create_new_window;
-use crate::eval::EvalResult;
+use crate::eval::EvalRegistry;
 use crate::events::IpcMessage;
+use crate::listener::ListenerRegistry;
 use crate::Config;
 use crate::WebviewHandler;
 use dioxus_core::ScopeState;
 use dioxus_core::VirtualDom;
-use serde_json::Value;
+use dioxus_hooks::{Eval, EvalError};
 use wry::application::event_loop::EventLoopProxy;
 use wry::application::event_loop::EventLoopWindowTarget;
 #[cfg(target_os = "ios")]
 use wry::application::platform::ios::WindowExtIOS;
 use wry::application::window::Fullscreen as WryFullscreen;
-use wry::application::window::Window;
 use wry::application::window::WindowId;
+use wry::application::window::{BadIcon, Icon, Window};
 use wry::webview::WebView;
 
 pub type ProxyType = EventLoopProxy<UserWindowEvent>;
 
 /// Get an imperative handle to the current window
 pub fn use_window(cx: &ScopeState) -> &DesktopContext {
-    cx.use_hook(|| cx.consume_context::<DesktopContext>())
-        .as_ref()
-        .unwrap()
+    cx.use_hook(|| cx.expect_context::<DesktopContext>())
+}
+
+/// Run `handler` with the id of every window of the app that closes, including the window of the component
+///
+/// The handler is removed when the component is dropped.
+pub fn use_window_close_handler(cx: &ScopeState, handler: impl FnMut(WindowId) + 'static) {
+    let desktop = use_window(cx);
+    let handler: CloseHandler = Rc::new(RefCell::new(handler));
+    let registration = cx
+        .use_hook(|| CloseHandlerRegistration(desktop.windows.add_close_handler(handler.clone())));
+    // keep the latest handler so it sees the latest props
+    desktop
+        .windows
+        .replace_close_handler(&registration.0, handler);
 }
 
 pub(crate) type WebviewQueue = Rc<RefCell<Vec<WebviewHandler>>>;
 
+type CloseHandler = Rc<RefCell<dyn FnMut(WindowId)>>;
+
+/// The windows of the app, which every window's [`DesktopContext`] shares
+pub(crate) struct WindowRegistry {
+    windows: RefCell<Vec<(WindowId, Weak<WebView>)>>,
+    close_handlers: RefCell<Vec<(usize, CloseHandler)>>,
+    next_handler: Cell<usize>,
+    shared_contexts: RefCell<Vec<Rc<dyn Fn(&ScopeState)>>>,
+    /// Every window hears about the tray icon
+    #[cfg(feature = "tray")]
+    pub(crate) tray_events: tokio::sync::broadcast::Sender<crate::TrayEvent>,
+    /// Every window hears about the links the app is opened with
+    #[cfg(feature = "deep-links")]
+    pub(crate) deep_links: tokio::sync::broadcast::Sender<String>,
+    #[cfg(feature = "deep-links")]
+    pub(crate) launch_url: RefCell<Option<String>>,
+}
+
+impl Default for WindowRegistry {
+    fn default() -> Self {
+        Self {
+            windows: Default::default(),
+            close_handlers: Default::default(),
+            next_handler: Default::default(),
+            shared_contexts: Default::default(),
+            #[cfg(feature = "tray")]
+            tray_events: tokio::sync::broadcast::channel(16).0,
+            #[cfg(feature = "deep-links")]
+            deep_links: tokio::sync::broadcast::channel(8).0,
+            #[cfg(feature = "deep-links")]
+            launch_url: Default::default(),
+        }
+    }
+}
+
+impl WindowRegistry {
+    /// Add a window as it's created, giving its VirtualDom the shared contexts
+    pub(crate) fn opened(&self, webview: &Rc<WebView>, dom: &VirtualDom) {
+        self.windows
+            .borrow_mut()
+            .push((webview.window().id(), Rc::downgrade(webview)));
+
+        for provide in self.shared_contexts.borrow().iter() {
+            provide(dom.base_scope());
+        }
+    }
+
+    /// Remove a window once it's been dropped, and tell the close handlers about it
+    pub(crate) fn closed(&self, id: WindowId) {
+        self.windows
+            .borrow_mut()
+            .retain(|(window, _)| *window != id);
+
+        // the handlers might add or remove handlers, so they can't run while the list is borrowed
+        let handlers: Vec<CloseHandler> = self
+            .close_handlers
+            .borrow()
+            .iter()
+            .map(|(_, handler)| handler.clone())
+            .collect();
+        for handler in handlers {
+            (handler.borrow_mut())(id);
+        }
+    }
+
+    fn add_close_handler(self: &Rc<Self>, handler: CloseHandler) -> (Weak<Self>, usize) {
+        let key = self.next_handler.get();
+        self.next_handler.set(key + 1);
+        self.close_handlers.borrow_mut().push((key, handler));
+        (Rc::downgrade(self), key)
+    }
+
+    fn replace_close_handler(&self, (_, key): &(Weak<Self>, usize), handler: CloseHandler) {
+        if let Some((_, old)) = self
+            .close_handlers
+            .borrow_mut()
+            .iter_mut()
+            .find(|(other, _)| other == key)
+        {
+            *old = handler;
+        }
+    }
+}
+
+struct CloseHandlerRegistration((Weak<WindowRegistry>, usize));
+
+impl Drop for CloseHandlerRegistration {
+    fn drop(&mut self) {
+        let (registry, key) = &self.0;
+        if let Some(registry) = registry.upgrade() {
+            registry
+                .close_handlers
+                .borrow_mut()
+                .retain(|(other, _)| other != key);
+        }
+    }
+}
+
 /// An imperative interface to the current window.
 ///
 /// To get a handle to the current window, use the [`use_window`] hook.
@@ -50,11 +162,19 @@ pub struct DesktopContext {
     /// The proxy to the event loop
     pub proxy: ProxyType,
 
-    /// The receiver for eval results since eval is async
-    pub(super) eval: tokio::sync::broadcast::Sender<Value>,
+    /// The scripts running in the window
+    pub(crate) evals: Rc<EvalRegistry>,
+
+    /// The listeners on the document and the window of the webview
+    pub(crate) listeners: Rc<ListenerRegistry>,
+
+    /// The ids of the items picked from the menu bar of the window
+    pub(crate) menu_events: tokio::sync::broadcast::Sender<String>,
 
     pub(super) pending_windows: WebviewQueue,
 
+    pub(crate) windows: Rc<WindowRegistry>,
+
     pub(crate) event_loop: EventLoopWindowTarget<UserWindowEvent>,
 
     #[cfg(target_os = "ios")]
@@ -76,13 +196,17 @@ impl DesktopContext {
         proxy: ProxyType,
         event_loop: EventLoopWindowTarget<UserWindowEvent>,
         webviews: WebviewQueue,
+        windows: Rc<WindowRegistry>,
     ) -> Self {
         Self {
             webview,
             proxy,
             event_loop,
-            eval: tokio::sync::broadcast::channel(8).0,
+            evals: Default::default(),
+            listeners: Default::default(),
+            menu_events: tokio::sync::broadcast::channel(8).0,
             pending_windows: webviews,
+            windows,
             #[cfg(target_os = "ios")]
             views: Default::default(),
         }
@@ -94,6 +218,9 @@ impl DesktopContext {
     ///
     /// You can use this to control other windows from the current window.
     ///
+    /// Every window has its own VirtualDom. The contexts shared with [`DesktopContext::share_context`] are provided to
+    /// its root, so windows can share state through them.
+    ///
     /// Be careful to not create a cycle of windows, or you might leak memory.
     pub fn new_window(&self, dom: VirtualDom, cfg: Config) -> Weak<WebView> {
         let window = create_new_window(
@@ -102,6 +229,7 @@ impl DesktopContext {
             &self.proxy,
             dom,
             &self.pending_windows,
+            &self.windows,
         );
 
         let id = window.webview.window().id();
@@ -121,6 +249,74 @@ impl DesktopContext {
         Rc::downgrade(&webview)
     }
 
+    /// Provide a context to the root of every window opened after this one, like a shared store
+    ///
+    /// Windows opened before the context was shared don't get it.
+    pub fn share_context<T: Clone + 'static>(&self, value: T) {
+        self.windows
+            .shared_contexts
+            .borrow_mut()
+            .push(Rc::new(move |root: &ScopeState| {
+                root.provide_context(value.clone());
+            }));
+    }
+
+    /// The ids of the open windows of the app, in the order they were opened
+    pub fn windows(&self) -> Vec<WindowId> {
+        self.windows
+            .windows
+            .borrow()
+            .iter()
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Get the webview of an open window
+    pub fn get_window(&self, id: WindowId) -> Option<Rc<WebView>> {
+        self.windows
+            .windows
+            .borrow()
+            .iter()
+            .find(|(window, _)| *window == id)
+            .and_then(|(_, webview)| webview.upgrade())
+    }
+
+    /// Listen to the ids of the items picked from the menu bar of this window
+    ///
+    /// See [`use_menu_event_handler`](crate::use_menu_event_handler) to handle them in a component.
+    pub fn menu_events(&self) -> tokio::sync::broadcast::Receiver<String> {
+        self.menu_events.subscribe()
+    }
+
+    /// Listen to the events of the tray icon the app was launched with
+    ///
+    /// See [`use_tray_event_handler`](crate::use_tray_event_handler) to handle them in a component.
+    #[cfg(feature = "tray")]
+    pub fn tray_events(&self) -> tokio::sync::broadcast::Receiver<crate::TrayEvent> {
+        self.windows.tray_events.subscribe()
+    }
+
+    /// Listen to the URLs of the [scheme](crate::Config::with_url_scheme) of the app that it's opened with
+    ///
+    /// See [`use_deep_link_handler`](crate::use_deep_link_handler) to handle them in a component.
+    #[cfg(feature = "deep-links")]
+    pub fn deep_links(&self) -> tokio::sync::broadcast::Receiver<String> {
+        self.windows.deep_links.subscribe()
+    }
+
+    /// The URL of the [scheme](crate::Config::with_url_scheme) of the app that it was launched with, if any
+    #[cfg(feature = "deep-links")]
+    pub fn launch_url(&self) -> Option<String> {
+        self.windows.launch_url.borrow().clone()
+    }
+
+    /// Bring an open window to the front and focus it
+    pub fn focus_window(&self, id: WindowId) {
+        if let Some(webview) = self.get_window(id) {
+            webview.window().set_focus();
+        }
+    }
+
     /// trigger the drag-window event
     ///
     /// Moves the window with the left mouse button until the button is released.
@@ -169,6 +365,8 @@ impl DesktopContext {
     }
 
     /// launch print modal
+    ///
+    /// See [`DesktopContext::export_pdf`] to print to a file without asking the user.
     pub fn print(&self) {
         if let Err(e) = self.webview.print() {
             log::warn!("Open print modal failed: {e}");
@@ -182,38 +380,50 @@ impl DesktopContext {
 
     /// opens DevTool window
     pub fn devtool(&self) {
-        #[cfg(debug_assertions)]
+        #[cfg(any(debug_assertions, feature = "devtools"))]
         self.webview.open_devtools();
 
-        #[cfg(not(debug_assertions))]
-        log::warn!("Devtools are disabled in release builds");
-    }
-
-    /// Evaluate a javascript expression
-    pub fn eval(&self, code: &str) -> EvalResult {
-        // Embed the return of the eval in a function so we can send it back to the main thread
-        let script = format!(
-            r#"
-            window.ipc.postMessage(
-                JSON.stringify({{
-                    "method":"eval_result",
-                    "params": (
-                        function(){{
-                            {}
-                        }}
-                    )()
-                }})
-            );
-            "#,
-            code
-        );
+        #[cfg(not(any(debug_assertions, feature = "devtools")))]
+        log::warn!("Devtools are disabled in release builds without the devtools feature");
+    }
+
+    /// Close the DevTool window
+    pub fn close_devtools(&self) {
+        #[cfg(any(debug_assertions, feature = "devtools"))]
+        self.webview.close_devtools();
+    }
 
-        if let Err(e) = self.webview.evaluate_script(&script) {
-            // send an error to the eval receiver
-            log::warn!("Eval script error: {e}");
+    /// Check if the DevTool window is open
+    pub fn is_devtools_open(&self) -> bool {
+        let open = false;
+        #[cfg(any(debug_assertions, feature = "devtools"))]
+        let open = self.webview.is_devtools_open();
+        open
+    }
+
+    /// Set the color behind the page, like `(0, 0, 0, 0)` to see through it
+    ///
+    /// The window only shows what's behind it if it was built with
+    /// [`with_transparent`](crate::WindowBuilder::with_transparent), and a page that sets its own background covers the color.
+    pub fn set_background_color(&self, (red, green, blue, alpha): (u8, u8, u8, u8)) {
+        if let Err(e) = self.webview.set_background_color((red, green, blue, alpha)) {
+            log::warn!("Setting the background color failed: {e}");
         }
+    }
 
-        EvalResult::new(self.eval.clone())
+    /// Replace the icon of the window with an image of `width` by `height` RGBA pixels
+    pub fn set_icon(&self, rgba: Vec<u8>, width: u32, height: u32) -> Result<(), BadIcon> {
+        let icon = Icon::from_rgba(rgba, width, height)?;
+        self.webview.window().set_window_icon(Some(icon));
+        Ok(())
+    }
+
+    /// Run JavaScript in the webview, returning an [`Eval`] to talk to it and get what it returns
+    ///
+    /// See [`use_eval`](crate::use_eval) for what the script can do.
+    pub fn eval(&self, code: &str) -> Result<Eval, EvalError> {
+        let evaluator = self.evals.start(&self.webview, code)?;
+        Ok(Eval::new(evaluator))
     }
 
     /// Push an objc view to the window
@@ -260,8 +470,30 @@ pub struct UserWindowEvent(pub EventData, pub WindowId);
 pub enum EventData {
     Poll,
 
+    #[cfg(all(feature = "hot-reload", debug_assertions))]
+    HotReload(dioxus_core::Template<'static>),
+
     Ipc(IpcMessage),
 
+    /// The app was opened with a URL of its scheme, or opened again without one
+    #[cfg(feature = "deep-links")]
+    DeepLink(String),
+
+    /// An inspector asked about the VirtualDom of the window
+    #[cfg(feature = "inspector")]
+    Inspect(crate::inspector::InspectorRequest),
+
+    /// Assistive technology asked to do something to an element of the window
+    #[cfg(feature = "accessibility")]
+    Accessibility(accesskit::ActionRequest),
+
+    /// Files are dragged over the window, dropped on it, or dragged back out, at a point in logical pixels
+    FileDrop {
+        kind: &'static str,
+        x: f64,
+        y: f64,
+    },
+
     NewWindow,
 
     CloseWindow,
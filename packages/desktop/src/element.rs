@@ -0,0 +1,138 @@
+use std::rc::Rc;
+
+use crate::DesktopContext;
+use dioxus_core::ElementId;
+use dioxus_html::{
+    geometry::{euclid::Rect, ClientRect},
+    MountedError, MountedFuture, MountedResult, RenderedElement, ScrollBehavior,
+};
+
+/// A handle to an element mounted in the webview, passed to `onmounted` handlers
+///
+/// Every query is sent to the webview as a script, so the results come back asynchronously.
+#[derive(Clone)]
+pub struct DesktopElement {
+    id: ElementId,
+    desktop: DesktopContext,
+}
+
+impl DesktopElement {
+    pub(crate) fn new(id: ElementId, desktop: DesktopContext) -> Self {
+        Self { id, desktop }
+    }
+
+    /// The id of the element in the VirtualDom
+    pub fn id(&self) -> ElementId {
+        self.id
+    }
+
+    fn query<T: 'static>(
+        &self,
+        script: String,
+        read: impl FnOnce(serde_json::Value) -> Option<T> + 'static,
+    ) -> MountedFuture<T> {
+        let eval = self.desktop.eval(&script);
+
+        Box::pin(async move {
+            let value = eval
+                .map_err(|err| MountedError::OperationFailed(Box::new(err)))?
+                .join::<serde_json::Value>()
+                .await
+                .map_err(|err| MountedError::OperationFailed(Box::new(err)))?;
+
+            read(value).ok_or_else(|| {
+                MountedError::OperationFailed(Box::new(DesktopQueryError::ElementNotFound))
+            })
+        })
+    }
+}
+
+impl RenderedElement for DesktopElement {
+    fn get_raw_element(&self) -> MountedResult<&dyn std::any::Any> {
+        Ok(self)
+    }
+
+    fn get_client_rect(&self) -> MountedFuture<ClientRect> {
+        self.query(
+            format!("return window.interpreter.GetClientRect({});", self.id.0),
+            |value| {
+                let field = |name: &str| value.get(name)?.as_f64();
+                Some(Rect::new(
+                    (field("x")?, field("y")?).into(),
+                    (field("width")?, field("height")?).into(),
+                ))
+            },
+        )
+    }
+
+    fn scroll_to(&self, behavior: ScrollBehavior) -> MountedFuture<()> {
+        let behavior = match behavior {
+            ScrollBehavior::Instant => "instant",
+            ScrollBehavior::Smooth => "smooth",
+        };
+
+        self.query(
+            format!(
+                "return window.interpreter.ScrollTo({}, {:?});",
+                self.id.0, behavior
+            ),
+            found,
+        )
+    }
+
+    fn set_scroll_position(&self, top: f64, left: f64) -> MountedFuture<()> {
+        self.query(
+            format!(
+                "return window.interpreter.SetScrollPosition({}, {}, {});",
+                self.id.0, top, left
+            ),
+            found,
+        )
+    }
+
+    fn set_focus(&self, focus: bool) -> MountedFuture<()> {
+        self.query(
+            format!(
+                "return window.interpreter.SetFocus({}, {});",
+                self.id.0, focus
+            ),
+            found,
+        )
+    }
+}
+
+/// The interpreter answers `false` when the element is gone from the webview
+fn found(value: serde_json::Value) -> Option<()> {
+    value.as_bool().filter(|found| *found).map(|_| ())
+}
+
+#[derive(Debug)]
+enum DesktopQueryError {
+    ElementNotFound,
+}
+
+impl std::fmt::Display for DesktopQueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DesktopQueryError::ElementNotFound => write!(f, "the element is no longer mounted"),
+        }
+    }
+}
+
+impl std::error::Error for DesktopQueryError {}
+
+/// Send the `onmounted` events for the elements in a batch of edits the webview was just sent
+pub(crate) fn send_mounted_events(dom: &mut dioxus_core::VirtualDom, mounted: Vec<ElementId>) {
+    let desktop = match dom.base_scope().consume_context::<DesktopContext>() {
+        Some(desktop) => desktop,
+        None => return,
+    };
+
+    for id in mounted {
+        let data = Rc::new(dioxus_html::MountedData::new(DesktopElement::new(
+            id,
+            desktop.clone(),
+        )));
+        dom.handle_event("mounted", data, id, false);
+    }
+}
@@ -1,45 +1,180 @@
-use std::rc::Rc;
+use crate::escape::escape_js_string;
+use dioxus_hooks::{EvalBackend, EvalChannel, EvalError, Evaluator};
+use serde_json::Value;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+use std::task::{Context, Poll};
+use wry::webview::WebView;
 
-use crate::use_window;
-use dioxus_core::ScopeState;
-use serde::de::Error;
-use std::future::Future;
-use std::future::IntoFuture;
-use std::pin::Pin;
+/// Runs the scripts of [`use_eval`](dioxus_hooks::use_eval) in the webview of a window
+pub(crate) struct DesktopEval {
+    webview: Rc<WebView>,
+    scripts: Rc<EvalRegistry>,
+}
 
-/// A future that resolves to the result of a JavaScript evaluation.
-pub struct EvalResult {
-    pub(crate) broadcast: tokio::sync::broadcast::Sender<serde_json::Value>,
+impl DesktopEval {
+    pub(crate) fn new(webview: Rc<WebView>, scripts: Rc<EvalRegistry>) -> Self {
+        Self { webview, scripts }
+    }
 }
 
-impl EvalResult {
-    pub(crate) fn new(sender: tokio::sync::broadcast::Sender<serde_json::Value>) -> Self {
-        Self { broadcast: sender }
+impl EvalBackend for DesktopEval {
+    fn eval(&self, script: &str) -> Result<Rc<dyn Evaluator>, EvalError> {
+        self.scripts
+            .start(&self.webview, script)
+            .map(|evaluator| evaluator as Rc<dyn Evaluator>)
     }
 }
 
-impl IntoFuture for EvalResult {
-    type Output = Result<serde_json::Value, serde_json::Error>;
+/// The scripts running in a window, by the id the webview knows them by
+#[derive(Default)]
+pub(crate) struct EvalRegistry {
+    next_id: Cell<usize>,
+    running: RefCell<HashMap<usize, Weak<DesktopEvaluator>>>,
+    listeners: RefCell<HashMap<usize, Rc<dyn Fn(Value)>>>,
+}
+
+impl EvalRegistry {
+    pub(crate) fn start(
+        &self,
+        webview: &Rc<WebView>,
+        script: &str,
+    ) -> Result<Rc<DesktopEvaluator>, EvalError> {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+
+        webview
+            .evaluate_script(&wrap_script(id, script))
+            .map_err(|err| EvalError::Failed(err.to_string()))?;
+
+        let evaluator = Rc::new(DesktopEvaluator {
+            id,
+            webview: webview.clone(),
+            channel: EvalChannel::default(),
+        });
+        self.running
+            .borrow_mut()
+            .insert(id, Rc::downgrade(&evaluator));
+        Ok(evaluator)
+    }
+
+    /// Start a script whose messages are handed to `listener` as they arrive, instead of being queued for `recv`
+    pub(crate) fn listen(
+        &self,
+        webview: &Rc<WebView>,
+        script: &str,
+        listener: impl Fn(Value) + 'static,
+    ) -> Result<Rc<DesktopEvaluator>, EvalError> {
+        let evaluator = self.start(webview, script)?;
+        self.listeners
+            .borrow_mut()
+            .insert(evaluator.id, Rc::new(listener));
+        Ok(evaluator)
+    }
+
+    /// Stop handing the messages of a script to its listener
+    pub(crate) fn stop_listening(&self, evaluator: &DesktopEvaluator) {
+        self.listeners.borrow_mut().remove(&evaluator.id);
+    }
+
+    /// Route a message of a script to its evaluator, if it's still around
+    pub(crate) fn handle_message(&self, method: &str, params: &Value) {
+        let id = match params.get("id").and_then(Value::as_u64) {
+            Some(id) => id as usize,
+            None => return,
+        };
 
-    type IntoFuture = Pin<Box<dyn Future<Output = Result<serde_json::Value, serde_json::Error>>>>;
+        match method {
+            "eval_send" => {
+                // The listener is cloned out, so it can start or stop scripts itself
+                let listener = self.listeners.borrow().get(&id).cloned();
+                if let Some(listener) = listener {
+                    listener(params.get("data").cloned().unwrap_or(Value::Null));
+                    return;
+                }
 
-    fn into_future(self) -> Self::IntoFuture {
-        Box::pin(async move {
-            let mut reciever = self.broadcast.subscribe();
-            match reciever.recv().await {
-                Ok(result) => Ok(result),
-                Err(_) => Err(serde_json::Error::custom("No result returned")),
+                let evaluator = self.running.borrow().get(&id).and_then(Weak::upgrade);
+                if let Some(evaluator) = evaluator {
+                    let data = params.get("data").cloned().unwrap_or(Value::Null);
+                    evaluator.channel.receive(data);
+                }
+            }
+            "eval_result" => {
+                self.listeners.borrow_mut().remove(&id);
+                let evaluator = self.running.borrow_mut().remove(&id);
+                if let Some(evaluator) = evaluator.as_ref().and_then(Weak::upgrade) {
+                    let result = match params.get("error") {
+                        Some(error) => Err(EvalError::Failed(
+                            error.as_str().unwrap_or_default().to_string(),
+                        )),
+                        None => Ok(params.get("ok").cloned().unwrap_or(Value::Null)),
+                    };
+                    evaluator.channel.finish(result);
+                }
             }
-        }) as Pin<Box<dyn Future<Output = Result<serde_json::Value, serde_json::Error>>>>
+            _ => {}
+        }
     }
 }
 
-/// Get a closure that executes any JavaScript in the WebView context.
-pub fn use_eval(cx: &ScopeState) -> &Rc<dyn Fn(String) -> EvalResult> {
-    let desktop = use_window(cx);
-    &*cx.use_hook(|| {
-        let desktop = desktop.clone();
+/// A script running in the webview
+pub(crate) struct DesktopEvaluator {
+    id: usize,
+    webview: Rc<WebView>,
+    channel: EvalChannel,
+}
+
+impl Evaluator for DesktopEvaluator {
+    fn send(&self, value: Value) -> Result<(), EvalError> {
+        if self.channel.is_finished() {
+            return Err(EvalError::Finished);
+        }
+
+        let script = format!(
+            "window.__dioxusEvals && window.__dioxusEvals[{id}] && window.__dioxusEvals[{id}]({value});",
+            id = self.id,
+            value = value
+        );
+        self.webview
+            .evaluate_script(&script)
+            .map_err(|err| EvalError::Failed(err.to_string()))
+    }
+
+    fn poll_recv(&self, cx: &mut Context<'_>) -> Poll<Result<Value, EvalError>> {
+        self.channel.poll_recv(cx)
+    }
+
+    fn poll_join(&self, cx: &mut Context<'_>) -> Poll<Result<Value, EvalError>> {
+        self.channel.poll_join(cx)
+    }
+}
 
-        Rc::new(move |script: String| desktop.eval(&script)) as Rc<dyn Fn(String) -> EvalResult>
-    })
+/// Run the script as the body of an async function with a `dioxus` object to talk to Rust
+///
+/// The script is compiled at runtime so a syntax error is reported like any other error it throws.
+fn wrap_script(id: usize, script: &str) -> String {
+    format!(
+        r#"
+        (() => {{
+            const id = {id};
+            const post = (method, params) => window.ipc.postMessage(JSON.stringify({{ method, params }}));
+            const received = [];
+            const waiting = [];
+            window.__dioxusEvals = window.__dioxusEvals || {{}};
+            window.__dioxusEvals[id] = (value) => waiting.length ? waiting.shift()(value) : received.push(value);
+            const dioxus = {{
+                send: (value) => post("eval_send", {{ id, data: value === undefined ? null : value }}),
+                recv: () => new Promise((resolve) => received.length ? resolve(received.shift()) : waiting.push(resolve)),
+            }};
+            const AsyncFunction = Object.getPrototypeOf(async function () {{}}).constructor;
+            new Promise((resolve) => resolve(new AsyncFunction("dioxus", {script})(dioxus)))
+                .then((value) => post("eval_result", {{ id, ok: value === undefined ? null : value }}))
+                .catch((error) => post("eval_result", {{ id, error: String(error) }}))
+                .finally(() => delete window.__dioxusEvals[id]);
+        }})();
+        "#,
+        id = id,
+        script = escape_js_string(script)
+    )
 }
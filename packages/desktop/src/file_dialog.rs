@@ -0,0 +1,135 @@
+use crate::file_engine::NativeFileEngine;
+use crate::DesktopContext;
+use dioxus_html::FileEngine;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// The options of a native file dialog
+///
+/// ```rust, ignore
+/// let desktop = use_window(cx).clone();
+/// cx.spawn(async move {
+///     let dialog = FileDialog::new().add_filter("Images", &["png", "jpg"]).multiple(true);
+///     if let Some(files) = desktop.open_file_dialog(dialog).await {
+///         for name in files.files() {
+///             let bytes = files.read_file(&name).await;
+///         }
+///     }
+/// });
+/// ```
+#[derive(Default)]
+pub struct FileDialog {
+    title: Option<String>,
+    directory: Option<PathBuf>,
+    file_name: Option<String>,
+    filters: Vec<(String, Vec<String>)>,
+    multiple: bool,
+}
+
+impl FileDialog {
+    /// A dialog that picks any file
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the title of the dialog
+    pub fn set_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the folder the dialog opens in
+    pub fn set_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    /// Set the name a save dialog suggests
+    pub fn set_file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// Only show the files with these extensions, which are written without the dot
+    ///
+    /// With more than one filter, the user picks which one applies.
+    pub fn add_filter(mut self, name: impl Into<String>, extensions: &[&str]) -> Self {
+        self.filters.push((
+            name.into(),
+            extensions.iter().map(|ext| ext.to_string()).collect(),
+        ));
+        self
+    }
+
+    /// Let the user pick more than one file to open
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    fn into_rfd(self) -> rfd::AsyncFileDialog {
+        let mut dialog = rfd::AsyncFileDialog::new();
+        if let Some(title) = &self.title {
+            dialog = dialog.set_title(title);
+        }
+        if let Some(directory) = &self.directory {
+            dialog = dialog.set_directory(directory);
+        }
+        if let Some(file_name) = &self.file_name {
+            dialog = dialog.set_file_name(file_name);
+        }
+        for (name, extensions) in &self.filters {
+            dialog = dialog.add_filter(name, extensions.as_slice());
+        }
+        dialog
+    }
+}
+
+impl DesktopContext {
+    /// Ask the user for files to open, or `None` if they cancel
+    ///
+    /// The picked files are read like the files of a form event: their names are their full paths.
+    pub fn open_file_dialog(
+        &self,
+        dialog: FileDialog,
+    ) -> impl Future<Output = Option<Arc<dyn FileEngine>>> + 'static {
+        let multiple = dialog.multiple;
+        let dialog = dialog.into_rfd();
+        async move {
+            let paths: Vec<PathBuf> = if multiple {
+                dialog
+                    .pick_files()
+                    .await?
+                    .into_iter()
+                    .map(|file| file.path().to_path_buf())
+                    .collect()
+            } else {
+                vec![dialog.pick_file().await?.path().to_path_buf()]
+            };
+
+            let engine: Arc<dyn FileEngine> = Arc::new(NativeFileEngine::new(paths));
+            Some(engine)
+        }
+    }
+
+    /// Ask the user for a folder, or `None` if they cancel
+    pub fn pick_folder_dialog(
+        &self,
+        dialog: FileDialog,
+    ) -> impl Future<Output = Option<PathBuf>> + 'static {
+        let dialog = dialog.into_rfd();
+        async move { Some(dialog.pick_folder().await?.path().to_path_buf()) }
+    }
+
+    /// Ask the user where to save a file, or `None` if they cancel
+    ///
+    /// Nothing is written, the app writes the file to the path itself.
+    pub fn save_file_dialog(
+        &self,
+        dialog: FileDialog,
+    ) -> impl Future<Output = Option<PathBuf>> + 'static {
+        let dialog = dialog.into_rfd();
+        async move { Some(dialog.save_file().await?.path().to_path_buf()) }
+    }
+}
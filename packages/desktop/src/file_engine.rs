@@ -0,0 +1,40 @@
+use dioxus_html::FileEngine;
+use std::path::PathBuf;
+
+/// Reads the files that were dropped on the window from their paths
+///
+/// The names of the files are their full paths.
+pub(crate) struct NativeFileEngine {
+    paths: Vec<PathBuf>,
+}
+
+impl NativeFileEngine {
+    pub(crate) fn new(paths: Vec<PathBuf>) -> Self {
+        Self { paths }
+    }
+
+    /// Only the dropped files can be read, not any file the name happens to point to
+    fn find(&self, name: &str) -> Option<&PathBuf> {
+        self.paths
+            .iter()
+            .find(|path| path.display().to_string() == name)
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl FileEngine for NativeFileEngine {
+    fn files(&self) -> Vec<String> {
+        self.paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect()
+    }
+
+    async fn read_file(&self, file: &str) -> Option<Vec<u8>> {
+        std::fs::read(self.find(file)?).ok()
+    }
+
+    async fn read_file_to_string(&self, file: &str) -> Option<String> {
+        std::fs::read_to_string(self.find(file)?).ok()
+    }
+}
@@ -0,0 +1,40 @@
+use dioxus_hooks::{HeadBackend, HeadElement};
+use std::rc::Rc;
+use wry::webview::WebView;
+
+/// Backs `use_head` with the head of the page in the webview, and the title of the window
+pub(crate) struct DesktopHead {
+    webview: Rc<WebView>,
+    window_title: String,
+}
+
+impl DesktopHead {
+    /// The window goes back to the title it was created with when no component sets one
+    pub(crate) fn new(webview: Rc<WebView>) -> Self {
+        let window_title = webview.window().title();
+        Self {
+            webview,
+            window_title,
+        }
+    }
+}
+
+impl HeadBackend for DesktopHead {
+    fn update(&self, elements: &[HeadElement]) {
+        let title = elements
+            .iter()
+            .rev()
+            .find(|element| element.tag() == "title")
+            .map_or(self.window_title.as_str(), HeadElement::text);
+        let window = self.webview.window();
+        if window.title() != title {
+            window.set_title(title);
+        }
+
+        let elements: Vec<_> = elements.iter().map(HeadElement::to_json).collect();
+        _ = self.webview.evaluate_script(&format!(
+            "window.interpreter.UpdateHead({});",
+            serde_json::Value::from(elements)
+        ));
+    }
+}
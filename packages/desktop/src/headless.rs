@@ -0,0 +1,311 @@
+//! Run an app in a window that's never shown, and drive it from a test
+//!
+//! ```rust, ignore
+//! #[test]
+//! fn counts_clicks() {
+//!     let mut app = HeadlessApp::new(app);
+//!     app.wait_for_render().unwrap();
+//!
+//!     app.send_event("#increment", "click").unwrap();
+//!     app.wait_for_render().unwrap();
+//!
+//!     assert!(app.html().unwrap().contains("High-Five counter: 1"));
+//!     std::fs::write("counter.png", app.screenshot().unwrap()).unwrap();
+//! }
+//! ```
+//!
+//! The window still needs a windowing system, so tests on a Linux CI machine without a display run under something
+//! like `xvfb-run`. The event loop can only ever run on one thread of a process: on Linux and Windows that can be any
+//! thread, as long as every headless app of the test binary is created on the same one. macOS only allows the main
+//! thread, so the tests need `harness = false`.
+
+use crate::desktop_context::UserWindowEvent;
+use crate::escape::escape_js_string;
+use crate::{App, Config, DesktopContext};
+use dioxus_core::Component;
+use dioxus_hooks::EvalError;
+use futures_util::{pin_mut, FutureExt};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::fmt;
+use std::task::Poll;
+use std::time::{Duration, Instant};
+use wry::application::event_loop::{ControlFlow, EventLoop, EventLoopBuilder};
+use wry::application::platform::run_return::EventLoopExtRunReturn;
+
+/// An app running in a hidden window, which only moves when it's told to
+pub struct HeadlessApp {
+    event_loop: EventLoop<UserWindowEvent>,
+    app: App,
+    runtime: tokio::runtime::Runtime,
+    root_name: String,
+    timeout: Duration,
+}
+
+impl HeadlessApp {
+    /// Start an app with the default configuration
+    pub fn new(root: Component) -> Self {
+        Self::with_props(root, (), Config::default())
+    }
+
+    /// Start an app with props and a configuration
+    ///
+    /// The window of the configuration is kept hidden, and nothing happens until the app is told to do something.
+    pub fn with_props<P: 'static>(root: Component<P>, props: P, mut cfg: Config) -> Self {
+        let mut builder = EventLoopBuilder::<UserWindowEvent>::with_user_event();
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ))]
+        wry::application::platform::unix::EventLoopBuilderExtUnix::with_any_thread(
+            &mut builder,
+            true,
+        );
+        #[cfg(target_os = "windows")]
+        wry::application::platform::windows::EventLoopBuilderExtWindows::with_any_thread(
+            &mut builder,
+            true,
+        );
+        let event_loop = builder.build();
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        cfg.window = std::mem::take(&mut cfg.window).with_visible(false);
+        let root_name = cfg.root_name.clone();
+
+        let app = {
+            let _guard = runtime.enter();
+            App::new(root, props, cfg, &event_loop)
+        };
+
+        Self {
+            event_loop,
+            app,
+            runtime,
+            root_name,
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Give up waiting on the app after this long, instead of after ten seconds
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The context of the window, like a component gets from [`use_window`](crate::use_window)
+    pub fn desktop(&self) -> Result<DesktopContext, HeadlessError> {
+        self.app
+            .desktop_context(self.app.main_window)
+            .ok_or(HeadlessError::Closed)
+    }
+
+    /// Run the event loop until the app is done rendering
+    ///
+    /// The app is done once the webview applied every edit, and handled every event it sent the app, without the
+    /// app rendering again.
+    pub fn wait_for_render(&mut self) -> Result<(), HeadlessError> {
+        let deadline = Instant::now() + self.timeout;
+
+        loop {
+            let renders = self.app.renders;
+
+            // The webview runs scripts in order and sends its messages in order, so once this one returns the webview
+            // applied the edits sent before it, and the app handled the events the webview sent before it
+            self.eval::<Value>("return null;")?;
+
+            if self.app.renders == renders {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(HeadlessError::Timeout);
+            }
+        }
+    }
+
+    /// Fire an event at the first element matching a CSS selector, like the user did it
+    ///
+    /// Mouse, keyboard and focus events are fired with their own kind of DOM event. The app handles the event the next
+    /// time it runs, like in [`HeadlessApp::wait_for_render`].
+    pub fn send_event(&mut self, selector: &str, event: &str) -> Result<(), HeadlessError> {
+        let script = format!(
+            r#"
+            const selector = {selector};
+            const name = {event};
+            const target = document.querySelector(selector);
+            if (!target) {{
+                throw new Error(`nothing matches ${{selector}}`);
+            }}
+            const Kind = /^(click|dblclick|contextmenu|mouse|pointer)/.test(name)
+                ? MouseEvent
+                : /^key/.test(name)
+                ? KeyboardEvent
+                : /^(focus|blur)/.test(name)
+                ? FocusEvent
+                : Event;
+            target.dispatchEvent(new Kind(name, {{ bubbles: true, cancelable: true }}));
+            return null;
+            "#,
+            selector = escape_js_string(selector),
+            event = escape_js_string(event),
+        );
+        self.eval::<Value>(&script).map(|_| ())
+    }
+
+    /// The HTML the app rendered into the page
+    pub fn html(&mut self) -> Result<String, HeadlessError> {
+        self.eval(&format!(
+            "return document.getElementById({}).innerHTML;",
+            escape_js_string(&self.root_name)
+        ))
+    }
+
+    /// Draw the page into a PNG image the size of the window
+    ///
+    /// The webview draws the page into an image like it would draw an SVG with the page in it, so the screenshot is
+    /// close to what the window shows but not exact: stylesheets are inlined, but images and fonts that aren't loaded
+    /// in the page are left out. Webviews that don't allow reading back such an image fail with
+    /// [`HeadlessError::Screenshot`].
+    pub fn screenshot(&mut self) -> Result<Vec<u8>, HeadlessError> {
+        self.eval(SCREENSHOT_JS).map_err(|err| match err {
+            HeadlessError::Eval(EvalError::Failed(reason)) => HeadlessError::Screenshot(reason),
+            err => err,
+        })
+    }
+
+    /// Run a script in the page and run the event loop until it returns
+    pub fn eval<T: DeserializeOwned>(&mut self, script: &str) -> Result<T, HeadlessError> {
+        // the page has to load before it can run anything
+        let deadline = Instant::now() + self.timeout;
+        self.run_until(deadline, |app| app.renders > 0)?;
+
+        let eval = self.desktop()?.eval(script).map_err(HeadlessError::Eval)?;
+        let join = eval.join::<T>();
+        pin_mut!(join);
+
+        // the script finishes while the event loop handles its message, so it's polled after every event
+        let waker = futures_util::task::noop_waker();
+        let mut cx = std::task::Context::from_waker(&waker);
+        let mut result = None;
+        self.run_until(deadline, |_| match join.poll_unpin(&mut cx) {
+            Poll::Ready(value) => {
+                result = Some(value);
+                true
+            }
+            Poll::Pending => false,
+        })?;
+
+        result
+            .expect("the script finished")
+            .map_err(HeadlessError::Eval)
+    }
+
+    /// Run the event loop until `done` says so, the window closes, or the deadline passes
+    fn run_until(
+        &mut self,
+        deadline: Instant,
+        mut done: impl FnMut(&App) -> bool,
+    ) -> Result<(), HeadlessError> {
+        let Self {
+            event_loop,
+            app,
+            runtime,
+            ..
+        } = self;
+
+        if app.desktop_context(app.main_window).is_none() {
+            return Err(HeadlessError::Closed);
+        }
+
+        let _guard = runtime.enter();
+        let mut result = Err(HeadlessError::Timeout);
+        event_loop.run_return(|event, _, control_flow| {
+            app.handle_event(event, control_flow);
+
+            if app.desktop_context(app.main_window).is_none() {
+                result = Err(HeadlessError::Closed);
+                *control_flow = ControlFlow::Exit;
+            } else if done(app) {
+                result = Ok(());
+                *control_flow = ControlFlow::Exit;
+            } else if Instant::now() >= deadline {
+                *control_flow = ControlFlow::Exit;
+            } else {
+                *control_flow = ControlFlow::WaitUntil(deadline);
+            }
+        });
+
+        result
+    }
+}
+
+/// Why a headless app couldn't do what it was told
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeadlessError {
+    /// The window of the app closed
+    Closed,
+
+    /// The app didn't finish in time
+    Timeout,
+
+    /// A script in the page failed
+    Eval(EvalError),
+
+    /// The webview couldn't draw the page into an image
+    Screenshot(String),
+}
+
+impl fmt::Display for HeadlessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeadlessError::Closed => write!(f, "the window of the app closed"),
+            HeadlessError::Timeout => write!(f, "the app took too long"),
+            HeadlessError::Eval(err) => write!(f, "{}", err),
+            HeadlessError::Screenshot(reason) => {
+                write!(f, "the page couldn't be drawn into an image: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HeadlessError {}
+
+// Inline the styles into a copy of the page, draw it as an SVG image into a canvas, and return the bytes of the PNG
+const SCREENSHOT_JS: &str = r#"
+const width = document.documentElement.clientWidth;
+const height = document.documentElement.clientHeight;
+const page = document.documentElement.cloneNode(true);
+page.querySelectorAll("script, link[rel=stylesheet]").forEach((node) => node.remove());
+const style = document.createElement("style");
+style.textContent = Array.from(document.styleSheets)
+    .map((sheet) => {
+        try {
+            return Array.from(sheet.cssRules).map((rule) => rule.cssText).join("\n");
+        } catch {
+            return "";
+        }
+    })
+    .join("\n");
+page.querySelector("head")?.appendChild(style);
+const svg = `<svg xmlns="http://www.w3.org/2000/svg" width="${width}" height="${height}">`
+    + `<foreignObject width="100%" height="100%">${new XMLSerializer().serializeToString(page)}</foreignObject></svg>`;
+const image = new Image();
+image.src = "data:image/svg+xml;charset=utf-8," + encodeURIComponent(svg);
+await image.decode();
+const canvas = document.createElement("canvas");
+canvas.width = width * window.devicePixelRatio;
+canvas.height = height * window.devicePixelRatio;
+const context = canvas.getContext("2d");
+context.scale(window.devicePixelRatio, window.devicePixelRatio);
+context.drawImage(image, 0, 0);
+const png = await new Promise((resolve, reject) =>
+    canvas.toBlob((blob) => (blob ? resolve(blob) : reject(new Error("the canvas is empty"))), "image/png")
+);
+return Array.from(new Uint8Array(await png.arrayBuffer()));
+"#;
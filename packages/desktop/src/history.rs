@@ -0,0 +1,70 @@
+use dioxus_core::VirtualDom;
+use dioxus_history::History;
+use std::rc::Rc;
+
+/// Move the history of the window like the page asked for, when back or forward was pressed
+pub(crate) fn handle_message(dom: &VirtualDom, params: &serde_json::Value) {
+    let delta = match params.get("delta").and_then(|delta| delta.as_i64()) {
+        Some(delta) => delta as isize,
+        None => return,
+    };
+    if let Some(history) = dom.base_scope().consume_context::<Rc<dyn History>>() {
+        history.go(delta);
+    }
+}
+
+/// Sends the back and forward keys and mouse buttons of browsers to the history of the window
+///
+/// Alt with the arrow keys is left alone in text fields, where it moves the cursor by words.
+pub(crate) const HISTORY_JS: &str = r#"
+function historyGo(delta) {
+  window.ipc.postMessage(serializeIpcMessage("history", { delta }));
+}
+document.addEventListener("keydown", (event) => {
+  if (event.defaultPrevented) {
+    return;
+  }
+  const editing = event.target instanceof Element &&
+    event.target.closest("input, textarea, select, [contenteditable]");
+  if (event.altKey && !editing && event.key === "ArrowLeft") {
+    historyGo(-1);
+  } else if (event.altKey && !editing && event.key === "ArrowRight") {
+    historyGo(1);
+  } else if (event.metaKey && event.key === "[") {
+    historyGo(-1);
+  } else if (event.metaKey && event.key === "]") {
+    historyGo(1);
+  } else {
+    return;
+  }
+  event.preventDefault();
+});
+document.addEventListener("mouseup", (event) => {
+  if (event.button === 3) {
+    historyGo(-1);
+  } else if (event.button === 4) {
+    historyGo(1);
+  }
+});
+"#;
+
+#[test]
+fn moves_the_history_of_the_window() {
+    use dioxus_core::{Element, Scope};
+    use dioxus_history::MemoryHistory;
+
+    fn app(_: Scope) -> Element {
+        None
+    }
+
+    let dom = VirtualDom::new(app);
+    let history: Rc<dyn History> = Rc::new(MemoryHistory::with_initial_path("/"));
+    history.push("/settings");
+    dom.base_scope().provide_context(history.clone());
+
+    handle_message(&dom, &serde_json::json!({ "delta": -1 }));
+    assert_eq!(history.current_path(), "/");
+
+    handle_message(&dom, &serde_json::json!({ "delta": 1 }));
+    assert_eq!(history.current_path(), "/settings");
+}
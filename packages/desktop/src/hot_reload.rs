@@ -1,11 +1,13 @@
 #![allow(dead_code)]
 
+use crate::desktop_context::{EventData, UserWindowEvent};
 use dioxus_core::Template;
-
 use interprocess::local_socket::{LocalSocketListener, LocalSocketStream};
 use std::io::{BufRead, BufReader};
 use std::time::Duration;
 use std::{sync::Arc, sync::Mutex};
+use wry::application::event_loop::EventLoopProxy;
+use wry::application::window::WindowId;
 
 fn handle_error(connection: std::io::Result<LocalSocketStream>) -> Option<LocalSocketStream> {
     connection
@@ -13,7 +15,10 @@ fn handle_error(connection: std::io::Result<LocalSocketStream>) -> Option<LocalS
         .ok()
 }
 
-pub(crate) fn init(proxy: futures_channel::mpsc::UnboundedSender<Template<'static>>) {
+/// Listen for templates from the hot reload server, forwarding each one to the event loop
+///
+/// Templates apply to every window, so the window id is only there to fill out the event.
+pub(crate) fn init(proxy: EventLoopProxy<UserWindowEvent>, window: WindowId) {
     let latest_in_connection: Arc<Mutex<Option<BufReader<LocalSocketStream>>>> =
         Arc::new(Mutex::new(None));
 
@@ -38,7 +43,7 @@ pub(crate) fn init(proxy: futures_channel::mpsc::UnboundedSender<Template<'stati
                     Ok(_) => {
                         let msg: Template<'static> =
                             serde_json::from_str(Box::leak(buf.into_boxed_str())).unwrap();
-                        proxy.unbounded_send(msg).unwrap();
+                        _ = proxy.send_event(UserWindowEvent(EventData::HotReload(msg), window));
                     }
                     Err(err) => {
                         if err.kind() != std::io::ErrorKind::WouldBlock {
@@ -0,0 +1,101 @@
+//! Serves the devtools protocol of the VirtualDom over a WebSocket, so an inspector can look at the running app
+
+use crate::desktop_context::{EventData, UserWindowEvent};
+use dioxus_core::{DevtoolsRequest, DevtoolsResponse, VirtualDom};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc;
+use tungstenite::Message;
+use wry::application::event_loop::EventLoopProxy;
+use wry::application::window::WindowId;
+use wry::webview::WebView;
+
+/// A request of an inspector, and where the event loop sends the answer to it
+#[derive(Debug, Clone)]
+pub(crate) struct InspectorRequest {
+    pub(crate) request: DevtoolsRequest,
+    pub(crate) reply: mpsc::Sender<DevtoolsResponse>,
+}
+
+/// Accept inspectors on the address, and hand their requests to the event loop for the window
+///
+/// Every inspector gets a thread of its own, which waits for the answer to one request before it reads the next.
+pub(crate) fn serve(addr: SocketAddr, proxy: EventLoopProxy<UserWindowEvent>, window: WindowId) {
+    let listener = match TcpListener::bind(addr) {
+        Ok(listener) => listener,
+        Err(err) => {
+            log::error!("Failed to start the inspector server on {}: {}", addr, err);
+            return;
+        }
+    };
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming().filter_map(Result::ok) {
+            let proxy = proxy.clone();
+            std::thread::spawn(move || inspect(stream, proxy, window));
+        }
+    });
+}
+
+fn inspect(stream: TcpStream, proxy: EventLoopProxy<UserWindowEvent>, window: WindowId) {
+    let mut socket = match tungstenite::accept(stream) {
+        Ok(socket) => socket,
+        Err(err) => {
+            log::error!("An inspector failed to connect: {}", err);
+            return;
+        }
+    };
+
+    loop {
+        let text = match socket.read_message() {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) | Err(_) => return,
+            Ok(_) => continue,
+        };
+        let request = match serde_json::from_str(&text) {
+            Ok(request) => request,
+            Err(err) => {
+                log::error!("The inspector sent a message that isn't a request: {}", err);
+                continue;
+            }
+        };
+
+        // the app is gone once the event loop or the window stops answering
+        let (reply, answer) = mpsc::channel();
+        let event = EventData::Inspect(InspectorRequest { request, reply });
+        if proxy.send_event(UserWindowEvent(event, window)).is_err() {
+            return;
+        }
+        let response = match answer.recv() {
+            Ok(response) => response,
+            Err(_) => return,
+        };
+
+        let response = serde_json::to_string(&response).unwrap();
+        if socket.write_message(Message::Text(response)).is_err() {
+            return;
+        }
+    }
+}
+
+/// Answer a request with the VirtualDom of a window, outlining components in its webview
+pub(crate) fn answer(
+    dom: &VirtualDom,
+    webview: &WebView,
+    request: DevtoolsRequest,
+) -> DevtoolsResponse {
+    match request {
+        DevtoolsRequest::Tree => DevtoolsResponse::Tree {
+            scopes: dom.inspect(),
+        },
+        DevtoolsRequest::Highlight { scope } => {
+            let elements = dom.scope_elements(scope);
+            let ids: Vec<usize> = elements.iter().map(|id| id.0).collect();
+            _ = webview.evaluate_script(&format!("window.interpreter.Highlight({:?});", ids));
+            DevtoolsResponse::Highlighted { elements }
+        }
+        DevtoolsRequest::ClearHighlight => {
+            _ = webview.evaluate_script("window.interpreter.Highlight([]);");
+            DevtoolsResponse::Cleared
+        }
+    }
+}
@@ -3,27 +3,86 @@
 #![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
 #![deny(missing_docs)]
 
+mod accessibility;
 mod cfg;
+mod clipboard;
+#[cfg(feature = "deep-links")]
+mod deep_link;
 mod desktop_context;
+mod element;
 mod escape;
 mod eval;
 mod events;
+#[cfg(all(
+    feature = "file-dialog",
+    not(any(target_os = "ios", target_os = "android"))
+))]
+mod file_dialog;
+mod file_engine;
+mod head;
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+mod headless;
+mod history;
+#[cfg(feature = "inspector")]
+mod inspector;
+mod listener;
+mod menu;
+#[cfg(all(
+    feature = "notifications",
+    not(any(target_os = "ios", target_os = "android"))
+))]
+mod notification;
+mod observer;
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+mod pdf;
 mod protocol;
-mod waker;
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+mod shortcut;
+mod storage;
+mod titlebar;
+#[cfg(feature = "tray")]
+mod tray;
 mod webview;
+mod window;
+mod window_state;
 
 #[cfg(all(feature = "hot-reload", debug_assertions))]
 mod hot_reload;
 
 pub use cfg::Config;
-pub use desktop_context::{use_window, DesktopContext};
-use desktop_context::{EventData, UserWindowEvent, WebviewQueue};
+#[cfg(feature = "deep-links")]
+pub use deep_link::use_deep_link_handler;
+pub use desktop_context::{use_window, use_window_close_handler, DesktopContext};
+use desktop_context::{EventData, UserWindowEvent, WebviewQueue, WindowRegistry};
 use dioxus_core::*;
-use dioxus_html::HtmlEvent;
-pub use eval::{use_eval, EvalResult};
-use futures_util::{pin_mut, FutureExt};
+pub use dioxus_hooks::{use_eval, Eval, EvalError};
+use dioxus_html::{FileChunk, HtmlEvent, SerializedFileEngine};
+pub use element::DesktopElement;
+#[cfg(all(
+    feature = "file-dialog",
+    not(any(target_os = "ios", target_os = "android"))
+))]
+pub use file_dialog::FileDialog;
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+pub use headless::{HeadlessApp, HeadlessError};
+pub use menu::{use_menu_event_handler, Menu, MenuBar, NativeMenuItem};
+#[cfg(all(
+    feature = "notifications",
+    not(any(target_os = "ios", target_os = "android"))
+))]
+pub use notification::{
+    use_notifier, Notification, NotificationError, NotificationEvent, Notifier, PendingNotification,
+};
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+pub use pdf::{PdfError, PdfOptions};
+pub use protocol::Asset;
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+pub use shortcut::{use_global_shortcut, GlobalShortcutHandle};
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::rc::Rc;
+use std::sync::Arc;
 use std::task::Waker;
 pub use tao::dpi::{LogicalSize, PhysicalSize};
 use tao::event_loop::{EventLoopProxy, EventLoopWindowTarget};
@@ -32,6 +91,9 @@ use tao::{
     event::{Event, StartCause, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
 };
+pub use titlebar::WindowControl;
+#[cfg(feature = "tray")]
+pub use tray::{use_tray_event_handler, Tray, TrayEvent, TrayIcon, TrayMenu};
 pub use wry;
 pub use wry::application as tao;
 use wry::application::window::WindowId;
@@ -105,9 +167,16 @@ pub fn launch_cfg(root: Component, config_builder: Config) {
 /// }
 /// ```
 pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, cfg: Config) {
-    let event_loop = EventLoop::<UserWindowEvent>::with_user_event();
+    // Only one instance of an app with a scheme runs, and it gets the links the others are opened with
+    #[cfg(feature = "deep-links")]
+    if let Some(scheme) = &cfg.url_scheme {
+        let url = deep_link::url_from_args(scheme);
+        if deep_link::forward_to_running_instance(scheme, url.as_deref()) {
+            return;
+        }
+    }
 
-    let proxy = event_loop.create_proxy();
+    let event_loop = EventLoop::<UserWindowEvent>::with_user_event();
 
     // We start the tokio runtime *on this thread*
     // Any future we poll later will use this runtime to spawn tasks and for IO
@@ -119,144 +188,535 @@ pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, cfg: Config)
     // We enter the runtime but we poll futures manually, circumventing the per-task runtime budget
     let _guard = rt.enter();
 
+    let mut app = App::new(root, props, cfg, &event_loop);
+
+    #[cfg(all(feature = "hot-reload", debug_assertions))]
+    hot_reload::init(app.proxy.clone(), app.main_window);
+
+    event_loop.run(move |window_event, _event_loop, control_flow| {
+        app.handle_event(window_event, control_flow)
+    })
+}
+
+/// The windows of a running app and everything the event loop needs to drive them
+pub(crate) struct App {
+    proxy: EventLoopProxy<UserWindowEvent>,
+
     // We only have one webview right now, but we'll have more later
     // Store them in a hashmap so we can remove them when they're closed
-    let mut webviews = HashMap::<WindowId, WebviewHandler>::new();
+    webviews: HashMap<WindowId, WebviewHandler>,
 
-    let queue = WebviewQueue::default();
+    queue: WebviewQueue,
 
-    // By default, we'll create a new window when the app starts
-    queue.borrow_mut().push(create_new_window(
-        cfg,
-        &event_loop,
-        &proxy,
-        VirtualDom::new_with_props(root, props),
-        &queue,
-    ));
+    windows: Rc<WindowRegistry>,
 
-    event_loop.run(move |window_event, _event_loop, control_flow| {
+    // The tray lives as long as the event loop, not the window that was configured with it
+    #[cfg(feature = "tray")]
+    tray: Option<tray::ActiveTray>,
+
+    /// The window the app was launched with
+    pub(crate) main_window: WindowId,
+
+    /// Routes the main window to the links the app is opened with
+    #[cfg(feature = "deep-links")]
+    deep_link_history: Option<Rc<deep_link::DeepLinkHistory>>,
+
+    /// How many times the windows sent edits to their webviews, so a headless app can tell when they're done
+    pub(crate) renders: usize,
+}
+
+impl App {
+    pub(crate) fn new<P: 'static>(
+        root: Component<P>,
+        props: P,
+        mut cfg: Config,
+        event_loop: &EventLoopWindowTarget<UserWindowEvent>,
+    ) -> Self {
+        let proxy = event_loop.create_proxy();
+        let queue = WebviewQueue::default();
+        let windows = Rc::new(WindowRegistry::default());
+
+        #[cfg(feature = "tray")]
+        let tray = cfg
+            .tray
+            .take()
+            .and_then(|tray| tray::ActiveTray::new(tray, event_loop));
+
+        #[cfg(feature = "deep-links")]
+        let url_scheme = cfg.url_scheme.take();
+
+        #[cfg(feature = "inspector")]
+        let inspector = cfg.inspector.take();
+
+        // By default, we'll create a new window when the app starts
+        queue.borrow_mut().push(create_new_window(
+            cfg,
+            event_loop,
+            &proxy,
+            VirtualDom::new_with_props(root, props),
+            &queue,
+            &windows,
+        ));
+        let main_window = queue.borrow()[0].webview.window().id();
+
+        #[cfg(feature = "inspector")]
+        if let Some(addr) = inspector {
+            inspector::serve(addr, proxy.clone(), main_window);
+        }
+
+        #[cfg(feature = "deep-links")]
+        let deep_link_history = url_scheme.map(|scheme| {
+            if let Err(err) = deep_link::register(&scheme) {
+                log::error!("Failed to register the url scheme {}: {}", scheme, err);
+            }
+            deep_link::listen(&scheme, proxy.clone(), main_window);
+
+            let url = deep_link::url_from_args(&scheme);
+            let history = Rc::new(deep_link::DeepLinkHistory::new(url.as_deref()));
+            queue.borrow()[0]
+                .dom
+                .base_scope()
+                .provide_context(history.clone() as Rc<dyn dioxus_history::History>);
+            *windows.launch_url.borrow_mut() = url;
+            history
+        });
+
+        Self {
+            proxy,
+            webviews: HashMap::new(),
+            queue,
+            windows,
+            #[cfg(feature = "tray")]
+            tray,
+            main_window,
+            #[cfg(feature = "deep-links")]
+            deep_link_history,
+            renders: 0,
+        }
+    }
+
+    /// The context of a window that's open, or that opens the next time the event loop runs
+    pub(crate) fn desktop_context(&self, id: WindowId) -> Option<DesktopContext> {
+        let context = |view: &WebviewHandler| view.dom.base_scope().consume_context();
+        match self.webviews.get(&id) {
+            Some(view) => context(view),
+            None => self
+                .queue
+                .borrow()
+                .iter()
+                .find(|view| view.webview.window().id() == id)
+                .and_then(context),
+        }
+    }
+
+    pub(crate) fn handle_event(
+        &mut self,
+        window_event: Event<'_, UserWindowEvent>,
+        control_flow: &mut ControlFlow,
+    ) {
         *control_flow = ControlFlow::Wait;
 
         match window_event {
             Event::WindowEvent {
                 event, window_id, ..
             } => match event {
-                WindowEvent::CloseRequested => {
-                    webviews.remove(&window_id);
-
-                    if webviews.is_empty() {
-                        *control_flow = ControlFlow::Exit
+                #[cfg(feature = "tray")]
+                WindowEvent::CloseRequested
+                    if window_id == self.main_window
+                        && self.tray.as_ref().map_or(false, |tray| tray.hide_on_close) =>
+                {
+                    if let Some(view) = self.webviews.get(&window_id) {
+                        view.webview.window().set_visible(false);
                     }
                 }
-                WindowEvent::Destroyed { .. } => {
-                    webviews.remove(&window_id);
-
-                    if webviews.is_empty() {
-                        *control_flow = ControlFlow::Exit;
-                    }
+                WindowEvent::CloseRequested | WindowEvent::Destroyed { .. } => {
+                    close_window(&mut self.webviews, &self.windows, window_id, control_flow);
+                }
+                // use_window_size and use_media_query check their window again
+                WindowEvent::Resized(_) | WindowEvent::ScaleFactorChanged { .. } => {
+                    dioxus_hooks::window_changed();
                 }
                 _ => {}
             },
 
+            #[cfg(not(any(target_os = "ios", target_os = "android")))]
+            Event::GlobalShortcutEvent(id) => shortcut::handle_global_shortcut(id),
+
+            #[cfg(feature = "tray")]
+            Event::TrayEvent { event, .. } => {
+                let _ = self.windows.tray_events.send(tray::icon_event(event));
+            }
+
+            Event::MenuEvent {
+                window_id: Some(window_id),
+                menu_id,
+                origin: tao::menu::MenuType::MenuBar,
+                ..
+            } => {
+                if let Some(view) = self.webviews.get(&window_id) {
+                    if let Some(id) = view.menu_ids.get(&menu_id) {
+                        let _ = view.menu_events.send(id.clone());
+                    }
+                }
+            }
+
+            #[cfg(feature = "tray")]
+            Event::MenuEvent {
+                menu_id,
+                origin: tao::menu::MenuType::ContextMenu,
+                ..
+            } => {
+                if let Some(event) = self.tray.as_ref().and_then(|tray| tray.menu_event(menu_id)) {
+                    let _ = self.windows.tray_events.send(event);
+                }
+            }
+
             Event::NewEvents(StartCause::Init)
             | Event::UserEvent(UserWindowEvent(EventData::NewWindow, _)) => {
-                for handler in queue.borrow_mut().drain(..) {
+                for handler in self.queue.borrow_mut().drain(..) {
                     let id = handler.webview.window().id();
-                    webviews.insert(id, handler);
-                    _ = proxy.send_event(UserWindowEvent(EventData::Poll, id));
+                    self.webviews.insert(id, handler);
+                    _ = self.proxy.send_event(UserWindowEvent(EventData::Poll, id));
                 }
             }
 
             Event::UserEvent(event) => match event.0 {
                 EventData::CloseWindow => {
-                    webviews.remove(&event.1);
+                    close_window(&mut self.webviews, &self.windows, event.1, control_flow);
+                }
 
-                    if webviews.is_empty() {
-                        *control_flow = ControlFlow::Exit
+                EventData::Poll => {
+                    if let Some(view) = self.webviews.get_mut(&event.1) {
+                        self.renders += poll_vdom(view);
                     }
                 }
 
-                EventData::Poll => {
-                    if let Some(view) = webviews.get_mut(&event.1) {
-                        poll_vdom(view);
+                // Templates are shared by every window, so they all need to pick up the new one
+                #[cfg(all(feature = "hot-reload", debug_assertions))]
+                EventData::HotReload(template) => {
+                    for view in self.webviews.values_mut() {
+                        view.dom.replace_template(template);
+                        let mounted = send_edits(
+                            view.dom.render_immediate(),
+                            &view.webview,
+                            &mut view.accessibility,
+                        );
+                        element::send_mounted_events(&mut view.dom, mounted);
+                        self.renders += 1;
                     }
                 }
 
                 EventData::Ipc(msg) if msg.method() == "user_event" => {
-                    let evt = match serde_json::from_value::<HtmlEvent>(msg.params()) {
+                    let params = msg.params();
+                    let href = params
+                        .get("href")
+                        .and_then(|href| href.as_str())
+                        .map(str::to_string);
+                    let mut evt = match serde_json::from_value::<HtmlEvent>(params) {
                         Ok(value) => value,
                         Err(_) => return,
                     };
 
-                    let view = webviews.get_mut(&event.1).unwrap();
+                    let view = self.webviews.get_mut(&event.1).unwrap();
+
+                    // The files the webview read are sent right before the event they belong to. Files dragged in
+                    // from outside are read from the paths the window reported, which are kept until the drop.
+                    let dropped = match evt.name.as_str() {
+                        "drop" => std::mem::take(&mut *view.dropped_files.borrow_mut()),
+                        "dragenter" | "dragover" => view.dropped_files.borrow().clone(),
+                        _ => Vec::new(),
+                    };
+                    if !view.pending_files.is_empty() {
+                        let files = std::mem::take(&mut view.pending_files);
+                        evt.data.set_files(Arc::new(files));
+                    } else if !dropped.is_empty() {
+                        let files = file_engine::NativeFileEngine::new(dropped);
+                        evt.data.set_files(Arc::new(files));
+                    }
+
+                    let prevented = view.dom.handle_event(
+                        &evt.name,
+                        evt.data.into_any(),
+                        evt.element,
+                        evt.bubbles,
+                    );
+
+                    // Links open in the browser of the system, unless a listener prevented the default of the click
+                    if let (false, Some(href)) = (prevented, href) {
+                        open_browser(&href);
+                    }
 
-                    view.dom
-                        .handle_event(&evt.name, evt.data.into_any(), evt.element, evt.bubbles);
+                    let mounted = send_edits(
+                        view.dom.render_immediate(),
+                        &view.webview,
+                        &mut view.accessibility,
+                    );
+
+                    // Inputs with a value are controlled by the app, so edits it didn't accept are undone
+                    if matches!(evt.name.as_str(), "input" | "change") {
+                        _ = view
+                            .webview
+                            .evaluate_script("window.interpreter.RestoreControlled();");
+                    }
+                    element::send_mounted_events(&mut view.dom, mounted);
+                    self.renders += 1;
+                }
 
-                    send_edits(view.dom.render_immediate(), &view.webview);
+                #[cfg(feature = "deep-links")]
+                EventData::DeepLink(url) => {
+                    if let Some(view) = self.webviews.get(&event.1) {
+                        let window = view.webview.window();
+                        window.set_visible(true);
+                        window.set_minimized(false);
+                        window.set_focus();
+                    }
+
+                    // another instance was opened without a link, so there's nothing else to do
+                    if !url.is_empty() {
+                        if let Some(history) = &self.deep_link_history {
+                            history.open(&url);
+                        }
+                        let _ = self.windows.deep_links.send(url);
+                    }
+                }
+
+                #[cfg(feature = "inspector")]
+                EventData::Inspect(inspect) => {
+                    if let Some(view) = self.webviews.get(&event.1) {
+                        let response = inspector::answer(&view.dom, &view.webview, inspect.request);
+                        _ = inspect.reply.send(response);
+                    }
+                }
+
+                #[cfg(feature = "accessibility")]
+                EventData::Accessibility(request) => {
+                    if let Some(view) = self.webviews.get(&event.1) {
+                        view.accessibility.action(&view.webview, request);
+                    }
+                }
+
+                EventData::FileDrop { kind, x, y } => {
+                    if let Some(view) = self.webviews.get(&event.1) {
+                        _ = view.webview.evaluate_script(&format!(
+                            "window.interpreter.DispatchFileDrop({:?}, {}, {});",
+                            kind, x, y
+                        ));
+                    }
+                }
+
+                EventData::Ipc(msg) if msg.method() == "file_chunk" => {
+                    let chunk = match serde_json::from_value::<FileChunk>(msg.params()) {
+                        Ok(value) => value,
+                        Err(_) => return,
+                    };
+
+                    let view = self.webviews.get_mut(&event.1).unwrap();
+                    if let Err(err) = view.pending_files.push_chunk(&chunk) {
+                        log::error!("Failed to read the file {}: {}", chunk.name, err);
+                    }
                 }
 
                 EventData::Ipc(msg) if msg.method() == "initialize" => {
-                    let view = webviews.get_mut(&event.1).unwrap();
-                    send_edits(view.dom.rebuild(), &view.webview);
+                    let view = self.webviews.get_mut(&event.1).unwrap();
+
+                    // The webview holds on to the events that may be deferred until the next animation frame
+                    let deferred = serde_json::to_string(&view.dom.deferred_events()).unwrap();
+                    _ = view.webview.evaluate_script(&format!(
+                        "window.interpreter.SetDeferredEvents({});",
+                        deferred
+                    ));
+
+                    let mounted =
+                        send_edits(view.dom.rebuild(), &view.webview, &mut view.accessibility);
+                    element::send_mounted_events(&mut view.dom, mounted);
+                    self.renders += 1;
                 }
 
-                // When the webview chirps back with the result of the eval, we send it to the active receiver
-                //
-                // This currently doesn't perform any targeting to the callsite, so if you eval multiple times at once,
-                // you might the wrong result. This should be fixed
-                EventData::Ipc(msg) if msg.method() == "eval_result" => {
-                    webviews[&event.1]
-                        .dom
-                        .base_scope()
-                        .consume_context::<DesktopContext>()
-                        .unwrap()
-                        .eval
-                        .send(msg.params())
-                        .unwrap();
+                // The scripts of use_eval tell us which one they are, so every message goes back to its own Eval
+                EventData::Ipc(msg)
+                    if msg.method() == "eval_send" || msg.method() == "eval_result" =>
+                {
+                    if let Some(view) = self.webviews.get(&event.1) {
+                        if let Some(desktop) =
+                            view.dom.base_scope().consume_context::<DesktopContext>()
+                        {
+                            let method = msg.method().to_string();
+                            desktop.evals.handle_message(&method, &msg.params());
+                        }
+                    }
                 }
 
-                EventData::Ipc(msg) if msg.method() == "browser_open" => {
-                    if let Some(temp) = msg.params().as_object() {
-                        if temp.contains_key("href") {
-                            let open = webbrowser::open(temp["href"].as_str().unwrap());
-                            if let Err(e) = open {
-                                log::error!("Open Browser error: {:?}", e);
-                            }
+                // The listeners of use_document_listener and use_window_listener report their events by their id
+                EventData::Ipc(msg) if msg.method() == "global_event" => {
+                    if let Some(view) = self.webviews.get(&event.1) {
+                        if let Some(desktop) =
+                            view.dom.base_scope().consume_context::<DesktopContext>()
+                        {
+                            desktop.listeners.handle_message(&msg.params());
                         }
                     }
                 }
 
+                EventData::Ipc(msg) if msg.method() == "history" => {
+                    if let Some(view) = self.webviews.get(&event.1) {
+                        history::handle_message(&view.dom, &msg.params());
+                    }
+                }
+
+                EventData::Ipc(msg) if msg.method() == "window_control" => {
+                    if let Some(desktop) = self.desktop_context(event.1) {
+                        titlebar::handle_message(&desktop, &msg.params());
+                    }
+                }
+
+                EventData::Ipc(msg) if msg.method() == "browser_open" => {
+                    if let Some(href) = msg.params().get("href").and_then(|href| href.as_str()) {
+                        open_browser(href);
+                    }
+                }
+
                 _ => {}
             },
             _ => {}
         }
-    })
+    }
+}
+
+/// Drop a window, quitting once the last one is gone
+fn close_window(
+    webviews: &mut HashMap<WindowId, WebviewHandler>,
+    windows: &WindowRegistry,
+    id: WindowId,
+    control_flow: &mut ControlFlow,
+) {
+    // a window gets both a close request and a destroyed event, but only closes once
+    if let Some(view) = webviews.remove(&id) {
+        if let Some(window_state) = &view.window_state {
+            window_state.save(view.webview.window());
+        }
+        windows.closed(id);
+        #[cfg(not(any(target_os = "ios", target_os = "android")))]
+        shortcut::window_closed(id);
+    }
+
+    if webviews.is_empty() {
+        *control_flow = ControlFlow::Exit
+    }
+}
+
+/// Open a link of the app in the browser of the system, instead of navigating the webview away from the app
+fn open_browser(href: &str) {
+    if let Err(e) = webbrowser::open(href) {
+        log::error!("Open Browser error: {:?}", e);
+    }
 }
 
 fn create_new_window(
     mut cfg: Config,
     event_loop: &EventLoopWindowTarget<UserWindowEvent>,
     proxy: &EventLoopProxy<UserWindowEvent>,
-    dom: VirtualDom,
+    mut dom: VirtualDom,
     queue: &WebviewQueue,
+    windows: &Rc<WindowRegistry>,
 ) -> WebviewHandler {
-    let webview = webview::build(&mut cfg, event_loop, proxy.clone());
+    for (event, dispatch) in cfg.event_dispatch.drain(..) {
+        dom.set_event_dispatch(event, dispatch);
+    }
 
-    dom.base_scope().provide_context(DesktopContext::new(
+    let mut menu_ids = HashMap::new();
+    if let Some(menu_bar) = cfg.menu_bar.take() {
+        let menu = menu_bar.build(&mut menu_ids);
+        cfg.window = std::mem::take(&mut cfg.window).with_menu(menu);
+    }
+
+    let window_state = cfg
+        .window_state_id
+        .take()
+        .map(|id| window_state::PersistentWindow::new(&id, cfg.storage_dir.clone()));
+    if let Some(window_state) = &window_state {
+        cfg.window = window_state.restore(std::mem::take(&mut cfg.window), event_loop);
+    }
+
+    let dropped_files = Rc::new(RefCell::new(Vec::new()));
+    let webview = webview::build(&mut cfg, event_loop, proxy.clone(), dropped_files.clone());
+
+    dom.base_scope()
+        .provide_context(dioxus_hooks::PersistentBackend::new(
+            storage::FileStorage::new(cfg.storage_dir.take()),
+        ));
+
+    // the router keeps the routes of the window in here, and the back and forward keys move through them
+    dom.base_scope()
+        .provide_context::<Rc<dyn dioxus_history::History>>(Rc::new(
+            dioxus_history::MemoryHistory::new(),
+        ));
+
+    dom.base_scope()
+        .provide_context(dioxus_hooks::ClipboardContext::new(
+            clipboard::SystemClipboard,
+        ));
+
+    dom.base_scope()
+        .provide_context(dioxus_hooks::WindowContext::new(
+            window::DesktopWindow::new(webview.clone()),
+        ));
+
+    // use_head updates the head of the page, and the title of the window along with it
+    dom.base_scope()
+        .provide_context(dioxus_hooks::HeadContext::new(head::DesktopHead::new(
+            webview.clone(),
+        )));
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    dom.base_scope()
+        .provide_context(dioxus_hooks::ShortcutContext::new(
+            shortcut::DesktopShortcuts::new(event_loop),
+        ));
+
+    let desktop_context = DesktopContext::new(
         webview.clone(),
         proxy.clone(),
         event_loop.clone(),
         queue.clone(),
-    ));
+        windows.clone(),
+    );
+    let menu_events = desktop_context.menu_events.clone();
+    dom.base_scope()
+        .provide_context(dioxus_hooks::EvalContext::new(eval::DesktopEval::new(
+            webview.clone(),
+            desktop_context.evals.clone(),
+        )));
+    dom.base_scope()
+        .provide_context(dioxus_hooks::ObserverContext::new(
+            observer::DesktopObserver::new(webview.clone(), desktop_context.evals.clone()),
+        ));
+    dom.base_scope()
+        .provide_context(dioxus_hooks::GlobalListenerContext::new(
+            listener::DesktopListeners::new(webview.clone(), desktop_context.listeners.clone()),
+        ));
+    dom.base_scope().provide_context(desktop_context);
+
+    // The contexts of the config go last, so they can replace the ones above
+    for provide in &cfg.root_contexts {
+        provide(dom.base_scope());
+    }
+
+    windows.opened(&webview, &dom);
 
     let id = webview.window().id();
+    let accessibility = accessibility::Accessibility::new(webview.window(), proxy);
 
     // We want to poll the virtualdom and the event loop at the same time, so the waker will be connected to both
     WebviewHandler {
         webview,
         dom,
-        waker: waker::tao_waker(proxy, id),
+        waker: dioxus_event_loop::tao_waker(proxy, move || UserWindowEvent(EventData::Poll, id)),
+        pending_files: SerializedFileEngine::new(),
+        dropped_files,
+        menu_ids,
+        menu_events,
+        window_state,
+        accessibility,
     }
 }
 
@@ -264,35 +724,62 @@ struct WebviewHandler {
     dom: VirtualDom,
     webview: Rc<wry::webview::WebView>,
     waker: Waker,
+    /// The files the webview sent for the next event
+    pending_files: SerializedFileEngine,
+    /// The paths of the files that were last dropped on the window
+    dropped_files: Rc<RefCell<Vec<PathBuf>>>,
+    /// Our ids for the tao ids of the items in the menu bar
+    menu_ids: HashMap<tao::menu::MenuId, String>,
+    menu_events: tokio::sync::broadcast::Sender<String>,
+    /// Saves where the window was when it closes
+    window_state: Option<window_state::PersistentWindow>,
+    /// Describes the elements of the window to assistive technology
+    accessibility: accessibility::Accessibility,
 }
 
-/// Poll the virtualdom until it's pending
+/// Poll the virtualdom until it's pending, returning how many times it rendered
 ///
 /// The waker we give it is connected to the event loop, so it will wake up the event loop when it's ready to be polled again
 ///
 /// All IO is done on the tokio runtime we started earlier
-fn poll_vdom(view: &mut WebviewHandler) {
-    let mut cx = std::task::Context::from_waker(&view.waker);
+fn poll_vdom(view: &mut WebviewHandler) -> usize {
+    let mut renders = 0;
+
+    while dioxus_event_loop::poll_work(&mut view.dom, &view.waker) {
+        let mounted = send_edits(
+            view.dom.render_immediate(),
+            &view.webview,
+            &mut view.accessibility,
+        );
+        element::send_mounted_events(&mut view.dom, mounted);
+        renders += 1;
+    }
 
-    loop {
-        {
-            let fut = view.dom.wait_for_work();
-            pin_mut!(fut);
+    renders
+}
 
-            match fut.poll_unpin(&mut cx) {
-                std::task::Poll::Ready(_) => {}
-                std::task::Poll::Pending => break,
-            }
-        }
+/// Send a list of mutations to the webview, returning the elements that are waiting on an `onmounted` event
+fn send_edits(
+    edits: Mutations,
+    webview: &WebView,
+    accessibility: &mut accessibility::Accessibility,
+) -> Vec<ElementId> {
+    #[cfg(feature = "profiling")]
+    let _span = tracing::trace_span!("apply mutations", edits = edits.edits.len()).entered();
 
-        send_edits(view.dom.render_immediate(), &view.webview);
-    }
-}
+    accessibility.update(&edits);
 
-/// Send a list of mutations to the webview
-fn send_edits(edits: Mutations, webview: &WebView) {
     let serialized = serde_json::to_string(&edits).unwrap();
 
     // todo: use SSE and binary data to send the edits with lower overhead
     _ = webview.evaluate_script(&format!("window.interpreter.handleEdits({})", serialized));
+
+    edits
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            Mutation::NewEventListener { name, id, .. } if *name == "mounted" => Some(*id),
+            _ => None,
+        })
+        .collect()
 }
@@ -0,0 +1,107 @@
+use dioxus_hooks::{GlobalHandler, GlobalListenerBackend, ListenerGuard, ListenerTarget};
+use dioxus_html::EventData;
+use serde_json::{json, Value};
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use wry::webview::WebView;
+
+/// Backs `use_document_listener` and `use_window_listener` with listeners the interpreter adds in the webview
+pub(crate) struct DesktopListeners {
+    webview: Rc<WebView>,
+    registry: Rc<ListenerRegistry>,
+}
+
+impl DesktopListeners {
+    pub(crate) fn new(webview: Rc<WebView>, registry: Rc<ListenerRegistry>) -> Self {
+        Self { webview, registry }
+    }
+}
+
+impl GlobalListenerBackend for DesktopListeners {
+    fn listen(&self, target: ListenerTarget, event: &str, handler: GlobalHandler) -> ListenerGuard {
+        let id = self.registry.add(target, event, handler);
+        sync(&self.webview, &self.registry);
+
+        let (webview, registry) = (self.webview.clone(), self.registry.clone());
+        ListenerGuard::new(move || {
+            registry.remove(id);
+            sync(&webview, &registry);
+        })
+    }
+}
+
+/// The whole set of listeners is sent every time, so the interpreter only has to add and remove the difference
+fn sync(webview: &WebView, registry: &ListenerRegistry) {
+    _ = webview.evaluate_script(&format!(
+        "window.interpreter.SetGlobalListeners({});",
+        registry.to_json()
+    ));
+}
+
+/// The listeners on the document and the window of a webview, by the id the webview knows them by
+#[derive(Default)]
+pub(crate) struct ListenerRegistry {
+    next_id: Cell<usize>,
+    listeners: RefCell<BTreeMap<usize, Listener>>,
+}
+
+struct Listener {
+    target: ListenerTarget,
+    name: String,
+    handler: Rc<GlobalHandler>,
+}
+
+impl ListenerRegistry {
+    fn add(&self, target: ListenerTarget, name: &str, handler: GlobalHandler) -> usize {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        self.listeners.borrow_mut().insert(
+            id,
+            Listener {
+                target,
+                name: name.to_string(),
+                handler: Rc::new(handler),
+            },
+        );
+        id
+    }
+
+    fn remove(&self, id: usize) {
+        self.listeners.borrow_mut().remove(&id);
+    }
+
+    fn to_json(&self) -> Value {
+        self.listeners
+            .borrow()
+            .iter()
+            .map(|(id, listener)| {
+                json!({ "id": id, "target": listener.target.as_str(), "name": listener.name })
+            })
+            .collect()
+    }
+
+    /// Hand an event the webview reported to the listener it's for, if it's still around
+    pub(crate) fn handle_message(&self, params: &Value) {
+        let id = match params.get("id").and_then(Value::as_u64) {
+            Some(id) => id as usize,
+            None => return,
+        };
+
+        // The handler is cloned out, so it can add and remove listeners itself
+        let listener = self
+            .listeners
+            .borrow()
+            .get(&id)
+            .map(|listener| (listener.name.clone(), listener.handler.clone()));
+        if let Some((name, handler)) = listener {
+            let data = params.get("data").cloned().unwrap_or(Value::Null);
+            match EventData::from_json(&name, &data.to_string()) {
+                Ok(data) => {
+                    handler(data.into_any());
+                }
+                Err(err) => log::warn!("The data of a {} event couldn't be read: {}", name, err),
+            }
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use crate::use_window;
+use dioxus_core::ScopeState;
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use tokio::sync::broadcast::error::RecvError;
+use wry::application::{
+    accelerator::Accelerator,
+    menu::{MenuBar as TaoMenuBar, MenuId, MenuItemAttributes},
+};
+
+pub use wry::application::menu::MenuItem as NativeMenuItem;
+
+/// The menu bar of a window, built from [`Menu`]s
+///
+/// Picking one of its items sends the id of the item to the [`use_menu_event_handler`]s of the window.
+///
+/// ```rust, ignore
+/// let menu_bar = MenuBar::new()
+///     .menu(
+///         "File",
+///         Menu::new()
+///             .item_with_accelerator("open", "Open…", "CmdOrCtrl+O")
+///             .item_with_accelerator("save", "Save", "CmdOrCtrl+S")
+///             .separator()
+///             .native(NativeMenuItem::Quit),
+///     )
+///     .menu("Edit", Menu::edit());
+///
+/// dioxus_desktop::launch_cfg(app, Config::new().with_menu_bar(menu_bar));
+/// ```
+#[derive(Default)]
+pub struct MenuBar {
+    menus: Vec<(String, Menu)>,
+}
+
+impl MenuBar {
+    /// Create a menu bar without any menus
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a menu to the end of the bar
+    pub fn menu(mut self, title: impl Into<String>, menu: Menu) -> Self {
+        self.menus.push((title.into(), menu));
+        self
+    }
+
+    /// Build the menu bar for tao, remembering which of our ids each item got
+    pub(crate) fn build(&self, ids: &mut HashMap<MenuId, String>) -> TaoMenuBar {
+        let mut bar = TaoMenuBar::new();
+        for (title, menu) in &self.menus {
+            bar.add_submenu(title, true, menu.build(ids));
+        }
+        bar
+    }
+}
+
+/// A menu of the [`MenuBar`]
+#[derive(Default)]
+pub struct Menu {
+    entries: Vec<MenuEntry>,
+}
+
+enum MenuEntry {
+    Item {
+        id: String,
+        title: String,
+        accelerator: Option<String>,
+        enabled: bool,
+    },
+    Native(NativeMenuItem),
+    Submenu {
+        title: String,
+        menu: Menu,
+    },
+}
+
+impl Menu {
+    /// Create an empty menu
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The standard edit menu, with undo, redo, cut, copy, paste and select all
+    pub fn edit() -> Self {
+        Self::new()
+            .native(NativeMenuItem::Undo)
+            .native(NativeMenuItem::Redo)
+            .separator()
+            .native(NativeMenuItem::Cut)
+            .native(NativeMenuItem::Copy)
+            .native(NativeMenuItem::Paste)
+            .native(NativeMenuItem::SelectAll)
+    }
+
+    /// Add an item
+    pub fn item(self, id: impl Into<String>, title: impl Into<String>) -> Self {
+        self.entry(MenuEntry::Item {
+            id: id.into(),
+            title: title.into(),
+            accelerator: None,
+            enabled: true,
+        })
+    }
+
+    /// Add an item that's also picked with a keyboard shortcut, like `"CmdOrCtrl+S"` or `"Shift+Alt+N"`
+    ///
+    /// A shortcut that can't be parsed is logged and left off.
+    pub fn item_with_accelerator(
+        self,
+        id: impl Into<String>,
+        title: impl Into<String>,
+        accelerator: impl Into<String>,
+    ) -> Self {
+        self.entry(MenuEntry::Item {
+            id: id.into(),
+            title: title.into(),
+            accelerator: Some(accelerator.into()),
+            enabled: true,
+        })
+    }
+
+    /// Add an item that's greyed out and can't be picked
+    pub fn disabled_item(self, id: impl Into<String>, title: impl Into<String>) -> Self {
+        self.entry(MenuEntry::Item {
+            id: id.into(),
+            title: title.into(),
+            accelerator: None,
+            enabled: false,
+        })
+    }
+
+    /// Add an item that the platform handles itself, like copy or quit
+    ///
+    /// Not every platform has every native item. The ones a platform doesn't have are left out.
+    pub fn native(self, item: NativeMenuItem) -> Self {
+        self.entry(MenuEntry::Native(item))
+    }
+
+    /// Add a line between the items before and after it
+    pub fn separator(self) -> Self {
+        self.native(NativeMenuItem::Separator)
+    }
+
+    /// Add a menu that opens from an item
+    pub fn submenu(self, title: impl Into<String>, menu: Menu) -> Self {
+        self.entry(MenuEntry::Submenu {
+            title: title.into(),
+            menu,
+        })
+    }
+
+    fn entry(mut self, entry: MenuEntry) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    fn build(&self, ids: &mut HashMap<MenuId, String>) -> TaoMenuBar {
+        let mut menu = TaoMenuBar::new();
+        for entry in &self.entries {
+            match entry {
+                MenuEntry::Item {
+                    id,
+                    title,
+                    accelerator,
+                    enabled,
+                } => {
+                    let mut attributes = MenuItemAttributes::new(title).with_enabled(*enabled);
+                    match accelerator.as_deref().map(str::parse::<Accelerator>) {
+                        Some(Ok(accelerator)) => {
+                            attributes = attributes.with_accelerators(&accelerator);
+                        }
+                        Some(Err(_)) => {
+                            log::error!(
+                                "{:?} of the menu item {:?} isn't a shortcut",
+                                accelerator,
+                                id
+                            );
+                        }
+                        None => {}
+                    }
+                    let item = menu.add_item(attributes);
+                    ids.insert(item.id(), id.clone());
+                }
+                MenuEntry::Native(item) => {
+                    menu.add_native_item(item.clone());
+                }
+                MenuEntry::Submenu {
+                    title,
+                    menu: submenu,
+                } => {
+                    menu.add_submenu(title, true, submenu.build(ids));
+                }
+            }
+        }
+        menu
+    }
+}
+
+/// Run `handler` with the id of every item picked from the menu bar of this window while the component is mounted
+pub fn use_menu_event_handler(cx: &ScopeState, handler: impl FnMut(&str) + 'static) {
+    let desktop = use_window(cx);
+    let current = cx.use_hook(|| Rc::new(RefCell::new(None::<Box<dyn FnMut(&str)>>)));
+    // keep the latest handler so it sees the latest props
+    current.replace(Some(Box::new(handler)));
+
+    cx.use_hook(|| {
+        let mut events = desktop.menu_events();
+        let current = current.clone();
+        cx.push_future(async move {
+            loop {
+                match events.recv().await {
+                    Ok(id) => {
+                        if let Some(handler) = current.borrow_mut().as_mut() {
+                            handler(&id);
+                        }
+                    }
+                    Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    });
+}
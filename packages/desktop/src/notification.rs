@@ -0,0 +1,189 @@
+use crate::{use_window, DesktopContext};
+use dioxus_core::ScopeState;
+use futures_channel::{mpsc, oneshot};
+use futures_util::{future::LocalBoxFuture, stream::FuturesUnordered, FutureExt, StreamExt};
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A notification shown by the operating system
+///
+/// ```rust, ignore
+/// let notifier = use_notifier(cx);
+///
+/// notifier.notify(
+///     Notification::new("New message", "Are we still on for lunch?").with_action("reply", "Reply"),
+///     |event| if event == NotificationEvent::Action("reply".into()) { /* ... */ },
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Notification {
+    pub title: String,
+    pub body: String,
+    /// The name of an icon of the icon theme, or the path to an image
+    pub icon: Option<String>,
+    /// The buttons of the notification, as `(id, label)`
+    pub actions: Vec<(String, String)>,
+}
+
+impl Notification {
+    pub fn new(title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            body: body.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Add a button, which reports [`NotificationEvent::Action`] with its id when it's clicked
+    pub fn with_action(mut self, id: impl Into<String>, label: impl Into<String>) -> Self {
+        self.actions.push((id.into(), label.into()));
+        self
+    }
+}
+
+/// What the user did with a notification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationEvent {
+    /// The notification itself was clicked
+    Clicked,
+
+    /// One of the buttons was clicked, with the id it was added with
+    Action(String),
+
+    /// The notification went away without being clicked
+    ///
+    /// Only Linux and the BSDs report clicks. Everywhere else, every notification ends up dismissed.
+    Dismissed,
+}
+
+/// The operating system couldn't show a notification
+#[derive(Debug)]
+pub struct NotificationError(String);
+
+impl fmt::Display for NotificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the notification couldn't be shown: {}", self.0)
+    }
+}
+
+impl std::error::Error for NotificationError {}
+
+/// A notification that's showing, which resolves to what the user did with it
+pub struct PendingNotification {
+    event: oneshot::Receiver<NotificationEvent>,
+}
+
+impl Future for PendingNotification {
+    type Output = NotificationEvent;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.event
+            .poll_unpin(cx)
+            .map(|event| event.unwrap_or(NotificationEvent::Dismissed))
+    }
+}
+
+impl DesktopContext {
+    /// Show a notification
+    ///
+    /// Await the notification to find out what the user did with it, or use [`use_notifier`] to handle that with a
+    /// callback instead.
+    pub fn notify(
+        &self,
+        notification: Notification,
+    ) -> Result<PendingNotification, NotificationError> {
+        let mut native = notify_rust::Notification::new();
+        native.summary(&notification.title).body(&notification.body);
+        if let Some(icon) = &notification.icon {
+            native.icon(icon);
+        }
+        for (id, label) in &notification.actions {
+            native.action(id, label);
+        }
+
+        let (sender, event) = oneshot::channel();
+        let shown = native
+            .show()
+            .map_err(|err| NotificationError(err.to_string()))?;
+
+        #[cfg(all(unix, not(target_os = "macos")))]
+        std::thread::spawn(move || {
+            // waiting blocks until the notification is gone
+            shown.wait_for_action(|action| {
+                let event = match action {
+                    "default" => NotificationEvent::Clicked,
+                    "__closed" => NotificationEvent::Dismissed,
+                    id => NotificationEvent::Action(id.to_string()),
+                };
+                let _ = sender.send(event);
+            });
+        });
+
+        // the other backends don't report back, so the notification is dismissed as far as we know
+        #[cfg(not(all(unix, not(target_os = "macos"))))]
+        {
+            let _ = shown;
+            let _ = sender.send(NotificationEvent::Dismissed);
+        }
+
+        Ok(PendingNotification { event })
+    }
+}
+
+/// Shows notifications and runs their callbacks in the component that showed them
+pub struct Notifier {
+    desktop: DesktopContext,
+    callbacks: mpsc::UnboundedSender<LocalBoxFuture<'static, ()>>,
+}
+
+impl Notifier {
+    /// Show a notification, running `on_event` with what the user did with it
+    ///
+    /// The callback is dropped without running if the component is removed first.
+    pub fn notify(
+        &self,
+        notification: Notification,
+        on_event: impl FnOnce(NotificationEvent) + 'static,
+    ) -> Result<(), NotificationError> {
+        let pending = self.desktop.notify(notification)?;
+        let _ = self
+            .callbacks
+            .unbounded_send(async move { on_event(pending.await) }.boxed_local());
+        Ok(())
+    }
+}
+
+/// Get a [`Notifier`] that runs the callbacks of its notifications in this component
+pub fn use_notifier(cx: &ScopeState) -> &Notifier {
+    let desktop = use_window(cx);
+    cx.use_hook(|| {
+        let (callbacks, mut receiver) = mpsc::unbounded::<LocalBoxFuture<'static, ()>>();
+
+        cx.push_future(async move {
+            let mut pending = FuturesUnordered::new();
+            loop {
+                futures_util::select! {
+                    callback = receiver.next() => match callback {
+                        Some(callback) => pending.push(callback),
+                        None => break,
+                    },
+                    _ = pending.select_next_some() => {}
+                }
+            }
+        });
+
+        Notifier {
+            desktop: desktop.clone(),
+            callbacks,
+        }
+    })
+}
@@ -0,0 +1,141 @@
+use crate::element::DesktopElement;
+use crate::eval::EvalRegistry;
+use dioxus_hooks::{
+    EvalError, Evaluator, IntersectionEntry, IntersectionOptions, ObserverBackend, ObserverGuard,
+    ResizeEntry,
+};
+use dioxus_html::{
+    geometry::{euclid::Rect, ClientRect},
+    MountedData, MountedError, MountedResult,
+};
+use serde_json::Value;
+use std::rc::Rc;
+use wry::webview::WebView;
+
+/// Backs `use_intersection_observer` and `use_resize_observer` with the observers of the webview
+///
+/// Every observation is a script that reports its entries until it's told to disconnect.
+pub(crate) struct DesktopObserver {
+    webview: Rc<WebView>,
+    scripts: Rc<EvalRegistry>,
+}
+
+impl DesktopObserver {
+    pub(crate) fn new(webview: Rc<WebView>, scripts: Rc<EvalRegistry>) -> Self {
+        Self { webview, scripts }
+    }
+
+    fn observe(
+        &self,
+        script: String,
+        report: impl Fn(Value) + 'static,
+    ) -> MountedResult<ObserverGuard> {
+        let evaluator = self
+            .scripts
+            .listen(&self.webview, &script, report)
+            .map_err(operation_failed)?;
+
+        let scripts = self.scripts.clone();
+        Ok(ObserverGuard::new(move || {
+            scripts.stop_listening(&evaluator);
+            _ = evaluator.send(Value::Null);
+        }))
+    }
+}
+
+impl ObserverBackend for DesktopObserver {
+    fn observe_intersection(
+        &self,
+        element: &MountedData,
+        options: &IntersectionOptions,
+        callback: Box<dyn Fn(IntersectionEntry)>,
+    ) -> MountedResult<ObserverGuard> {
+        let script = format!(
+            r#"
+            const node = window.interpreter.nodes[{id}];
+            if (!node) {{
+                return;
+            }}
+            const rect = (rect) => ({{ x: rect.x, y: rect.y, width: rect.width, height: rect.height }});
+            const observer = new IntersectionObserver((entries) => {{
+                const entry = entries[entries.length - 1];
+                dioxus.send({{
+                    is_intersecting: entry.isIntersecting,
+                    intersection_ratio: entry.intersectionRatio,
+                    bounding_client_rect: rect(entry.boundingClientRect),
+                    intersection_rect: rect(entry.intersectionRect),
+                }});
+            }}, {{ rootMargin: {margin}, threshold: {thresholds} }});
+            observer.observe(node);
+            await dioxus.recv();
+            observer.disconnect();
+            "#,
+            id = element_id(element)?,
+            margin = Value::from(options.get_root_margin()),
+            thresholds = Value::from(options.get_thresholds().to_vec()),
+        );
+
+        self.observe(script, move |value| {
+            let entry = (|| {
+                Some(IntersectionEntry {
+                    is_intersecting: value.get("is_intersecting")?.as_bool()?,
+                    intersection_ratio: value.get("intersection_ratio")?.as_f64()?,
+                    bounding_client_rect: to_rect(value.get("bounding_client_rect")?)?,
+                    intersection_rect: to_rect(value.get("intersection_rect")?)?,
+                })
+            })();
+            if let Some(entry) = entry {
+                callback(entry);
+            }
+        })
+    }
+
+    fn observe_resize(
+        &self,
+        element: &MountedData,
+        callback: Box<dyn Fn(ResizeEntry)>,
+    ) -> MountedResult<ObserverGuard> {
+        let script = format!(
+            r#"
+            const node = window.interpreter.nodes[{id}];
+            if (!node) {{
+                return;
+            }}
+            const observer = new ResizeObserver((entries) => {{
+                const rect = entries[entries.length - 1].contentRect;
+                dioxus.send({{ x: rect.x, y: rect.y, width: rect.width, height: rect.height }});
+            }});
+            observer.observe(node);
+            await dioxus.recv();
+            observer.disconnect();
+            "#,
+            id = element_id(element)?,
+        );
+
+        self.observe(script, move |value| {
+            if let Some(content_rect) = to_rect(&value) {
+                callback(ResizeEntry { content_rect });
+            }
+        })
+    }
+}
+
+fn element_id(element: &MountedData) -> MountedResult<usize> {
+    element
+        .get_raw_element()?
+        .downcast_ref::<DesktopElement>()
+        .map(|element| element.id().0)
+        .ok_or(MountedError::NotSupported)
+}
+
+fn to_rect(value: &Value) -> Option<ClientRect> {
+    let field = |name: &str| value.get(name)?.as_f64();
+    Some(Rect::new(
+        (field("x")?, field("y")?).into(),
+        (field("width")?, field("height")?).into(),
+    ))
+}
+
+fn operation_failed(err: EvalError) -> MountedError {
+    MountedError::OperationFailed(Box::new(err))
+}
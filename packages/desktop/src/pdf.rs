@@ -0,0 +1,296 @@
+use crate::DesktopContext;
+use futures_channel::oneshot;
+use std::fmt;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+/// How a page is laid out when it's exported to a PDF
+///
+/// ```rust, ignore
+/// let desktop = use_window(cx).clone();
+/// cx.spawn(async move {
+///     let options = PdfOptions { landscape: true, ..Default::default() };
+///     if let Err(err) = desktop.export_pdf("report.pdf", options).await {
+///         log::error!("{}", err);
+///     }
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PdfOptions {
+    /// Lay the pages out sideways
+    pub landscape: bool,
+    /// The width and height of a page in portrait, in millimeters
+    pub page_size: (f64, f64),
+    /// The margin around every side of a page, in millimeters
+    pub margin: f64,
+    /// Print the background colors and images of the page
+    ///
+    /// macOS leaves that to the `print-color-adjust` style of the page.
+    pub print_backgrounds: bool,
+}
+
+impl Default for PdfOptions {
+    /// A4 pages in portrait with a one centimeter margin
+    fn default() -> Self {
+        Self {
+            landscape: false,
+            page_size: (210.0, 297.0),
+            margin: 10.0,
+            print_backgrounds: true,
+        }
+    }
+}
+
+/// The webview couldn't export the page
+#[derive(Debug)]
+pub struct PdfError(String);
+
+impl fmt::Display for PdfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the page couldn't be exported to a PDF: {}", self.0)
+    }
+}
+
+impl std::error::Error for PdfError {}
+
+impl DesktopContext {
+    /// Print the page of the window into a PDF file, without showing a print dialog
+    ///
+    /// The page is printed like it is with [`DesktopContext::print`], so `@media print` styles apply.
+    pub fn export_pdf(
+        &self,
+        path: impl AsRef<Path>,
+        options: PdfOptions,
+    ) -> impl Future<Output = Result<(), PdfError>> {
+        let (sender, done) = oneshot::channel();
+        self.start_pdf_export(path.as_ref().to_path_buf(), options, sender);
+        async move {
+            done.await
+                .unwrap_or_else(|_| Err(PdfError("the webview stopped exporting".to_string())))
+        }
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    fn start_pdf_export(
+        &self,
+        path: PathBuf,
+        options: PdfOptions,
+        sender: oneshot::Sender<Result<(), PdfError>>,
+    ) {
+        use gtk::glib;
+        use std::{cell::RefCell, rc::Rc};
+        use webkit2gtk::{PrintOperationExt, SettingsExt, WebViewExt};
+        use wry::webview::WebviewExtUnix;
+
+        let uri = match glib::filename_to_uri(&path, None) {
+            Ok(uri) => uri,
+            Err(err) => {
+                let _ = sender.send(Err(PdfError(err.to_string())));
+                return;
+            }
+        };
+
+        let webview = self.webview.webview();
+        if let Some(settings) = WebViewExt::settings(&*webview) {
+            settings.set_print_backgrounds(options.print_backgrounds);
+        }
+
+        let settings = gtk::PrintSettings::new();
+        settings.set("printer", Some("Print to File"));
+        settings.set("output-file-format", Some("pdf"));
+        settings.set("output-uri", Some(&uri));
+
+        let (width, height) = options.page_size;
+        let page = gtk::PageSetup::new();
+        page.set_paper_size(&gtk::PaperSize::new_custom(
+            "dioxus",
+            "Custom",
+            width,
+            height,
+            gtk::Unit::Mm,
+        ));
+        page.set_orientation(match options.landscape {
+            true => gtk::PageOrientation::Landscape,
+            false => gtk::PageOrientation::Portrait,
+        });
+        page.set_top_margin(options.margin, gtk::Unit::Mm);
+        page.set_bottom_margin(options.margin, gtk::Unit::Mm);
+        page.set_left_margin(options.margin, gtk::Unit::Mm);
+        page.set_right_margin(options.margin, gtk::Unit::Mm);
+
+        let operation = webkit2gtk::PrintOperation::new(&*webview);
+        operation.set_print_settings(&settings);
+        operation.set_page_setup(&page);
+
+        // a failed export reports that it failed and then that it finished, but only the first one counts
+        let sender = Rc::new(RefCell::new(Some(sender)));
+        operation.connect_failed({
+            let sender = sender.clone();
+            move |_, err| {
+                if let Some(sender) = sender.borrow_mut().take() {
+                    let _ = sender.send(Err(PdfError(err.to_string())));
+                }
+            }
+        });
+        operation.connect_finished(move |_| {
+            if let Some(sender) = sender.borrow_mut().take() {
+                let _ = sender.send(Ok(()));
+            }
+        });
+        operation.print();
+    }
+
+    #[cfg(target_os = "windows")]
+    fn start_pdf_export(
+        &self,
+        path: PathBuf,
+        options: PdfOptions,
+        sender: oneshot::Sender<Result<(), PdfError>>,
+    ) {
+        use webview2_com::Microsoft::Web::WebView2::Win32::{
+            ICoreWebView2Environment6, ICoreWebView2_2, ICoreWebView2_7,
+            COREWEBVIEW2_PRINT_ORIENTATION_LANDSCAPE, COREWEBVIEW2_PRINT_ORIENTATION_PORTRAIT,
+        };
+        use webview2_com::PrintToPdfCompletedHandler;
+        use windows::core::{Interface, HSTRING};
+        use wry::webview::WebviewExtWindows;
+
+        // WebView2 measures pages in inches
+        let inches = |millimeters: f64| millimeters / 25.4;
+        let (width, height) = options.page_size;
+
+        let started = unsafe {
+            (|| {
+                let webview = self.webview.controller().CoreWebView2()?;
+                let environment = webview.cast::<ICoreWebView2_2>()?.Environment()?;
+                let settings = environment
+                    .cast::<ICoreWebView2Environment6>()?
+                    .CreatePrintSettings()?;
+                settings.SetOrientation(match options.landscape {
+                    true => COREWEBVIEW2_PRINT_ORIENTATION_LANDSCAPE,
+                    false => COREWEBVIEW2_PRINT_ORIENTATION_PORTRAIT,
+                })?;
+                settings.SetPageWidth(inches(width))?;
+                settings.SetPageHeight(inches(height))?;
+                settings.SetMarginTop(inches(options.margin))?;
+                settings.SetMarginBottom(inches(options.margin))?;
+                settings.SetMarginLeft(inches(options.margin))?;
+                settings.SetMarginRight(inches(options.margin))?;
+                settings.SetShouldPrintBackgrounds(options.print_backgrounds)?;
+
+                let handler =
+                    PrintToPdfCompletedHandler::create(Box::new(move |result, succeeded| {
+                        let result = match (result, succeeded) {
+                            (Ok(()), true) => Ok(()),
+                            (Ok(()), false) => {
+                                Err(PdfError("the webview couldn't print".to_string()))
+                            }
+                            (Err(err), _) => Err(PdfError(err.to_string())),
+                        };
+                        let _ = sender.send(result);
+                        Ok(())
+                    }));
+                webview.cast::<ICoreWebView2_7>()?.PrintToPdf(
+                    &HSTRING::from(path.as_os_str()),
+                    &settings,
+                    &handler,
+                )
+            })()
+        };
+
+        // the handler was dropped with the sender, so the export reports that it stopped
+        if let Err(err) = started {
+            log::error!("Failed to export the page to a PDF: {}", err);
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn start_pdf_export(
+        &self,
+        path: PathBuf,
+        options: PdfOptions,
+        sender: oneshot::Sender<Result<(), PdfError>>,
+    ) {
+        use objc::runtime::{Object, BOOL, NO, YES};
+        use objc::{class, msg_send, sel, sel_impl};
+        use std::ffi::CString;
+        use wry::webview::WebviewExtMacOS;
+
+        #[repr(C)]
+        #[derive(Clone, Copy)]
+        struct NSSize {
+            width: f64,
+            height: f64,
+        }
+        unsafe impl objc::Encode for NSSize {
+            fn encode() -> objc::Encoding {
+                unsafe { objc::Encoding::from_str("{CGSize=dd}") }
+            }
+        }
+        #[repr(C)]
+        #[derive(Clone, Copy)]
+        struct NSRect {
+            origin: NSSize,
+            size: NSSize,
+        }
+        unsafe impl objc::Encode for NSRect {
+            fn encode() -> objc::Encoding {
+                unsafe { objc::Encoding::from_str("{CGRect={CGPoint=dd}{CGSize=dd}}") }
+            }
+        }
+
+        unsafe fn ns_string(string: &str) -> *mut Object {
+            let string = CString::new(string).unwrap_or_default();
+            msg_send![class!(NSString), stringWithUTF8String: string.as_ptr()]
+        }
+
+        // Cocoa measures pages in points
+        let points = |millimeters: f64| millimeters / 25.4 * 72.0;
+        let (width, height) = options.page_size;
+
+        let printed: BOOL = unsafe {
+            let webview: *mut Object = self.webview.webview();
+            let url: *mut Object = msg_send![
+                class!(NSURL),
+                fileURLWithPath: ns_string(&path.to_string_lossy())
+            ];
+
+            let shared: *mut Object = msg_send![class!(NSPrintInfo), sharedPrintInfo];
+            let info: *mut Object = msg_send![shared, copy];
+            let dictionary: *mut Object = msg_send![info, dictionary];
+            let _: () =
+                msg_send![dictionary, setObject: url forKey: ns_string("NSPrintJobSavingURL")];
+            let _: () = msg_send![info, setJobDisposition: ns_string("NSPrintSaveJob")];
+            let _: () = msg_send![info, setPaperSize: NSSize { width: points(width), height: points(height) }];
+            let _: () = msg_send![info, setOrientation: options.landscape as isize];
+            let _: () = msg_send![info, setTopMargin: points(options.margin)];
+            let _: () = msg_send![info, setBottomMargin: points(options.margin)];
+            let _: () = msg_send![info, setLeftMargin: points(options.margin)];
+            let _: () = msg_send![info, setRightMargin: points(options.margin)];
+
+            let operation: *mut Object = msg_send![webview, printOperationWithPrintInfo: info];
+            let _: () = msg_send![operation, setShowsPrintPanel: NO];
+            let _: () = msg_send![operation, setShowsProgressPanel: NO];
+
+            // the webview prints blank pages unless the view of the operation has a size
+            let view: *mut Object = msg_send![operation, view];
+            let bounds: NSRect = msg_send![webview, bounds];
+            let _: () = msg_send![view, setFrame: bounds];
+
+            let printed: BOOL = msg_send![operation, runOperation];
+            let _: () = msg_send![info, release];
+            printed
+        };
+
+        let _ = sender.send(match printed == YES {
+            true => Ok(()),
+            false => Err(PdfError("the webview couldn't print".to_string())),
+        });
+    }
+}
@@ -1,20 +1,59 @@
+use crate::history::HISTORY_JS;
+use crate::titlebar::TITLEBAR_JS;
 use dioxus_interpreter_js::INTERPRETER_JS;
-use std::path::{Path, PathBuf};
+use std::{
+    fs::File,
+    io::{Cursor, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
 use wry::{
     http::{status::StatusCode, Request, Response},
     Result,
 };
 
+/// The handler of the assets under a path, which gets the rest of the path after it
+pub(crate) type AssetHandler = dyn Fn(&str) -> Option<Asset>;
+
+/// The bytes [`Config::with_asset_handler`](crate::Config::with_asset_handler) serves for a path
+pub struct Asset {
+    data: Vec<u8>,
+    mime: Option<String>,
+}
+
+impl Asset {
+    /// Serve bytes, with a mime type guessed from the bytes and the extension of the path
+    pub fn new(data: impl Into<Vec<u8>>) -> Self {
+        Self {
+            data: data.into(),
+            mime: None,
+        }
+    }
+
+    /// Read a file to serve
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        std::fs::read(path).map(Self::new)
+    }
+
+    /// Serve the bytes with this mime type, like `"font/woff2"`, instead of guessing it
+    pub fn with_mime(mut self, mime: impl Into<String>) -> Self {
+        self.mime = Some(mime.into());
+        self
+    }
+}
+
 fn module_loader(root_name: &str) -> String {
     format!(
         r#"
 <script>
     {INTERPRETER_JS}
+    {TITLEBAR_JS}
+    {HISTORY_JS}
 
     let rootname = "{}";
     let root = window.document.getElementById(rootname);
     if (root != null) {{
         window.interpreter = new Interpreter(root);
+        window.interpreter.controlInputs = true;
         window.ipc.postMessage(serializeIpcMessage("initialize"));
     }}
 </script>
@@ -28,6 +67,7 @@ pub(super) fn desktop_handler(
     custom_head: Option<String>,
     custom_index: Option<String>,
     root_name: &str,
+    asset_handlers: &[(String, Box<AssetHandler>)],
 ) -> Result<Response<Vec<u8>>> {
     // If the request is for the root, we'll serve the index.html file.
     if request.uri().path() == "/" {
@@ -58,8 +98,44 @@ pub(super) fn desktop_handler(
             .map_err(From::from);
     }
 
+    // Files included with `asset!` have names of their own, so nothing else can be under them
+    let request_path = request.uri().path();
+    if let Some(name) = request_path
+        .strip_prefix(dioxus_assets::ASSET_PREFIX)
+        .and_then(|rest| rest.strip_prefix('/'))
+    {
+        return match dioxus_assets::find_asset(name) {
+            Some(asset) => serve(request, Cursor::new(asset.bytes()), asset.mime()),
+            None => not_found(),
+        };
+    }
+
+    // Assets of the app come before the filesystem, so they can shadow it
+    for (prefix, handler) in asset_handlers {
+        let rest = match request_path.strip_prefix(prefix.as_str()) {
+            Some("") => "",
+            Some(rest) => match rest.strip_prefix('/') {
+                Some(rest) => rest,
+                // `/assets` isn't the prefix of `/assetsfoo`
+                None => continue,
+            },
+            None => continue,
+        };
+
+        return match handler(&decode_path(rest)) {
+            Some(asset) => {
+                let mime = match asset.mime {
+                    Some(mime) => mime,
+                    None => get_mime(Path::new(rest), &asset.data).to_string(),
+                };
+                serve(request, Cursor::new(asset.data), &mime)
+            }
+            None => not_found(),
+        };
+    }
+
     // Else, try to serve a file from the filesystem.
-    let path = PathBuf::from(request.uri().path().trim_start_matches('/'));
+    let path = PathBuf::from(decode_path(request_path.trim_start_matches('/')));
 
     // If the path is relative, we'll try to serve it from the assets directory.
     let mut asset = get_asset_root()
@@ -71,18 +147,125 @@ pub(super) fn desktop_handler(
     }
 
     if asset.exists() {
-        return Response::builder()
-            .header("Content-Type", get_mime_from_path(&asset)?)
-            .body(std::fs::read(asset)?)
-            .map_err(From::from);
+        // Media can be huge, so only the start of the file is read to guess its type
+        let mut file = File::open(&asset)?;
+        let mut head = Vec::new();
+        (&mut file).take(MIME_HEAD).read_to_end(&mut head)?;
+        let mime = get_mime(&asset, &head);
+        return serve(request, file, mime);
     }
 
+    not_found()
+}
+
+fn not_found() -> Result<Response<Vec<u8>>> {
     Response::builder()
         .status(StatusCode::NOT_FOUND)
         .body(String::from("Not Found").into_bytes())
         .map_err(From::from)
 }
 
+/// How many bytes at the start of a file the mime type is guessed from
+const MIME_HEAD: u64 = 8192;
+
+/// Respond with the bytes, or the part of them the `Range` header asks for so audio and video can seek
+///
+/// Only the bytes that are sent are read, so seeking through a long video doesn't read all of it every time.
+fn serve(
+    request: &Request<Vec<u8>>,
+    mut body: impl Read + Seek,
+    mime: &str,
+) -> Result<Response<Vec<u8>>> {
+    let response = Response::builder()
+        .header("Content-Type", mime)
+        .header("Accept-Ranges", "bytes");
+    let len = body.seek(SeekFrom::End(0))? as usize;
+
+    let range = match request.headers().get("Range") {
+        Some(range) => range.to_str().ok(),
+        None => {
+            let mut data = Vec::with_capacity(len);
+            body.seek(SeekFrom::Start(0))?;
+            body.read_to_end(&mut data)?;
+            return response.body(data).map_err(From::from);
+        }
+    };
+
+    match range.and_then(|range| parse_range(range, len)) {
+        Some((start, end)) => {
+            let mut data = vec![0; end - start + 1];
+            body.seek(SeekFrom::Start(start as u64))?;
+            body.read_exact(&mut data)?;
+            response
+                .status(StatusCode::PARTIAL_CONTENT)
+                .header("Content-Range", format!("bytes {}-{}/{}", start, end, len))
+                .body(data)
+                .map_err(From::from)
+        }
+        None => response
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header("Content-Range", format!("bytes */{}", len))
+            .body(Vec::new())
+            .map_err(From::from),
+    }
+}
+
+/// The first and last byte of a `Range` header, like `bytes=0-1023`
+///
+/// Only the first range of a list is served.
+fn parse_range(header: &str, len: usize) -> Option<(usize, usize)> {
+    let spec = header.trim().strip_prefix("bytes=")?.split(',').next()?;
+    let (start, end) = spec.trim().split_once('-')?;
+    let last = len.checked_sub(1)?;
+
+    let (start, end) = if start.is_empty() {
+        // `bytes=-500` is the last 500 bytes
+        let suffix: usize = end.parse().ok()?;
+        if suffix == 0 {
+            return None;
+        }
+        (len.saturating_sub(suffix), last)
+    } else {
+        let start: usize = start.parse().ok()?;
+        let end = match end {
+            "" => last,
+            end => end.parse::<usize>().ok()?.min(last),
+        };
+        (start, end)
+    };
+
+    if start > end {
+        return None;
+    }
+    Some((start, end))
+}
+
+/// Decode the `%20`s of a path
+fn decode_path(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let escaped = match (bytes[idx], bytes.get(idx + 1..idx + 3)) {
+            (b'%', Some(hex)) => std::str::from_utf8(hex)
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                idx += 3;
+            }
+            None => {
+                decoded.push(bytes[idx]);
+                idx += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
 #[allow(unreachable_code)]
 fn get_asset_root() -> Option<PathBuf> {
     /*
@@ -116,19 +299,17 @@ fn get_asset_root() -> Option<PathBuf> {
     None
 }
 
-/// Get the mime type from a path-like string
-fn get_mime_from_path(trimmed: &Path) -> Result<&'static str> {
+/// Get the mime type from the contents of a file, or its path if they don't tell
+fn get_mime(trimmed: &Path, data: &[u8]) -> &'static str {
     if trimmed.ends_with(".svg") {
-        return Ok("image/svg+xml");
+        return "image/svg+xml";
     }
 
-    let res = match infer::get_from_path(trimmed)?.map(|f| f.mime_type()) {
+    match infer::get(data).map(|f| f.mime_type()) {
         Some(t) if t == "text/plain" => get_mime_by_ext(trimmed),
         Some(f) => f,
         None => get_mime_by_ext(trimmed),
-    };
-
-    Ok(res)
+    }
 }
 
 /// Get the mime type from a URI using its extension
@@ -153,3 +334,62 @@ fn get_mime_by_ext(trimmed: &Path) -> &'static str {
         None => "application/octet-stream",
     }
 }
+
+#[test]
+fn ranges() {
+    assert_eq!(parse_range("bytes=0-99", 1000), Some((0, 99)));
+    assert_eq!(parse_range("bytes=900-", 1000), Some((900, 999)));
+    assert_eq!(parse_range("bytes=-100", 1000), Some((900, 999)));
+    assert_eq!(parse_range("bytes=500-5000", 1000), Some((500, 999)));
+    assert_eq!(parse_range("bytes=0-9, 20-29", 1000), Some((0, 9)));
+    assert_eq!(parse_range("bytes=1000-", 1000), None);
+    assert_eq!(parse_range("bytes=-0", 1000), None);
+    assert_eq!(parse_range("bytes=0-0", 0), None);
+    assert_eq!(parse_range("items=0-9", 1000), None);
+
+    assert_eq!(decode_path("my%20song.mp3"), "my song.mp3");
+    assert_eq!(decode_path("100%"), "100%");
+}
+
+#[test]
+fn only_the_range_is_read() {
+    // Counts the bytes read out of it
+    struct Counted {
+        inner: Cursor<Vec<u8>>,
+        read: usize,
+    }
+
+    impl Read for Counted {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let read = self.inner.read(buf)?;
+            self.read += read;
+            Ok(read)
+        }
+    }
+
+    impl Seek for Counted {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    let mut body = Counted {
+        inner: Cursor::new((0..=255).collect()),
+        read: 0,
+    };
+    let request = Request::builder()
+        .header("Range", "bytes=10-19")
+        .body(Vec::new())
+        .unwrap();
+
+    let response = serve(&request, &mut body, "video/mp4").unwrap();
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.headers()["Content-Range"], "bytes 10-19/256");
+    assert_eq!(response.body(), &(10..20).collect::<Vec<u8>>());
+    assert_eq!(body.read, 10);
+
+    let request = Request::builder().body(Vec::new()).unwrap();
+    let response = serve(&request, &mut body, "video/mp4").unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.body().len(), 256);
+}
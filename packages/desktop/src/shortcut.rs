@@ -0,0 +1,241 @@
+use crate::desktop_context::UserWindowEvent;
+use crate::{use_window, DesktopContext};
+use dioxus_core::ScopeState;
+use dioxus_hooks::{Shortcut, ShortcutBackend, ShortcutError};
+use std::{cell::RefCell, rc::Rc};
+use wry::application::{
+    accelerator::{Accelerator, AcceleratorId},
+    event_loop::EventLoopWindowTarget,
+    global_shortcut::{GlobalShortcut, ShortcutManager},
+    window::WindowId,
+};
+
+/// Backs `use_keyboard_shortcut` with the global accelerators of the operating system
+///
+/// The accelerators belong to the process, so every window shares one manager.
+pub(crate) struct DesktopShortcuts {
+    registry: Rc<RefCell<Registry>>,
+}
+
+struct Registry {
+    manager: ShortcutManager,
+    registered: Vec<(Shortcut, GlobalShortcut)>,
+    handlers: Vec<WindowShortcut>,
+    next_handler: usize,
+}
+
+/// A shortcut registered with [`DesktopContext::register_global_shortcut`]
+struct WindowShortcut {
+    key: usize,
+    window: WindowId,
+    global: GlobalShortcut,
+    handler: Rc<RefCell<dyn FnMut()>>,
+}
+
+thread_local! {
+    static REGISTRY: RefCell<Option<Rc<RefCell<Registry>>>> = RefCell::new(None);
+}
+
+impl DesktopShortcuts {
+    pub(crate) fn new(event_loop: &EventLoopWindowTarget<UserWindowEvent>) -> Self {
+        let registry = REGISTRY.with(|registry| {
+            registry
+                .borrow_mut()
+                .get_or_insert_with(|| {
+                    Rc::new(RefCell::new(Registry {
+                        manager: ShortcutManager::new(event_loop),
+                        registered: Vec::new(),
+                        handlers: Vec::new(),
+                        next_handler: 0,
+                    }))
+                })
+                .clone()
+        });
+
+        Self { registry }
+    }
+}
+
+impl ShortcutBackend for DesktopShortcuts {
+    fn register(&self, shortcut: &Shortcut) -> Result<(), ShortcutError> {
+        let accelerator = to_accelerator(shortcut)?;
+
+        let mut registry = self.registry.borrow_mut();
+        let global = registry
+            .manager
+            .register(accelerator)
+            .map_err(|err| ShortcutError::Platform(format!("{:?}", err)))?;
+        registry.registered.push((shortcut.clone(), global));
+        Ok(())
+    }
+
+    fn unregister(&self, shortcut: &Shortcut) {
+        let mut registry = self.registry.borrow_mut();
+        let index = registry.registered.iter().position(|(s, _)| s == shortcut);
+        if let Some(index) = index {
+            let (_, global) = registry.registered.remove(index);
+            if let Err(err) = registry.manager.unregister(global) {
+                log::error!("Failed to unregister the shortcut {}: {:?}", shortcut, err);
+            }
+        }
+    }
+}
+
+/// Run the handler of a global accelerator that was pressed
+pub(crate) fn handle_global_shortcut(id: AcceleratorId) {
+    let (shortcut, handler) = REGISTRY.with(|registry| {
+        let registry = registry.borrow();
+        let registry = match registry.as_ref() {
+            Some(registry) => registry.borrow(),
+            None => return (None, None),
+        };
+        let shortcut = registry
+            .registered
+            .iter()
+            .find(|(_, global)| global.id() == id)
+            .map(|(shortcut, _)| shortcut.clone());
+        let handler = registry
+            .handlers
+            .iter()
+            .find(|registered| registered.global.id() == id)
+            .map(|registered| registered.handler.clone());
+        (shortcut, handler)
+    });
+
+    // the handlers run once the registry is released, so they can register and unregister shortcuts themselves
+    if let Some(shortcut) = shortcut {
+        dioxus_hooks::trigger_shortcut(&shortcut);
+    }
+    if let Some(handler) = handler {
+        (handler.borrow_mut())();
+    }
+}
+
+/// Unregister the shortcuts a window registered when it closes
+pub(crate) fn window_closed(window: WindowId) {
+    REGISTRY.with(|registry| {
+        if let Some(registry) = registry.borrow().as_ref() {
+            registry
+                .borrow_mut()
+                .remove(|registered| registered.window == window);
+        }
+    });
+}
+
+impl Registry {
+    fn remove(&mut self, mut remove: impl FnMut(&WindowShortcut) -> bool) {
+        let mut idx = 0;
+        while idx < self.handlers.len() {
+            if remove(&self.handlers[idx]) {
+                let registered = self.handlers.remove(idx);
+                if let Err(err) = self.manager.unregister(registered.global) {
+                    log::error!("Failed to unregister a global shortcut: {:?}", err);
+                }
+            } else {
+                idx += 1;
+            }
+        }
+    }
+}
+
+/// A global shortcut registered with [`DesktopContext::register_global_shortcut`]
+///
+/// The shortcut is unregistered when this is dropped, or when the window that registered it closes.
+pub struct GlobalShortcutHandle {
+    key: usize,
+}
+
+impl Drop for GlobalShortcutHandle {
+    fn drop(&mut self) {
+        let key = self.key;
+        REGISTRY.with(|registry| {
+            if let Some(registry) = registry.borrow().as_ref() {
+                registry
+                    .borrow_mut()
+                    .remove(|registered| registered.key == key);
+            }
+        });
+    }
+}
+
+impl DesktopContext {
+    /// Run `handler` whenever the accelerator is pressed, even while the app isn't focused
+    ///
+    /// Accelerators are written like `"CmdOrCtrl+Shift+P"`, with the key last. Keep the handle for as long as the
+    /// shortcut should stay registered. It's unregistered when the handle is dropped or this window closes.
+    /// [`use_global_shortcut`] ties the shortcut to a component instead.
+    pub fn register_global_shortcut(
+        &self,
+        accelerator: &str,
+        handler: impl FnMut() + 'static,
+    ) -> Result<GlobalShortcutHandle, ShortcutError> {
+        let parsed = accelerator
+            .parse::<Accelerator>()
+            .map_err(|_| ShortcutError::Invalid(accelerator.to_string()))?;
+
+        let shortcuts = DesktopShortcuts::new(&self.event_loop);
+        let mut registry = shortcuts.registry.borrow_mut();
+        let global = registry
+            .manager
+            .register(parsed)
+            .map_err(|err| ShortcutError::Platform(format!("{:?}", err)))?;
+
+        let key = registry.next_handler;
+        registry.next_handler += 1;
+        registry.handlers.push(WindowShortcut {
+            key,
+            window: self.webview.window().id(),
+            global,
+            handler: Rc::new(RefCell::new(handler)),
+        });
+
+        Ok(GlobalShortcutHandle { key })
+    }
+}
+
+/// Run `handler` whenever the accelerator is pressed, even while the app isn't focused, until the component is removed
+///
+/// The accelerator is read the first time the hook runs, while the handler is replaced on every render so it sees the
+/// latest state. See [`DesktopContext::register_global_shortcut`] for how accelerators are written.
+pub fn use_global_shortcut<'a>(
+    cx: &'a ScopeState,
+    accelerator: &str,
+    handler: impl FnMut() + 'static,
+) -> &'a Result<GlobalShortcutHandle, ShortcutError> {
+    let desktop = use_window(cx);
+    let current = cx.use_hook(|| Rc::new(RefCell::new(None::<Box<dyn FnMut()>>)));
+    current.replace(Some(Box::new(handler)));
+
+    cx.use_hook(|| {
+        let current = current.clone();
+        let registered = desktop.register_global_shortcut(accelerator, move || {
+            if let Some(handler) = current.borrow_mut().as_mut() {
+                handler();
+            }
+        });
+        if let Err(err) = &registered {
+            log::warn!("The shortcut {:?} wasn't registered: {}", accelerator, err);
+        }
+        registered
+    })
+}
+
+/// The names of keys in a [`Shortcut`] are the lowercase names of tao's key codes
+fn to_accelerator(shortcut: &Shortcut) -> Result<Accelerator, ShortcutError> {
+    let modifiers = [
+        (shortcut.ctrl(), "CONTROL+"),
+        (shortcut.alt(), "ALT+"),
+        (shortcut.shift(), "SHIFT+"),
+        (shortcut.meta(), "SUPER+"),
+    ];
+    let mut accelerator = modifiers
+        .iter()
+        .filter(|(held, _)| *held)
+        .map(|(_, name)| *name)
+        .collect::<String>();
+    accelerator.push_str(&shortcut.key().to_ascii_uppercase());
+
+    accelerator
+        .parse()
+        .map_err(|_| ShortcutError::Platform(format!("{} can't be a global shortcut", shortcut)))
+}
@@ -0,0 +1,60 @@
+use dioxus_hooks::PersistentStorage;
+use std::path::PathBuf;
+
+/// Backs `use_persistent` with one json file per key
+pub(crate) struct FileStorage {
+    dir: Option<PathBuf>,
+}
+
+impl FileStorage {
+    /// Store the files in `dir`, or in a folder named after the executable in the config directory of the user
+    pub(crate) fn new(dir: Option<PathBuf>) -> Self {
+        let dir = dir.or_else(|| {
+            let name = std::env::current_exe().ok()?.file_stem()?.to_owned();
+            Some(dirs::config_dir()?.join(name))
+        });
+
+        Self { dir }
+    }
+
+    fn path(&self, key: &str) -> Option<PathBuf> {
+        // Keys come from the app, but they shouldn't be able to point outside of the storage directory
+        let name: String = key
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
+                _ => '_',
+            })
+            .collect();
+
+        Some(self.dir.as_ref()?.join(name).with_extension("json"))
+    }
+}
+
+impl PersistentStorage for FileStorage {
+    fn load(&self, key: &str) -> Option<String> {
+        std::fs::read_to_string(self.path(key)?).ok()
+    }
+
+    fn store(&self, key: &str, value: &str) {
+        let path = match self.path(key) {
+            Some(path) => path,
+            None => {
+                log::warn!(
+                    "No config directory was found, {:?} won't be persisted",
+                    key
+                );
+                return;
+            }
+        };
+
+        let written = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::write(&path, value));
+
+        if let Err(err) = written {
+            log::error!("Failed to write {:?} to {}: {}", key, path.display(), err);
+        }
+    }
+}
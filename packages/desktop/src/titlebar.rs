@@ -0,0 +1,120 @@
+use crate::DesktopContext;
+
+/// A button of a custom titlebar, for the `data-dioxus-window-control` attribute
+///
+/// Windows without decorations need a titlebar made out of elements. An element with a `data-dioxus-drag-region`
+/// attribute moves the window when it's dragged, and maximizes it when it's double clicked, unless the attribute is set
+/// to `"no-maximize"`. Buttons, links and inputs in it still work like they normally do. An element with a
+/// `data-dioxus-window-control` attribute does what the control it's set to does when it's clicked.
+///
+/// ```rust, ignore
+/// rsx! {
+///     div { "data-dioxus-drag-region": "",
+///         span { "My App" }
+///         button { "data-dioxus-window-control": WindowControl::Minimize.as_str(), "_" }
+///         button { "data-dioxus-window-control": WindowControl::Maximize.as_str(), "□" }
+///         button { "data-dioxus-window-control": WindowControl::Close.as_str(), "×" }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowControl {
+    /// Minimize the window
+    Minimize,
+
+    /// Maximize the window, or restore it if it's maximized
+    Maximize,
+
+    /// Close the window
+    Close,
+}
+
+impl WindowControl {
+    /// The value of the `data-dioxus-window-control` attribute for this control
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WindowControl::Minimize => "minimize",
+            WindowControl::Maximize => "maximize",
+            WindowControl::Close => "close",
+        }
+    }
+
+    fn parse(control: &str) -> Option<Self> {
+        match control {
+            "minimize" => Some(WindowControl::Minimize),
+            "maximize" => Some(WindowControl::Maximize),
+            "close" => Some(WindowControl::Close),
+            _ => None,
+        }
+    }
+}
+
+impl DesktopContext {
+    /// Minimize the window
+    pub fn minimize(&self) {
+        self.webview.window().set_minimized(true);
+    }
+
+    /// Maximize the window
+    pub fn maximize(&self) {
+        self.webview.window().set_maximized(true);
+    }
+
+    /// Do what a button of the titlebar does
+    pub fn window_control(&self, control: WindowControl) {
+        match control {
+            WindowControl::Minimize => self.minimize(),
+            WindowControl::Maximize => self.toggle_maximized(),
+            WindowControl::Close => self.close(),
+        }
+    }
+}
+
+/// Do what the titlebar of the page asked for
+pub(crate) fn handle_message(desktop: &DesktopContext, params: &serde_json::Value) {
+    match params.get("action").and_then(|action| action.as_str()) {
+        Some("drag") => desktop.drag(),
+        Some(control) => {
+            if let Some(control) = WindowControl::parse(control) {
+                desktop.window_control(control);
+            }
+        }
+        None => {}
+    }
+}
+
+/// Makes the drag regions and window controls of the page work
+pub(crate) const TITLEBAR_JS: &str = r#"
+function windowControl(action) {
+  window.ipc.postMessage(serializeIpcMessage("window_control", { action }));
+}
+document.addEventListener("mousedown", (event) => {
+  if (event.button !== 0 || !(event.target instanceof Element)) {
+    return;
+  }
+  const region = event.target.closest("[data-dioxus-drag-region]");
+  if (!region) {
+    return;
+  }
+  // the controls of the titlebar, and anything else that can be clicked, keep working
+  const interactive = event.target.closest(
+    "a, button, input, select, textarea, [contenteditable], [data-dioxus-window-control]"
+  );
+  if (interactive && region.contains(interactive)) {
+    return;
+  }
+  if (event.detail === 2) {
+    if (region.dataset.dioxusDragRegion !== "no-maximize") {
+      windowControl("maximize");
+    }
+  } else {
+    windowControl("drag");
+  }
+});
+document.addEventListener("click", (event) => {
+  const control = event.target instanceof Element && event.target.closest("[data-dioxus-window-control]");
+  if (control) {
+    windowControl(control.dataset.dioxusWindowControl);
+  }
+});
+"#;
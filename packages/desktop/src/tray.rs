@@ -0,0 +1,237 @@
+use crate::use_window;
+use dioxus_core::ScopeState;
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use tokio::sync::broadcast::error::RecvError;
+use wry::application::{
+    event::TrayEvent as TaoTrayEvent,
+    event_loop::EventLoopWindowTarget,
+    menu::{ContextMenu, MenuId, MenuItem, MenuItemAttributes},
+    system_tray::{SystemTray, SystemTrayBuilder},
+};
+
+pub use wry::application::system_tray::Icon as TrayIcon;
+
+/// An icon in the system tray, with an optional menu
+///
+/// ```rust, ignore
+/// let tray = Tray::new(icon)
+///     .with_tooltip("My App")
+///     .with_menu(TrayMenu::new().item("show", "Show").separator().item("quit", "Quit"))
+///     .with_hide_on_close(true);
+///
+/// dioxus_desktop::launch_cfg(app, Config::new().with_tray(tray));
+/// ```
+pub struct Tray {
+    pub(crate) icon: TrayIcon,
+    pub(crate) menu: Option<TrayMenu>,
+    pub(crate) tooltip: Option<String>,
+    pub(crate) hide_on_close: bool,
+}
+
+impl Tray {
+    /// Create a tray icon without a menu
+    pub fn new(icon: TrayIcon) -> Self {
+        Self {
+            icon,
+            menu: None,
+            tooltip: None,
+            hide_on_close: false,
+        }
+    }
+
+    /// Set the menu that opens from the icon
+    pub fn with_menu(mut self, menu: TrayMenu) -> Self {
+        self.menu = Some(menu);
+        self
+    }
+
+    /// Set the text shown when hovering over the icon
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Hide the main window when it's closed instead of quitting, so the app keeps running in the tray
+    ///
+    /// Show it again with `set_visible(true)` on its [`DesktopContext`](crate::DesktopContext), and quit by closing it
+    /// with [`DesktopContext::close`](crate::DesktopContext::close).
+    pub fn with_hide_on_close(mut self, hide: bool) -> Self {
+        self.hide_on_close = hide;
+        self
+    }
+}
+
+/// The menu of a tray icon
+///
+/// Items have an id, which is what [`TrayEvent::MenuItem`] reports when the item is picked.
+#[derive(Default)]
+pub struct TrayMenu {
+    entries: Vec<TrayMenuEntry>,
+}
+
+enum TrayMenuEntry {
+    Item {
+        id: String,
+        title: String,
+        enabled: bool,
+    },
+    Separator,
+    Submenu {
+        title: String,
+        menu: TrayMenu,
+    },
+}
+
+impl TrayMenu {
+    /// Create an empty menu
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an item
+    pub fn item(self, id: impl Into<String>, title: impl Into<String>) -> Self {
+        self.entry(TrayMenuEntry::Item {
+            id: id.into(),
+            title: title.into(),
+            enabled: true,
+        })
+    }
+
+    /// Add an item that's greyed out and can't be picked
+    pub fn disabled_item(self, id: impl Into<String>, title: impl Into<String>) -> Self {
+        self.entry(TrayMenuEntry::Item {
+            id: id.into(),
+            title: title.into(),
+            enabled: false,
+        })
+    }
+
+    /// Add a line between the items before and after it
+    pub fn separator(self) -> Self {
+        self.entry(TrayMenuEntry::Separator)
+    }
+
+    /// Add a menu that opens from an item
+    pub fn submenu(self, title: impl Into<String>, menu: TrayMenu) -> Self {
+        self.entry(TrayMenuEntry::Submenu {
+            title: title.into(),
+            menu,
+        })
+    }
+
+    fn entry(mut self, entry: TrayMenuEntry) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    fn build(&self, ids: &mut HashMap<MenuId, String>) -> ContextMenu {
+        let mut menu = ContextMenu::new();
+        for entry in &self.entries {
+            match entry {
+                TrayMenuEntry::Item { id, title, enabled } => {
+                    let item = menu.add_item(MenuItemAttributes::new(title).with_enabled(*enabled));
+                    ids.insert(item.id(), id.clone());
+                }
+                TrayMenuEntry::Separator => {
+                    menu.add_native_item(MenuItem::Separator);
+                }
+                TrayMenuEntry::Submenu {
+                    title,
+                    menu: submenu,
+                } => {
+                    menu.add_submenu(title, true, submenu.build(ids));
+                }
+            }
+        }
+        menu
+    }
+}
+
+/// Something the user did with the tray icon
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrayEvent {
+    /// The icon was clicked
+    Click,
+
+    /// The icon was double clicked
+    DoubleClick,
+
+    /// The icon was right clicked, which opens the menu on most platforms
+    RightClick,
+
+    /// An item of the menu was picked, with the id it was added with
+    MenuItem(String),
+}
+
+/// The tray icon of a running app
+pub(crate) struct ActiveTray {
+    // the icon goes away when this is dropped
+    _tray: SystemTray,
+    menu_ids: HashMap<MenuId, String>,
+    pub(crate) hide_on_close: bool,
+}
+
+impl ActiveTray {
+    pub(crate) fn new<T>(tray: Tray, event_loop: &EventLoopWindowTarget<T>) -> Option<Self> {
+        let mut menu_ids = HashMap::new();
+        let menu = tray.menu.as_ref().map(|menu| menu.build(&mut menu_ids));
+
+        let mut builder = SystemTrayBuilder::new(tray.icon, menu);
+        if let Some(tooltip) = &tray.tooltip {
+            builder = builder.with_tooltip(tooltip);
+        }
+
+        match builder.build(event_loop) {
+            Ok(system_tray) => Some(Self {
+                _tray: system_tray,
+                menu_ids,
+                hide_on_close: tray.hide_on_close,
+            }),
+            Err(err) => {
+                log::error!("Failed to create the tray icon: {}", err);
+                None
+            }
+        }
+    }
+
+    /// The event for a menu item of the tray, if the menu item is one of ours
+    pub(crate) fn menu_event(&self, id: MenuId) -> Option<TrayEvent> {
+        self.menu_ids.get(&id).cloned().map(TrayEvent::MenuItem)
+    }
+}
+
+pub(crate) fn icon_event(event: TaoTrayEvent) -> TrayEvent {
+    match event {
+        TaoTrayEvent::DoubleClick => TrayEvent::DoubleClick,
+        TaoTrayEvent::RightClick => TrayEvent::RightClick,
+        _ => TrayEvent::Click,
+    }
+}
+
+/// Run `handler` for every [`TrayEvent`] while the component is mounted
+///
+/// Every window gets every event, so handle each event in one window.
+pub fn use_tray_event_handler(cx: &ScopeState, handler: impl FnMut(TrayEvent) + 'static) {
+    let desktop = use_window(cx);
+    let current = cx.use_hook(|| Rc::new(RefCell::new(None::<Box<dyn FnMut(TrayEvent)>>)));
+    // keep the latest handler so it sees the latest props
+    current.replace(Some(Box::new(handler)));
+
+    cx.use_hook(|| {
+        let mut events = desktop.tray_events();
+        let current = current.clone();
+        cx.push_future(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => {
+                        if let Some(handler) = current.borrow_mut().as_mut() {
+                            handler(event);
+                        }
+                    }
+                    Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    });
+}
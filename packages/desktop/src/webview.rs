@@ -1,3 +1,5 @@
+use std::cell::RefCell;
+use std::path::PathBuf;
 use std::rc::Rc;
 
 use crate::desktop_context::EventData;
@@ -7,12 +9,13 @@ use tao::event_loop::{EventLoopProxy, EventLoopWindowTarget};
 pub use wry;
 pub use wry::application as tao;
 use wry::application::window::Window;
-use wry::webview::{WebView, WebViewBuilder};
+use wry::webview::{FileDropEvent, WebView, WebViewBuilder};
 
 pub fn build(
     cfg: &mut Config,
     event_loop: &EventLoopWindowTarget<UserWindowEvent>,
     proxy: EventLoopProxy<UserWindowEvent>,
+    dropped_files: Rc<RefCell<Vec<PathBuf>>>,
 ) -> Rc<WebView> {
     let builder = cfg.window.clone();
     let window = builder.build(event_loop).unwrap();
@@ -20,6 +23,7 @@ pub fn build(
     let custom_head = cfg.custom_head.clone();
     let index_file = cfg.custom_index.clone();
     let root_name = cfg.root_name.clone();
+    let asset_handlers = std::mem::take(&mut cfg.asset_handlers);
 
     // We assume that if the icon is None in cfg, then the user just didnt set it
     if cfg.window.window.window_icon.is_none() {
@@ -33,6 +37,7 @@ pub fn build(
         ));
     }
 
+    let drop_proxy = proxy.clone();
     let mut webview = WebViewBuilder::new(window)
         .unwrap()
         .with_transparent(cfg.window.window.transparent)
@@ -45,13 +50,48 @@ pub fn build(
             }
         })
         .with_custom_protocol(String::from("dioxus"), move |r| {
-            protocol::desktop_handler(r, custom_head.clone(), index_file.clone(), &root_name)
+            protocol::desktop_handler(
+                r,
+                custom_head.clone(),
+                index_file.clone(),
+                &root_name,
+                &asset_handlers,
+            )
         })
         .with_file_drop_handler(move |window, evet| {
-            file_handler
+            let (kind, position) = match &evet {
+                FileDropEvent::Hovered { paths, position } => {
+                    *dropped_files.borrow_mut() = paths.clone();
+                    ("hover", *position)
+                }
+                FileDropEvent::Dropped { paths, position } => {
+                    *dropped_files.borrow_mut() = paths.clone();
+                    ("drop", *position)
+                }
+                _ => {
+                    dropped_files.borrow_mut().clear();
+                    ("cancel", Default::default())
+                }
+            };
+
+            let handled = file_handler
                 .as_ref()
                 .map(|handler| handler(window, evet))
-                .unwrap_or_default()
+                .unwrap_or_default();
+
+            // Fire the drag events at the element under the cursor ourselves, with the files read from their paths,
+            // instead of letting the webview fire its own
+            if !handled {
+                let position = position.to_logical::<f64>(window.scale_factor());
+                let event = EventData::FileDrop {
+                    kind,
+                    x: position.x,
+                    y: position.y,
+                };
+                _ = drop_proxy.send_event(UserWindowEvent(event, window.id()));
+            }
+
+            true
         });
 
     for (name, handler) in cfg.protocols.drain(..) {
@@ -78,5 +118,15 @@ pub fn build(
         webview = webview.with_devtools(true);
     }
 
+    // the devtools feature keeps the DevTool window around in release builds
+    #[cfg(feature = "devtools")]
+    {
+        webview = webview.with_devtools(true);
+    }
+
+    if let Some(user_agent) = &cfg.user_agent {
+        webview = webview.with_user_agent(user_agent);
+    }
+
     Rc::new(webview.build().unwrap())
 }
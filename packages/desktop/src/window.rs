@@ -0,0 +1,27 @@
+use dioxus_hooks::{WindowBackend, WindowSize};
+use std::rc::Rc;
+use wry::webview::WebView;
+
+/// Backs `use_window_size` and `use_media_query` with the window of a webview
+pub(crate) struct DesktopWindow {
+    webview: Rc<WebView>,
+}
+
+impl DesktopWindow {
+    pub(crate) fn new(webview: Rc<WebView>) -> Self {
+        Self { webview }
+    }
+}
+
+impl WindowBackend for DesktopWindow {
+    /// The size is logical, so it matches the CSS pixels inside the webview
+    fn size(&self) -> WindowSize {
+        let window = self.webview.window();
+        let size = window.inner_size().to_logical::<f64>(window.scale_factor());
+
+        WindowSize {
+            width: size.width,
+            height: size.height,
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use crate::storage::FileStorage;
+use dioxus_hooks::PersistentStorage;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use wry::application::{
+    dpi::{PhysicalPosition, PhysicalSize},
+    event_loop::EventLoopWindowTarget,
+    window::{Window, WindowBuilder},
+};
+
+const KEY: &str = "window-state";
+
+/// Where a window was when it closed, in physical pixels
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+struct WindowState {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    maximized: bool,
+}
+
+/// Saves the size and position of a window when it closes, and restores them when it opens again
+pub(crate) struct PersistentWindow {
+    storage: FileStorage,
+}
+
+impl PersistentWindow {
+    /// Keep the state in the storage directory, or in a folder named after the app in the config directory of the user
+    pub(crate) fn new(app_id: &str, storage_dir: Option<PathBuf>) -> Self {
+        let dir = storage_dir.or_else(|| Some(dirs::config_dir()?.join(app_id)));
+        Self {
+            storage: FileStorage::new(dir),
+        }
+    }
+
+    fn load(&self) -> Option<WindowState> {
+        serde_json::from_str(&self.storage.load(KEY)?).ok()
+    }
+
+    /// Open the window the way it was when it closed
+    ///
+    /// Monitors come and go, so the window is only moved back if its title bar would be on one of them, and it's
+    /// never bigger than the primary monitor.
+    pub(crate) fn restore<T>(
+        &self,
+        builder: WindowBuilder,
+        event_loop: &EventLoopWindowTarget<T>,
+    ) -> WindowBuilder {
+        let state = match self.load() {
+            Some(state) => state,
+            None => return builder,
+        };
+
+        let monitors = event_loop
+            .available_monitors()
+            .map(|monitor| (monitor.position(), monitor.size()));
+        let primary = event_loop.primary_monitor().map(|primary| primary.size());
+        let (size, position) = place(state, primary, monitors);
+
+        let mut builder = builder
+            .with_inner_size(size)
+            .with_maximized(state.maximized);
+        if let Some(position) = position {
+            builder = builder.with_position(position);
+        }
+
+        builder
+    }
+
+    /// Remember where the window is
+    pub(crate) fn save(&self, window: &Window) {
+        let maximized = window.is_maximized();
+
+        // A maximized window fills its monitor, so it keeps the size it had before it was maximized
+        let state = match self.load() {
+            Some(previous) if maximized => WindowState {
+                maximized,
+                ..previous
+            },
+            _ => {
+                let position = window.outer_position().unwrap_or_default();
+                let size = window.inner_size();
+                WindowState {
+                    x: position.x,
+                    y: position.y,
+                    width: size.width,
+                    height: size.height,
+                    maximized,
+                }
+            }
+        };
+
+        self.store(state);
+    }
+
+    fn store(&self, state: WindowState) {
+        match serde_json::to_string(&state) {
+            Ok(json) => self.storage.store(KEY, &json),
+            Err(err) => log::error!("Failed to save the state of the window: {}", err),
+        }
+    }
+}
+
+/// The size of a restored window, and its position if its title bar would be on one of the monitors
+fn place(
+    state: WindowState,
+    primary: Option<PhysicalSize<u32>>,
+    mut monitors: impl Iterator<Item = (PhysicalPosition<i32>, PhysicalSize<u32>)>,
+) -> (PhysicalSize<u32>, Option<PhysicalPosition<i32>>) {
+    let (mut width, mut height) = (state.width, state.height);
+    if let Some(primary) = primary {
+        width = width.min(primary.width);
+        height = height.min(primary.height);
+    }
+
+    // a point on the title bar, a little in from the left edge
+    let grab_x = state.x + (width as i32 / 2).min(50);
+    let grab_y = state.y + 10;
+    let on_a_monitor = monitors.any(|(position, size)| {
+        (position.x..position.x + size.width as i32).contains(&grab_x)
+            && (position.y..position.y + size.height as i32).contains(&grab_y)
+    });
+
+    let position = match on_a_monitor {
+        true => Some(PhysicalPosition::new(state.x, state.y)),
+        false => None,
+    };
+    (PhysicalSize::new(width, height), position)
+}
+
+#[cfg(test)]
+fn storage(name: &str) -> PersistentWindow {
+    let dir = std::env::temp_dir().join(format!("@dioxus-{}-{}", name, std::process::id()));
+    _ = std::fs::remove_dir_all(&dir);
+    PersistentWindow::new("test", Some(dir))
+}
+
+#[cfg(test)]
+const STATE: WindowState = WindowState {
+    x: 100,
+    y: 200,
+    width: 800,
+    height: 600,
+    maximized: true,
+};
+
+#[test]
+fn state_round_trips() {
+    let window = storage("window-state");
+    assert_eq!(window.load(), None);
+
+    window.store(STATE);
+    assert_eq!(window.load(), Some(STATE));
+}
+
+#[test]
+fn corrupt_state_is_ignored() {
+    let window = storage("corrupt-window-state");
+    window.storage.store(KEY, "{\"x\": 100, \"y\":");
+    assert_eq!(window.load(), None);
+
+    // the next save replaces it
+    window.store(STATE);
+    assert_eq!(window.load(), Some(STATE));
+}
+
+#[test]
+fn off_screen_windows_stay_where_the_platform_puts_them() {
+    let monitor = (PhysicalPosition::new(0, 0), PhysicalSize::new(1920, 1080));
+    let primary = Some(monitor.1);
+
+    let (size, position) = place(STATE, primary, std::iter::once(monitor));
+    assert_eq!(size, PhysicalSize::new(800, 600));
+    assert_eq!(position, Some(PhysicalPosition::new(100, 200)));
+
+    // the monitor it was on was unplugged
+    let gone = WindowState { x: 2500, ..STATE };
+    let (_, position) = place(gone, primary, std::iter::once(monitor));
+    assert_eq!(position, None);
+
+    // only the title bar counts, a window hanging off the top can't be dragged back
+    let above = WindowState { y: -300, ..STATE };
+    let (_, position) = place(above, primary, std::iter::once(monitor));
+    assert_eq!(position, None);
+
+    // windows bigger than the primary monitor are shrunk to fit it
+    let huge = WindowState {
+        width: 4000,
+        height: 3000,
+        ..STATE
+    };
+    let (size, _) = place(huge, primary, std::iter::once(monitor));
+    assert_eq!(size, PhysicalSize::new(1920, 1080));
+}
@@ -0,0 +1,48 @@
+#![doc = include_str!("../README.md")]
+#![doc(html_logo_url = "https://avatars.githubusercontent.com/u/79236386")]
+#![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
+
+use dioxus_core::VirtualDom;
+use futures_util::{pin_mut, task::ArcWake, FutureExt};
+use std::sync::Arc;
+use std::task::{Context, Waker};
+use tao::event_loop::EventLoopProxy;
+
+/// Create a waker that sends the event loop the event `poll` makes, so it polls the VirtualDom again
+///
+/// This lets the VirtualDom "come up for air" and process events while the main thread is busy with the window.
+pub fn tao_waker<T: 'static>(
+    proxy: &EventLoopProxy<T>,
+    poll: impl Fn() -> T + Send + Sync + 'static,
+) -> Waker {
+    struct DomHandle<T: 'static, F> {
+        proxy: EventLoopProxy<T>,
+        poll: F,
+    }
+
+    // this should be implemented by most platforms, but ios is missing this until
+    // https://github.com/tauri-apps/wry/issues/830 is resolved
+    unsafe impl<T: 'static, F: Send> Send for DomHandle<T, F> {}
+    unsafe impl<T: 'static, F: Sync> Sync for DomHandle<T, F> {}
+
+    impl<T: 'static, F: Fn() -> T + Send + Sync> ArcWake for DomHandle<T, F> {
+        fn wake_by_ref(arc_self: &Arc<Self>) {
+            _ = arc_self.proxy.send_event((arc_self.poll)());
+        }
+    }
+
+    futures_util::task::waker(Arc::new(DomHandle {
+        proxy: proxy.clone(),
+        poll,
+    }))
+}
+
+/// Poll the VirtualDom with a waker from [`tao_waker`], returning true if it has work to render
+///
+/// Call it until it returns false, rendering in between. After that, the waker wakes the event loop once there's more.
+pub fn poll_work(dom: &mut VirtualDom, waker: &Waker) -> bool {
+    let mut cx = Context::from_waker(waker);
+    let fut = dom.wait_for_work();
+    pin_mut!(fut);
+    fut.poll_unpin(&mut cx).is_ready()
+}
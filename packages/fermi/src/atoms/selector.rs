@@ -1 +1,52 @@
+use crate::{AtomId, AtomRoot, Readable};
+use im_rc::HashSet;
+use std::{cell::RefCell, rc::Rc};
 
+/// A value computed from other atoms and selectors
+///
+/// The selector tracks what it reads through the [`SelectorBuilder`], and is only computed again when one of those
+/// changes. Scopes reading it are only re-rendered when the new value is different from the old one.
+///
+/// ```rust, ignore
+/// static ITEMS: Atom<Vec<Item>> = |_| Vec::new();
+/// static TOTAL: Selector<f64> = |s| s.get(ITEMS).iter().map(|item| item.price).sum();
+/// ```
+pub type Selector<T> = fn(&SelectorBuilder) -> T;
+
+/// Reads atoms and selectors on behalf of a [`Selector`], keeping track of what it depends on
+pub struct SelectorBuilder<'a> {
+    root: &'a AtomRoot,
+    dependencies: RefCell<HashSet<AtomId>>,
+}
+
+impl<'a> SelectorBuilder<'a> {
+    pub(crate) fn new(root: &'a AtomRoot) -> Self {
+        Self {
+            root,
+            dependencies: RefCell::new(HashSet::new()),
+        }
+    }
+
+    pub(crate) fn into_dependencies(self) -> HashSet<AtomId> {
+        self.dependencies.into_inner()
+    }
+
+    /// Read an atom, computing the selector again whenever it changes
+    pub fn get<V: 'static>(&self, atom: impl Readable<V>) -> Rc<V> {
+        self.dependencies.borrow_mut().insert(atom.unique_id());
+        self.root.read(atom)
+    }
+
+    /// Read another selector, computing this one again whenever its value changes
+    pub fn select<V: PartialEq + 'static>(&self, selector: Selector<V>) -> Rc<V> {
+        self.dependencies.borrow_mut().insert(selector_id(selector));
+        self.root.select(selector)
+    }
+}
+
+pub(crate) fn selector_id<V: 'static>(selector: Selector<V>) -> AtomId {
+    AtomId {
+        ptr: selector as *const (),
+        type_id: std::any::TypeId::of::<V>(),
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{use_atom_root, AtomFamily, AtomId, AtomRoot, Readable};
+use dioxus_core::{ScopeId, ScopeState};
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
+
+/// Read and write a single member of an atom family
+///
+/// The component only re-renders when the member with its key changes, not when any other member of the family does.
+///
+/// ```rust, ignore
+/// static TODOS: AtomFamily<u32, Todo> = |_| im_rc::HashMap::new();
+///
+/// #[inline_props]
+/// fn TodoItem(cx: Scope, id: u32) -> Element {
+///     let todo = use_atom_family(cx, TODOS, *id);
+///     let title = todo.get().map(|todo| todo.title.as_str()).unwrap_or_default();
+///     cx.render(rsx!{ li { "{title}" } })
+/// }
+/// ```
+pub fn use_atom_family<K, V>(
+    cx: &ScopeState,
+    family: AtomFamily<K, V>,
+    key: K,
+) -> &UseAtomFamily<K, V>
+where
+    K: Hash + Eq + Clone + 'static,
+    V: Clone + 'static,
+{
+    let root = use_atom_root(cx);
+
+    let inner = cx.use_hook(|| {
+        let key_hash = hash_key(&key);
+        UseAtomFamily {
+            root: root.clone(),
+            family,
+            id: family.unique_id(),
+            key: key.clone(),
+            key_hash,
+            scope_id: cx.scope_id(),
+            value: None,
+        }
+    });
+
+    // The component can switch to another member of the family
+    if inner.key != key {
+        inner
+            .root
+            .unsubscribe_key(inner.id, inner.key_hash, inner.scope_id);
+        inner.key_hash = hash_key(&key);
+        inner.key = key;
+    }
+
+    let members = inner
+        .root
+        .register_key(family, inner.key_hash, inner.scope_id);
+    inner.value = members.get(&inner.key).cloned();
+
+    inner
+}
+
+fn hash_key<K: Hash>(key: &K) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// A handle to a single member of an atom family, returned by [`use_atom_family`]
+pub struct UseAtomFamily<K: 'static, V: 'static> {
+    root: Rc<AtomRoot>,
+    family: AtomFamily<K, V>,
+    id: AtomId,
+    key: K,
+    key_hash: u64,
+    scope_id: ScopeId,
+    value: Option<V>,
+}
+
+impl<K, V> UseAtomFamily<K, V>
+where
+    K: Hash + Eq + Clone + 'static,
+    V: Clone + 'static,
+{
+    /// Get the value of the member, if there is one
+    pub fn get(&self) -> Option<&V> {
+        self.value.as_ref()
+    }
+
+    /// Set the value of the member
+    pub fn set(&self, value: V) {
+        let members = self.root.read(self.family).update(self.key.clone(), value);
+        self.root.set_key(self.id, self.key_hash, members);
+    }
+
+    /// Remove the member from the family
+    pub fn remove(&self) {
+        let members = self.root.read(self.family).without(&self.key);
+        self.root.set_key(self.id, self.key_hash, members);
+    }
+}
+
+impl<K: 'static, V: 'static> Drop for UseAtomFamily<K, V> {
+    fn drop(&mut self) {
+        self.root
+            .unsubscribe_key(self.id, self.key_hash, self.scope_id)
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{selector_id, use_atom_root, AtomId, AtomRoot, Selector};
+use dioxus_core::{ScopeId, ScopeState};
+use std::rc::Rc;
+
+/// Read the value of a selector, re-rendering the component only when the value changes
+///
+/// ```rust, ignore
+/// static TOTAL: Selector<f64> = |s| s.get(ITEMS).iter().map(|item| item.price).sum();
+///
+/// fn Total(cx: Scope) -> Element {
+///     let total = use_selector(cx, TOTAL);
+///     cx.render(rsx!{ "Total: {total}" })
+/// }
+/// ```
+pub fn use_selector<V: PartialEq + 'static>(cx: &ScopeState, selector: Selector<V>) -> &V {
+    let root = use_atom_root(cx);
+
+    struct UseSelectorInner<V> {
+        root: Rc<AtomRoot>,
+        id: AtomId,
+        scope_id: ScopeId,
+        value: Option<Rc<V>>,
+    }
+
+    impl<V> Drop for UseSelectorInner<V> {
+        fn drop(&mut self) {
+            self.root.unsubscribe(self.id, self.scope_id)
+        }
+    }
+
+    let inner = cx.use_hook(|| UseSelectorInner {
+        value: None,
+        root: root.clone(),
+        scope_id: cx.scope_id(),
+        id: selector_id(selector),
+    });
+
+    let value = inner.root.select(selector);
+    inner.root.subscribe(inner.id, inner.scope_id);
+
+    inner.value = Some(value);
+    inner.value.as_ref().unwrap()
+}
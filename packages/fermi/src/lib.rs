@@ -8,6 +8,7 @@ mod root;
 
 pub use atoms::*;
 pub use hooks::*;
+pub use middleware::*;
 pub use root::*;
 
 mod atoms {
@@ -24,17 +25,31 @@ mod atoms {
     pub use selectorfamily::*;
 }
 
+mod middleware {
+    mod history;
+    #[cfg(feature = "persist")]
+    mod persist;
+
+    pub use history::*;
+    #[cfg(feature = "persist")]
+    pub use persist::*;
+}
+
 pub mod hooks {
     mod atom_ref;
     mod atom_root;
+    mod family;
     mod init_atom_root;
     mod read;
+    mod select;
     mod set;
     mod state;
     pub use atom_ref::*;
     pub use atom_root::*;
+    pub use family::*;
     pub use init_atom_root::*;
     pub use read::*;
+    pub use select::*;
     pub use set::*;
     pub use state::*;
 }
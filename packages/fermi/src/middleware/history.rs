@@ -0,0 +1,237 @@
+use crate::{use_atom_root, AtomId, AtomMiddleware, AtomRoot, Readable};
+use dioxus_core::ScopeState;
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    rc::{Rc, Weak},
+};
+
+/// Record the history of some atoms so it can be undone and redone
+///
+/// The history is created and attached to the atom root the first time the hook runs. `setup` picks the atoms it
+/// tracks. Any component below can get it back with `cx.consume_context::<History>()`.
+///
+/// ```rust, ignore
+/// static SHAPES: Atom<Vec<Shape>> = |_| vec![];
+///
+/// fn Editor(cx: Scope) -> Element {
+///     let history = use_history(cx, |history| history.track(SHAPES));
+///
+///     cx.render(rsx!{
+///         button { onclick: move |_| { history.undo(); }, "Undo" }
+///         button { onclick: move |_| { history.redo(); }, "Redo" }
+///         Canvas { onstroke: move |_| history.checkpoint() }
+///     })
+/// }
+/// ```
+pub fn use_history(cx: &ScopeState, setup: impl FnOnce(History) -> History) -> &History {
+    let root = use_atom_root(cx);
+    cx.use_hook(|| {
+        let history = setup(History::new());
+        history.attach(root);
+        cx.provide_context(history)
+    })
+}
+
+/// Undo and redo the writes to a set of atoms
+///
+/// The history is a list of snapshots of the tracked atoms. [`History::checkpoint`] adds one with their current values,
+/// [`History::undo`] and [`History::redo`] move between them. Writes since the last checkpoint are undone together, so
+/// a drag that sets an atom on every mouse move can be undone in one step by taking a checkpoint when it ends.
+///
+/// Only writes that go through the atom root are recorded, which includes atoms and atom families but not atom refs.
+/// An atom that's first set without ever being read has no value to be undone to, and keeps the one it was set to.
+#[derive(Clone, Default)]
+pub struct History {
+    inner: Rc<HistoryInner>,
+}
+
+#[derive(Default)]
+struct HistoryInner {
+    tracked: RefCell<HashSet<AtomId>>,
+    root: RefCell<Weak<AtomRoot>>,
+    snapshots: RefCell<Vec<Snapshot>>,
+    cursor: Cell<usize>,
+    // whether a tracked atom was written since the snapshot under the cursor
+    dirty: Cell<bool>,
+    restoring: Cell<bool>,
+}
+
+type Snapshot = HashMap<AtomId, Rc<dyn Any>>;
+
+impl History {
+    /// Create a history that doesn't track any atom yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Track the writes to an atom
+    pub fn track<V: 'static>(self, atom: impl Readable<V>) -> Self {
+        self.inner.tracked.borrow_mut().insert(atom.unique_id());
+        self
+    }
+
+    /// Start recording the writes to the atoms of a root
+    pub fn attach(&self, root: &Rc<AtomRoot>) {
+        *self.inner.root.borrow_mut() = Rc::downgrade(root);
+        root.add_middleware(Rc::new(self.clone()));
+    }
+
+    /// Mark the current values of the tracked atoms as a step that can be undone to
+    pub fn checkpoint(&self) {
+        let inner = &self.inner;
+        if !inner.dirty.get() && !inner.snapshots.borrow().is_empty() {
+            return;
+        }
+
+        let snapshot = self.current();
+        let mut snapshots = inner.snapshots.borrow_mut();
+        snapshots.truncate(inner.cursor.get() + 1);
+        snapshots.push(snapshot);
+        inner.cursor.set(snapshots.len() - 1);
+        inner.dirty.set(false);
+    }
+
+    /// Restore the tracked atoms to the checkpoint before the current one, returning whether there was one
+    ///
+    /// Writes since the last checkpoint are checkpointed first, so they can be redone.
+    pub fn undo(&self) -> bool {
+        if self.inner.dirty.get() {
+            self.checkpoint();
+        }
+
+        let cursor = self.inner.cursor.get();
+        if cursor == 0 {
+            return false;
+        }
+
+        self.inner.cursor.set(cursor - 1);
+        self.restore(cursor - 1);
+        true
+    }
+
+    /// Restore the tracked atoms to the checkpoint that was undone last, returning whether there was one
+    ///
+    /// Writing a tracked atom after an undo discards the checkpoints that could be redone.
+    pub fn redo(&self) -> bool {
+        if !self.can_redo() {
+            return false;
+        }
+
+        let cursor = self.inner.cursor.get() + 1;
+        self.inner.cursor.set(cursor);
+        self.restore(cursor);
+        true
+    }
+
+    /// Whether [`History::undo`] would restore anything
+    pub fn can_undo(&self) -> bool {
+        self.inner.dirty.get() || self.inner.cursor.get() > 0
+    }
+
+    /// Whether [`History::redo`] would restore anything
+    pub fn can_redo(&self) -> bool {
+        !self.inner.dirty.get() && self.inner.cursor.get() + 1 < self.inner.snapshots.borrow().len()
+    }
+
+    /// Forget every checkpoint
+    pub fn clear(&self) {
+        self.inner.snapshots.borrow_mut().clear();
+        self.inner.cursor.set(0);
+        self.inner.dirty.set(false);
+    }
+
+    fn current(&self) -> Snapshot {
+        let root = self.inner.root.borrow().upgrade();
+        let tracked = self.inner.tracked.borrow();
+        tracked
+            .iter()
+            .filter_map(|id| Some((*id, root.as_ref()?.value(*id)?)))
+            .collect()
+    }
+
+    fn restore(&self, index: usize) {
+        let root = match self.inner.root.borrow().upgrade() {
+            Some(root) => root,
+            None => return,
+        };
+        let snapshot = self.inner.snapshots.borrow()[index].clone();
+
+        self.inner.restoring.set(true);
+        root.transaction(|| {
+            for (id, value) in snapshot {
+                root.set_any(id, value);
+            }
+        });
+        self.inner.restoring.set(false);
+    }
+}
+
+impl AtomMiddleware for History {
+    fn write(&self, atom: AtomId, old: Option<&Rc<dyn Any>>, _new: &Rc<dyn Any>) {
+        let inner = &self.inner;
+        if inner.restoring.get() || !inner.tracked.borrow().contains(&atom) {
+            return;
+        }
+
+        // The first write is recorded against the values from before it, so it can be undone without a checkpoint
+        if inner.snapshots.borrow().is_empty() {
+            let mut snapshot = self.current();
+            match old {
+                Some(old) => snapshot.insert(atom, old.clone()),
+                None => snapshot.remove(&atom),
+            };
+            inner.snapshots.borrow_mut().push(snapshot);
+            inner.cursor.set(0);
+        }
+
+        inner
+            .snapshots
+            .borrow_mut()
+            .truncate(inner.cursor.get() + 1);
+        inner.dirty.set(true);
+    }
+}
+
+#[test]
+fn undo_and_redo() {
+    use crate::Atom;
+    use std::sync::Arc;
+
+    static COUNT: Atom<i32> = |_| 0;
+    static UNTRACKED: Atom<i32> = |_| 0;
+
+    let root = Rc::new(AtomRoot::new(Arc::new(|_| {})));
+    let history = History::new().track(COUNT);
+    history.attach(&root);
+    let count = || *root.read(COUNT);
+
+    assert_eq!(count(), 0);
+    assert!(!history.can_undo());
+
+    root.set(COUNT.unique_id(), 1);
+    root.set(COUNT.unique_id(), 2);
+    history.checkpoint();
+    root.set(COUNT.unique_id(), 3);
+    root.set(UNTRACKED.unique_id(), 10);
+
+    assert!(history.undo());
+    assert_eq!(count(), 2);
+    assert!(history.undo());
+    assert_eq!(count(), 0);
+    assert!(!history.undo());
+    assert_eq!(*root.read(UNTRACKED), 10);
+
+    assert!(history.redo());
+    assert_eq!(count(), 2);
+    assert!(history.redo());
+    assert_eq!(count(), 3);
+    assert!(!history.redo());
+
+    history.undo();
+    root.set(COUNT.unique_id(), 5);
+    assert!(!history.can_redo());
+    assert!(history.undo());
+    assert_eq!(count(), 2);
+}
@@ -0,0 +1,107 @@
+use crate::{use_atom_root, AtomId, AtomMiddleware, AtomRoot, Readable};
+use dioxus_core::ScopeState;
+use dioxus_hooks::{PersistentBackend, PersistentStorage};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{any::Any, collections::HashMap, rc::Rc};
+
+/// Save some atoms to the storage of the platform, and restore them when the app starts again
+///
+/// The atoms are stored with the [`PersistentBackend`] the renderer provides: `localStorage` on web, and a file in the
+/// config directory of the user on desktop. Without one, nothing is stored. An atom is only restored if it hasn't been
+/// read yet, so the hook should run right after `use_init_atom_root` in the root component.
+///
+/// ```rust, ignore
+/// static SETTINGS: Atom<Settings> = |_| Settings::default();
+///
+/// fn App(cx: Scope) -> Element {
+///     use_init_atom_root(cx);
+///     use_persist_atoms(cx, |persist| persist.atom(SETTINGS, "settings"));
+///     // ...
+/// }
+/// ```
+pub fn use_persist_atoms(cx: &ScopeState, setup: impl FnOnce(Persist) -> Persist) {
+    let root = use_atom_root(cx);
+    cx.use_hook(|| match cx.consume_context::<PersistentBackend>() {
+        Some(backend) => setup(Persist::new(backend)).attach(root),
+        None => log::warn!("No persistent storage was provided, so atoms won't be persisted"),
+    });
+}
+
+/// A middleware that stores atoms as json every time they're set
+pub struct Persist {
+    storage: Box<dyn PersistentStorage>,
+    atoms: HashMap<AtomId, PersistedAtom>,
+}
+
+struct PersistedAtom {
+    key: String,
+    load: fn(&str) -> Option<Rc<dyn Any>>,
+    save: fn(&dyn Any) -> Option<String>,
+}
+
+impl Persist {
+    /// Create a middleware that doesn't persist any atom yet
+    pub fn new(storage: impl PersistentStorage + 'static) -> Self {
+        Self {
+            storage: Box::new(storage),
+            atoms: HashMap::new(),
+        }
+    }
+
+    /// Persist an atom under `key`
+    ///
+    /// If the stored value can't be deserialized anymore, the atom starts from its initializer.
+    pub fn atom<V: Serialize + DeserializeOwned + 'static>(
+        mut self,
+        atom: impl Readable<V>,
+        key: impl Into<String>,
+    ) -> Self {
+        fn load<V: DeserializeOwned + 'static>(stored: &str) -> Option<Rc<dyn Any>> {
+            match serde_json::from_str::<V>(stored) {
+                Ok(value) => Some(Rc::new(value) as Rc<dyn Any>),
+                Err(err) => {
+                    log::warn!("Ignoring the stored value of an atom: {}", err);
+                    None
+                }
+            }
+        }
+
+        fn save<V: Serialize + 'static>(value: &dyn Any) -> Option<String> {
+            let value = value.downcast_ref::<V>()?;
+            serde_json::to_string(value)
+                .map_err(|err| log::error!("Failed to serialize an atom: {}", err))
+                .ok()
+        }
+
+        self.atoms.insert(
+            atom.unique_id(),
+            PersistedAtom {
+                key: key.into(),
+                load: load::<V>,
+                save: save::<V>,
+            },
+        );
+        self
+    }
+
+    /// Start restoring and saving the atoms of a root
+    pub fn attach(self, root: &AtomRoot) {
+        root.add_middleware(Rc::new(self));
+    }
+}
+
+impl AtomMiddleware for Persist {
+    fn init(&self, atom: AtomId) -> Option<Rc<dyn Any>> {
+        let persisted = self.atoms.get(&atom)?;
+        let stored = self.storage.load(&persisted.key)?;
+        (persisted.load)(&stored)
+    }
+
+    fn write(&self, atom: AtomId, _old: Option<&Rc<dyn Any>>, new: &Rc<dyn Any>) {
+        if let Some(persisted) = self.atoms.get(&atom) {
+            if let Some(value) = (persisted.save)(&**new) {
+                self.storage.store(&persisted.key, &value);
+            }
+        }
+    }
+}
@@ -9,7 +9,7 @@ use std::{
 use dioxus_core::ScopeId;
 use im_rc::HashSet;
 
-use crate::Readable;
+use crate::{Readable, Selector, SelectorBuilder};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct AtomId {
@@ -20,85 +20,172 @@ pub struct AtomId {
 pub struct AtomRoot {
     pub atoms: RefCell<HashMap<AtomId, Slot>>,
     pub update_any: Arc<dyn Fn(ScopeId)>,
+    selectors: RefCell<HashMap<AtomId, SelectorSlot>>,
+    transaction: RefCell<Option<Vec<Change>>>,
+    middleware: RefCell<Vec<Rc<dyn AtomMiddleware>>>,
+}
+
+/// Runs alongside the writes to the atoms of an [`AtomRoot`], like [`crate::History`] and `Persist`
+pub trait AtomMiddleware {
+    /// Provide the value an atom starts with, instead of its initializer
+    ///
+    /// The value must have the type of the atom.
+    fn init(&self, _atom: AtomId) -> Option<Rc<dyn Any>> {
+        None
+    }
+
+    /// Called after an atom was set, with its old value if it had been initialized
+    fn write(&self, _atom: AtomId, _old: Option<&Rc<dyn Any>>, _new: &Rc<dyn Any>) {}
 }
 
 pub struct Slot {
     pub value: Rc<dyn Any>,
     pub subscribers: HashSet<ScopeId>,
+    /// Scopes that only read one key of an atom family, by the hash of the key
+    pub keyed_subscribers: HashMap<u64, HashSet<ScopeId>>,
+    /// Selectors that read this atom, and need to be computed again when it changes
+    pub dependents: HashSet<AtomId>,
+}
+
+impl Slot {
+    fn new(value: Rc<dyn Any>) -> Self {
+        Self {
+            value,
+            subscribers: HashSet::new(),
+            keyed_subscribers: HashMap::new(),
+            dependents: HashSet::new(),
+        }
+    }
+}
+
+struct SelectorSlot {
+    compute: Rc<dyn Fn(&SelectorBuilder) -> Rc<dyn Any>>,
+    eq: fn(&dyn Any, &dyn Any) -> bool,
+    dependencies: HashSet<AtomId>,
 }
 
+/// An atom that was written, along with the key of the family member that changed, if only one did
+type Change = (AtomId, Option<u64>);
+
 impl AtomRoot {
     pub fn new(update_any: Arc<dyn Fn(ScopeId)>) -> Self {
         Self {
             update_any,
             atoms: RefCell::new(HashMap::new()),
+            selectors: RefCell::new(HashMap::new()),
+            transaction: RefCell::new(None),
+            middleware: RefCell::new(Vec::new()),
         }
     }
 
+    /// Run a middleware alongside every atom that's initialized or set from now on
+    pub fn add_middleware(&self, middleware: Rc<dyn AtomMiddleware>) {
+        self.middleware.borrow_mut().push(middleware);
+    }
+
     pub fn initialize<V: 'static>(&self, f: impl Readable<V>) {
         let id = f.unique_id();
         if self.atoms.borrow().get(&id).is_none() {
-            self.atoms.borrow_mut().insert(
-                id,
-                Slot {
-                    value: Rc::new(f.init()),
-                    subscribers: HashSet::new(),
-                },
-            );
+            let value = self.initial_value(&f);
+            self.atoms.borrow_mut().insert(id, Slot::new(value));
         }
     }
 
+    /// Get the value an atom starts with, from the first middleware that has one or from its initializer
+    fn initial_value<V: 'static>(&self, f: &impl Readable<V>) -> Rc<dyn Any> {
+        let id = f.unique_id();
+        let middleware = self.middleware.borrow().clone();
+        middleware
+            .iter()
+            .find_map(|middleware| middleware.init(id))
+            .unwrap_or_else(|| Rc::new(f.init()))
+    }
+
     pub fn register<V: 'static>(&self, f: impl Readable<V>, scope: ScopeId) -> Rc<V> {
-        let mut atoms = self.atoms.borrow_mut();
+        self.register_with(f, |slot| {
+            slot.subscribers.insert(scope);
+        })
+    }
+
+    /// Subscribe a scope to a single key of an atom family
+    pub fn register_key<V: 'static>(&self, f: impl Readable<V>, key: u64, scope: ScopeId) -> Rc<V> {
+        self.register_with(f, |slot| {
+            slot.keyed_subscribers.entry(key).or_default().insert(scope);
+        })
+    }
 
+    fn register_with<V: 'static>(
+        &self,
+        f: impl Readable<V>,
+        subscribe: impl FnOnce(&mut Slot),
+    ) -> Rc<V> {
         // initialize the value if it's not already initialized
-        if let Some(slot) = atoms.get_mut(&f.unique_id()) {
-            slot.subscribers.insert(scope);
-            match slot.value.clone().downcast() {
-                Ok(res) => res,
-                Err(e) => panic!(
-                    "Downcasting atom failed: {:?}. Has typeid of {:?} but needs typeid of {:?}",
-                    f.unique_id(),
-                    e.type_id(),
-                    TypeId::of::<V>()
-                ),
-            }
-        } else {
-            let value = Rc::new(f.init());
-            let mut subscribers = HashSet::new();
-            subscribers.insert(scope);
+        self.initialize_with(&f);
 
-            atoms.insert(
+        let mut atoms = self.atoms.borrow_mut();
+        let slot = atoms.get_mut(&f.unique_id()).unwrap();
+        subscribe(slot);
+
+        match slot.value.clone().downcast() {
+            Ok(res) => res,
+            Err(e) => panic!(
+                "Downcasting atom failed: {:?}. Has typeid of {:?} but needs typeid of {:?}",
                 f.unique_id(),
-                Slot {
-                    value: value.clone(),
-                    subscribers,
-                },
-            );
-            value
+                e.type_id(),
+                TypeId::of::<V>()
+            ),
+        }
+    }
+
+    fn initialize_with<V: 'static>(&self, f: &impl Readable<V>) {
+        if !self.atoms.borrow().contains_key(&f.unique_id()) {
+            let value = self.initial_value(f);
+            self.atoms
+                .borrow_mut()
+                .insert(f.unique_id(), Slot::new(value));
         }
     }
 
     pub fn set<V: 'static>(&self, ptr: AtomId, value: V) {
+        self.write(ptr, Rc::new(value), None);
+    }
+
+    /// Set the value of an atom family where only the member with the given key changed
+    pub fn set_key<V: 'static>(&self, ptr: AtomId, key: u64, value: V) {
+        self.write(ptr, Rc::new(value), Some(key));
+    }
+
+    /// Set an atom to a value that's already type erased, like one that was saved by a middleware
+    pub(crate) fn set_any(&self, ptr: AtomId, value: Rc<dyn Any>) {
+        self.write(ptr, value, None);
+    }
+
+    /// Get the type erased value of an atom, if it was initialized
+    pub(crate) fn value(&self, ptr: AtomId) -> Option<Rc<dyn Any>> {
+        self.atoms.borrow().get(&ptr).map(|slot| slot.value.clone())
+    }
+
+    fn write(&self, ptr: AtomId, value: Rc<dyn Any>, key: Option<u64>) {
+        let old = self.store(ptr, value.clone());
+
+        let middleware = self.middleware.borrow().clone();
+        for middleware in middleware.iter() {
+            middleware.write(ptr, old.as_ref(), &value);
+        }
+
+        self.changed(ptr, key);
+    }
+
+    /// Replace the value of an atom, returning the old one
+    fn store(&self, ptr: AtomId, value: Rc<dyn Any>) -> Option<Rc<dyn Any>> {
         let mut atoms = self.atoms.borrow_mut();
 
         if let Some(slot) = atoms.get_mut(&ptr) {
-            slot.value = Rc::new(value);
-            log::trace!("found item with subscribers {:?}", slot.subscribers);
-
-            for scope in &slot.subscribers {
-                log::trace!("updating subcsriber");
-                (self.update_any)(*scope);
-            }
+            Some(std::mem::replace(&mut slot.value, value))
         } else {
             log::trace!("no atoms found for {:?}", ptr);
-            atoms.insert(
-                ptr,
-                Slot {
-                    value: Rc::new(value),
-                    subscribers: HashSet::new(),
-                },
-            );
+            atoms.insert(ptr, Slot::new(value));
+            None
         }
     }
 
@@ -110,32 +197,212 @@ impl AtomRoot {
         }
     }
 
+    pub fn unsubscribe_key(&self, ptr: AtomId, key: u64, scope: ScopeId) {
+        let mut atoms = self.atoms.borrow_mut();
+
+        if let Some(keyed) = atoms
+            .get_mut(&ptr)
+            .and_then(|slot| slot.keyed_subscribers.get_mut(&key))
+        {
+            keyed.remove(&scope);
+        }
+    }
+
     // force update of all subscribers
     pub fn force_update(&self, ptr: AtomId) {
-        if let Some(slot) = self.atoms.borrow_mut().get(&ptr) {
-            for scope in slot.subscribers.iter() {
-                log::trace!("updating subcsriber");
-                (self.update_any)(*scope);
-            }
-        }
+        self.changed(ptr, None);
     }
 
     pub fn read<V: 'static>(&self, f: impl Readable<V>) -> Rc<V> {
-        let mut atoms = self.atoms.borrow_mut();
-
         // initialize the value if it's not already initialized
-        if let Some(slot) = atoms.get_mut(&f.unique_id()) {
-            slot.value.clone().downcast().unwrap()
-        } else {
-            let value = Rc::new(f.init());
-            atoms.insert(
-                f.unique_id(),
-                Slot {
-                    value: value.clone(),
-                    subscribers: HashSet::new(),
+        self.initialize_with(&f);
+
+        self.atoms.borrow()[&f.unique_id()]
+            .value
+            .clone()
+            .downcast()
+            .unwrap()
+    }
+
+    /// Get the value of a selector, computing it the first time it's read
+    pub fn select<V: PartialEq + 'static>(&self, selector: Selector<V>) -> Rc<V> {
+        let id = crate::selector_id(selector);
+
+        if !self.selectors.borrow().contains_key(&id) {
+            self.selectors.borrow_mut().insert(
+                id,
+                SelectorSlot {
+                    compute: Rc::new(move |builder| Rc::new(selector(builder)) as Rc<dyn Any>),
+                    eq: |a, b| a.downcast_ref::<V>() == b.downcast_ref::<V>(),
+                    dependencies: HashSet::new(),
                 },
             );
-            value
+            let value = self.evaluate(id);
+            self.store(id, value);
+        }
+
+        self.atoms.borrow()[&id].value.clone().downcast().unwrap()
+    }
+
+    /// Subscribe a scope to a selector that was read with [`AtomRoot::select`]
+    pub fn subscribe(&self, ptr: AtomId, scope: ScopeId) {
+        if let Some(slot) = self.atoms.borrow_mut().get_mut(&ptr) {
+            slot.subscribers.insert(scope);
+        }
+    }
+
+    /// Run `f`, holding off every update until it's done
+    ///
+    /// Every atom written in the transaction is flushed at once: selectors are computed once, and every scope that
+    /// reads any of the atoms is re-rendered once. Transactions can be nested, in which case the outermost one flushes.
+    pub fn transaction<O>(&self, f: impl FnOnce() -> O) -> O {
+        if self.transaction.borrow().is_some() {
+            return f();
+        }
+
+        *self.transaction.borrow_mut() = Some(Vec::new());
+        let out = f();
+        let changes = self.transaction.borrow_mut().take().unwrap_or_default();
+        self.flush(changes);
+        out
+    }
+
+    fn changed(&self, ptr: AtomId, key: Option<u64>) {
+        if let Some(changes) = self.transaction.borrow_mut().as_mut() {
+            changes.push((ptr, key));
+            return;
+        }
+
+        self.flush(vec![(ptr, key)]);
+    }
+
+    /// Compute the selectors that depend on the changed atoms, and update every scope that reads something that changed
+    fn flush(&self, mut changes: Vec<Change>) {
+        let mut scopes = std::collections::HashSet::new();
+
+        while let Some((ptr, key)) = changes.pop() {
+            let dependents = {
+                let atoms = self.atoms.borrow();
+                let slot = match atoms.get(&ptr) {
+                    Some(slot) => slot,
+                    None => continue,
+                };
+                log::trace!("found item with subscribers {:?}", slot.subscribers);
+
+                scopes.extend(slot.subscribers.iter().copied());
+                match key {
+                    Some(key) => {
+                        if let Some(keyed) = slot.keyed_subscribers.get(&key) {
+                            scopes.extend(keyed.iter().copied());
+                        }
+                    }
+                    None => {
+                        for keyed in slot.keyed_subscribers.values() {
+                            scopes.extend(keyed.iter().copied());
+                        }
+                    }
+                }
+
+                slot.dependents.iter().copied().collect::<Vec<_>>()
+            };
+
+            // Selectors whose value didn't change don't update their readers
+            for selector in dependents {
+                if self.recompute(selector) {
+                    changes.push((selector, None));
+                }
+            }
+        }
+
+        for scope in scopes {
+            log::trace!("updating subcsriber");
+            (self.update_any)(scope);
+        }
+    }
+
+    /// Compute a selector and track the atoms it read
+    fn evaluate(&self, id: AtomId) -> Rc<dyn Any> {
+        let compute = self.selectors.borrow()[&id].compute.clone();
+        let builder = SelectorBuilder::new(self);
+        let value = compute(&builder);
+        let dependencies = builder.into_dependencies();
+
+        let old = std::mem::replace(
+            &mut self
+                .selectors
+                .borrow_mut()
+                .get_mut(&id)
+                .unwrap()
+                .dependencies,
+            dependencies.clone(),
+        );
+
+        let mut atoms = self.atoms.borrow_mut();
+        for dependency in old.iter().filter(|dep| !dependencies.contains(*dep)) {
+            if let Some(slot) = atoms.get_mut(dependency) {
+                slot.dependents.remove(&id);
+            }
         }
+        for dependency in dependencies.iter() {
+            if let Some(slot) = atoms.get_mut(dependency) {
+                slot.dependents.insert(id);
+            }
+        }
+
+        value
+    }
+
+    /// Compute a selector again, returning whether its value changed
+    fn recompute(&self, id: AtomId) -> bool {
+        let value = self.evaluate(id);
+        let eq = self.selectors.borrow()[&id].eq;
+        let unchanged = self
+            .atoms
+            .borrow()
+            .get(&id)
+            .map_or(false, |slot| eq(&*slot.value, &*value));
+
+        if !unchanged {
+            self.store(id, value);
+        }
+        !unchanged
     }
 }
+
+#[test]
+fn selectors_and_transactions() {
+    use crate::Atom;
+    use std::sync::Mutex;
+
+    static FIRST: Atom<i32> = |_| 1;
+    static SECOND: Atom<i32> = |_| 2;
+    static SUM: Selector<i32> = |s| *s.get(FIRST) + *s.get(SECOND);
+    static IS_POSITIVE: Selector<bool> = |s| *s.select(SUM) > 0;
+
+    let updated = Arc::new(Mutex::new(Vec::new()));
+    let root = AtomRoot::new({
+        let updated = updated.clone();
+        Arc::new(move |scope| updated.lock().unwrap().push(scope))
+    });
+    let take = || std::mem::take(&mut *updated.lock().unwrap());
+
+    assert!(*root.select(IS_POSITIVE));
+    root.subscribe(crate::selector_id(SUM), ScopeId(1));
+    root.subscribe(crate::selector_id(IS_POSITIVE), ScopeId(2));
+    root.register(FIRST, ScopeId(3));
+
+    // Only the readers of what changed are updated
+    root.set(FIRST.unique_id(), 5);
+    assert_eq!(*root.select(SUM), 7);
+    let mut scopes = take();
+    scopes.sort_by_key(|s| s.0);
+    assert_eq!(scopes, [ScopeId(1), ScopeId(3)]);
+
+    // Both writes are flushed together, and the sum ends up where it started
+    root.transaction(|| {
+        root.set(FIRST.unique_id(), 1);
+        root.set(SECOND.unique_id(), 6);
+    });
+    assert_eq!(*root.select(SUM), 7);
+    assert_eq!(take(), [ScopeId(3)]);
+}
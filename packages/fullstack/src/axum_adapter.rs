@@ -0,0 +1,83 @@
+use crate::server_fns;
+use axum::extract::Path;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use std::collections::HashSet;
+
+/// Serve the server functions of the app from an axum router
+///
+/// ```rust, ignore
+/// let app = axum::Router::new()
+///     .register_server_fns()
+///     .route("/", get(index));
+/// ```
+pub trait DioxusRouterExt {
+    /// Add a route for every server function, under [`SERVER_FN_PREFIX`](crate::SERVER_FN_PREFIX)
+    ///
+    /// Panics if two server functions have the same name, since the client couldn't tell them apart.
+    fn register_server_fns(self) -> Self;
+
+    /// Serve the files the app included with `asset!` under [`ASSET_PREFIX`](dioxus_assets::ASSET_PREFIX)
+    ///
+    /// Their names change with their contents, so browsers are told to cache them forever.
+    fn serve_dioxus_assets(self) -> Self;
+}
+
+impl<S> DioxusRouterExt for axum::Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn register_server_fns(self) -> Self {
+        let mut endpoints = HashSet::new();
+        let mut router = self;
+
+        for entry in server_fns() {
+            if !endpoints.insert(entry.endpoint()) {
+                panic!(
+                    "there are two server functions named {}, so one of them needs another name, like #[server(\"{}_2\")]",
+                    entry.endpoint(),
+                    entry.endpoint()
+                );
+            }
+
+            let entry = *entry;
+            router = router.route(
+                &entry.path(),
+                post(move |body: String| async move {
+                    let response = entry.call(body).await;
+                    let status = StatusCode::from_u16(response.status)
+                        .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+                    (
+                        status,
+                        [(header::CONTENT_TYPE, "application/json")],
+                        response.body,
+                    )
+                }),
+            );
+        }
+
+        router
+    }
+
+    fn serve_dioxus_assets(self) -> Self {
+        self.route(
+            &format!("{}/:name", dioxus_assets::ASSET_PREFIX),
+            get(|Path(name): Path<String>| async move { serve_asset(&name) }),
+        )
+    }
+}
+
+fn serve_asset(name: &str) -> Response {
+    match dioxus_assets::find_asset(name) {
+        Some(asset) => (
+            [
+                (header::CONTENT_TYPE, asset.mime()),
+                (header::CACHE_CONTROL, "public, max-age=31536000, immutable"),
+            ],
+            asset.bytes(),
+        )
+            .into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
@@ -0,0 +1,22 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+
+mod server_fn;
+mod server_state;
+
+#[cfg(feature = "ssr")]
+mod axum_adapter;
+
+#[cfg(feature = "ssr")]
+pub use axum_adapter::*;
+pub use dioxus_server_macro::server;
+pub use server_fn::*;
+pub use server_state::*;
+
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "ssr")]
+    pub use inventory;
+    pub use serde;
+    pub use serde_json;
+}
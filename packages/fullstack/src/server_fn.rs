@@ -0,0 +1,185 @@
+use once_cell::sync::OnceCell;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+
+/// The path the endpoints of the server functions are under, like `/api/get_user`
+pub const SERVER_FN_PREFIX: &str = "/api";
+
+/// Why a server function didn't return, or the error it returned if it only returns these
+///
+/// Server functions can return errors of their own, as long as they convert from this, since calling one can fail
+/// without the server ever hearing about it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, thiserror::Error)]
+pub enum ServerFnError {
+    /// The server couldn't be reached, or it went away before it responded
+    #[error("the server couldn't be reached: {0}")]
+    Request(String),
+
+    /// The arguments couldn't be sent, or the server couldn't read them
+    #[error("the arguments of the server function didn't make it: {0}")]
+    Arguments(String),
+
+    /// The server responded with something that isn't what the server function returns
+    #[error("the response of the server function couldn't be read: {0}")]
+    Response(String),
+
+    /// The server function ran, and failed
+    #[error("the server function failed: {0}")]
+    ServerError(String),
+}
+
+impl ServerFnError {
+    /// The server function failed with this error, which is sent to the client as its message
+    pub fn server(err: impl Display) -> Self {
+        ServerFnError::ServerError(err.to_string())
+    }
+}
+
+/// What the endpoint of a server function responds with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerFnResponse {
+    /// The HTTP status of the response
+    pub status: u16,
+
+    /// The json of the `Result` the server function returned, or why the arguments couldn't be read
+    pub body: String,
+}
+
+impl ServerFnResponse {
+    /// Respond with what the server function returned
+    pub fn from_result<T: Serialize, E: Serialize>(result: &Result<T, E>) -> Self {
+        let status = match result {
+            Ok(_) => 200,
+            Err(_) => 500,
+        };
+        match serde_json::to_string(result) {
+            Ok(body) => Self { status, body },
+            Err(err) => Self {
+                status: 500,
+                body: format!("the result can't be serialized: {}", err),
+            },
+        }
+    }
+
+    /// Respond that the arguments of the request aren't the arguments of the server function
+    pub fn bad_arguments(err: impl Display) -> Self {
+        Self {
+            status: 400,
+            body: err.to_string(),
+        }
+    }
+}
+
+/// The future the handler of a server function returns
+pub type ServerFnFuture = Pin<Box<dyn Future<Output = ServerFnResponse> + Send>>;
+
+/// A server function, with the endpoint it's served at and the handler that runs it with a request
+///
+/// `#[server]` submits one for every server function it expands on the server.
+#[derive(Debug, Clone, Copy)]
+pub struct ServerFnEntry {
+    endpoint: &'static str,
+    handler: fn(String) -> ServerFnFuture,
+}
+
+impl ServerFnEntry {
+    #[doc(hidden)]
+    pub const fn new(endpoint: &'static str, handler: fn(String) -> ServerFnFuture) -> Self {
+        Self { endpoint, handler }
+    }
+
+    /// The name of the endpoint, which is the name of the function unless it was given another one
+    pub fn endpoint(&self) -> &'static str {
+        self.endpoint
+    }
+
+    /// The path the endpoint is served at
+    pub fn path(&self) -> String {
+        format!("{}/{}", SERVER_FN_PREFIX, self.endpoint)
+    }
+
+    /// Run the server function with the json of its arguments
+    pub fn call(&self, body: String) -> ServerFnFuture {
+        (self.handler)(body)
+    }
+}
+
+#[cfg(feature = "ssr")]
+inventory::collect!(ServerFnEntry);
+
+/// All the server functions of the app, from every crate it's built from
+#[cfg(feature = "ssr")]
+pub fn server_fns() -> impl Iterator<Item = &'static ServerFnEntry> {
+    inventory::iter::<ServerFnEntry>.into_iter()
+}
+
+static SERVER_URL: OnceCell<String> = OnceCell::new();
+
+/// Set the url of the server the client calls the server functions of, like `http://localhost:8080`
+///
+/// In the browser the server functions are called on the server the page came from, unless this is set. Everywhere
+/// else, like on desktop, it has to be set before a server function is called. It can only be set once.
+pub fn set_server_url(url: impl Into<String>) {
+    let url = url.into();
+    let url = url.trim_end_matches('/').to_string();
+    if SERVER_URL.set(url).is_err() {
+        panic!("the url of the server can only be set once");
+    }
+}
+
+fn server_url() -> Option<String> {
+    if let Some(url) = SERVER_URL.get() {
+        return Some(url.clone());
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    return web_sys::window().and_then(|window| window.location().origin().ok());
+
+    #[cfg(not(target_arch = "wasm32"))]
+    return None;
+}
+
+/// Call a server function on the server, which is what the client side of `#[server]` does
+#[doc(hidden)]
+pub async fn call_server_fn<A, T, E>(endpoint: &str, args: &A) -> Result<T, E>
+where
+    A: Serialize,
+    T: DeserializeOwned,
+    E: DeserializeOwned + From<ServerFnError>,
+{
+    let url = match server_url() {
+        Some(url) => format!("{}{}/{}", url, SERVER_FN_PREFIX, endpoint),
+        None => {
+            return Err(ServerFnError::Request(
+                "the url of the server wasn't set with set_server_url".to_string(),
+            )
+            .into())
+        }
+    };
+    let body = serde_json::to_string(args)
+        .map_err(|err| E::from(ServerFnError::Arguments(err.to_string())))?;
+
+    let response = reqwest::Client::new()
+        .post(url)
+        .header("content-type", "application/json")
+        .body(body)
+        .send()
+        .await
+        .map_err(|err| E::from(ServerFnError::Request(err.to_string())))?;
+    let status = response.status();
+    let text = response
+        .text()
+        .await
+        .map_err(|err| E::from(ServerFnError::Request(err.to_string())))?;
+
+    if status == reqwest::StatusCode::BAD_REQUEST {
+        return Err(ServerFnError::Arguments(text).into());
+    }
+    match serde_json::from_str::<Result<T, E>>(&text) {
+        Ok(result) => result,
+        Err(err) => Err(ServerFnError::Response(format!("{} ({})", err, status)).into()),
+    }
+}
@@ -0,0 +1,222 @@
+use dioxus_core::ScopeState;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::cell::{Ref, RefCell};
+use std::collections::HashMap;
+use std::future::Future;
+use std::panic::Location;
+use std::rc::Rc;
+
+/// The id of the script element the values of [`use_server_future`] are embedded in
+pub const SERVER_STATE_ID: &str = "dioxus-server-state";
+
+/// The values the [`use_server_future`]s of a page resolved to on the server, which the client hydrates them with
+///
+/// On the server it records the values, and on the client the hooks take them back out, so the page doesn't fetch what
+/// it was rendered with a second time. The values are found by where the hook is called and how many times it was
+/// called there before, so the client has to render the same components the server did.
+#[derive(Clone, Default)]
+pub struct ServerState(Rc<RefCell<StateInner>>);
+
+#[derive(Default)]
+struct StateInner {
+    values: HashMap<String, serde_json::Value>,
+    calls: HashMap<&'static Location<'static>, usize>,
+    // the futures that didn't resolve yet, while the server renders
+    pending: usize,
+    recording: bool,
+}
+
+impl ServerState {
+    /// An empty state that records the values of the futures, for the server
+    pub fn new() -> Self {
+        Self(Rc::new(RefCell::new(StateInner {
+            recording: true,
+            ..Default::default()
+        })))
+    }
+
+    /// The state the server serialized with [`to_json`](ServerState::to_json), for the client
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        Ok(Self(Rc::new(RefCell::new(StateInner {
+            values: serde_json::from_str(json)?,
+            ..Default::default()
+        }))))
+    }
+
+    /// Serialize the values the futures resolved to so far
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.0.borrow().values).unwrap_or_default()
+    }
+
+    /// The values in a script element for the page, which the client reads them back from
+    pub fn to_script(&self) -> String {
+        // the json can't end the script early, and `<\/` is still `</` to a json parser
+        format!(
+            "<script type=\"application/json\" id=\"{}\">{}</script>",
+            SERVER_STATE_ID,
+            self.to_json().replace("</", "<\\/")
+        )
+    }
+
+    /// Whether some futures still didn't resolve
+    pub fn is_pending(&self) -> bool {
+        self.0.borrow().pending > 0
+    }
+
+    fn key(&self, location: &'static Location<'static>) -> String {
+        let mut inner = self.0.borrow_mut();
+        let calls = inner.calls.entry(location).or_default();
+        let key = format!(
+            "{}:{}:{}#{}",
+            location.file(),
+            location.line(),
+            location.column(),
+            calls
+        );
+        *calls += 1;
+        key
+    }
+
+    fn take<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let value = self.0.borrow_mut().values.remove(key)?;
+        serde_json::from_value(value).ok()
+    }
+
+    fn record<T: Serialize>(&self, key: String, value: &T) {
+        let mut inner = self.0.borrow_mut();
+        if inner.recording {
+            if let Ok(value) = serde_json::to_value(value) {
+                inner.values.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Keeps the server waiting for a future until it resolves or goes away with its component
+struct Pending(ServerState);
+
+impl Pending {
+    fn new(state: ServerState) -> Self {
+        state.0.borrow_mut().pending += 1;
+        Self(state)
+    }
+}
+
+impl Drop for Pending {
+    fn drop(&mut self) {
+        let Pending(state) = self;
+        state.0.borrow_mut().pending -= 1;
+    }
+}
+
+/// The state of the page the client is hydrating, which it reads once
+#[cfg(target_arch = "wasm32")]
+fn page_state() -> Option<ServerState> {
+    thread_local! {
+        static PAGE: Option<ServerState> = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.get_element_by_id(SERVER_STATE_ID))
+            .and_then(|script| script.text_content())
+            .and_then(|json| ServerState::from_json(&json).ok());
+    }
+    PAGE.with(|state| state.clone())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn page_state() -> Option<ServerState> {
+    None
+}
+
+/// Run a future on the server while the page renders, and hydrate the client with what it resolved to
+///
+/// On the server, [`pre_render_with_state`] waits for the future before the page is rendered, and embeds its value in
+/// the page. When the client hydrates the page, the hook starts out with that value instead of running the future, so
+/// the page isn't fetched twice. Anywhere else, it's a future that runs once, like `use_future`.
+///
+/// ```rust, ignore
+/// fn Profile(cx: Scope<ProfileProps>) -> Element {
+///     let id = cx.props.id;
+///     let user = use_server_future(cx, || get_user(id));
+///     match user.value().as_deref() {
+///         Some(Ok(user)) => render! { h1 { "{user.name}" } },
+///         Some(Err(err)) => render! { "couldn't load the user: {err}" },
+///         None => render! { "loading..." },
+///     }
+/// }
+/// ```
+#[track_caller]
+pub fn use_server_future<T, F>(cx: &ScopeState, future: impl FnOnce() -> F) -> &UseServerFuture<T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+    F: Future<Output = T> + 'static,
+{
+    let location = Location::caller();
+
+    cx.use_hook(|| {
+        let state = cx.consume_context::<ServerState>().or_else(page_state);
+        let key = state.as_ref().map(|state| state.key(location));
+        let value = Rc::new(RefCell::new(None));
+
+        let hydrated = match (&state, &key) {
+            (Some(state), Some(key)) => state.take::<T>(key),
+            _ => None,
+        };
+        if let Some(hydrated) = hydrated {
+            *value.borrow_mut() = Some(hydrated);
+            return UseServerFuture { value };
+        }
+
+        let future = future();
+        let pending = state.clone().map(Pending::new);
+        let update = cx.schedule_update();
+        let resolved = value.clone();
+        cx.push_future(async move {
+            let output = future.await;
+            if let (Some(state), Some(key)) = (state, key) {
+                state.record(key, &output);
+            }
+            *resolved.borrow_mut() = Some(output);
+            drop(pending);
+            update();
+        });
+
+        UseServerFuture { value }
+    })
+}
+
+/// A handle to a [`use_server_future`]
+pub struct UseServerFuture<T> {
+    value: Rc<RefCell<Option<T>>>,
+}
+
+impl<T> UseServerFuture<T> {
+    /// What the future resolved to, if it did
+    pub fn value(&self) -> Option<Ref<'_, T>> {
+        Ref::filter_map(self.value.borrow(), |value| value.as_ref()).ok()
+    }
+}
+
+/// Rebuild a VirtualDom for a page, and pre-render it once its [`use_server_future`]s resolved
+///
+/// Returns the html of the app, and the state to embed in the page with [`ServerState::to_script`], outside of the
+/// element the app hydrates.
+///
+/// ```rust, ignore
+/// let mut dom = VirtualDom::new(app);
+/// let (html, state) = pre_render_with_state(&mut dom).await;
+/// let page = format!("<div id=\"main\">{}</div>{}", html, state.to_script());
+/// ```
+#[cfg(feature = "ssr")]
+pub async fn pre_render_with_state(dom: &mut dioxus_core::VirtualDom) -> (String, ServerState) {
+    let state = ServerState::new();
+    dom.base_scope().provide_context(state.clone());
+
+    _ = dom.rebuild();
+    while state.is_pending() {
+        dom.wait_for_work().await;
+        _ = dom.render_immediate();
+    }
+
+    (dioxus_ssr::pre_render(dom), state)
+}
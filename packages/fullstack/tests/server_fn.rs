@@ -0,0 +1,47 @@
+use dioxus_fullstack::*;
+
+#[server]
+async fn add(a: i32, b: i32) -> Result<i32, ServerFnError> {
+    a.checked_add(b)
+        .ok_or_else(|| ServerFnError::server("the sum overflows"))
+}
+
+#[server("ping")]
+async fn health() -> Result<String, ServerFnError> {
+    Ok("pong".to_string())
+}
+
+#[cfg(feature = "ssr")]
+#[tokio::test]
+async fn serves_the_server_functions() {
+    assert_eq!(add(1, 2).await, Ok(3));
+
+    let endpoint = |name: &str| *server_fns().find(|entry| entry.endpoint() == name).unwrap();
+    assert_eq!(endpoint("ping").path(), "/api/ping");
+
+    let response = endpoint("add").call(r#"{"a":1,"b":2}"#.to_string()).await;
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body, r#"{"Ok":3}"#);
+
+    let response = endpoint("add")
+        .call(format!(r#"{{"a":{},"b":1}}"#, i32::MAX))
+        .await;
+    assert_eq!(response.status, 500);
+    assert_eq!(
+        response.body,
+        r#"{"Err":{"ServerError":"the sum overflows"}}"#
+    );
+
+    let response = endpoint("add").call(r#"{"a":1}"#.to_string()).await;
+    assert_eq!(response.status, 400);
+
+    let response = endpoint("ping").call("{}".to_string()).await;
+    assert_eq!(response.body, r#"{"Ok":"pong"}"#);
+}
+
+#[cfg(not(feature = "ssr"))]
+#[tokio::test]
+async fn the_client_needs_a_server() {
+    assert!(matches!(add(1, 2).await, Err(ServerFnError::Request(_))));
+    assert!(matches!(health().await, Err(ServerFnError::Request(_))));
+}
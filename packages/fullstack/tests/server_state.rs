@@ -0,0 +1,51 @@
+#![cfg(feature = "ssr")]
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_fullstack::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+static FETCHES: AtomicUsize = AtomicUsize::new(0);
+
+fn App(cx: Scope) -> Element {
+    render! {
+        User { id: 1 }
+        User { id: 2 }
+    }
+}
+
+#[inline_props]
+fn User(cx: Scope, id: u32) -> Element {
+    let id = *id;
+    let name = use_server_future(cx, || async move {
+        FETCHES.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        format!("user {}", id)
+    });
+
+    match name.value() {
+        Some(name) => render! { p { "{name}" } },
+        None => render! { p { "loading" } },
+    }
+}
+
+#[tokio::test]
+async fn hydrates_the_values_of_the_server() {
+    let mut dom = VirtualDom::new(App);
+    let (html, state) = pre_render_with_state(&mut dom).await;
+    assert!(html.contains("user 1") && html.contains("user 2"));
+    assert_eq!(FETCHES.load(Ordering::SeqCst), 2);
+
+    let script = state.to_script();
+    assert!(script.starts_with("<script type=\"application/json\" id=\"dioxus-server-state\">"));
+
+    // the client starts out with what the server rendered, without fetching it again
+    let mut client = VirtualDom::new(App);
+    client
+        .base_scope()
+        .provide_context(ServerState::from_json(&state.to_json()).unwrap());
+    _ = client.rebuild();
+    assert_eq!(dioxus_ssr::render(&client), "<p>user 1</p><p>user 2</p>");
+    assert_eq!(FETCHES.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,36 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+
+mod memory;
+#[cfg(feature = "web")]
+mod web;
+
+pub use memory::*;
+#[cfg(feature = "web")]
+pub use web::*;
+
+/// The history of the locations an app has navigated to
+///
+/// Locations are paths with an optional query and fragment, like `/blog/1?draft=true#comments`.
+pub trait History {
+    /// The path of the current entry
+    fn current_path(&self) -> String;
+
+    /// Navigate to a path, adding an entry after the current one and dropping any entries that came after it
+    fn push(&self, path: &str);
+
+    /// Navigate to a path, replacing the current entry
+    fn replace(&self, path: &str);
+
+    /// Move through the entries, going back when `delta` is negative and forward when it's positive
+    ///
+    /// Moving past either end of the history does nothing.
+    fn go(&self, delta: isize);
+
+    /// Call `callback` whenever the current entry changes from [`History::go`] or from outside the app, like the back
+    /// button of the browser
+    ///
+    /// Pushing and replacing don't call it, since the caller already knows where it navigated to. A history only has
+    /// one callback, so this replaces the previous one.
+    fn subscribe(&self, callback: Box<dyn Fn()>);
+}
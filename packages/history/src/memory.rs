@@ -0,0 +1,121 @@
+use crate::History;
+use std::cell::{Cell, RefCell};
+
+/// A history that only lives in memory, for platforms without a url bar and for tests
+pub struct MemoryHistory {
+    entries: RefCell<Vec<String>>,
+    current: Cell<usize>,
+    callback: RefCell<Option<Box<dyn Fn()>>>,
+}
+
+impl Default for MemoryHistory {
+    fn default() -> Self {
+        Self::with_initial_path("/")
+    }
+}
+
+impl MemoryHistory {
+    /// Create a history that starts at `/`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a history that starts at `path`
+    pub fn with_initial_path(path: impl Into<String>) -> Self {
+        Self {
+            entries: RefCell::new(vec![path.into()]),
+            current: Cell::new(0),
+            callback: RefCell::new(None),
+        }
+    }
+
+    /// Check if [`History::go`] can move by `delta` entries
+    pub fn can_go(&self, delta: isize) -> bool {
+        self.target(delta).is_some()
+    }
+
+    fn target(&self, delta: isize) -> Option<usize> {
+        let target = self.current.get() as isize + delta;
+        (target >= 0 && (target as usize) < self.entries.borrow().len()).then(|| target as usize)
+    }
+}
+
+impl History for MemoryHistory {
+    fn current_path(&self) -> String {
+        self.entries.borrow()[self.current.get()].clone()
+    }
+
+    fn push(&self, path: &str) {
+        let mut entries = self.entries.borrow_mut();
+        entries.truncate(self.current.get() + 1);
+        entries.push(path.to_string());
+        self.current.set(entries.len() - 1);
+    }
+
+    fn replace(&self, path: &str) {
+        self.entries.borrow_mut()[self.current.get()] = path.to_string();
+    }
+
+    fn go(&self, delta: isize) {
+        match self.target(delta) {
+            Some(target) if target != self.current.get() => self.current.set(target),
+            _ => return,
+        }
+
+        if let Some(callback) = self.callback.borrow().as_ref() {
+            callback();
+        }
+    }
+
+    fn subscribe(&self, callback: Box<dyn Fn()>) {
+        self.callback.replace(Some(callback));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn go_back_and_forward() {
+        let history = MemoryHistory::new();
+        history.push("/blog");
+        history.push("/blog/1");
+
+        history.go(-2);
+        assert_eq!(history.current_path(), "/");
+        history.go(1);
+        assert_eq!(history.current_path(), "/blog");
+
+        // pushing drops the entries after the current one
+        history.push("/about");
+        assert!(!history.can_go(1));
+        history.go(1);
+        assert_eq!(history.current_path(), "/about");
+
+        history.replace("/contact");
+        history.go(-1);
+        history.go(1);
+        assert_eq!(history.current_path(), "/contact");
+    }
+
+    #[test]
+    fn subscribers_see_moves() {
+        let history = MemoryHistory::with_initial_path("/blog");
+        let moves = Rc::new(Cell::new(0));
+        history.subscribe(Box::new({
+            let moves = moves.clone();
+            move || moves.set(moves.get() + 1)
+        }));
+
+        history.push("/blog/1");
+        history.replace("/blog/2");
+        assert_eq!(moves.get(), 0);
+
+        history.go(-1);
+        history.go(-1);
+        assert_eq!(moves.get(), 1);
+        assert_eq!(history.current_path(), "/blog");
+    }
+}
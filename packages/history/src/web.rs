@@ -0,0 +1,117 @@
+use crate::History;
+use gloo_events::EventListener;
+use std::cell::RefCell;
+use wasm_bindgen::JsValue;
+use web_sys::Window;
+
+/// A history backed by the History API of the browser, with the path of the app in the path of the url
+pub struct BrowserHistory {
+    window: Window,
+    // keep it around so it drops when the history is dropped
+    listener: RefCell<Option<EventListener>>,
+}
+
+impl Default for BrowserHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BrowserHistory {
+    /// Create a history for the window of the page
+    pub fn new() -> Self {
+        Self {
+            window: web_sys::window().unwrap(),
+            listener: RefCell::new(None),
+        }
+    }
+}
+
+impl History for BrowserHistory {
+    fn current_path(&self) -> String {
+        let location = self.window.location();
+        format!(
+            "{}{}{}",
+            location.pathname().unwrap_or_default(),
+            location.search().unwrap_or_default(),
+            location.hash().unwrap_or_default()
+        )
+    }
+
+    fn push(&self, path: &str) {
+        if let Ok(history) = self.window.history() {
+            let _ = history.push_state_with_url(&JsValue::NULL, "", Some(path));
+        }
+    }
+
+    fn replace(&self, path: &str) {
+        if let Ok(history) = self.window.history() {
+            let _ = history.replace_state_with_url(&JsValue::NULL, "", Some(path));
+        }
+    }
+
+    fn go(&self, delta: isize) {
+        // going nowhere would reload the page
+        if delta == 0 {
+            return;
+        }
+        if let Ok(history) = self.window.history() {
+            let _ = history.go_with_delta(delta as i32);
+        }
+    }
+
+    fn subscribe(&self, callback: Box<dyn Fn()>) {
+        let listener = EventListener::new(&self.window, "popstate", move |_| callback());
+        self.listener.replace(Some(listener));
+    }
+}
+
+/// A history that keeps the path of the app in the fragment of the url, like `/#/blog/1`
+///
+/// Static hosts only serve the pages they have, so a reload of `/blog/1` with a [`BrowserHistory`] finds nothing.
+/// Everything after the `#` stays in the browser, so it works on any host.
+pub struct HashHistory {
+    inner: BrowserHistory,
+}
+
+impl Default for HashHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HashHistory {
+    /// Create a history for the window of the page
+    pub fn new() -> Self {
+        Self {
+            inner: BrowserHistory::new(),
+        }
+    }
+}
+
+impl History for HashHistory {
+    fn current_path(&self) -> String {
+        let hash = self.inner.window.location().hash().unwrap_or_default();
+        match hash.trim_start_matches('#') {
+            "" => "/".to_string(),
+            path if path.starts_with('/') => path.to_string(),
+            path => format!("/{}", path),
+        }
+    }
+
+    fn push(&self, path: &str) {
+        self.inner.push(&format!("#{}", path));
+    }
+
+    fn replace(&self, path: &str) {
+        self.inner.replace(&format!("#{}", path));
+    }
+
+    fn go(&self, delta: isize) {
+        self.inner.go(delta);
+    }
+
+    fn subscribe(&self, callback: Box<dyn Fn()>) {
+        self.inner.subscribe(callback);
+    }
+}
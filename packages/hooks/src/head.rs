@@ -0,0 +1,306 @@
+//! Components that add elements to the `<head>` of the document from anywhere in the tree
+//!
+//! They render nothing where they're used. Each of them adds its element with [`use_head`], so elements that describe
+//! the same thing are deduplicated and the one that was added last wins.
+//!
+//! ```rust, ignore
+//! use dioxus_hooks::head::*;
+//!
+//! fn BlogPost(cx: Scope<PostProps>) -> Element {
+//!     cx.render(rsx! {
+//!         Title { "{cx.props.title} - My blog" }
+//!         Meta { name: "description", content: "{cx.props.summary}" }
+//!         Link { rel: "canonical", href: "https://example.com/blog/{cx.props.slug}" }
+//!         Script { src: "/comments.js", defer: true }
+//!         article { ... }
+//!     })
+//! }
+//! ```
+
+use crate::{use_head, HeadElement};
+use dioxus_core::{DynamicNode, Element, Properties, Scope, TemplateNode, VNode};
+
+macro_rules! head_props {
+    (
+        $(#[$doc:meta])*
+        $props:ident, $builder:ident, $fields:ident {
+            $( $(#[$field_doc:meta])* $field:ident: $attribute:literal, )*
+        }
+        flags {
+            $( $(#[$flag_doc:meta])* $flag:ident: $flag_attribute:literal, )*
+        }
+        $( children: $children_doc:literal )?
+    ) => {
+        $(#[$doc])*
+        pub struct $props<'a> {
+            attributes: Vec<(&'static str, &'a str)>,
+            key: Option<&'a str>,
+            children: Element<'a>,
+        }
+
+        #[doc = concat!("The builder for [`", stringify!($props), "`]")]
+        pub struct $builder<'a> {
+            props: $props<'a>,
+        }
+
+        impl<'a> $builder<'a> {
+            $(
+                $(#[$field_doc])*
+                pub fn $field(mut self, value: &'a str) -> Self {
+                    self.props.attributes.push(($attribute, value));
+                    self
+                }
+            )*
+
+            $(
+                $(#[$flag_doc])*
+                pub fn $flag(mut self, value: bool) -> Self {
+                    if value {
+                        self.props.attributes.push(($flag_attribute, ""));
+                    }
+                    self
+                }
+            )*
+
+            /// Deduplicate the element by this key, instead of by what it describes
+            pub fn key(mut self, key: &'a str) -> Self {
+                self.props.key = Some(key);
+                self
+            }
+
+            $(
+                #[doc = $children_doc]
+                pub fn children(mut self, children: Element<'a>) -> Self {
+                    self.props.children = children;
+                    self
+                }
+            )?
+
+            /// Finish building the props
+            pub fn build(self) -> $props<'a> {
+                self.props
+            }
+
+            #[doc(hidden)]
+            pub fn __dioxus_fields(&self) -> $fields {
+                $fields
+            }
+        }
+
+        #[doc(hidden)]
+        pub struct $fields;
+
+        #[doc(hidden)]
+        #[allow(missing_docs)]
+        impl $fields {
+            $( pub fn $field(&self) {} )*
+            $( pub fn $flag(&self) {} )*
+            pub fn key(&self) {}
+        }
+
+        impl<'a> $props<'a> {
+            fn element(&self, tag: &'static str) -> HeadElement {
+                let mut element = HeadElement::new(tag).content(text_of(&self.children));
+                for (name, value) in &self.attributes {
+                    element = element.attribute(*name, *value);
+                }
+                match self.key {
+                    Some(key) => element.key(key),
+                    None => element,
+                }
+            }
+        }
+
+        impl<'a> Properties for $props<'a> {
+            type Builder = $builder<'a>;
+            const IS_STATIC: bool = false;
+            fn builder() -> Self::Builder {
+                $builder {
+                    props: $props {
+                        attributes: Vec::new(),
+                        key: None,
+                        children: None,
+                    },
+                }
+            }
+            unsafe fn memoize(&self, _other: &Self) -> bool {
+                false
+            }
+        }
+    };
+}
+
+head_props! {
+    /// The props for the [`Title`] component
+    TitleProps, TitleBuilder, TitleFields {}
+    flags {}
+    children: "Set the text of the title"
+}
+
+head_props! {
+    /// The props for the [`Meta`] component
+    MetaProps, MetaBuilder, MetaFields {
+        /// Set the name of the metadata, like `description` or `viewport`
+        name: "name",
+        /// Set the value of the metadata
+        content: "content",
+        /// Set the property of the metadata, like `og:title`
+        property: "property",
+        /// Set the character encoding of the document
+        charset: "charset",
+        /// Set the header the metadata stands in for
+        http_equiv: "http-equiv",
+        /// Set the microdata property of the metadata
+        itemprop: "itemprop",
+    }
+    flags {}
+}
+
+head_props! {
+    /// The props for the [`Link`] component
+    LinkProps, LinkBuilder, LinkFields {
+        /// Set how the linked resource relates to the document, like `stylesheet`, `icon` or `canonical`
+        rel: "rel",
+        /// Set the url of the linked resource
+        href: "href",
+        /// Set the MIME type of the linked resource
+        r#type: "type",
+        /// Set the media the linked resource applies to
+        media: "media",
+        /// Set the sizes of an icon
+        sizes: "sizes",
+        /// Set the language of the linked resource
+        hreflang: "hreflang",
+        /// Set the kind of resource that's preloaded
+        r#as: "as",
+        /// Set how the linked resource is fetched across origins
+        crossorigin: "crossorigin",
+        /// Set the hash the linked resource has to match
+        integrity: "integrity",
+    }
+    flags {}
+}
+
+head_props! {
+    /// The props for the [`Script`] component
+    ScriptProps, ScriptBuilder, ScriptFields {
+        /// Set the url of the script
+        src: "src",
+        /// Set the type of the script, like `module`
+        r#type: "type",
+        /// Set how the script is fetched across origins
+        crossorigin: "crossorigin",
+        /// Set the hash the script has to match
+        integrity: "integrity",
+    }
+    flags {
+        /// Run the script as soon as it's loaded
+        r#async: "async",
+        /// Run the script once the document is parsed
+        defer: "defer",
+    }
+    children: "Set the code of an inline script"
+}
+
+/// Set the `<title>` of the document while the component is mounted
+///
+/// The children are the text of the title. The title of the component that was mounted last wins, so the title of a
+/// route replaces the title of the app.
+///
+/// ```rust, ignore
+/// rsx!{
+///     Title { "{count} unread messages" }
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn Title<'a>(cx: Scope<'a, TitleProps<'a>>) -> Element {
+    use_head(cx, cx.props.element("title"));
+    None
+}
+
+/// Add a `<meta>` element to the head while the component is mounted
+///
+/// Meta elements with the same `name`, `property`, `http-equiv`, `itemprop` or `charset` replace each other.
+///
+/// ```rust, ignore
+/// rsx!{
+///     Meta { name: "description", content: "The blog of a fish" }
+///     Meta { property: "og:image", content: "/fish.png" }
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn Meta<'a>(cx: Scope<'a, MetaProps<'a>>) -> Element {
+    use_head(cx, cx.props.element("meta"));
+    None
+}
+
+/// Add a `<link>` element to the head while the component is mounted
+///
+/// Links with the same `rel` and `href` replace each other, and there's only ever one `rel: "canonical"`.
+///
+/// ```rust, ignore
+/// rsx!{
+///     Link { rel: "stylesheet", href: "/blog.css" }
+///     Link { rel: "icon", r#type: "image/png", href: "/fish.png" }
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn Link<'a>(cx: Scope<'a, LinkProps<'a>>) -> Element {
+    use_head(cx, cx.props.element("link"));
+    None
+}
+
+/// Add a `<script>` element to the head while the component is mounted
+///
+/// A script runs when it's added, and again whenever it changes. Scripts with the same `src` are only added once, and
+/// so are inline scripts with the same code.
+///
+/// ```rust, ignore
+/// rsx!{
+///     Script { src: "https://example.com/analytics.js", r#async: true }
+///     Script { "window.startedAt = Date.now();" }
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn Script<'a>(cx: Scope<'a, ScriptProps<'a>>) -> Element {
+    use_head(cx, cx.props.element("script"));
+    None
+}
+
+/// The text of the children of a head component, ignoring any elements or components in it
+fn text_of(children: &Element) -> String {
+    fn node_text(node: &VNode, text: &mut String) {
+        let template = node.template.get();
+        for root in template.roots {
+            template_text(node, root, text);
+        }
+    }
+
+    fn template_text(node: &VNode, template: &TemplateNode, text: &mut String) {
+        match template {
+            TemplateNode::Text { text: value } => text.push_str(value),
+            TemplateNode::Element { children, .. } => {
+                for child in children.iter() {
+                    template_text(node, child, text);
+                }
+            }
+            TemplateNode::Dynamic { id } | TemplateNode::DynamicText { id } => {
+                match &node.dynamic_nodes[*id] {
+                    DynamicNode::Text(value) => text.push_str(value.value),
+                    DynamicNode::Fragment(nodes) => {
+                        for node in nodes.iter() {
+                            node_text(node, text);
+                        }
+                    }
+                    DynamicNode::Component(_) | DynamicNode::Placeholder(_) => {}
+                }
+            }
+        }
+    }
+
+    let mut text = String::new();
+    if let Some(node) = children {
+        node_text(node, &mut text);
+    }
+    text
+}
@@ -44,3 +44,57 @@ pub use useeffect::*;
 
 mod usecallback;
 pub use usecallback::*;
+
+mod usereducer;
+pub use usereducer::*;
+
+mod usedebounce;
+pub use usedebounce::*;
+
+mod usetimer;
+pub use usetimer::*;
+
+mod usepersistent;
+pub use usepersistent::*;
+
+mod usequery;
+pub use usequery::*;
+
+mod useform;
+pub use useform::*;
+
+mod useclipboard;
+pub use useclipboard::*;
+
+mod usewindowsize;
+pub use usewindowsize::*;
+
+mod useobserver;
+pub use useobserver::*;
+
+mod usevirtuallist;
+pub use usevirtuallist::*;
+
+mod useshortcut;
+pub use useshortcut::*;
+
+mod uselistener;
+pub use uselistener::*;
+
+mod useeval;
+pub use useeval::*;
+
+mod usehead;
+pub use usehead::*;
+
+pub mod head;
+
+#[cfg(feature = "websocket")]
+mod usewebsocket;
+#[cfg(feature = "websocket")]
+pub use usewebsocket::*;
+
+#[cfg(feature = "sse")]
+mod useeventsource;
+#[cfg(feature = "sse")]
+pub use useeventsource::*;
@@ -1,4 +1,4 @@
-use dioxus_core::{ScopeId, ScopeState};
+use dioxus_core::{RenderReason, ScopeId, ScopeState};
 use std::{
     cell::{Cell, Ref, RefCell, RefMut},
     collections::HashSet,
@@ -169,7 +169,7 @@ pub fn use_shared_state_provider<T: 'static>(cx: &ScopeState, f: impl FnOnce() -
     cx.use_hook(|| {
         let state: ProvidedState<T> = Rc::new(RefCell::new(ProvidedStateInner {
             value: Rc::new(RefCell::new(f())),
-            notify_any: cx.schedule_update_any(),
+            notify_any: cx.schedule_update_any_with_reason(RenderReason::Context),
             consumers: HashSet::new(),
         }));
 
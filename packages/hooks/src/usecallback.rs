@@ -31,7 +31,9 @@ where
 {
     let mut hook = make();
 
-    move |evt| cx.spawn(hook(evt))
+    move |evt| {
+        cx.spawn(hook(evt));
+    }
 }
 
 fn _it_works(cx: &ScopeState) {
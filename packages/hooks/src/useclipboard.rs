@@ -0,0 +1,184 @@
+use dioxus_core::ScopeState;
+use std::{fmt, future::Future, pin::Pin, rc::Rc};
+
+/// Read and write the text on the system clipboard
+///
+/// On web this uses the async Clipboard API of the browser, which may ask the user for permission. On desktop it
+/// talks to the clipboard of the operating system. Failures, including a denied permission, come back as a
+/// [`ClipboardError`].
+///
+/// The clipboard is provided by the renderer through a [`ClipboardContext`]. Without one, every call fails with
+/// [`ClipboardError::Unavailable`].
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// let clipboard = use_clipboard(cx);
+///
+/// cx.render(rsx!{
+///     button {
+///         onclick: move |_| {
+///             to_owned![clipboard];
+///             async move {
+///                 if let Err(err) = clipboard.write("https://dioxuslabs.com").await {
+///                     log::warn!("Couldn't copy the link: {err}");
+///                 }
+///             }
+///         },
+///         "Copy link"
+///     }
+/// })
+/// ```
+pub fn use_clipboard(cx: &ScopeState) -> &UseClipboard {
+    cx.use_hook(|| UseClipboard {
+        backend: cx.consume_context::<ClipboardContext>(),
+    })
+}
+
+/// A handle to a [`use_clipboard`]
+#[derive(Clone)]
+pub struct UseClipboard {
+    backend: Option<ClipboardContext>,
+}
+
+impl UseClipboard {
+    /// Read the text on the clipboard
+    pub fn read(&self) -> impl Future<Output = Result<String, ClipboardError>> {
+        let read = self.backend.as_ref().map(|backend| backend.0.read());
+        async move {
+            match read {
+                Some(read) => read.await,
+                None => Err(ClipboardError::Unavailable),
+            }
+        }
+    }
+
+    /// Replace the text on the clipboard
+    pub fn write(
+        &self,
+        text: impl Into<String>,
+    ) -> impl Future<Output = Result<(), ClipboardError>> {
+        let write = self
+            .backend
+            .as_ref()
+            .map(|backend| backend.0.write(text.into()));
+        async move {
+            match write {
+                Some(write) => write.await,
+                None => Err(ClipboardError::Unavailable),
+            }
+        }
+    }
+}
+
+/// A future returned by a [`ClipboardBackend`]
+pub type ClipboardFuture<T> = Pin<Box<dyn Future<Output = Result<T, ClipboardError>>>>;
+
+/// The clipboard of a platform, provided by renderers for [`use_clipboard`]
+pub trait ClipboardBackend {
+    /// Read the text on the clipboard
+    fn read(&self) -> ClipboardFuture<String>;
+
+    /// Replace the text on the clipboard
+    fn write(&self, text: String) -> ClipboardFuture<()>;
+}
+
+/// The context that hands a [`ClipboardBackend`] to [`use_clipboard`]
+#[derive(Clone)]
+pub struct ClipboardContext(Rc<dyn ClipboardBackend>);
+
+impl ClipboardContext {
+    /// Wrap a clipboard so it can be provided as a context
+    pub fn new(backend: impl ClipboardBackend + 'static) -> Self {
+        Self(Rc::new(backend))
+    }
+}
+
+/// Why the clipboard couldn't be used
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClipboardError {
+    /// The platform doesn't have a clipboard, or the renderer didn't provide one
+    Unavailable,
+
+    /// The user or the platform didn't allow access to the clipboard
+    PermissionDenied(String),
+
+    /// The clipboard failed for another reason
+    Other(String),
+}
+
+impl fmt::Display for ClipboardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClipboardError::Unavailable => write!(f, "the clipboard is not available"),
+            ClipboardError::PermissionDenied(reason) => {
+                write!(f, "access to the clipboard was denied: {}", reason)
+            }
+            ClipboardError::Other(reason) => write!(f, "the clipboard failed: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ClipboardError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::prelude::*;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct MemoryClipboard(Rc<RefCell<String>>);
+
+    impl ClipboardBackend for MemoryClipboard {
+        fn read(&self) -> ClipboardFuture<String> {
+            let text = self.0.borrow().clone();
+            Box::pin(async move { Ok(text) })
+        }
+
+        fn write(&self, text: String) -> ClipboardFuture<()> {
+            *self.0.borrow_mut() = text;
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    thread_local! {
+        static CLIPBOARD: RefCell<Option<UseClipboard>> = RefCell::new(None);
+    }
+
+    fn app(cx: Scope) -> Element {
+        let clipboard = use_clipboard(cx);
+        CLIPBOARD.with(|c| *c.borrow_mut() = Some(clipboard.clone()));
+        None
+    }
+
+    fn block_on<T>(fut: impl Future<Output = T>) -> T {
+        let waker = futures_util::task::noop_waker();
+        let mut cx = std::task::Context::from_waker(&waker);
+        match Box::pin(fut).as_mut().poll(&mut cx) {
+            std::task::Poll::Ready(value) => value,
+            std::task::Poll::Pending => panic!("{}", "the clipboard should be ready"),
+        }
+    }
+
+    #[test]
+    fn without_backend() {
+        let mut dom = VirtualDom::new(app);
+        _ = dom.rebuild();
+
+        let clipboard = CLIPBOARD.with(|c| c.borrow().clone().unwrap());
+        assert_eq!(block_on(clipboard.read()), Err(ClipboardError::Unavailable));
+    }
+
+    #[test]
+    fn with_backend() {
+        let mut dom = VirtualDom::new(app);
+        dom.base_scope()
+            .provide_context(ClipboardContext::new(MemoryClipboard::default()));
+        _ = dom.rebuild();
+
+        let clipboard = CLIPBOARD.with(|c| c.borrow().clone().unwrap());
+        assert_eq!(block_on(clipboard.write("copied")), Ok(()));
+        assert_eq!(block_on(clipboard.read()), Ok("copied".to_string()));
+    }
+}
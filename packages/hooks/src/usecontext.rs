@@ -2,9 +2,12 @@ use dioxus_core::ScopeState;
 
 /// Consume some context in the tree, providing a sharable handle to the value
 ///
-/// Does not regenerate the value if the value is changed at the parent.
+/// The context is read again on every render, so the component picks up the new value when the parent replaces it
+/// with [`ScopeState::replace_context`].
 pub fn use_context<T: 'static + Clone>(cx: &ScopeState) -> Option<&T> {
-    cx.use_hook(|| cx.consume_context::<T>()).as_ref()
+    let value = cx.use_hook(|| None);
+    *value = cx.consume_context::<T>();
+    value.as_ref()
 }
 
 /// Provide some context via the tree and return a reference to it
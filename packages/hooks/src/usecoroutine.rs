@@ -1,6 +1,14 @@
 use dioxus_core::{ScopeState, TaskId};
 pub use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
-use std::future::Future;
+use futures_util::Stream;
+use std::{
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    sync::Arc,
+    task::{Context, Poll},
+};
 
 /// Maintain a handle over a future that can be paused, resumed, and canceled.
 ///
@@ -64,11 +72,68 @@ where
     M: 'static,
     G: FnOnce(UnboundedReceiver<M>) -> F,
     F: Future<Output = ()> + 'static,
+{
+    use_coroutine_with_updates(cx, move |rx, _: UpdateSender<()>| init(rx))
+}
+
+/// Maintain a coroutine that can push updates back to the component that owns it
+///
+/// This works like [`use_coroutine`], but the coroutine is also handed an [`UpdateSender`]. Every update it sends
+/// marks the owning component as dirty, and the component reads the updates back out of the [`Coroutine`] handle
+/// while rendering, or consumes them as a [`Stream`] through [`Coroutine::updates`].
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// enum Command {
+///     Fetch(String),
+/// }
+///
+/// enum Update {
+///     Loaded(String),
+/// }
+///
+/// let client = use_coroutine_with_updates(cx, |mut rx: UnboundedReceiver<Command>, tx| async move {
+///     while let Some(Command::Fetch(url)) = rx.next().await {
+///         tx.send(Update::Loaded(fetch(url).await));
+///     }
+/// });
+///
+/// let page = use_state(cx, String::new);
+/// while let Some(Update::Loaded(body)) = client.try_next_update() {
+///     page.set(body);
+/// }
+///
+/// cx.render(rsx!{
+///     button {
+///         onclick: move |_| client.send(Command::Fetch("/".into())),
+///         "Load"
+///     }
+///     "{page}"
+/// })
+/// ```
+pub fn use_coroutine_with_updates<M, U, G, F>(cx: &ScopeState, init: G) -> &Coroutine<M, U>
+where
+    M: 'static,
+    U: 'static,
+    G: FnOnce(UnboundedReceiver<M>, UpdateSender<U>) -> F,
+    F: Future<Output = ()> + 'static,
 {
     cx.use_hook(|| {
         let (tx, rx) = futures_channel::mpsc::unbounded();
-        let task = cx.push_future(init(rx));
-        cx.provide_context(Coroutine { tx, task })
+        let (updates_tx, updates_rx) = futures_channel::mpsc::unbounded();
+        let updates = UpdateSender {
+            tx: updates_tx,
+            update: cx.schedule_update(),
+        };
+        let task = cx.push_future(init(rx, updates));
+        cx.provide_context(Coroutine {
+            tx,
+            task,
+            updates: CoroutineUpdates {
+                rx: Rc::new(RefCell::new(updates_rx)),
+            },
+        })
     })
 }
 
@@ -80,22 +145,34 @@ pub fn use_coroutine_handle<M: 'static>(cx: &ScopeState) -> Option<&Coroutine<M>
         .as_ref()
 }
 
-pub struct Coroutine<T> {
+/// Get a handle to a coroutine higher in the tree that was created with [`use_coroutine_with_updates`]
+///
+/// Updates read through the handle are taken away from every other handle to the same coroutine.
+pub fn use_coroutine_handle_with_updates<M: 'static, U: 'static>(
+    cx: &ScopeState,
+) -> Option<&Coroutine<M, U>> {
+    cx.use_hook(|| cx.consume_context::<Coroutine<M, U>>())
+        .as_ref()
+}
+
+pub struct Coroutine<T, U = ()> {
     tx: UnboundedSender<T>,
     task: TaskId,
+    updates: CoroutineUpdates<U>,
 }
 
 // for use in futures
-impl<T> Clone for Coroutine<T> {
+impl<T, U> Clone for Coroutine<T, U> {
     fn clone(&self) -> Self {
         Self {
             tx: self.tx.clone(),
             task: self.task,
+            updates: self.updates.clone(),
         }
     }
 }
 
-impl<T> Coroutine<T> {
+impl<T, U> Coroutine<T, U> {
     /// Get the ID of this coroutine
     #[must_use]
     pub fn task_id(&self) -> TaskId {
@@ -106,6 +183,67 @@ impl<T> Coroutine<T> {
     pub fn send(&self, msg: T) {
         let _ = self.tx.unbounded_send(msg);
     }
+
+    /// Take the oldest update the coroutine sent back, if there is one
+    ///
+    /// This is meant to be called in a loop while rendering, since every update schedules a new render.
+    pub fn try_next_update(&self) -> Option<U> {
+        self.updates.rx.borrow_mut().try_next().ok().flatten()
+    }
+
+    /// Get the updates of the coroutine as a [`Stream`]
+    ///
+    /// The stream ends once the coroutine finishes and every update has been read.
+    pub fn updates(&self) -> CoroutineUpdates<U> {
+        self.updates.clone()
+    }
+}
+
+/// The sending half of the updates of a coroutine, from [`use_coroutine_with_updates`]
+///
+/// Sending an update marks the component that owns the coroutine as dirty.
+pub struct UpdateSender<U> {
+    tx: UnboundedSender<U>,
+    update: Arc<dyn Fn() + Send + Sync>,
+}
+
+impl<U> Clone for UpdateSender<U> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+            update: self.update.clone(),
+        }
+    }
+}
+
+impl<U> UpdateSender<U> {
+    /// Send an update back to the component and schedule it to render
+    pub fn send(&self, update: U) {
+        if self.tx.unbounded_send(update).is_ok() {
+            (self.update)();
+        }
+    }
+}
+
+/// The updates sent back by a coroutine, from [`Coroutine::updates`]
+pub struct CoroutineUpdates<U> {
+    rx: Rc<RefCell<UnboundedReceiver<U>>>,
+}
+
+impl<U> Clone for CoroutineUpdates<U> {
+    fn clone(&self) -> Self {
+        Self {
+            rx: self.rx.clone(),
+        }
+    }
+}
+
+impl<U> Stream for CoroutineUpdates<U> {
+    type Item = U;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<U>> {
+        Pin::new(&mut *self.rx.borrow_mut()).poll_next(cx)
+    }
 }
 
 #[cfg(test)]
@@ -150,4 +288,39 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn updates_render_the_owner() {
+        thread_local! {
+            static UPDATES: RefCell<Vec<i32>> = RefCell::new(Vec::new());
+        }
+
+        fn app(cx: Scope) -> Element {
+            let task =
+                use_coroutine_with_updates(cx, |mut rx: UnboundedReceiver<i32>, tx| async move {
+                    while let Some(msg) = rx.next().await {
+                        tx.send(msg * 10);
+                    }
+                });
+
+            if cx.generation() == 0 {
+                task.send(2);
+            }
+
+            while let Some(update) = task.try_next_update() {
+                UPDATES.with(|u| u.borrow_mut().push(update));
+            }
+
+            None
+        }
+
+        let mut dom = VirtualDom::new(app);
+        _ = dom.rebuild();
+
+        // The coroutine answers, which marks the app as dirty
+        dom.process_events();
+        _ = dom.render_immediate();
+
+        assert_eq!(UPDATES.with(|u| u.borrow().clone()), [20]);
+    }
 }
@@ -0,0 +1,245 @@
+use crate::usetimer::sleep;
+use dioxus_core::ScopeState;
+use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use futures_util::{
+    future::{select, Either},
+    StreamExt,
+};
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+/// Run a callback once values stop coming in for `duration`
+///
+/// Every call to [`UseDebounce::call`] restarts the timer, and only the last value is handed to the callback. This is
+/// the usual way to implement search-as-you-type without sending a request for every key press.
+///
+/// The timer runs on the component, so a pending call is dropped if the component is removed. The callback is replaced
+/// on every render, so it always sees the latest state it captured.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// let results = use_state(cx, Vec::new);
+/// let search = use_debounce(cx, Duration::from_millis(300), {
+///     to_owned![results];
+///     move |query: String| results.set(search_index(&query))
+/// });
+///
+/// cx.render(rsx!{
+///     input { oninput: move |evt| search.call(evt.value.clone()) }
+/// })
+/// ```
+pub fn use_debounce<T: 'static>(
+    cx: &ScopeState,
+    duration: Duration,
+    callback: impl FnMut(T) + 'static,
+) -> &UseDebounce<T> {
+    let tx = use_timed(cx, callback, move |mut rx, callback| async move {
+        let mut pending = None;
+        loop {
+            pending = match pending.take() {
+                None => match rx.next().await {
+                    Some(value) => Some(value),
+                    None => return,
+                },
+                Some(value) => match select(rx.next(), Box::pin(sleep(duration))).await {
+                    Either::Left((Some(newer), _)) => Some(newer),
+                    Either::Left((None, _)) => return,
+                    Either::Right(_) => {
+                        (callback.borrow_mut())(value);
+                        None
+                    }
+                },
+            };
+        }
+    });
+
+    cx.use_hook(|| UseDebounce { tx: tx.clone() })
+}
+
+/// Run a callback at most once every `duration`
+///
+/// The first call to [`UseThrottle::call`] runs the callback right away. Calls made while the callback is cooling down
+/// are collapsed into the last one, which runs once the cooldown is over.
+///
+/// The timer runs on the component, so a pending call is dropped if the component is removed. The callback is replaced
+/// on every render, so it always sees the latest state it captured.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// let position = use_state(cx, || (0.0, 0.0));
+/// let track = use_throttle(cx, Duration::from_millis(100), {
+///     to_owned![position];
+///     move |pos| position.set(pos)
+/// });
+///
+/// cx.render(rsx!{
+///     div { onmousemove: move |evt| track.call(evt.client_coordinates().to_tuple()) }
+/// })
+/// ```
+pub fn use_throttle<T: 'static>(
+    cx: &ScopeState,
+    duration: Duration,
+    callback: impl FnMut(T) + 'static,
+) -> &UseThrottle<T> {
+    let tx = use_timed(cx, callback, move |mut rx, callback| async move {
+        let mut value = match rx.next().await {
+            Some(value) => value,
+            None => return,
+        };
+
+        loop {
+            (callback.borrow_mut())(value);
+
+            // Hold onto the latest value until the cooldown is over
+            let mut latest = None;
+            let mut cooldown = Box::pin(sleep(duration));
+            loop {
+                match select(rx.next(), cooldown).await {
+                    Either::Left((Some(newer), timer)) => {
+                        latest = Some(newer);
+                        cooldown = timer;
+                    }
+                    Either::Left((None, _)) => return,
+                    Either::Right(_) => break,
+                }
+            }
+
+            value = match latest {
+                Some(value) => value,
+                None => match rx.next().await {
+                    Some(value) => value,
+                    None => return,
+                },
+            };
+        }
+    });
+
+    cx.use_hook(|| UseThrottle { tx: tx.clone() })
+}
+
+/// A handle to a [`use_debounce`]
+pub struct UseDebounce<T> {
+    tx: UnboundedSender<T>,
+}
+
+impl<T> Clone for UseDebounce<T> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+        }
+    }
+}
+
+impl<T> UseDebounce<T> {
+    /// Restart the timer, running the callback with `value` once it runs out
+    pub fn call(&self, value: T) {
+        let _ = self.tx.unbounded_send(value);
+    }
+}
+
+/// A handle to a [`use_throttle`]
+pub struct UseThrottle<T> {
+    tx: UnboundedSender<T>,
+}
+
+impl<T> Clone for UseThrottle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+        }
+    }
+}
+
+impl<T> UseThrottle<T> {
+    /// Run the callback with `value`, or with the latest value once the cooldown is over
+    pub fn call(&self, value: T) {
+        let _ = self.tx.unbounded_send(value);
+    }
+}
+
+type Callback<T> = Rc<RefCell<Box<dyn FnMut(T)>>>;
+
+/// Start the timer task on the component and keep its callback up to date
+fn use_timed<T: 'static, F>(
+    cx: &ScopeState,
+    callback: impl FnMut(T) + 'static,
+    task: impl FnOnce(UnboundedReceiver<T>, Callback<T>) -> F,
+) -> &UnboundedSender<T>
+where
+    F: std::future::Future<Output = ()> + 'static,
+{
+    let mut callback = Some(callback);
+
+    let (tx, current): &mut (UnboundedSender<T>, Callback<T>) = cx.use_hook(|| {
+        let (tx, rx) = futures_channel::mpsc::unbounded();
+        let first: Box<dyn FnMut(T)> = Box::new(callback.take().unwrap());
+        let current = Rc::new(RefCell::new(first));
+        cx.push_future(task(rx, current.clone()));
+        (tx, current)
+    });
+
+    if let Some(callback) = callback {
+        *current.borrow_mut() = Box::new(callback);
+    }
+
+    tx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::prelude::*;
+
+    thread_local! {
+        static CALLS: RefCell<Vec<i32>> = RefCell::new(Vec::new());
+    }
+
+    fn record(value: i32) {
+        CALLS.with(|c| c.borrow_mut().push(value));
+    }
+
+    fn calls() -> Vec<i32> {
+        CALLS.with(|c| c.borrow().clone())
+    }
+
+    #[test]
+    fn debounce_keeps_last_value() {
+        fn app(cx: Scope) -> Element {
+            let debounce = use_debounce(cx, Duration::from_millis(50), record);
+            if cx.generation() == 0 {
+                (1..=3).for_each(|i| debounce.call(i));
+            }
+            None
+        }
+
+        let mut dom = VirtualDom::new(app);
+        _ = dom.rebuild();
+        dom.process_events();
+        assert!(calls().is_empty());
+
+        std::thread::sleep(Duration::from_millis(200));
+        dom.process_events();
+        assert_eq!(calls(), [3]);
+    }
+
+    #[test]
+    fn throttle_runs_first_and_last() {
+        fn app(cx: Scope) -> Element {
+            let throttle = use_throttle(cx, Duration::from_millis(50), record);
+            if cx.generation() == 0 {
+                (1..=3).for_each(|i| throttle.call(i));
+            }
+            None
+        }
+
+        let mut dom = VirtualDom::new(app);
+        _ = dom.rebuild();
+        dom.process_events();
+        assert_eq!(calls(), [1]);
+
+        std::thread::sleep(Duration::from_millis(200));
+        dom.process_events();
+        assert_eq!(calls(), [1, 3]);
+    }
+}
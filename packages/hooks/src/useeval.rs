@@ -0,0 +1,317 @@
+use dioxus_core::ScopeState;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    fmt,
+    future::poll_fn,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+/// Get a function that runs JavaScript in the page and returns an [`Eval`] to talk to it
+///
+/// The script runs in an async function, so it can `await`. It sends values to Rust with `dioxus.send(value)` and
+/// receives the values Rust sends with `await dioxus.recv()`. What it returns, or the error it throws, is what
+/// [`Eval::join`] resolves to. Values cross as JSON, so they're serialized with serde on the Rust side.
+///
+/// The script is run by the renderer through an [`EvalContext`]. Without one, every script fails with
+/// [`EvalError::Unavailable`]. Web, desktop and LiveView all provide one, and run scripts the same way, so code that
+/// uses `use_eval` works on each of them without any cfg.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// let eval = use_eval(cx);
+///
+/// let running = eval(r#"
+///     const name = await dioxus.recv();
+///     dioxus.send(`hello ${name}`);
+///     return name.length;
+/// "#)?;
+///
+/// running.send("world")?;
+/// let greeting: String = running.recv().await?;
+/// let length: usize = running.join().await?;
+/// ```
+pub fn use_eval(cx: &ScopeState) -> &EvalCreator {
+    cx.use_hook(|| {
+        let backend = cx.consume_context::<EvalContext>();
+        Rc::new(move |script: &str| match &backend {
+            Some(backend) => backend.0.eval(script).map(Eval::new),
+            None => Err(EvalError::Unavailable),
+        }) as EvalCreator
+    })
+}
+
+/// Runs a script and returns an [`Eval`] for it, from [`use_eval`]
+pub type EvalCreator = Rc<dyn Fn(&str) -> Result<Eval, EvalError>>;
+
+/// A script that was started with [`use_eval`]
+#[derive(Clone)]
+pub struct Eval {
+    evaluator: Rc<dyn Evaluator>,
+}
+
+impl Eval {
+    /// Wrap the running script of a renderer
+    pub fn new(evaluator: Rc<dyn Evaluator>) -> Self {
+        Self { evaluator }
+    }
+
+    /// Send a value to the script, which it gets from `dioxus.recv()`
+    pub fn send(&self, value: impl Serialize) -> Result<(), EvalError> {
+        let value =
+            serde_json::to_value(value).map_err(|err| EvalError::Serialization(err.to_string()))?;
+        self.evaluator.send(value)
+    }
+
+    /// Wait for the next value the script sends with `dioxus.send(value)`
+    ///
+    /// Fails with [`EvalError::Finished`] once the script is done and every value it sent was received.
+    pub async fn recv<T: DeserializeOwned>(&self) -> Result<T, EvalError> {
+        let value = poll_fn(|cx| self.evaluator.poll_recv(cx)).await?;
+        deserialize(value)
+    }
+
+    /// Wait for the script to finish, with what it returned
+    pub async fn join<T: DeserializeOwned>(&self) -> Result<T, EvalError> {
+        let value = poll_fn(|cx| self.evaluator.poll_join(cx)).await?;
+        deserialize(value)
+    }
+}
+
+fn deserialize<T: DeserializeOwned>(value: Value) -> Result<T, EvalError> {
+    serde_json::from_value(value).map_err(|err| EvalError::Serialization(err.to_string()))
+}
+
+/// A script that a renderer is running, provided to [`Eval`]
+pub trait Evaluator {
+    /// Send a value to the script
+    fn send(&self, value: Value) -> Result<(), EvalError>;
+
+    /// Poll for the next value the script sent
+    fn poll_recv(&self, cx: &mut Context<'_>) -> Poll<Result<Value, EvalError>>;
+
+    /// Poll for what the script returned
+    fn poll_join(&self, cx: &mut Context<'_>) -> Poll<Result<Value, EvalError>>;
+}
+
+/// Runs scripts for a renderer, which provides it through an [`EvalContext`]
+pub trait EvalBackend {
+    /// Start running a script
+    fn eval(&self, script: &str) -> Result<Rc<dyn Evaluator>, EvalError>;
+}
+
+/// The context that hands an [`EvalBackend`] to [`use_eval`]
+#[derive(Clone)]
+pub struct EvalContext(Rc<dyn EvalBackend>);
+
+impl EvalContext {
+    /// Wrap the scripts of a renderer so they can be provided as a context
+    pub fn new(backend: impl EvalBackend + 'static) -> Self {
+        Self(Rc::new(backend))
+    }
+}
+
+/// The values a script sent and what it returned, for renderers to build an [`Evaluator`] on
+///
+/// The renderer pushes what it hears from the script, and the [`Evaluator`] polls it.
+#[derive(Default)]
+pub struct EvalChannel {
+    inner: RefCell<ChannelInner>,
+}
+
+#[derive(Default)]
+struct ChannelInner {
+    received: VecDeque<Value>,
+    result: Option<Result<Value, EvalError>>,
+    recv_waker: Option<Waker>,
+    join_waker: Option<Waker>,
+}
+
+impl EvalChannel {
+    /// The script sent a value
+    pub fn receive(&self, value: Value) {
+        let mut inner = self.inner.borrow_mut();
+        inner.received.push_back(value);
+        if let Some(waker) = inner.recv_waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// The script returned or threw
+    pub fn finish(&self, result: Result<Value, EvalError>) {
+        let mut inner = self.inner.borrow_mut();
+        if inner.result.is_none() {
+            inner.result = Some(result);
+        }
+        let recv_waker = inner.recv_waker.take();
+        for waker in recv_waker.into_iter().chain(inner.join_waker.take()) {
+            waker.wake();
+        }
+    }
+
+    /// Check if the script returned or threw
+    pub fn is_finished(&self) -> bool {
+        self.inner.borrow().result.is_some()
+    }
+
+    /// Poll for the next value the script sent
+    pub fn poll_recv(&self, cx: &mut Context<'_>) -> Poll<Result<Value, EvalError>> {
+        let mut inner = self.inner.borrow_mut();
+        if let Some(value) = inner.received.pop_front() {
+            return Poll::Ready(Ok(value));
+        }
+        if inner.result.is_some() {
+            return Poll::Ready(Err(EvalError::Finished));
+        }
+        inner.recv_waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    /// Poll for what the script returned
+    pub fn poll_join(&self, cx: &mut Context<'_>) -> Poll<Result<Value, EvalError>> {
+        let mut inner = self.inner.borrow_mut();
+        match &inner.result {
+            Some(result) => Poll::Ready(result.clone()),
+            None => {
+                inner.join_waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Why a script couldn't be run or talked to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EvalError {
+    /// The renderer can't run scripts
+    Unavailable,
+
+    /// The script didn't compile or threw an error
+    Failed(String),
+
+    /// The script is done, so it can't send or receive anything anymore
+    Finished,
+
+    /// A value couldn't be turned into JSON, or the JSON into the type that was asked for
+    Serialization(String),
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::Unavailable => write!(f, "the renderer can't run scripts"),
+            EvalError::Failed(reason) => write!(f, "the script failed: {}", reason),
+            EvalError::Finished => write!(f, "the script is finished"),
+            EvalError::Serialization(reason) => {
+                write!(f, "a value of the script couldn't be converted: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::prelude::*;
+    use std::future::Future;
+
+    /// Echoes back every value it gets, and returns how many it got once it gets `null`
+    #[derive(Default)]
+    struct EchoScript {
+        channel: EvalChannel,
+        count: std::cell::Cell<u64>,
+    }
+
+    impl Evaluator for EchoScript {
+        fn send(&self, value: Value) -> Result<(), EvalError> {
+            if self.channel.is_finished() {
+                return Err(EvalError::Finished);
+            }
+            if value.is_null() {
+                self.channel.finish(Ok(self.count.get().into()));
+            } else {
+                self.count.set(self.count.get() + 1);
+                self.channel.receive(value);
+            }
+            Ok(())
+        }
+
+        fn poll_recv(&self, cx: &mut Context<'_>) -> Poll<Result<Value, EvalError>> {
+            self.channel.poll_recv(cx)
+        }
+
+        fn poll_join(&self, cx: &mut Context<'_>) -> Poll<Result<Value, EvalError>> {
+            self.channel.poll_join(cx)
+        }
+    }
+
+    struct EchoBackend;
+
+    impl EvalBackend for EchoBackend {
+        fn eval(&self, script: &str) -> Result<Rc<dyn Evaluator>, EvalError> {
+            match script {
+                "echo" => Ok(Rc::new(EchoScript::default())),
+                _ => Err(EvalError::Failed(format!("unknown script {:?}", script))),
+            }
+        }
+    }
+
+    thread_local! {
+        static EVAL: RefCell<Option<EvalCreator>> = RefCell::new(None);
+    }
+
+    fn app(cx: Scope) -> Element {
+        let eval = use_eval(cx);
+        EVAL.with(|e| *e.borrow_mut() = Some(eval.clone()));
+        None
+    }
+
+    fn block_on<T>(fut: impl Future<Output = T>) -> T {
+        let waker = futures_util::task::noop_waker();
+        let mut cx = std::task::Context::from_waker(&waker);
+        match Box::pin(fut).as_mut().poll(&mut cx) {
+            std::task::Poll::Ready(value) => value,
+            std::task::Poll::Pending => panic!("{}", "the script should be ready"),
+        }
+    }
+
+    #[test]
+    fn without_backend() {
+        let mut dom = VirtualDom::new(app);
+        _ = dom.rebuild();
+
+        let eval = EVAL.with(|e| e.borrow().clone().unwrap());
+        assert_eq!(eval("echo").err(), Some(EvalError::Unavailable));
+    }
+
+    #[test]
+    fn talks_to_the_script() {
+        let mut dom = VirtualDom::new(app);
+        dom.base_scope()
+            .provide_context(EvalContext::new(EchoBackend));
+        _ = dom.rebuild();
+
+        let eval = EVAL.with(|e| e.borrow().clone().unwrap());
+        assert!(matches!(eval("nope").err(), Some(EvalError::Failed(_))));
+
+        let running = eval("echo").unwrap();
+        running.send("hello").unwrap();
+        running.send(vec![1, 2]).unwrap();
+        assert_eq!(block_on(running.recv::<String>()), Ok("hello".to_string()));
+        assert!(matches!(
+            block_on(running.recv::<String>()),
+            Err(EvalError::Serialization(_))
+        ));
+
+        running.send(()).unwrap();
+        assert_eq!(block_on(running.join::<u64>()), Ok(2));
+        assert_eq!(block_on(running.recv::<Value>()), Err(EvalError::Finished));
+        assert_eq!(running.send("late"), Err(EvalError::Finished));
+    }
+}
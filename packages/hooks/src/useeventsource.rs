@@ -0,0 +1,637 @@
+use crate::usetimer::sleep;
+use dioxus_core::ScopeState;
+use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use futures_util::{Stream, StreamExt};
+use std::{
+    cell::{Cell, RefCell},
+    pin::Pin,
+    rc::Rc,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// Listen to the server-sent events of a url for as long as the component is mounted
+///
+/// Events that arrive mark the component as dirty. The last one can be read while rendering with
+/// [`UseEventSource::last_event`], and every one of them can be consumed with [`UseEventSource::try_next_event`] or as
+/// a [`Stream`] through [`UseEventSource::events`].
+///
+/// On web this uses the browser's `EventSource`, which only hands out the events of the types it listens to - see
+/// [`EventSourceOptions::listen`]. Everywhere else the stream is read on its own thread with `reqwest`, so it works with
+/// any executor, and every event is handed out.
+///
+/// By default, the stream is opened again whenever it ends, backing off between attempts or waiting as long as the
+/// server asked with `retry:`. Streams opened again natively send the id of the last event in the `Last-Event-ID`
+/// header. The stream is closed when the component is removed.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// let prices = use_event_source(cx, "/prices");
+///
+/// cx.render(rsx!{
+///     match prices.last_event() {
+///         Some(event) => rsx!( p { "Latest price: {event.data}" } ),
+///         None => rsx!( p { "Waiting for prices..." } ),
+///     }
+/// })
+/// ```
+#[doc(alias = "use_sse")]
+pub fn use_event_source(cx: &ScopeState, url: impl Into<String>) -> &UseEventSource {
+    use_event_source_with_options(cx, url, EventSourceOptions::default())
+}
+
+/// A [`use_event_source`] that's configured with [`EventSourceOptions`]
+///
+/// The url and options are only read when the hook is first run.
+pub fn use_event_source_with_options(
+    cx: &ScopeState,
+    url: impl Into<String>,
+    options: EventSourceOptions,
+) -> &UseEventSource {
+    cx.use_hook(|| {
+        let (incoming_tx, incoming_rx) = futures_channel::mpsc::unbounded();
+
+        let status = Rc::new(Cell::new(EventSourceStatus::Connecting));
+        let last_event = Rc::new(RefCell::new(None));
+        let driver = Driver {
+            url: url.into(),
+            options,
+            status: status.clone(),
+            last_event: last_event.clone(),
+            incoming: incoming_tx,
+            update: cx.schedule_update(),
+        };
+        cx.push_future(driver.run());
+
+        UseEventSource {
+            status,
+            last_event,
+            events: ServerSentEvents {
+                rx: Rc::new(RefCell::new(incoming_rx)),
+            },
+        }
+    })
+}
+
+/// An event sent by the server over a [`use_event_source`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServerSentEvent {
+    /// The type of the event, `message` unless the server named it with `event:`
+    pub event: String,
+
+    /// The lines of data of the event, joined with newlines
+    pub data: String,
+
+    /// The id of the last event, if the server sent one so far
+    pub id: Option<String>,
+}
+
+/// The state of the connection of a [`use_event_source`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventSourceStatus {
+    /// The first connection is being made
+    Connecting,
+
+    /// The stream is open and events are flowing
+    Open,
+
+    /// The stream ended and will be opened again after a backoff
+    Reconnecting,
+
+    /// The stream ended and won't be opened again
+    Closed,
+}
+
+/// How a [`use_event_source_with_options`] connects and deals with the stream ending
+#[derive(Clone, Debug)]
+pub struct EventSourceOptions {
+    reconnect: bool,
+    max_retries: Option<usize>,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    event_types: Vec<String>,
+}
+
+impl Default for EventSourceOptions {
+    fn default() -> Self {
+        Self {
+            reconnect: true,
+            max_retries: None,
+            initial_backoff: Duration::from_secs(3),
+            max_backoff: Duration::from_secs(30),
+            event_types: Vec::new(),
+        }
+    }
+}
+
+impl EventSourceOptions {
+    /// Create the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether the stream is opened again after it ends. On by default.
+    pub fn reconnect(mut self, reconnect: bool) -> Self {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// Give up after this many attempts in a row fail to reconnect. Unlimited by default.
+    pub fn max_retries(mut self, retries: usize) -> Self {
+        self.max_retries = Some(retries);
+        self
+    }
+
+    /// Set how long to wait before the first attempt to reconnect. Every failed attempt doubles the wait, up to
+    /// `max`. Defaults to 3s and 30s. A `retry:` from the server replaces the initial wait.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Also hand out the events the server names with `event:`, on top of the unnamed `message` ones
+    ///
+    /// This only matters on web, where the browser drops the events nobody listens to.
+    pub fn listen(mut self, event_type: impl Into<String>) -> Self {
+        self.event_types.push(event_type.into());
+        self
+    }
+}
+
+/// A handle to a [`use_event_source`]
+#[derive(Clone)]
+pub struct UseEventSource {
+    status: Rc<Cell<EventSourceStatus>>,
+    last_event: Rc<RefCell<Option<ServerSentEvent>>>,
+    events: ServerSentEvents,
+}
+
+impl UseEventSource {
+    /// Get the state of the connection
+    pub fn status(&self) -> EventSourceStatus {
+        self.status.get()
+    }
+
+    /// Get the last event that arrived, even if it was already taken out with [`UseEventSource::try_next_event`]
+    pub fn last_event(&self) -> Option<ServerSentEvent> {
+        self.last_event.borrow().clone()
+    }
+
+    /// Take the oldest event that arrived, if there is one
+    ///
+    /// This is meant to be called in a loop while rendering, since every event schedules a new render.
+    pub fn try_next_event(&self) -> Option<ServerSentEvent> {
+        self.events.rx.borrow_mut().try_next().ok().flatten()
+    }
+
+    /// Get the events that arrive as a [`Stream`]
+    ///
+    /// Every event is only handed out once, either through the stream or [`UseEventSource::try_next_event`].
+    pub fn events(&self) -> ServerSentEvents {
+        self.events.clone()
+    }
+}
+
+/// The events of a [`use_event_source`], from [`UseEventSource::events`]
+#[derive(Clone)]
+pub struct ServerSentEvents {
+    rx: Rc<RefCell<UnboundedReceiver<ServerSentEvent>>>,
+}
+
+impl Stream for ServerSentEvents {
+    type Item = ServerSentEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut *self.rx.borrow_mut()).poll_next(cx)
+    }
+}
+
+/// What the platform reports about a stream
+enum SourceEvent {
+    Open,
+    Event(ServerSentEvent),
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    Retry(Duration),
+    /// The browser lost the stream and is opening it again by itself
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    Interrupted,
+    Closed,
+}
+
+/// The task that keeps the stream open
+struct Driver {
+    url: String,
+    options: EventSourceOptions,
+    status: Rc<Cell<EventSourceStatus>>,
+    last_event: Rc<RefCell<Option<ServerSentEvent>>>,
+    incoming: UnboundedSender<ServerSentEvent>,
+    update: Arc<dyn Fn()>,
+}
+
+impl Driver {
+    fn set_status(&self, status: EventSourceStatus) {
+        if self.status.replace(status) != status {
+            (self.update)();
+        }
+    }
+
+    async fn run(self) {
+        let mut retries = 0;
+        let mut initial_backoff = self.options.initial_backoff;
+        let mut backoff = initial_backoff;
+        let mut last_id = None;
+
+        loop {
+            let mut connection =
+                platform::Connection::open(&self.url, last_id.as_deref(), &self.options);
+
+            while let Some(event) = connection.events.next().await {
+                match event {
+                    SourceEvent::Open => {
+                        retries = 0;
+                        backoff = initial_backoff;
+                        self.set_status(EventSourceStatus::Open);
+                    }
+                    SourceEvent::Event(event) => {
+                        last_id = event.id.clone();
+                        *self.last_event.borrow_mut() = Some(event.clone());
+                        let _ = self.incoming.unbounded_send(event);
+                        (self.update)();
+                    }
+                    SourceEvent::Retry(retry) => {
+                        initial_backoff = retry;
+                        backoff = retry;
+                    }
+                    SourceEvent::Interrupted => self.set_status(EventSourceStatus::Reconnecting),
+                    SourceEvent::Closed => break,
+                }
+            }
+
+            drop(connection);
+
+            let exhausted = self.options.max_retries.map_or(false, |max| retries >= max);
+            if !self.options.reconnect || exhausted {
+                self.set_status(EventSourceStatus::Closed);
+                return;
+            }
+
+            retries += 1;
+            self.set_status(EventSourceStatus::Reconnecting);
+            sleep(backoff).await;
+            backoff = (backoff * 2).min(self.options.max_backoff);
+        }
+    }
+}
+
+/// Reads the `text/event-stream` format one line at a time
+///
+/// The browser parses the stream itself, so this is only used natively.
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+#[derive(Default)]
+struct EventStreamParser {
+    event: String,
+    data: String,
+    has_data: bool,
+    id: Option<String>,
+}
+
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+impl EventStreamParser {
+    fn new(last_id: Option<String>) -> Self {
+        Self {
+            id: last_id,
+            ..Default::default()
+        }
+    }
+
+    /// Feed a line without its line ending, getting back what it finished
+    fn line(&mut self, line: &str) -> Option<SourceEvent> {
+        // A blank line dispatches the event built up by the lines before it
+        if line.is_empty() {
+            let event = std::mem::take(&mut self.event);
+            let data = std::mem::take(&mut self.data);
+            if !std::mem::take(&mut self.has_data) {
+                return None;
+            }
+            return Some(SourceEvent::Event(ServerSentEvent {
+                event: if event.is_empty() {
+                    "message".to_string()
+                } else {
+                    event
+                },
+                data,
+                id: self.id.clone(),
+            }));
+        }
+
+        // Lines starting with a colon are comments, which servers send to keep the connection alive
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+
+        match field {
+            "event" => self.event = value.to_string(),
+            "data" => {
+                if self.has_data {
+                    self.data.push('\n');
+                }
+                self.data.push_str(value);
+                self.has_data = true;
+            }
+            "id" if !value.contains('\0') => self.id = Some(value.to_string()),
+            "retry" => {
+                if let Ok(millis) = value.parse() {
+                    return Some(SourceEvent::Retry(Duration::from_millis(millis)));
+                }
+            }
+            _ => {}
+        }
+
+        None
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod platform {
+    use super::{EventSourceOptions, ServerSentEvent, SourceEvent};
+    use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+    use wasm_bindgen::{closure::Closure, JsCast};
+    use web_sys::{EventSource, MessageEvent};
+
+    /// A browser event source and the handlers that feed its events into a channel
+    pub(super) struct Connection {
+        source: Option<EventSource>,
+        event_types: Vec<String>,
+        _handlers: Vec<Closure<dyn FnMut(web_sys::Event)>>,
+        pub(super) events: UnboundedReceiver<SourceEvent>,
+    }
+
+    impl Connection {
+        pub(super) fn open(
+            url: &str,
+            _last_id: Option<&str>,
+            options: &EventSourceOptions,
+        ) -> Self {
+            let (tx, events) = unbounded();
+
+            let source = match EventSource::new(url) {
+                Ok(source) => source,
+                Err(err) => {
+                    log::error!("Failed to open an event source to {}: {:?}", url, err);
+                    let _ = tx.unbounded_send(SourceEvent::Closed);
+                    return Self {
+                        source: None,
+                        event_types: Vec::new(),
+                        _handlers: Vec::new(),
+                        events,
+                    };
+                }
+            };
+
+            let handler =
+                |tx: &UnboundedSender<SourceEvent>,
+                 f: Box<dyn Fn(web_sys::Event) -> SourceEvent>| {
+                    let tx = tx.clone();
+                    Closure::wrap(Box::new(move |event: web_sys::Event| {
+                        let _ = tx.unbounded_send(f(event));
+                    }) as Box<dyn FnMut(web_sys::Event)>)
+                };
+
+            let onopen = handler(&tx, Box::new(|_| SourceEvent::Open));
+            let closed = source.clone();
+            let onerror = handler(
+                &tx,
+                Box::new(move |_| {
+                    // The browser opens the stream again by itself unless it gave up on it
+                    if closed.ready_state() == EventSource::CLOSED {
+                        SourceEvent::Closed
+                    } else {
+                        SourceEvent::Interrupted
+                    }
+                }),
+            );
+            source.set_onopen(Some(onopen.as_ref().unchecked_ref()));
+            source.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+
+            let mut event_types = vec!["message".to_string()];
+            event_types.extend(options.event_types.iter().cloned());
+
+            let mut handlers = vec![onopen, onerror];
+            for event_type in &event_types {
+                let name = event_type.clone();
+                let listener = handler(
+                    &tx,
+                    Box::new(move |event| {
+                        let event = event.unchecked_into::<MessageEvent>();
+                        let id = event.last_event_id();
+                        SourceEvent::Event(ServerSentEvent {
+                            event: name.clone(),
+                            data: event.data().as_string().unwrap_or_default(),
+                            id: (!id.is_empty()).then_some(id),
+                        })
+                    }),
+                );
+                let _ = source.add_event_listener_with_callback(
+                    event_type,
+                    listener.as_ref().unchecked_ref(),
+                );
+                handlers.push(listener);
+            }
+
+            Self {
+                source: Some(source),
+                event_types,
+                _handlers: handlers,
+                events,
+            }
+        }
+    }
+
+    impl Drop for Connection {
+        fn drop(&mut self) {
+            if let Some(source) = &self.source {
+                source.set_onopen(None);
+                source.set_onerror(None);
+                for (event_type, listener) in self.event_types.iter().zip(&self._handlers[2..]) {
+                    let _ = source.remove_event_listener_with_callback(
+                        event_type,
+                        listener.as_ref().unchecked_ref(),
+                    );
+                }
+                source.close();
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod platform {
+    use super::{EventSourceOptions, EventStreamParser, SourceEvent};
+    use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+    use std::io::{BufRead, BufReader};
+
+    /// A stream that's read on its own thread, sending what it reads to the driver through a channel
+    ///
+    /// Dropping the connection closes the channel, which stops the thread after the next line it reads.
+    pub(super) struct Connection {
+        pub(super) events: UnboundedReceiver<SourceEvent>,
+    }
+
+    impl Connection {
+        pub(super) fn open(
+            url: &str,
+            last_id: Option<&str>,
+            _options: &EventSourceOptions,
+        ) -> Self {
+            let (events_tx, events) = unbounded();
+
+            let url = url.to_string();
+            let last_id = last_id.map(str::to_string);
+            std::thread::spawn(move || run(url, last_id, events_tx));
+
+            Self { events }
+        }
+    }
+
+    fn run(url: String, last_id: Option<String>, events: UnboundedSender<SourceEvent>) {
+        let client = reqwest::blocking::Client::builder().timeout(None).build();
+        let mut request = match client {
+            Ok(client) => client.get(&url).header("Accept", "text/event-stream"),
+            Err(err) => {
+                log::error!("Failed to open an event source to {}: {}", url, err);
+                let _ = events.unbounded_send(SourceEvent::Closed);
+                return;
+            }
+        };
+        if let Some(id) = &last_id {
+            request = request.header("Last-Event-ID", id);
+        }
+
+        let response = match request
+            .send()
+            .and_then(|response| response.error_for_status())
+        {
+            Ok(response) => response,
+            Err(err) => {
+                log::error!("Failed to open an event source to {}: {}", url, err);
+                let _ = events.unbounded_send(SourceEvent::Closed);
+                return;
+            }
+        };
+        let _ = events.unbounded_send(SourceEvent::Open);
+
+        let mut parser = EventStreamParser::new(last_id);
+        let mut reader = BufReader::new(response);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match reader.read_line(&mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
+
+            let line = line.trim_end_matches(['\n', '\r']);
+            if let Some(event) = parser.line(line) {
+                if events.unbounded_send(event).is_err() {
+                    return;
+                }
+            }
+        }
+
+        let _ = events.unbounded_send(SourceEvent::Closed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::prelude::*;
+    use std::{io::Write, net::TcpListener};
+
+    fn parse(stream: &str) -> Vec<ServerSentEvent> {
+        let mut parser = EventStreamParser::new(None);
+        stream
+            .lines()
+            .filter_map(|line| match parser.line(line) {
+                Some(SourceEvent::Event(event)) => Some(event),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_event_streams() {
+        let events = parse(
+            ": keep alive\n\ndata: first\ndata: second\n\nevent: price\nid: 7\ndata:42\n\nretry: 100\n\n",
+        );
+        assert_eq!(
+            events,
+            [
+                ServerSentEvent {
+                    event: "message".to_string(),
+                    data: "first\nsecond".to_string(),
+                    id: None,
+                },
+                ServerSentEvent {
+                    event: "price".to_string(),
+                    data: "42".to_string(),
+                    id: Some("7".to_string()),
+                },
+            ]
+        );
+    }
+
+    thread_local! {
+        static URL: RefCell<String> = RefCell::new(String::new());
+        static RECEIVED: RefCell<Vec<String>> = RefCell::new(Vec::new());
+    }
+
+    #[test]
+    fn streams_events() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/events", listener.local_addr().unwrap());
+        URL.with(|u| *u.borrow_mut() = url);
+
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = stream.write_all(
+                b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\n\r\ndata: hello\n\n",
+            );
+            // Keep the stream open while the app reads it
+            std::thread::sleep(Duration::from_secs(5));
+        });
+
+        fn app(cx: Scope) -> Element {
+            let source = use_event_source(cx, URL.with(|u| u.borrow().clone()));
+
+            while let Some(event) = source.try_next_event() {
+                RECEIVED.with(|r| r.borrow_mut().push(event.data));
+            }
+
+            None
+        }
+
+        let mut dom = VirtualDom::new(app);
+        _ = dom.rebuild();
+
+        for _ in 0..100 {
+            std::thread::sleep(Duration::from_millis(20));
+            dom.process_events();
+            _ = dom.render_immediate();
+
+            if RECEIVED.with(|r| !r.borrow().is_empty()) {
+                break;
+            }
+        }
+
+        assert_eq!(RECEIVED.with(|r| r.borrow().clone()), ["hello"]);
+    }
+}
@@ -0,0 +1,431 @@
+use dioxus_core::ScopeState;
+use dioxus_html::FormData;
+use serde::de::DeserializeOwned;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    rc::Rc,
+    sync::Arc,
+};
+
+/// Track the values of a form, validate them, and turn them into a typed struct when it's submitted
+///
+/// Fields are registered once with [`FormFields::field`], along with any validators. After that, the form tracks for
+/// every field:
+///
+/// - its current value, set from `oninput` with [`UseForm::set`]
+/// - whether it was touched, usually from `onblur` with [`UseForm::touch`]
+/// - whether it's dirty, meaning it's different from its initial value
+/// - the error from its validators, if any
+///
+/// Synchronous validators run whenever a field changes. Async validators only run when the form is submitted.
+///
+/// [`UseForm::submit`] is meant to be awaited from an async `onsubmit` handler. It validates every field, and if they
+/// all pass, deserializes the values of the form into `T` with the same rules as a url-encoded form. The web and
+/// desktop renderers prevent the browser's default submission of forms handled by dioxus.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// #[derive(serde::Deserialize)]
+/// struct Signup {
+///     email: String,
+///     age: u8,
+/// }
+///
+/// let form = use_form::<Signup>(cx, |fields| {
+///     fields
+///         .field("email")
+///         .validate(|email| if email.contains('@') { Ok(()) } else { Err("Not an email".into()) })
+///         .validate_async(|email| async move { check_email_is_free(email).await });
+///     fields.field("age").initial("18");
+/// });
+///
+/// cx.render(rsx!{
+///     form {
+///         onsubmit: move |evt| {
+///             to_owned![form];
+///             async move {
+///                 if let Some(signup) = form.submit(&evt).await {
+///                     create_account(signup).await;
+///                 }
+///             }
+///         },
+///         input { name: "email", value: "{form.value(\"email\")}", oninput: move |evt| form.set("email", &evt.value), onblur: move |_| form.touch("email") }
+///         form.error("email").map(|err| rsx!( p { "{err}" } ))
+///         input { name: "age", r#type: "number", value: "{form.value(\"age\")}", oninput: move |evt| form.set("age", &evt.value) }
+///         button { "Sign up" }
+///     }
+/// })
+/// ```
+pub fn use_form<T: DeserializeOwned + 'static>(
+    cx: &ScopeState,
+    fields: impl FnOnce(&mut FormFields),
+) -> &UseForm<T> {
+    cx.use_hook(|| {
+        let mut registered = FormFields::default();
+        fields(&mut registered);
+
+        let values = registered
+            .fields
+            .iter()
+            .map(|(name, field)| (name.clone(), field.initial.clone()))
+            .collect();
+
+        UseForm {
+            inner: Rc::new(FormInner {
+                fields: registered.fields,
+                state: RefCell::new(FormState {
+                    values,
+                    ..Default::default()
+                }),
+                update: cx.schedule_update(),
+            }),
+            _marker: PhantomData,
+        }
+    })
+}
+
+/// The fields of a [`use_form`], registered when the hook is first run
+#[derive(Default)]
+pub struct FormFields {
+    fields: HashMap<String, FormField>,
+}
+
+impl FormFields {
+    /// Register a field by the `name` it has in the form
+    pub fn field(&mut self, name: impl Into<String>) -> &mut FormField {
+        self.fields.entry(name.into()).or_default()
+    }
+}
+
+type Validator = Box<dyn Fn(&str) -> Result<(), String>>;
+type AsyncValidator = Box<dyn Fn(String) -> Pin<Box<dyn Future<Output = Result<(), String>>>>>;
+
+/// A field of a [`use_form`]
+#[derive(Default)]
+pub struct FormField {
+    initial: String,
+    validators: Vec<Validator>,
+    async_validators: Vec<AsyncValidator>,
+}
+
+impl FormField {
+    /// Set the value the field starts with. A field is dirty when its value is different from this.
+    pub fn initial(&mut self, value: impl Into<String>) -> &mut Self {
+        self.initial = value.into();
+        self
+    }
+
+    /// Check the value whenever it changes. The first error is shown for the field.
+    pub fn validate(&mut self, f: impl Fn(&str) -> Result<(), String> + 'static) -> &mut Self {
+        self.validators.push(Box::new(f));
+        self
+    }
+
+    /// Check the value when the form is submitted, after every synchronous validator passed
+    pub fn validate_async<F>(&mut self, f: impl Fn(String) -> F + 'static) -> &mut Self
+    where
+        F: Future<Output = Result<(), String>> + 'static,
+    {
+        self.async_validators
+            .push(Box::new(move |value| Box::pin(f(value))));
+        self
+    }
+
+    fn check(&self, value: &str) -> Result<(), String> {
+        self.validators.iter().try_for_each(|f| f(value))
+    }
+}
+
+/// A handle to a [`use_form`]
+pub struct UseForm<T> {
+    inner: Rc<FormInner>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Clone for UseForm<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+struct FormInner {
+    fields: HashMap<String, FormField>,
+    state: RefCell<FormState>,
+    update: Arc<dyn Fn()>,
+}
+
+#[derive(Default)]
+struct FormState {
+    values: HashMap<String, String>,
+    touched: HashSet<String>,
+    errors: HashMap<String, String>,
+    form_error: Option<String>,
+    submitting: bool,
+}
+
+impl<T: DeserializeOwned> UseForm<T> {
+    /// Get the current value of a field
+    pub fn value(&self, name: &str) -> String {
+        self.inner
+            .state
+            .borrow()
+            .values
+            .get(name)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Change the value of a field and run its validators
+    pub fn set(&self, name: &str, value: &str) {
+        {
+            let mut state = self.inner.state.borrow_mut();
+            state.values.insert(name.to_string(), value.to_string());
+            self.inner.check_field(&mut state, name);
+        }
+        (self.inner.update)();
+    }
+
+    /// Mark a field as touched, showing its errors
+    pub fn touch(&self, name: &str) {
+        let newly_touched = self
+            .inner
+            .state
+            .borrow_mut()
+            .touched
+            .insert(name.to_string());
+        if newly_touched {
+            (self.inner.update)();
+        }
+    }
+
+    /// Check if the field was touched or the form was submitted
+    pub fn is_touched(&self, name: &str) -> bool {
+        self.inner.state.borrow().touched.contains(name)
+    }
+
+    /// Check if the value of the field is different from its initial value
+    pub fn is_dirty(&self, name: &str) -> bool {
+        let initial = self
+            .inner
+            .fields
+            .get(name)
+            .map_or("", |field| field.initial.as_str());
+        self.value(name) != initial
+    }
+
+    /// Get the error of a field, once it was touched
+    pub fn error(&self, name: &str) -> Option<String> {
+        let state = self.inner.state.borrow();
+        match state.touched.contains(name) {
+            true => state.errors.get(name).cloned(),
+            false => None,
+        }
+    }
+
+    /// Get the error that kept the values from becoming a `T` on the last submission
+    pub fn form_error(&self) -> Option<String> {
+        self.inner.state.borrow().form_error.clone()
+    }
+
+    /// Check if every field passes its synchronous validators
+    pub fn is_valid(&self) -> bool {
+        self.inner.state.borrow().errors.is_empty()
+    }
+
+    /// Check if a submission is waiting on async validators
+    pub fn is_submitting(&self) -> bool {
+        self.inner.state.borrow().submitting
+    }
+
+    /// Validate the whole form and try to turn it into a `T`
+    ///
+    /// The values from the event are merged into the form first, so fields that aren't updated with [`UseForm::set`]
+    /// are still submitted. Fields with several values contribute their first one. Every field is marked as touched so
+    /// its errors show up.
+    pub fn submit(&self, data: &FormData) -> impl Future<Output = Option<T>> + 'static
+    where
+        T: 'static,
+    {
+        let inner = self.inner.clone();
+
+        // Run the synchronous half right away, so the errors show up even if the future is dropped
+        let pending = {
+            let mut state = inner.state.borrow_mut();
+            state.values.extend(
+                data.values
+                    .iter()
+                    .filter_map(|(name, values)| Some((name.clone(), values.first()?.clone()))),
+            );
+            state.form_error = None;
+
+            for name in inner.fields.keys() {
+                state.touched.insert(name.clone());
+                inner.check_field(&mut state, name);
+            }
+
+            match state.errors.is_empty() {
+                true => {
+                    state.submitting = true;
+                    inner
+                        .fields
+                        .iter()
+                        .flat_map(|(name, field)| {
+                            let value = state.values.get(name).cloned().unwrap_or_default();
+                            field
+                                .async_validators
+                                .iter()
+                                .map(move |f| (name.clone(), f(value.clone())))
+                        })
+                        .collect::<Vec<_>>()
+                }
+                false => Vec::new(),
+            }
+        };
+        (inner.update)();
+
+        async move {
+            if !inner.state.borrow().submitting {
+                return None;
+            }
+
+            let mut errors = HashMap::new();
+            for (name, validation) in pending {
+                if let Err(err) = validation.await {
+                    errors.entry(name).or_insert(err);
+                }
+            }
+
+            let mut state = inner.state.borrow_mut();
+            state.submitting = false;
+            state.errors.extend(errors);
+
+            let value = match state.errors.is_empty() {
+                true => parse_values(&state.values)
+                    .map_err(|err| state.form_error = Some(err))
+                    .ok(),
+                false => None,
+            };
+            drop(state);
+
+            (inner.update)();
+            value
+        }
+    }
+}
+
+impl FormInner {
+    fn check_field(&self, state: &mut FormState, name: &str) {
+        let field = match self.fields.get(name) {
+            Some(field) => field,
+            None => return,
+        };
+
+        let value = state.values.get(name).map_or("", String::as_str);
+        match field.check(value) {
+            Ok(()) => state.errors.remove(name),
+            Err(err) => state.errors.insert(name.to_string(), err),
+        };
+    }
+}
+
+/// Deserialize the values like they were sent as a url-encoded form
+fn parse_values<T: DeserializeOwned>(values: &HashMap<String, String>) -> Result<T, String> {
+    let encoded = serde_urlencoded::to_string(values).map_err(|err| err.to_string())?;
+    serde_urlencoded::from_str(&encoded).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::prelude::*;
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Signup {
+        email: String,
+        age: u8,
+    }
+
+    thread_local! {
+        static FORM: RefCell<Option<UseForm<Signup>>> = RefCell::new(None);
+    }
+
+    fn form() -> UseForm<Signup> {
+        FORM.with(|f| f.borrow().clone().unwrap())
+    }
+
+    fn app(cx: Scope) -> Element {
+        let form = use_form::<Signup>(cx, |fields| {
+            fields
+                .field("email")
+                .validate(|email| match email.contains('@') {
+                    true => Ok(()),
+                    false => Err("Not an email".to_string()),
+                });
+            fields.field("age").initial("18");
+        });
+        FORM.with(|f| *f.borrow_mut() = Some(form.clone()));
+        None
+    }
+
+    fn submit(values: &[(&str, &str)]) -> Option<Signup> {
+        let data = FormData {
+            value: String::new(),
+            values: values
+                .iter()
+                .map(|(k, v)| (k.to_string(), vec![v.to_string()]))
+                .collect(),
+            files: None,
+        };
+
+        // Without async validators, the submission finishes on the first poll
+        let mut fut = Box::pin(form().submit(&data));
+        let waker = futures_util::task::noop_waker();
+        match fut
+            .as_mut()
+            .poll(&mut std::task::Context::from_waker(&waker))
+        {
+            std::task::Poll::Ready(value) => value,
+            std::task::Poll::Pending => panic!("{}", "the submission should be done"),
+        }
+    }
+
+    #[test]
+    fn validates_and_parses() {
+        let mut dom = VirtualDom::new(app);
+        _ = dom.rebuild();
+
+        let form = form();
+        form.set("email", "nope");
+        assert!(form.is_dirty("email"));
+        assert!(!form.is_dirty("age"));
+        assert!(!form.is_valid());
+
+        // Errors only show up once the field was touched
+        assert_eq!(form.error("email"), None);
+        form.touch("email");
+        assert_eq!(form.error("email").as_deref(), Some("Not an email"));
+
+        assert_eq!(submit(&[]), None);
+
+        form.set("email", "me@example.com");
+        assert_eq!(
+            submit(&[("age", "30")]),
+            Some(Signup {
+                email: "me@example.com".to_string(),
+                age: 30
+            })
+        );
+
+        assert_eq!(submit(&[("age", "old")]), None);
+        assert!(form.form_error().is_some());
+    }
+}
@@ -1,5 +1,5 @@
 #![allow(missing_docs)]
-use dioxus_core::{ScopeState, TaskId};
+use dioxus_core::{ScopeState, TaskHandle, TaskId};
 use std::{
     any::Any,
     cell::{Cell, RefCell},
@@ -18,7 +18,7 @@ use std::{
 ///
 /// Whenever the hooks dependencies change, the future will be re-evaluated.
 /// If a future is pending when the dependencies change, the previous future
-/// will be allowed to continue
+/// is cancelled, so a stale result can never overwrite a fresh one.
 ///
 /// - dependencies: a tuple of references to values that are PartialEq + Clone
 pub fn use_future<T, F, D>(
@@ -35,7 +35,8 @@ where
         update: cx.schedule_update(),
         needs_regen: Cell::new(true),
         values: Default::default(),
-        task: Cell::new(None),
+        task: RefCell::new(None),
+        status: Rc::new(Cell::new(Status::Running)),
         dependencies: Vec::new(),
         waker: Default::default(),
     });
@@ -51,17 +52,22 @@ where
 
         // Clone in our cells
         let values = state.values.clone();
+        let status = state.status.clone();
         let schedule_update = state.update.clone();
         let waker = state.waker.clone();
 
-        // Cancel the current future
+        // Cancel the current future. Once it finished, its id might belong to another task
         if let Some(current) = state.task.take() {
-            cx.remove_future(current);
+            if !current.is_finished() {
+                cx.remove_future(current.id());
+            }
         }
 
-        state.task.set(Some(cx.push_future(async move {
+        state.status.set(Status::Running);
+        state.task.replace(Some(cx.spawn(async move {
             let res = fut.await;
             values.borrow_mut().push(Box::leak(Box::new(res)));
+            status.set(Status::Done);
 
             // if there's a waker, we dont re-render the component. Instead we just progress that future
             match waker.borrow().as_ref() {
@@ -80,26 +86,43 @@ pub enum FutureState<'a, T> {
     Regenerating(&'a T), // the old value
 }
 
+/// Whether the latest run of a [`UseFuture`] is still going
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Status {
+    Running,
+    Done,
+    Cancelled,
+}
+
 pub struct UseFuture<T> {
     update: Arc<dyn Fn()>,
     needs_regen: Cell<bool>,
-    task: Cell<Option<TaskId>>,
+    task: RefCell<Option<TaskHandle>>,
+    status: Rc<Cell<Status>>,
     dependencies: Vec<Box<dyn Any>>,
     waker: Rc<RefCell<Option<std::task::Waker>>>,
     values: Rc<RefCell<Vec<*mut T>>>,
 }
 
+/// The state of a [`UseFuture`], from [`UseFuture::state`]
 pub enum UseFutureState<'a, T> {
+    /// The future is running and has never produced a value
     Pending,
-    Complete(&'a T),
+
+    /// The future is running again, holding onto the value from the last run
     Reloading(&'a T),
+
+    /// The latest run of the future finished with this value
+    Ready(&'a T),
+
+    /// The future was cancelled with [`UseFuture::cancel`] and won't run again until it's restarted
+    Cancelled,
 }
 
 impl<T> UseFuture<T> {
     /// Restart the future with new dependencies.
     ///
-    /// Will not cancel the previous future, but will ignore any values that it
-    /// generates.
+    /// The component is rendered again, which cancels the running future and starts a new one.
     pub fn restart(&self) {
         self.needs_regen.set(true);
         (self.update)();
@@ -108,7 +131,10 @@ impl<T> UseFuture<T> {
     /// Forcefully cancel a future
     pub fn cancel(&self, cx: &ScopeState) {
         if let Some(task) = self.task.take() {
-            cx.remove_future(task);
+            if !task.is_finished() {
+                self.status.set(Status::Cancelled);
+                cx.remove_future(task.id());
+            }
         }
     }
 
@@ -140,25 +166,17 @@ impl<T> UseFuture<T> {
 
     /// Get the ID of the future in Dioxus' internal scheduler
     pub fn task(&self) -> Option<TaskId> {
-        self.task.get()
+        self.task.borrow().as_ref().map(TaskHandle::id)
     }
 
-    /// Get the current stateof the future.
+    /// Get the current state of the future.
     pub fn state(&self) -> UseFutureState<T> {
-        todo!()
-        // match (&self.task.get(), &self.value) {
-        //     // If we have a task and an existing value, we're reloading
-        //     (Some(_), Some(val)) => UseFutureState::Reloading(val),
-
-        //     // no task, but value - we're done
-        //     (None, Some(val)) => UseFutureState::Complete(val),
-
-        //     // no task, no value - something's wrong? return pending
-        //     (None, None) => UseFutureState::Pending,
-
-        //     // Task, no value - we're still pending
-        //     (Some(_), None) => UseFutureState::Pending,
-        // }
+        match (self.status.get(), self.value()) {
+            (Status::Cancelled, _) => UseFutureState::Cancelled,
+            (Status::Running, Some(val)) => UseFutureState::Reloading(val),
+            (Status::Done, Some(val)) => UseFutureState::Ready(val),
+            (_, None) => UseFutureState::Pending,
+        }
     }
 }
 
@@ -343,4 +361,59 @@ mod tests {
             todo!()
         }
     }
+
+    #[test]
+    fn restart_cancels_previous_future() {
+        use dioxus_core::prelude::*;
+        use std::cell::Cell;
+
+        thread_local! {
+            static DEP: Cell<i32> = Cell::new(0);
+            static DROPPED: Cell<bool> = Cell::new(false);
+            static STATE: Cell<Option<Option<i32>>> = Cell::new(None);
+        }
+
+        struct Guard;
+        impl Drop for Guard {
+            fn drop(&mut self) {
+                DROPPED.with(|d| d.set(true));
+            }
+        }
+
+        fn app(cx: Scope) -> Element {
+            let dep = DEP.with(Cell::get);
+
+            let fut = use_future(cx, (&dep,), |(dep,)| async move {
+                if dep == 0 {
+                    let _guard = Guard;
+                    std::future::pending::<()>().await;
+                }
+                dep
+            });
+
+            let state = match fut.state() {
+                UseFutureState::Ready(val) => Some(*val),
+                _ => None,
+            };
+            STATE.with(|s| s.set(Some(state)));
+
+            None
+        }
+
+        let mut dom = VirtualDom::new(app);
+        _ = dom.rebuild();
+        dom.process_events();
+        assert_eq!(STATE.with(Cell::get), Some(None));
+        assert!(!DROPPED.with(Cell::get));
+
+        // Changing the dependency throws away the future that never finished
+        DEP.with(|d| d.set(1));
+        dom.mark_dirty(ScopeId(0));
+        _ = dom.render_immediate();
+        assert!(DROPPED.with(Cell::get));
+
+        dom.process_events();
+        _ = dom.render_immediate();
+        assert_eq!(STATE.with(Cell::get), Some(Some(1)));
+    }
 }
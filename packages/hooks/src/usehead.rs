@@ -0,0 +1,466 @@
+use dioxus_core::ScopeState;
+use serde_json::{json, Value};
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Write,
+    rc::Rc,
+};
+
+/// Put an element in the `<head>` of the document for as long as the component is mounted
+///
+/// Components anywhere in the tree can add to the head. Elements that describe the same thing, like two titles or two
+/// `<meta name="description">`, are deduplicated: the one added last wins, so the title of a route replaces the title
+/// of the app while the route is rendered, and the title of the app comes back when it isn't. The element is updated
+/// whenever the component renders it differently, and removed when the component is unmounted.
+///
+/// The head is kept by the renderer through a [`HeadContext`], and the hook does nothing without one. Web, desktop and
+/// LiveView update `document.head` as it changes, and `dioxus_ssr::render_head` renders it for the page. Most apps use
+/// the components in [`head`](crate::head) instead of calling the hook themselves.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// use_head(cx, HeadElement::title(format!("{} - My blog", post.title)));
+/// use_head(cx, HeadElement::meta().attribute("name", "description").attribute("content", &post.summary));
+/// ```
+pub fn use_head(cx: &ScopeState, element: HeadElement) {
+    let entry = cx.use_hook(|| HeadEntry::new(cx.consume_context::<HeadContext>()));
+    entry.set(element);
+}
+
+/// An element of the `<head>` of the document, added with [`use_head`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeadElement {
+    tag: &'static str,
+    attributes: Vec<(&'static str, String)>,
+    content: String,
+    key: Option<String>,
+}
+
+impl HeadElement {
+    /// An element with a tag and nothing else
+    pub fn new(tag: &'static str) -> Self {
+        Self {
+            tag,
+            attributes: Vec::new(),
+            content: String::new(),
+            key: None,
+        }
+    }
+
+    /// The `<title>` of the document
+    pub fn title(text: impl Into<String>) -> Self {
+        Self::new("title").content(text)
+    }
+
+    /// A `<meta>` element
+    pub fn meta() -> Self {
+        Self::new("meta")
+    }
+
+    /// A `<link>` element
+    pub fn link() -> Self {
+        Self::new("link")
+    }
+
+    /// A `<script>` element
+    pub fn script() -> Self {
+        Self::new("script")
+    }
+
+    /// Set an attribute of the element
+    pub fn attribute(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        let value = value.into();
+        match self.attributes.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, old)) => *old = value,
+            None => self.attributes.push((name, value)),
+        }
+        self
+    }
+
+    /// Set the text inside the element, like the text of a title or the code of an inline script
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        self.content = content.into();
+        self
+    }
+
+    /// Deduplicate the element with the other elements that have the same key, instead of the key it's given
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// The tag of the element
+    pub fn tag(&self) -> &'static str {
+        self.tag
+    }
+
+    /// The attributes of the element, in the order they were set
+    pub fn attributes(&self) -> &[(&'static str, String)] {
+        &self.attributes
+    }
+
+    /// Get the value of an attribute of the element
+    pub fn get_attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The text inside the element
+    pub fn text(&self) -> &str {
+        &self.content
+    }
+
+    /// What the element describes, which only one element in the head can
+    ///
+    /// There's one title and one `<link rel="canonical">`. Meta elements are the same if they have the same `charset`,
+    /// `name`, `property`, `http-equiv` or `itemprop`, links if they have the same `rel` and `href`, and scripts if they
+    /// have the same `src`, or the same code if they're inline. Any other element is only the same as an element with
+    /// the same attributes.
+    pub fn dedupe_key(&self) -> String {
+        if let Some(key) = &self.key {
+            return key.clone();
+        }
+
+        let attribute = |name| self.get_attribute(name).unwrap_or_default();
+        match self.tag {
+            "title" => "title".to_string(),
+            "meta" if self.get_attribute("charset").is_some() => "meta:charset".to_string(),
+            "meta" => {
+                match ["name", "property", "http-equiv", "itemprop"]
+                    .iter()
+                    .find_map(|name| Some((*name, self.get_attribute(name)?)))
+                {
+                    Some((name, value)) => format!("meta:{}={}", name, value),
+                    None => self.attributes_key(),
+                }
+            }
+            "link" if attribute("rel") == "canonical" => "link:canonical".to_string(),
+            "link" => format!("link:{}:{}", attribute("rel"), attribute("href")),
+            "script" if self.get_attribute("src").is_some() => {
+                format!("script:{}", attribute("src"))
+            }
+            "script" => format!("script:{}", self.content),
+            _ => self.attributes_key(),
+        }
+    }
+
+    fn attributes_key(&self) -> String {
+        let mut key = self.tag.to_string();
+        for (name, value) in &self.attributes {
+            _ = write!(key, ":{}={}", name, value);
+        }
+        key
+    }
+
+    /// The element as JSON, for renderers that update the head with a script
+    ///
+    /// It's an object with the `tag`, the dedupe `key`, the `attributes` as `[name, value]` pairs and the `content`.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "tag": self.tag,
+            "key": self.dedupe_key(),
+            "attributes": self.attributes,
+            "content": self.content,
+        })
+    }
+
+    /// Render the element as html
+    ///
+    /// Everything but the title is marked with its dedupe key in [`HEAD_ATTRIBUTE`], so a client that takes over a
+    /// page rendered on the server replaces the elements instead of adding them again.
+    pub fn to_html(&self) -> String {
+        let mut html = format!("<{}", self.tag);
+        for (name, value) in &self.attributes {
+            _ = write!(html, " {}=\"{}\"", name, escape(value, true));
+        }
+        if self.tag != "title" {
+            _ = write!(
+                html,
+                " {}=\"{}\"",
+                HEAD_ATTRIBUTE,
+                escape(&self.dedupe_key(), true)
+            );
+        }
+        html.push('>');
+
+        match self.tag {
+            "meta" | "link" | "base" => return html,
+            // the code of a script isn't escaped, but it can't end the script early
+            "script" | "style" => html.push_str(&self.content.replace("</", "<\\/")),
+            _ => html.push_str(&escape(&self.content, false)),
+        }
+        _ = write!(html, "</{}>", self.tag);
+        html
+    }
+}
+
+/// The attribute that the elements added to the head by [`use_head`] carry their dedupe key in
+pub const HEAD_ATTRIBUTE: &str = "data-dioxus-head";
+
+fn escape(text: &str, attribute: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' if attribute => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The head of a document, provided by renderers for [`use_head`]
+pub trait HeadBackend {
+    /// Make the head match the elements that were added to it
+    ///
+    /// The elements are already deduplicated. The title is always the last one that was added, and the renderer
+    /// should go back to its own title when there isn't one.
+    fn update(&self, elements: &[HeadElement]);
+}
+
+/// The context that collects the elements of [`use_head`] and hands them to a [`HeadBackend`]
+///
+/// The default context only collects the elements, which is what server side rendering needs.
+#[derive(Clone, Default)]
+pub struct HeadContext(Rc<HeadInner>);
+
+#[derive(Default)]
+struct HeadInner {
+    backend: Option<Rc<dyn HeadBackend>>,
+    entries: RefCell<Vec<(usize, HeadElement)>>,
+    next_id: Cell<usize>,
+}
+
+impl HeadContext {
+    /// Wrap the head of a renderer so it can be provided as a context
+    pub fn new(backend: impl HeadBackend + 'static) -> Self {
+        Self(Rc::new(HeadInner {
+            backend: Some(Rc::new(backend)),
+            ..Default::default()
+        }))
+    }
+
+    /// The elements in the head, deduplicated, in the order they were first added
+    pub fn elements(&self) -> Vec<HeadElement> {
+        let entries = self.0.entries.borrow();
+        let mut elements: Vec<(String, &HeadElement)> = Vec::with_capacity(entries.len());
+        for (_, element) in entries.iter() {
+            let key = element.dedupe_key();
+            match elements.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, old)) => *old = element,
+                None => elements.push((key, element)),
+            }
+        }
+        elements
+            .into_iter()
+            .map(|(_, element)| element.clone())
+            .collect()
+    }
+
+    /// Render the elements in the head as html, to go in the `<head>` of a page
+    pub fn to_html(&self) -> String {
+        self.elements().iter().map(HeadElement::to_html).collect()
+    }
+
+    fn next_id(&self) -> usize {
+        let id = self.0.next_id.get();
+        self.0.next_id.set(id + 1);
+        id
+    }
+
+    fn set(&self, id: usize, element: HeadElement) {
+        {
+            let mut entries = self.0.entries.borrow_mut();
+            match entries.iter_mut().find(|(i, _)| *i == id) {
+                Some((_, old)) if *old == element => return,
+                Some((_, old)) => *old = element,
+                None => entries.push((id, element)),
+            }
+        }
+        self.sync();
+    }
+
+    fn remove(&self, id: usize) {
+        let removed = {
+            let mut entries = self.0.entries.borrow_mut();
+            let len = entries.len();
+            entries.retain(|(i, _)| *i != id);
+            entries.len() != len
+        };
+        if removed {
+            self.sync();
+        }
+    }
+
+    fn sync(&self) {
+        if let Some(backend) = &self.0.backend {
+            backend.update(&self.elements());
+        }
+    }
+}
+
+/// The element a component added to the head, which is taken out again when the component is unmounted
+struct HeadEntry {
+    head: Option<HeadContext>,
+    id: usize,
+}
+
+impl HeadEntry {
+    fn new(head: Option<HeadContext>) -> Self {
+        let id = head.as_ref().map_or(0, HeadContext::next_id);
+        Self { head, id }
+    }
+
+    fn set(&self, element: HeadElement) {
+        if let Some(head) = &self.head {
+            head.set(self.id, element);
+        }
+    }
+}
+
+impl Drop for HeadEntry {
+    fn drop(&mut self) {
+        if let Some(head) = &self.head {
+            head.remove(self.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::prelude::*;
+
+    #[derive(Default)]
+    struct RecordedHead(Rc<RefCell<Vec<Vec<HeadElement>>>>);
+
+    impl HeadBackend for RecordedHead {
+        fn update(&self, elements: &[HeadElement]) {
+            self.0.borrow_mut().push(elements.to_vec());
+        }
+    }
+
+    #[test]
+    fn dedupes_by_what_the_element_describes() {
+        let keys = [
+            HeadElement::title("a"),
+            HeadElement::meta().attribute("charset", "utf-8"),
+            HeadElement::meta()
+                .attribute("name", "description")
+                .attribute("content", "a"),
+            HeadElement::link()
+                .attribute("rel", "canonical")
+                .attribute("href", "/a"),
+            HeadElement::link()
+                .attribute("rel", "stylesheet")
+                .attribute("href", "/a.css"),
+            HeadElement::script().attribute("src", "/a.js"),
+            HeadElement::script().key("analytics"),
+        ]
+        .map(|element| element.dedupe_key());
+        assert_eq!(
+            keys,
+            [
+                "title",
+                "meta:charset",
+                "meta:name=description",
+                "link:canonical",
+                "link:stylesheet:/a.css",
+                "script:/a.js",
+                "analytics",
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_html() {
+        assert_eq!(
+            HeadElement::title("Fish & <Chips>").to_html(),
+            "<title>Fish &amp; &lt;Chips&gt;</title>"
+        );
+        assert_eq!(
+            HeadElement::meta()
+                .attribute("name", "description")
+                .attribute("content", "\"quoted\"")
+                .to_html(),
+            "<meta name=\"description\" content=\"&quot;quoted&quot;\" data-dioxus-head=\"meta:name=description\">"
+        );
+        assert_eq!(
+            HeadElement::script()
+                .content("let a = \"</script>\";")
+                .key("inline")
+                .to_html(),
+            "<script data-dioxus-head=\"inline\">let a = \"<\\/script>\";</script>"
+        );
+    }
+
+    #[test]
+    fn the_last_element_added_wins_until_it_is_removed() {
+        let recorded = RecordedHead::default();
+        let updates = recorded.0.clone();
+        let head = HeadContext::new(recorded);
+        let titles = || {
+            head.elements()
+                .iter()
+                .map(|element| element.text().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let app = HeadEntry::new(Some(head.clone()));
+        app.set(HeadElement::title("App"));
+        let route = HeadEntry::new(Some(head.clone()));
+        route.set(HeadElement::title("Route"));
+        assert_eq!(titles(), ["Route"]);
+
+        // the app renders its title again, which doesn't take it over from the route
+        app.set(HeadElement::title("App again"));
+        assert_eq!(titles(), ["Route"]);
+
+        drop(route);
+        assert_eq!(titles(), ["App again"]);
+        assert_eq!(updates.borrow().len(), 4);
+
+        // setting the same element doesn't touch the head
+        app.set(HeadElement::title("App again"));
+        assert_eq!(updates.borrow().len(), 4);
+    }
+
+    #[test]
+    fn removed_when_unmounted() {
+        fn app(cx: Scope) -> Element {
+            use_head(cx, HeadElement::title("App"));
+            use_head(
+                cx,
+                HeadElement::meta()
+                    .attribute("name", "description")
+                    .attribute("content", "An app"),
+            );
+            None
+        }
+
+        let recorded = RecordedHead::default();
+        let updates = recorded.0.clone();
+        let head = HeadContext::new(recorded);
+
+        let mut dom = VirtualDom::new(app);
+        dom.base_scope().provide_context(head.clone());
+        _ = dom.rebuild();
+        assert_eq!(
+            head.to_html(),
+            "<title>App</title><meta name=\"description\" content=\"An app\" data-dioxus-head=\"meta:name=description\">"
+        );
+
+        dom.mark_dirty(ScopeId(0));
+        _ = dom.render_immediate();
+        assert_eq!(updates.borrow().len(), 2);
+
+        drop(dom);
+        assert!(head.elements().is_empty());
+        assert!(updates.borrow().last().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,248 @@
+use dioxus_core::{Event, ScopeState};
+use std::{any::Any, cell::RefCell, rc::Rc};
+
+/// Run a handler for an event of the whole document, like a `keydown` anywhere on the page
+///
+/// The listener is attached to the document itself rather than to an element of the component, so it hears every event
+/// that bubbles up to it. The handler gets the same typed data an element listener for the event would get, which
+/// decides what it's called with: a `keydown` handler takes a [`KeyboardEvent`](dioxus_html::KeyboardEvent). Events
+/// whose data doesn't match the handler are skipped.
+///
+/// The listener is provided by the renderer through a [`GlobalListenerContext`], which the web, desktop and liveview
+/// renderers do. It's removed when the component is. The event is read when the hook is first run, while the handler
+/// is replaced on every render so it always sees the latest state it captured.
+///
+/// On web the handler runs while the browser dispatches the event, so [`Event::prevent_default`] stops the browser
+/// from acting on it. The other renderers get the event after the fact, so calling it does nothing there.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// let open = use_state(cx, || true);
+/// use_document_listener(cx, "keydown", {
+///     to_owned![open];
+///     move |evt: KeyboardEvent| {
+///         if evt.key() == Key::Escape {
+///             open.set(false);
+///         }
+///     }
+/// });
+/// ```
+pub fn use_document_listener<T: Clone + 'static>(
+    cx: &ScopeState,
+    event: &str,
+    handler: impl FnMut(Event<T>) + 'static,
+) {
+    use_global_listener(cx, ListenerTarget::Document, event, handler)
+}
+
+/// Run a handler for an event of the window, like `resize`, `focus` or `online`
+///
+/// This works like [`use_document_listener`], except the listener is attached to the window. Events that only the
+/// window gets, like `resize`, `hashchange` or the `blur` of the whole page, need this.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// let focused = use_state(cx, || true);
+/// use_window_listener(cx, "blur", {
+///     to_owned![focused];
+///     move |_: FocusEvent| focused.set(false)
+/// });
+/// ```
+pub fn use_window_listener<T: Clone + 'static>(
+    cx: &ScopeState,
+    event: &str,
+    handler: impl FnMut(Event<T>) + 'static,
+) {
+    use_global_listener(cx, ListenerTarget::Window, event, handler)
+}
+
+fn use_global_listener<T: Clone + 'static>(
+    cx: &ScopeState,
+    target: ListenerTarget,
+    event: &str,
+    handler: impl FnMut(Event<T>) + 'static,
+) {
+    let mut handler = Some(handler);
+
+    let hook = cx.use_hook(|| {
+        let first: Box<dyn FnMut(Event<T>)> = Box::new(handler.take().unwrap());
+        let handler = Rc::new(RefCell::new(first));
+
+        let guard = cx
+            .consume_context::<GlobalListenerContext>()
+            .map(|backend| {
+                let handler = handler.clone();
+                let name = event.to_string();
+                backend.0.listen(
+                    target,
+                    event,
+                    Box::new(move |data: Rc<dyn Any>| match data.downcast_ref::<T>() {
+                        Some(data) => {
+                            let evt = Event::new(data.clone());
+                            (handler.borrow_mut())(evt.clone());
+                            evt.default_prevented()
+                        }
+                        None => {
+                            log::warn!("The data of a {} event doesn't fit its listener", name);
+                            false
+                        }
+                    }),
+                )
+            });
+
+        GlobalListener {
+            handler,
+            _guard: guard,
+        }
+    });
+
+    if let Some(handler) = handler {
+        *hook.handler.borrow_mut() = Box::new(handler);
+    }
+}
+
+struct GlobalListener<T: 'static> {
+    handler: Rc<RefCell<Box<dyn FnMut(Event<T>)>>>,
+    _guard: Option<ListenerGuard>,
+}
+
+/// What a [`use_document_listener`] or [`use_window_listener`] listens to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ListenerTarget {
+    /// The document
+    Document,
+
+    /// The window
+    Window,
+}
+
+impl ListenerTarget {
+    /// The lowercase name of the target, which is how it's sent to interpreters
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ListenerTarget::Document => "document",
+            ListenerTarget::Window => "window",
+        }
+    }
+}
+
+/// Calls the handler of a listener with the data of an event, returning whether it prevented the default
+///
+/// The data is type-erased the same way [`VirtualDom::handle_event`](dioxus_core::VirtualDom::handle_event) takes it.
+pub type GlobalHandler = Box<dyn Fn(Rc<dyn Any>) -> bool>;
+
+/// Listeners of a platform, provided by renderers for [`use_document_listener`] and [`use_window_listener`]
+pub trait GlobalListenerBackend {
+    /// Call `handler` for every `event` of `target` until the guard is dropped
+    fn listen(&self, target: ListenerTarget, event: &str, handler: GlobalHandler) -> ListenerGuard;
+}
+
+/// The context that hands a [`GlobalListenerBackend`] to [`use_document_listener`] and [`use_window_listener`]
+#[derive(Clone)]
+pub struct GlobalListenerContext(Rc<dyn GlobalListenerBackend>);
+
+impl GlobalListenerContext {
+    /// Wrap the listeners so they can be provided as a context
+    pub fn new(backend: impl GlobalListenerBackend + 'static) -> Self {
+        Self(Rc::new(backend))
+    }
+}
+
+/// Removes a listener when it's dropped
+pub struct ListenerGuard {
+    remove: Option<Box<dyn FnOnce()>>,
+}
+
+impl ListenerGuard {
+    /// Run `remove` when the guard is dropped
+    pub fn new(remove: impl FnOnce() + 'static) -> Self {
+        Self {
+            remove: Some(Box::new(remove)),
+        }
+    }
+}
+
+impl Drop for ListenerGuard {
+    fn drop(&mut self) {
+        if let Some(remove) = self.remove.take() {
+            remove();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::prelude::*;
+    use std::cell::Cell;
+
+    thread_local! {
+        static HANDLERS: RefCell<Vec<(ListenerTarget, String, Rc<GlobalHandler>)>> = RefCell::new(Vec::new());
+        static REMOVED: Cell<usize> = Cell::new(0);
+        static PRESSED: RefCell<Vec<String>> = RefCell::new(Vec::new());
+    }
+
+    struct TestListeners;
+
+    impl GlobalListenerBackend for TestListeners {
+        fn listen(
+            &self,
+            target: ListenerTarget,
+            event: &str,
+            handler: GlobalHandler,
+        ) -> ListenerGuard {
+            HANDLERS.with(|h| {
+                h.borrow_mut()
+                    .push((target, event.to_string(), Rc::new(handler)))
+            });
+            ListenerGuard::new(|| REMOVED.with(|r| r.set(r.get() + 1)))
+        }
+    }
+
+    fn dispatch(data: Rc<dyn Any>) -> bool {
+        let handler = HANDLERS.with(|h| h.borrow()[0].2.clone());
+        handler(data)
+    }
+
+    fn app(cx: Scope) -> Element {
+        use_document_listener(cx, "keydown", |evt: Event<String>| {
+            if *evt.data == "Escape" {
+                evt.prevent_default();
+            }
+            PRESSED.with(|p| p.borrow_mut().push((*evt.data).clone()));
+        });
+        None
+    }
+
+    #[test]
+    fn routes_events_until_unmounted() {
+        let mut dom = VirtualDom::new(app);
+        dom.base_scope()
+            .provide_context(GlobalListenerContext::new(TestListeners));
+        _ = dom.rebuild();
+
+        HANDLERS.with(|h| {
+            let handlers = h.borrow();
+            assert_eq!(handlers.len(), 1);
+            assert_eq!(handlers[0].0, ListenerTarget::Document);
+            assert_eq!(handlers[0].1, "keydown");
+        });
+
+        assert!(!dispatch(Rc::new("a".to_string())));
+        assert!(dispatch(Rc::new("Escape".to_string())));
+        // data of another kind never reaches the handler
+        assert!(!dispatch(Rc::new(1_u32)));
+        PRESSED.with(|p| assert_eq!(*p.borrow(), ["a", "Escape"]));
+
+        // rendering again keeps the listener, with the new handler
+        dom.mark_dirty(ScopeId(0));
+        _ = dom.render_immediate();
+        assert_eq!(HANDLERS.with(|h| h.borrow().len()), 1);
+        assert_eq!(REMOVED.with(Cell::get), 0);
+
+        drop(dom);
+        assert_eq!(REMOVED.with(Cell::get), 1);
+    }
+}
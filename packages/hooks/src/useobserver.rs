@@ -0,0 +1,342 @@
+use dioxus_core::ScopeState;
+use dioxus_html::{geometry::ClientRect, MountedData, MountedError, MountedEvent, MountedResult};
+use std::{cell::RefCell, rc::Rc, sync::Arc};
+
+/// Track how much of an element is visible in the viewport
+///
+/// The element is attached from its `onmounted` event with [`UseObserver::observe`]. Every time the browser reports a
+/// change, the latest [`IntersectionEntry`] is stored and the component re-renders. This is what infinite scrolling and
+/// lazy loading of images are built on.
+///
+/// The observer is provided by the renderer through an [`ObserverContext`]. The web renderer backs it with an
+/// `IntersectionObserver`; renderers without one return [`MountedError::NotSupported`] from `observe`.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// let sentinel = use_intersection_observer(cx);
+///
+/// if sentinel.entry().map_or(false, |entry| entry.is_intersecting) {
+///     load_next_page();
+/// }
+///
+/// cx.render(rsx!{
+///     Items {}
+///     div { onmounted: move |evt| { let _ = sentinel.observe(&evt); } }
+/// })
+/// ```
+pub fn use_intersection_observer(cx: &ScopeState) -> &UseObserver<IntersectionEntry> {
+    use_intersection_observer_with_options(cx, IntersectionOptions::default())
+}
+
+/// A [`use_intersection_observer`] that's configured with [`IntersectionOptions`]
+///
+/// The options are read when the hook is first run.
+pub fn use_intersection_observer_with_options(
+    cx: &ScopeState,
+    options: IntersectionOptions,
+) -> &UseObserver<IntersectionEntry> {
+    use_observer(cx, move |backend, element, callback| {
+        backend.observe_intersection(element, &options, callback)
+    })
+}
+
+/// Track the size of an element
+///
+/// The element is attached from its `onmounted` event with [`UseObserver::observe`]. Every time the browser reports a
+/// new size, the latest [`ResizeEntry`] is stored and the component re-renders.
+///
+/// The observer is provided by the renderer through an [`ObserverContext`]. The web renderer backs it with a
+/// `ResizeObserver`; renderers without one return [`MountedError::NotSupported`] from `observe`.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// let panel = use_resize_observer(cx);
+/// let width = panel.entry().map_or(0.0, |entry| entry.content_rect.width());
+///
+/// cx.render(rsx!{
+///     div { onmounted: move |evt| { let _ = panel.observe(&evt); }, "{width}px wide" }
+/// })
+/// ```
+pub fn use_resize_observer(cx: &ScopeState) -> &UseObserver<ResizeEntry> {
+    use_observer(cx, |backend, element, callback| {
+        backend.observe_resize(element, callback)
+    })
+}
+
+type Start<E> =
+    dyn Fn(&dyn ObserverBackend, &MountedData, Box<dyn Fn(E)>) -> MountedResult<ObserverGuard>;
+
+fn use_observer<E: Clone + 'static>(
+    cx: &ScopeState,
+    start: impl Fn(&dyn ObserverBackend, &MountedData, Box<dyn Fn(E)>) -> MountedResult<ObserverGuard>
+        + 'static,
+) -> &UseObserver<E> {
+    cx.use_hook(|| UseObserver {
+        inner: Rc::new(ObserverInner {
+            backend: cx.consume_context::<ObserverContext>(),
+            start: Box::new(start),
+            entry: RefCell::new(None),
+            guard: RefCell::new(None),
+            update: cx.schedule_update(),
+        }),
+    })
+}
+
+/// A handle to a [`use_intersection_observer`] or [`use_resize_observer`]
+pub struct UseObserver<E> {
+    inner: Rc<ObserverInner<E>>,
+}
+
+impl<E> Clone for UseObserver<E> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+struct ObserverInner<E> {
+    backend: Option<ObserverContext>,
+    start: Box<Start<E>>,
+    entry: RefCell<Option<E>>,
+    guard: RefCell<Option<ObserverGuard>>,
+    update: Arc<dyn Fn()>,
+}
+
+impl<E: Clone + 'static> UseObserver<E> {
+    /// Start observing the element from an `onmounted` event, instead of the element that was observed before
+    pub fn observe(&self, element: &MountedEvent) -> MountedResult<()> {
+        self.observe_element(element)
+    }
+
+    /// Start observing a mounted element, instead of the element that was observed before
+    pub fn observe_element(&self, element: &MountedData) -> MountedResult<()> {
+        self.disconnect();
+
+        let backend = self
+            .inner
+            .backend
+            .as_ref()
+            .ok_or(MountedError::NotSupported)?;
+
+        let inner = Rc::downgrade(&self.inner);
+        let callback = Box::new(move |entry: E| {
+            if let Some(inner) = inner.upgrade() {
+                *inner.entry.borrow_mut() = Some(entry);
+                (inner.update)();
+            }
+        });
+
+        let guard = (self.inner.start)(&*backend.0, element, callback)?;
+        *self.inner.guard.borrow_mut() = Some(guard);
+        Ok(())
+    }
+
+    /// Stop observing the element. The last entry is kept.
+    pub fn disconnect(&self) {
+        // The guard is taken out first, in case disconnecting reports one last entry
+        let guard = self.inner.guard.borrow_mut().take();
+        drop(guard);
+    }
+
+    /// Get the latest entry reported for the element
+    pub fn entry(&self) -> Option<E> {
+        self.inner.entry.borrow().clone()
+    }
+}
+
+/// How much of an element intersects with the viewport, as reported by a [`use_intersection_observer`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntersectionEntry {
+    /// Whether any part of the element is visible
+    pub is_intersecting: bool,
+
+    /// The fraction of the element that's visible, from 0 to 1
+    pub intersection_ratio: f64,
+
+    /// The bounds of the element
+    pub bounding_client_rect: ClientRect,
+
+    /// The bounds of the visible part of the element
+    pub intersection_rect: ClientRect,
+}
+
+/// The size of an element, as reported by a [`use_resize_observer`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResizeEntry {
+    /// The bounds of the content of the element, without its padding and border
+    pub content_rect: ClientRect,
+}
+
+/// How a [`use_intersection_observer_with_options`] decides when to report
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntersectionOptions {
+    root_margin: String,
+    thresholds: Vec<f64>,
+}
+
+impl Default for IntersectionOptions {
+    fn default() -> Self {
+        Self {
+            root_margin: "0px".to_string(),
+            thresholds: vec![0.0],
+        }
+    }
+}
+
+impl IntersectionOptions {
+    /// Create the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Grow or shrink the viewport before checking the intersection, like a CSS margin. `"0px"` by default.
+    pub fn root_margin(mut self, margin: impl Into<String>) -> Self {
+        self.root_margin = margin.into();
+        self
+    }
+
+    /// Report whenever the visible fraction of the element crosses one of these. `[0.0]` by default.
+    pub fn thresholds(mut self, thresholds: impl Into<Vec<f64>>) -> Self {
+        self.thresholds = thresholds.into();
+        self
+    }
+
+    /// The margin around the viewport
+    pub fn get_root_margin(&self) -> &str {
+        &self.root_margin
+    }
+
+    /// The fractions of the element that are reported
+    pub fn get_thresholds(&self) -> &[f64] {
+        &self.thresholds
+    }
+}
+
+/// Observers of a platform, provided by renderers for [`use_intersection_observer`] and [`use_resize_observer`]
+pub trait ObserverBackend {
+    /// Report how much of the element intersects with the viewport until the guard is dropped
+    fn observe_intersection(
+        &self,
+        _element: &MountedData,
+        _options: &IntersectionOptions,
+        _callback: Box<dyn Fn(IntersectionEntry)>,
+    ) -> MountedResult<ObserverGuard> {
+        Err(MountedError::NotSupported)
+    }
+
+    /// Report the size of the element until the guard is dropped
+    fn observe_resize(
+        &self,
+        _element: &MountedData,
+        _callback: Box<dyn Fn(ResizeEntry)>,
+    ) -> MountedResult<ObserverGuard> {
+        Err(MountedError::NotSupported)
+    }
+}
+
+/// The context that hands an [`ObserverBackend`] to [`use_intersection_observer`] and [`use_resize_observer`]
+#[derive(Clone)]
+pub struct ObserverContext(pub(crate) Rc<dyn ObserverBackend>);
+
+impl ObserverContext {
+    /// Wrap the observers so they can be provided as a context
+    pub fn new(backend: impl ObserverBackend + 'static) -> Self {
+        Self(Rc::new(backend))
+    }
+}
+
+/// Stops an observation when it's dropped
+pub struct ObserverGuard {
+    disconnect: Option<Box<dyn FnOnce()>>,
+}
+
+impl ObserverGuard {
+    /// Run `disconnect` when the guard is dropped
+    pub fn new(disconnect: impl FnOnce() + 'static) -> Self {
+        Self {
+            disconnect: Some(Box::new(disconnect)),
+        }
+    }
+}
+
+impl Drop for ObserverGuard {
+    fn drop(&mut self) {
+        if let Some(disconnect) = self.disconnect.take() {
+            disconnect();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::prelude::*;
+    use dioxus_html::RenderedElement;
+    use std::cell::Cell;
+
+    struct TestElement;
+    impl RenderedElement for TestElement {}
+
+    thread_local! {
+        static OBSERVER: RefCell<Option<UseObserver<ResizeEntry>>> = RefCell::new(None);
+        static CALLBACK: RefCell<Option<Box<dyn Fn(ResizeEntry)>>> = RefCell::new(None);
+        static DISCONNECTED: Cell<bool> = Cell::new(false);
+    }
+
+    struct TestObserver;
+
+    impl ObserverBackend for TestObserver {
+        fn observe_resize(
+            &self,
+            _element: &MountedData,
+            callback: Box<dyn Fn(ResizeEntry)>,
+        ) -> MountedResult<ObserverGuard> {
+            CALLBACK.with(|c| *c.borrow_mut() = Some(callback));
+            Ok(ObserverGuard::new(|| DISCONNECTED.with(|d| d.set(true))))
+        }
+    }
+
+    fn app(cx: Scope) -> Element {
+        let observer = use_resize_observer(cx);
+        OBSERVER.with(|o| *o.borrow_mut() = Some(observer.clone()));
+        None
+    }
+
+    #[test]
+    fn reports_entries_until_disconnected() {
+        let mut dom = VirtualDom::new(app);
+        dom.base_scope()
+            .provide_context(ObserverContext::new(TestObserver));
+        _ = dom.rebuild();
+
+        let observer = OBSERVER.with(|o| o.borrow().clone().unwrap());
+        observer
+            .observe_element(&MountedData::new(TestElement))
+            .unwrap();
+        assert_eq!(observer.entry(), None);
+
+        let entry = ResizeEntry {
+            content_rect: ClientRect::new((0.0, 0.0).into(), (100.0, 50.0).into()),
+        };
+        CALLBACK.with(|c| (c.borrow().as_ref().unwrap())(entry.clone()));
+        assert_eq!(observer.entry(), Some(entry));
+
+        observer.disconnect();
+        assert!(DISCONNECTED.with(Cell::get));
+    }
+
+    #[test]
+    fn not_supported_without_backend() {
+        let mut dom = VirtualDom::new(app);
+        _ = dom.rebuild();
+
+        let observer = OBSERVER.with(|o| o.borrow().clone().unwrap());
+        assert!(matches!(
+            observer.observe_element(&MountedData::new(TestElement)),
+            Err(MountedError::NotSupported)
+        ));
+    }
+}
@@ -0,0 +1,183 @@
+use dioxus_core::ScopeState;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+    sync::Arc,
+};
+
+/// Store a value that survives restarts of the app
+///
+/// The value is serialized as json and written to the storage of the platform every time it changes: `localStorage`
+/// on web, and a file in the config directory of the user on desktop. The first time the hook runs, it restores the
+/// stored value, falling back to `default` if there is none or it can't be deserialized anymore.
+///
+/// The storage is provided by the renderer through a [`PersistentBackend`] context. Without one, the value is kept in
+/// memory like a [`crate::use_state`] would.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// let theme = use_persistent(cx, "theme", || "light".to_string());
+///
+/// cx.render(rsx!{
+///     button {
+///         onclick: move |_| theme.modify(|theme| *theme = "dark".to_string()),
+///         "Current theme: {theme.get()}"
+///     }
+/// })
+/// ```
+pub fn use_persistent<T: Serialize + DeserializeOwned + 'static>(
+    cx: &ScopeState,
+    key: impl ToString,
+    default: impl FnOnce() -> T,
+) -> &UsePersistent<T> {
+    cx.use_hook(|| {
+        let key = key.to_string();
+        let backend = cx.consume_context::<PersistentBackend>();
+
+        let value = backend
+            .as_ref()
+            .and_then(|backend| backend.0.load(&key))
+            .and_then(|stored| match serde_json::from_str(&stored) {
+                Ok(value) => Some(value),
+                Err(err) => {
+                    log::warn!("Ignoring the stored value of {:?}: {}", key, err);
+                    None
+                }
+            })
+            .unwrap_or_else(default);
+
+        UsePersistent {
+            key,
+            value: Rc::new(RefCell::new(value)),
+            backend,
+            update: cx.schedule_update(),
+        }
+    })
+}
+
+/// Where [`use_persistent`] stores its values, provided by renderers as a root context
+pub trait PersistentStorage {
+    /// Read the value stored under `key`
+    fn load(&self, key: &str) -> Option<String>;
+
+    /// Replace the value stored under `key`
+    fn store(&self, key: &str, value: &str);
+}
+
+/// The context that hands a [`PersistentStorage`] to [`use_persistent`]
+///
+/// ```rust, ignore
+/// let dom = VirtualDom::new(app);
+/// dom.base_scope().provide_context(PersistentBackend::new(MyStorage));
+/// ```
+#[derive(Clone)]
+pub struct PersistentBackend(Rc<dyn PersistentStorage>);
+
+impl PersistentBackend {
+    /// Wrap a storage so it can be provided as a context
+    pub fn new(storage: impl PersistentStorage + 'static) -> Self {
+        Self(Rc::new(storage))
+    }
+}
+
+impl PersistentStorage for PersistentBackend {
+    fn load(&self, key: &str) -> Option<String> {
+        self.0.load(key)
+    }
+
+    fn store(&self, key: &str, value: &str) {
+        self.0.store(key, value)
+    }
+}
+
+/// A handle to a [`use_persistent`]
+pub struct UsePersistent<T> {
+    key: String,
+    value: Rc<RefCell<T>>,
+    backend: Option<PersistentBackend>,
+    update: Arc<dyn Fn()>,
+}
+
+impl<T> Clone for UsePersistent<T> {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            value: self.value.clone(),
+            backend: self.backend.clone(),
+            update: self.update.clone(),
+        }
+    }
+}
+
+impl<T: Serialize> UsePersistent<T> {
+    /// Read the current value
+    pub fn get(&self) -> Ref<'_, T> {
+        self.value.borrow()
+    }
+
+    /// Replace the value, store it, and render the component again
+    pub fn set(&self, value: T) {
+        self.modify(|old| *old = value);
+    }
+
+    /// Change the value in place, store it, and render the component again
+    pub fn modify(&self, f: impl FnOnce(&mut T)) {
+        f(&mut self.value.borrow_mut());
+
+        if let Some(backend) = &self.backend {
+            match serde_json::to_string(&*self.value.borrow()) {
+                Ok(json) => backend.0.store(&self.key, &json),
+                Err(err) => log::error!("Failed to store the value of {:?}: {}", self.key, err),
+            }
+        }
+
+        (self.update)();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::prelude::*;
+    use std::collections::HashMap;
+
+    #[derive(Clone, Default)]
+    struct MemoryStorage(Rc<RefCell<HashMap<String, String>>>);
+
+    impl PersistentStorage for MemoryStorage {
+        fn load(&self, key: &str) -> Option<String> {
+            self.0.borrow().get(key).cloned()
+        }
+
+        fn store(&self, key: &str, value: &str) {
+            self.0
+                .borrow_mut()
+                .insert(key.to_string(), value.to_string());
+        }
+    }
+
+    #[test]
+    fn restores_stored_values() {
+        fn app(cx: Scope) -> Element {
+            let count = use_persistent(cx, "count", || 0);
+            if cx.generation() == 0 {
+                let next = *count.get() + 1;
+                count.set(next);
+            }
+            None
+        }
+
+        let storage = MemoryStorage::default();
+
+        for expected in ["1", "2"] {
+            let mut dom = VirtualDom::new(app);
+            dom.base_scope()
+                .provide_context(PersistentBackend::new(storage.clone()));
+            _ = dom.rebuild();
+
+            assert_eq!(storage.load("count").as_deref(), Some(expected));
+        }
+    }
+}
@@ -0,0 +1,348 @@
+use dioxus_core::ScopeState;
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    future::Future,
+    rc::Rc,
+    sync::Arc,
+};
+
+/// Fetch data into a cache that's shared by every component using the same key
+///
+/// This works like SWR or React Query:
+///
+/// - Components asking for the same key share one value, and only one request for it is ever in flight.
+/// - A component that mounts with a key that's already cached shows the cached value right away, and fetches a fresh
+///   one in the background (stale-while-revalidate).
+/// - [`UseQuery::invalidate`] and [`invalidate_query`] mark a value as stale, which fetches it again for every
+///   component that's using it.
+///
+/// The fetcher is only called when a new value is needed. It runs on the component that started the request, so if
+/// that component is removed before the request is done, another component using the key will start it over.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// #[inline_props]
+/// fn Profile(cx: Scope, id: u32) -> Element {
+///     let user = use_query(cx, format!("user/{id}"), || fetch_user(*id));
+///
+///     cx.render(match user.value() {
+///         Some(Ok(user)) => rsx!( "{user.name}" ),
+///         Some(Err(err)) => rsx!( "Failed to load the user: {err}" ),
+///         None => rsx!( "Loading..." ),
+///     })
+/// }
+/// ```
+pub fn use_query<T, Fut>(
+    cx: &ScopeState,
+    key: impl Into<String>,
+    fetcher: impl FnOnce() -> Fut,
+) -> &UseQuery<T>
+where
+    T: 'static,
+    Fut: Future<Output = T> + 'static,
+{
+    use_query_with_options(cx, key, QueryOptions::default(), fetcher)
+}
+
+/// A [`use_query`] that's configured with [`QueryOptions`]
+///
+/// The options are read when the component starts using a key.
+pub fn use_query_with_options<T, Fut>(
+    cx: &ScopeState,
+    key: impl Into<String>,
+    options: QueryOptions,
+    fetcher: impl FnOnce() -> Fut,
+) -> &UseQuery<T>
+where
+    T: 'static,
+    Fut: Future<Output = T> + 'static,
+{
+    let hook = cx.use_hook(|| UseQuery {
+        id: NEXT_SUBSCRIBER.with(|next| next.replace(next.get() + 1)),
+        key: None,
+        value: None,
+        fetching: false,
+        update: cx.schedule_update(),
+    });
+
+    let key = key.into();
+    if hook.key.as_deref() != Some(key.as_str()) {
+        if let Some(old) = hook.key.take() {
+            unsubscribe(&old, hook.id);
+        }
+
+        with_entry(&key, |entry| {
+            // A cached value is shown right away, but it's fetched again in case it changed
+            if options.revalidate_on_mount && entry.value.is_some() {
+                entry.stale = true;
+            }
+
+            entry.subscribers.push(Subscriber {
+                id: hook.id,
+                update: hook.update.clone(),
+                refetch_on_focus: options.refetch_on_focus,
+            });
+        });
+
+        hook.key = Some(key.clone());
+    }
+
+    let (value, needs_fetch, fetching) = with_entry(&key, |entry| {
+        let needs_fetch = !entry.fetching && (entry.value.is_none() || entry.stale);
+        if needs_fetch {
+            entry.fetching = true;
+            entry.stale = false;
+        }
+        (entry.value.clone(), needs_fetch, entry.fetching)
+    });
+
+    hook.value = value.and_then(|value| match value.downcast::<T>() {
+        Ok(value) => Some(value),
+        Err(_) => {
+            log::error!("The query {:?} is used with different types", key);
+            None
+        }
+    });
+    hook.fetching = fetching;
+
+    if needs_fetch {
+        let fut = fetcher();
+        let mut request = InFlight { key, done: false };
+        cx.spawn(async move {
+            let value = fut.await;
+            request.done = true;
+            finish(&request.key, Some(Rc::new(value)));
+        });
+    }
+
+    hook
+}
+
+/// How a [`use_query_with_options`] keeps its value fresh
+#[derive(Clone, Copy, Debug)]
+pub struct QueryOptions {
+    revalidate_on_mount: bool,
+    refetch_on_focus: bool,
+}
+
+impl Default for QueryOptions {
+    fn default() -> Self {
+        Self {
+            revalidate_on_mount: true,
+            refetch_on_focus: false,
+        }
+    }
+}
+
+impl QueryOptions {
+    /// Create the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fetch the value again when a component starts using a key that's already cached. On by default.
+    pub fn revalidate_on_mount(mut self, revalidate: bool) -> Self {
+        self.revalidate_on_mount = revalidate;
+        self
+    }
+
+    /// Fetch the value again when the window gets focus. Off by default.
+    ///
+    /// Renderers report focus by calling [`revalidate_on_focus`]. The web renderer does this for you.
+    pub fn refetch_on_focus(mut self, refetch: bool) -> Self {
+        self.refetch_on_focus = refetch;
+        self
+    }
+}
+
+/// A handle to a [`use_query`]
+pub struct UseQuery<T> {
+    id: usize,
+    key: Option<String>,
+    value: Option<Rc<T>>,
+    fetching: bool,
+    update: Arc<dyn Fn()>,
+}
+
+impl<T> UseQuery<T> {
+    /// The latest value of the query, which might be stale while a new one is fetched
+    ///
+    /// This is `None` until the first request finishes.
+    pub fn value(&self) -> Option<&T> {
+        self.value.as_deref()
+    }
+
+    /// Check if a request for the query is in flight
+    pub fn is_fetching(&self) -> bool {
+        self.fetching
+    }
+
+    /// Mark the value as stale, fetching it again for every component that uses the query
+    pub fn invalidate(&self) {
+        if let Some(key) = &self.key {
+            invalidate_query(key);
+        }
+    }
+}
+
+impl<T> Drop for UseQuery<T> {
+    fn drop(&mut self) {
+        if let Some(key) = &self.key {
+            unsubscribe(key, self.id);
+        }
+    }
+}
+
+/// Mark the value of a query as stale, fetching it again for every component that uses it
+pub fn invalidate_query(key: &str) {
+    let updates = CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let entry = match cache.get_mut(key) {
+            Some(entry) => entry,
+            None => return Vec::new(),
+        };
+        entry.stale = true;
+        entry.updates(|_| true)
+    });
+
+    updates.iter().for_each(|update| update());
+}
+
+/// Fetch the queries that opted into [`QueryOptions::refetch_on_focus`] again
+///
+/// This is meant to be called by renderers when the window gets focus.
+pub fn revalidate_on_focus() {
+    let updates = CACHE.with(|cache| {
+        let mut updates = Vec::new();
+        for entry in cache.borrow_mut().values_mut() {
+            let focused = entry.updates(|sub| sub.refetch_on_focus);
+            if !focused.is_empty() {
+                entry.stale = true;
+                updates.extend(focused);
+            }
+        }
+        updates
+    });
+
+    updates.iter().for_each(|update| update());
+}
+
+thread_local! {
+    static CACHE: RefCell<HashMap<String, QueryEntry>> = RefCell::new(HashMap::new());
+    static NEXT_SUBSCRIBER: Cell<usize> = Cell::new(0);
+}
+
+#[derive(Default)]
+struct QueryEntry {
+    value: Option<Rc<dyn Any>>,
+    fetching: bool,
+    stale: bool,
+    subscribers: Vec<Subscriber>,
+}
+
+impl QueryEntry {
+    fn updates(&self, filter: impl Fn(&Subscriber) -> bool) -> Vec<Arc<dyn Fn()>> {
+        self.subscribers
+            .iter()
+            .filter(|sub| filter(sub))
+            .map(|sub| sub.update.clone())
+            .collect()
+    }
+}
+
+struct Subscriber {
+    id: usize,
+    update: Arc<dyn Fn()>,
+    refetch_on_focus: bool,
+}
+
+fn with_entry<O>(key: &str, f: impl FnOnce(&mut QueryEntry) -> O) -> O {
+    CACHE.with(|cache| f(cache.borrow_mut().entry(key.to_string()).or_default()))
+}
+
+fn unsubscribe(key: &str, id: usize) {
+    with_entry(key, |entry| entry.subscribers.retain(|sub| sub.id != id));
+}
+
+/// Store the result of a request, or just give up on it if the request was cancelled
+fn finish(key: &str, value: Option<Rc<dyn Any>>) {
+    let updates = with_entry(key, |entry| {
+        entry.fetching = false;
+        if value.is_some() {
+            entry.value = value;
+        }
+        entry.updates(|_| true)
+    });
+
+    updates.iter().for_each(|update| update());
+}
+
+/// A request that lets the other subscribers take over if it's dropped before it's done
+struct InFlight {
+    key: String,
+    done: bool,
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        if !self.done {
+            finish(&self.key, None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::prelude::*;
+
+    thread_local! {
+        static FETCHES: Cell<i32> = Cell::new(0);
+        static SEEN: RefCell<Vec<Option<i32>>> = RefCell::new(Vec::new());
+    }
+
+    fn fetch() -> impl Future<Output = i32> {
+        let count = FETCHES.with(|f| f.replace(f.get() + 1)) + 1;
+        async move { count }
+    }
+
+    #[test]
+    fn queries_are_shared_and_revalidated() {
+        fn app(cx: Scope) -> Element {
+            let query = use_query(cx, "shared", fetch);
+            SEEN.with(|s| s.borrow_mut().push(query.value().copied()));
+
+            if cx.generation() == 2 {
+                query.invalidate();
+            }
+
+            // A second subscriber doesn't start another request
+            use_query(cx, "shared", fetch);
+
+            None
+        }
+
+        let mut dom = VirtualDom::new(app);
+        _ = dom.rebuild();
+        assert_eq!(FETCHES.with(Cell::get), 1);
+
+        // The request finishes and renders the value
+        dom.process_events();
+        _ = dom.render_immediate();
+
+        // The invalidation from the last render fetches again while the old value is shown
+        dom.mark_dirty(ScopeId(0));
+        _ = dom.render_immediate();
+        dom.process_events();
+        _ = dom.render_immediate();
+
+        assert_eq!(FETCHES.with(Cell::get), 2);
+        assert_eq!(
+            SEEN.with(|s| s.borrow().clone()),
+            [None, Some(1), Some(1), Some(2)]
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use dioxus_core::ScopeState;
+use std::{cell::RefCell, future::Future, pin::Pin, rc::Rc, sync::Arc};
+
+/// Store state that only changes through actions, like a reducer from Redux or React's `useReducer`
+///
+/// The reducer takes the current state and an action and produces the next state. Dispatching an action marks the
+/// component as dirty, and the new state is visible on the next render.
+///
+/// This is a good fit for components that would otherwise juggle many `use_state` cells that change together.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// enum Action {
+///     Increment,
+///     Reset,
+/// }
+///
+/// let (count, dispatch) = use_reducer(cx, || 0, |count, action| match action {
+///     Action::Increment => count + 1,
+///     Action::Reset => 0,
+/// });
+///
+/// cx.render(rsx!{
+///     "{count}"
+///     button { onclick: move |_| dispatch.dispatch(Action::Increment), "+" }
+///     button { onclick: move |_| dispatch.dispatch(Action::Reset), "reset" }
+/// })
+/// ```
+pub fn use_reducer<S: 'static, A: 'static>(
+    cx: &ScopeState,
+    initial: impl FnOnce() -> S,
+    reducer: impl Fn(&S, A) -> S + 'static,
+) -> (&S, &Dispatch<S, A>) {
+    use_reducer_with_middleware(cx, initial, reducer, Vec::new)
+}
+
+/// A reducer with a chain of [`Middleware`] that sees every action before the reducer does
+///
+/// The middleware run in order. Each one gets the [`Dispatch`] handle, the action, and a `next` function that passes
+/// the action along to the rest of the chain. A middleware can change the action, drop it, or do work before and after
+/// the state changes:
+///
+/// - logging: print the action, call `next`, then print the new [`Dispatch::state`]
+/// - persistence: call `next`, then save the new state somewhere
+/// - async thunks: start a future with [`Dispatch::spawn`] that dispatches more actions once it's done
+///
+/// The middleware are created once, when the hook is first run.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// let (todos, dispatch) = use_reducer_with_middleware(cx, Vec::new, reduce_todos, || {
+///     vec![Box::new(|dispatch: &Dispatch<_, _>, action, next: &dyn Fn(_)| {
+///         log::info!("{action:?}");
+///         next(action);
+///         log::info!("{:?}", dispatch.state());
+///     })]
+/// });
+/// ```
+pub fn use_reducer_with_middleware<S: 'static, A: 'static>(
+    cx: &ScopeState,
+    initial: impl FnOnce() -> S,
+    reducer: impl Fn(&S, A) -> S + 'static,
+    middleware: impl FnOnce() -> Vec<Middleware<S, A>>,
+) -> (&S, &Dispatch<S, A>) {
+    let hook = cx.use_hook(|| {
+        let inner = Rc::new(ReducerInner {
+            state: RefCell::new(Rc::new(initial())),
+            reducer: Box::new(reducer),
+            middleware: middleware(),
+            thunks: RefCell::new(Vec::new()),
+            update: cx.schedule_update(),
+        });
+
+        UseReducer {
+            current: inner.state.borrow().clone(),
+            dispatch: Dispatch { inner },
+        }
+    });
+
+    hook.current = hook.dispatch.inner.state.borrow().clone();
+
+    // Futures are spawned on the scope so they're dropped along with the component
+    let thunks = std::mem::take(&mut *hook.dispatch.inner.thunks.borrow_mut());
+    for thunk in thunks {
+        cx.spawn(thunk);
+    }
+
+    (&*hook.current, &hook.dispatch)
+}
+
+/// A step in the chain of a [`use_reducer_with_middleware`]
+///
+/// It's called with the [`Dispatch`] handle, the action, and the function that runs the rest of the chain.
+pub type Middleware<S, A> = Box<dyn Fn(&Dispatch<S, A>, A, &dyn Fn(A))>;
+
+struct UseReducer<S, A> {
+    current: Rc<S>,
+    dispatch: Dispatch<S, A>,
+}
+
+struct ReducerInner<S, A> {
+    state: RefCell<Rc<S>>,
+    reducer: Box<dyn Fn(&S, A) -> S>,
+    middleware: Vec<Middleware<S, A>>,
+    thunks: RefCell<Vec<Pin<Box<dyn Future<Output = ()>>>>>,
+    update: Arc<dyn Fn()>,
+}
+
+/// The handle to send actions to a [`use_reducer`]
+///
+/// It can be cloned and moved into event handlers and futures.
+pub struct Dispatch<S, A> {
+    inner: Rc<ReducerInner<S, A>>,
+}
+
+impl<S, A> Clone for Dispatch<S, A> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<S: 'static, A: 'static> Dispatch<S, A> {
+    /// Send an action through the middleware to the reducer, and render the component with the new state
+    pub fn dispatch(&self, action: A) {
+        self.run(0, action);
+    }
+
+    /// Get the latest state, including changes the component hasn't rendered yet
+    pub fn state(&self) -> Rc<S> {
+        self.inner.state.borrow().clone()
+    }
+
+    /// Run a future on the component that owns the reducer, usually to dispatch actions once some async work is done
+    ///
+    /// The future starts the next time the component renders, and is cancelled if the component is removed.
+    pub fn spawn(&self, fut: impl Future<Output = ()> + 'static) {
+        self.inner.thunks.borrow_mut().push(Box::pin(fut));
+        (self.inner.update)();
+    }
+
+    fn run(&self, index: usize, action: A) {
+        match self.inner.middleware.get(index) {
+            Some(middleware) => middleware(self, action, &|action| self.run(index + 1, action)),
+            None => {
+                let new = (self.inner.reducer)(&self.inner.state.borrow(), action);
+                *self.inner.state.borrow_mut() = Rc::new(new);
+                (self.inner.update)();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::prelude::*;
+
+    #[test]
+    fn middleware_and_thunks() {
+        thread_local! {
+            static LOG: RefCell<Vec<String>> = RefCell::new(Vec::new());
+        }
+
+        enum Action {
+            Add(i32),
+            AddLater(i32),
+        }
+
+        fn app(cx: Scope) -> Element {
+            let (total, dispatch) = use_reducer_with_middleware(
+                cx,
+                || 0,
+                |total, action| match action {
+                    Action::Add(n) => total + n,
+                    Action::AddLater(_) => unreachable!(),
+                },
+                || -> Vec<Middleware<i32, Action>> {
+                    vec![
+                        Box::new(
+                            |dispatch: &Dispatch<i32, Action>,
+                             action: Action,
+                             next: &dyn Fn(Action)| {
+                                next(action);
+                                LOG.with(|l| {
+                                    l.borrow_mut().push(format!("after {}", dispatch.state()))
+                                });
+                            },
+                        ),
+                        Box::new(
+                            |dispatch: &Dispatch<i32, Action>,
+                             action: Action,
+                             next: &dyn Fn(Action)| {
+                                match action {
+                                    Action::AddLater(n) => {
+                                        let later = dispatch.clone();
+                                        dispatch.spawn(async move {
+                                            later.dispatch(Action::Add(n));
+                                        });
+                                    }
+                                    action => next(action),
+                                }
+                            },
+                        ),
+                    ]
+                },
+            );
+
+            LOG.with(|l| l.borrow_mut().push(format!("render {}", total)));
+
+            if cx.generation() == 0 {
+                dispatch.dispatch(Action::Add(1));
+                dispatch.dispatch(Action::AddLater(10));
+            }
+
+            None
+        }
+
+        let mut dom = VirtualDom::new(app);
+        _ = dom.rebuild();
+
+        // The render from the first dispatches starts the thunk
+        dom.process_events();
+        _ = dom.render_immediate();
+
+        dom.process_events();
+        _ = dom.render_immediate();
+
+        assert_eq!(
+            LOG.with(|l| l.borrow().clone()),
+            [
+                "render 0",
+                "after 1",
+                "after 1",
+                "render 1",
+                "after 11",
+                "render 11"
+            ]
+        );
+    }
+}
@@ -0,0 +1,406 @@
+use dioxus_core::ScopeState;
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt,
+    rc::Rc,
+    str::FromStr,
+};
+
+/// Run a handler whenever a keyboard shortcut like `"ctrl+k"` is pressed
+///
+/// On web the shortcut is handled for the whole document, and the browser's own handling of it is prevented. On
+/// desktop it's registered as a global accelerator with the operating system, so it works even when the window isn't
+/// focused.
+///
+/// A shortcut can only be claimed by one component at a time. If another component already claimed it, the shortcut
+/// isn't registered and [`UseKeyboardShortcut::error`] returns [`ShortcutError::Conflict`]. The shortcut is released
+/// when the component is removed.
+///
+/// The shortcut is read when the hook is first run, while the handler is replaced on every render so it always sees
+/// the latest state it captured. See [`Shortcut`] for how shortcuts are written.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// let open = use_state(cx, || false);
+/// use_keyboard_shortcut(cx, "mod+k", {
+///     to_owned![open];
+///     move || open.set(true)
+/// });
+/// ```
+pub fn use_keyboard_shortcut<'a>(
+    cx: &'a ScopeState,
+    shortcut: &str,
+    handler: impl FnMut() + 'static,
+) -> &'a UseKeyboardShortcut {
+    let mut handler = Some(handler);
+
+    let hook = cx.use_hook(|| {
+        let first: Box<dyn FnMut()> = Box::new(handler.take().unwrap());
+        let handler = Rc::new(RefCell::new(first));
+        let backend = cx.consume_context::<ShortcutContext>();
+
+        let claim = shortcut
+            .parse::<Shortcut>()
+            .and_then(|shortcut| claim(shortcut, handler.clone(), backend));
+        if let Err(err) = &claim {
+            log::warn!("The shortcut {:?} wasn't registered: {}", shortcut, err);
+        }
+
+        UseKeyboardShortcut { handler, claim }
+    });
+
+    if let Some(handler) = handler {
+        *hook.handler.borrow_mut() = Box::new(handler);
+    }
+
+    hook
+}
+
+/// A handle to a [`use_keyboard_shortcut`]
+pub struct UseKeyboardShortcut {
+    handler: Rc<RefCell<Box<dyn FnMut()>>>,
+    claim: Result<Claim, ShortcutError>,
+}
+
+impl UseKeyboardShortcut {
+    /// Get the reason the shortcut couldn't be registered
+    pub fn error(&self) -> Option<&ShortcutError> {
+        self.claim.as_ref().err()
+    }
+
+    /// Check if the shortcut is registered for this component
+    pub fn is_registered(&self) -> bool {
+        self.claim.is_ok()
+    }
+}
+
+/// Run the handler registered for a shortcut, returning whether there was one
+///
+/// This is meant to be called by renderers when a shortcut is pressed.
+pub fn trigger_shortcut(shortcut: &Shortcut) -> bool {
+    let handler = SHORTCUTS.with(|shortcuts| {
+        shortcuts
+            .borrow()
+            .get(shortcut)
+            .map(|(_, handler)| handler.clone())
+    });
+
+    match handler {
+        Some(handler) => {
+            (handler.borrow_mut())();
+            true
+        }
+        None => false,
+    }
+}
+
+/// A keyboard shortcut: any number of modifiers and a single key, joined with `+`
+///
+/// The modifiers are `ctrl`, `alt`, `shift` and `meta`, which is the command key on macOS and the Windows key on
+/// Windows. `mod` is `meta` on macOS and `ctrl` everywhere else.
+///
+/// Keys are named after where they are on the keyboard, so `shift+1` works no matter which symbol the layout puts
+/// there. Letters and digits are used as is, and other keys use their lowercase name, like `enter`, `escape`, `space`,
+/// `arrowup` or `f1`. A few keys can also be written as the character they type, like `/` or `,`.
+///
+/// Case and spaces don't matter: `"Ctrl + Shift + P"` and `"shift+ctrl+p"` are the same shortcut.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    meta: bool,
+    key: String,
+}
+
+impl Shortcut {
+    /// Check if the shortcut holds down control
+    pub fn ctrl(&self) -> bool {
+        self.ctrl
+    }
+
+    /// Check if the shortcut holds down alt, which is option on macOS
+    pub fn alt(&self) -> bool {
+        self.alt
+    }
+
+    /// Check if the shortcut holds down shift
+    pub fn shift(&self) -> bool {
+        self.shift
+    }
+
+    /// Check if the shortcut holds down meta, which is command on macOS
+    pub fn meta(&self) -> bool {
+        self.meta
+    }
+
+    /// Get the normalized name of the key
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl FromStr for Shortcut {
+    type Err = ShortcutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut shortcut = Shortcut {
+            ctrl: false,
+            alt: false,
+            shift: false,
+            meta: false,
+            key: String::new(),
+        };
+
+        // A trailing `+` is the plus key, like in `ctrl++`
+        let (modifiers, key) = match s.trim().strip_suffix("++") {
+            Some(modifiers) => (modifiers, "+"),
+            None => match s.trim().rsplit_once('+') {
+                Some((modifiers, key)) => (modifiers, key),
+                None => ("", s),
+            },
+        };
+
+        for modifier in modifiers.split('+').filter(|m| !m.trim().is_empty()) {
+            let flag = match modifier.trim().to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => &mut shortcut.ctrl,
+                "alt" | "option" => &mut shortcut.alt,
+                "shift" => &mut shortcut.shift,
+                "meta" | "cmd" | "command" | "super" | "win" => &mut shortcut.meta,
+                "mod" if cfg!(target_os = "macos") => &mut shortcut.meta,
+                "mod" => &mut shortcut.ctrl,
+                _ => return Err(ShortcutError::Invalid(s.to_string())),
+            };
+            *flag = true;
+        }
+
+        shortcut.key =
+            normalize_key(key.trim()).ok_or_else(|| ShortcutError::Invalid(s.to_string()))?;
+        Ok(shortcut)
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let modifiers = [
+            (self.ctrl, "ctrl+"),
+            (self.alt, "alt+"),
+            (self.shift, "shift+"),
+            (self.meta, "meta+"),
+        ];
+        for (_, name) in modifiers.iter().filter(|(held, _)| *held) {
+            f.write_str(name)?;
+        }
+        f.write_str(&self.key)
+    }
+}
+
+/// Turn the name of a key, or the character it types, into the name it's matched by
+fn normalize_key(key: &str) -> Option<String> {
+    let key = key.to_ascii_lowercase();
+    let named = match key.as_str() {
+        "" => return None,
+        "ctrl" | "control" | "alt" | "option" | "shift" | "meta" | "cmd" | "command" | "super"
+        | "win" | "mod" => return None,
+        "esc" => "escape",
+        "return" => "enter",
+        " " => "space",
+        "up" => "arrowup",
+        "down" => "arrowdown",
+        "left" => "arrowleft",
+        "right" => "arrowright",
+        "del" => "delete",
+        "/" => "slash",
+        "\\" => "backslash",
+        "." => "period",
+        "," => "comma",
+        ";" => "semicolon",
+        "'" => "quote",
+        "`" => "backquote",
+        "-" => "minus",
+        "=" | "+" => "equal",
+        "[" => "bracketleft",
+        "]" => "bracketright",
+        _ => key.as_str(),
+    };
+
+    // The physical codes that browsers report, like `KeyK` or `Digit1`
+    let named = named
+        .strip_prefix("key")
+        .filter(|rest| rest.len() == 1)
+        .or_else(|| named.strip_prefix("digit").filter(|rest| rest.len() == 1))
+        .unwrap_or(named);
+
+    Some(named.to_string())
+}
+
+/// Why a shortcut couldn't be registered
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ShortcutError {
+    /// The shortcut couldn't be parsed
+    Invalid(String),
+
+    /// Another component already registered the shortcut
+    Conflict(Shortcut),
+
+    /// The platform refused to register the shortcut
+    Platform(String),
+}
+
+impl fmt::Display for ShortcutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShortcutError::Invalid(shortcut) => write!(f, "{:?} is not a valid shortcut", shortcut),
+            ShortcutError::Conflict(shortcut) => {
+                write!(f, "{} is already registered by another component", shortcut)
+            }
+            ShortcutError::Platform(reason) => {
+                write!(f, "the platform refused the shortcut: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ShortcutError {}
+
+/// Global shortcuts of a platform, provided by renderers that need to register them for [`use_keyboard_shortcut`]
+///
+/// Renderers that see every key press, like the web renderer, don't need one. They call [`trigger_shortcut`] with
+/// every chord that's pressed.
+pub trait ShortcutBackend {
+    /// Start listening for the shortcut
+    fn register(&self, shortcut: &Shortcut) -> Result<(), ShortcutError>;
+
+    /// Stop listening for the shortcut
+    fn unregister(&self, shortcut: &Shortcut);
+}
+
+/// The context that hands a [`ShortcutBackend`] to [`use_keyboard_shortcut`]
+#[derive(Clone)]
+pub struct ShortcutContext(Rc<dyn ShortcutBackend>);
+
+impl ShortcutContext {
+    /// Wrap the shortcuts of a platform so they can be provided as a context
+    pub fn new(backend: impl ShortcutBackend + 'static) -> Self {
+        Self(Rc::new(backend))
+    }
+}
+
+type Handler = Rc<RefCell<Box<dyn FnMut()>>>;
+
+thread_local! {
+    static SHORTCUTS: RefCell<HashMap<Shortcut, (usize, Handler)>> = RefCell::new(HashMap::new());
+    static NEXT_CLAIM: Cell<usize> = Cell::new(0);
+}
+
+/// Releases a shortcut when the hook is dropped
+struct Claim {
+    id: usize,
+    shortcut: Shortcut,
+    backend: Option<ShortcutContext>,
+}
+
+fn claim(
+    shortcut: Shortcut,
+    handler: Handler,
+    backend: Option<ShortcutContext>,
+) -> Result<Claim, ShortcutError> {
+    if SHORTCUTS.with(|shortcuts| shortcuts.borrow().contains_key(&shortcut)) {
+        return Err(ShortcutError::Conflict(shortcut));
+    }
+
+    if let Some(backend) = &backend {
+        backend.0.register(&shortcut)?;
+    }
+
+    let id = NEXT_CLAIM.with(|next| next.replace(next.get() + 1));
+    SHORTCUTS.with(|shortcuts| {
+        shortcuts
+            .borrow_mut()
+            .insert(shortcut.clone(), (id, handler))
+    });
+
+    Ok(Claim {
+        id,
+        shortcut,
+        backend,
+    })
+}
+
+impl Drop for Claim {
+    fn drop(&mut self) {
+        let removed = SHORTCUTS.with(|shortcuts| {
+            let mut shortcuts = shortcuts.borrow_mut();
+            match shortcuts.get(&self.shortcut) {
+                Some((id, _)) if *id == self.id => shortcuts.remove(&self.shortcut),
+                _ => None,
+            }
+        });
+
+        if removed.is_some() {
+            if let Some(backend) = &self.backend {
+                backend.0.unregister(&self.shortcut);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::prelude::*;
+
+    thread_local! {
+        static PRESSES: Cell<usize> = Cell::new(0);
+        static CONFLICTS: Cell<usize> = Cell::new(0);
+    }
+
+    fn shortcut(s: &str) -> Shortcut {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_shortcuts() {
+        assert_eq!(shortcut("Ctrl + Shift + P"), shortcut("shift+ctrl+p"));
+        assert_eq!(shortcut("ctrl+KeyK"), shortcut("ctrl+k"));
+        assert_eq!(shortcut("alt+Digit1"), shortcut("alt+1"));
+        assert_eq!(shortcut("ctrl+/"), shortcut("ctrl+slash"));
+        assert_eq!(shortcut("ctrl++").key(), "equal");
+        assert_eq!(shortcut("meta+shift+esc").to_string(), "shift+meta+escape");
+
+        assert!("ctrl+".parse::<Shortcut>().is_err());
+        assert!("hyper+k".parse::<Shortcut>().is_err());
+        assert!("ctrl+shift".parse::<Shortcut>().is_err());
+    }
+
+    #[test]
+    fn one_handler_per_shortcut() {
+        fn app(cx: Scope) -> Element {
+            let first =
+                use_keyboard_shortcut(cx, "ctrl+k", || PRESSES.with(|p| p.set(p.get() + 1)));
+            assert!(first.is_registered());
+
+            let second =
+                use_keyboard_shortcut(cx, "control+K", || panic!("{}", "conflicting handler ran"));
+            if let Some(ShortcutError::Conflict(_)) = second.error() {
+                CONFLICTS.with(|c| c.set(c.get() + 1));
+            }
+
+            None
+        }
+
+        let mut dom = VirtualDom::new(app);
+        _ = dom.rebuild();
+        assert_eq!(CONFLICTS.with(Cell::get), 1);
+
+        assert!(trigger_shortcut(&shortcut("ctrl+k")));
+        assert!(!trigger_shortcut(&shortcut("ctrl+j")));
+        assert_eq!(PRESSES.with(Cell::get), 1);
+
+        // The shortcut is released with the component
+        drop(dom);
+        assert!(!trigger_shortcut(&shortcut("ctrl+k")));
+    }
+}
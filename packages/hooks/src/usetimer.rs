@@ -0,0 +1,325 @@
+use dioxus_core::ScopeState;
+use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use futures_util::{
+    future::{select, Either},
+    Stream, StreamExt,
+};
+use std::{
+    cell::{Cell, RefCell},
+    pin::Pin,
+    rc::Rc,
+    time::Duration,
+};
+
+/// Run a callback every `period` for as long as the component is mounted
+///
+/// The timer runs on the component, so it stops when the component is removed. It can be paused and resumed with the
+/// returned [`UseTimer`]. Resuming starts a full period over.
+///
+/// The callback is replaced on every render, so it always sees the latest state it captured.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// let seconds = use_state(cx, || 0);
+/// let timer = use_interval(cx, Duration::from_secs(1), {
+///     to_owned![seconds];
+///     move || seconds.modify(|s| s + 1)
+/// });
+///
+/// cx.render(rsx!{
+///     "{seconds}"
+///     button { onclick: move |_| timer.pause(), "Pause" }
+///     button { onclick: move |_| timer.resume(), "Resume" }
+/// })
+/// ```
+pub fn use_interval(
+    cx: &ScopeState,
+    period: Duration,
+    callback: impl FnMut() + 'static,
+) -> &UseTimer {
+    use_timer(cx, period, true, callback)
+}
+
+/// Run a callback once `delay` is over, unless the component is removed first
+///
+/// Pausing the returned [`UseTimer`] holds off the callback, and resuming starts the full delay over. Once the callback
+/// ran, the timer is finished.
+///
+/// The callback is replaced on every render, so it always sees the latest state it captured.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// let visible = use_state(cx, || true);
+/// use_timeout(cx, Duration::from_secs(5), {
+///     to_owned![visible];
+///     move || visible.set(false)
+/// });
+/// ```
+pub fn use_timeout(
+    cx: &ScopeState,
+    delay: Duration,
+    callback: impl FnMut() + 'static,
+) -> &UseTimer {
+    use_timer(cx, delay, false, callback)
+}
+
+/// A handle to a [`use_interval`] or [`use_timeout`]
+#[derive(Clone)]
+pub struct UseTimer {
+    paused: Rc<Cell<bool>>,
+    tx: UnboundedSender<bool>,
+}
+
+impl UseTimer {
+    /// Stop the timer until it's resumed
+    pub fn pause(&self) {
+        self.set_paused(true);
+    }
+
+    /// Start the timer again, waiting a full period before the callback runs
+    pub fn resume(&self) {
+        self.set_paused(false);
+    }
+
+    /// Check if the timer is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused.get()
+    }
+
+    fn set_paused(&self, paused: bool) {
+        if self.paused.replace(paused) != paused {
+            let _ = self.tx.unbounded_send(paused);
+        }
+    }
+}
+
+fn use_timer(
+    cx: &ScopeState,
+    period: Duration,
+    repeat: bool,
+    callback: impl FnMut() + 'static,
+) -> &UseTimer {
+    let mut callback = Some(callback);
+
+    let (timer, current) = cx.use_hook(|| {
+        let (tx, rx) = futures_channel::mpsc::unbounded();
+        let first: Box<dyn FnMut()> = Box::new(callback.take().unwrap());
+        let current = Rc::new(RefCell::new(first));
+        let start = move || ticks(period, repeat);
+        cx.push_future(run_timer(rx, start, repeat, current.clone()));
+
+        let timer = UseTimer {
+            paused: Rc::new(Cell::new(false)),
+            tx,
+        };
+        (timer, current)
+    });
+
+    if let Some(callback) = callback {
+        *current.borrow_mut() = Box::new(callback);
+    }
+
+    timer
+}
+
+/// Run the callback on every tick of the timer `start` starts, until the handle is dropped
+///
+/// Pausing drops the timer, and resuming starts a new one.
+async fn run_timer(
+    mut rx: UnboundedReceiver<bool>,
+    mut start: impl FnMut() -> Pin<Box<dyn Stream<Item = ()>>>,
+    repeat: bool,
+    callback: Rc<RefCell<Box<dyn FnMut()>>>,
+) {
+    let mut paused = false;
+    let mut timer = None;
+    loop {
+        if paused {
+            match rx.next().await {
+                Some(now) => paused = now,
+                None => return,
+            }
+            continue;
+        }
+
+        // Pausing drops the timer, so resuming starts a full period over
+        let ticking = timer.get_or_insert_with(&mut start);
+        let paused_now = match select(rx.next(), ticking.next()).await {
+            Either::Left((Some(now), _)) => Some(now),
+            Either::Left((None, _)) => return,
+            Either::Right((Some(()), _)) => None,
+            Either::Right((None, _)) => return,
+        };
+
+        match paused_now {
+            Some(now) => {
+                paused = now;
+                timer = None;
+            }
+            None => {
+                (callback.borrow_mut())();
+                if !repeat {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// The ticks of an interval, or the single tick of a timeout
+fn ticks(period: Duration, repeat: bool) -> Pin<Box<dyn Stream<Item = ()>>> {
+    match repeat {
+        true => Box::pin(interval(period)),
+        false => Box::pin(futures_util::stream::once(sleep(period))),
+    }
+}
+
+/// Tick every `period` on a single timer, without tying the hooks to a particular executor
+#[cfg(target_arch = "wasm32")]
+fn interval(period: Duration) -> impl Stream<Item = ()> {
+    gloo_timers::future::IntervalStream::new(period.as_millis() as u32)
+}
+
+/// Tick every `period` on a single timer, without tying the hooks to a particular executor
+#[cfg(not(target_arch = "wasm32"))]
+fn interval(period: Duration) -> impl Stream<Item = ()> {
+    let mut timer = async_io::Timer::interval(period);
+    futures_util::stream::poll_fn(move |cx| {
+        timer.poll_next_unpin(cx).map(|tick| {
+            // The next period starts now, so a late wakeup doesn't run the callback for every period it missed
+            timer.set_interval(period);
+            tick.map(|_| ())
+        })
+    })
+}
+
+/// Wait for `duration` without tying the hooks to a particular executor
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn sleep(duration: Duration) {
+    gloo_timers::future::sleep(duration).await;
+}
+
+/// Wait for `duration` without tying the hooks to a particular executor
+///
+/// The timers of async-io are driven by a single background thread when no executor drives them.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn sleep(duration: Duration) {
+    async_io::Timer::after(duration).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::{task::noop_waker_ref, FutureExt};
+    use std::{
+        future::Future,
+        task::{Context, Poll},
+    };
+
+    /// A timer that ticks when the test says so
+    #[derive(Default)]
+    struct Clock {
+        // the ticks of every timer that was started, the running one last
+        timers: Rc<RefCell<Vec<UnboundedSender<()>>>>,
+        calls: Rc<Cell<usize>>,
+    }
+
+    impl Clock {
+        fn run(&self, repeat: bool) -> (UnboundedSender<bool>, Pin<Box<dyn Future<Output = ()>>>) {
+            let (tx, rx) = futures_channel::mpsc::unbounded();
+            let timers = self.timers.clone();
+            let start = move || {
+                let (tick, ticks) = futures_channel::mpsc::unbounded();
+                timers.borrow_mut().push(tick);
+                Box::pin(ticks) as Pin<Box<dyn Stream<Item = ()>>>
+            };
+            let calls = self.calls.clone();
+            let callback: Box<dyn FnMut()> = Box::new(move || calls.set(calls.get() + 1));
+            let running = run_timer(rx, start, repeat, Rc::new(RefCell::new(callback)));
+            (tx, Box::pin(running))
+        }
+
+        /// Tick the running timer, returning whether one is running
+        fn tick(&self) -> bool {
+            match self.timers.borrow().last() {
+                Some(timer) => timer.unbounded_send(()).is_ok(),
+                None => false,
+            }
+        }
+
+        fn started(&self) -> usize {
+            self.timers.borrow().len()
+        }
+    }
+
+    fn poll(running: &mut Pin<Box<dyn Future<Output = ()>>>) -> Poll<()> {
+        running.poll_unpin(&mut Context::from_waker(noop_waker_ref()))
+    }
+
+    #[test]
+    fn interval_runs_on_every_tick() {
+        let clock = Clock::default();
+        let (_handle, mut running) = clock.run(true);
+        assert_eq!(poll(&mut running), Poll::Pending);
+        assert_eq!(clock.calls.get(), 0);
+
+        for _ in 0..3 {
+            assert!(clock.tick());
+        }
+        assert_eq!(poll(&mut running), Poll::Pending);
+        assert_eq!(clock.calls.get(), 3);
+        assert_eq!(clock.started(), 1);
+    }
+
+    #[test]
+    fn interval_pauses_and_resumes() {
+        let clock = Clock::default();
+        let (handle, mut running) = clock.run(true);
+        _ = poll(&mut running);
+        clock.tick();
+        _ = poll(&mut running);
+        assert_eq!(clock.calls.get(), 1);
+
+        // pausing stops the timer, so it can't tick anymore
+        handle.unbounded_send(true).unwrap();
+        _ = poll(&mut running);
+        assert!(!clock.tick());
+        _ = poll(&mut running);
+        assert_eq!(clock.calls.get(), 1);
+
+        // resuming starts a new timer, which ticks a full period after it started
+        handle.unbounded_send(false).unwrap();
+        _ = poll(&mut running);
+        assert_eq!(clock.started(), 2);
+        assert_eq!(clock.calls.get(), 1);
+        assert!(clock.tick());
+        _ = poll(&mut running);
+        assert_eq!(clock.calls.get(), 2);
+    }
+
+    #[test]
+    fn timeout_runs_once() {
+        let clock = Clock::default();
+        let (_handle, mut running) = clock.run(false);
+        assert_eq!(poll(&mut running), Poll::Pending);
+
+        clock.tick();
+        clock.tick();
+        assert_eq!(poll(&mut running), Poll::Ready(()));
+        assert_eq!(clock.calls.get(), 1);
+    }
+
+    #[test]
+    fn dropping_the_handle_stops_the_timer() {
+        let clock = Clock::default();
+        let (handle, mut running) = clock.run(true);
+        assert_eq!(poll(&mut running), Poll::Pending);
+
+        drop(handle);
+        clock.tick();
+        assert_eq!(poll(&mut running), Poll::Ready(()));
+        assert_eq!(clock.calls.get(), 0);
+    }
+}
@@ -0,0 +1,418 @@
+use crate::{ObserverContext, ObserverGuard, ResizeEntry};
+use dioxus_core::ScopeState;
+use dioxus_html::{
+    MountedData, MountedError, MountedEvent, MountedFuture, MountedResult, ScrollEvent,
+};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    ops::Range,
+    rc::Rc,
+    sync::Arc,
+};
+
+/// Render only the rows of a long list that are scrolled into view
+///
+/// Diffing and mounting tens of thousands of rows makes every render slow. This hook tracks the scroll position and the
+/// height of the scrolling container, and gives back the [`range`](UseVirtualList::range) of rows that are visible, with a
+/// few rows of [`overscan`](VirtualListOptions::overscan) around them. The rows before and after the range are replaced
+/// by empty space, so the scrollbar still covers the whole list.
+///
+/// Every row has to be [`item_height`](VirtualListOptions::new) tall. The container is attached from its `onmounted`
+/// event with [`UseVirtualList::observe`] and reports its scroll position with [`UseVirtualList::handle_scroll`]. The
+/// component only re-renders when a different row comes into view.
+///
+/// Rows are keyed by `key`, which should be the key the rows are rendered with. When rows are inserted or removed before
+/// the visible ones, the list scrolls by the same amount so the rows that were in view stay in place.
+///
+/// The height of the container is measured with the observers of the renderer, which the web and desktop renderers
+/// provide through an [`ObserverContext`]. Without one, the height comes from the scroll events and is
+/// [estimated](VirtualListOptions::estimated_viewport_height) until the first one.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// let list = use_virtual_list(cx, &messages, |message| message.id, VirtualListOptions::new(32.0));
+///
+/// cx.render(rsx!{
+///     div {
+///         style: "height: 600px; overflow-y: auto",
+///         onmounted: move |evt| { let _ = list.observe(&evt); },
+///         onscroll: move |evt| list.handle_scroll(&evt),
+///         div {
+///             style: "height: {list.total_height()}px; padding-top: {list.offset()}px; box-sizing: border-box",
+///             for message in &messages[list.range()] {
+///                 div { key: "{message.id}", style: "height: 32px", "{message.text}" }
+///             }
+///         }
+///     }
+/// })
+/// ```
+pub fn use_virtual_list<'a, T, K: PartialEq + 'static>(
+    cx: &'a ScopeState,
+    items: &[T],
+    key: impl Fn(&T) -> K,
+    options: VirtualListOptions,
+) -> &'a UseVirtualList {
+    let list = cx.use_hook(|| UseVirtualList {
+        inner: Rc::new(VirtualListInner {
+            observer: cx.consume_context::<ObserverContext>(),
+            update: cx.schedule_update(),
+            container: RefCell::new(None),
+            guard: RefCell::new(None),
+            anchor: RefCell::new(None),
+            scroll: Cell::new(Scroll {
+                top: 0.0,
+                left: 0.0,
+                viewport: None,
+            }),
+            layout: Cell::new(Layout::default()),
+            rendered: Cell::new(Rendered::default()),
+        }),
+    });
+    let inner = &list.inner;
+
+    let mut scroll = inner.scroll.get();
+    let layout = Layout {
+        item_count: items.len(),
+        item_height: options.item_height,
+        overscan: options.overscan,
+        estimated_viewport: options.estimated_viewport_height,
+    };
+    inner.layout.set(layout);
+
+    // Keep the row that was at the top of the viewport in place when rows are added or removed before it
+    let anchor = inner.anchor.borrow_mut().take();
+    if let Some((index, anchor)) = anchor {
+        let moved_to = match anchor.downcast_ref::<K>() {
+            Some(anchor) if items.get(index).map_or(true, |item| key(item) != *anchor) => {
+                items.iter().position(|item| key(item) == *anchor)
+            }
+            _ => None,
+        };
+
+        if let Some(moved_to) = moved_to {
+            scroll.top += (moved_to as f64 - index as f64) * layout.item_height;
+            scroll.top = scroll.top.min(layout.max_scroll(scroll.viewport)).max(0.0);
+
+            if let Some(container) = inner.container.borrow().clone() {
+                let (top, left) = (scroll.top, scroll.left);
+                // The spacer before the rows only grows once the edits of this render are applied
+                cx.push_on_mount(move || {
+                    // Both renderers start scrolling right away, so there's nothing to wait for
+                    drop(container.set_scroll_position(top, left));
+                });
+            }
+        }
+    }
+
+    // The list may have gotten shorter than the scroll position
+    scroll.top = scroll.top.min(layout.max_scroll(scroll.viewport)).max(0.0);
+    inner.scroll.set(scroll);
+
+    let rendered = layout.rendered(scroll);
+    inner.rendered.set(rendered);
+    if let Some(item) = items.get(rendered.first_visible) {
+        *inner.anchor.borrow_mut() = Some((rendered.first_visible, Box::new(key(item))));
+    }
+
+    list
+}
+
+/// How a [`use_virtual_list`] lays out its rows
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VirtualListOptions {
+    item_height: f64,
+    overscan: usize,
+    estimated_viewport_height: f64,
+}
+
+impl VirtualListOptions {
+    /// Lay out rows that are all `item_height` CSS pixels tall
+    pub fn new(item_height: f64) -> Self {
+        Self {
+            item_height,
+            overscan: 3,
+            estimated_viewport_height: 800.0,
+        }
+    }
+
+    /// Render this many rows before and after the visible ones, so fast scrolling doesn't show empty space. `3` by default.
+    pub fn overscan(mut self, rows: usize) -> Self {
+        self.overscan = rows;
+        self
+    }
+
+    /// The height of the container until it's measured, in CSS pixels. `800` by default.
+    pub fn estimated_viewport_height(mut self, height: f64) -> Self {
+        self.estimated_viewport_height = height;
+        self
+    }
+
+    /// The height of every row
+    pub fn get_item_height(&self) -> f64 {
+        self.item_height
+    }
+
+    /// The rows rendered around the visible ones
+    pub fn get_overscan(&self) -> usize {
+        self.overscan
+    }
+}
+
+/// A handle to a [`use_virtual_list`]
+#[derive(Clone)]
+pub struct UseVirtualList {
+    inner: Rc<VirtualListInner>,
+}
+
+struct VirtualListInner {
+    observer: Option<ObserverContext>,
+    update: Arc<dyn Fn()>,
+    container: RefCell<Option<MountedData>>,
+    guard: RefCell<Option<ObserverGuard>>,
+    /// The index and key of the row at the top of the viewport in the last render
+    anchor: RefCell<Option<(usize, Box<dyn Any>)>>,
+    scroll: Cell<Scroll>,
+    layout: Cell<Layout>,
+    rendered: Cell<Rendered>,
+}
+
+impl UseVirtualList {
+    /// Attach the scrolling container from its `onmounted` event, and measure its height from then on
+    ///
+    /// The container is kept even if its height can't be measured, which is reported as an error.
+    pub fn observe(&self, container: &MountedEvent) -> MountedResult<()> {
+        let container: &MountedData = container;
+        *self.inner.container.borrow_mut() = Some(container.clone());
+
+        // The old observation is stopped before the new one starts
+        let guard = self.inner.guard.borrow_mut().take();
+        drop(guard);
+
+        let observer = self
+            .inner
+            .observer
+            .as_ref()
+            .ok_or(MountedError::NotSupported)?;
+
+        let inner = Rc::downgrade(&self.inner);
+        let guard = observer.0.observe_resize(
+            container,
+            Box::new(move |entry: ResizeEntry| {
+                if let Some(inner) = inner.upgrade() {
+                    let mut scroll = inner.scroll.get();
+                    scroll.viewport = Some(entry.content_rect.height());
+                    inner.set_scroll(scroll);
+                }
+            }),
+        )?;
+        *self.inner.guard.borrow_mut() = Some(guard);
+        Ok(())
+    }
+
+    /// Track the scroll position from an `onscroll` event of the container
+    pub fn handle_scroll(&self, event: &ScrollEvent) {
+        let data = &event.data;
+        self.inner.set_scroll(Scroll {
+            top: data.scroll_top,
+            left: data.scroll_left,
+            viewport: Some(data.client_height).filter(|height| *height > 0.0),
+        });
+    }
+
+    /// Scroll the container so the row at `index` is at the top
+    pub fn scroll_to_index(&self, index: usize) -> MountedFuture<()> {
+        let layout = self.inner.layout.get();
+        let mut scroll = self.inner.scroll.get();
+        scroll.top = (index as f64 * layout.item_height)
+            .min(layout.max_scroll(scroll.viewport))
+            .max(0.0);
+        self.inner.set_scroll(scroll);
+
+        match self.inner.container.borrow().as_ref() {
+            Some(container) => container.set_scroll_position(scroll.top, scroll.left),
+            None => Box::pin(async { Err(MountedError::NotSupported) }),
+        }
+    }
+
+    /// The rows to render
+    pub fn range(&self) -> Range<usize> {
+        self.inner.rendered.get().range()
+    }
+
+    /// The height of the rows before the rendered ones, in CSS pixels
+    pub fn offset(&self) -> f64 {
+        self.inner.rendered.get().start as f64 * self.inner.layout.get().item_height
+    }
+
+    /// The height of all of the rows, in CSS pixels
+    pub fn total_height(&self) -> f64 {
+        self.inner.layout.get().total_height()
+    }
+
+    /// How far the container is scrolled down, in CSS pixels
+    pub fn scroll_top(&self) -> f64 {
+        self.inner.scroll.get().top
+    }
+}
+
+impl VirtualListInner {
+    /// Store the new scroll position, and re-render if different rows come into view
+    fn set_scroll(&self, scroll: Scroll) {
+        self.scroll.set(scroll);
+        if self.layout.get().rendered(scroll) != self.rendered.get() {
+            (self.update)();
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Scroll {
+    top: f64,
+    left: f64,
+    /// The height of the container, once it's known
+    viewport: Option<f64>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Layout {
+    item_count: usize,
+    item_height: f64,
+    overscan: usize,
+    estimated_viewport: f64,
+}
+
+/// The rows a render of the list covers
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Rendered {
+    first_visible: usize,
+    start: usize,
+    end: usize,
+}
+
+impl Rendered {
+    fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+impl Layout {
+    fn total_height(&self) -> f64 {
+        self.item_count as f64 * self.item_height
+    }
+
+    fn max_scroll(&self, viewport: Option<f64>) -> f64 {
+        (self.total_height() - viewport.unwrap_or(self.estimated_viewport)).max(0.0)
+    }
+
+    fn rendered(&self, scroll: Scroll) -> Rendered {
+        if self.item_count == 0 || self.item_height <= 0.0 {
+            return Rendered::default();
+        }
+
+        let viewport = scroll.viewport.unwrap_or(self.estimated_viewport);
+        let last = self.item_count - 1;
+        let first_visible = ((scroll.top / self.item_height).floor().max(0.0) as usize).min(last);
+        let end_visible = (((scroll.top + viewport) / self.item_height).ceil().max(0.0) as usize)
+            .min(self.item_count);
+
+        Rendered {
+            first_visible,
+            start: first_visible.saturating_sub(self.overscan),
+            end: (end_visible.max(first_visible + 1) + self.overscan).min(self.item_count),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::prelude::*;
+
+    fn layout(item_count: usize) -> Layout {
+        Layout {
+            item_count,
+            item_height: 20.0,
+            overscan: 2,
+            estimated_viewport: 100.0,
+        }
+    }
+
+    fn scrolled(top: f64, viewport: Option<f64>) -> Scroll {
+        Scroll {
+            top,
+            left: 0.0,
+            viewport,
+        }
+    }
+
+    #[test]
+    fn renders_the_visible_rows_and_the_overscan() {
+        let layout = layout(50_000);
+
+        assert_eq!(layout.rendered(scrolled(0.0, None)).range(), 0..7);
+        assert_eq!(
+            layout.rendered(scrolled(2000.0, Some(200.0))).range(),
+            98..112
+        );
+        // A row that's partly scrolled out of view is still visible
+        assert_eq!(
+            layout.rendered(scrolled(2010.0, Some(200.0))).range(),
+            98..113
+        );
+        assert_eq!(
+            layout.rendered(scrolled(999_900.0, Some(100.0))).range(),
+            49_993..50_000
+        );
+        assert_eq!(layout.total_height(), 1_000_000.0);
+    }
+
+    #[test]
+    fn renders_nothing_without_rows() {
+        assert_eq!(
+            layout(0).rendered(scrolled(500.0, Some(100.0))).range(),
+            0..0
+        );
+        assert_eq!(layout(3).rendered(scrolled(0.0, Some(100.0))).range(), 0..3);
+    }
+
+    thread_local! {
+        static ITEMS: RefCell<Vec<u32>> = RefCell::new((0..1000).collect());
+        static LIST: RefCell<Option<UseVirtualList>> = RefCell::new(None);
+    }
+
+    fn app(cx: Scope) -> Element {
+        let items = ITEMS.with(|items| items.borrow().clone());
+        let list = use_virtual_list(cx, &items, |item| *item, VirtualListOptions::new(20.0));
+        LIST.with(|l| *l.borrow_mut() = Some(list.clone()));
+        None
+    }
+
+    #[test]
+    fn keeps_the_visible_rows_in_place() {
+        let mut dom = VirtualDom::new(app);
+        _ = dom.rebuild();
+
+        let list = LIST.with(|l| l.borrow().clone().unwrap());
+        list.inner.set_scroll(scrolled(2000.0, Some(200.0)));
+        dom.mark_dirty(ScopeId(0));
+        _ = dom.render_immediate();
+        assert_eq!(list.range(), 97..113);
+
+        // Ten rows are added at the top, which pushes item 100 down to row 110
+        ITEMS.with(|items| {
+            items.borrow_mut().splice(0..0, 1000..1010);
+        });
+        dom.mark_dirty(ScopeId(0));
+        _ = dom.render_immediate();
+        assert_eq!(list.scroll_top(), 2200.0);
+        assert_eq!(list.range(), 107..123);
+
+        // Rows after the viewport don't move it
+        ITEMS.with(|items| items.borrow_mut().truncate(500));
+        dom.mark_dirty(ScopeId(0));
+        _ = dom.render_immediate();
+        assert_eq!(list.scroll_top(), 2200.0);
+    }
+}
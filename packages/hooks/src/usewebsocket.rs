@@ -0,0 +1,602 @@
+use crate::usetimer::sleep;
+use dioxus_core::ScopeState;
+use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use futures_util::{
+    future::{select, Either},
+    Stream, StreamExt,
+};
+use std::{
+    cell::{Cell, RefCell},
+    pin::Pin,
+    rc::Rc,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// Connect to a websocket for as long as the component is mounted
+///
+/// Messages that arrive mark the component as dirty, and can be read while rendering with
+/// [`UseWebSocket::try_next_message`] or consumed as a [`Stream`] through [`UseWebSocket::messages`]. Messages sent
+/// before the socket is open are held until it is.
+///
+/// On web this uses the browser's `WebSocket`. Everywhere else the socket runs on its own thread with
+/// `tokio-tungstenite`, so it works with any executor. `wss://` urls need the `websocket-tls` feature there.
+///
+/// By default, the socket reconnects whenever it's closed, backing off between attempts. The socket is closed when the
+/// component is removed.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// let socket = use_websocket(cx, "ws://localhost:3000/chat");
+/// let log = use_ref(cx, Vec::new);
+///
+/// while let Some(WebSocketMessage::Text(text)) = socket.try_next_message() {
+///     log.write_silent().push(text);
+/// }
+///
+/// cx.render(rsx!{
+///     log.read().iter().map(|line| rsx!( p { "{line}" } ))
+///     button {
+///         onclick: move |_| socket.send(WebSocketMessage::Text("hello".into())),
+///         "Say hello"
+///     }
+/// })
+/// ```
+pub fn use_websocket(cx: &ScopeState, url: impl Into<String>) -> &UseWebSocket {
+    use_websocket_with_options(cx, url, WebSocketOptions::default())
+}
+
+/// A [`use_websocket`] that's configured with [`WebSocketOptions`]
+///
+/// The url and options are only read when the hook is first run.
+pub fn use_websocket_with_options(
+    cx: &ScopeState,
+    url: impl Into<String>,
+    options: WebSocketOptions,
+) -> &UseWebSocket {
+    cx.use_hook(|| {
+        let (outgoing_tx, outgoing_rx) = futures_channel::mpsc::unbounded();
+        let (incoming_tx, incoming_rx) = futures_channel::mpsc::unbounded();
+
+        let status = Rc::new(Cell::new(WebSocketStatus::Connecting));
+        let driver = Driver {
+            url: url.into(),
+            options,
+            status: status.clone(),
+            incoming: incoming_tx,
+            update: cx.schedule_update(),
+        };
+        cx.push_future(driver.run(outgoing_rx));
+
+        UseWebSocket {
+            tx: outgoing_tx,
+            status,
+            messages: WebSocketMessages {
+                rx: Rc::new(RefCell::new(incoming_rx)),
+            },
+        }
+    })
+}
+
+/// A message sent over a websocket
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WebSocketMessage {
+    /// A text message
+    Text(String),
+
+    /// A binary message
+    Binary(Vec<u8>),
+}
+
+/// The state of the connection of a [`use_websocket`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebSocketStatus {
+    /// The first connection is being made
+    Connecting,
+
+    /// The socket is open and messages are flowing
+    Open,
+
+    /// The socket was closed and will be opened again after a backoff
+    Reconnecting,
+
+    /// The socket was closed and won't be opened again
+    Closed,
+}
+
+/// How a [`use_websocket_with_options`] deals with the socket closing
+#[derive(Clone, Copy, Debug)]
+pub struct WebSocketOptions {
+    reconnect: bool,
+    max_retries: Option<usize>,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl Default for WebSocketOptions {
+    fn default() -> Self {
+        Self {
+            reconnect: true,
+            max_retries: None,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl WebSocketOptions {
+    /// Create the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether the socket is opened again after it's closed. On by default.
+    pub fn reconnect(mut self, reconnect: bool) -> Self {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// Give up after this many attempts in a row fail to reconnect. Unlimited by default.
+    pub fn max_retries(mut self, retries: usize) -> Self {
+        self.max_retries = Some(retries);
+        self
+    }
+
+    /// Set how long to wait before the first attempt to reconnect. Every failed attempt doubles the wait, up to
+    /// `max`. Defaults to 500ms and 30s.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+}
+
+/// A handle to a [`use_websocket`]
+#[derive(Clone)]
+pub struct UseWebSocket {
+    tx: UnboundedSender<WebSocketMessage>,
+    status: Rc<Cell<WebSocketStatus>>,
+    messages: WebSocketMessages,
+}
+
+impl UseWebSocket {
+    /// Send a message, waiting for the socket to open if it isn't yet
+    pub fn send(&self, message: WebSocketMessage) {
+        let _ = self.tx.unbounded_send(message);
+    }
+
+    /// Get the state of the connection
+    pub fn status(&self) -> WebSocketStatus {
+        self.status.get()
+    }
+
+    /// Take the oldest message that arrived, if there is one
+    ///
+    /// This is meant to be called in a loop while rendering, since every message schedules a new render.
+    pub fn try_next_message(&self) -> Option<WebSocketMessage> {
+        self.messages.rx.borrow_mut().try_next().ok().flatten()
+    }
+
+    /// Get the messages that arrive as a [`Stream`]
+    ///
+    /// Every message is only handed out once, either through the stream or [`UseWebSocket::try_next_message`].
+    pub fn messages(&self) -> WebSocketMessages {
+        self.messages.clone()
+    }
+}
+
+/// The messages of a [`use_websocket`], from [`UseWebSocket::messages`]
+#[derive(Clone)]
+pub struct WebSocketMessages {
+    rx: Rc<RefCell<UnboundedReceiver<WebSocketMessage>>>,
+}
+
+impl Stream for WebSocketMessages {
+    type Item = WebSocketMessage;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut *self.rx.borrow_mut()).poll_next(cx)
+    }
+}
+
+/// What the platform reports about a connection
+enum SocketEvent {
+    Open,
+    Message(WebSocketMessage),
+    Closed,
+}
+
+/// The task that keeps the socket connected
+struct Driver {
+    url: String,
+    options: WebSocketOptions,
+    status: Rc<Cell<WebSocketStatus>>,
+    incoming: UnboundedSender<WebSocketMessage>,
+    update: Arc<dyn Fn()>,
+}
+
+impl Driver {
+    fn set_status(&self, status: WebSocketStatus) {
+        if self.status.replace(status) != status {
+            (self.update)();
+        }
+    }
+
+    async fn run(self, mut outgoing: UnboundedReceiver<WebSocketMessage>) {
+        let mut retries = 0;
+        let mut backoff = self.options.initial_backoff;
+
+        loop {
+            let mut connection = platform::Connection::open(&self.url);
+            let mut open = false;
+
+            loop {
+                // Outgoing messages wait in the channel until the socket is open
+                let next = if open {
+                    match select(outgoing.next(), connection.events.next()).await {
+                        Either::Left((message, _)) => Either::Left(message),
+                        Either::Right((event, _)) => Either::Right(event),
+                    }
+                } else {
+                    Either::Right(connection.events.next().await)
+                };
+
+                let event = match next {
+                    Either::Left(Some(message)) => {
+                        connection.send(message);
+                        continue;
+                    }
+                    Either::Left(None) => return,
+                    Either::Right(event) => event,
+                };
+
+                match event {
+                    Some(SocketEvent::Open) => {
+                        open = true;
+                        retries = 0;
+                        backoff = self.options.initial_backoff;
+                        self.set_status(WebSocketStatus::Open);
+                    }
+                    Some(SocketEvent::Message(message)) => {
+                        if self.incoming.unbounded_send(message).is_ok() {
+                            (self.update)();
+                        }
+                    }
+                    Some(SocketEvent::Closed) | None => break,
+                }
+            }
+
+            drop(connection);
+
+            let exhausted = self.options.max_retries.map_or(false, |max| retries >= max);
+            if !self.options.reconnect || exhausted {
+                self.set_status(WebSocketStatus::Closed);
+                return;
+            }
+
+            retries += 1;
+            self.set_status(WebSocketStatus::Reconnecting);
+            sleep(backoff).await;
+            backoff = (backoff * 2).min(self.options.max_backoff);
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod platform {
+    use super::{SocketEvent, WebSocketMessage};
+    use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+    use wasm_bindgen::{closure::Closure, JsCast};
+    use web_sys::{BinaryType, MessageEvent, WebSocket};
+
+    /// A browser websocket and the handlers that feed its events into a channel
+    pub(super) struct Connection {
+        socket: Option<WebSocket>,
+        _handlers: Vec<Closure<dyn FnMut(web_sys::Event)>>,
+        pub(super) events: UnboundedReceiver<SocketEvent>,
+    }
+
+    impl Connection {
+        pub(super) fn open(url: &str) -> Self {
+            let (tx, events) = unbounded();
+
+            let socket = match WebSocket::new(url) {
+                Ok(socket) => socket,
+                Err(err) => {
+                    log::error!("Failed to open a websocket to {}: {:?}", url, err);
+                    let _ = tx.unbounded_send(SocketEvent::Closed);
+                    return Self {
+                        socket: None,
+                        _handlers: Vec::new(),
+                        events,
+                    };
+                }
+            };
+            socket.set_binary_type(BinaryType::Arraybuffer);
+
+            let handler = |tx: &UnboundedSender<SocketEvent>,
+                           f: fn(web_sys::Event) -> SocketEvent| {
+                let tx = tx.clone();
+                Closure::wrap(Box::new(move |event: web_sys::Event| {
+                    let _ = tx.unbounded_send(f(event));
+                }) as Box<dyn FnMut(web_sys::Event)>)
+            };
+
+            let onopen = handler(&tx, |_| SocketEvent::Open);
+            let onclose = handler(&tx, |_| SocketEvent::Closed);
+            let onmessage = handler(&tx, |event| {
+                let data = event.unchecked_into::<MessageEvent>().data();
+                match data.as_string() {
+                    Some(text) => SocketEvent::Message(WebSocketMessage::Text(text)),
+                    None => SocketEvent::Message(WebSocketMessage::Binary(
+                        js_sys::Uint8Array::new(&data).to_vec(),
+                    )),
+                }
+            });
+
+            socket.set_onopen(Some(onopen.as_ref().unchecked_ref()));
+            socket.set_onclose(Some(onclose.as_ref().unchecked_ref()));
+            socket.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+
+            Self {
+                socket: Some(socket),
+                _handlers: vec![onopen, onclose, onmessage],
+                events,
+            }
+        }
+
+        pub(super) fn send(&self, message: WebSocketMessage) {
+            let socket = match &self.socket {
+                Some(socket) => socket,
+                None => return,
+            };
+
+            let sent = match message {
+                WebSocketMessage::Text(text) => socket.send_with_str(&text),
+                WebSocketMessage::Binary(bytes) => socket.send_with_u8_array(&bytes),
+            };
+            if let Err(err) = sent {
+                log::error!("Failed to send a websocket message: {:?}", err);
+            }
+        }
+    }
+
+    impl Drop for Connection {
+        fn drop(&mut self) {
+            if let Some(socket) = &self.socket {
+                socket.set_onopen(None);
+                socket.set_onclose(None);
+                socket.set_onmessage(None);
+                let _ = socket.close();
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod platform {
+    use super::{SocketEvent, WebSocketMessage};
+    use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+    use futures_util::{
+        future::{select, Either},
+        SinkExt, StreamExt,
+    };
+    use tokio_tungstenite::tungstenite::Message;
+
+    /// A socket that runs on its own thread, talking to the driver through channels
+    ///
+    /// Dropping the connection closes the channel of outgoing messages, which closes the socket.
+    pub(super) struct Connection {
+        outgoing: UnboundedSender<WebSocketMessage>,
+        pub(super) events: UnboundedReceiver<SocketEvent>,
+    }
+
+    impl Connection {
+        pub(super) fn open(url: &str) -> Self {
+            let (outgoing, outgoing_rx) = unbounded();
+            let (events_tx, events) = unbounded();
+
+            let url = url.to_string();
+            std::thread::spawn(move || {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build();
+
+                match runtime {
+                    Ok(runtime) => runtime.block_on(run(url, outgoing_rx, events_tx)),
+                    Err(err) => {
+                        log::error!("Failed to start the runtime of a websocket: {}", err);
+                        let _ = events_tx.unbounded_send(SocketEvent::Closed);
+                    }
+                }
+            });
+
+            Self { outgoing, events }
+        }
+
+        pub(super) fn send(&self, message: WebSocketMessage) {
+            let _ = self.outgoing.unbounded_send(message);
+        }
+    }
+
+    /// What woke the socket up
+    enum Next {
+        Send(Option<WebSocketMessage>),
+        Receive(Option<Message>),
+    }
+
+    async fn run(
+        url: String,
+        mut outgoing: UnboundedReceiver<WebSocketMessage>,
+        events: UnboundedSender<SocketEvent>,
+    ) {
+        let mut socket = match tokio_tungstenite::connect_async(url.as_str()).await {
+            Ok((socket, _)) => socket,
+            Err(err) => {
+                log::error!("Failed to open a websocket to {}: {}", url, err);
+                let _ = events.unbounded_send(SocketEvent::Closed);
+                return;
+            }
+        };
+        let _ = events.unbounded_send(SocketEvent::Open);
+
+        loop {
+            // Waiting on a message is cancelled as soon as there's something to send, so a quiet socket never holds up
+            // the messages going out
+            let next = match select(outgoing.next(), socket.next()).await {
+                Either::Left((message, _)) => Next::Send(message),
+                Either::Right((message, _)) => Next::Receive(message.and_then(Result::ok)),
+            };
+
+            let message = match next {
+                Next::Send(Some(message)) => {
+                    let message = match message {
+                        WebSocketMessage::Text(text) => Message::Text(text),
+                        WebSocketMessage::Binary(bytes) => Message::Binary(bytes),
+                    };
+                    if let Err(err) = socket.send(message).await {
+                        log::error!("Failed to send a websocket message: {}", err);
+                    }
+                    continue;
+                }
+                Next::Send(None) => {
+                    let _ = socket.close(None).await;
+                    return;
+                }
+                Next::Receive(Some(Message::Text(text))) => WebSocketMessage::Text(text),
+                Next::Receive(Some(Message::Binary(bytes))) => WebSocketMessage::Binary(bytes),
+                Next::Receive(Some(Message::Close(_))) | Next::Receive(None) => break,
+                Next::Receive(Some(_)) => continue,
+            };
+
+            if events
+                .unbounded_send(SocketEvent::Message(message))
+                .is_err()
+            {
+                return;
+            }
+        }
+
+        let _ = events.unbounded_send(SocketEvent::Closed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::prelude::*;
+    use std::net::TcpListener;
+
+    thread_local! {
+        static URL: RefCell<String> = RefCell::new(String::new());
+        static RECEIVED: RefCell<Vec<WebSocketMessage>> = RefCell::new(Vec::new());
+    }
+
+    #[test]
+    fn echo() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        URL.with(|u| *u.borrow_mut() = url);
+
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut socket = tokio_tungstenite::tungstenite::accept(stream).unwrap();
+            while let Ok(message) = socket.read_message() {
+                if message.is_text() && socket.write_message(message).is_err() {
+                    return;
+                }
+            }
+        });
+
+        fn app(cx: Scope) -> Element {
+            let socket = use_websocket(cx, URL.with(|u| u.borrow().clone()));
+
+            // Sent before the socket is open, so it has to be held until it is
+            if cx.generation() == 0 {
+                socket.send(WebSocketMessage::Text("hello".to_string()));
+            }
+
+            while let Some(message) = socket.try_next_message() {
+                RECEIVED.with(|r| r.borrow_mut().push(message));
+            }
+
+            None
+        }
+
+        let mut dom = VirtualDom::new(app);
+        _ = dom.rebuild();
+
+        for _ in 0..100 {
+            std::thread::sleep(Duration::from_millis(20));
+            dom.process_events();
+            _ = dom.render_immediate();
+
+            if RECEIVED.with(|r| !r.borrow().is_empty()) {
+                break;
+            }
+        }
+
+        assert_eq!(
+            RECEIVED.with(|r| r.borrow().clone()),
+            [WebSocketMessage::Text("hello".to_string())]
+        );
+    }
+
+    #[test]
+    fn sends_while_nothing_arrives() {
+        thread_local! {
+            static SOCKET: RefCell<Option<UseWebSocket>> = RefCell::new(None);
+        }
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        URL.with(|u| *u.borrow_mut() = url);
+
+        // The server never sends anything, it only reports what it receives
+        let (received_tx, received) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut socket = tokio_tungstenite::tungstenite::accept(stream).unwrap();
+            while let Ok(message) = socket.read_message() {
+                if received_tx.send(message).is_err() {
+                    return;
+                }
+            }
+        });
+
+        fn app(cx: Scope) -> Element {
+            let socket = use_websocket(cx, URL.with(|u| u.borrow().clone()));
+            SOCKET.with(|s| *s.borrow_mut() = Some(socket.clone()));
+            None
+        }
+
+        let status = || SOCKET.with(|s| s.borrow().as_ref().unwrap().status());
+
+        let mut dom = VirtualDom::new(app);
+        _ = dom.rebuild();
+
+        for _ in 0..100 {
+            if status() == WebSocketStatus::Open {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+            dom.process_events();
+            _ = dom.render_immediate();
+        }
+        assert_eq!(status(), WebSocketStatus::Open);
+
+        SOCKET.with(|s| {
+            let socket = s.borrow();
+            socket
+                .as_ref()
+                .unwrap()
+                .send(WebSocketMessage::Text("ping".to_string()))
+        });
+        dom.process_events();
+
+        let message = received.recv_timeout(Duration::from_secs(2)).unwrap();
+        assert_eq!(message.into_text().unwrap(), "ping");
+    }
+}
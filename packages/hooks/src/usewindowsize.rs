@@ -0,0 +1,304 @@
+use dioxus_core::ScopeState;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+/// Get the inner size of the window, re-rendering the component whenever it changes
+///
+/// The size is in CSS pixels on web and in logical pixels on desktop, so it lines up with the sizes used in styles. It's
+/// provided by the renderer through a [`WindowContext`] and is zero without one.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// let size = use_window_size(cx);
+///
+/// cx.render(rsx!{
+///     "The window is {size.width} by {size.height}"
+/// })
+/// ```
+pub fn use_window_size(cx: &ScopeState) -> WindowSize {
+    let hook = cx.use_hook(|| {
+        let window = cx.consume_context::<WindowContext>();
+        let size = Rc::new(Cell::new(
+            window.as_ref().map(|w| w.0.size()).unwrap_or_default(),
+        ));
+
+        let check = {
+            let size = size.clone();
+            let update = cx.schedule_update();
+            move || {
+                if let Some(window) = &window {
+                    let new = window.0.size();
+                    if size.replace(new) != new {
+                        update();
+                    }
+                }
+            }
+        };
+
+        (size, WindowListener::new(check))
+    });
+
+    hook.0.get()
+}
+
+/// Check if a CSS media query matches the window, re-rendering the component whenever the answer changes
+///
+/// On web the query is answered by the browser's `matchMedia`. On desktop it's checked against the size of the window,
+/// which covers the `width`, `height` and `orientation` features along with their `min-` and `max-` forms. Features the
+/// renderer can't check don't match.
+///
+/// The query is read when the hook is first run.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// let wide = use_media_query(cx, "(min-width: 768px)");
+///
+/// cx.render(match wide {
+///     true => rsx!( Sidebar {} ),
+///     false => rsx!( Menu {} ),
+/// })
+/// ```
+pub fn use_media_query(cx: &ScopeState, query: &str) -> bool {
+    let hook = cx.use_hook(|| {
+        let window = cx.consume_context::<WindowContext>();
+        let query = query.to_string();
+        let matches = Rc::new(Cell::new(
+            window.as_ref().map_or(false, |w| w.0.matches_media(&query)),
+        ));
+
+        let check = {
+            let matches = matches.clone();
+            let update = cx.schedule_update();
+            move || {
+                if let Some(window) = &window {
+                    let new = window.0.matches_media(&query);
+                    if matches.replace(new) != new {
+                        update();
+                    }
+                }
+            }
+        };
+
+        (matches, WindowListener::new(check))
+    });
+
+    hook.0.get()
+}
+
+/// The inner size of a window
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WindowSize {
+    /// The width of the window
+    pub width: f64,
+
+    /// The height of the window
+    pub height: f64,
+}
+
+/// The window of a platform, provided by renderers for [`use_window_size`] and [`use_media_query`]
+///
+/// Renderers call [`window_changed`] whenever the window is resized, or anything else a media query could depend on
+/// changes.
+pub trait WindowBackend {
+    /// Get the inner size of the window
+    fn size(&self) -> WindowSize;
+
+    /// Check if a CSS media query matches the window
+    ///
+    /// By default, the query is checked against [`WindowBackend::size`].
+    fn matches_media(&self, query: &str) -> bool {
+        matches_size(query, self.size())
+    }
+}
+
+/// The context that hands a [`WindowBackend`] to [`use_window_size`] and [`use_media_query`]
+#[derive(Clone)]
+pub struct WindowContext(Rc<dyn WindowBackend>);
+
+impl WindowContext {
+    /// Wrap a window so it can be provided as a context
+    pub fn new(backend: impl WindowBackend + 'static) -> Self {
+        Self(Rc::new(backend))
+    }
+}
+
+/// Check the window again for every [`use_window_size`] and [`use_media_query`]
+///
+/// This is meant to be called by renderers when the window is resized. Only the components whose answer changed are
+/// re-rendered.
+pub fn window_changed() {
+    let checks = LISTENERS.with(|listeners| {
+        listeners
+            .borrow()
+            .iter()
+            .map(|(_, check)| check.clone())
+            .collect::<Vec<_>>()
+    });
+
+    checks.iter().for_each(|check| check());
+}
+
+thread_local! {
+    static LISTENERS: RefCell<Vec<(usize, Rc<dyn Fn()>)>> = RefCell::new(Vec::new());
+    static NEXT_LISTENER: Cell<usize> = Cell::new(0);
+}
+
+/// Keeps a check registered with [`window_changed`] for as long as the hook is alive
+struct WindowListener {
+    id: usize,
+}
+
+impl WindowListener {
+    fn new(check: impl Fn() + 'static) -> Self {
+        let id = NEXT_LISTENER.with(|next| next.replace(next.get() + 1));
+        LISTENERS.with(|listeners| listeners.borrow_mut().push((id, Rc::new(check))));
+        Self { id }
+    }
+}
+
+impl Drop for WindowListener {
+    fn drop(&mut self) {
+        LISTENERS.with(|listeners| listeners.borrow_mut().retain(|(id, _)| *id != self.id));
+    }
+}
+
+/// Check a media query against the size of a window
+///
+/// A query list matches if any of its queries match. Each query is an optional media type followed by features joined
+/// with `and`. Lengths are in `px`, or in `em` and `rem` of 16 pixels.
+fn matches_size(query: &str, size: WindowSize) -> bool {
+    query
+        .split(',')
+        .any(|query| matches_single(query.trim(), size).unwrap_or(false))
+}
+
+fn matches_single(query: &str, size: WindowSize) -> Option<bool> {
+    let query = query.to_ascii_lowercase();
+    let (negated, query) = match query.strip_prefix("not ") {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, query.strip_prefix("only ").unwrap_or(query.as_str())),
+    };
+
+    let mut matches = true;
+    for part in query.split(" and ") {
+        let part = part.trim();
+        let feature = match part.strip_prefix('(').and_then(|p| p.strip_suffix(')')) {
+            Some(feature) => feature,
+            None => {
+                // A media type. The window is always a screen.
+                matches &= match part {
+                    "all" | "screen" => true,
+                    "print" | "speech" => false,
+                    _ => return None,
+                };
+                continue;
+            }
+        };
+
+        let (name, value) = match feature.split_once(':') {
+            Some((name, value)) => (name.trim(), value.trim()),
+            None => return None,
+        };
+
+        matches &= match name {
+            "orientation" => match value {
+                "portrait" => size.height >= size.width,
+                "landscape" => size.width > size.height,
+                _ => return None,
+            },
+            "width" => size.width == length(value)?,
+            "min-width" => size.width >= length(value)?,
+            "max-width" => size.width <= length(value)?,
+            "height" => size.height == length(value)?,
+            "min-height" => size.height >= length(value)?,
+            "max-height" => size.height <= length(value)?,
+            _ => return None,
+        };
+    }
+
+    Some(matches != negated)
+}
+
+fn length(value: &str) -> Option<f64> {
+    let (number, scale) = if let Some(px) = value.strip_suffix("px") {
+        (px, 1.0)
+    } else if let Some(rem) = value.strip_suffix("rem") {
+        (rem, 16.0)
+    } else if let Some(em) = value.strip_suffix("em") {
+        (em, 16.0)
+    } else if value == "0" {
+        (value, 1.0)
+    } else {
+        return None;
+    };
+
+    number.trim().parse::<f64>().ok().map(|n| n * scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::prelude::*;
+
+    thread_local! {
+        static SIZE: Cell<WindowSize> = Cell::new(WindowSize { width: 800.0, height: 600.0 });
+        static RENDERS: Cell<usize> = Cell::new(0);
+    }
+
+    struct TestWindow;
+
+    impl WindowBackend for TestWindow {
+        fn size(&self) -> WindowSize {
+            SIZE.with(Cell::get)
+        }
+    }
+
+    fn resize(width: f64, height: f64) {
+        SIZE.with(|s| s.set(WindowSize { width, height }));
+        window_changed();
+    }
+
+    #[test]
+    fn media_queries() {
+        let size = WindowSize {
+            width: 800.0,
+            height: 600.0,
+        };
+
+        assert!(matches_size("(min-width: 768px)", size));
+        assert!(!matches_size("(max-width: 767px)", size));
+        assert!(matches_size("screen and (orientation: landscape)", size));
+        assert!(matches_size("not print", size));
+        assert!(matches_size("(max-width: 40em), (min-height: 500px)", size));
+        assert!(!matches_size("(prefers-color-scheme: dark)", size));
+    }
+
+    #[test]
+    fn renders_when_the_answer_changes() {
+        fn app(cx: Scope) -> Element {
+            RENDERS.with(|r| r.set(r.get() + 1));
+            use_media_query(cx, "(min-width: 768px)");
+            None
+        }
+
+        let mut dom = VirtualDom::new(app);
+        dom.base_scope()
+            .provide_context(WindowContext::new(TestWindow));
+        _ = dom.rebuild();
+
+        // The query still matches, so nothing renders
+        resize(1000.0, 600.0);
+        dom.process_events();
+        _ = dom.render_immediate();
+        assert_eq!(RENDERS.with(Cell::get), 1);
+
+        resize(500.0, 600.0);
+        dom.process_events();
+        _ = dom.render_immediate();
+        assert_eq!(RENDERS.with(Cell::get), 2);
+    }
+}
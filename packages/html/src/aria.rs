@@ -0,0 +1,231 @@
+//! Typed values for the ARIA attributes whose values come from a fixed set
+//!
+//! Every value converts into an attribute value, so it can be passed straight to the attribute:
+//!
+//! ```rust, ignore
+//! rsx! {
+//!     div {
+//!         role: AriaRole::Status,
+//!         aria_live: AriaLive::Polite,
+//!         "{message}"
+//!     }
+//! }
+//! ```
+//!
+//! Attributes that take `"true"` or `"false"` accept a plain `bool`.
+
+use dioxus_core::exports::bumpalo::Bump;
+use dioxus_core::{prelude::IntoAttributeValue, AttributeValue};
+use std::fmt::{Display, Formatter};
+
+macro_rules! attribute_values {
+    (
+        $(
+            $(#[$attr:meta])*
+            $name:ident {
+                $(
+                    $(#[$variant_attr:meta])*
+                    $variant:ident => $value:literal,
+                )*
+            }
+        )*
+    ) => {
+        $(
+            $(#[$attr])*
+            #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+            pub enum $name {
+                $(
+                    $(#[$variant_attr])*
+                    $variant,
+                )*
+            }
+
+            impl $name {
+                /// The value as it's written in the attribute
+                pub fn as_str(&self) -> &'static str {
+                    match self {
+                        $(Self::$variant => $value,)*
+                    }
+                }
+            }
+
+            impl Display for $name {
+                fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
+
+            impl<'a> IntoAttributeValue<'a> for $name {
+                fn into_value(self, _: &'a Bump) -> AttributeValue<'a> {
+                    AttributeValue::Text(self.as_str())
+                }
+            }
+        )*
+    };
+}
+
+attribute_values! {
+    /// The values of [`aria-autocomplete`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-autocomplete)
+    AriaAutocomplete {
+        None => "none",
+        Inline => "inline",
+        List => "list",
+        Both => "both",
+    }
+
+    /// The values of [`aria-checked`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-checked)
+    /// and [`aria-pressed`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-pressed)
+    AriaTristate {
+        False => "false",
+        True => "true",
+        Mixed => "mixed",
+    }
+
+    /// The values of [`aria-current`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-current)
+    AriaCurrent {
+        False => "false",
+        True => "true",
+        Page => "page",
+        Step => "step",
+        Location => "location",
+        Date => "date",
+        Time => "time",
+    }
+
+    /// The values of [`aria-haspopup`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-haspopup)
+    AriaHaspopup {
+        False => "false",
+        True => "true",
+        Menu => "menu",
+        Listbox => "listbox",
+        Tree => "tree",
+        Grid => "grid",
+        Dialog => "dialog",
+    }
+
+    /// The values of [`aria-invalid`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-invalid)
+    AriaInvalid {
+        False => "false",
+        True => "true",
+        Grammar => "grammar",
+        Spelling => "spelling",
+    }
+
+    /// The values of [`aria-live`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-live)
+    AriaLive {
+        /// Changes aren't announced unless the user is focused on the region
+        Off => "off",
+        /// Changes are announced when the user is idle
+        Polite => "polite",
+        /// Changes are announced right away, interrupting the user
+        Assertive => "assertive",
+    }
+
+    /// The values of [`aria-orientation`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-orientation)
+    AriaOrientation {
+        Horizontal => "horizontal",
+        Vertical => "vertical",
+    }
+
+    /// The values of [`aria-relevant`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-relevant)
+    AriaRelevant {
+        Additions => "additions",
+        AdditionsText => "additions text",
+        All => "all",
+        Removals => "removals",
+        Text => "text",
+    }
+
+    /// The values of [`aria-sort`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-sort)
+    AriaSort {
+        None => "none",
+        Ascending => "ascending",
+        Descending => "descending",
+        Other => "other",
+    }
+
+    /// The non-abstract [ARIA roles](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Roles)
+    AriaRole {
+        Alert => "alert",
+        AlertDialog => "alertdialog",
+        Application => "application",
+        Article => "article",
+        Banner => "banner",
+        Blockquote => "blockquote",
+        Button => "button",
+        Caption => "caption",
+        Cell => "cell",
+        Checkbox => "checkbox",
+        Code => "code",
+        ColumnHeader => "columnheader",
+        Combobox => "combobox",
+        Complementary => "complementary",
+        ContentInfo => "contentinfo",
+        Definition => "definition",
+        Deletion => "deletion",
+        Dialog => "dialog",
+        Document => "document",
+        Emphasis => "emphasis",
+        Feed => "feed",
+        Figure => "figure",
+        Form => "form",
+        Generic => "generic",
+        Grid => "grid",
+        GridCell => "gridcell",
+        Group => "group",
+        Heading => "heading",
+        Img => "img",
+        Insertion => "insertion",
+        Link => "link",
+        List => "list",
+        Listbox => "listbox",
+        ListItem => "listitem",
+        Log => "log",
+        Main => "main",
+        Marquee => "marquee",
+        Math => "math",
+        Menu => "menu",
+        Menubar => "menubar",
+        MenuItem => "menuitem",
+        MenuItemCheckbox => "menuitemcheckbox",
+        MenuItemRadio => "menuitemradio",
+        Meter => "meter",
+        Navigation => "navigation",
+        None => "none",
+        Note => "note",
+        Option => "option",
+        Paragraph => "paragraph",
+        Presentation => "presentation",
+        ProgressBar => "progressbar",
+        Radio => "radio",
+        RadioGroup => "radiogroup",
+        Region => "region",
+        Row => "row",
+        RowGroup => "rowgroup",
+        RowHeader => "rowheader",
+        Scrollbar => "scrollbar",
+        Search => "search",
+        Searchbox => "searchbox",
+        Separator => "separator",
+        Slider => "slider",
+        SpinButton => "spinbutton",
+        Status => "status",
+        Strong => "strong",
+        Subscript => "subscript",
+        Superscript => "superscript",
+        Switch => "switch",
+        Tab => "tab",
+        Table => "table",
+        TabList => "tablist",
+        TabPanel => "tabpanel",
+        Term => "term",
+        Textbox => "textbox",
+        Time => "time",
+        Timer => "timer",
+        Toolbar => "toolbar",
+        Tooltip => "tooltip",
+        Tree => "tree",
+        TreeGrid => "treegrid",
+        TreeItem => "treeitem",
+    }
+}
@@ -1,7 +1,7 @@
 #![allow(non_upper_case_globals)]
 #[cfg(feature = "hot-reload-context")]
-use crate::{map_global_attributes, map_svg_attributes};
-use crate::{GlobalAttributes, SvgAttributes};
+use crate::{map_global_attributes, map_mathml_attributes, map_svg_attributes};
+use crate::{GlobalAttributes, MathMlAttributes, SvgAttributes};
 #[cfg(feature = "hot-reload-context")]
 use dioxus_rsx::HotReloadingContext;
 
@@ -108,6 +108,85 @@ macro_rules! impl_element {
         impl GlobalAttributes for $name {}
     };
 
+    // MathML elements take their attributes without a namespace, like html elements
+    (
+        $(#[$attr:meta])*
+        $name:ident ["http://www.w3.org/1998/Math/MathML", $tag:tt] {
+            $(
+                $(#[$attr_method:meta])*
+                $fil:ident: $vil:ident $extra:tt,
+            )*
+        }
+    ) => {
+        #[allow(non_camel_case_types)]
+        $(#[$attr])*
+        pub struct $name;
+
+        impl MathMlAttributes for $name {}
+
+        impl $name {
+            pub const TAG_NAME: &'static str = $tag;
+            pub const NAME_SPACE: Option<&'static str> = Some("http://www.w3.org/1998/Math/MathML");
+
+            $(
+                impl_attribute!(
+                    $(#[$attr_method])*
+                    $fil: $vil ($extra),
+                );
+            )*
+        }
+    };
+
+    (
+        $(#[$attr:meta])*
+        $name:ident "http://www.w3.org/1998/Math/MathML" {
+            $(
+                $(#[$attr_method:meta])*
+                $fil:ident: $vil:ident $extra:tt,
+            )*
+        }
+    ) => {
+        impl_element!(
+            $(#[$attr])*
+            $name ["http://www.w3.org/1998/Math/MathML", (stringify!($name))] {
+                $(
+                    $(#[$attr_method])*
+                    $fil: $vil $extra,
+                )*
+            }
+        );
+    };
+
+    // An element whose tag isn't the name of its struct, because the name is a keyword or already taken by an html
+    // element
+    (
+        $(#[$attr:meta])*
+        $name:ident [$namespace:tt, $tag:tt] {
+            $(
+                $(#[$attr_method:meta])*
+                $fil:ident: $vil:ident $extra:tt,
+            )*
+        }
+    ) => {
+        #[allow(non_camel_case_types)]
+        $(#[$attr])*
+        pub struct $name;
+
+        impl SvgAttributes for $name {}
+
+        impl $name {
+            pub const TAG_NAME: &'static str = $tag;
+            pub const NAME_SPACE: Option<&'static str> = Some($namespace);
+
+            $(
+                impl_attribute!(
+                    $(#[$attr_method])*
+                    $fil: $vil in $namespace $extra
+                );
+            )*
+        }
+    };
+
     (
         $(#[$attr:meta])*
         $name:ident $namespace:tt {
@@ -151,6 +230,18 @@ macro_rules! impl_element_match {
         }
     };
 
+    (
+        $el:ident $name:ident [$namespace:tt, $tag:tt] {
+            $(
+                $fil:ident: $vil:ident $extra:tt,
+            )*
+        }
+    ) => {
+        if $el == stringify!($name) {
+            return Some(($tag, Some($namespace)));
+        }
+    };
+
     (
         $el:ident $name:ident $namespace:tt {
             $(
@@ -159,7 +250,7 @@ macro_rules! impl_element_match {
         }
     ) => {
         if $el == stringify!($name) {
-            return Some((stringify!($name), Some(stringify!($namespace))));
+            return Some((stringify!($name), Some($namespace)));
         }
     };
 }
@@ -182,6 +273,38 @@ macro_rules! impl_element_match_attributes {
         }
     };
 
+    (
+        $el:ident $attr:ident $name:ident "http://www.w3.org/1998/Math/MathML" {
+            $(
+                $fil:ident: $vil:ident $extra:tt,
+            )*
+        }
+    ) => {
+        if $el == stringify!($name) {
+            $(
+                impl_attribute_match!(
+                    $attr $fil: $vil ($extra),
+                );
+            )*
+        }
+    };
+
+    (
+        $el:ident $attr:ident $name:ident ["http://www.w3.org/1998/Math/MathML", $tag:tt] {
+            $(
+                $fil:ident: $vil:ident $extra:tt,
+            )*
+        }
+    ) => {
+        if $el == stringify!($name) {
+            $(
+                impl_attribute_match!(
+                    $attr $fil: $vil ($extra),
+                );
+            )*
+        }
+    };
+
     (
         $el:ident $attr:ident $name:ident $namespace:tt {
             $(
@@ -226,7 +349,9 @@ macro_rules! builder_constructors {
                         }
                     );
                 )*
-                map_global_attributes(attribute).or_else(|| map_svg_attributes(attribute))
+                map_global_attributes(attribute)
+                    .or_else(|| map_svg_attributes(attribute))
+                    .or_else(|| map_mathml_attributes(attribute))
             }
 
             fn map_element(element: &str) -> Option<(&'static str, Option<&'static str>)> {
@@ -257,6 +382,81 @@ macro_rules! builder_constructors {
     };
 }
 
+/// Declare custom elements, like web components, that can be used in rsx
+///
+/// Each element takes the name of its struct, its tag and the attributes it accepts. Attributes are written as they
+/// are in html unless they're given a name. The global html attributes are available on every custom element.
+///
+/// rsx looks elements up in the `dioxus_elements` module that's in scope, so declare them in a module of that name
+/// which also brings in the html elements:
+///
+/// ```rust, ignore
+/// mod dioxus_elements {
+///     pub use dioxus::prelude::dioxus_elements::*;
+///
+///     dioxus_html::custom_element! {
+///         /// A color picker from our component library
+///         color_picker "color-picker" {
+///             value;
+///             show_alpha: "show-alpha";
+///         };
+///     }
+/// }
+///
+/// rsx! {
+///     color_picker {
+///         value: "{color}",
+///         show_alpha: true,
+///         "oncolor-changed": move |evt: CustomEvent| log::info!("{}", evt.detail),
+///     }
+/// }
+/// ```
+///
+/// Listeners for the events of custom elements are written with a string name that starts with `on`. See
+/// [`custom_event`](crate::events::custom_event).
+#[macro_export]
+macro_rules! custom_element {
+    (
+        $(
+            $(#[$attr:meta])*
+            $name:ident $tag:literal {
+                $(
+                    $(#[$attr_method:meta])*
+                    $fil:ident $(: $fil_name:literal)?;
+                )*
+            };
+        )*
+    ) => {
+        $(
+            #[allow(non_camel_case_types)]
+            $(#[$attr])*
+            pub struct $name;
+
+            #[allow(non_upper_case_globals)]
+            impl $name {
+                pub const TAG_NAME: &'static str = $tag;
+                pub const NAME_SPACE: Option<&'static str> = None;
+
+                $(
+                    $(#[$attr_method])*
+                    pub const $fil: $crate::AttributeDiscription =
+                        ($crate::custom_element!(@name $fil $($fil_name)?), None, false);
+                )*
+            }
+
+            impl $crate::GlobalAttributes for $name {}
+        )*
+    };
+
+    (@name $fil:ident) => {
+        stringify!($fil)
+    };
+
+    (@name $fil:ident $fil_name:literal) => {
+        $fil_name
+    };
+}
+
 // Organized in the same order as
 // https://developer.mozilla.org/en-US/docs/Web/HTML/Element
 //
@@ -1266,10 +1466,12 @@ builder_constructors! {
     svg "http://www.w3.org/2000/svg" { };
 
 
-    // /// Build a
-    // /// [`<a>`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/a)
-    // /// element.
-    // a "http://www.w3.org/2000/svg" {};
+    /// Build a
+    /// [`<a>`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/a)
+    /// element.
+    ///
+    /// `a` is the html element of the same name, which doesn't work inside an `svg`.
+    svg_a ["http://www.w3.org/2000/svg", "a"] {};
 
     /// Build a
     /// [`<animate>`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/animate)
@@ -1466,10 +1668,10 @@ builder_constructors! {
     /// element.
     hatchpath "http://www.w3.org/2000/svg" {};
 
-    // /// Build a
-    // /// [`<image>`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/image)
-    // /// element.
-    // image "http://www.w3.org/2000/svg" {};
+    /// Build a
+    /// [`<image>`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/image)
+    /// element.
+    image "http://www.w3.org/2000/svg" {};
 
     /// Build a
     /// [`<line>`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/line)
@@ -1531,10 +1733,12 @@ builder_constructors! {
     /// element.
     rect "http://www.w3.org/2000/svg" {};
 
-    // /// Build a
-    // /// [`<script>`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/script)
-    // /// element.
-    // script "http://www.w3.org/2000/svg" {};
+    /// Build a
+    /// [`<script>`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/script)
+    /// element.
+    ///
+    /// `script` is the html element of the same name, which doesn't work inside an `svg`.
+    svg_script ["http://www.w3.org/2000/svg", "script"] {};
 
     /// Build a
     /// [`<set>`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/set)
@@ -1546,10 +1750,12 @@ builder_constructors! {
     /// element.
     stop "http://www.w3.org/2000/svg" {};
 
-    // /// Build a
-    // /// [`<style>`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/style)
-    // /// element.
-    // style "http://www.w3.org/2000/svg" {};
+    /// Build a
+    /// [`<style>`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/style)
+    /// element.
+    ///
+    /// `style` is the html element of the same name, which doesn't work inside an `svg`.
+    svg_style ["http://www.w3.org/2000/svg", "style"] {};
 
     // /// Build a
     // /// [`<svg>`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/svg)
@@ -1576,10 +1782,12 @@ builder_constructors! {
     /// element.
     textPath "http://www.w3.org/2000/svg" {};
 
-    // /// Build a
-    // /// [`<title>`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/title)
-    // /// element.
-    // title "http://www.w3.org/2000/svg" {};
+    /// Build a
+    /// [`<title>`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/title)
+    /// element.
+    ///
+    /// `title` is the html element of the same name, which doesn't work inside an `svg`.
+    svg_title ["http://www.w3.org/2000/svg", "title"] {};
 
     /// Build a
     /// [`<tspan>`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/tspan)
@@ -1591,10 +1799,199 @@ builder_constructors! {
     /// element.
     view "http://www.w3.org/2000/svg" {};
 
-    // /// Build a
-    // /// [`<use>`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/use)
-    // /// element.
-    // use "http://www.w3.org/2000/svg" {};
+    /// Build a
+    /// [`<use>`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/use)
+    /// element.
+    r#use ["http://www.w3.org/2000/svg", "use"] {};
+
+    // MathML components
+    /// Build a
+    /// [`<math>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/math)
+    /// element.
+    math "http://www.w3.org/1998/Math/MathML" {
+        display: String DEFAULT,
+    };
+
+    /// Build a
+    /// [`<semantics>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/semantics)
+    /// element.
+    semantics "http://www.w3.org/1998/Math/MathML" {};
+
+    /// Build a
+    /// [`<annotation>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/annotation)
+    /// element.
+    annotation "http://www.w3.org/1998/Math/MathML" {
+        encoding: String DEFAULT,
+    };
+
+    /// Build a
+    /// [`<annotation-xml>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/annotation-xml)
+    /// element.
+    annotation_xml ["http://www.w3.org/1998/Math/MathML", "annotation-xml"] {
+        encoding: String DEFAULT,
+    };
+
+    /// Build a
+    /// [`<merror>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/merror)
+    /// element.
+    merror "http://www.w3.org/1998/Math/MathML" {};
+
+    /// Build a
+    /// [`<mfrac>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mfrac)
+    /// element.
+    mfrac "http://www.w3.org/1998/Math/MathML" {
+        linethickness: String DEFAULT,
+    };
+
+    /// Build a
+    /// [`<mi>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mi)
+    /// element.
+    mi "http://www.w3.org/1998/Math/MathML" {
+        mathvariant: String DEFAULT,
+    };
+
+    /// Build a
+    /// [`<mmultiscripts>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mmultiscripts)
+    /// element.
+    mmultiscripts "http://www.w3.org/1998/Math/MathML" {};
+
+    /// Build a
+    /// [`<mn>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mn)
+    /// element.
+    mn "http://www.w3.org/1998/Math/MathML" {};
+
+    /// Build a
+    /// [`<mo>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mo)
+    /// element.
+    mo "http://www.w3.org/1998/Math/MathML" {
+        accent: String DEFAULT,
+        fence: String DEFAULT,
+        form: String DEFAULT,
+        largeop: String DEFAULT,
+        lspace: String DEFAULT,
+        maxsize: String DEFAULT,
+        minsize: String DEFAULT,
+        movablelimits: String DEFAULT,
+        rspace: String DEFAULT,
+        separator: String DEFAULT,
+        stretchy: String DEFAULT,
+        symmetric: String DEFAULT,
+    };
+
+    /// Build a
+    /// [`<mover>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mover)
+    /// element.
+    mover "http://www.w3.org/1998/Math/MathML" {
+        accent: String DEFAULT,
+    };
+
+    /// Build a
+    /// [`<mpadded>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mpadded)
+    /// element.
+    mpadded "http://www.w3.org/1998/Math/MathML" {
+        depth: String DEFAULT,
+        height: String DEFAULT,
+        lspace: String DEFAULT,
+        voffset: String DEFAULT,
+        width: String DEFAULT,
+    };
+
+    /// Build a
+    /// [`<mphantom>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mphantom)
+    /// element.
+    mphantom "http://www.w3.org/1998/Math/MathML" {};
+
+    /// Build a
+    /// [`<mprescripts>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mprescripts)
+    /// element.
+    mprescripts "http://www.w3.org/1998/Math/MathML" {};
+
+    /// Build a
+    /// [`<mroot>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mroot)
+    /// element.
+    mroot "http://www.w3.org/1998/Math/MathML" {};
+
+    /// Build a
+    /// [`<mrow>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mrow)
+    /// element.
+    mrow "http://www.w3.org/1998/Math/MathML" {};
+
+    /// Build a
+    /// [`<ms>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/ms)
+    /// element.
+    ms "http://www.w3.org/1998/Math/MathML" {};
+
+    /// Build a
+    /// [`<mspace>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mspace)
+    /// element.
+    mspace "http://www.w3.org/1998/Math/MathML" {
+        depth: String DEFAULT,
+        height: String DEFAULT,
+        width: String DEFAULT,
+    };
+
+    /// Build a
+    /// [`<msqrt>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/msqrt)
+    /// element.
+    msqrt "http://www.w3.org/1998/Math/MathML" {};
+
+    /// Build a
+    /// [`<mstyle>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mstyle)
+    /// element.
+    mstyle "http://www.w3.org/1998/Math/MathML" {};
+
+    /// Build a
+    /// [`<msub>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/msub)
+    /// element.
+    msub "http://www.w3.org/1998/Math/MathML" {};
+
+    /// Build a
+    /// [`<msubsup>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/msubsup)
+    /// element.
+    msubsup "http://www.w3.org/1998/Math/MathML" {};
+
+    /// Build a
+    /// [`<msup>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/msup)
+    /// element.
+    msup "http://www.w3.org/1998/Math/MathML" {};
+
+    /// Build a
+    /// [`<mtable>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mtable)
+    /// element.
+    mtable "http://www.w3.org/1998/Math/MathML" {};
+
+    /// Build a
+    /// [`<mtd>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mtd)
+    /// element.
+    mtd "http://www.w3.org/1998/Math/MathML" {
+        columnspan: String DEFAULT,
+        rowspan: String DEFAULT,
+    };
+
+    /// Build a
+    /// [`<mtext>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mtext)
+    /// element.
+    mtext "http://www.w3.org/1998/Math/MathML" {};
+
+    /// Build a
+    /// [`<mtr>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mtr)
+    /// element.
+    mtr "http://www.w3.org/1998/Math/MathML" {};
+
+    /// Build a
+    /// [`<munder>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/munder)
+    /// element.
+    munder "http://www.w3.org/1998/Math/MathML" {
+        accentunder: String DEFAULT,
+    };
+
+    /// Build a
+    /// [`<munderover>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/munderover)
+    /// element.
+    munderover "http://www.w3.org/1998/Math/MathML" {
+        accent: String DEFAULT,
+        accentunder: String DEFAULT,
+    };
 
 
 }
@@ -8,28 +8,34 @@ macro_rules! impl_event {
     ) => {
         $(
             $( #[$attr] )*
-            pub fn $name<'a>(_cx: &'a ::dioxus_core::ScopeState, _f: impl FnMut(::dioxus_core::Event<$data>) + 'a) -> ::dioxus_core::Attribute<'a> {
+            pub fn $name<'a, E: crate::events::EventReturn<T>, T>(_cx: &'a ::dioxus_core::ScopeState, mut _f: impl FnMut(::dioxus_core::Event<$data>) -> E + 'a) -> ::dioxus_core::Attribute<'a> {
                 ::dioxus_core::Attribute {
-                    name: stringify!($name),
-                    value: _cx.listener(_f),
+                    name: <E as crate::events::EventReturn<T>>::OPTIONS.attribute_name(_cx, stringify!($name)),
+                    value: _cx.listener(move |e: ::dioxus_core::Event<$data>| _f(e).spawn(_cx)),
                     namespace: None,
                     mounted_element: Default::default(),
                     volatile: false,
+                    live: None,
                 }
             }
         )*
     };
 }
 
+use dioxus_core::{Event, ListenerOptions};
+
 mod animation;
 mod clipboard;
 mod composition;
+mod custom;
 mod drag;
 mod focus;
 mod form;
+mod gesture;
 mod image;
 mod keyboard;
 mod media;
+mod mounted;
 mod mouse;
 mod pointer;
 mod scroll;
@@ -42,12 +48,15 @@ mod wheel;
 pub use animation::*;
 pub use clipboard::*;
 pub use composition::*;
+pub use custom::*;
 pub use drag::*;
 pub use focus::*;
 pub use form::*;
+pub use gesture::*;
 pub use image::*;
 pub use keyboard::*;
 pub use media::*;
+pub use mounted::*;
 pub use mouse::*;
 pub use pointer::*;
 pub use scroll::*;
@@ -57,6 +66,107 @@ pub use touch::*;
 pub use transition::*;
 pub use wheel::*;
 
+/// Something an event handler can return
+///
+/// Handlers either return nothing, or a future that gets spawned on the scope that rendered the listener. Like any
+/// other task on the scope, the future is cancelled if the scope is unmounted before it finishes.
+///
+/// ```rust, ignore
+/// button {
+///     onclick: move |_| async move {
+///         let user = fetch_user().await;
+///         name.set(user.name);
+///     },
+///     "Load"
+/// }
+/// ```
+pub trait EventReturn<P>: Sized {
+    /// How the listener of the handler is attached to its element, set with [`ListenerExt`]
+    const OPTIONS: ListenerOptions = ListenerOptions {
+        capture: false,
+        once: false,
+        passive: false,
+    };
+
+    /// Hand off any work left by the handler to the scope
+    fn spawn(self, _cx: &dioxus_core::ScopeState) {}
+}
+
+impl EventReturn<()> for () {}
+
+#[doc(hidden)]
+pub struct AsyncEventMarker;
+
+impl<F> EventReturn<AsyncEventMarker> for F
+where
+    F: std::future::Future<Output = ()> + 'static,
+{
+    fn spawn(self, cx: &dioxus_core::ScopeState) {
+        cx.spawn(self);
+    }
+}
+
+/// What a handler wrapped with [`ListenerExt::once`] returns
+pub struct OnceListener<R>(pub R);
+
+#[doc(hidden)]
+pub struct OnceMarker<P>(std::marker::PhantomData<P>);
+
+impl<P, R: EventReturn<P>> EventReturn<OnceMarker<P>> for OnceListener<R> {
+    const OPTIONS: ListenerOptions = R::OPTIONS.once(true);
+
+    fn spawn(self, cx: &dioxus_core::ScopeState) {
+        self.0.spawn(cx)
+    }
+}
+
+/// What a handler wrapped with [`ListenerExt::passive`] returns
+pub struct PassiveListener<R>(pub R);
+
+#[doc(hidden)]
+pub struct PassiveMarker<P>(std::marker::PhantomData<P>);
+
+impl<P, R: EventReturn<P>> EventReturn<PassiveMarker<P>> for PassiveListener<R> {
+    const OPTIONS: ListenerOptions = R::OPTIONS.passive(true);
+
+    fn spawn(self, cx: &dioxus_core::ScopeState) {
+        self.0.spawn(cx)
+    }
+}
+
+/// Options for the listener of an event handler
+///
+/// The options can be combined, and work with capturing listeners too:
+///
+/// ```rust, ignore
+/// let on_scroll = move |evt: ScrollEvent| offset.set(evt.scroll_top);
+/// let on_click = move |_: MouseEvent| log::info!("clicked for the first time");
+///
+/// rsx! {
+///     div { onscroll: on_scroll.passive(), button { onclick: on_click.once() } }
+/// }
+/// ```
+pub trait ListenerExt<T: 'static, R>: FnMut(Event<T>) -> R + Sized {
+    /// Only run the handler for the first event, until the element is removed
+    fn once<'a>(mut self) -> Box<dyn FnMut(Event<T>) -> OnceListener<R> + 'a>
+    where
+        Self: 'a,
+    {
+        Box::new(move |evt: Event<T>| OnceListener(self(evt)))
+    }
+
+    /// Promise the renderer that the handler never calls [`Event::prevent_default`], so the platform doesn't wait for
+    /// it before scrolling
+    fn passive<'a>(mut self) -> Box<dyn FnMut(Event<T>) -> PassiveListener<R> + 'a>
+    where
+        Self: 'a,
+    {
+        Box::new(move |evt: Event<T>| PassiveListener(self(evt)))
+    }
+}
+
+impl<T: 'static, R, F: FnMut(Event<T>) -> R> ListenerExt<T, R> for F {}
+
 pub fn event_bubbles(evt: &str) -> bool {
     match evt {
         "copy" => true,
@@ -141,6 +251,12 @@ pub fn event_bubbles(evt: &str) -> bool {
         "animationiteration" => true,
         "transitionend" => true,
         "toggle" => true,
-        _ => true,
+        "mounted" => false,
+        "load" => false,
+        "swipe" => false,
+        "pinch" => false,
+        "longpress" => false,
+        // Custom events, like the ones web components dispatch, don't bubble unless they ask to
+        _ => false,
     }
 }
@@ -5,8 +5,13 @@ pub type AnimationEvent = Event<AnimationData>;
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq)]
 pub struct AnimationData {
+    /// The `animation-name` of the animation
     pub animation_name: String,
+
+    /// The pseudo-element the animation runs on, like `::before`, or an empty string for the element itself
     pub pseudo_element: String,
+
+    /// How long the animation has been running for, in seconds, not counting its delay
     pub elapsed_time: f32,
 }
 
@@ -4,18 +4,24 @@ pub type CompositionEvent = Event<CompositionData>;
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CompositionData {
+    /// The text being composed by the input method editor
+    ///
+    /// In `oncompositionend` this is the text that was committed to the input.
     pub data: String,
 }
 
 impl_event! [
     CompositionData;
 
-    /// oncompositionstart
+    /// An input method editor started composing text, like a CJK input method or a dead key
     oncompositionstart
 
-    /// oncompositionend
+    /// The input method editor committed or cancelled the text
+    ///
+    /// `oninput` doesn't fire while text is being composed, so the committed text is delivered to `oninput` right
+    /// after this event.
     oncompositionend
 
-    /// oncompositionupdate
+    /// The text being composed changed
     oncompositionupdate
 ];
@@ -0,0 +1,51 @@
+use crate::events::EventReturn;
+use dioxus_core::{Attribute, Event, ScopeState};
+
+pub type CustomEvent = Event<CustomData>;
+
+/// An event that isn't part of html, like the events web components dispatch
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CustomData {
+    /// The `detail` of the event as JSON, or an empty string if the event doesn't have one
+    pub detail: String,
+}
+
+/// Listen for an event that isn't part of html
+///
+/// The name is the name of the event prefixed with `on`. In rsx, write the name as a string instead of calling this
+/// directly:
+///
+/// ```rust, ignore
+/// rsx! {
+///     color_picker {
+///         "oncolor-changed": move |evt: CustomEvent| log::info!("{}", evt.detail),
+///     }
+/// }
+/// ```
+///
+/// Custom events usually don't bubble, so the listener is attached to the element itself.
+pub fn custom_event<'a, E: EventReturn<T>, T>(
+    cx: &'a ScopeState,
+    name: &'static str,
+    mut f: impl FnMut(CustomEvent) -> E + 'a,
+) -> Attribute<'a> {
+    debug_assert!(
+        name.starts_with("on"),
+        "the name of a listener starts with `on`, found {}",
+        name
+    );
+
+    Attribute {
+        name: E::OPTIONS.attribute_name(cx, name),
+        value: cx.listener(move |e: CustomEvent| f(e).spawn(cx)),
+        namespace: None,
+        mounted_element: Default::default(),
+        volatile: false,
+        live: None,
+    }
+}
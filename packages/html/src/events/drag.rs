@@ -1,6 +1,8 @@
 use dioxus_core::Event;
+use std::fmt::Debug;
+use std::sync::Arc;
 
-use crate::MouseData;
+use crate::{FileEngine, MouseData};
 
 pub type DragEvent = Event<DragData>;
 
@@ -9,10 +11,50 @@ pub type DragEvent = Event<DragData>;
 /// (such as another DOM element). Applications are free to interpret a drag and drop interaction in an
 /// application-specific way.
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone)]
 pub struct DragData {
     /// Inherit mouse data
+    #[cfg_attr(feature = "serialize", serde(flatten))]
     pub mouse: MouseData,
+
+    /// The files that were dropped, in `ondrop`
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub files: Option<Arc<dyn FileEngine>>,
+}
+
+impl DragData {
+    /// The names of the files that were dropped, or none if no files were dropped
+    pub fn files(&self) -> Vec<String> {
+        self.files
+            .as_ref()
+            .map(|files| files.files())
+            .unwrap_or_default()
+    }
+
+    /// Read a dropped file to bytes
+    pub async fn read_to_bytes(&self, file: &str) -> Option<Vec<u8>> {
+        self.files.as_ref()?.read_file(file).await
+    }
+
+    /// Read a dropped file to a string
+    pub async fn read_to_string(&self, file: &str) -> Option<String> {
+        self.files.as_ref()?.read_file_to_string(file).await
+    }
+}
+
+impl PartialEq for DragData {
+    fn eq(&self, other: &Self) -> bool {
+        self.mouse == other.mouse
+    }
+}
+
+impl Debug for DragData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DragData")
+            .field("mouse", &self.mouse)
+            .field("files", &self.files())
+            .finish()
+    }
 }
 
 impl_event! {
@@ -2,6 +2,8 @@ use std::{collections::HashMap, fmt::Debug};
 
 use dioxus_core::Event;
 
+use crate::{FileProgress, FileStream};
+
 pub type FormEvent = Event<FormData>;
 
 /* DOMEvent:  Send + SyncTarget relatedTarget */
@@ -10,12 +12,81 @@ pub type FormEvent = Event<FormData>;
 pub struct FormData {
     pub value: String,
 
-    pub values: HashMap<String, String>,
+    /// The values of the named fields of the form
+    ///
+    /// Fields that share a name, like a group of checkboxes or a `select` with `multiple`, have a value for every
+    /// checked or selected option. A checkbox without a `value` attribute is `"true"` or `"false"`.
+    pub values: HashMap<String, Vec<String>>,
 
+    /// The files picked in the file inputs of the form, or in the file input the event is for
     #[cfg_attr(feature = "serialize", serde(skip))]
     pub files: Option<std::sync::Arc<dyn FileEngine>>,
 }
 
+impl FormData {
+    /// The first value of a field, which is the only one for most fields
+    pub fn value_of(&self, name: &str) -> Option<&str> {
+        self.values
+            .get(name)
+            .and_then(|values| values.first())
+            .map(String::as_str)
+    }
+
+    /// Every value of a field, or none if the form doesn't have the field
+    pub fn values_of(&self, name: &str) -> &[String] {
+        self.values.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// The names of the files picked in the form, or none if no files were picked
+    pub fn files(&self) -> Vec<String> {
+        self.files
+            .as_ref()
+            .map(|files| files.files())
+            .unwrap_or_default()
+    }
+
+    /// Read a picked file to bytes
+    pub async fn read_to_bytes(&self, file: &str) -> Option<Vec<u8>> {
+        self.files.as_ref()?.read_file(file).await
+    }
+
+    /// Read a picked file to a string
+    pub async fn read_to_string(&self, file: &str) -> Option<String> {
+        self.files.as_ref()?.read_file_to_string(file).await
+    }
+
+    /// Read a picked file in pieces as it arrives, to show the progress of a large upload
+    pub fn read_stream<'a>(&'a self, file: &'a str) -> Option<FileStream<'a>> {
+        Some(self.files.as_ref()?.read_file_stream(file))
+    }
+
+    /// Deserialize the values of the form into `T`
+    ///
+    /// Fields are looked up by name. Numbers and booleans are parsed from the text of the field, `Option`s are `None`
+    /// when the form doesn't have the field, and sequences take every value of the field.
+    ///
+    /// ```rust, ignore
+    /// #[derive(Deserialize)]
+    /// struct Order {
+    ///     name: String,
+    ///     quantity: u32,
+    ///     toppings: Vec<String>,
+    /// }
+    ///
+    /// form {
+    ///     onsubmit: move |evt| match evt.parsed::<Order>() {
+    ///         Ok(order) => place(order),
+    ///         Err(err) => error.set(Some(err.to_string())),
+    ///     },
+    ///     ...
+    /// }
+    /// ```
+    #[cfg(feature = "serialize")]
+    pub fn parsed<T: serde::de::DeserializeOwned>(&self) -> Result<T, serde::de::value::Error> {
+        T::deserialize(parse::FormDeserializer(&self.values))
+    }
+}
+
 impl PartialEq for FormData {
     fn eq(&self, other: &Self) -> bool {
         self.value == other.value && self.values == other.values
@@ -31,16 +102,42 @@ impl Debug for FormData {
     }
 }
 
+/// The files picked in a file input or dropped on an element
+///
+/// Files are identified by their names. The web renderer reads the `File`s of the browser on demand, desktop reads
+/// dropped files from their paths, and the files picked in the webview of desktop or liveview are sent along with the
+/// event in a [`SerializedFileEngine`](crate::SerializedFileEngine).
 #[async_trait::async_trait(?Send)]
 pub trait FileEngine {
-    // get a list of file names
+    /// The names of the files
     fn files(&self) -> Vec<String>;
 
-    // read a file to bytes
+    /// Read a file to bytes
     async fn read_file(&self, file: &str) -> Option<Vec<u8>>;
 
-    // read a file to string
+    /// Read a file to a string
     async fn read_file_to_string(&self, file: &str) -> Option<String>;
+
+    /// Read a file in pieces as it becomes available, with how much of it was read so far
+    ///
+    /// Most engines have the whole file at hand, so the file comes in one piece. The files liveview streams come in
+    /// the pieces the browser sent them in.
+    fn read_file_stream<'a>(&'a self, file: &'a str) -> FileStream<'a>
+    where
+        Self: 'a,
+    {
+        use futures_util::StreamExt;
+
+        Box::pin(
+            futures_util::stream::once(self.read_file(file)).filter_map(|contents| {
+                futures_util::future::ready(contents.map(|bytes| FileProgress {
+                    read: bytes.len(),
+                    size: bytes.len(),
+                    bytes,
+                }))
+            }),
+        )
+    }
 }
 
 impl_event! {
@@ -61,3 +158,145 @@ impl_event! {
     /// onsubmit
     onsubmit
 }
+
+#[cfg(feature = "serialize")]
+mod parse {
+    use serde::de::{
+        value::{Error, MapDeserializer, SeqDeserializer},
+        Error as _, IntoDeserializer, Visitor,
+    };
+    use serde::{forward_to_deserialize_any, Deserializer};
+    use std::collections::HashMap;
+
+    /// Deserializes the form as a map from the names of the fields to their values
+    pub(super) struct FormDeserializer<'a>(pub(super) &'a HashMap<String, Vec<String>>);
+
+    impl<'de, 'a> Deserializer<'de> for FormDeserializer<'a> {
+        type Error = Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            visitor.visit_map(MapDeserializer::new(
+                self.0
+                    .iter()
+                    .map(|(name, values)| (name.as_str(), FieldDeserializer(values))),
+            ))
+        }
+
+        forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map struct enum identifier ignored_any
+        }
+    }
+
+    /// Deserializes the values of one field, parsing them from text when needed
+    struct FieldDeserializer<'a>(&'a [String]);
+
+    impl<'a> FieldDeserializer<'a> {
+        fn first(&self) -> Result<&'a str, Error> {
+            self.0
+                .first()
+                .map(String::as_str)
+                .ok_or_else(|| Error::custom("the field doesn't have a value"))
+        }
+    }
+
+    impl<'de, 'a> IntoDeserializer<'de, Error> for FieldDeserializer<'a> {
+        type Deserializer = Self;
+
+        fn into_deserializer(self) -> Self {
+            self
+        }
+    }
+
+    macro_rules! parse_first {
+        ($($method:ident => $visit:ident,)*) => {
+            $(
+                fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                    let value = self.first()?;
+                    match value.parse() {
+                        Ok(parsed) => visitor.$visit(parsed),
+                        Err(_) => Err(Error::custom(format!("{:?} isn't a valid number", value))),
+                    }
+                }
+            )*
+        };
+    }
+
+    impl<'de, 'a> Deserializer<'de> for FieldDeserializer<'a> {
+        type Error = Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            match self.0 {
+                [value] => visitor.visit_str(value),
+                _ => self.deserialize_seq(visitor),
+            }
+        }
+
+        fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            match self.first()? {
+                "true" | "on" => visitor.visit_bool(true),
+                "false" | "off" | "" => visitor.visit_bool(false),
+                other => Err(Error::custom(format!("{:?} isn't a boolean", other))),
+            }
+        }
+
+        parse_first! {
+            deserialize_i8 => visit_i8,
+            deserialize_i16 => visit_i16,
+            deserialize_i32 => visit_i32,
+            deserialize_i64 => visit_i64,
+            deserialize_u8 => visit_u8,
+            deserialize_u16 => visit_u16,
+            deserialize_u32 => visit_u32,
+            deserialize_u64 => visit_u64,
+            deserialize_f32 => visit_f32,
+            deserialize_f64 => visit_f64,
+        }
+
+        fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            visitor.visit_str(self.first()?)
+        }
+
+        fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            self.deserialize_str(visitor)
+        }
+
+        fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            match self.0.is_empty() {
+                true => visitor.visit_none(),
+                false => visitor.visit_some(self),
+            }
+        }
+
+        fn deserialize_newtype_struct<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            visitor: V,
+        ) -> Result<V::Value, Error> {
+            visitor.visit_newtype_struct(self)
+        }
+
+        fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            visitor.visit_seq(SeqDeserializer::new(
+                self.0
+                    .iter()
+                    .map(|value| FieldDeserializer(std::slice::from_ref(value))),
+            ))
+        }
+
+        fn deserialize_enum<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            _variants: &'static [&'static str],
+            visitor: V,
+        ) -> Result<V::Value, Error> {
+            visitor.visit_enum(self.first()?.into_deserializer())
+        }
+
+        forward_to_deserialize_any! {
+            i128 u128 char bytes byte_buf unit unit_struct tuple
+            tuple_struct map struct identifier ignored_any
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use crate::geometry::ClientPoint;
+use dioxus_core::Event;
+
+pub type SwipeEvent = Event<SwipeData>;
+pub type PinchEvent = Event<PinchData>;
+pub type LongPressEvent = Event<LongPressData>;
+
+/// How far a pointer can move, in CSS pixels, and still be pressing the same spot
+pub const PRESS_TOLERANCE: f64 = 10.0;
+
+/// How long a pointer has to be held down without moving for a long press, in milliseconds
+pub const LONG_PRESS_DELAY: f64 = 500.0;
+
+/// How far a pointer has to move for a swipe, in CSS pixels
+pub const SWIPE_DISTANCE: f64 = 30.0;
+
+/// How fast a pointer has to move for a swipe, in CSS pixels per millisecond
+pub const SWIPE_VELOCITY: f64 = 0.3;
+
+/// The way a pointer swiped, along the axis it moved the most on
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// A pointer that was pressed, moved quickly in one direction and released
+///
+/// Swipes are recognized from the pointer events of the element by the renderer. The pointer has to move at least
+/// [`SWIPE_DISTANCE`], at [`SWIPE_VELOCITY`] or faster. A pointer that was part of a pinch never swipes.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwipeData {
+    /// The way the pointer swiped
+    pub direction: SwipeDirection,
+
+    /// How far the pointer moved to the right, in CSS pixels
+    pub delta_x: f64,
+
+    /// How far the pointer moved down, in CSS pixels
+    pub delta_y: f64,
+
+    /// How fast the pointer moved, in CSS pixels per millisecond
+    pub velocity: f64,
+
+    /// How long the pointer was down, in milliseconds
+    pub duration: f64,
+
+    /// The kind of device, which is `"mouse"`, `"pen"` or `"touch"`
+    pub pointer_type: String,
+}
+
+/// Where a gesture that takes a while is at
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GesturePhase {
+    /// The gesture was recognized
+    Start,
+
+    /// The gesture changed
+    Move,
+
+    /// The gesture is over, or was cancelled
+    End,
+}
+
+/// Two pointers moving towards or away from each other
+///
+/// A pinch starts when a second pointer is pressed on the element, and fires again every time either pointer moves,
+/// until one of them is released. Browsers zoom the page with two fingers on their own, so elements that are pinched
+/// on touch screens usually need `touch-action: none`.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PinchData {
+    /// Where the pinch is at
+    pub phase: GesturePhase,
+
+    /// The distance between the pointers relative to when the pinch started, so below 1 when they moved closer
+    pub scale: f64,
+
+    /// The horizontal coordinate of the point between the pointers, relative to the viewport
+    pub center_x: f64,
+
+    /// The vertical coordinate of the point between the pointers, relative to the viewport
+    pub center_y: f64,
+}
+
+impl PinchData {
+    /// The point between the pointers, relative to the viewport
+    pub fn center(&self) -> ClientPoint {
+        ClientPoint::new(self.center_x, self.center_y)
+    }
+}
+
+/// A pointer that was held down on the same spot for a while
+///
+/// The pointer has to stay within [`PRESS_TOLERANCE`] for [`LONG_PRESS_DELAY`]. The event fires while the pointer is
+/// still down, and the pointer doesn't swipe when it's released.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LongPressData {
+    /// The horizontal coordinate of the pointer, relative to the viewport
+    pub client_x: f64,
+
+    /// The vertical coordinate of the pointer, relative to the viewport
+    pub client_y: f64,
+
+    /// The kind of device, which is `"mouse"`, `"pen"` or `"touch"`
+    pub pointer_type: String,
+}
+
+impl LongPressData {
+    /// The coordinates of the pointer, relative to the viewport
+    pub fn client_coordinates(&self) -> ClientPoint {
+        ClientPoint::new(self.client_x, self.client_y)
+    }
+}
+
+impl_event! {
+    SwipeData;
+
+    /// onswipe
+    onswipe
+}
+
+impl_event! {
+    PinchData;
+
+    /// onpinch
+    onpinch
+}
+
+impl_event! {
+    LongPressData;
+
+    /// onlongpress
+    onlongpress
+}
@@ -53,6 +53,10 @@ pub struct KeyboardData {
 
     #[deprecated(since = "0.3.0", note = "use code() or key() instead")]
     pub which: usize,
+
+    /// If the key was pressed while an input method editor was composing text
+    #[cfg_attr(feature = "serialize", serde(default))]
+    is_composing: bool,
 }
 
 impl_event! {
@@ -66,6 +70,12 @@ impl_event! {
 
     /// onkeyup
     onkeyup
+
+    /// onkeydown_capture
+    onkeydown_capture
+
+    /// onkeyup_capture
+    onkeyup_capture
 }
 
 impl KeyboardData {
@@ -96,9 +106,16 @@ impl KeyboardData {
                 .legacy_charcode()
                 .try_into()
                 .expect("could not convert charcode to usize"),
+            is_composing: false,
         }
     }
 
+    /// Mark the key as pressed while an input method editor was composing text
+    pub fn with_composing(mut self, is_composing: bool) -> Self {
+        self.is_composing = is_composing;
+        self
+    }
+
     /// The value of the key pressed by the user, taking into consideration the state of modifier keys such as Shift as well as the keyboard locale and layout.
     pub fn key(&self) -> Key {
         #[allow(deprecated)]
@@ -144,6 +161,14 @@ impl KeyboardData {
         #[allow(deprecated)]
         self.repeat
     }
+
+    /// `true` iff the key was pressed while an input method editor was composing text.
+    ///
+    /// These keys edit the composition, like picking a candidate in a CJK input method, so they usually shouldn't
+    /// trigger shortcuts or submit forms.
+    pub fn is_composing(&self) -> bool {
+        self.is_composing
+    }
 }
 
 impl Debug for KeyboardData {
@@ -154,6 +179,7 @@ impl Debug for KeyboardData {
             .field("modifiers", &self.modifiers())
             .field("location", &self.location())
             .field("is_auto_repeating", &self.is_auto_repeating())
+            .field("is_composing", &self.is_composing())
             .finish()
     }
 }
@@ -1,9 +1,33 @@
 use dioxus_core::Event;
 
 pub type MediaEvent = Event<MediaData>;
-#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct MediaData {}
+
+/// The state of the `audio` or `video` element when the event fired
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MediaData {
+    /// The playback position, in seconds
+    pub current_time: f64,
+
+    /// The length of the media in seconds, or `None` if it isn't known yet or the media is a live stream
+    pub duration: Option<f64>,
+
+    /// The volume, from 0.0 (silent) to 1.0 (loudest)
+    pub volume: f64,
+
+    pub muted: bool,
+
+    pub paused: bool,
+
+    pub ended: bool,
+
+    /// How fast the media plays, where 1.0 is normal speed
+    pub playback_rate: f64,
+}
 
 impl_event! [
     MediaData;
@@ -0,0 +1,139 @@
+//! Handles querying data from the renderer
+
+use crate::geometry::ClientRect;
+use dioxus_core::Event;
+use std::{
+    any::Any,
+    fmt::{Debug, Display, Formatter},
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+};
+
+/// An element that has been rendered, which can be queried and modified through the renderer
+///
+/// Every renderer implements this for its own kind of node, and renderers only support the parts of it that make sense
+/// for their platform. Anything that isn't supported returns [`MountedError::NotSupported`].
+pub trait RenderedElement {
+    /// Get the renderer specific element so it can be downcast to the platform's node type
+    fn get_raw_element(&self) -> MountedResult<&dyn Any> {
+        Err(MountedError::NotSupported)
+    }
+
+    /// Get the bounding rectangle of the element relative to the viewport
+    fn get_client_rect(&self) -> MountedFuture<ClientRect> {
+        Box::pin(async { Err(MountedError::NotSupported) })
+    }
+
+    /// Scroll the element into view
+    fn scroll_to(&self, _behavior: ScrollBehavior) -> MountedFuture<()> {
+        Box::pin(async { Err(MountedError::NotSupported) })
+    }
+
+    /// Scroll the content of the element to an offset from its top left corner, in CSS pixels
+    fn set_scroll_position(&self, _top: f64, _left: f64) -> MountedFuture<()> {
+        Box::pin(async { Err(MountedError::NotSupported) })
+    }
+
+    /// Focus or blur the element
+    fn set_focus(&self, _focus: bool) -> MountedFuture<()> {
+        Box::pin(async { Err(MountedError::NotSupported) })
+    }
+}
+
+/// The data for an `onmounted` event: a handle to the element that was just mounted
+#[derive(Clone)]
+pub struct MountedData {
+    inner: Rc<dyn RenderedElement>,
+}
+
+impl MountedData {
+    /// Create a new MountedData from a rendered element
+    pub fn new(element: impl RenderedElement + 'static) -> Self {
+        Self {
+            inner: Rc::new(element),
+        }
+    }
+
+    /// Get the renderer specific element so it can be downcast to the platform's node type
+    ///
+    /// On the web this is a `web_sys::Element`.
+    pub fn get_raw_element(&self) -> MountedResult<&dyn Any> {
+        self.inner.get_raw_element()
+    }
+
+    /// Get the bounding rectangle of the element relative to the viewport
+    pub fn get_client_rect(&self) -> MountedFuture<ClientRect> {
+        self.inner.get_client_rect()
+    }
+
+    /// Scroll the element into view
+    pub fn scroll_to(&self, behavior: ScrollBehavior) -> MountedFuture<()> {
+        self.inner.scroll_to(behavior)
+    }
+
+    /// Scroll the content of the element to an offset from its top left corner, in CSS pixels
+    pub fn set_scroll_position(&self, top: f64, left: f64) -> MountedFuture<()> {
+        self.inner.set_scroll_position(top, left)
+    }
+
+    /// Focus or blur the element
+    pub fn set_focus(&self, focus: bool) -> MountedFuture<()> {
+        self.inner.set_focus(focus)
+    }
+}
+
+impl Debug for MountedData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MountedData").finish_non_exhaustive()
+    }
+}
+
+pub type MountedEvent = Event<MountedData>;
+
+impl_event! [
+    MountedData;
+
+    /// mounted
+    onmounted
+];
+
+/// How to scroll an element into view
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollBehavior {
+    /// Jump to the element right away
+    Instant,
+
+    /// Animate the scroll over to the element
+    Smooth,
+}
+
+/// The result of a query on a [`MountedData`]
+pub type MountedResult<T> = Result<T, MountedError>;
+
+/// A query on a [`MountedData`] that the renderer resolves asynchronously
+pub type MountedFuture<T> = Pin<Box<dyn Future<Output = MountedResult<T>>>>;
+
+/// The reason a query on a [`MountedData`] failed
+#[derive(Debug)]
+pub enum MountedError {
+    /// The renderer doesn't support this query
+    NotSupported,
+
+    /// The renderer supports this query, but it failed
+    OperationFailed(Box<dyn std::error::Error>),
+}
+
+impl Display for MountedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MountedError::NotSupported => {
+                write!(f, "this operation is not supported by the renderer")
+            }
+            MountedError::OperationFailed(e) => write!(f, "the operation failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for MountedError {}
@@ -147,6 +147,26 @@ impl_event! {
 
     /// onmouseup
     onmouseup
+
+    /// Execute a callback when a button is clicked, before any [`onclick`] listeners run.
+    ///
+    /// Capturing listeners run from the root down to the target element, so a parent can see the event before its
+    /// children do.
+    ///
+    /// ## Example
+    /// ```rust, ignore
+    /// rsx!( div { onclick_capture: move |evt| evt.stop_propagation(), button { "click me" } } )
+    /// ```
+    onclick_capture
+
+    /// onmousedown_capture
+    onmousedown_capture
+
+    /// onmousemove_capture
+    onmousemove_capture
+
+    /// onmouseup_capture
+    onmouseup_capture
 }
 
 impl MouseData {
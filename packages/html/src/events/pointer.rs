@@ -1,3 +1,4 @@
+use crate::geometry::{ClientPoint, PagePoint, ScreenPoint};
 use dioxus_core::Event;
 
 pub type PointerEvent = Event<PointerData>;
@@ -17,19 +18,56 @@ pub struct PointerData {
     pub screen_x: i32,
     pub screen_y: i32,
     pub shift_key: bool,
+
+    /// Identifies the pointer for as long as it's in contact, so several pointers can be told apart
     pub pointer_id: i32,
+
+    /// The width of the area of contact, in pixels
     pub width: i32,
+
+    /// The height of the area of contact, in pixels
     pub height: i32,
+
+    /// How hard the pointer is pressed, from 0 to 1
     pub pressure: f32,
+
+    /// The pressure from a control like the barrel wheel of an airbrush, from -1 to 1
     pub tangential_pressure: f32,
+
+    /// The angle of the pen from the Y-Z plane, from -90 to 90 degrees
     pub tilt_x: i32,
+
+    /// The angle of the pen from the X-Z plane, from -90 to 90 degrees
     pub tilt_y: i32,
+
+    /// How many degrees the pen is rotated clockwise around its own axis, from 0 to 359
     pub twist: i32,
+
+    /// The kind of device, which is `"mouse"`, `"pen"` or `"touch"`
     pub pointer_type: String,
+
+    /// Whether this is the main pointer of its kind, like the first finger that touched the surface
     pub is_primary: bool,
     // pub get_modifier_state: bool,
 }
 
+impl PointerData {
+    /// The coordinates of the pointer relative to the screen
+    pub fn screen_coordinates(&self) -> ScreenPoint {
+        ScreenPoint::new(self.screen_x.into(), self.screen_y.into())
+    }
+
+    /// The coordinates of the pointer relative to the viewport
+    pub fn client_coordinates(&self) -> ClientPoint {
+        ClientPoint::new(self.client_x.into(), self.client_y.into())
+    }
+
+    /// The coordinates of the pointer relative to the document
+    pub fn page_coordinates(&self) -> PagePoint {
+        PagePoint::new(self.page_x.into(), self.page_y.into())
+    }
+}
+
 impl_event![
     PointerData;
     /// pointerdown
@@ -1,9 +1,35 @@
 use dioxus_core::Event;
 
 pub type ScrollEvent = Event<ScrollData>;
-#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ScrollData {}
+
+/// The scroll position of the element that scrolled, and how much there is to scroll through
+///
+/// When the document scrolls, these are the offsets of its scrolling element.
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScrollData {
+    /// How far the content is scrolled down, in CSS pixels
+    pub scroll_top: f64,
+
+    /// How far the content is scrolled to the right, in CSS pixels
+    pub scroll_left: f64,
+
+    /// The height of all of the content, including the part that's scrolled out of view
+    pub scroll_height: f64,
+
+    /// The width of all of the content, including the part that's scrolled out of view
+    pub scroll_width: f64,
+
+    /// The height of the visible part of the element, without its border and scrollbar
+    pub client_height: f64,
+
+    /// The width of the visible part of the element, without its border and scrollbar
+    pub client_width: f64,
+}
 
 impl_event! {
     ScrollData;
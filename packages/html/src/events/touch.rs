@@ -1,17 +1,81 @@
+use crate::geometry::{ClientPoint, PagePoint, ScreenPoint};
 use dioxus_core::Event;
 
 pub type TouchEvent = Event<TouchData>;
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TouchData {
     pub alt_key: bool,
     pub ctrl_key: bool,
     pub meta_key: bool,
     pub shift_key: bool,
     // get_modifier_state: bool,
-    // changedTouches: DOMTouchList,
-    // targetTouches: DOMTouchList,
-    // touches: DOMTouchList,
+    /// Every point that's touching the surface
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub touches: Vec<TouchPoint>,
+
+    /// The points that started, moved or ended with this event
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub changed_touches: Vec<TouchPoint>,
+
+    /// The points that started on the element the event is for, and are still touching the surface
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub target_touches: Vec<TouchPoint>,
+}
+
+/// A single point of contact in a touch event, like a finger or a stylus
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TouchPoint {
+    /// Identifies the point for as long as it's touching the surface, across events
+    pub identifier: i32,
+
+    /// The X coordinate of the point in global (screen) coordinates
+    pub screen_x: f64,
+    /// The Y coordinate of the point in global (screen) coordinates
+    pub screen_y: f64,
+
+    /// The X coordinate of the point within the viewport
+    pub client_x: f64,
+    /// The Y coordinate of the point within the viewport
+    pub client_y: f64,
+
+    /// The X coordinate of the point within the whole document, including the parts that are scrolled out of view
+    pub page_x: f64,
+    /// The Y coordinate of the point within the whole document, including the parts that are scrolled out of view
+    pub page_y: f64,
+
+    /// The X radius of the ellipse that most closely covers the area of contact
+    pub radius_x: f64,
+    /// The Y radius of the ellipse that most closely covers the area of contact
+    pub radius_y: f64,
+
+    /// How many degrees the ellipse described by `radius_x` and `radius_y` is rotated clockwise
+    pub rotation_angle: f64,
+
+    /// How hard the point is pressed, from 0 to 1
+    pub force: f64,
+}
+
+impl TouchPoint {
+    /// The coordinates of the point relative to the screen
+    pub fn screen_coordinates(&self) -> ScreenPoint {
+        ScreenPoint::new(self.screen_x, self.screen_y)
+    }
+
+    /// The coordinates of the point relative to the viewport
+    pub fn client_coordinates(&self) -> ClientPoint {
+        ClientPoint::new(self.client_x, self.client_y)
+    }
+
+    /// The coordinates of the point relative to the document
+    pub fn page_coordinates(&self) -> PagePoint {
+        PagePoint::new(self.page_x, self.page_y)
+    }
 }
 
 impl_event! {
@@ -4,8 +4,13 @@ pub type TransitionEvent = Event<TransitionData>;
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq)]
 pub struct TransitionData {
+    /// The CSS property that finished transitioning, like `opacity`
     pub property_name: String,
+
+    /// The pseudo-element the transition ran on, like `::before`, or an empty string for the element itself
     pub pseudo_element: String,
+
+    /// How long the transition ran for, in seconds, not counting its delay
     pub elapsed_time: f32,
 }
 
@@ -0,0 +1,271 @@
+use crate::FileEngine;
+use futures_util::Stream;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// Files that were read ahead of the event they belong to
+///
+/// Desktop and liveview can't reach the files the user picked in the webview, so the interpreter reads them and
+/// sends them over in [`FileChunk`]s right before the event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SerializedFileEngine {
+    files: Vec<(String, Vec<u8>)>,
+}
+
+impl SerializedFileEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add bytes to the end of a file, creating the file if it's the first chunk
+    pub fn push(&mut self, name: &str, bytes: &[u8]) {
+        match self.files.iter_mut().find(|(file, _)| file == name) {
+            Some((_, contents)) => contents.extend_from_slice(bytes),
+            None => self.files.push((name.to_string(), bytes.to_vec())),
+        }
+    }
+
+    /// Add a chunk that was sent by the interpreter
+    #[cfg(feature = "serialize")]
+    pub fn push_chunk(&mut self, chunk: &FileChunk) -> Result<(), base64::DecodeError> {
+        use base64::Engine;
+
+        let bytes = base64::engine::general_purpose::STANDARD.decode(&chunk.data)?;
+        self.push(&chunk.name, &bytes);
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    fn contents(&self, name: &str) -> Option<&[u8]> {
+        self.files
+            .iter()
+            .find(|(file, _)| file == name)
+            .map(|(_, contents)| contents.as_slice())
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl FileEngine for SerializedFileEngine {
+    fn files(&self) -> Vec<String> {
+        self.files.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    async fn read_file(&self, file: &str) -> Option<Vec<u8>> {
+        self.contents(file).map(<[u8]>::to_vec)
+    }
+
+    async fn read_file_to_string(&self, file: &str) -> Option<String> {
+        String::from_utf8(self.contents(file)?.to_vec()).ok()
+    }
+}
+
+/// A piece of a file the interpreter read for an event, sent as a `file_chunk` message
+///
+/// Every file is sent in at least one chunk, even if it's empty.
+#[cfg(feature = "serialize")]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FileChunk {
+    /// The name of the file
+    pub name: String,
+
+    /// The bytes of the chunk, encoded in base64
+    pub data: String,
+}
+
+/// The pieces of a file, in order, as they become available
+pub type FileStream<'a> = Pin<Box<dyn Stream<Item = FileProgress> + 'a>>;
+
+/// A piece of a file read with [`FileEngine::read_file_stream`], and how far along the file it is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileProgress {
+    /// The bytes that arrived since the last piece
+    pub bytes: Vec<u8>,
+
+    /// How many bytes of the file arrived so far, counting these
+    pub read: usize,
+
+    /// The size of the whole file
+    pub size: usize,
+}
+
+/// Files that keep arriving after the event they belong to
+///
+/// Liveview sends the event as soon as the files are picked and streams the files after it, so a component can show
+/// the progress of a large upload with [`FileEngine::read_file_stream`]. Reading a whole file waits for the rest of it.
+#[derive(Debug, Clone, Default)]
+pub struct StreamedFileEngine {
+    files: Arc<Mutex<Vec<StreamedFile>>>,
+}
+
+#[derive(Debug)]
+struct StreamedFile {
+    name: String,
+    size: usize,
+    contents: Vec<u8>,
+    aborted: bool,
+    wakers: Vec<Waker>,
+}
+
+impl StreamedFile {
+    fn is_done(&self) -> bool {
+        self.aborted || self.contents.len() >= self.size
+    }
+
+    fn wake(&mut self) {
+        for waker in self.wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+impl StreamedFileEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expect a file of `size` bytes
+    pub fn start(&self, name: &str, size: usize) {
+        let mut files = self.files.lock().unwrap();
+        if files.iter().all(|file| file.name != name) {
+            files.push(StreamedFile {
+                name: name.to_string(),
+                size,
+                contents: Vec::with_capacity(size),
+                aborted: false,
+                wakers: Vec::new(),
+            });
+        }
+    }
+
+    /// Add bytes to the end of a file that was started, waking up whatever is reading it
+    pub fn push(&self, name: &str, bytes: &[u8]) {
+        let mut files = self.files.lock().unwrap();
+        if let Some(file) = files.iter_mut().find(|file| file.name == name) {
+            if !file.is_done() {
+                file.contents.extend_from_slice(bytes);
+                file.wake();
+            }
+        }
+    }
+
+    /// Add a chunk that was sent by the interpreter
+    #[cfg(feature = "serialize")]
+    pub fn push_chunk(&self, chunk: &FileChunk) -> Result<(), base64::DecodeError> {
+        use base64::Engine;
+
+        let bytes = base64::engine::general_purpose::STANDARD.decode(&chunk.data)?;
+        self.push(&chunk.name, &bytes);
+        Ok(())
+    }
+
+    /// Whether every file arrived in full
+    pub fn is_complete(&self) -> bool {
+        let files = self.files.lock().unwrap();
+        files
+            .iter()
+            .all(|file| !file.aborted && file.contents.len() >= file.size)
+    }
+
+    /// Stop waiting for the rest of the files, like when the connection they came over closes
+    ///
+    /// Streams of the files end with the pieces that arrived, and whole reads of the files that didn't arrive in full
+    /// come up empty.
+    pub fn abort(&self) {
+        let mut files = self.files.lock().unwrap();
+        for file in files.iter_mut().filter(|file| !file.is_done()) {
+            file.aborted = true;
+            file.wake();
+        }
+    }
+
+    fn poll_contents(&self, name: &str, cx: &mut Context<'_>) -> Poll<Option<Vec<u8>>> {
+        let mut files = self.files.lock().unwrap();
+        let file = match files.iter_mut().find(|file| file.name == name) {
+            Some(file) => file,
+            None => return Poll::Ready(None),
+        };
+
+        match (file.aborted, file.is_done()) {
+            (true, _) => Poll::Ready(None),
+            (false, true) => Poll::Ready(Some(file.contents.clone())),
+            (false, false) => {
+                file.wakers.push(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl FileEngine for StreamedFileEngine {
+    fn files(&self) -> Vec<String> {
+        let files = self.files.lock().unwrap();
+        files.iter().map(|file| file.name.clone()).collect()
+    }
+
+    async fn read_file(&self, file: &str) -> Option<Vec<u8>> {
+        futures_util::future::poll_fn(|cx| self.poll_contents(file, cx)).await
+    }
+
+    async fn read_file_to_string(&self, file: &str) -> Option<String> {
+        String::from_utf8(self.read_file(file).await?).ok()
+    }
+
+    fn read_file_stream<'a>(&'a self, file: &'a str) -> FileStream<'a>
+    where
+        Self: 'a,
+    {
+        Box::pin(StreamedFileReader {
+            files: self,
+            name: file,
+            read: 0,
+            yielded: false,
+        })
+    }
+}
+
+/// Yields the bytes of a streamed file as they arrive
+struct StreamedFileReader<'a> {
+    files: &'a StreamedFileEngine,
+    name: &'a str,
+    read: usize,
+    yielded: bool,
+}
+
+impl Stream for StreamedFileReader<'_> {
+    type Item = FileProgress;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<FileProgress>> {
+        let this = &mut *self;
+        let mut files = this.files.files.lock().unwrap();
+        let file = match files.iter_mut().find(|file| file.name == this.name) {
+            Some(file) => file,
+            None => return Poll::Ready(None),
+        };
+
+        // an empty file still has one empty piece
+        let complete = !file.aborted && file.contents.len() >= file.size;
+        if file.contents.len() > this.read || (complete && !this.yielded) {
+            let bytes = file.contents[this.read..].to_vec();
+            this.read = file.contents.len();
+            this.yielded = true;
+            return Poll::Ready(Some(FileProgress {
+                bytes,
+                read: this.read,
+                size: file.size,
+            }));
+        }
+
+        match file.is_done() {
+            true => Poll::Ready(None),
+            false => {
+                file.wakers.push(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -14,6 +14,8 @@ pub type ScreenPoint = Point2D<f64, ScreenSpace>;
 pub struct ClientSpace;
 /// A point in ClientSpace
 pub type ClientPoint = Point2D<f64, ClientSpace>;
+/// A rectangle in ClientSpace
+pub type ClientRect = Rect<f64, ClientSpace>;
 
 /// Coordinate space relative to an element
 pub struct ElementSpace;
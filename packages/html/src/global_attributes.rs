@@ -96,6 +96,8 @@ trait_methods! {
 
 
     /// <https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/autofocus>
+    ///
+    /// Like in react, an element with `autofocus` takes the focus whenever it's created, not just when the page loads.
     autofocus;
 
     /// The HTML class attribute is used to specify a class for an HTML element.
@@ -209,6 +211,8 @@ trait_methods! {
     /// HTML from code is risky because it’s easy to inadvertently expose your users to a cross-site scripting (XSS)
     /// attack. So, you can set HTML directly from Dioxus, but you have to type out dangerous_inner_html to remind
     /// yourself that it’s dangerous
+    ///
+    /// To render raw HTML as a node of its own, use `RawHtml` instead.
     dangerous_inner_html;
 
     // This macro creates an explicit method call for each of the style attributes.
@@ -1459,6 +1463,15 @@ trait_methods! {
     /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-roledescription>
     aria_roledescription: "aria-roledescription";
 
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-braillelabel>
+    aria_braillelabel: "aria-braillelabel";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-brailleroledescription>
+    aria_brailleroledescription: "aria-brailleroledescription";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-description>
+    aria_description: "aria-description";
+
 // Widget Attributes
 
     /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-autocomplete>
@@ -1584,6 +1597,12 @@ trait_methods! {
 
     /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-setsize>
     aria_setsize: "aria-setsize";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-colindextext>
+    aria_colindextext: "aria-colindextext";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-rowindextext>
+    aria_rowindextext: "aria-rowindextext";
 }
 
 trait_methods! {
@@ -1771,6 +1790,12 @@ trait_methods! {
     /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/filterUnits>
     filterUnits: "filterUnits";
 
+    /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/filterRes>
+    filter_res: "filterRes";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/filterUnits>
+    filter_units: "filterUnits";
+
     /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/flood-color>
     flood_color: "flood-color";
 
@@ -1864,8 +1889,11 @@ trait_methods! {
     /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/image-rendering>
     image_rendering: "image-rendering";
 
-    /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/_in>
-    _in: "_in";
+    /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/in>
+    r#in: "in";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/in>
+    _in: "in";
 
     /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/in2>
     in2: "in2";
@@ -1945,6 +1973,9 @@ trait_methods! {
     /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/mask>
     mask: "mask";
 
+    /// <https://developer.mozilla.org/en-US/docs/Web/CSS/mask-type>
+    mask_type: "mask-type";
+
     /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/maskContentUnits>
     mask_content_units: "maskContentUnits";
 
@@ -2113,6 +2144,9 @@ trait_methods! {
     /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/shape-rendering>
     shape_rendering: "shape-rendering";
 
+    /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Element/textPath#side>
+    side: "side";
+
     /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/slope>
     slope: "slope";
 
@@ -2230,8 +2264,8 @@ trait_methods! {
     /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/transform-origin>
     transform_origin: "transform-origin";
 
-    /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/_type>
-    r#type: "_type";
+    /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/type>
+    r#type: "type";
 
     /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/u1>
     u1: "u1";
@@ -2320,9 +2354,44 @@ trait_methods! {
     /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/x2>
     x2: "x2";
 
+    /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/xlink:actuate>
+    xlink_actuate: "xlink:actuate", "http://www.w3.org/1999/xlink";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/xlink:arcrole>
+    xlink_arcrole: "xlink:arcrole", "http://www.w3.org/1999/xlink";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/xlink:href>
+    ///
+    /// Older renderers only understand this form of `href`.
+    xlink_href: "xlink:href", "http://www.w3.org/1999/xlink";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/xlink:role>
+    xlink_role: "xlink:role", "http://www.w3.org/1999/xlink";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/xlink:show>
+    xlink_show: "xlink:show", "http://www.w3.org/1999/xlink";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/xlink:title>
+    xlink_title: "xlink:title", "http://www.w3.org/1999/xlink";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/xlink:type>
+    xlink_type: "xlink:type", "http://www.w3.org/1999/xlink";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/xml:base>
+    xml_base: "xml:base", "http://www.w3.org/XML/1998/namespace";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/xml:lang>
+    xml_lang: "xml:lang", "http://www.w3.org/XML/1998/namespace";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/xml:space>
+    xml_space: "xml:space", "http://www.w3.org/XML/1998/namespace";
+
     /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/xmlns>
     xmlns: "xmlns";
 
+    /// Declares the `xlink` prefix, which standalone SVG documents need for the `xlink_*` attributes
+    xmlns_xlink: "xmlns:xlink", "http://www.w3.org/2000/xmlns/";
+
     /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/xChannelSelector>
     x_channel_selector: "xChannelSelector";
 
@@ -2344,4 +2413,219 @@ trait_methods! {
     /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/zoomAndPan>
     zoom_and_pan: "zoomAndPan";
 
+    // ARIA attributes, which SVG 2 allows on every element
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-current>
+    aria_current: "aria-current";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-details>
+    aria_details: "aria-details";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-disabled>
+    aria_disabled: "aria-disabled";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-hidden>
+    aria_hidden: "aria-hidden";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-invalid>
+    aria_invalid: "aria-invalid";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-keyshortcuts>
+    aria_keyshortcuts: "aria-keyshortcuts";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-label>
+    aria_label: "aria-label";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-roledescription>
+    aria_roledescription: "aria-roledescription";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-braillelabel>
+    aria_braillelabel: "aria-braillelabel";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-brailleroledescription>
+    aria_brailleroledescription: "aria-brailleroledescription";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-description>
+    aria_description: "aria-description";
+
+// Widget Attributes
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-autocomplete>
+    aria_autocomplete: "aria-autocomplete";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-checked>
+    aria_checked: "aria-checked";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-expanded>
+    aria_expanded: "aria-expanded";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-haspopup>
+    aria_haspopup: "aria-haspopup";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-level>
+    aria_level: "aria-level";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-modal>
+    aria_modal: "aria-modal";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-multiline>
+    aria_multiline: "aria-multiline";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-multiselectable>
+    aria_multiselectable: "aria-multiselectable";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-orientation>
+    aria_orientation: "aria-orientation";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-placeholder>
+    aria_placeholder: "aria-placeholder";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-pressed>
+    aria_pressed: "aria-pressed";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-readonly>
+    aria_readonly: "aria-readonly";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-required>
+    aria_required: "aria-required";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-selected>
+    aria_selected: "aria-selected";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-sort>
+    aria_sort: "aria-sort";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-valuemax>
+    aria_valuemax: "aria-valuemax";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-valuemin>
+    aria_valuemin: "aria-valuemin";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-valuenow>
+    aria_valuenow: "aria-valuenow";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-valuetext>
+    aria_valuetext: "aria-valuetext";
+
+// Live Region Attributes
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-atomic>
+    aria_atomic: "aria-atomic";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-busy>
+    aria_busy: "aria-busy";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-live>
+    aria_live: "aria-live";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-relevant>
+    aria_relevant: "aria-relevant";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-dropeffect>
+    aria_dropeffect: "aria-dropeffect";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-grabbed>
+    aria_grabbed: "aria-grabbed";
+
+// Relationship Attributes
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-activedescendant>
+    aria_activedescendant: "aria-activedescendant";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-colcount>
+    aria_colcount: "aria-colcount";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-colindex>
+    aria_colindex: "aria-colindex";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-colspan>
+    aria_colspan: "aria-colspan";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-controls>
+    aria_controls: "aria-controls";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-describedby>
+    aria_describedby: "aria-describedby";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-errormessage>
+    aria_errormessage: "aria-errormessage";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-flowto>
+    aria_flowto: "aria-flowto";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-labelledby>
+    aria_labelledby: "aria-labelledby";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-owns>
+    aria_owns: "aria-owns";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-posinset>
+    aria_posinset: "aria-posinset";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-rowcount>
+    aria_rowcount: "aria-rowcount";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-rowindex>
+    aria_rowindex: "aria-rowindex";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-rowspan>
+    aria_rowspan: "aria-rowspan";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-setsize>
+    aria_setsize: "aria-setsize";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-colindextext>
+    aria_colindextext: "aria-colindextext";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-rowindextext>
+    aria_rowindextext: "aria-rowindextext";
+
+}
+
+trait_methods! {
+    @base
+    MathMlAttributes;
+    map_mathml_attributes;
+
+    /// Prevent the default action for this element.
+    ///
+    /// For more information, see the MDN docs:
+    /// <https://developer.mozilla.org/en-US/docs/Web/API/Event/preventDefault>
+    prevent_default: "dioxus-prevent-default";
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/autofocus>
+    autofocus;
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/MathML/Global_attributes/class>
+    class;
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/MathML/Global_attributes/dir>
+    dir;
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/MathML/Global_attributes/displaystyle>
+    displaystyle;
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/MathML/Global_attributes/id>
+    id;
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/MathML/Global_attributes/mathbackground>
+    mathbackground;
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/MathML/Global_attributes/mathcolor>
+    mathcolor;
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/MathML/Global_attributes/mathsize>
+    mathsize;
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/MathML/Global_attributes/nonce>
+    nonce;
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/MathML/Global_attributes/scriptlevel>
+    scriptlevel;
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/MathML/Global_attributes/style>
+    style;
+
+    /// <https://developer.mozilla.org/en-US/docs/Web/MathML/Global_attributes/tabindex>
+    tabindex;
 }
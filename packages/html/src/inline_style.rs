@@ -0,0 +1,182 @@
+use crate::{AttributeDiscription, GlobalAttributes};
+use dioxus_core::exports::bumpalo::Bump;
+use dioxus_core::{prelude::IntoAttributeValue, AttributeValue};
+use std::fmt::{Display, Formatter};
+
+/// The declarations of an inline `style` attribute
+///
+/// Build it with [`style!`](crate::style!), or by setting properties by hand. Setting a property that's already set
+/// overrides it in place, like a later declaration does in CSS.
+///
+/// ```rust, ignore
+/// let mut style = style! { color: "black", padding: "{padding}px" };
+/// style.merge(&highlight);
+///
+/// rsx! {
+///     div { style: style, "{text}" }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Style {
+    declarations: Vec<(&'static str, String)>,
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a property, like `"background-color"`, overriding any value it already has
+    pub fn set(&mut self, property: &'static str, value: impl Display) {
+        let value = value.to_string();
+        match self
+            .declarations
+            .iter_mut()
+            .find(|(name, _)| *name == property)
+        {
+            Some((_, old)) => *old = value,
+            None => self.declarations.push((property, value)),
+        }
+    }
+
+    /// The value of a property, if it's set
+    pub fn get(&self, property: &str) -> Option<&str> {
+        self.declarations
+            .iter()
+            .find(|(name, _)| *name == property)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Set every property of `other`, overriding the properties both styles set
+    pub fn merge(&mut self, other: &Style) {
+        for (property, value) in &other.declarations {
+            self.set(*property, value);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.declarations.is_empty()
+    }
+}
+
+impl Display for Style {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (idx, (property, value)) in self.declarations.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}: {};", property, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> IntoAttributeValue<'a> for Style {
+    fn into_value(self, bump: &'a Bump) -> AttributeValue<'a> {
+        AttributeValue::Text(bump.alloc_str(&self.to_string()))
+    }
+}
+
+/// Looks up the css properties for [`style!`](crate::style!) among the attributes in the `style` namespace
+#[doc(hidden)]
+pub struct StyleProperties;
+
+impl GlobalAttributes for StyleProperties {}
+
+/// Check that an attribute is a css property when `style!` is compiled
+#[doc(hidden)]
+pub const fn css_property(attribute: AttributeDiscription) -> &'static str {
+    let namespace = match attribute.1 {
+        Some(namespace) => namespace.as_bytes(),
+        None => panic!("style! only takes css properties"),
+    };
+    let style = "style".as_bytes();
+    let mut idx = 0;
+    if namespace.len() != style.len() {
+        panic!("style! only takes css properties");
+    }
+    while idx < style.len() {
+        if namespace[idx] != style[idx] {
+            panic!("style! only takes css properties");
+        }
+        idx += 1;
+    }
+    attribute.0
+}
+
+/// Build a [`Style`] from css properties, written like the style attributes of rsx
+///
+/// The names of the properties are checked when the macro is compiled. Literals are format strings, like in rsx, so
+/// numbers are written as strings too (`opacity: "0.5"`). Any other value is written with its `Display` impl.
+/// Declarations in an `if` block are only set when the condition holds, and override the declarations before them.
+///
+/// ```rust, ignore
+/// rsx! {
+///     div {
+///         style: style! {
+///             background_color: "white",
+///             width: "{width}px",
+///             opacity: opacity,
+///             if selected {
+///                 background_color: "lightblue",
+///                 font_weight: "bold",
+///             }
+///         },
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! style {
+    ($($body:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut style = $crate::Style::new();
+        $crate::__style_declarations!(style; $($body)*);
+        style
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __style_declarations {
+    ($style:ident;) => {};
+
+    ($style:ident; if $($rest:tt)+) => {
+        $crate::__style_declarations!(@condition $style; []; $($rest)+);
+    };
+
+    ($style:ident; $name:ident: $value:literal $(, $($rest:tt)*)?) => {
+        $style.set($crate::__style_property!($name), ::std::format!($value));
+        $crate::__style_declarations!($style; $($($rest)*)?);
+    };
+
+    ($style:ident; $name:ident: $value:expr $(, $($rest:tt)*)?) => {
+        $style.set($crate::__style_property!($name), $value);
+        $crate::__style_declarations!($style; $($($rest)*)?);
+    };
+
+    // The condition of an `if` block is every token up to the block
+    (@condition $style:ident; [$($cond:tt)+]; { $($body:tt)* } , $($rest:tt)*) => {
+        $crate::__style_declarations!(@condition $style; [$($cond)+]; { $($body)* } $($rest)*);
+    };
+
+    (@condition $style:ident; [$($cond:tt)+]; { $($body:tt)* } $($rest:tt)*) => {
+        if $($cond)+ {
+            $crate::__style_declarations!($style; $($body)*);
+        }
+        $crate::__style_declarations!($style; $($rest)*);
+    };
+
+    (@condition $style:ident; [$($cond:tt)*]; $next:tt $($rest:tt)*) => {
+        $crate::__style_declarations!(@condition $style; [$($cond)* $next]; $($rest)*);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __style_property {
+    ($name:ident) => {{
+        const PROPERTY: &str =
+            $crate::css_property(<$crate::StyleProperties as $crate::GlobalAttributes>::$name);
+        PROPERTY
+    }};
+}
@@ -13,14 +13,19 @@
 //!
 //! Currently, we don't validate for structures, but do validate attributes.
 
+pub mod aria;
 mod elements;
 #[cfg(feature = "hot-reload-context")]
 pub use elements::HtmlCtx;
 pub mod events;
+mod file_engine;
 pub mod geometry;
 mod global_attributes;
+mod inline_style;
 pub mod input_data;
+mod raw_html;
 mod render_template;
+mod shadow_root;
 #[cfg(feature = "wasm-bind")]
 mod web_sys_bind;
 
@@ -32,9 +37,17 @@ pub use transit::*;
 
 pub use elements::*;
 pub use events::*;
+pub use file_engine::*;
 pub use global_attributes::*;
+pub use inline_style::*;
+pub use raw_html::RawHtml;
 pub use render_template::*;
+pub use shadow_root::*;
 
 pub mod prelude {
+    pub use crate::aria::*;
     pub use crate::events::*;
+    pub use crate::raw_html::RawHtml;
+    pub use crate::shadow_root::ShadowRoot;
+    pub use crate::{style, Style};
 }
@@ -0,0 +1,90 @@
+use dioxus_core::{
+    Attribute, AttributeValue, DynamicNode, IntoDynNode, ScopeState, Template, TemplateAttribute,
+    TemplateNode, VNode,
+};
+use std::{borrow::Cow, cell::Cell};
+
+/// A node of raw HTML that's rendered as it is, without being escaped
+///
+/// The HTML is placed in a wrapper element with `display: contents`, so it doesn't take part in the layout. It's set
+/// as the `dangerous_inner_html` of that element in every renderer, including SSR.
+///
+/// HTML from users or other untrusted sources can inject scripts into the page. Use [`RawHtml::sanitized`] for those,
+/// which is behind the `sanitize` feature.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// let html = markdown::to_html(&cx.props.post);
+///
+/// cx.render(rsx! {
+///     article { RawHtml::new(&html) }
+/// })
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RawHtml<'a> {
+    html: Cow<'a, str>,
+}
+
+impl<'a> RawHtml<'a> {
+    /// Render the HTML as it is
+    pub fn new(html: impl Into<Cow<'a, str>>) -> Self {
+        Self { html: html.into() }
+    }
+
+    /// Render the HTML after removing scripts, event handlers and anything else that isn't safe with [`ammonia`]
+    #[cfg(feature = "sanitize")]
+    pub fn sanitized(html: &str) -> Self {
+        Self {
+            html: Cow::Owned(ammonia::clean(html)),
+        }
+    }
+
+    /// The HTML that will be rendered
+    pub fn html(&self) -> &str {
+        &self.html
+    }
+}
+
+static TEMPLATE: Template<'static> = Template {
+    name: concat!(file!(), ":", line!(), ":", column!(), ":", 0),
+    roots: &[TemplateNode::Element {
+        tag: "div",
+        namespace: None,
+        attrs: &[
+            TemplateAttribute::Static {
+                name: "style",
+                value: "display: contents",
+                namespace: None,
+            },
+            TemplateAttribute::Dynamic { id: 0 },
+        ],
+        children: &[],
+    }],
+    node_paths: &[],
+    attr_paths: &[&[0]],
+};
+
+impl<'a, 'b> IntoDynNode<'a> for RawHtml<'b> {
+    fn into_vnode(self, cx: &'a ScopeState) -> DynamicNode<'a> {
+        let html = cx.bump().alloc_str(&self.html);
+
+        let node = VNode {
+            parent: None,
+            key: None,
+            template: Cell::new(TEMPLATE),
+            root_ids: Default::default(),
+            dynamic_nodes: &[],
+            dynamic_attrs: cx.bump().alloc([Attribute {
+                name: "dangerous_inner_html",
+                value: AttributeValue::Text(html),
+                namespace: None,
+                mounted_element: Default::default(),
+                volatile: false,
+                live: None,
+            }]),
+        };
+
+        DynamicNode::Fragment(cx.bump().alloc([node]))
+    }
+}
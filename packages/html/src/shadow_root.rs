@@ -0,0 +1,99 @@
+use dioxus_core::{
+    Attribute, AttributeValue, DynamicNode, Element, IntoDynNode, ScopeState, Template,
+    TemplateAttribute, TemplateNode, VNode,
+};
+use std::{borrow::Cow, cell::Cell};
+
+/// An element whose children are rendered in a shadow root, so the styles of the page and of the rest of the app
+/// don't reach them
+///
+/// The shadow root adopts the stylesheets, which only style the children. Every shadow root with the same stylesheets
+/// shares one constructed stylesheet. The element has `display: contents`, so it doesn't take part in the layout.
+///
+/// The web renderer attaches the shadow root. Renderers without shadow roots render the children in the element like
+/// any other.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// cx.render(rsx! {
+///     ShadowRoot::new(cx.render(rsx! {
+///         button { class: "primary", "Buy now" }
+///     }))
+///     .stylesheet(".primary { background: teal; }")
+/// })
+/// ```
+pub struct ShadowRoot<'a> {
+    children: Element<'a>,
+    stylesheets: Vec<Cow<'a, str>>,
+}
+
+impl<'a> ShadowRoot<'a> {
+    /// Render the children in a shadow root
+    pub fn new(children: Element<'a>) -> Self {
+        Self {
+            children,
+            stylesheets: Vec::new(),
+        }
+    }
+
+    /// Adopt the css of a stylesheet in the shadow root
+    pub fn stylesheet(mut self, css: impl Into<Cow<'a, str>>) -> Self {
+        self.stylesheets.push(css.into());
+        self
+    }
+}
+
+/// The attribute that makes the interpreter attach a shadow root to an element
+pub const SHADOW_ROOT_ATTRIBUTE: &str = "dioxus-shadow-root";
+
+/// The attribute with the css the shadow root of an element adopts
+pub const SHADOW_STYLES_ATTRIBUTE: &str = "dioxus-shadow-styles";
+
+static TEMPLATE: Template<'static> = Template {
+    name: concat!(file!(), ":", line!(), ":", column!(), ":", 0),
+    roots: &[TemplateNode::Element {
+        tag: "div",
+        namespace: None,
+        attrs: &[
+            TemplateAttribute::Static {
+                name: SHADOW_ROOT_ATTRIBUTE,
+                value: "open",
+                namespace: None,
+            },
+            TemplateAttribute::Static {
+                name: "style",
+                value: "display: contents",
+                namespace: None,
+            },
+            TemplateAttribute::Dynamic { id: 0 },
+        ],
+        children: &[TemplateNode::Dynamic { id: 0 }],
+    }],
+    node_paths: &[&[0, 0]],
+    attr_paths: &[&[0]],
+};
+
+impl<'a> IntoDynNode<'a> for ShadowRoot<'a> {
+    fn into_vnode(self, cx: &'a ScopeState) -> DynamicNode<'a> {
+        let css = cx.bump().alloc_str(&self.stylesheets.join("\n"));
+
+        let node = VNode {
+            parent: None,
+            key: None,
+            template: Cell::new(TEMPLATE),
+            root_ids: Default::default(),
+            dynamic_nodes: cx.bump().alloc([self.children.into_vnode(cx)]),
+            dynamic_attrs: cx.bump().alloc([Attribute {
+                name: SHADOW_STYLES_ATTRIBUTE,
+                value: AttributeValue::Text(css),
+                namespace: None,
+                mounted_element: Default::default(),
+                volatile: false,
+                live: None,
+            }]),
+        };
+
+        DynamicNode::Fragment(cx.bump().alloc([node]))
+    }
+}
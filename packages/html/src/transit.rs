@@ -1,7 +1,7 @@
-use std::{any::Any, rc::Rc};
+use std::{any::Any, rc::Rc, sync::Arc};
 
 use crate::events::*;
-use dioxus_core::ElementId;
+use dioxus_core::{ElementId, VirtualDom};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Debug, Clone, PartialEq)]
@@ -82,8 +82,8 @@ fn fun_name(
 
         // Pointer
         "pointerlockchange" | "pointerlockerror" | "pointerdown" | "pointermove" | "pointerup"
-        | "pointerover" | "pointerout" | "pointerenter" | "pointerleave" | "gotpointercapture"
-        | "lostpointercapture" => Pointer(de(data)?),
+        | "pointercancel" | "pointerover" | "pointerout" | "pointerenter" | "pointerleave"
+        | "gotpointercapture" | "lostpointercapture" => Pointer(de(data)?),
 
         // Selection
         "selectstart" | "selectionchange" | "select" => Selection(de(data)?),
@@ -113,14 +113,16 @@ fn fun_name(
         // Toggle
         "toggle" => Toggle(de(data)?),
 
+        // Gestures, which the renderer recognizes from the pointer events
+        "swipe" => Swipe(de(data)?),
+        "pinch" => Pinch(de(data)?),
+        "longpress" => LongPress(de(data)?),
+
         // ImageData => "load" | "error";
         // OtherData => "abort" | "afterprint" | "beforeprint" | "beforeunload" | "hashchange" | "languagechange" | "message" | "offline" | "online" | "pagehide" | "pageshow" | "popstate" | "rejectionhandled" | "storage" | "unhandledrejection" | "unload" | "userproximity" | "vrdisplayactivate" | "vrdisplayblur" | "vrdisplayconnect" | "vrdisplaydeactivate" | "vrdisplaydisconnect" | "vrdisplayfocus" | "vrdisplaypointerrestricted" | "vrdisplaypointerunrestricted" | "vrdisplaypresentchange";
-        other => {
-            return Err(serde_value::DeserializerError::UnknownVariant(
-                other.to_string(),
-                &[],
-            ))
-        }
+
+        // Anything that isn't part of html, like the events of web components
+        _ => Custom(de(data)?),
     };
 
     Ok(data)
@@ -151,9 +153,28 @@ pub enum EventData {
     Animation(AnimationData),
     Transition(TransitionData),
     Toggle(ToggleData),
+    Swipe(SwipeData),
+    Pinch(PinchData),
+    LongPress(LongPressData),
+    Custom(CustomData),
 }
 
 impl EventData {
+    /// Decode the data of an event from json, picking the kind of data from the name of the event
+    pub fn from_json(name: &str, json: &str) -> Result<Self, serde_json::Error> {
+        let data = serde_json::from_str(json)?;
+        fun_name(name, data).map_err(serde::de::Error::custom)
+    }
+
+    /// Attach files to the event, if it's an event that can carry files
+    pub fn set_files(&mut self, files: Arc<dyn FileEngine>) {
+        match self {
+            EventData::Form(data) => data.files = Some(files),
+            EventData::Drag(data) => data.files = Some(files),
+            _ => {}
+        }
+    }
+
     pub fn into_any(self) -> Rc<dyn Any> {
         match self {
             EventData::Mouse(data) => Rc::new(data) as Rc<dyn Any>,
@@ -172,10 +193,41 @@ impl EventData {
             EventData::Animation(data) => Rc::new(data) as Rc<dyn Any>,
             EventData::Transition(data) => Rc::new(data) as Rc<dyn Any>,
             EventData::Toggle(data) => Rc::new(data) as Rc<dyn Any>,
+            EventData::Swipe(data) => Rc::new(data) as Rc<dyn Any>,
+            EventData::Pinch(data) => Rc::new(data) as Rc<dyn Any>,
+            EventData::LongPress(data) => Rc::new(data) as Rc<dyn Any>,
+            EventData::Custom(data) => Rc::new(data) as Rc<dyn Any>,
         }
     }
 }
 
+/// Inject events into a [`VirtualDom`] in their serialized form, like remote renderers and event recorders send them
+pub trait SerializedEvents {
+    /// Decode the json data of an event and dispatch it like [`VirtualDom::handle_event`]
+    ///
+    /// Returns whether a listener called `prevent_default`, or the error if the data doesn't fit the event.
+    fn handle_event_serialized(
+        &mut self,
+        name: &str,
+        json: &str,
+        element: ElementId,
+        bubbles: bool,
+    ) -> Result<bool, serde_json::Error>;
+}
+
+impl SerializedEvents for VirtualDom {
+    fn handle_event_serialized(
+        &mut self,
+        name: &str,
+        json: &str,
+        element: ElementId,
+        bubbles: bool,
+    ) -> Result<bool, serde_json::Error> {
+        let data = EventData::from_json(name, json)?;
+        Ok(self.handle_event(name, data.into_any(), element, bubbles))
+    }
+}
+
 #[test]
 fn test_back_and_forth() {
     let data = HtmlEvent {
@@ -215,3 +267,366 @@ fn test_back_and_forth() {
 
     assert_eq!(data, p);
 }
+
+#[test]
+fn test_touch_points() {
+    let o = r#"
+{
+  "element": 0,
+  "name": "touchstart",
+  "bubbles": true,
+  "data": {
+    "alt_key": false,
+    "ctrl_key": false,
+    "meta_key": false,
+    "shift_key": false,
+    "touches": [
+      { "identifier": 1, "client_x": 10.5, "client_y": 20.0, "force": 0.5 },
+      { "identifier": 2, "client_x": 30.0, "client_y": 40.0 }
+    ],
+    "changed_touches": [
+      { "identifier": 2, "client_x": 30.0, "client_y": 40.0 }
+    ]
+  }
+}
+    "#;
+
+    let p: HtmlEvent = serde_json::from_str(o).unwrap();
+
+    let touch = match p.data {
+        EventData::Touch(touch) => touch,
+        other => panic!("expected touch data, got {:?}", other),
+    };
+    assert_eq!(touch.touches.len(), 2);
+    assert_eq!(touch.touches[0].force, 0.5);
+    assert_eq!(
+        touch.touches[0].client_coordinates(),
+        crate::geometry::ClientPoint::new(10.5, 20.0)
+    );
+    assert_eq!(touch.changed_touches[0].identifier, 2);
+    assert!(touch.target_touches.is_empty());
+}
+
+#[test]
+fn test_gestures() {
+    let swipe = EventData::from_json(
+        "swipe",
+        r#"{ "direction": "left", "delta_x": -80.0, "delta_y": 4.0, "velocity": 0.8, "duration": 100.0, "pointer_type": "touch" }"#,
+    )
+    .unwrap();
+    match swipe {
+        EventData::Swipe(swipe) => {
+            assert_eq!(swipe.direction, SwipeDirection::Left);
+            assert_eq!(swipe.delta_x, -80.0);
+        }
+        other => panic!("expected swipe data, got {:?}", other),
+    }
+
+    let pinch = EventData::from_json(
+        "pinch",
+        r#"{ "phase": "move", "scale": 1.5, "center_x": 100.0, "center_y": 50.0 }"#,
+    )
+    .unwrap();
+    match pinch {
+        EventData::Pinch(pinch) => {
+            assert_eq!(pinch.phase, GesturePhase::Move);
+            assert_eq!(
+                pinch.center(),
+                crate::geometry::ClientPoint::new(100.0, 50.0)
+            );
+        }
+        other => panic!("expected pinch data, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_custom_event() {
+    let o = r#"
+{
+  "element": 3,
+  "name": "color-changed",
+  "bubbles": false,
+  "data": {
+    "detail": "{\"color\":\"red\"}"
+  }
+}
+    "#;
+
+    let p: HtmlEvent = serde_json::from_str(o).unwrap();
+
+    assert_eq!(
+        p.data,
+        EventData::Custom(CustomData {
+            detail: r#"{"color":"red"}"#.to_string()
+        })
+    );
+    assert!(!p.bubbles());
+}
+
+#[test]
+fn test_form_values() {
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Order {
+        name: String,
+        quantity: u32,
+        gift: bool,
+        toppings: Vec<String>,
+        note: Option<String>,
+    }
+
+    let o = r#"
+{
+  "element": 1,
+  "name": "submit",
+  "bubbles": true,
+  "data": {
+    "value": "",
+    "values": {
+      "name": ["Pizza"],
+      "quantity": ["2"],
+      "gift": ["false"],
+      "toppings": ["olives", "basil"]
+    }
+  }
+}
+    "#;
+
+    let p: HtmlEvent = serde_json::from_str(o).unwrap();
+
+    let form = match p.data {
+        EventData::Form(form) => form,
+        other => panic!("expected form data, got {:?}", other),
+    };
+    assert_eq!(form.value_of("name"), Some("Pizza"));
+    assert_eq!(form.values_of("toppings"), ["olives", "basil"]);
+    assert!(form.values_of("note").is_empty());
+    assert_eq!(
+        form.parsed::<Order>().unwrap(),
+        Order {
+            name: "Pizza".to_string(),
+            quantity: 2,
+            gift: false,
+            toppings: vec!["olives".to_string(), "basil".to_string()],
+            note: None,
+        }
+    );
+}
+
+#[test]
+fn test_file_chunks() {
+    let chunks = r#"[
+  { "name": "notes.txt", "data": "aGVs" },
+  { "name": "empty.txt", "data": "" },
+  { "name": "notes.txt", "data": "bG8=" }
+]"#;
+
+    let mut files = crate::SerializedFileEngine::new();
+    for chunk in serde_json::from_str::<Vec<crate::FileChunk>>(chunks).unwrap() {
+        files.push_chunk(&chunk).unwrap();
+    }
+
+    let mut expected = crate::SerializedFileEngine::new();
+    expected.push("notes.txt", b"hello");
+    expected.push("empty.txt", b"");
+    assert_eq!(files, expected);
+
+    let bad = crate::FileChunk {
+        name: "notes.txt".to_string(),
+        data: "not base64!".to_string(),
+    };
+    assert!(files.push_chunk(&bad).is_err());
+
+    let o = r#"
+{
+  "element": 1,
+  "name": "drop",
+  "bubbles": true,
+  "data": {
+    "alt_key": false,
+    "button": 0,
+    "buttons": 0,
+    "client_x": 10,
+    "client_y": 20,
+    "ctrl_key": false,
+    "meta_key": false,
+    "offset_x": 10,
+    "offset_y": 20,
+    "page_x": 10,
+    "page_y": 20,
+    "screen_x": 10,
+    "screen_y": 20,
+    "shift_key": false
+  }
+}
+    "#;
+
+    let mut p: HtmlEvent = serde_json::from_str(o).unwrap();
+    p.data.set_files(Arc::new(files));
+
+    let drag = match p.data {
+        EventData::Drag(drag) => drag,
+        other => panic!("expected drag data, got {:?}", other),
+    };
+    assert_eq!(drag.files(), ["notes.txt", "empty.txt"]);
+}
+
+#[test]
+fn test_streamed_files() {
+    use futures_util::{FutureExt, StreamExt};
+
+    let files = crate::StreamedFileEngine::new();
+    files.start("notes.txt", 5);
+    files.start("empty.txt", 0);
+    files.start("cut.txt", 4);
+
+    let mut stream = files.read_file_stream("notes.txt");
+    let mut read = files.read_file("notes.txt");
+    assert_eq!(stream.next().now_or_never(), None);
+
+    files.push("notes.txt", b"hel");
+    let piece = stream.next().now_or_never().flatten().unwrap();
+    assert_eq!(
+        (piece.bytes.as_slice(), piece.read, piece.size),
+        (&b"hel"[..], 3, 5)
+    );
+    assert_eq!(read.as_mut().now_or_never(), None);
+
+    files.push("notes.txt", b"lo");
+    let piece = stream.next().now_or_never().flatten().unwrap();
+    assert_eq!((piece.bytes.as_slice(), piece.read), (&b"lo"[..], 5));
+    assert_eq!(stream.next().now_or_never(), Some(None));
+    assert_eq!(read.now_or_never(), Some(Some(b"hello".to_vec())));
+
+    let mut empty = files.read_file_stream("empty.txt");
+    let piece = empty.next().now_or_never().flatten().unwrap();
+    assert!(piece.bytes.is_empty());
+    assert_eq!(empty.next().now_or_never(), Some(None));
+
+    files.push("cut.txt", b"ab");
+    assert!(!files.is_complete());
+    files.abort();
+    assert_eq!(files.read_file("cut.txt").now_or_never(), Some(None));
+    let pieces: Vec<_> = files
+        .read_file_stream("cut.txt")
+        .collect::<Vec<_>>()
+        .now_or_never()
+        .unwrap();
+    assert_eq!(pieces.len(), 1);
+    assert_eq!(pieces[0].read, 2);
+}
+
+#[test]
+fn test_media_event() {
+    let o = r#"
+{
+  "element": 1,
+  "name": "timeupdate",
+  "bubbles": false,
+  "data": {
+    "current_time": 12.5,
+    "duration": null,
+    "volume": 0.5,
+    "muted": false,
+    "paused": false,
+    "ended": false,
+    "playback_rate": 1.0
+  }
+}
+    "#;
+
+    let p: HtmlEvent = serde_json::from_str(o).unwrap();
+
+    assert_eq!(
+        p.data,
+        EventData::Media(MediaData {
+            current_time: 12.5,
+            duration: None,
+            volume: 0.5,
+            muted: false,
+            paused: false,
+            ended: false,
+            playback_rate: 1.0,
+        })
+    );
+
+    let o = r#"
+{
+  "element": 1,
+  "name": "transitionend",
+  "bubbles": true,
+  "data": {
+    "property_name": "opacity",
+    "pseudo_element": "",
+    "elapsed_time": 0.25
+  }
+}
+    "#;
+
+    let p: HtmlEvent = serde_json::from_str(o).unwrap();
+
+    assert_eq!(
+        p.data,
+        EventData::Transition(TransitionData {
+            property_name: "opacity".to_string(),
+            pseudo_element: String::new(),
+            elapsed_time: 0.25,
+        })
+    );
+}
+
+#[test]
+fn test_event_data_from_json() {
+    let json = r#"
+{
+  "alt_key": false,
+  "button": 0,
+  "buttons": 0,
+  "client_x": 1,
+  "client_y": 2,
+  "ctrl_key": false,
+  "meta_key": false,
+  "offset_x": 1,
+  "offset_y": 2,
+  "page_x": 1,
+  "page_y": 2,
+  "screen_x": 1,
+  "screen_y": 2,
+  "shift_key": false
+}
+    "#;
+
+    let mouse = match EventData::from_json("click", json).unwrap() {
+        EventData::Mouse(mouse) => mouse,
+        other => panic!("expected mouse data, got {:?}", other),
+    };
+    assert_eq!(
+        mouse.client_coordinates(),
+        crate::geometry::ClientPoint::new(1.0, 2.0)
+    );
+
+    // Data that doesn't fit the event is an error instead of a panic
+    assert!(EventData::from_json("click", "[1, 2, 3]").is_err());
+    assert!(EventData::from_json("click", "not json").is_err());
+}
+
+#[test]
+fn test_scroll() {
+    let scroll = EventData::from_json(
+        "scroll",
+        r#"{ "scroll_top": 1200.0, "scroll_left": 0.0, "scroll_height": 50000.0, "scroll_width": 300.0, "client_height": 400.0, "client_width": 300.0 }"#,
+    )
+    .unwrap();
+    match scroll {
+        EventData::Scroll(scroll) => {
+            assert_eq!(scroll.scroll_top, 1200.0);
+            assert_eq!(scroll.client_height, 400.0);
+        }
+        other => panic!("expected scroll data, got {:?}", other),
+    }
+
+    // Renderers that don't know the offsets send an empty object
+    assert_eq!(
+        EventData::from_json("scroll", "{}").unwrap(),
+        EventData::Scroll(ScrollData::default())
+    );
+}
@@ -1,17 +1,19 @@
 use crate::events::{
-    AnimationData, CompositionData, KeyboardData, MouseData, PointerData, TouchData,
-    TransitionData, WheelData,
+    AnimationData, CompositionData, KeyboardData, MountedError, MountedFuture, MountedResult,
+    MouseData, PointerData, RenderedElement, ScrollBehavior, TouchData, TouchPoint, TransitionData,
+    WheelData,
 };
-use crate::geometry::{ClientPoint, Coordinates, ElementPoint, PagePoint, ScreenPoint};
+use crate::geometry::{ClientPoint, ClientRect, Coordinates, ElementPoint, PagePoint, ScreenPoint};
 use crate::input_data::{decode_key_location, decode_mouse_button_set, MouseButton};
 use crate::DragData;
 use keyboard_types::{Code, Key, Modifiers};
 use std::convert::TryInto;
 use std::str::FromStr;
 use wasm_bindgen::JsCast;
+use wasm_bindgen::JsValue;
 use web_sys::{
-    AnimationEvent, CompositionEvent, Event, KeyboardEvent, MouseEvent, PointerEvent, TouchEvent,
-    TransitionEvent, WheelEvent,
+    AnimationEvent, CompositionEvent, Event, HtmlElement, KeyboardEvent, MouseEvent, PointerEvent,
+    ScrollIntoViewOptions, Touch, TouchEvent, TouchList, TransitionEvent, WheelEvent,
 };
 
 macro_rules! uncheck_convert {
@@ -85,6 +87,7 @@ impl From<&KeyboardEvent> for KeyboardData {
             e.repeat(),
             modifiers,
         )
+        .with_composing(e.is_composing())
     }
 }
 
@@ -123,6 +126,7 @@ impl From<&MouseEvent> for DragData {
     fn from(value: &MouseEvent) -> Self {
         Self {
             mouse: MouseData::from(value),
+            files: None,
         }
     }
 }
@@ -134,6 +138,34 @@ impl From<&TouchEvent> for TouchData {
             ctrl_key: e.ctrl_key(),
             meta_key: e.meta_key(),
             shift_key: e.shift_key(),
+            touches: touch_points(&e.touches()),
+            changed_touches: touch_points(&e.changed_touches()),
+            target_touches: touch_points(&e.target_touches()),
+        }
+    }
+}
+
+fn touch_points(list: &TouchList) -> Vec<TouchPoint> {
+    (0..list.length())
+        .filter_map(|index| list.get(index))
+        .map(|touch| TouchPoint::from(&touch))
+        .collect()
+}
+
+impl From<&Touch> for TouchPoint {
+    fn from(touch: &Touch) -> Self {
+        Self {
+            identifier: touch.identifier(),
+            screen_x: touch.screen_x().into(),
+            screen_y: touch.screen_y().into(),
+            client_x: touch.client_x().into(),
+            client_y: touch.client_y().into(),
+            page_x: touch.page_x().into(),
+            page_y: touch.page_y().into(),
+            radius_x: touch.radius_x().into(),
+            radius_y: touch.radius_y().into(),
+            rotation_angle: touch.rotation_angle().into(),
+            force: touch.force().into(),
         }
     }
 }
@@ -193,3 +225,55 @@ impl From<&TransitionEvent> for TransitionData {
         }
     }
 }
+
+impl RenderedElement for web_sys::Element {
+    fn get_raw_element(&self) -> MountedResult<&dyn std::any::Any> {
+        Ok(self)
+    }
+
+    fn get_client_rect(&self) -> MountedFuture<ClientRect> {
+        let rect = self.get_bounding_client_rect();
+        let rect = ClientRect::new(
+            euclid::Point2D::new(rect.left(), rect.top()),
+            euclid::Size2D::new(rect.width(), rect.height()),
+        );
+        Box::pin(async move { Ok(rect) })
+    }
+
+    fn scroll_to(&self, behavior: ScrollBehavior) -> MountedFuture<()> {
+        let mut options = ScrollIntoViewOptions::new();
+        options.behavior(match behavior {
+            ScrollBehavior::Instant => web_sys::ScrollBehavior::Instant,
+            ScrollBehavior::Smooth => web_sys::ScrollBehavior::Smooth,
+        });
+        self.scroll_into_view_with_scroll_into_view_options(&options);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn set_scroll_position(&self, top: f64, left: f64) -> MountedFuture<()> {
+        self.set_scroll_top(top.round() as i32);
+        self.set_scroll_left(left.round() as i32);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn set_focus(&self, focus: bool) -> MountedFuture<()> {
+        let result = match self.dyn_ref::<HtmlElement>() {
+            Some(element) if focus => element.focus(),
+            Some(element) => element.blur(),
+            None => Ok(()),
+        }
+        .map_err(|err| MountedError::OperationFailed(Box::new(FocusError(err))));
+        Box::pin(async move { result })
+    }
+}
+
+#[derive(Debug)]
+struct FocusError(JsValue);
+
+impl std::fmt::Display for FocusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to focus element {:?}", self.0)
+    }
+}
+
+impl std::error::Error for FocusError {}
@@ -0,0 +1,9 @@
+//! `style!` should only compile with css properties and values that can be displayed
+
+#[test]
+fn style_macro() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/style/valid.rs");
+    t.compile_fail("tests/style/unknown_property.rs");
+    t.compile_fail("tests/style/invalid_value.rs");
+}
@@ -0,0 +1,7 @@
+use dioxus_html::style;
+
+struct Pixels;
+
+fn main() {
+    let _ = style! { width: Pixels };
+}
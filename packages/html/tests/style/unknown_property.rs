@@ -0,0 +1,5 @@
+use dioxus_html::style;
+
+fn main() {
+    let _ = style! { colour: "red" };
+}
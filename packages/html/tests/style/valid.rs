@@ -0,0 +1,10 @@
+use dioxus_html::style;
+
+fn main() {
+    let width = 10;
+    let style = style! { background_color: "white", width: "{width}px", opacity: 0.5 };
+    assert_eq!(
+        style.to_string(),
+        "background-color: white; width: 10px; opacity: 0.5;"
+    );
+}
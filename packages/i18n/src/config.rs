@@ -0,0 +1,91 @@
+use crate::negotiate_locale;
+use fluent_bundle::FluentResource;
+use unic_langid::LanguageIdentifier;
+
+/// The locales an app is translated into, and which one it starts in
+///
+/// ```rust, ignore
+/// I18nConfig::new()
+///     .with_locale(langid!("en-US"), include_str!("../locales/en-US.ftl"))
+///     .with_locale(langid!("fr"), include_str!("../locales/fr.ftl"))
+///     .with_accept_language("fr-CH, fr;q=0.9, en;q=0.8")
+/// ```
+pub struct I18nConfig {
+    pub(crate) locales: Vec<(LanguageIdentifier, Vec<FluentResource>)>,
+    pub(crate) initial: Option<LanguageIdentifier>,
+    pub(crate) fallback: Option<LanguageIdentifier>,
+    pub(crate) use_isolating: bool,
+}
+
+impl Default for I18nConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl I18nConfig {
+    /// A config without any locales
+    pub fn new() -> Self {
+        Self {
+            locales: Vec::new(),
+            initial: None,
+            fallback: None,
+            use_isolating: true,
+        }
+    }
+
+    /// Add the messages of a Fluent source to a locale
+    ///
+    /// A locale can be split up over several sources. Parts of a source that don't parse are logged and left out.
+    pub fn with_locale(mut self, id: LanguageIdentifier, source: impl Into<String>) -> Self {
+        let resource = match FluentResource::try_new(source.into()) {
+            Ok(resource) => resource,
+            Err((resource, errors)) => {
+                log::error!("Failed to parse the messages for {}: {:?}", id, errors);
+                resource
+            }
+        };
+
+        match self
+            .locales
+            .iter_mut()
+            .find(|(existing, _)| *existing == id)
+        {
+            Some((_, resources)) => resources.push(resource),
+            None => self.locales.push((id, vec![resource])),
+        }
+        self
+    }
+
+    /// Start in a locale. By default, the app starts in the first locale that was added
+    pub fn with_initial_locale(mut self, id: LanguageIdentifier) -> Self {
+        self.initial = Some(id);
+        self
+    }
+
+    /// Start in the locale that fits an `Accept-Language` header best, which is how servers pick the locale to render
+    ///
+    /// Locales have to be added first. If none of them are acceptable, the initial locale doesn't change.
+    pub fn with_accept_language(mut self, accept_language: &str) -> Self {
+        let available: Vec<_> = self.locales.iter().map(|(id, _)| id.clone()).collect();
+        if let Some(id) = negotiate_locale(accept_language, &available) {
+            self.initial = Some(id);
+        }
+        self
+    }
+
+    /// Look up messages that are missing from the current locale in another one. By default, that's the first locale
+    /// that was added
+    pub fn with_fallback(mut self, id: LanguageIdentifier) -> Self {
+        self.fallback = Some(id);
+        self
+    }
+
+    /// Wrap arguments in unicode isolation marks, so they can't change the direction of the text around them
+    ///
+    /// This is on by default, like in Fluent itself.
+    pub fn with_isolating(mut self, use_isolating: bool) -> Self {
+        self.use_isolating = use_isolating;
+        self
+    }
+}
@@ -0,0 +1,120 @@
+/// Check if a Fluent source defines a message, or an attribute of a message when the key has a dot in it
+///
+/// This only looks at where definitions start, which is enough to tell [`key!`](crate::key) about typos at compile
+/// time. Parsing the source is left to the bundle at runtime.
+pub const fn has_message(source: &str, key: &str) -> bool {
+    let source = source.as_bytes();
+    let key = key.as_bytes();
+
+    // split the key into the message and the attribute
+    let mut dot = 0;
+    while dot < key.len() && key[dot] != b'.' {
+        dot += 1;
+    }
+
+    let mut line = 0;
+    while line < source.len() {
+        if defines(source, line, key, 0, dot) {
+            if dot == key.len() {
+                return true;
+            }
+            return has_attribute(source, next_line(source, line), key, dot + 1);
+        }
+        line = next_line(source, line);
+    }
+
+    false
+}
+
+/// Look through the indented lines that continue a message for an attribute
+const fn has_attribute(source: &[u8], mut line: usize, key: &[u8], start: usize) -> bool {
+    while line < source.len() {
+        let mut pos = line;
+        while pos < source.len() && source[pos] == b' ' {
+            pos += 1;
+        }
+
+        let blank = pos == source.len() || source[pos] == b'\n' || source[pos] == b'\r';
+        if pos == line && !blank {
+            // the message is over
+            return false;
+        }
+        if !blank && source[pos] == b'.' && defines(source, pos + 1, key, start, key.len()) {
+            return true;
+        }
+
+        line = next_line(source, line);
+    }
+
+    false
+}
+
+/// Check if the identifier `key[start..end]` is defined at `pos`
+const fn defines(source: &[u8], mut pos: usize, key: &[u8], start: usize, end: usize) -> bool {
+    if start == end {
+        return false;
+    }
+
+    let mut i = start;
+    while i < end {
+        if pos >= source.len() || source[pos] != key[i] {
+            return false;
+        }
+        pos += 1;
+        i += 1;
+    }
+
+    while pos < source.len() && source[pos] == b' ' {
+        pos += 1;
+    }
+    pos < source.len() && source[pos] == b'='
+}
+
+const fn next_line(source: &[u8], mut pos: usize) -> usize {
+    while pos < source.len() && source[pos] != b'\n' {
+        pos += 1;
+    }
+    pos + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "\
+# comments don't define anything
+welcome = Welcome!
+greeting= Hello, { $name }!
+
+login =
+    .placeholder = Email
+    .aria-label = Login input
+
+    .title = Log in
+-brand = Dioxus
+after = The end
+";
+
+    #[test]
+    fn finds_messages() {
+        assert!(has_message(SOURCE, "welcome"));
+        assert!(has_message(SOURCE, "greeting"));
+        assert!(has_message(SOURCE, "after"));
+
+        assert!(!has_message(SOURCE, "welcom"));
+        assert!(!has_message(SOURCE, "comments"));
+        assert!(!has_message(SOURCE, "brand"));
+        assert!(!has_message(SOURCE, ""));
+    }
+
+    #[test]
+    fn finds_attributes() {
+        assert!(has_message(SOURCE, "login.placeholder"));
+        assert!(has_message(SOURCE, "login.aria-label"));
+        assert!(has_message(SOURCE, "login.title"));
+
+        assert!(!has_message(SOURCE, "login.value"));
+        assert!(!has_message(SOURCE, "welcome.placeholder"));
+        assert!(!has_message(SOURCE, "login."));
+    }
+}
@@ -0,0 +1,78 @@
+#![doc = include_str!("../README.md")]
+
+mod config;
+mod keys;
+mod negotiate;
+mod use_i18n;
+
+pub use config::*;
+pub use keys::*;
+pub use negotiate::*;
+pub use use_i18n::*;
+
+pub use fluent_bundle::{FluentArgs, FluentValue};
+pub use unic_langid::LanguageIdentifier;
+
+pub mod prelude {
+    pub use crate::{key, t, use_i18n, use_init_i18n, I18n, I18nConfig, LanguageIdentifier};
+}
+
+/// Translate a message with the current locale of an [`I18n`] handle
+///
+/// Arguments are passed by name, and end up as the variables of the message:
+///
+/// ```rust, ignore
+/// let i18n = use_i18n(cx);
+///
+/// t!(i18n, "title");
+/// t!(i18n, "greeting", name: "Dioxus", unread: 3);
+/// ```
+///
+/// Attributes of a message are looked up with a dot, like `t!(i18n, "login.placeholder")`.
+#[macro_export]
+macro_rules! t {
+    ($i18n:expr, $key:expr $(,)?) => {
+        $i18n.translate($key, None)
+    };
+    ($i18n:expr, $key:expr, $($name:ident : $value:expr),+ $(,)?) => {{
+        let mut args = $crate::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $i18n.translate($key, Some(&args))
+    }};
+}
+
+/// Check at compile time that a message exists in a Fluent source, and evaluate to its key
+///
+/// The source has to be a constant, which is what `include_str!` gives you:
+///
+/// ```rust
+/// use dioxus_i18n::key;
+///
+/// const EN_US: &str = "greeting = Hello, { $name }!";
+///
+/// assert_eq!(key!(EN_US, "greeting"), "greeting");
+/// ```
+///
+/// Keys that aren't in the source fail the build:
+///
+/// ```rust, compile_fail
+/// use dioxus_i18n::key;
+///
+/// const EN_US: &str = "greeting = Hello, { $name }!";
+///
+/// key!(EN_US, "farewell");
+/// ```
+#[macro_export]
+macro_rules! key {
+    ($source:expr, $key:literal) => {{
+        const _: () = assert!(
+            $crate::has_message($source, $key),
+            concat!(
+                "there is no message named `",
+                $key,
+                "` in the fluent source"
+            )
+        );
+        $key
+    }};
+}
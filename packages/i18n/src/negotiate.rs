@@ -0,0 +1,70 @@
+use fluent_langneg::{negotiate_languages, NegotiationStrategy};
+use unic_langid::LanguageIdentifier;
+
+/// Pick the available locale that fits an `Accept-Language` header best
+///
+/// ```rust
+/// use dioxus_i18n::{negotiate_locale, LanguageIdentifier};
+///
+/// let available: Vec<LanguageIdentifier> = vec!["en-US".parse().unwrap(), "fr".parse().unwrap()];
+/// let locale = negotiate_locale("fr-CH, fr;q=0.9, en;q=0.8", &available);
+/// assert_eq!(locale, Some("fr".parse().unwrap()));
+/// ```
+pub fn negotiate_locale(
+    accept_language: &str,
+    available: &[LanguageIdentifier],
+) -> Option<LanguageIdentifier> {
+    let requested = accepted_languages(accept_language);
+    negotiate_languages(&requested, available, None, NegotiationStrategy::Filtering)
+        .first()
+        .map(|id| (*id).clone())
+}
+
+/// The languages in an `Accept-Language` header, from the most to the least preferred
+fn accepted_languages(accept_language: &str) -> Vec<LanguageIdentifier> {
+    let mut languages: Vec<(LanguageIdentifier, f32)> = accept_language
+        .split(',')
+        .filter_map(|language| {
+            let mut parts = language.split(';').map(str::trim);
+            let id = parts.next().filter(|id| !id.is_empty())?.parse().ok()?;
+            let quality = parts
+                .find_map(|part| part.strip_prefix("q="))
+                .map_or(Some(1.0), |quality| quality.parse().ok())?;
+            Some((id, quality))
+        })
+        .filter(|(_, quality)| *quality > 0.0)
+        .collect();
+
+    // the sort is stable, so languages of the same quality stay in the order of the header
+    languages.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+    languages.into_iter().map(|(id, _)| id).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locales(ids: &[&str]) -> Vec<LanguageIdentifier> {
+        ids.iter().map(|id| id.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn negotiates_by_quality() {
+        let available = locales(&["en-US", "de", "fr"]);
+
+        let best = negotiate_locale("fr;q=0.5, de;q=0.9", &available);
+        assert_eq!(best, Some("de".parse().unwrap()));
+
+        // a language without a region matches the regions of it
+        let best = negotiate_locale("en", &available);
+        assert_eq!(best, Some("en-US".parse().unwrap()));
+    }
+
+    #[test]
+    fn nothing_acceptable() {
+        let available = locales(&["en-US", "de"]);
+
+        assert_eq!(negotiate_locale("ja, zh;q=0.5", &available), None);
+        assert_eq!(negotiate_locale("", &available), None);
+    }
+}
@@ -0,0 +1,302 @@
+use crate::I18nConfig;
+use dioxus_core::{ScopeId, ScopeState};
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashSet,
+    rc::Rc,
+    sync::Arc,
+};
+use unic_langid::LanguageIdentifier;
+
+/// Translate the app below this component
+///
+/// Every component under it can get the translations with [`use_i18n`]. The config is only read on the first render.
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     use_init_i18n(cx, || {
+///         I18nConfig::new()
+///             .with_locale(langid!("en-US"), include_str!("../locales/en-US.ftl"))
+///             .with_locale(langid!("fr"), include_str!("../locales/fr.ftl"))
+///     });
+///
+///     render! { greeting {} }
+/// }
+/// ```
+pub fn use_init_i18n(cx: &ScopeState, config: impl FnOnce() -> I18nConfig) -> &I18n {
+    let subscription = cx.use_hook(|| {
+        let i18n = I18n::new(config(), cx.schedule_update_any());
+        cx.provide_context(i18n.clone());
+        I18nSubscription::new(i18n, cx.scope_id())
+    });
+    &subscription.i18n
+}
+
+/// Get the translations of the app, and re-render this component when the locale changes
+///
+/// ```rust, ignore
+/// fn greeting(cx: Scope) -> Element {
+///     let i18n = use_i18n(cx);
+///
+///     render! {
+///         h1 { t!(i18n, "greeting", name: "Dioxus") }
+///         button { onclick: move |_| i18n.set_locale(langid!("fr")), t!(i18n, "switch-locale") }
+///     }
+/// }
+/// ```
+///
+/// # Panics
+///
+/// If no parent component called [`use_init_i18n`].
+pub fn use_i18n(cx: &ScopeState) -> &I18n {
+    let subscription = cx.use_hook(|| {
+        let i18n = cx.expect_context::<I18n>();
+        I18nSubscription::new(i18n, cx.scope_id())
+    });
+    &subscription.i18n
+}
+
+struct I18nSubscription {
+    i18n: I18n,
+    scope: ScopeId,
+}
+
+impl I18nSubscription {
+    fn new(i18n: I18n, scope: ScopeId) -> Self {
+        i18n.inner.consumers.borrow_mut().insert(scope);
+        Self { i18n, scope }
+    }
+}
+
+impl Drop for I18nSubscription {
+    fn drop(&mut self) {
+        self.i18n.inner.consumers.borrow_mut().remove(&self.scope);
+    }
+}
+
+/// The translations of an app in all of its locales
+#[derive(Clone)]
+pub struct I18n {
+    inner: Rc<I18nInner>,
+}
+
+struct I18nInner {
+    bundles: Vec<FluentBundle<FluentResource>>,
+    current: Cell<usize>,
+    fallback: Option<usize>,
+    consumers: RefCell<HashSet<ScopeId>>,
+    notify_any: Arc<dyn Fn(ScopeId)>,
+}
+
+impl I18n {
+    fn new(config: I18nConfig, notify_any: Arc<dyn Fn(ScopeId)>) -> Self {
+        let position = |id: &Option<LanguageIdentifier>| {
+            let id = id.as_ref()?;
+            let position = config.locales.iter().position(|(locale, _)| locale == id);
+            if position.is_none() {
+                log::error!("There are no messages for {}", id);
+            }
+            position
+        };
+        let current = position(&config.initial).unwrap_or(0);
+        let fallback = match config.fallback {
+            Some(_) => position(&config.fallback),
+            None if config.locales.is_empty() => None,
+            None => Some(0),
+        };
+
+        let bundles = config
+            .locales
+            .into_iter()
+            .map(|(id, resources)| {
+                let mut bundle = FluentBundle::new(vec![id]);
+                bundle.set_use_isolating(config.use_isolating);
+                for resource in resources {
+                    if let Err(errors) = bundle.add_resource(resource) {
+                        log::error!("Some messages are defined twice: {:?}", errors);
+                    }
+                }
+                bundle
+            })
+            .collect();
+
+        Self {
+            inner: Rc::new(I18nInner {
+                bundles,
+                current: Cell::new(current),
+                fallback,
+                consumers: Default::default(),
+                notify_any,
+            }),
+        }
+    }
+
+    /// The locale that messages are translated into
+    pub fn locale(&self) -> Option<&LanguageIdentifier> {
+        self.inner
+            .bundles
+            .get(self.inner.current.get())
+            .and_then(|bundle| bundle.locales.first())
+    }
+
+    /// All the locales there are messages for
+    pub fn locales(&self) -> impl Iterator<Item = &LanguageIdentifier> {
+        self.inner
+            .bundles
+            .iter()
+            .filter_map(|bundle| bundle.locales.first())
+    }
+
+    /// Switch to another locale, and re-render every component that uses the translations
+    ///
+    /// Returns false without switching if there are no messages for the locale.
+    pub fn set_locale(&self, id: LanguageIdentifier) -> bool {
+        let position = self.locales().position(|locale| *locale == id);
+        let position = match position {
+            Some(position) => position,
+            None => {
+                log::error!("Can't switch to {}, there are no messages for it", id);
+                return false;
+            }
+        };
+
+        if self.inner.current.replace(position) != position {
+            for consumer in self.inner.consumers.borrow().iter() {
+                (self.inner.notify_any)(*consumer);
+            }
+        }
+        true
+    }
+
+    /// Translate a message with the current locale, like [`t!`](crate::t) does
+    ///
+    /// Messages that are missing from the current locale come from the fallback. If the fallback doesn't have it either,
+    /// this returns the key so the gap is easy to spot.
+    pub fn translate(&self, key: &str, args: Option<&FluentArgs>) -> String {
+        let current = Some(self.inner.current.get());
+        let fallback = self
+            .inner
+            .fallback
+            .filter(|fallback| Some(*fallback) != current);
+
+        for bundle in current.into_iter().chain(fallback) {
+            if let Some(translation) = self.translate_in(bundle, key, args) {
+                return translation;
+            }
+        }
+
+        log::warn!("There is no message named {}", key);
+        key.to_string()
+    }
+
+    fn translate_in(&self, bundle: usize, key: &str, args: Option<&FluentArgs>) -> Option<String> {
+        let bundle = self.inner.bundles.get(bundle)?;
+
+        let mut parts = key.splitn(2, '.');
+        let message = bundle.get_message(parts.next()?)?;
+        let pattern = match parts.next() {
+            Some(attribute) => message.get_attribute(attribute)?.value(),
+            None => message.value()?,
+        };
+
+        let mut errors = Vec::new();
+        let translation = bundle.format_pattern(pattern, args, &mut errors);
+        if !errors.is_empty() {
+            log::warn!("Failed to translate {}: {:?}", key, errors);
+        }
+        Some(translation.into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::prelude::*;
+
+    const EN_US: &str = "\
+greeting = Hello, { $name }!
+farewell = Goodbye!
+login =
+    .placeholder = Email
+";
+
+    const FR: &str = "\
+greeting = Bonjour, { $name } !
+";
+
+    fn config() -> I18nConfig {
+        I18nConfig::new()
+            .with_locale("en-US".parse().unwrap(), EN_US)
+            .with_locale("fr".parse().unwrap(), FR)
+            .with_isolating(false)
+    }
+
+    fn new_i18n(config: I18nConfig) -> I18n {
+        I18n::new(config, Arc::new(|_| {}))
+    }
+
+    #[test]
+    fn translates_messages() {
+        let i18n = new_i18n(config());
+
+        assert_eq!(
+            crate::t!(i18n, "greeting", name: "Dioxus"),
+            "Hello, Dioxus!"
+        );
+        assert_eq!(crate::t!(i18n, "login.placeholder"), "Email");
+        assert_eq!(crate::t!(i18n, "missing"), "missing");
+
+        assert!(i18n.set_locale("fr".parse().unwrap()));
+        assert_eq!(
+            crate::t!(i18n, "greeting", name: "Dioxus"),
+            "Bonjour, Dioxus !"
+        );
+
+        // french falls back to the first locale
+        assert_eq!(crate::t!(i18n, "farewell"), "Goodbye!");
+
+        assert!(!i18n.set_locale("de".parse().unwrap()));
+        assert_eq!(i18n.locale(), Some(&"fr".parse().unwrap()));
+    }
+
+    #[test]
+    fn starts_in_the_negotiated_locale() {
+        let i18n = new_i18n(config().with_accept_language("de-DE, fr;q=0.8, en;q=0.5"));
+        assert_eq!(i18n.locale(), Some(&"fr".parse().unwrap()));
+
+        let i18n = new_i18n(config().with_accept_language("ja"));
+        assert_eq!(i18n.locale(), Some(&"en-US".parse().unwrap()));
+    }
+
+    thread_local! {
+        static RENDERED: RefCell<Vec<String>> = RefCell::new(Vec::new());
+        static I18N: RefCell<Option<I18n>> = RefCell::new(None);
+    }
+
+    #[test]
+    fn switching_locales_rerenders_consumers() {
+        fn app(cx: Scope) -> Element {
+            use_init_i18n(cx, config);
+
+            // the provider can use the translations itself
+            let i18n = use_i18n(cx);
+            I18N.with(|cell| *cell.borrow_mut() = Some(i18n.clone()));
+            let greeting = crate::t!(i18n, "greeting", name: "Dioxus");
+            RENDERED.with(|rendered| rendered.borrow_mut().push(greeting));
+            None
+        }
+
+        let mut dom = VirtualDom::new(app);
+        _ = dom.rebuild();
+
+        let i18n = I18N.with(|cell| cell.borrow().clone()).unwrap();
+        i18n.set_locale("fr".parse().unwrap());
+        dom.process_events();
+        _ = dom.render_immediate();
+
+        RENDERED.with(|rendered| {
+            assert_eq!(*rendered.borrow(), ["Hello, Dioxus!", "Bonjour, Dioxus !"])
+        });
+    }
+}
@@ -1,55 +1,256 @@
+// Touch and scroll listeners are passive unless a handler asks to prevent the default, so they never hold up scrolling
+const PASSIVE_EVENTS = ["scroll", "wheel", "touchstart", "touchmove", "touchend", "touchcancel"];
+
+function prevents_default(element, event_name) {
+  const prevent = element.getAttribute("dioxus-prevent-default");
+  return prevent === "on" + event_name || prevent === event_name;
+}
+
+// Gestures aren't events of the browser, so they're recognized from the pointer events of the element that listens
+// for them and dispatched on it. The thresholds match the ones documented in dioxus-html.
+const GESTURE_EVENTS = ["swipe", "pinch", "longpress"];
+const PRESS_TOLERANCE = 10;
+const LONG_PRESS_DELAY = 500;
+const SWIPE_DISTANCE = 30;
+const SWIPE_VELOCITY = 0.3;
+
+class GestureRecognizer {
+  constructor(element) {
+    this.element = element;
+    // the gesture listeners of the element, which share the recognizer
+    this.count = 0;
+    this.pointers = new Map();
+    this.pinch = null;
+    // a pointer that was part of a pinch or a long press doesn't swipe once it's released
+    this.consumed = false;
+    this.pressTimer = null;
+    this.onPointer = (event) => this.handle(event);
+    for (const type of ["pointerdown", "pointermove", "pointerup", "pointercancel", "pointerleave"]) {
+      element.addEventListener(type, this.onPointer, { passive: true });
+    }
+  }
+
+  detach() {
+    for (const type of ["pointerdown", "pointermove", "pointerup", "pointercancel", "pointerleave"]) {
+      this.element.removeEventListener(type, this.onPointer);
+    }
+    clearTimeout(this.pressTimer);
+  }
+
+  dispatch(name, detail) {
+    this.element.dispatchEvent(new CustomEvent(name, { detail }));
+  }
+
+  cancelPress() {
+    clearTimeout(this.pressTimer);
+    this.pressTimer = null;
+  }
+
+  pinchDetail(phase) {
+    const [a, b] = [...this.pointers.values()];
+    const distance = Math.hypot(a.x - b.x, a.y - b.y);
+    this.pinch.scale = this.pinch.distance > 0 ? distance / this.pinch.distance : 1;
+    return { phase, scale: this.pinch.scale, center_x: (a.x + b.x) / 2, center_y: (a.y + b.y) / 2 };
+  }
+
+  handle(event) {
+    const pointer = this.pointers.get(event.pointerId);
+    switch (event.type) {
+      case "pointerdown": {
+        const down = { startX: event.clientX, startY: event.clientY, x: event.clientX, y: event.clientY };
+        down.start = event.timeStamp;
+        down.type = event.pointerType;
+        this.pointers.set(event.pointerId, down);
+        this.cancelPress();
+        if (this.pointers.size === 1) {
+          this.consumed = false;
+          this.pressTimer = setTimeout(() => {
+            this.pressTimer = null;
+            this.consumed = true;
+            this.dispatch("longpress", { client_x: down.x, client_y: down.y, pointer_type: down.type });
+          }, LONG_PRESS_DELAY);
+        } else if (this.pointers.size === 2) {
+          const [a, b] = [...this.pointers.values()];
+          this.consumed = true;
+          this.pinch = { distance: Math.hypot(a.x - b.x, a.y - b.y), scale: 1 };
+          this.dispatch("pinch", this.pinchDetail("start"));
+        }
+        break;
+      }
+      case "pointermove": {
+        if (pointer === undefined) {
+          return;
+        }
+        pointer.x = event.clientX;
+        pointer.y = event.clientY;
+        if (Math.hypot(pointer.x - pointer.startX, pointer.y - pointer.startY) > PRESS_TOLERANCE) {
+          this.cancelPress();
+        }
+        if (this.pinch !== null && this.pointers.size === 2) {
+          this.dispatch("pinch", this.pinchDetail("move"));
+        }
+        break;
+      }
+      default: {
+        // the pointer was released, cancelled or left the element
+        if (pointer === undefined) {
+          return;
+        }
+        this.cancelPress();
+        if (this.pinch !== null && this.pointers.size === 2) {
+          this.dispatch("pinch", this.pinchDetail("end"));
+          this.pinch = null;
+        }
+        this.pointers.delete(event.pointerId);
+        if (event.type !== "pointerup" || this.consumed || this.pointers.size > 0) {
+          return;
+        }
+        const delta_x = event.clientX - pointer.startX;
+        const delta_y = event.clientY - pointer.startY;
+        const distance = Math.hypot(delta_x, delta_y);
+        const duration = Math.max(event.timeStamp - pointer.start, 1);
+        const velocity = distance / duration;
+        if (distance < SWIPE_DISTANCE || velocity < SWIPE_VELOCITY) {
+          return;
+        }
+        let direction;
+        if (Math.abs(delta_x) >= Math.abs(delta_y)) {
+          direction = delta_x < 0 ? "left" : "right";
+        } else {
+          direction = delta_y < 0 ? "up" : "down";
+        }
+        this.dispatch("swipe", { direction, delta_x, delta_y, velocity, duration, pointer_type: pointer.type });
+      }
+    }
+  }
+}
+
 class ListenerMap {
   constructor(root) {
-    // bubbling events can listen at the root element
+    // bubbling events listen at the root element, which is only non-passive while a listener needs it to be
     this.global = {};
-    // non bubbling events listen at the element the listener was created at
+    // the listeners of every element, by the id of the element
     this.local = {};
+    // the gesture recognizers of the elements that listen for gestures, by the id of the element
+    this.gestures = {};
     this.root = root;
   }
 
-  create(event_name, element, handler, bubbles) {
+  create(event_name, element, handler, bubbles, passive) {
+    const id = element.getAttribute("data-dioxus-id");
+    if (!this.local[id]) {
+      this.local[id] = {};
+    }
+    const listener = { bubbles, handler, passive, active: this.needs_active(element, event_name, passive) };
+    this.local[id][event_name] = listener;
+    if (GESTURE_EVENTS.includes(event_name)) {
+      this.gestures[id] ??= new GestureRecognizer(element);
+      this.gestures[id].count++;
+    }
+
     if (bubbles) {
       if (this.global[event_name] === undefined) {
-        this.global[event_name] = {};
-        this.global[event_name].active = 1;
-        this.global[event_name].callback = handler;
-        this.root.addEventListener(event_name, handler);
-      } else {
-        this.global[event_name].active++;
+        this.global[event_name] = { count: 0, active: 0, callback: handler, passive: null };
       }
-    }
-    else {
-      const id = element.getAttribute("data-dioxus-id");
-      if (!this.local[id]) {
-        this.local[id] = {};
+      const global = this.global[event_name];
+      global.count++;
+      if (listener.active) {
+        global.active++;
       }
-      this.local[id][event_name] = handler;
-      element.addEventListener(event_name, handler);
+      this.sync_global(event_name);
+    } else {
+      // non bubbling events never reach the root, so they listen at the element itself
+      element.addEventListener(event_name, handler, { passive: !listener.active });
     }
   }
 
-  remove(element, event_name, bubbles) {
-    if (bubbles) {
-      this.global[event_name].active--;
-      if (this.global[event_name].active === 0) {
-        this.root.removeEventListener(event_name, this.global[event_name].callback);
-        delete this.global[event_name];
-      }
-    }
-    else {
-      const id = element.getAttribute("data-dioxus-id");
+  // Returns whether the element still has listeners
+  remove(element, event_name) {
+    const id = element.getAttribute("data-dioxus-id");
+    const listener = this.local[id]?.[event_name];
+    if (listener !== undefined) {
       delete this.local[id][event_name];
-      if (this.local[id].length === 0) {
-        delete this.local[id];
-      }
-      element.removeEventListener(event_name, handler);
+      this.detach(element, event_name, listener);
+    }
+    if (this.local[id] !== undefined && Object.keys(this.local[id]).length === 0) {
+      delete this.local[id];
     }
+    return this.local[id] !== undefined;
   }
 
   removeAllNonBubbling(element) {
     const id = element.getAttribute("data-dioxus-id");
+    for (const event_name in this.local[id] ?? {}) {
+      this.detach(element, event_name, this.local[id][event_name]);
+    }
     delete this.local[id];
   }
+
+  // The prevent default attribute changed, so the listeners of the element might have to change modes
+  update_prevent_default(element) {
+    const id = element.getAttribute("data-dioxus-id");
+    for (const event_name in this.local[id] ?? {}) {
+      const listener = this.local[id][event_name];
+      const active = this.needs_active(element, event_name, listener.passive);
+      if (active === listener.active) {
+        continue;
+      }
+      listener.active = active;
+      if (listener.bubbles) {
+        this.global[event_name].active += active ? 1 : -1;
+        this.sync_global(event_name);
+      } else {
+        element.removeEventListener(event_name, listener.handler);
+        element.addEventListener(event_name, listener.handler, { passive: !active });
+      }
+    }
+  }
+
+  // Passive listeners promised to never prevent the default, so the attribute doesn't change them
+  needs_active(element, event_name, passive) {
+    if (passive) {
+      return false;
+    }
+    return !PASSIVE_EVENTS.includes(event_name) || prevents_default(element, event_name);
+  }
+
+  detach(element, event_name, listener) {
+    const id = element.getAttribute("data-dioxus-id");
+    const gestures = this.gestures[id];
+    if (gestures !== undefined && GESTURE_EVENTS.includes(event_name) && --gestures.count === 0) {
+      gestures.detach();
+      delete this.gestures[id];
+    }
+    if (listener.bubbles) {
+      const global = this.global[event_name];
+      global.count--;
+      if (listener.active) {
+        global.active--;
+      }
+      if (global.count === 0) {
+        this.root.removeEventListener(event_name, global.callback);
+        delete this.global[event_name];
+      } else {
+        this.sync_global(event_name);
+      }
+    } else {
+      element.removeEventListener(event_name, listener.handler);
+    }
+  }
+
+  // Listen at the root in the mode the listeners need, switching modes by listening again
+  sync_global(event_name) {
+    const global = this.global[event_name];
+    const passive = global.active === 0;
+    if (global.passive === passive) {
+      return;
+    }
+    if (global.passive !== null) {
+      this.root.removeEventListener(event_name, global.callback);
+    }
+    this.root.addEventListener(event_name, global.callback, { passive });
+    global.passive = passive;
+  }
 }
 
 class Interpreter {
@@ -61,6 +262,24 @@ class Interpreter {
     this.handlers = {};
     this.templates = {};
     this.lastNodeWasText = false;
+    this.dropTarget = null;
+    // send events before their files and stream the files after them, for renderers that read files as they arrive
+    this.streamFiles = false;
+    // the values the renderer gave form elements, which they go back to when the app doesn't accept an edit to them
+    this.controlInputs = false;
+    this.controlled = new WeakMap();
+    // the targets of the input events the renderer hasn't answered yet, in the order they were sent
+    this.awaitingControl = [];
+    this.inflight = new WeakMap();
+    // the events that wait for the next animation frame, where only the last of each type on an element is sent
+    this.deferredEvents = new Set();
+    this.pendingEvents = new Map();
+    this.pendingFrame = null;
+    // input events are skipped while an input method editor is composing text, so deliver the committed text once
+    // the composition ends
+    root.addEventListener("compositionend", (event) => {
+      event.target.dispatchEvent(new Event("input", { bubbles: true }));
+    });
   }
   top() {
     return this.stack[this.stack.length - 1];
@@ -74,6 +293,176 @@ class Interpreter {
   SetNode(id, node) {
     this.nodes[id] = node;
   }
+  GetClientRect(id) {
+    const node = this.nodes[id];
+    if (!node) {
+      return;
+    }
+    const rect = node.getBoundingClientRect();
+    return {
+      x: rect.x,
+      y: rect.y,
+      width: rect.width,
+      height: rect.height,
+    };
+  }
+  // Outline the nodes of a component for an inspector, or take the outline away when there are no nodes
+  Highlight(ids) {
+    let overlay = document.getElementById("dioxus-inspector-highlight");
+    const rects = ids
+      .map((id) => this.nodes[id])
+      .filter((node) => node)
+      .map((node) => {
+        if (node.getBoundingClientRect) {
+          return node.getBoundingClientRect();
+        }
+        // text nodes only have a size through a range around them
+        const range = document.createRange();
+        range.selectNode(node);
+        return range.getBoundingClientRect();
+      })
+      .filter((rect) => rect.width > 0 || rect.height > 0);
+    if (rects.length === 0) {
+      if (overlay) {
+        overlay.remove();
+      }
+      return;
+    }
+    if (!overlay) {
+      overlay = document.createElement("div");
+      overlay.id = "dioxus-inspector-highlight";
+      overlay.style.cssText =
+        "position: fixed; pointer-events: none; z-index: 2147483647; " +
+        "background: rgba(66, 133, 244, 0.25); outline: 2px solid rgb(66, 133, 244);";
+      document.body.appendChild(overlay);
+    }
+    const left = Math.min(...rects.map((rect) => rect.left));
+    const top = Math.min(...rects.map((rect) => rect.top));
+    const right = Math.max(...rects.map((rect) => rect.right));
+    const bottom = Math.max(...rects.map((rect) => rect.bottom));
+    overlay.style.left = left + "px";
+    overlay.style.top = top + "px";
+    overlay.style.width = right - left + "px";
+    overlay.style.height = bottom - top + "px";
+  }
+  ScrollTo(id, behavior) {
+    const node = this.nodes[id];
+    if (!node) {
+      return false;
+    }
+    node.scrollIntoView({
+      behavior: behavior,
+    });
+    return true;
+  }
+  SetScrollPosition(id, top, left) {
+    const node = this.nodes[id];
+    if (!node) {
+      return false;
+    }
+    node.scrollTop = top;
+    node.scrollLeft = left;
+    return true;
+  }
+  SetFocus(id, focus) {
+    const node = this.nodes[id];
+    if (!node) {
+      return false;
+    }
+    if (focus) {
+      node.focus();
+    } else {
+      node.blur();
+    }
+    return true;
+  }
+  // Files dragged over a native window are reported with where the cursor is, so the drag events are fired at the
+  // element under it like a browser would
+  DispatchFileDrop(kind, x, y) {
+    const previous = this.dropTarget;
+    const target = kind === "cancel" ? null : document.elementFromPoint(x, y) ?? this.root;
+    const fire = (element, type) =>
+      element.dispatchEvent(
+        new DragEvent(type, { bubbles: true, cancelable: true, clientX: x, clientY: y })
+      );
+    if (previous && previous !== target) {
+      fire(previous, "dragleave");
+    }
+    if (target && target !== previous && kind === "hover") {
+      fire(target, "dragenter");
+    }
+    if (target) {
+      fire(target, kind === "drop" ? "drop" : "dragover");
+    }
+    this.dropTarget = kind === "hover" ? target : null;
+  }
+  // Make the elements the app added to the head match what it has now, keeping the ones that didn't change so their
+  // styles aren't reloaded and their scripts aren't run again
+  UpdateHead(elements) {
+    if (this.initialTitle === undefined) {
+      this.initialTitle = document.title;
+    }
+    const managed = new Map();
+    for (const node of document.head.querySelectorAll("[data-dioxus-head]")) {
+      managed.set(node.getAttribute("data-dioxus-head"), node);
+    }
+    let title = this.initialTitle;
+    for (const { tag, key, attributes, content } of elements) {
+      if (tag === "title") {
+        title = content;
+        continue;
+      }
+      const node = document.createElement(tag);
+      for (const [name, value] of attributes) {
+        node.setAttribute(name, value);
+      }
+      node.setAttribute("data-dioxus-head", key);
+      if (content) {
+        node.textContent = content;
+      }
+      const existing = managed.get(key);
+      managed.delete(key);
+      if (!existing) {
+        document.head.appendChild(node);
+      } else if (!existing.isEqualNode(node)) {
+        existing.replaceWith(node);
+      }
+    }
+    for (const node of managed.values()) {
+      node.remove();
+    }
+    if (document.title !== title) {
+      document.title = title;
+    }
+  }
+  // Make the listeners of use_document_listener and use_window_listener match the ones the app has now. Their events
+  // are reported by the id of the listener, since they don't happen on an element of the app.
+  SetGlobalListeners(listeners) {
+    if (this.globalListeners === undefined) {
+      this.globalListeners = new Map();
+    }
+    const wanted = new Set();
+    for (const { id, target, name } of listeners) {
+      wanted.add(id);
+      if (this.globalListeners.has(id)) {
+        continue;
+      }
+      const node = target === "window" ? window : document;
+      const handler = (event) => {
+        window.ipc.postMessage(
+          serializeIpcMessage("global_event", { id, name, data: serialize_event(event) })
+        );
+      };
+      node.addEventListener(name, handler);
+      this.globalListeners.set(id, { node, name, handler });
+    }
+    for (const [id, { node, name, handler }] of this.globalListeners) {
+      if (!wanted.has(id)) {
+        node.removeEventListener(name, handler);
+        this.globalListeners.delete(id);
+      }
+    }
+  }
   PushRoot(root) {
     const node = this.nodes[root];
     this.stack.push(node);
@@ -130,15 +519,17 @@ class Interpreter {
     this.stack.push(el);
     this.nodes[root] = el;
   }
-  NewEventListener(event_name, root, bubbles, handler) {
+  NewEventListener(event_name, root, bubbles, handler, passive = false) {
     const element = this.nodes[root];
     element.setAttribute("data-dioxus-id", `${root}`);
-    this.listeners.create(event_name, element, handler, bubbles);
+    this.listeners.create(event_name, element, handler, bubbles, passive);
   }
-  RemoveEventListener(root, event_name, bubbles) {
+  RemoveEventListener(root, event_name) {
     const element = this.nodes[root];
-    element.removeAttribute(`data-dioxus-id`);
-    this.listeners.remove(element, event_name, bubbles);
+    // other listeners of the element still need its id
+    if (!this.listeners.remove(element, event_name)) {
+      element.removeAttribute(`data-dioxus-id`);
+    }
   }
   SetText(root, text) {
     this.nodes[root].textContent = text;
@@ -150,6 +541,11 @@ class Interpreter {
     else {
       const node = this.nodes[id];
       this.SetAttributeInner(node, field, value, ns);
+      if (ns == null && (field === "value" || field === "checked") && is_form_control(node)) {
+        const values = this.controlled.get(node) ?? {};
+        values[field] = value;
+        this.controlled.set(node, values);
+      }
     }
   }
   SetAttributeInner(node, field, value, ns) {
@@ -166,7 +562,7 @@ class Interpreter {
       switch (name) {
         case "value":
           if (value !== node.value) {
-            node.value = value;
+            set_value(node, value);
           }
           break;
         case "checked":
@@ -185,12 +581,18 @@ class Interpreter {
           } else {
             node.setAttribute(name, value);
           }
+          if (name === "dioxus-prevent-default") {
+            this.listeners.update_prevent_default(node);
+          }
       }
     }
   }
   RemoveAttribute(root, field, ns) {
     const name = field;
     const node = this.nodes[root];
+    if (ns == null) {
+      delete this.controlled.get(node)?.[name];
+    }
     if (ns == "style") {
       node.style.removeProperty(name);
     } else if (ns !== null || ns !== undefined) {
@@ -206,14 +608,97 @@ class Interpreter {
     } else {
       node.removeAttribute(name);
     }
+    if (name === "dioxus-prevent-default") {
+      this.listeners.update_prevent_default(node);
+    }
+  }
+  SetDeferredEvents(names) {
+    this.deferredEvents = new Set(names);
+  }
+  // Send an event to the renderer. Deferred events replace the one of the same type on the same element that's still
+  // waiting, and every other event sends the waiting ones ahead of it so the renderer sees them in order.
+  SendEvent(name, id, send) {
+    if (this.deferredEvents.has(name)) {
+      const key = `${name}:${id}`;
+      this.pendingEvents.delete(key);
+      this.pendingEvents.set(key, send);
+      if (this.pendingFrame === null) {
+        this.pendingFrame = requestAnimationFrame(() => this.FlushEvents());
+      }
+    } else {
+      this.FlushEvents();
+      send();
+    }
+  }
+  FlushEvents() {
+    if (this.pendingFrame !== null) {
+      cancelAnimationFrame(this.pendingFrame);
+      this.pendingFrame = null;
+    }
+    const pending = [...this.pendingEvents.values()];
+    this.pendingEvents.clear();
+    for (const send of pending) {
+      send();
+    }
+  }
+  // The renderer handled the oldest input event it hasn't answered, so its target goes back to the value the app gave
+  // it, unless the app changed the value. Targets of newer events are left alone until those are handled too, so the
+  // app doesn't undo typing it hasn't seen yet.
+  RestoreControlled() {
+    const target = this.awaitingControl.shift();
+    if (target == null) {
+      return;
+    }
+    const inflight = (this.inflight.get(target) ?? 1) - 1;
+    if (inflight > 0) {
+      this.inflight.set(target, inflight);
+      return;
+    }
+    this.inflight.delete(target);
+
+    restore_controlled(target, this.controlled);
+    // checking a radio button unchecks the others in its group
+    if (target.type === "radio" && target.name) {
+      const group = document.querySelectorAll(`input[type="radio"][name="${CSS.escape(target.name)}"]`);
+      for (const radio of group) {
+        restore_controlled(radio, this.controlled);
+      }
+    }
   }
   handleEdits(edits) {
     for (let template of edits.templates) {
       this.SaveTemplate(template);
     }
 
+    // Moving a node takes it out of the document for a moment, which blurs it and resets its scroll offsets
+    const focused = save_focus();
+    const scrolled = new Map();
+    const created = [];
+
     for (let edit of edits.edits) {
+      if (edit.type === "PushRoot") {
+        // the node hasn't moved yet, so its offsets are still there to read
+        const node = this.nodes[edit.id];
+        for (const element of [node, node?.parentElement]) {
+          if (element && (element.scrollTop || element.scrollLeft) && !scrolled.has(element)) {
+            scrolled.set(element, [element.scrollTop, element.scrollLeft]);
+          }
+        }
+      }
       this.handleEdit(edit);
+      if (edit.type === "LoadTemplate" || (edit.type === "SetAttribute" && edit.name === "autofocus")) {
+        created.push(this.nodes[edit.id]);
+      }
+    }
+
+    for (const [element, [top, left]] of scrolled) {
+      element.scrollTop = top;
+      element.scrollLeft = left;
+    }
+
+    // Like react, elements created with `autofocus` take the focus. Otherwise the element that had it keeps it.
+    if (!focus_autofocus(created)) {
+      restore_focus(focused);
     }
   }
 
@@ -351,24 +836,25 @@ class Interpreter {
         // this handler is only provided on desktop implementations since this
         // method is not used by the web implementation
         let handler = (event) => {
-          let target = event.target;
+          // the text isn't final until the composition ends
+          if (event.type === "input" && event.isComposing) {
+            return;
+          }
+          // non bubbling events listen at the element itself, which might not be where the event started
+          let target = bubbles ? event.target : event.currentTarget;
           if (target != null) {
             let realId = target.getAttribute(`data-dioxus-id`);
             let shouldPreventDefault = target.getAttribute(
               `dioxus-prevent-default`
             );
 
+            // the renderer follows the link once the listeners had their chance to prevent it
+            let href = null;
             if (event.type === "click") {
-              // todo call prevent default if it's the right type of event
               if (shouldPreventDefault !== `onclick`) {
                 if (target.tagName === "A") {
                   event.preventDefault();
-                  const href = target.getAttribute("href");
-                  if (href !== "" && href !== null && href !== undefined) {
-                    window.ipc.postMessage(
-                      serializeIpcMessage("browser_open", { href })
-                    );
-                  }
+                  href = target.getAttribute("href") || null;
                 }
               }
 
@@ -381,6 +867,12 @@ class Interpreter {
             while (realId == null) {
               // we've reached the root we don't want to send an event
               if (target.parentElement === null) {
+                // but nothing the app rendered holds the link, so nothing can prevent it either
+                if (href !== null) {
+                  window.ipc.postMessage(
+                    serializeIpcMessage("browser_open", { href })
+                  );
+                }
                 return;
               }
 
@@ -410,17 +902,30 @@ class Interpreter {
                 let element = target.elements[x];
                 let name = element.getAttribute("name");
                 if (name != null) {
-                  if (element.getAttribute("type") === "checkbox") {
-                    // @ts-ignore
-                    contents.values[name] = element.checked ? "true" : "false";
-                  } else if (element.getAttribute("type") === "radio") {
+                  let field = contents.values[name] ?? [];
+                  contents.values[name] = field;
+                  let type = element.getAttribute("type");
+                  if (type === "checkbox" && element.hasAttribute("value")) {
+                    // like html, a group of checkboxes has the values of the checked ones
+                    if (element.checked) {
+                      field.push(element.value);
+                    }
+                  } else if (type === "checkbox") {
+                    field.push(element.checked ? "true" : "false");
+                  } else if (type === "radio") {
                     if (element.checked) {
-                      contents.values[name] = element.value;
+                      field.push(element.value);
+                    }
+                  } else if (type === "file") {
+                    for (let file of element.files) {
+                      field.push(file.name);
+                    }
+                  } else if (element.tagName === "SELECT") {
+                    for (let option of element.selectedOptions) {
+                      field.push(option.value);
                     }
                   } else {
-                    // @ts-ignore
-                    contents.values[name] =
-                      element.value ?? element.textContent;
+                    field.push(element.value ?? element.textContent);
                   }
                 }
               }
@@ -429,17 +934,36 @@ class Interpreter {
             if (realId === null) {
               return;
             }
-            window.ipc.postMessage(
-              serializeIpcMessage("user_event", {
-                name: edit.name,
-                element: parseInt(realId),
-                data: contents,
-                bubbles,
-              })
-            );
+            let message = serializeIpcMessage("user_event", {
+              name: edit.name,
+              element: parseInt(realId),
+              data: contents,
+              bubbles,
+              href,
+            });
+            // the files have to be read here, so they're sent ahead of the event
+            let files = event_files(event, target);
+            let eventTarget = event.target;
+            this.SendEvent(edit.name, realId, () => {
+              // the renderer answers every input event it's sent, so the ones merged into a later event aren't counted
+              if (this.controlInputs && (edit.name === "input" || edit.name === "change")) {
+                const controlled = this.controlled.has(eventTarget) ? eventTarget : null;
+                this.awaitingControl.push(controlled);
+                if (controlled !== null) {
+                  this.inflight.set(controlled, (this.inflight.get(controlled) ?? 0) + 1);
+                }
+              }
+              if (files.length === 0) {
+                window.ipc.postMessage(message);
+              } else if (this.streamFiles) {
+                stream_files(files, message);
+              } else {
+                send_files(files).then(() => window.ipc.postMessage(message));
+              }
+            });
           }
         };
-        this.NewEventListener(edit.name, edit.id, bubbles, handler);
+        this.NewEventListener(edit.name, edit.id, bubbles, handler, edit.passive ?? false);
         break;
     }
   }
@@ -475,6 +999,7 @@ function serialize_event(event) {
         repeat,
         which,
         code,
+        isComposing,
       } = event;
       return {
         char_code: charCode,
@@ -488,6 +1013,7 @@ function serialize_event(event) {
         repeat: repeat,
         which: which,
         code,
+        is_composing: isComposing,
       };
     }
     case "focus":
@@ -643,17 +1169,31 @@ function serialize_event(event) {
     case "touchstart": {
       const { altKey, ctrlKey, metaKey, shiftKey } = event;
       return {
-        // changed_touches: event.changedTouches,
-        // target_touches: event.targetTouches,
-        // touches: event.touches,
         alt_key: altKey,
         ctrl_key: ctrlKey,
         meta_key: metaKey,
         shift_key: shiftKey,
+        touches: serialize_touches(event.touches),
+        changed_touches: serialize_touches(event.changedTouches),
+        target_touches: serialize_touches(event.targetTouches),
       };
     }
     case "scroll": {
-      return {};
+      // The document scrolls with its scrolling element, which is the one that has the offsets
+      const target =
+        event.target instanceof Document ? event.target.scrollingElement : event.target;
+      if (!target) {
+        return {};
+      }
+      const { scrollTop, scrollLeft, scrollHeight, scrollWidth, clientHeight, clientWidth } = target;
+      return {
+        scroll_top: scrollTop,
+        scroll_left: scrollLeft,
+        scroll_height: scrollHeight,
+        scroll_width: scrollWidth,
+        client_height: clientHeight,
+        client_width: clientWidth,
+      };
     }
     case "wheel": {
       const { deltaX, deltaY, deltaZ, deltaMode } = event;
@@ -705,16 +1245,233 @@ function serialize_event(event) {
     case "timeupdate":
     case "volumechange":
     case "waiting": {
-      return {};
+      const { currentTime, duration, volume, muted, paused, ended, playbackRate } =
+        event.target;
+      return {
+        current_time: currentTime,
+        // NaN and Infinity don't survive JSON, and mean the duration isn't known
+        duration: Number.isFinite(duration) ? duration : null,
+        volume: volume,
+        muted: muted,
+        paused: paused,
+        ended: ended,
+        playback_rate: playbackRate,
+      };
     }
     case "toggle": {
       return {};
     }
+    case "swipe":
+    case "pinch":
+    case "longpress": {
+      // the data of gestures is already in the shape of the data in dioxus-html
+      return event.detail;
+    }
     default: {
+      // custom events, like the ones web components dispatch, carry their data in detail
+      if (event instanceof CustomEvent) {
+        return { detail: JSON.stringify(event.detail) ?? "" };
+      }
       return {};
     }
   }
 }
+// The element with the focus, with the selection of its text
+function is_form_control(node) {
+  return node instanceof HTMLInputElement || node instanceof HTMLTextAreaElement || node instanceof HTMLSelectElement;
+}
+
+function restore_controlled(node, controlled) {
+  const values = controlled.get(node);
+  if (values === undefined) {
+    return;
+  }
+  if ("value" in values && node.value !== values.value) {
+    set_value(node, values.value);
+  }
+  if ("checked" in values) {
+    node.checked = values.checked === "true";
+  }
+}
+
+// Set the value of a text field without moving the caret of the user to the end
+//
+// The text before and after the part that changed stays where it was, so a caret in it keeps its place relative to that
+// text. A caret inside of the changed part goes to the end of the new text there.
+function set_value(node, value) {
+  if (node !== document.activeElement || node.selectionStart === undefined || node.selectionStart === null) {
+    node.value = value;
+    return;
+  }
+  const old = node.value;
+  const [start, end, direction] = [node.selectionStart, node.selectionEnd, node.selectionDirection];
+  node.value = value;
+
+  let prefix = 0;
+  while (prefix < old.length && prefix < value.length && old[prefix] === value[prefix]) {
+    prefix++;
+  }
+  let suffix = 0;
+  while (
+    suffix < old.length - prefix &&
+    suffix < value.length - prefix &&
+    old[old.length - 1 - suffix] === value[value.length - 1 - suffix]
+  ) {
+    suffix++;
+  }
+  const map = (position) => {
+    if (position <= prefix) {
+      return position;
+    }
+    if (position >= old.length - suffix) {
+      return position - old.length + value.length;
+    }
+    return value.length - suffix;
+  };
+  try {
+    node.setSelectionRange(map(start), map(end), direction ?? undefined);
+  } catch {
+    // some input types, like email, don't have a selection
+  }
+}
+
+function save_focus() {
+  const element = document.activeElement;
+  if (element === null || element === document.body) {
+    return null;
+  }
+  let selection = null;
+  try {
+    if (element.selectionStart !== undefined && element.selectionStart !== null) {
+      selection = [element.selectionStart, element.selectionEnd];
+    }
+  } catch {
+    // inputs like checkboxes don't have a selection
+  }
+  return { element, selection };
+}
+
+// Give the focus back if a move took it, as long as the element is still in the document
+function restore_focus(focused) {
+  if (focused === null || document.activeElement === focused.element || !focused.element.isConnected) {
+    return;
+  }
+  focused.element.focus();
+  if (focused.selection !== null) {
+    focused.element.setSelectionRange(...focused.selection);
+  }
+}
+
+// Focus the first new element with the autofocus attribute, returning whether there was one
+function focus_autofocus(created) {
+  for (const node of created) {
+    if (!(node instanceof Element)) {
+      continue;
+    }
+    const element = node.hasAttribute("autofocus") ? node : node.querySelector("[autofocus]");
+    if (element !== null && element.isConnected) {
+      element.focus();
+      return true;
+    }
+  }
+  return false;
+}
+
+function serialize_touches(list) {
+  return Array.from(list, (touch) => ({
+    identifier: touch.identifier,
+    screen_x: touch.screenX,
+    screen_y: touch.screenY,
+    client_x: touch.clientX,
+    client_y: touch.clientY,
+    page_x: touch.pageX,
+    page_y: touch.pageY,
+    radius_x: touch.radiusX,
+    radius_y: touch.radiusY,
+    rotation_angle: touch.rotationAngle,
+    force: touch.force,
+  }));
+}
+
+// The files that go along with an event: the dropped files, or the files picked in a file input or a form
+function event_files(event, target) {
+  if (event.type === "drop") {
+    return Array.from(event.dataTransfer?.files ?? []);
+  }
+  if (!["change", "input", "submit"].includes(event.type)) {
+    return [];
+  }
+  if (event.target.tagName === "INPUT" && event.target.type === "file") {
+    return Array.from(event.target.files ?? []);
+  }
+  if (target.tagName === "FORM") {
+    let files = [];
+    for (let element of target.elements) {
+      if (element.tagName === "INPUT" && element.type === "file") {
+        files.push(...(element.files ?? []));
+      }
+    }
+    return files;
+  }
+  return [];
+}
+
+const FILE_CHUNK_SIZE = 49152;
+
+// Read every file first and then send all the chunks at once, so the chunks of
+// different events don't get mixed up
+async function send_files(files) {
+  let buffers = await Promise.all(files.map((file) => file.arrayBuffer()));
+  files.forEach((file, idx) => {
+    let bytes = new Uint8Array(buffers[idx]);
+    // every file is sent in at least one chunk, even if it's empty
+    let start = 0;
+    do {
+      let chunk = bytes.subarray(start, start + FILE_CHUNK_SIZE);
+      window.ipc.postMessage(
+        serializeIpcMessage("file_chunk", { name: file.name, data: encode_chunk(chunk) })
+      );
+      start += FILE_CHUNK_SIZE;
+    } while (start < bytes.length);
+  });
+}
+
+function encode_chunk(chunk) {
+  let binary = "";
+  for (let byte of chunk) {
+    binary += String.fromCharCode(byte);
+  }
+  return btoa(binary);
+}
+
+let next_upload = 0;
+
+// Announce the files, send the event, and then send the files a chunk at a
+// time as they're read, so a large file doesn't hold up its event
+async function stream_files(files, message) {
+  let upload = next_upload++;
+  for (let file of files) {
+    window.ipc.postMessage(
+      serializeIpcMessage("file_start", { upload, name: file.name, size: file.size })
+    );
+  }
+  window.ipc.postMessage(message);
+  for (let file of files) {
+    let start = 0;
+    while (start < file.size) {
+      let chunk = await file.slice(start, start + FILE_CHUNK_SIZE).arrayBuffer();
+      window.ipc.postMessage(
+        serializeIpcMessage("file_data", {
+          upload,
+          name: file.name,
+          data: encode_chunk(new Uint8Array(chunk)),
+        })
+      );
+      start += FILE_CHUNK_SIZE;
+    }
+  }
+}
+
 function serializeIpcMessage(method, params = {}) {
   return JSON.stringify({ method, params });
 }
@@ -917,7 +1674,16 @@ function event_bubbles(event) {
       return true;
     case "toggle":
       return true;
+    case "mounted":
+      return false;
+    case "load":
+      return false;
+    case "swipe":
+    case "pinch":
+    case "longpress":
+      return false;
   }
 
-  return true;
+  // custom events, like the ones web components dispatch, don't bubble unless they ask to
+  return false;
 }
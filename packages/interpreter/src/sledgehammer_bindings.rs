@@ -5,56 +5,331 @@ use web_sys::Node;
 #[bindgen]
 mod js {
     const JS: &str = r#"
+    // Touch and scroll listeners are passive unless a handler asks to prevent the default, so they never hold up scrolling
+    const PASSIVE_EVENTS = ["scroll", "wheel", "touchstart", "touchmove", "touchend", "touchcancel"];
+
+    function prevents_default(element, event_name) {
+        const prevent = element.getAttribute("dioxus-prevent-default");
+        return prevent === "on" + event_name || prevent === event_name;
+    }
+
+    // Gestures aren't events of the browser, so they're recognized from the pointer events of the element that listens
+    // for them and dispatched on it. The thresholds match the ones documented in dioxus-html.
+    const GESTURE_EVENTS = ["swipe", "pinch", "longpress"];
+    const PRESS_TOLERANCE = 10;
+    const LONG_PRESS_DELAY = 500;
+    const SWIPE_DISTANCE = 30;
+    const SWIPE_VELOCITY = 0.3;
+
+    class GestureRecognizer {
+        constructor(element) {
+            this.element = element;
+            // the gesture listeners of the element, which share the recognizer
+            this.count = 0;
+            this.pointers = new Map();
+            this.pinch = null;
+            // a pointer that was part of a pinch or a long press doesn't swipe once it's released
+            this.consumed = false;
+            this.pressTimer = null;
+            this.onPointer = (event) => this.handle(event);
+            for (const type of ["pointerdown", "pointermove", "pointerup", "pointercancel", "pointerleave"]) {
+                element.addEventListener(type, this.onPointer, { passive: true });
+            }
+        }
+
+        detach() {
+            for (const type of ["pointerdown", "pointermove", "pointerup", "pointercancel", "pointerleave"]) {
+                this.element.removeEventListener(type, this.onPointer);
+            }
+            clearTimeout(this.pressTimer);
+        }
+
+        dispatch(name, detail) {
+            this.element.dispatchEvent(new CustomEvent(name, { detail }));
+        }
+
+        cancelPress() {
+            clearTimeout(this.pressTimer);
+            this.pressTimer = null;
+        }
+
+        pinchDetail(phase) {
+            const [a, b] = [...this.pointers.values()];
+            const distance = Math.hypot(a.x - b.x, a.y - b.y);
+            this.pinch.scale = this.pinch.distance > 0 ? distance / this.pinch.distance : 1;
+            return { phase, scale: this.pinch.scale, center_x: (a.x + b.x) / 2, center_y: (a.y + b.y) / 2 };
+        }
+
+        handle(event) {
+            const pointer = this.pointers.get(event.pointerId);
+            switch (event.type) {
+                case "pointerdown": {
+                    const down = { startX: event.clientX, startY: event.clientY, x: event.clientX, y: event.clientY };
+                    down.start = event.timeStamp;
+                    down.type = event.pointerType;
+                    this.pointers.set(event.pointerId, down);
+                    this.cancelPress();
+                    if (this.pointers.size === 1) {
+                        this.consumed = false;
+                        this.pressTimer = setTimeout(() => {
+                            this.pressTimer = null;
+                            this.consumed = true;
+                            this.dispatch("longpress", { client_x: down.x, client_y: down.y, pointer_type: down.type });
+                        }, LONG_PRESS_DELAY);
+                    } else if (this.pointers.size === 2) {
+                        const [a, b] = [...this.pointers.values()];
+                        this.consumed = true;
+                        this.pinch = { distance: Math.hypot(a.x - b.x, a.y - b.y), scale: 1 };
+                        this.dispatch("pinch", this.pinchDetail("start"));
+                    }
+                    break;
+                }
+                case "pointermove": {
+                    if (pointer === undefined) {
+                        return;
+                    }
+                    pointer.x = event.clientX;
+                    pointer.y = event.clientY;
+                    if (Math.hypot(pointer.x - pointer.startX, pointer.y - pointer.startY) > PRESS_TOLERANCE) {
+                        this.cancelPress();
+                    }
+                    if (this.pinch !== null && this.pointers.size === 2) {
+                        this.dispatch("pinch", this.pinchDetail("move"));
+                    }
+                    break;
+                }
+                default: {
+                    // the pointer was released, cancelled or left the element
+                    if (pointer === undefined) {
+                        return;
+                    }
+                    this.cancelPress();
+                    if (this.pinch !== null && this.pointers.size === 2) {
+                        this.dispatch("pinch", this.pinchDetail("end"));
+                        this.pinch = null;
+                    }
+                    this.pointers.delete(event.pointerId);
+                    if (event.type !== "pointerup" || this.consumed || this.pointers.size > 0) {
+                        return;
+                    }
+                    const delta_x = event.clientX - pointer.startX;
+                    const delta_y = event.clientY - pointer.startY;
+                    const distance = Math.hypot(delta_x, delta_y);
+                    const duration = Math.max(event.timeStamp - pointer.start, 1);
+                    const velocity = distance / duration;
+                    if (distance < SWIPE_DISTANCE || velocity < SWIPE_VELOCITY) {
+                        return;
+                    }
+                    let direction;
+                    if (Math.abs(delta_x) >= Math.abs(delta_y)) {
+                        direction = delta_x < 0 ? "left" : "right";
+                    } else {
+                        direction = delta_y < 0 ? "up" : "down";
+                    }
+                    this.dispatch("swipe", { direction, delta_x, delta_y, velocity, duration, pointer_type: pointer.type });
+                }
+            }
+        }
+    }
+
+    // Elements with the shadow root attribute render their children in a shadow root, which adopts the css of their
+    // shadow styles attribute
+    const SHADOW_ROOT = "dioxus-shadow-root";
+    const SHADOW_STYLES = "dioxus-shadow-styles";
+    // Events that bubble without leaving the shadow root they're dispatched in, so shadow roots listen for them too
+    const CONTAINED_EVENTS = ["change", "submit", "reset", "select"];
+
+    // the shadow roots the interpreter attached, by their host
+    const shadows = new WeakMap();
+    // every stylesheet by its css, so the shadow roots with the same styles share it
+    const stylesheets = new Map();
+
+    // The node the children of a node are in, which is the shadow root of shadow hosts
+    function container(node) {
+        return shadows.get(node) ?? node;
+    }
+
+    function AdoptStyles(shadow, css) {
+        if (!css) {
+            shadow.adoptedStyleSheets = [];
+            return;
+        }
+        let sheet = stylesheets.get(css);
+        if (sheet === undefined) {
+            sheet = new CSSStyleSheet();
+            sheet.replaceSync(css);
+            stylesheets.set(css, sheet);
+        }
+        shadow.adoptedStyleSheets = [sheet];
+    }
+
+    function AttachShadow(host) {
+        if (shadows.has(host)) {
+            return;
+        }
+        const shadow = host.attachShadow({ mode: "open" });
+        shadow.append(...host.childNodes);
+        shadows.set(host, shadow);
+        AdoptStyles(shadow, host.getAttribute(SHADOW_STYLES));
+        listeners.listen_in(shadow);
+    }
+
+    // Attach the shadow roots of a node that was just created and its children, before any path walks into them
+    function AttachShadows(node) {
+        if (node.nodeType !== Node.ELEMENT_NODE) {
+            return;
+        }
+        // the hosts are found up front, since the children of a host move out of reach of querySelectorAll
+        const hosts = [...node.querySelectorAll(`[${SHADOW_ROOT}]`)];
+        if (node.hasAttribute(SHADOW_ROOT)) {
+            hosts.unshift(node);
+        }
+        for (const host of hosts) {
+            AttachShadow(host);
+        }
+    }
+
     class ListenerMap {
         constructor(root) {
-            // bubbling events can listen at the root element
+            // bubbling events listen at the root element, which is only non-passive while a listener needs it to be
             this.global = {};
-            // non bubbling events listen at the element the listener was created at
+            // the listeners of every element, by the id of the element
             this.local = {};
+            // the gesture recognizers of the elements that listen for gestures, by the id of the element
+            this.gestures = {};
             this.root = null;
             this.handler = null;
         }
 
-        create(event_name, element, bubbles) {
+        create(event_name, element, bubbles, passive) {
+            const id = element.getAttribute("data-dioxus-id");
+            if (!this.local[id]) {
+                this.local[id] = {};
+            }
+            const listener = { bubbles, passive, active: this.needs_active(element, event_name, passive) };
+            this.local[id][event_name] = listener;
+            if (GESTURE_EVENTS.includes(event_name)) {
+                this.gestures[id] ??= new GestureRecognizer(element);
+                this.gestures[id].count++;
+            }
+
             if (bubbles) {
                 if (this.global[event_name] === undefined) {
-                    this.global[event_name] = {};
-                    this.global[event_name].active = 1;
-                    this.root.addEventListener(event_name, this.handler);
-                } else {
-                    this.global[event_name].active++;
+                    this.global[event_name] = { count: 0, active: 0, passive: null };
+                }
+                const global = this.global[event_name];
+                global.count++;
+                if (listener.active) {
+                    global.active++;
                 }
+                this.sync_global(event_name);
             }
             else {
-                const id = element.getAttribute("data-dioxus-id");
-                if (!this.local[id]) {
-                    this.local[id] = {};
+                // non bubbling events never reach the root, so they listen at the element itself
+                element.addEventListener(event_name, this.handler, { passive: !listener.active });
+            }
+        }
+
+        // Returns whether the element still has listeners
+        remove(element, event_name) {
+            const id = element.getAttribute("data-dioxus-id");
+            const listener = this.local[id]?.[event_name];
+            if (listener !== undefined) {
+                delete this.local[id][event_name];
+                this.detach(element, event_name, listener);
+            }
+            if (this.local[id] !== undefined && Object.keys(this.local[id]).length === 0) {
+                delete this.local[id];
+            }
+            return this.local[id] !== undefined;
+        }
+
+        removeAllNonBubbling(element) {
+            const id = element.getAttribute("data-dioxus-id");
+            for (const event_name in this.local[id] ?? {}) {
+                this.detach(element, event_name, this.local[id][event_name]);
+            }
+            delete this.local[id];
+        }
+
+        // The prevent default attribute changed, so the listeners of the element might have to change modes
+        update_prevent_default(element) {
+            const id = element.getAttribute("data-dioxus-id");
+            for (const event_name in this.local[id] ?? {}) {
+                const listener = this.local[id][event_name];
+                const active = this.needs_active(element, event_name, listener.passive);
+                if (active === listener.active) {
+                    continue;
+                }
+                listener.active = active;
+                if (listener.bubbles) {
+                    this.global[event_name].active += active ? 1 : -1;
+                    this.sync_global(event_name);
+                }
+                else {
+                    element.removeEventListener(event_name, this.handler);
+                    element.addEventListener(event_name, this.handler, { passive: !active });
                 }
-                element.addEventListener(event_name, this.handler);
             }
         }
 
-        remove(element, event_name, bubbles) {
-            if (bubbles) {
-                this.global[event_name].active--;
-                if (this.global[event_name].active === 0) {
-                    this.root.removeEventListener(event_name, this.global[event_name].callback);
+        // Passive listeners promised to never prevent the default, so the attribute doesn't change them
+        needs_active(element, event_name, passive) {
+            if (passive) {
+                return false;
+            }
+            return !PASSIVE_EVENTS.includes(event_name) || prevents_default(element, event_name);
+        }
+
+        detach(element, event_name, listener) {
+            const id = element.getAttribute("data-dioxus-id");
+            const gestures = this.gestures[id];
+            if (gestures !== undefined && GESTURE_EVENTS.includes(event_name) && --gestures.count === 0) {
+                gestures.detach();
+                delete this.gestures[id];
+            }
+            if (listener.bubbles) {
+                const global = this.global[event_name];
+                global.count--;
+                if (listener.active) {
+                    global.active--;
+                }
+                if (global.count === 0) {
+                    this.root.removeEventListener(event_name, this.handler);
                     delete this.global[event_name];
                 }
+                else {
+                    this.sync_global(event_name);
+                }
             }
             else {
-                const id = element.getAttribute("data-dioxus-id");
-                delete this.local[id][event_name];
-                if (this.local[id].length === 0) {
-                    delete this.local[id];
-                }
                 element.removeEventListener(event_name, this.handler);
             }
         }
 
-        removeAllNonBubbling(element) {
-            const id = element.getAttribute("data-dioxus-id");
-            delete this.local[id];
+        // Bubbling events that don't leave a shadow root never reach the root, so the shadow root hands them over
+        listen_in(shadow) {
+            for (const event_name of CONTAINED_EVENTS) {
+                shadow.addEventListener(event_name, (event) => {
+                    if (!event.composed && this.global[event_name] !== undefined) {
+                        this.handler(event);
+                    }
+                });
+            }
+        }
+
+        // Listen at the root in the mode the listeners need, switching modes by listening again
+        sync_global(event_name) {
+            const global = this.global[event_name];
+            const passive = global.active === 0;
+            if (global.passive === passive) {
+                return;
+            }
+            if (global.passive !== null) {
+                this.root.removeEventListener(event_name, this.handler);
+            }
+            this.root.addEventListener(event_name, this.handler, { passive });
+            global.passive = passive;
         }
     }
     function SetAttributeInner(node, field, value, ns) {
@@ -71,7 +346,7 @@ mod js {
             switch (name) {
                 case "value":
                     if (value !== node.value) {
-                        node.value = value;
+                        set_value(node, value);
                     }
                     break;
                 case "checked":
@@ -90,7 +365,56 @@ mod js {
                     } else {
                         node.setAttribute(name, value);
                     }
+                    if (name === "dioxus-prevent-default") {
+                        listeners.update_prevent_default(node);
+                    }
+                    if (name === SHADOW_ROOT) {
+                        AttachShadow(node);
+                    }
+                    if (name === SHADOW_STYLES && shadows.has(node)) {
+                        AdoptStyles(shadows.get(node), value);
+                    }
+            }
+        }
+    }
+    // Set the value of a text field without moving the caret of the user to the end
+    //
+    // The text before and after the part that changed stays where it was, so a caret in it keeps its place relative to that
+    // text. A caret inside of the changed part goes to the end of the new text there.
+    function set_value(node, value) {
+        if (node !== document.activeElement || node.selectionStart === undefined || node.selectionStart === null) {
+            node.value = value;
+            return;
+        }
+        const old = node.value;
+        const [start, end, direction] = [node.selectionStart, node.selectionEnd, node.selectionDirection];
+        node.value = value;
+
+        let prefix = 0;
+        while (prefix < old.length && prefix < value.length && old[prefix] === value[prefix]) {
+            prefix++;
+        }
+        let suffix = 0;
+        while (
+            suffix < old.length - prefix &&
+            suffix < value.length - prefix &&
+            old[old.length - 1 - suffix] === value[value.length - 1 - suffix]
+        ) {
+            suffix++;
+        }
+        const map = (position) => {
+            if (position <= prefix) {
+                return position;
             }
+            if (position >= old.length - suffix) {
+                return position - old.length + value.length;
+            }
+            return value.length - suffix;
+        };
+        try {
+            node.setSelectionRange(map(start), map(end), direction ?? undefined);
+        } catch {
+            // some input types, like email, don't have a selection
         }
     }
     function LoadChild(ptr, len) {
@@ -99,7 +423,7 @@ mod js {
         ptr_end = ptr + len;
         for (; ptr < ptr_end; ptr++) {
             end = m.getUint8(ptr);
-            for (node = node.firstChild; end > 0; end--) {
+            for (node = container(node).firstChild; end > 0; end--) {
                 node = node.nextSibling;
             }
         }
@@ -109,21 +433,42 @@ mod js {
     let nodes = [];
     let stack = [];
     const templates = {};
+    // whether each root of every template has shadow hosts in it
+    const shadow_templates = {};
     let node, els, end, ptr_end, k;
     export function save_template(nodes, tmpl_id) {
         templates[tmpl_id] = nodes;
+        shadow_templates[tmpl_id] = nodes.map(
+            (node) => node.nodeType === Node.ELEMENT_NODE && (node.hasAttribute(SHADOW_ROOT) || node.querySelector(`[${SHADOW_ROOT}]`) !== null)
+        );
     }
     export function set_node(id, node) {
         nodes[id] = node;
     }
+    export function get_node(id) {
+        return nodes[id];
+    }
     export function initilize(root, handler) {
         listeners.handler = handler;
         nodes = [root];
         stack = [root];
         listeners.root = root;
+        root.addEventListener("compositionend", (event) => {
+            event.composedPath()[0].dispatchEvent(new Event("input", { bubbles: true, composed: true }));
+        });
+    }
+    // Attach a shadow root to an element of the page for an app to mount in, returning the shadow root
+    export function attach_shadow(host, css, contained) {
+        const shadow = host.attachShadow({ mode: "open" });
+        AdoptStyles(shadow, css);
+        // apps that listen at the document only see the events that leave the shadow root
+        if (contained) {
+            listeners.listen_in(shadow);
+        }
+        return shadow;
     }
     function AppendChildren(id, many){
-        root = nodes[id];
+        root = container(nodes[id]);
         els = stack.splice(stack.length-many);
         for (k = 0; k < many; k++) {
             root.appendChild(els[k]);
@@ -166,8 +511,14 @@ mod js {
         #[wasm_bindgen]
         pub fn set_node(id: u32, node: Node);
 
+        #[wasm_bindgen]
+        pub fn get_node(id: u32) -> Node;
+
         #[wasm_bindgen]
         pub fn initilize(root: Node, handler: &Function);
+
+        #[wasm_bindgen]
+        pub fn attach_shadow(host: Node, css: &str, contained: bool) -> Node;
     }
 
     fn mount_to_root() {
@@ -203,11 +554,11 @@ mod js {
     fn create_placeholder(id: u32) {
         "{node = document.createElement('pre'); node.hidden = true; stack.push(node); nodes[$id$] = node;}"
     }
-    fn new_event_listener(event_name: &str<u8, evt>, id: u32, bubbles: u8) {
-        r#"node = nodes[id]; if(node.listening){node.listening += 1;}else{node.listening = 1;} node.setAttribute('data-dioxus-id', `\${id}`); listeners.create($event_name$, node, $bubbles$);"#
+    fn new_event_listener(event_name: &str<u8, evt>, id: u32, bubbles: u8, passive: u8) {
+        r#"node = nodes[id]; if(node.listening){node.listening += 1;}else{node.listening = 1;} node.setAttribute('data-dioxus-id', `\${id}`); listeners.create($event_name$, node, $bubbles$, $passive$);"#
     }
-    fn remove_event_listener(event_name: &str<u8, evt>, id: u32, bubbles: u8) {
-        "{node = nodes[$id$]; node.listening -= 1; node.removeAttribute('data-dioxus-id'); listeners.remove(node, $event_name$, $bubbles$);}"
+    fn remove_event_listener(event_name: &str<u8, evt>, id: u32) {
+        "{node = nodes[$id$]; node.listening -= 1; if (!listeners.remove(node, $event_name$)) { node.removeAttribute('data-dioxus-id'); }}"
     }
     fn set_text(id: u32, text: &str) {
         "{nodes[$id$].textContent = $text$;}"
@@ -232,6 +583,12 @@ mod js {
             node.innerHTML = "";
         } else {
             node.removeAttribute(name);
+        }
+        if (name === "dioxus-prevent-default") {
+            listeners.update_prevent_default(node);
+        }
+        if (name === SHADOW_STYLES && shadows.has(node)) {
+            AdoptStyles(shadows.get(node), "");
         }}"#
     }
     fn assign_id(ptr: u32, len: u8, id: u32) {
@@ -254,6 +611,6 @@ mod js {
         "{els = stack.splice(stack.length - $n$); node = LoadChild($ptr$, $len$); node.replaceWith(...els);}"
     }
     fn load_template(tmpl_id: u32, index: u32, id: u32) {
-        "{node = templates[$tmpl_id$][$index$].cloneNode(true); nodes[$id$] = node; stack.push(node);}"
+        "{k = $tmpl_id$; end = $index$; node = templates[k][end].cloneNode(true); if (shadow_templates[k][end]) { AttachShadows(node); } nodes[$id$] = node; stack.push(node);}"
     }
 }
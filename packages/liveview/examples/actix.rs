@@ -0,0 +1,58 @@
+use actix_web::{web, App, HttpResponse, HttpServer};
+use dioxus::prelude::*;
+use dioxus_liveview::{ActixUpgrade, LiveViewPool};
+use std::net::SocketAddr;
+
+fn app(cx: Scope) -> Element {
+    let mut num = use_state(cx, || 0);
+
+    cx.render(rsx! {
+        div {
+            "hello actix! {num}"
+            button { onclick: move |_| num += 1, "Increment" }
+        }
+    })
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    pretty_env_logger::init();
+
+    let addr: SocketAddr = ([127, 0, 0, 1], 3030).into();
+
+    let view = web::Data::new(LiveViewPool::new());
+
+    println!("Listening on http://{}", addr);
+
+    HttpServer::new(move || {
+        App::new()
+            .app_data(view.clone())
+            .route("/", web::get().to(index))
+            .route("/ws", web::get().to(ws))
+    })
+    .bind(addr)?
+    .run()
+    .await
+}
+
+async fn index() -> HttpResponse {
+    let addr: SocketAddr = ([127, 0, 0, 1], 3030).into();
+    HttpResponse::Ok().content_type("text/html").body(format!(
+        r#"
+            <!DOCTYPE html>
+            <html>
+                <head> <title>Dioxus LiveView with Actix</title>  </head>
+                <body> <div id="main"></div> </body>
+                {glue}
+            </html>
+            "#,
+        glue = dioxus_liveview::interpreter_glue(&format!("ws://{addr}/ws"))
+    ))
+}
+
+async fn ws(
+    view: web::Data<LiveViewPool>,
+    ws: ActixUpgrade,
+) -> Result<HttpResponse, actix_web::Error> {
+    view.launch(ws, app)
+}
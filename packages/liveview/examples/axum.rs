@@ -1,5 +1,6 @@
-use axum::{extract::ws::WebSocketUpgrade, response::Html, routing::get, Router};
+use axum::{response::Html, routing::get, Router};
 use dioxus::prelude::*;
+use dioxus_liveview::LiveViewUpgrade;
 
 fn app(cx: Scope) -> Element {
     let mut num = use_state(cx, || 0);
@@ -39,11 +40,7 @@ async fn main() {
         )
         .route(
             "/ws",
-            get(move |ws: WebSocketUpgrade| async move {
-                ws.on_upgrade(move |socket| async move {
-                    _ = view.launch(dioxus_liveview::axum_socket(socket), app).await;
-                })
-            }),
+            get(move |ws: LiveViewUpgrade| async move { view.launch(ws, app) }),
         );
 
     println!("Listening on http://{}", addr);
@@ -1,5 +1,5 @@
 use dioxus::prelude::*;
-use dioxus_liveview::LiveViewPool;
+use dioxus_liveview::{LiveViewPool, SalvoUpgrade};
 use salvo::affix;
 use salvo::prelude::*;
 use std::net::SocketAddr;
@@ -58,9 +58,5 @@ async fn connect(
 ) -> Result<(), StatusError> {
     let view = depot.obtain::<Arc<LiveViewPool>>().unwrap().clone();
 
-    WebSocketUpgrade::new()
-        .upgrade(req, res, |ws| async move {
-            _ = view.launch(dioxus_liveview::salvo_socket(ws), app).await;
-        })
-        .await
+    view.launch(SalvoUpgrade::new(req, res), app).await
 }
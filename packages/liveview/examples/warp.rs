@@ -1,5 +1,4 @@
 use dioxus::prelude::*;
-use dioxus_liveview::adapters::warp_adapter::warp_socket;
 use dioxus_liveview::LiveViewPool;
 use std::net::SocketAddr;
 use warp::ws::Ws;
@@ -44,11 +43,7 @@ async fn main() {
     let ws = warp::path("ws")
         .and(warp::ws())
         .and(warp::any().map(move || pool.clone()))
-        .map(move |ws: Ws, pool: LiveViewPool| {
-            ws.on_upgrade(|ws| async move {
-                let _ = pool.launch(warp_socket(ws), app).await;
-            })
-        });
+        .map(move |ws: Ws, pool: LiveViewPool| pool.launch(ws, app));
 
     println!("Listening on http://{}", addr);
 
@@ -0,0 +1,152 @@
+use crate::{LiveViewConnection, LiveViewError, LiveViewPool, LiveViewSocket};
+use actix_web::{dev::Payload, web, FromRequest, HttpRequest, HttpResponse};
+use actix_ws::{Message, MessageStream, Session};
+use dioxus_core::{Element, Scope};
+use futures_channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use futures_util::future::LocalBoxFuture;
+use futures_util::{Sink, Stream, StreamExt};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Convert an actix websocket into a LiveViewSocket
+///
+/// The messages of an actix websocket can't leave the worker that accepted it, so they're pumped through channels by a
+/// task on that worker. This has to be called from a handler.
+pub fn actix_socket(session: Session, messages: MessageStream) -> impl LiveViewSocket {
+    let (to_app, from_client) = mpsc::unbounded();
+    let (to_client, from_app) = mpsc::unbounded();
+    actix_web::rt::spawn(pump(session, messages, to_app, from_app));
+
+    ActixSocket {
+        tx: to_client,
+        rx: from_client,
+    }
+}
+
+async fn pump(
+    mut session: Session,
+    mut messages: MessageStream,
+    to_app: UnboundedSender<Result<String, LiveViewError>>,
+    mut from_app: UnboundedReceiver<String>,
+) {
+    loop {
+        tokio::select! {
+            message = messages.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    if to_app.unbounded_send(Ok(text.to_string())).is_err() {
+                        break;
+                    }
+                }
+                Some(Ok(Message::Ping(bytes))) => {
+                    if session.pong(&bytes).await.is_err() {
+                        break;
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None => break,
+                Some(Ok(_)) => {}
+                Some(Err(_)) => {
+                    _ = to_app.unbounded_send(Err(LiveViewError::SendingFailed));
+                }
+            },
+
+            message = from_app.next() => match message {
+                Some(text) => {
+                    if session.text(text).await.is_err() {
+                        break;
+                    }
+                }
+                None => break,
+            },
+        }
+    }
+
+    // the app stops once the channel it reads from closes
+    _ = session.close(None).await;
+}
+
+struct ActixSocket {
+    tx: UnboundedSender<String>,
+    rx: UnboundedReceiver<Result<String, LiveViewError>>,
+}
+
+impl Stream for ActixSocket {
+    type Item = Result<String, LiveViewError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_next_unpin(cx)
+    }
+}
+
+impl Sink<String> for ActixSocket {
+    type Error = LiveViewError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.tx)
+            .poll_ready(cx)
+            .map_err(|_| LiveViewError::SendingFailed)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: String) -> Result<(), Self::Error> {
+        Pin::new(&mut self.tx)
+            .start_send(item)
+            .map_err(|_| LiveViewError::SendingFailed)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.tx)
+            .poll_flush(cx)
+            .map_err(|_| LiveViewError::SendingFailed)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.tx)
+            .poll_close(cx)
+            .map_err(|_| LiveViewError::SendingFailed)
+    }
+}
+
+/// An extractor for the websocket request of a LiveView app
+///
+/// ```rust, ignore
+/// async fn ws(view: web::Data<LiveViewPool>, ws: ActixUpgrade) -> Result<HttpResponse, actix_web::Error> {
+///     view.launch(ws, app)
+/// }
+/// ```
+pub struct ActixUpgrade {
+    request: HttpRequest,
+    body: web::Payload,
+}
+
+impl FromRequest for ActixUpgrade {
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let request = req.clone();
+        let body = web::Payload::from_request(req, payload);
+        Box::pin(async move {
+            Ok(Self {
+                request,
+                body: body.await?,
+            })
+        })
+    }
+}
+
+impl LiveViewConnection for ActixUpgrade {
+    type Response = Result<HttpResponse, actix_web::Error>;
+
+    fn upgrade<T: Send + 'static>(
+        self,
+        pool: LiveViewPool,
+        app: fn(Scope<T>) -> Element,
+        props: T,
+    ) -> Self::Response {
+        let (response, session, messages) = actix_ws::handle(&self.request, self.body)?;
+        let socket = actix_socket(session, messages);
+        actix_web::rt::spawn(async move {
+            _ = pool.launch_socket_with_props(socket, app, props).await;
+        });
+        Ok(response)
+    }
+}
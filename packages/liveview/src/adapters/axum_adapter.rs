@@ -1,5 +1,15 @@
-use crate::{LiveViewError, LiveViewSocket};
-use axum::extract::ws::{Message, WebSocket};
+use crate::{LiveViewConnection, LiveViewError, LiveViewPool, LiveViewSocket};
+use axum::{
+    async_trait,
+    extract::{
+        rejection::WebSocketUpgradeRejection,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        FromRequestParts,
+    },
+    http::request::Parts,
+    response::{IntoResponse, Response},
+};
+use dioxus_core::{Element, Scope};
 use futures_util::{SinkExt, StreamExt};
 
 /// Convert a warp websocket into a LiveViewSocket
@@ -21,3 +31,56 @@ fn transform_rx(message: Result<Message, axum::Error>) -> Result<String, LiveVie
 async fn transform_tx(message: String) -> Result<Message, axum::Error> {
     Ok(Message::Text(message))
 }
+
+/// An extractor for the websocket request of a LiveView app
+///
+/// ```rust, ignore
+/// let view = LiveViewPool::new();
+/// let router = Router::new().route(
+///     "/ws",
+///     get(move |ws: LiveViewUpgrade| async move { view.launch(ws, app) }),
+/// );
+/// ```
+pub struct LiveViewUpgrade(WebSocketUpgrade);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for LiveViewUpgrade {
+    type Rejection = WebSocketUpgradeRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        WebSocketUpgrade::from_request_parts(parts, state)
+            .await
+            .map(Self)
+    }
+}
+
+impl LiveViewConnection for LiveViewUpgrade {
+    type Response = Response;
+
+    fn upgrade<T: Send + 'static>(
+        self,
+        pool: LiveViewPool,
+        app: fn(Scope<T>) -> Element,
+        props: T,
+    ) -> Self::Response {
+        self.0.upgrade(pool, app, props)
+    }
+}
+
+impl LiveViewConnection for WebSocketUpgrade {
+    type Response = Response;
+
+    fn upgrade<T: Send + 'static>(
+        self,
+        pool: LiveViewPool,
+        app: fn(Scope<T>) -> Element,
+        props: T,
+    ) -> Self::Response {
+        self.on_upgrade(move |socket| async move {
+            _ = pool
+                .launch_socket_with_props(axum_socket(socket), app, props)
+                .await;
+        })
+        .into_response()
+    }
+}
@@ -1,7 +1,10 @@
-use futures_util::{SinkExt, StreamExt};
-use salvo::ws::{Message, WebSocket};
+use futures_util::{future::BoxFuture, SinkExt, StreamExt};
+use salvo::http::StatusError;
+use salvo::ws::{Message, WebSocket, WebSocketUpgrade};
+use salvo::{Request, Response};
 
-use crate::{LiveViewError, LiveViewSocket};
+use crate::{LiveViewConnection, LiveViewError, LiveViewPool, LiveViewSocket};
+use dioxus_core::{Element, Scope};
 
 /// Convert a salvo websocket into a LiveViewSocket
 ///
@@ -23,3 +26,44 @@ fn transform_rx(message: Result<Message, salvo::Error>) -> Result<String, LiveVi
 async fn transform_tx(message: String) -> Result<Message, salvo::Error> {
     Ok(Message::text(message))
 }
+
+/// The websocket request of a LiveView app in a salvo handler
+///
+/// ```rust, ignore
+/// #[handler]
+/// async fn connect(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<(), StatusError> {
+///     let view = depot.obtain::<Arc<LiveViewPool>>().unwrap().clone();
+///     view.launch(SalvoUpgrade::new(req, res), app).await
+/// }
+/// ```
+pub struct SalvoUpgrade<'a> {
+    req: &'a mut Request,
+    res: &'a mut Response,
+}
+
+impl<'a> SalvoUpgrade<'a> {
+    pub fn new(req: &'a mut Request, res: &'a mut Response) -> Self {
+        Self { req, res }
+    }
+}
+
+impl<'a> LiveViewConnection for SalvoUpgrade<'a> {
+    type Response = BoxFuture<'a, Result<(), StatusError>>;
+
+    fn upgrade<T: Send + 'static>(
+        self,
+        pool: LiveViewPool,
+        app: fn(Scope<T>) -> Element,
+        props: T,
+    ) -> Self::Response {
+        Box::pin(async move {
+            WebSocketUpgrade::new()
+                .upgrade(self.req, self.res, move |socket| async move {
+                    _ = pool
+                        .launch_socket_with_props(salvo_socket(socket), app, props)
+                        .await;
+                })
+                .await
+        })
+    }
+}
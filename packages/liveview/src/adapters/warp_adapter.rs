@@ -1,6 +1,8 @@
-use crate::{LiveViewError, LiveViewSocket};
+use crate::{LiveViewConnection, LiveViewError, LiveViewPool, LiveViewSocket};
+use dioxus_core::{Element, Scope};
 use futures_util::{SinkExt, StreamExt};
-use warp::ws::{Message, WebSocket};
+use warp::ws::{Message, WebSocket, Ws};
+use warp::Reply;
 
 /// Convert a warp websocket into a LiveViewSocket
 ///
@@ -26,3 +28,21 @@ fn transform_rx(message: Result<Message, warp::Error>) -> Result<String, LiveVie
 async fn transform_tx(message: String) -> Result<Message, warp::Error> {
     Ok(Message::text(message))
 }
+
+impl LiveViewConnection for Ws {
+    type Response = warp::reply::Response;
+
+    fn upgrade<T: Send + 'static>(
+        self,
+        pool: LiveViewPool,
+        app: fn(Scope<T>) -> Element,
+        props: T,
+    ) -> Self::Response {
+        self.on_upgrade(move |socket| async move {
+            _ = pool
+                .launch_socket_with_props(warp_socket(socket), app, props)
+                .await;
+        })
+        .into_response()
+    }
+}
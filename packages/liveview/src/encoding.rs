@@ -0,0 +1,723 @@
+use dioxus_core::{BorrowedAttributeValue, ElementId, Template, VirtualDom, WriteMutations};
+use std::collections::HashMap;
+use std::future::Future;
+use std::io::Write;
+
+/// How the edits of the app are encoded for the browser
+///
+/// The browser lists the encodings it understands when it connects, and the app uses the one the pool prefers if the
+/// browser understands it, or json if it doesn't.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    /// Every batch of edits is the json of its [`Mutations`](dioxus_core::Mutations), which is what the other
+    /// renderers of the interpreter read
+    Json,
+
+    /// Every batch of edits is a flat json array of opcodes and their arguments
+    ///
+    /// The names of the templates are only sent the first time they're loaded on a connection, so long lists of the same
+    /// template come down to a few numbers per item.
+    Compact,
+}
+
+impl Encoding {
+    /// The name of the encoding in the handshake of the browser
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Encoding::Json => "json",
+            Encoding::Compact => "compact",
+        }
+    }
+
+    /// Pick the encoding for a browser that understands `offered`
+    pub(crate) fn negotiate(self, offered: &[String]) -> Self {
+        match offered.iter().any(|name| name == self.as_str()) {
+            true => self,
+            false => Encoding::Json,
+        }
+    }
+}
+
+/// Turns the edits of the VirtualDom into the messages of one connection
+pub(crate) enum EditEncoder {
+    Json,
+    Compact(CompactEdits),
+}
+
+impl EditEncoder {
+    pub(crate) fn new(encoding: Encoding) -> Self {
+        match encoding {
+            Encoding::Json => EditEncoder::Json,
+            Encoding::Compact => EditEncoder::Compact(CompactEdits::default()),
+        }
+    }
+
+    /// The first render of the app
+    pub(crate) fn rebuild(&mut self, vdom: &mut VirtualDom) -> String {
+        match self {
+            EditEncoder::Json => serde_json::to_string(&vdom.rebuild()).unwrap(),
+            EditEncoder::Compact(edits) => {
+                vdom.rebuild_to(edits);
+                edits.finish()
+            }
+        }
+    }
+
+    /// Every element of an app that's already running, for a browser that starts out empty
+    pub(crate) fn rebuild_in_place(&mut self, vdom: &mut VirtualDom) -> String {
+        match self {
+            EditEncoder::Json => serde_json::to_string(&vdom.rebuild_in_place()).unwrap(),
+            EditEncoder::Compact(edits) => {
+                vdom.rebuild_in_place_to(edits);
+                edits.finish()
+            }
+        }
+    }
+
+    /// Whatever the app has ready to render by the deadline
+    pub(crate) async fn render(
+        &mut self,
+        vdom: &mut VirtualDom,
+        deadline: impl Future<Output = ()>,
+    ) -> String {
+        match self {
+            EditEncoder::Json => {
+                let edits = vdom.render_with_deadline(deadline).await;
+                serde_json::to_string(&edits).unwrap()
+            }
+            EditEncoder::Compact(edits) => {
+                vdom.render_with_deadline_to(deadline, edits).await;
+                edits.finish()
+            }
+        }
+    }
+}
+
+/// Writes the edits of the VirtualDom straight into the compact encoding, without collecting them first
+///
+/// A message is `[templates, ops]`. The templates are serialized like they are in
+/// [`Mutations`](dioxus_core::Mutations). Every op is its opcode followed by its arguments, in the order of the fields of
+/// the mutation. The name of a loaded template is a string the first time the connection sees it, and after that the
+/// index of the name among the ones sent so far.
+///
+/// | opcode | mutation              | arguments               |
+/// |--------|-----------------------|-------------------------|
+/// | 0      | `AppendChildren`      | id, m                   |
+/// | 1      | `AssignId`            | path, id                |
+/// | 2      | `CreatePlaceholder`   | id                      |
+/// | 3      | `CreateTextNode`      | value, id               |
+/// | 4      | `HydrateText`         | path, value, id         |
+/// | 5      | `LoadTemplate`        | name, index, id         |
+/// | 6      | `ReplaceWith`         | id, m                   |
+/// | 7      | `ReplacePlaceholder`  | path, m                 |
+/// | 8      | `InsertAfter`         | id, m                   |
+/// | 9      | `InsertBefore`        | id, m                   |
+/// | 10     | `SetAttribute`        | name, value, id, ns     |
+/// | 11     | `SetText`             | value, id               |
+/// | 12     | `NewEventListener`    | name, id, passive       |
+/// | 13     | `RemoveEventListener` | name, id                |
+/// | 14     | `Remove`              | id                      |
+/// | 15     | `PushRoot`            | id                      |
+#[derive(Default)]
+pub(crate) struct CompactEdits {
+    names: HashMap<&'static str, usize>,
+    templates: Vec<u8>,
+    ops: Vec<u8>,
+}
+
+impl CompactEdits {
+    /// Take the message of the edits written since the last one, keeping the buffers for the next
+    pub(crate) fn finish(&mut self) -> String {
+        let mut message = Vec::with_capacity(self.templates.len() + self.ops.len() + 6);
+        message.extend_from_slice(b"[[");
+        message.append(&mut self.templates);
+        message.extend_from_slice(b"],[");
+        message.append(&mut self.ops);
+        message.extend_from_slice(b"]]");
+
+        // everything in the buffers was written by serde_json or as ascii
+        String::from_utf8(message).unwrap()
+    }
+
+    fn op(&mut self, opcode: u8) {
+        if !self.ops.is_empty() {
+            self.ops.push(b',');
+        }
+        let _ = write!(self.ops, "{}", opcode);
+    }
+
+    fn number(&mut self, number: usize) {
+        let _ = write!(self.ops, ",{}", number);
+    }
+
+    fn id(&mut self, id: ElementId) {
+        self.number(id.0);
+    }
+
+    fn json(&mut self, value: &impl serde::Serialize) {
+        self.ops.push(b',');
+        // writing into a vec can only fail for values that can't be serialized, which these aren't
+        let _ = serde_json::to_writer(&mut self.ops, value);
+    }
+}
+
+impl WriteMutations<'_> for CompactEdits {
+    fn register_template(&mut self, template: Template<'static>) {
+        if !self.templates.is_empty() {
+            self.templates.push(b',');
+        }
+        let _ = serde_json::to_writer(&mut self.templates, &template);
+    }
+
+    fn append_children(&mut self, id: ElementId, m: usize) {
+        self.op(0);
+        self.id(id);
+        self.number(m);
+    }
+
+    fn assign_id(&mut self, path: &'static [u8], id: ElementId) {
+        self.op(1);
+        self.json(&path);
+        self.id(id);
+    }
+
+    fn create_placeholder(&mut self, id: ElementId) {
+        self.op(2);
+        self.id(id);
+    }
+
+    fn create_text_node(&mut self, value: &str, id: ElementId) {
+        self.op(3);
+        self.json(&value);
+        self.id(id);
+    }
+
+    fn hydrate_text(&mut self, path: &'static [u8], value: &str, id: ElementId) {
+        self.op(4);
+        self.json(&path);
+        self.json(&value);
+        self.id(id);
+    }
+
+    fn load_template(&mut self, name: &'static str, index: usize, id: ElementId) {
+        self.op(5);
+        match self.names.get(name).copied() {
+            Some(known) => self.number(known),
+            None => {
+                self.names.insert(name, self.names.len());
+                self.json(&name);
+            }
+        }
+        self.number(index);
+        self.id(id);
+    }
+
+    fn replace_with(&mut self, id: ElementId, m: usize) {
+        self.op(6);
+        self.id(id);
+        self.number(m);
+    }
+
+    fn replace_placeholder(&mut self, path: &'static [u8], m: usize) {
+        self.op(7);
+        self.json(&path);
+        self.number(m);
+    }
+
+    fn insert_after(&mut self, id: ElementId, m: usize) {
+        self.op(8);
+        self.id(id);
+        self.number(m);
+    }
+
+    fn insert_before(&mut self, id: ElementId, m: usize) {
+        self.op(9);
+        self.id(id);
+        self.number(m);
+    }
+
+    fn set_attribute(
+        &mut self,
+        name: &str,
+        ns: Option<&str>,
+        value: BorrowedAttributeValue,
+        id: ElementId,
+    ) {
+        self.op(10);
+        self.json(&name);
+        match value {
+            // any values only live in the VirtualDom, so the browser removes the attribute like it does for none
+            BorrowedAttributeValue::Any(_) => self.json(&()),
+            value => self.json(&value),
+        }
+        self.id(id);
+        self.json(&ns);
+    }
+
+    fn set_text(&mut self, value: &str, id: ElementId) {
+        self.op(11);
+        self.json(&value);
+        self.id(id);
+    }
+
+    fn new_event_listener(&mut self, name: &str, id: ElementId, passive: bool) {
+        self.op(12);
+        self.json(&name);
+        self.id(id);
+        self.json(&passive);
+    }
+
+    fn remove_event_listener(&mut self, name: &str, id: ElementId) {
+        self.op(13);
+        self.json(&name);
+        self.id(id);
+    }
+
+    fn remove(&mut self, id: ElementId) {
+        self.op(14);
+        self.id(id);
+    }
+
+    fn push_root(&mut self, id: ElementId) {
+        self.op(15);
+        self.id(id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::{AnyValue, Mutation, TemplateAttribute, TemplateNode};
+    use serde_json::Value;
+    use std::cell::RefCell;
+
+    static NESTED: Template<'static> = Template {
+        name: "nested",
+        roots: &[TemplateNode::Element {
+            tag: "div",
+            namespace: None,
+            attrs: &[
+                TemplateAttribute::Static {
+                    name: "class",
+                    value: "outer",
+                    namespace: None,
+                },
+                TemplateAttribute::Dynamic { id: 0 },
+            ],
+            children: &[
+                TemplateNode::Element {
+                    tag: "p",
+                    namespace: None,
+                    attrs: &[],
+                    children: &[
+                        TemplateNode::Text { text: "inner" },
+                        TemplateNode::DynamicText { id: 0 },
+                    ],
+                },
+                TemplateNode::Dynamic { id: 1 },
+            ],
+        }],
+        node_paths: &[&[0, 0, 1], &[0, 1]],
+        attr_paths: &[&[0]],
+    };
+
+    const ESCAPED: &str = "say \"hi\" \\ to\n\tthe ünïcode ✓ </script>";
+
+    fn leak(value: &str) -> &'static str {
+        Box::leak(value.to_string().into_boxed_str())
+    }
+
+    /// Reads the arguments of the ops back, the way the browser does
+    struct Reader {
+        ops: std::vec::IntoIter<Value>,
+    }
+
+    impl Reader {
+        fn value(&mut self) -> Value {
+            self.ops.next().expect("an op is missing arguments")
+        }
+
+        fn number(&mut self) -> usize {
+            self.value().as_u64().unwrap() as usize
+        }
+
+        fn id(&mut self) -> ElementId {
+            ElementId(self.number())
+        }
+
+        fn text(&mut self) -> &'static str {
+            leak(self.value().as_str().unwrap())
+        }
+
+        fn path(&mut self) -> &'static [u8] {
+            let path: Vec<u8> = serde_json::from_value(self.value()).unwrap();
+            Box::leak(path.into_boxed_slice())
+        }
+
+        fn attribute(&mut self) -> BorrowedAttributeValue<'static> {
+            match self.value() {
+                Value::String(value) => BorrowedAttributeValue::Text(leak(&value)),
+                Value::Bool(value) => BorrowedAttributeValue::Bool(value),
+                Value::Number(value) if value.is_i64() => {
+                    BorrowedAttributeValue::Int(value.as_i64().unwrap())
+                }
+                Value::Number(value) => BorrowedAttributeValue::Float(value.as_f64().unwrap()),
+                Value::Null => BorrowedAttributeValue::None,
+                value => panic!("{} is not an attribute value", value),
+            }
+        }
+    }
+
+    /// Read a message back into the templates and the mutations it was written from
+    ///
+    /// `names` are the names of the templates loaded in the messages read before, like the browser remembers them.
+    fn decode(
+        message: String,
+        names: &mut Vec<&'static str>,
+    ) -> (Vec<Template<'static>>, Vec<Mutation<'static>>) {
+        let (templates, ops): (Vec<Template<'static>>, Vec<Value>) =
+            serde_json::from_str(leak(&message)).unwrap();
+
+        let mut reader = Reader {
+            ops: ops.into_iter(),
+        };
+        let mut mutations = Vec::new();
+        while let Some(opcode) = reader.ops.next() {
+            let mutation = match opcode.as_u64().unwrap() {
+                0 => Mutation::AppendChildren {
+                    id: reader.id(),
+                    m: reader.number(),
+                },
+                1 => Mutation::AssignId {
+                    path: reader.path(),
+                    id: reader.id(),
+                },
+                2 => Mutation::CreatePlaceholder { id: reader.id() },
+                3 => Mutation::CreateTextNode {
+                    value: reader.text(),
+                    id: reader.id(),
+                },
+                4 => Mutation::HydrateText {
+                    path: reader.path(),
+                    value: reader.text(),
+                    id: reader.id(),
+                },
+                5 => {
+                    let name = match reader.value() {
+                        Value::String(name) => {
+                            names.push(leak(&name));
+                            names[names.len() - 1]
+                        }
+                        known => names[known.as_u64().unwrap() as usize],
+                    };
+                    Mutation::LoadTemplate {
+                        name,
+                        index: reader.number(),
+                        id: reader.id(),
+                    }
+                }
+                6 => Mutation::ReplaceWith {
+                    id: reader.id(),
+                    m: reader.number(),
+                },
+                7 => Mutation::ReplacePlaceholder {
+                    path: reader.path(),
+                    m: reader.number(),
+                },
+                8 => Mutation::InsertAfter {
+                    id: reader.id(),
+                    m: reader.number(),
+                },
+                9 => Mutation::InsertBefore {
+                    id: reader.id(),
+                    m: reader.number(),
+                },
+                10 => Mutation::SetAttribute {
+                    name: reader.text(),
+                    value: reader.attribute(),
+                    id: reader.id(),
+                    ns: reader.value().as_str().map(leak),
+                },
+                11 => Mutation::SetText {
+                    value: reader.text(),
+                    id: reader.id(),
+                },
+                12 => Mutation::NewEventListener {
+                    name: reader.text(),
+                    id: reader.id(),
+                    passive: reader.value().as_bool().unwrap(),
+                },
+                13 => Mutation::RemoveEventListener {
+                    name: reader.text(),
+                    id: reader.id(),
+                },
+                14 => Mutation::Remove { id: reader.id() },
+                15 => Mutation::PushRoot { id: reader.id() },
+                opcode => panic!("unknown opcode {}", opcode),
+            };
+            mutations.push(mutation);
+        }
+
+        (templates, mutations)
+    }
+
+    #[test]
+    fn every_opcode_round_trips() {
+        let any: RefCell<Box<dyn AnyValue>> = RefCell::new(Box::new(1u8));
+
+        let mut edits = CompactEdits::default();
+        edits.register_template(NESTED);
+        edits.append_children(ElementId(1), 2);
+        edits.assign_id(&[0, 1], ElementId(2));
+        edits.create_placeholder(ElementId(3));
+        edits.create_text_node("text", ElementId(4));
+        edits.hydrate_text(&[0, 0, 1], "hydrated", ElementId(5));
+        edits.load_template("nested", 0, ElementId(6));
+        edits.replace_with(ElementId(7), 1);
+        edits.replace_placeholder(&[0, 1], 3);
+        edits.insert_after(ElementId(8), 4);
+        edits.insert_before(ElementId(9), 5);
+        edits.set_attribute(
+            "class",
+            None,
+            BorrowedAttributeValue::Text("big"),
+            ElementId(10),
+        );
+        edits.set_attribute(
+            "opacity",
+            None,
+            BorrowedAttributeValue::Float(0.5),
+            ElementId(10),
+        );
+        edits.set_attribute(
+            "tabindex",
+            None,
+            BorrowedAttributeValue::Int(-1),
+            ElementId(10),
+        );
+        edits.set_attribute(
+            "hidden",
+            None,
+            BorrowedAttributeValue::Bool(true),
+            ElementId(10),
+        );
+        edits.set_attribute("title", None, BorrowedAttributeValue::None, ElementId(10));
+        edits.set_attribute(
+            "data",
+            None,
+            BorrowedAttributeValue::Any(std::cell::Ref::map(any.borrow(), |any| &**any)),
+            ElementId(10),
+        );
+        edits.set_attribute(
+            "color",
+            Some("style"),
+            BorrowedAttributeValue::Text("red"),
+            ElementId(10),
+        );
+        edits.set_text("set", ElementId(11));
+        edits.new_event_listener("click", ElementId(12), false);
+        edits.new_event_listener("scroll", ElementId(12), true);
+        edits.remove_event_listener("click", ElementId(12));
+        edits.remove(ElementId(13));
+        edits.push_root(ElementId(14));
+
+        let (templates, mutations) = decode(edits.finish(), &mut Vec::new());
+        assert_eq!(templates, [NESTED]);
+        assert_eq!(
+            mutations,
+            [
+                Mutation::AppendChildren {
+                    id: ElementId(1),
+                    m: 2
+                },
+                Mutation::AssignId {
+                    path: &[0, 1],
+                    id: ElementId(2)
+                },
+                Mutation::CreatePlaceholder { id: ElementId(3) },
+                Mutation::CreateTextNode {
+                    value: "text",
+                    id: ElementId(4)
+                },
+                Mutation::HydrateText {
+                    path: &[0, 0, 1],
+                    value: "hydrated",
+                    id: ElementId(5)
+                },
+                Mutation::LoadTemplate {
+                    name: "nested",
+                    index: 0,
+                    id: ElementId(6)
+                },
+                Mutation::ReplaceWith {
+                    id: ElementId(7),
+                    m: 1
+                },
+                Mutation::ReplacePlaceholder {
+                    path: &[0, 1],
+                    m: 3
+                },
+                Mutation::InsertAfter {
+                    id: ElementId(8),
+                    m: 4
+                },
+                Mutation::InsertBefore {
+                    id: ElementId(9),
+                    m: 5
+                },
+                Mutation::SetAttribute {
+                    name: "class",
+                    value: BorrowedAttributeValue::Text("big"),
+                    id: ElementId(10),
+                    ns: None,
+                },
+                Mutation::SetAttribute {
+                    name: "opacity",
+                    value: BorrowedAttributeValue::Float(0.5),
+                    id: ElementId(10),
+                    ns: None,
+                },
+                Mutation::SetAttribute {
+                    name: "tabindex",
+                    value: BorrowedAttributeValue::Int(-1),
+                    id: ElementId(10),
+                    ns: None,
+                },
+                Mutation::SetAttribute {
+                    name: "hidden",
+                    value: BorrowedAttributeValue::Bool(true),
+                    id: ElementId(10),
+                    ns: None,
+                },
+                Mutation::SetAttribute {
+                    name: "title",
+                    value: BorrowedAttributeValue::None,
+                    id: ElementId(10),
+                    ns: None,
+                },
+                // any values can't leave the VirtualDom, so the attribute is removed
+                Mutation::SetAttribute {
+                    name: "data",
+                    value: BorrowedAttributeValue::None,
+                    id: ElementId(10),
+                    ns: None,
+                },
+                Mutation::SetAttribute {
+                    name: "color",
+                    value: BorrowedAttributeValue::Text("red"),
+                    id: ElementId(10),
+                    ns: Some("style"),
+                },
+                Mutation::SetText {
+                    value: "set",
+                    id: ElementId(11)
+                },
+                Mutation::NewEventListener {
+                    name: "click",
+                    id: ElementId(12),
+                    passive: false
+                },
+                Mutation::NewEventListener {
+                    name: "scroll",
+                    id: ElementId(12),
+                    passive: true
+                },
+                Mutation::RemoveEventListener {
+                    name: "click",
+                    id: ElementId(12)
+                },
+                Mutation::Remove { id: ElementId(13) },
+                Mutation::PushRoot { id: ElementId(14) },
+            ]
+        );
+    }
+
+    #[test]
+    fn template_names_are_sent_once_per_connection() {
+        let mut edits = CompactEdits::default();
+        let mut names = Vec::new();
+
+        edits.load_template("first", 0, ElementId(1));
+        edits.load_template("first", 1, ElementId(2));
+        let message = edits.finish();
+        assert_eq!(message, r#"[[],[5,"first",0,1,5,0,1,2]]"#);
+        let (_, mutations) = decode(message, &mut names);
+        assert_eq!(
+            mutations,
+            [
+                Mutation::LoadTemplate {
+                    name: "first",
+                    index: 0,
+                    id: ElementId(1)
+                },
+                Mutation::LoadTemplate {
+                    name: "first",
+                    index: 1,
+                    id: ElementId(2)
+                },
+            ]
+        );
+
+        // the names sent in earlier messages are still known
+        edits.load_template("second", 0, ElementId(3));
+        edits.load_template("first", 0, ElementId(4));
+        let message = edits.finish();
+        assert_eq!(message, r#"[[],[5,"second",0,3,5,0,0,4]]"#);
+        let (_, mutations) = decode(message, &mut names);
+        assert_eq!(
+            mutations,
+            [
+                Mutation::LoadTemplate {
+                    name: "second",
+                    index: 0,
+                    id: ElementId(3)
+                },
+                Mutation::LoadTemplate {
+                    name: "first",
+                    index: 0,
+                    id: ElementId(4)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn text_and_attribute_values_are_escaped() {
+        let mut edits = CompactEdits::default();
+        edits.create_text_node(ESCAPED, ElementId(1));
+        edits.hydrate_text(&[0], ESCAPED, ElementId(2));
+        edits.set_text(ESCAPED, ElementId(1));
+        edits.set_attribute(
+            "title",
+            None,
+            BorrowedAttributeValue::Text(ESCAPED),
+            ElementId(2),
+        );
+
+        let (_, mutations) = decode(edits.finish(), &mut Vec::new());
+        assert_eq!(
+            mutations,
+            [
+                Mutation::CreateTextNode {
+                    value: ESCAPED,
+                    id: ElementId(1)
+                },
+                Mutation::HydrateText {
+                    path: &[0],
+                    value: ESCAPED,
+                    id: ElementId(2)
+                },
+                Mutation::SetText {
+                    value: ESCAPED,
+                    id: ElementId(1)
+                },
+                Mutation::SetAttribute {
+                    name: "title",
+                    value: BorrowedAttributeValue::Text(ESCAPED),
+                    id: ElementId(2),
+                    ns: None,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use dioxus_hooks::{EvalBackend, EvalChannel, EvalError, Evaluator};
+use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use serde_json::{json, Value};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+use std::task::{Context, Poll};
+
+/// Runs the scripts of [`use_eval`](dioxus_hooks::use_eval) in the browser on the other end of the socket
+pub(crate) struct LiveViewEval {
+    scripts: Rc<EvalRegistry>,
+}
+
+impl LiveViewEval {
+    pub(crate) fn new(scripts: Rc<EvalRegistry>) -> Self {
+        Self { scripts }
+    }
+}
+
+impl EvalBackend for LiveViewEval {
+    fn eval(&self, script: &str) -> Result<Rc<dyn Evaluator>, EvalError> {
+        self.scripts
+            .start(script)
+            .map(|evaluator| evaluator as Rc<dyn Evaluator>)
+    }
+}
+
+/// The scripts running in the browser, by the id the browser knows them by
+///
+/// The messages for the browser go out through the session, which sends them over whatever socket it's serving.
+pub(crate) struct EvalRegistry {
+    next_id: Cell<usize>,
+    running: RefCell<HashMap<usize, Weak<LiveViewEvaluator>>>,
+    messages: UnboundedSender<String>,
+}
+
+impl EvalRegistry {
+    pub(crate) fn new() -> (Self, UnboundedReceiver<String>) {
+        let (messages, rx) = unbounded();
+        let registry = Self {
+            next_id: Cell::new(0),
+            running: RefCell::new(HashMap::new()),
+            messages,
+        };
+        (registry, rx)
+    }
+
+    fn start(&self, script: &str) -> Result<Rc<LiveViewEvaluator>, EvalError> {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+
+        let message = json!({ "eval": { "id": id, "script": script } }).to_string();
+        self.messages
+            .unbounded_send(message)
+            .map_err(|_| EvalError::Unavailable)?;
+
+        let evaluator = Rc::new(LiveViewEvaluator {
+            id,
+            messages: self.messages.clone(),
+            channel: EvalChannel::default(),
+        });
+        self.running
+            .borrow_mut()
+            .insert(id, Rc::downgrade(&evaluator));
+        Ok(evaluator)
+    }
+
+    /// Route a message of a script to its evaluator, if it's still around
+    pub(crate) fn handle_message(&self, method: &str, params: &Value) {
+        let id = match params.get("id").and_then(Value::as_u64) {
+            Some(id) => id as usize,
+            None => return,
+        };
+
+        match method {
+            "eval_send" => {
+                let evaluator = self.running.borrow().get(&id).and_then(Weak::upgrade);
+                if let Some(evaluator) = evaluator {
+                    let data = params.get("data").cloned().unwrap_or(Value::Null);
+                    evaluator.channel.receive(data);
+                }
+            }
+            "eval_result" => {
+                let evaluator = self.running.borrow_mut().remove(&id);
+                if let Some(evaluator) = evaluator.as_ref().and_then(Weak::upgrade) {
+                    let result = match params.get("error") {
+                        Some(error) => Err(EvalError::Failed(
+                            error.as_str().unwrap_or_default().to_string(),
+                        )),
+                        None => Ok(params.get("ok").cloned().unwrap_or(Value::Null)),
+                    };
+                    evaluator.channel.finish(result);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Fail the scripts the last browser was running, which went away with its page
+    pub(crate) fn abandon(&self) {
+        let running = std::mem::take(&mut *self.running.borrow_mut());
+        for evaluator in running.values().filter_map(Weak::upgrade) {
+            evaluator.channel.finish(Err(EvalError::Failed(
+                "the browser lost its connection".to_string(),
+            )));
+        }
+    }
+}
+
+/// A script running in the browser
+pub(crate) struct LiveViewEvaluator {
+    id: usize,
+    messages: UnboundedSender<String>,
+    channel: EvalChannel,
+}
+
+impl Evaluator for LiveViewEvaluator {
+    fn send(&self, value: Value) -> Result<(), EvalError> {
+        if self.channel.is_finished() {
+            return Err(EvalError::Finished);
+        }
+
+        let message = json!({ "eval_send": { "id": self.id, "data": value } }).to_string();
+        self.messages
+            .unbounded_send(message)
+            .map_err(|_| EvalError::Unavailable)
+    }
+
+    fn poll_recv(&self, cx: &mut Context<'_>) -> Poll<Result<Value, EvalError>> {
+        self.channel.poll_recv(cx)
+    }
+
+    fn poll_join(&self, cx: &mut Context<'_>) -> Poll<Result<Value, EvalError>> {
+        self.channel.poll_join(cx)
+    }
+}
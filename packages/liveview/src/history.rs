@@ -0,0 +1,71 @@
+use dioxus_history::History;
+use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use std::cell::RefCell;
+
+/// The history of the browser on the other end of the socket
+///
+/// Liveview provides it to the app as a `Rc<dyn History>` context, which is what the router looks for. Navigating
+/// sends the change to the browser, and the browser sends its path back when it connects and when it moves through
+/// its history. Until it connects the path is `/`.
+pub struct LiveviewHistory {
+    current: RefCell<String>,
+    commands: UnboundedSender<HistoryCommand>,
+    callback: RefCell<Option<Box<dyn Fn()>>>,
+}
+
+/// A change to the history for the browser to make
+#[derive(serde::Serialize, Debug)]
+#[serde(tag = "method", rename_all = "lowercase")]
+pub(crate) enum HistoryCommand {
+    Push { path: String },
+    Replace { path: String },
+    Go { delta: isize },
+}
+
+impl LiveviewHistory {
+    pub(crate) fn new() -> (Self, UnboundedReceiver<HistoryCommand>) {
+        let (commands, rx) = unbounded();
+        let history = Self {
+            current: RefCell::new("/".to_string()),
+            commands,
+            callback: RefCell::new(None),
+        };
+        (history, rx)
+    }
+
+    /// Take the path the browser is at
+    pub(crate) fn follow(&self, path: String) {
+        self.current.replace(path);
+        if let Some(callback) = self.callback.borrow().as_ref() {
+            callback();
+        }
+    }
+}
+
+impl History for LiveviewHistory {
+    fn current_path(&self) -> String {
+        self.current.borrow().clone()
+    }
+
+    fn push(&self, path: &str) {
+        self.current.replace(path.to_string());
+        let _ = self.commands.unbounded_send(HistoryCommand::Push {
+            path: path.to_string(),
+        });
+    }
+
+    fn replace(&self, path: &str) {
+        self.current.replace(path.to_string());
+        let _ = self.commands.unbounded_send(HistoryCommand::Replace {
+            path: path.to_string(),
+        });
+    }
+
+    fn go(&self, delta: isize) {
+        let _ = self.commands.unbounded_send(HistoryCommand::Go { delta });
+    }
+
+    fn subscribe(&self, callback: Box<dyn Fn()>) {
+        self.callback.replace(Some(callback));
+    }
+}
@@ -14,13 +14,30 @@ pub mod adapters {
 
     #[cfg(feature = "salvo")]
     pub use salvo_adapter::*;
+
+    #[cfg(feature = "actix")]
+    pub mod actix_adapter;
+
+    #[cfg(feature = "actix")]
+    pub use actix_adapter::*;
 }
 
 pub use adapters::*;
 
+mod encoding;
+mod eval;
+mod history;
+mod listener;
 pub mod pool;
+mod session;
+mod throttle;
+mod upload;
+pub use dioxus_hooks::{use_eval, Eval, EvalError};
+pub use encoding::Encoding;
 use futures_util::{SinkExt, StreamExt};
+pub use history::LiveviewHistory;
 pub use pool::*;
+pub use throttle::EventThrottle;
 
 pub trait WebsocketTx: SinkExt<String, Error = LiveViewError> {}
 impl<T> WebsocketTx for T where T: SinkExt<String, Error = LiveViewError> {}
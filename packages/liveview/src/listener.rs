@@ -0,0 +1,108 @@
+use dioxus_hooks::{GlobalHandler, GlobalListenerBackend, ListenerGuard, ListenerTarget};
+use dioxus_html::EventData;
+use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use serde_json::{json, Value};
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+/// Backs `use_document_listener` and `use_window_listener` with listeners the interpreter adds in the browser
+pub(crate) struct LiveViewListeners {
+    registry: Rc<ListenerRegistry>,
+}
+
+impl LiveViewListeners {
+    pub(crate) fn new(registry: Rc<ListenerRegistry>) -> Self {
+        Self { registry }
+    }
+}
+
+impl GlobalListenerBackend for LiveViewListeners {
+    fn listen(&self, target: ListenerTarget, event: &str, handler: GlobalHandler) -> ListenerGuard {
+        let id = self.registry.add(target, event, handler);
+        let registry = self.registry.clone();
+        ListenerGuard::new(move || registry.remove(id))
+    }
+}
+
+/// The listeners on the document and the window of the browser, by the id the browser knows them by
+///
+/// The whole set is sent whenever it changes, and again to a browser that reconnects, so the interpreter only has to
+/// add and remove the difference.
+pub(crate) struct ListenerRegistry {
+    next_id: Cell<usize>,
+    listeners: RefCell<BTreeMap<usize, Listener>>,
+    messages: UnboundedSender<String>,
+}
+
+struct Listener {
+    target: ListenerTarget,
+    name: String,
+    handler: Rc<GlobalHandler>,
+}
+
+impl ListenerRegistry {
+    pub(crate) fn new() -> (Self, UnboundedReceiver<String>) {
+        let (messages, rx) = unbounded();
+        let registry = Self {
+            next_id: Cell::new(0),
+            listeners: RefCell::new(BTreeMap::new()),
+            messages,
+        };
+        (registry, rx)
+    }
+
+    fn add(&self, target: ListenerTarget, name: &str, handler: GlobalHandler) -> usize {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        self.listeners.borrow_mut().insert(
+            id,
+            Listener {
+                target,
+                name: name.to_string(),
+                handler: Rc::new(handler),
+            },
+        );
+        let _ = self.messages.unbounded_send(self.message());
+        id
+    }
+
+    fn remove(&self, id: usize) {
+        self.listeners.borrow_mut().remove(&id);
+        let _ = self.messages.unbounded_send(self.message());
+    }
+
+    /// The message that makes the listeners of the browser match these
+    pub(crate) fn message(&self) -> String {
+        let listeners: Vec<_> = self
+            .listeners
+            .borrow()
+            .iter()
+            .map(|(id, listener)| {
+                json!({ "id": id, "target": listener.target.as_str(), "name": listener.name })
+            })
+            .collect();
+        json!({ "listeners": listeners }).to_string()
+    }
+
+    /// Hand an event the browser reported to the listener it's for, if it's still around
+    pub(crate) fn handle_message(&self, params: &Value) {
+        let id = match params.get("id").and_then(Value::as_u64) {
+            Some(id) => id as usize,
+            None => return,
+        };
+
+        // The handler is cloned out, so it can add and remove listeners itself
+        let listener = self
+            .listeners
+            .borrow()
+            .get(&id)
+            .map(|listener| (listener.name.clone(), listener.handler.clone()));
+        if let Some((name, handler)) = listener {
+            let data = params.get("data").cloned().unwrap_or(Value::Null);
+            if let Ok(data) = EventData::from_json(&name, &data.to_string()) {
+                handler(data.into_any());
+            }
+        }
+    }
+}
@@ -5,39 +5,291 @@ function main() {
   }
 }
 
+// the session and the values it persisted are kept for the tab, so a reload picks up where the app was
+const SESSION_KEY = "dioxus-liveview-session";
+const SNAPSHOT_KEY = "dioxus-liveview-snapshot";
+
+function loadSnapshot() {
+  try {
+    return JSON.parse(window.sessionStorage.getItem(SNAPSHOT_KEY)) ?? {};
+  } catch {
+    return {};
+  }
+}
+
+// make the changes the app made to its history
+function navigate(command) {
+  switch (command.method) {
+    case "push":
+      window.history.pushState(null, "", command.path);
+      break;
+    case "replace":
+      window.history.replaceState(null, "", command.path);
+      break;
+    case "go":
+      window.history.go(command.delta);
+      break;
+  }
+}
+
+// the scripts the app runs with use_eval, by their id, which get the values the app sends them
+const evals = {};
+
+// run a script of the app as the body of an async function with a `dioxus` object to talk to the app
+function runEval(id, script) {
+  const post = (method, params) => window.ipc.postMessage(serializeIpcMessage(method, params));
+  const received = [];
+  const waiting = [];
+  evals[id] = (value) => (waiting.length ? waiting.shift()(value) : received.push(value));
+  const dioxus = {
+    send: (value) => post("eval_send", { id, data: value === undefined ? null : value }),
+    recv: () => new Promise((resolve) => (received.length ? resolve(received.shift()) : waiting.push(resolve))),
+  };
+  // the script is compiled here so a syntax error is reported like any other error it throws
+  const AsyncFunction = Object.getPrototypeOf(async function () {}).constructor;
+  new Promise((resolve) => resolve(new AsyncFunction("dioxus", script)(dioxus)))
+    .then((value) => post("eval_result", { id, ok: value === undefined ? null : value }))
+    .catch((error) => post("eval_result", { id, error: String(error) }))
+    .finally(() => delete evals[id]);
+}
+
+// turn a message of the compact encoding back into the edits of the interpreter, with the names of the templates the
+// connection was sent so far
+function decodeCompact([templates, ops], names) {
+  const edits = [];
+  let i = 0;
+  const next = () => ops[i++];
+  while (i < ops.length) {
+    switch (next()) {
+      case 0:
+        edits.push({ type: "AppendChildren", id: next(), m: next() });
+        break;
+      case 1:
+        edits.push({ type: "AssignId", path: next(), id: next() });
+        break;
+      case 2:
+        edits.push({ type: "CreatePlaceholder", id: next() });
+        break;
+      case 3:
+        edits.push({ type: "CreateTextNode", value: next(), id: next() });
+        break;
+      case 4:
+        edits.push({ type: "HydrateText", path: next(), value: next(), id: next() });
+        break;
+      case 5: {
+        let name = next();
+        if (typeof name === "string") {
+          names.push(name);
+        } else {
+          name = names[name];
+        }
+        edits.push({ type: "LoadTemplate", name, index: next(), id: next() });
+        break;
+      }
+      case 6:
+        edits.push({ type: "ReplaceWith", id: next(), m: next() });
+        break;
+      case 7:
+        edits.push({ type: "ReplacePlaceholder", path: next(), m: next() });
+        break;
+      case 8:
+        edits.push({ type: "InsertAfter", id: next(), m: next() });
+        break;
+      case 9:
+        edits.push({ type: "InsertBefore", id: next(), m: next() });
+        break;
+      case 10:
+        edits.push({ type: "SetAttribute", name: next(), value: next(), id: next(), ns: next() });
+        break;
+      case 11:
+        edits.push({ type: "SetText", value: next(), id: next() });
+        break;
+      case 12:
+        edits.push({ type: "NewEventListener", name: next(), id: next(), passive: next() });
+        break;
+      case 13:
+        edits.push({ type: "RemoveEventListener", name: next(), id: next() });
+        break;
+      case 14:
+        edits.push({ type: "Remove", id: next() });
+        break;
+      case 15:
+        edits.push({ type: "PushRoot", id: next() });
+        break;
+    }
+  }
+  return { templates, edits };
+}
+
+function currentPath() {
+  let location = window.location;
+  return location.pathname + location.search + location.hash;
+}
+
 class IPC {
   constructor(root) {
-    // connect to the websocket
+    this.root = root;
+    this.retries = 0;
+    // the milliseconds between two events of a kind, which the app sends when it connects
+    this.throttles = {};
+    // the last event of every throttled kind and element that's waiting for its interval to be over
+    this.pending = new Map();
+    // when an event of every throttled kind and element was last sent
+    this.sent = new Map();
     window.interpreter = new Interpreter(root);
+    // the session reads the files of an event as they arrive
+    window.interpreter.streamFiles = true;
+
+    // the app follows the back and forward buttons
+    window.addEventListener("popstate", () => {
+      this.postMessage(serializeIpcMessage("history", { path: currentPath() }));
+    });
 
+    // we ping every 30 seconds to keep the websocket alive
+    setInterval(() => this.send("__ping__"), 30000);
+
+    this.connect();
+  }
+
+  connect() {
     let ws = new WebSocket(WS_ADDR);
 
-    function ping() {
-      ws.send("__ping__");
-    }
+    // the first edits of every connection build the page from scratch
+    let rebuilt = false;
+    // the names of the templates sent over the connection, which the compact encoding only sends once
+    const names = [];
 
     ws.onopen = () => {
-      // we ping every 30 seconds to keep the websocket alive
-      setInterval(ping, 30000);
-      ws.send(serializeIpcMessage("initialize"));
+      this.retries = 0;
+      ws.send(
+        serializeIpcMessage("initialize", {
+          path: currentPath(),
+          session: window.sessionStorage.getItem(SESSION_KEY),
+          snapshot: loadSnapshot(),
+          encodings: ["compact", "json"],
+        })
+      );
     };
 
-    ws.onerror = (err) => {
-      // todo: retry the connection
+    ws.onclose = () => {
+      // try again, waiting twice as long every time up to ten seconds
+      const delay = Math.min(250 * 2 ** this.retries, 10000);
+      this.retries++;
+      setTimeout(() => this.connect(), delay);
     };
 
     ws.onmessage = (event) => {
       // Ignore pongs
-      if (event.data != "__pong__") {
-        let edits = JSON.parse(event.data);
-        window.interpreter.handleEdits(edits);
+      if (event.data == "__pong__") {
+        return;
+      }
+      let message = JSON.parse(event.data);
+      if (message.session !== undefined) {
+        window.sessionStorage.setItem(SESSION_KEY, message.session);
+      } else if (message.snapshot !== undefined) {
+        const snapshot = Object.assign(loadSnapshot(), message.snapshot);
+        window.sessionStorage.setItem(SNAPSHOT_KEY, JSON.stringify(snapshot));
+      } else if (message.throttle !== undefined) {
+        this.throttles = message.throttle;
+      } else if (message.eval !== undefined) {
+        runEval(message.eval.id, message.eval.script);
+      } else if (message.eval_send !== undefined) {
+        evals[message.eval_send.id]?.(message.eval_send.data);
+      } else if (message.head !== undefined) {
+        window.interpreter.UpdateHead(message.head);
+      } else if (message.listeners !== undefined) {
+        window.interpreter.SetGlobalListeners(message.listeners);
+      } else if (message.history) {
+        navigate(message.history);
+      } else if (message.open !== undefined) {
+        window.location.assign(message.open);
+      } else {
+        if (!rebuilt) {
+          rebuilt = true;
+          this.reset();
+        }
+        window.interpreter.handleEdits(Array.isArray(message) ? decodeCompact(message, names) : message);
       }
     };
 
     this.ws = ws;
   }
 
+  // swap the root for an empty copy, which drops the elements and listeners of the last connection
+  reset() {
+    // the listeners on the document and the window outlive the root, so they're removed on their own
+    window.interpreter?.SetGlobalListeners([]);
+    const root = this.root.cloneNode(false);
+    this.root.replaceWith(root);
+    this.root = root;
+    window.interpreter = new Interpreter(root);
+
+    for (const pending of this.pending.values()) {
+      clearTimeout(pending.timer);
+    }
+    this.pending.clear();
+  }
+
   postMessage(msg) {
-    this.ws.send(msg);
+    const throttled = this.throttled(msg);
+    if (throttled === null) {
+      // the coalesced events fired first, so the app gets them first
+      this.flush();
+      this.send(msg);
+      return;
+    }
+
+    const { key, interval } = throttled;
+    const pending = this.pending.get(key);
+    if (pending !== undefined) {
+      pending.msg = msg;
+      return;
+    }
+    const wait = (this.sent.get(key) ?? -Infinity) + interval - performance.now();
+    if (wait <= 0) {
+      this.sent.set(key, performance.now());
+      this.send(msg);
+    } else {
+      this.pending.set(key, { msg, timer: setTimeout(() => this.release(key), wait) });
+    }
+  }
+
+  // the kind and element of a user event that's throttled, and its interval
+  throttled(msg) {
+    if (Object.keys(this.throttles).length === 0) {
+      return null;
+    }
+    let message;
+    try {
+      message = JSON.parse(msg);
+    } catch {
+      return null;
+    }
+    const interval = message.method === "user_event" ? this.throttles[message.params.name] : undefined;
+    if (!interval) {
+      return null;
+    }
+    return { key: `${message.params.name}:${message.params.element}`, interval };
+  }
+
+  release(key) {
+    const pending = this.pending.get(key);
+    this.pending.delete(key);
+    this.sent.set(key, performance.now());
+    this.send(pending.msg);
+  }
+
+  flush() {
+    for (const key of Array.from(this.pending.keys())) {
+      clearTimeout(this.pending.get(key).timer);
+      this.release(key);
+    }
+  }
+
+  send(msg) {
+    // the events of a page that lost its connection are about elements that will be rebuilt
+    if (this.ws.readyState === WebSocket.OPEN) {
+      this.ws.send(msg);
+    }
   }
 }
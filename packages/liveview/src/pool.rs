@@ -1,13 +1,26 @@
-use crate::LiveViewError;
+use crate::session::{
+    new_token, read_handshake, BoxedSocket, Handshake, Reconnection, RootContext, Session, Sessions,
+};
+use crate::{Encoding, EventThrottle, LiveViewError};
 use dioxus_core::prelude::*;
-use dioxus_html::HtmlEvent;
-use futures_util::{pin_mut, SinkExt, StreamExt};
+use futures_util::{SinkExt, StreamExt};
+use std::sync::Arc;
 use std::time::Duration;
 use tokio_util::task::LocalPoolHandle;
 
+/// Runs the VirtualDoms of the browsers connected to a server
+///
+/// When the socket of a browser closes, its VirtualDom waits for the browser to reconnect with the token of its
+/// session, and then rebuilds the page of the browser from the state it's in. A browser that comes back after that gets
+/// a new VirtualDom, which restores the values of `use_persistent` from the browser.
 #[derive(Clone)]
 pub struct LiveViewPool {
     pub(crate) pool: LocalPoolHandle,
+    sessions: Sessions,
+    reconnect_timeout: Duration,
+    encoding: Encoding,
+    throttle: EventThrottle,
+    root_contexts: Vec<RootContext>,
 }
 
 impl Default for LiveViewPool {
@@ -20,24 +33,121 @@ impl LiveViewPool {
     pub fn new() -> Self {
         LiveViewPool {
             pool: LocalPoolHandle::new(16),
+            sessions: Sessions::default(),
+            reconnect_timeout: Duration::from_secs(30),
+            encoding: Encoding::Compact,
+            throttle: EventThrottle::default(),
+            root_contexts: Vec::new(),
         }
     }
 
-    pub async fn launch(
+    /// Provide a context to the root of the VirtualDom of every browser, for the global services every component can
+    /// reach with `use_context`, like a database pool or the config of the server.
+    ///
+    /// Each VirtualDom gets its own clone of the context. The pool's own contexts are provided first, so a context of
+    /// the same type replaces them.
+    ///
+    /// ```rust, ignore
+    /// let view = LiveViewPool::new().with_context(db.clone());
+    /// ```
+    pub fn with_context<T: Clone + Send + Sync + 'static>(mut self, context: T) -> Self {
+        self.root_contexts.push(Arc::new(move |scope| {
+            scope.provide_context(context.clone());
+        }));
+        self
+    }
+
+    /// Tell the browsers how often to send the events that can fire many times a second
+    pub fn with_event_throttle(mut self, throttle: EventThrottle) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// Send the edits in this encoding to the browsers that understand it, instead of the compact one
+    ///
+    /// Browsers that don't understand it get json.
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Keep the VirtualDom of a browser that lost its connection around for this long, instead of thirty seconds
+    pub fn with_reconnect_timeout(mut self, timeout: Duration) -> Self {
+        self.reconnect_timeout = timeout;
+        self
+    }
+
+    /// Accept a websocket connection of a web framework, and run the app on it once it's open
+    ///
+    /// Every framework adapter provides its websocket upgrade as a [`LiveViewConnection`], so a handler looks the same
+    /// everywhere:
+    ///
+    /// ```rust, ignore
+    /// get(move |ws: LiveViewUpgrade| async move { view.launch(ws, app) })
+    /// ```
+    pub fn launch<C: LiveViewConnection>(
+        &self,
+        ws: C,
+        app: fn(Scope<()>) -> Element,
+    ) -> C::Response {
+        self.launch_with_props(ws, app, ())
+    }
+
+    /// Accept a websocket connection of a web framework, and run the app with props on it once it's open
+    pub fn launch_with_props<C: LiveViewConnection, T: Send + 'static>(
+        &self,
+        ws: C,
+        app: fn(Scope<T>) -> Element,
+        props: T,
+    ) -> C::Response {
+        ws.upgrade(self.clone(), app, props)
+    }
+
+    /// Run the app on a websocket that's already open, until the browser is gone for longer than the reconnect timeout
+    pub async fn launch_socket(
         &self,
         ws: impl LiveViewSocket,
         app: fn(Scope<()>) -> Element,
     ) -> Result<(), LiveViewError> {
-        self.launch_with_props(ws, app, ()).await
+        self.launch_socket_with_props(ws, app, ()).await
     }
 
-    pub async fn launch_with_props<T: Send + 'static>(
+    /// Run the app with props on a websocket that's already open, until the browser is gone for longer than the reconnect
+    /// timeout
+    ///
+    /// A browser that reconnects to a session that's still running hands its socket to the session, and this returns
+    /// right away.
+    pub async fn launch_socket_with_props<T: Send + 'static>(
         &self,
         ws: impl LiveViewSocket,
         app: fn(Scope<T>) -> Element,
         props: T,
     ) -> Result<(), LiveViewError> {
-        match self.pool.spawn_pinned(move || run(app, props, ws)).await {
+        let mut ws: BoxedSocket = Box::pin(ws);
+        let handshake = match read_handshake(&mut ws).await? {
+            Some(handshake) => handshake,
+            None => return Ok(()),
+        };
+
+        // a browser that had a session picks up where it left off
+        let ws = match handshake.session.as_deref() {
+            Some(token) => {
+                let reconnection = Reconnection {
+                    ws,
+                    path: handshake.path.clone(),
+                    encoding: self.encoding.negotiate(&handshake.encodings),
+                };
+                match self.sessions.reconnect(token, reconnection) {
+                    Ok(()) => return Ok(()),
+                    Err(reconnection) => reconnection.ws,
+                }
+            }
+            None => ws,
+        };
+
+        let pool = self.clone();
+        let session = move || run_session(app, props, ws, handshake, pool);
+        match self.pool.spawn_pinned(session).await {
             Ok(Ok(_)) => Ok(()),
             Ok(Err(e)) => Err(e),
             Err(_) => Err(LiveViewError::SendingFailed),
@@ -45,6 +155,23 @@ impl LiveViewPool {
     }
 }
 
+/// A websocket request of a web framework, which a [`LiveViewPool`] can run an app on
+///
+/// The adapters implement this for the websocket upgrades of their frameworks, so [`LiveViewPool::launch`] hands the
+/// framework back the response it expects.
+pub trait LiveViewConnection {
+    /// What the handler of the web framework returns to accept the connection
+    type Response;
+
+    /// Accept the connection, running the app on the pool once the websocket is open
+    fn upgrade<T: Send + 'static>(
+        self,
+        pool: LiveViewPool,
+        app: fn(Scope<T>) -> Element,
+        props: T,
+    ) -> Self::Response;
+}
+
 /// A LiveViewSocket is a Sink and Stream of Strings that Dioxus uses to communicate with the client
 ///
 /// Most websockets from most HTTP frameworks can be converted into a LiveViewSocket using the appropriate adapter.
@@ -95,6 +222,8 @@ impl<S> LiveViewSocket for S where
 /// As long as your framework can provide a Sink and Stream of Strings, you can use this function.
 ///
 /// You might need to transform the error types of the web backend into the LiveView error type.
+///
+/// The app stops when the socket closes. Apps launched on a [`LiveViewPool`] wait for the browser to reconnect instead.
 pub async fn run<T>(
     app: Component<T>,
     props: T,
@@ -103,51 +232,53 @@ pub async fn run<T>(
 where
     T: Send + 'static,
 {
-    let mut vdom = VirtualDom::new_with_props(app, props);
-
-    // todo: use an efficient binary packed format for this
-    let edits = serde_json::to_string(&vdom.rebuild()).unwrap();
-
-    // pin the futures so we can use select!
-    pin_mut!(ws);
+    let mut ws: BoxedSocket = Box::pin(ws);
+    let handshake = match read_handshake(&mut ws).await? {
+        Some(handshake) => handshake,
+        None => return Ok(()),
+    };
 
-    // send the initial render to the client
-    ws.send(edits).await?;
+    let encoding = Encoding::Compact.negotiate(&handshake.encodings);
+    Session::new(app, props, handshake.snapshot, &[])
+        .serve(
+            &mut ws,
+            None,
+            handshake.path,
+            encoding,
+            &EventThrottle::default(),
+        )
+        .await
+}
 
-    // desktop uses this wrapper struct thing around the actual event itself
-    // this is sorta driven by tao/wry
-    #[derive(serde::Deserialize)]
-    struct IpcMessage {
-        params: HtmlEvent,
-    }
+/// Serve a session to its browser, and to the browser again every time it reconnects in time
+async fn run_session<T: 'static>(
+    app: Component<T>,
+    props: T,
+    mut ws: BoxedSocket,
+    handshake: Handshake,
+    pool: LiveViewPool,
+) -> Result<(), LiveViewError> {
+    let token = new_token();
+    let mut session = Session::new(app, props, handshake.snapshot, &pool.root_contexts);
+    let mut path = handshake.path;
+    let mut encoding = pool.encoding.negotiate(&handshake.encodings);
 
     loop {
-        tokio::select! {
-            // poll any futures or suspense
-            _ = vdom.wait_for_work() => {}
-
-            evt = ws.next() => {
-                match evt.as_ref().map(|o| o.as_deref()) {
-                    // respond with a pong every ping to keep the websocket alive
-                    Some(Ok("__ping__")) => {
-                        ws.send("__pong__".to_string()).await?;
-                    }
-                    Some(Ok(evt)) => {
-                        if let Ok(IpcMessage { params }) = serde_json::from_str::<IpcMessage>(evt) {
-                            vdom.handle_event(&params.name, params.data.into_any(), params.element, params.bubbles);
-                        }
-                    }
-                    // log this I guess? when would we get an error here?
-                    Some(Err(_e)) => {},
-                    None => return Ok(()),
-                }
-            }
-        }
-
-        let edits = vdom
-            .render_with_deadline(tokio::time::sleep(Duration::from_millis(10)))
+        let result = session
+            .serve(&mut ws, Some(&token), path, encoding, &pool.throttle)
             .await;
 
-        ws.send(serde_json::to_string(&edits).unwrap()).await?;
+        match pool
+            .sessions
+            .wait_for_reconnection(&token, pool.reconnect_timeout)
+            .await
+        {
+            Some(reconnection) => {
+                ws = reconnection.ws;
+                path = reconnection.path;
+                encoding = reconnection.encoding;
+            }
+            None => return result,
+        }
     }
 }
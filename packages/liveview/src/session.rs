@@ -0,0 +1,448 @@
+use crate::encoding::{EditEncoder, Encoding};
+use crate::eval::{EvalRegistry, LiveViewEval};
+use crate::history::HistoryCommand;
+use crate::listener::{ListenerRegistry, LiveViewListeners};
+use crate::throttle::EventThrottle;
+use crate::upload::Uploads;
+use crate::{LiveViewError, LiveviewHistory};
+use dioxus_core::prelude::*;
+use dioxus_history::History;
+use dioxus_hooks::{
+    EvalContext, GlobalListenerContext, HeadBackend, HeadContext, HeadElement, PersistentBackend,
+    PersistentStorage,
+};
+use dioxus_html::{FileChunk, HtmlEvent, SerializedFileEngine};
+use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures_channel::oneshot;
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// A socket of any framework, boxed so a session can switch to the socket of a browser that reconnects
+pub(crate) type BoxedSocket = Pin<Box<dyn SessionSocket>>;
+
+pub(crate) trait SessionSocket:
+    Sink<String, Error = LiveViewError> + Stream<Item = Result<String, LiveViewError>> + Send
+{
+}
+
+impl<S> SessionSocket for S where
+    S: Sink<String, Error = LiveViewError> + Stream<Item = Result<String, LiveViewError>> + Send
+{
+}
+
+// desktop uses this wrapper struct thing around the actual event itself
+// this is sorta driven by tao/wry
+#[derive(serde::Deserialize)]
+struct IpcMessage {
+    method: String,
+    params: serde_json::Value,
+}
+
+// the path of the browser, which it sends when it moves through its history
+#[derive(serde::Deserialize)]
+struct BrowserPath {
+    path: String,
+}
+
+/// The first message of a browser, which says where it is and which session it had before it lost its connection
+#[derive(serde::Deserialize)]
+pub(crate) struct Handshake {
+    pub(crate) path: String,
+    #[serde(default)]
+    pub(crate) session: Option<String>,
+    // the values of `use_persistent` the browser was sent, for a session that's gone
+    #[serde(default)]
+    pub(crate) snapshot: HashMap<String, String>,
+    // the encodings of edits the browser understands
+    #[serde(default)]
+    pub(crate) encodings: Vec<String>,
+}
+
+/// Wait for the browser to say hello, answering its pings in the meantime
+///
+/// Returns `None` if the socket closes first.
+pub(crate) async fn read_handshake(
+    ws: &mut BoxedSocket,
+) -> Result<Option<Handshake>, LiveViewError> {
+    while let Some(message) = ws.next().await {
+        let message = message?;
+        if message == "__ping__" {
+            ws.send("__pong__".to_string()).await?;
+            continue;
+        }
+
+        match serde_json::from_str::<IpcMessage>(&message) {
+            Ok(IpcMessage { method, params }) if method == "initialize" => {
+                if let Ok(handshake) = serde_json::from_value(params) {
+                    return Ok(Some(handshake));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(None)
+}
+
+/// A socket of a browser that came back for its session
+pub(crate) struct Reconnection {
+    pub(crate) ws: BoxedSocket,
+    pub(crate) path: String,
+    pub(crate) encoding: Encoding,
+}
+
+/// The sessions that lost their browser, by their token
+#[derive(Clone, Default)]
+pub(crate) struct Sessions(Arc<Mutex<HashMap<String, oneshot::Sender<Reconnection>>>>);
+
+impl Sessions {
+    /// Hand the socket to the session it belongs to, or give it back if the session isn't waiting for it
+    ///
+    /// A session that's still connected isn't waiting, so a copy of the tab gets a session of its own.
+    pub(crate) fn reconnect(
+        &self,
+        token: &str,
+        reconnection: Reconnection,
+    ) -> Result<(), Reconnection> {
+        let waiting = self.0.lock().unwrap().remove(token);
+        match waiting {
+            Some(session) => session.send(reconnection),
+            None => Err(reconnection),
+        }
+    }
+
+    /// Wait for the browser of a session to come back, for as long as the timeout
+    pub(crate) async fn wait_for_reconnection(
+        &self,
+        token: &str,
+        timeout: Duration,
+    ) -> Option<Reconnection> {
+        let (sender, mut receiver) = oneshot::channel();
+        self.0.lock().unwrap().insert(token.to_string(), sender);
+
+        match tokio::time::timeout(timeout, &mut receiver).await {
+            Ok(reconnection) => reconnection.ok(),
+            Err(_) => {
+                // the browser might have taken the sender right as the time ran out
+                if self.0.lock().unwrap().remove(token).is_some() {
+                    return None;
+                }
+                receiver.await.ok()
+            }
+        }
+    }
+}
+
+/// A token that's hard to guess, so a browser can't take over the session of another one
+///
+/// The keys of `RandomState` come from the randomness of the operating system.
+pub(crate) fn new_token() -> String {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hash, Hasher};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut token = String::with_capacity(32);
+    for _ in 0..2 {
+        let mut hasher = RandomState::new().build_hasher();
+        COUNTER.fetch_add(1, Ordering::Relaxed).hash(&mut hasher);
+        std::time::SystemTime::now().hash(&mut hasher);
+        token.push_str(&format!("{:016x}", hasher.finish()));
+    }
+    token
+}
+
+/// Backs `use_persistent` with the storage of the browser tab, so a session that's gone comes back with its values
+struct SnapshotStorage {
+    values: Rc<RefCell<HashMap<String, String>>>,
+    changes: UnboundedSender<(String, String)>,
+}
+
+impl PersistentStorage for SnapshotStorage {
+    fn load(&self, key: &str) -> Option<String> {
+        self.values.borrow().get(key).cloned()
+    }
+
+    fn store(&self, key: &str, value: &str) {
+        self.values
+            .borrow_mut()
+            .insert(key.to_string(), value.to_string());
+        let _ = self
+            .changes
+            .unbounded_send((key.to_string(), value.to_string()));
+    }
+}
+
+/// Backs `use_head` with the head of the browser, which is sent the whole head again when it reconnects
+struct BrowserHead {
+    latest: Rc<RefCell<Option<String>>>,
+    changes: UnboundedSender<String>,
+}
+
+impl HeadBackend for BrowserHead {
+    fn update(&self, elements: &[HeadElement]) {
+        let elements: Vec<_> = elements.iter().map(HeadElement::to_json).collect();
+        let message = serde_json::json!({ "head": elements }).to_string();
+        *self.latest.borrow_mut() = Some(message.clone());
+        let _ = self.changes.unbounded_send(message);
+    }
+}
+
+/// Provides a context of the [`crate::LiveViewPool`] to the root of a new VirtualDom
+pub(crate) type RootContext = Arc<dyn Fn(&ScopeState) + Send + Sync>;
+
+/// A VirtualDom and everything it needs to be served to one browser after another
+pub(crate) struct Session {
+    vdom: VirtualDom,
+    built: bool,
+    history: Rc<LiveviewHistory>,
+    history_rx: UnboundedReceiver<HistoryCommand>,
+    snapshot: Rc<RefCell<HashMap<String, String>>>,
+    snapshot_rx: UnboundedReceiver<(String, String)>,
+    evals: Rc<EvalRegistry>,
+    eval_rx: UnboundedReceiver<String>,
+    head: Rc<RefCell<Option<String>>>,
+    head_rx: UnboundedReceiver<String>,
+    listeners: Rc<ListenerRegistry>,
+    listeners_rx: UnboundedReceiver<String>,
+}
+
+impl Session {
+    /// Create the VirtualDom of a session, restoring the values it had in `snapshot`
+    pub(crate) fn new<T: 'static>(
+        app: Component<T>,
+        props: T,
+        snapshot: HashMap<String, String>,
+        root_contexts: &[RootContext],
+    ) -> Self {
+        let vdom = VirtualDom::new_with_props(app, props);
+
+        // the router navigates the history of the browser through this
+        let (history, history_rx) = LiveviewHistory::new();
+        let history = Rc::new(history);
+        vdom.base_scope()
+            .provide_context::<Rc<dyn History>>(history.clone());
+
+        let snapshot = Rc::new(RefCell::new(snapshot));
+        let (changes, snapshot_rx) = unbounded();
+        vdom.base_scope()
+            .provide_context(PersistentBackend::new(SnapshotStorage {
+                values: snapshot.clone(),
+                changes,
+            }));
+
+        // the scripts of use_eval run in the browser, and talk to the app over the socket
+        let (evals, eval_rx) = EvalRegistry::new();
+        let evals = Rc::new(evals);
+        vdom.base_scope()
+            .provide_context(EvalContext::new(LiveViewEval::new(evals.clone())));
+
+        // the elements of use_head go in the head of the browser
+        let head = Rc::new(RefCell::new(None));
+        let (changes, head_rx) = unbounded();
+        vdom.base_scope()
+            .provide_context(HeadContext::new(BrowserHead {
+                latest: head.clone(),
+                changes,
+            }));
+
+        // use_document_listener and use_window_listener listen in the browser, which reports their events by id
+        let (listeners, listeners_rx) = ListenerRegistry::new();
+        let listeners = Rc::new(listeners);
+        vdom.base_scope()
+            .provide_context(GlobalListenerContext::new(LiveViewListeners::new(
+                listeners.clone(),
+            )));
+
+        // The contexts of the pool go last, so they can replace the ones above
+        for provide in root_contexts {
+            provide(vdom.base_scope());
+        }
+
+        Self {
+            vdom,
+            built: false,
+            history,
+            history_rx,
+            snapshot,
+            snapshot_rx,
+            evals,
+            eval_rx,
+            head,
+            head_rx,
+            listeners,
+            listeners_rx,
+        }
+    }
+
+    /// Serve the app to a browser until its socket closes
+    ///
+    /// The browser starts out with an empty page: the first time it's the first render, and after that every element is
+    /// rebuilt from the state the VirtualDom is in.
+    pub(crate) async fn serve(
+        &mut self,
+        ws: &mut BoxedSocket,
+        token: Option<&str>,
+        path: String,
+        encoding: Encoding,
+        throttle: &EventThrottle,
+    ) -> Result<(), LiveViewError> {
+        let Self {
+            vdom,
+            built,
+            history,
+            history_rx,
+            snapshot,
+            snapshot_rx,
+            evals,
+            eval_rx,
+            head,
+            head_rx,
+            listeners,
+            listeners_rx,
+        } = self;
+
+        history.follow(path);
+
+        // the scripts of the last browser, and the messages for them, went away with its page
+        evals.abandon();
+        while let Ok(Some(_)) = eval_rx.try_next() {}
+
+        if let Some(token) = token {
+            ws.send(serde_json::json!({ "session": token }).to_string())
+                .await?;
+        }
+        let values = serde_json::json!({ "snapshot": &*snapshot.borrow() }).to_string();
+        ws.send(values).await?;
+        ws.send(serde_json::json!({ "throttle": throttle.to_json(vdom) }).to_string())
+            .await?;
+
+        let mut encoder = EditEncoder::new(encoding);
+        let edits = match std::mem::replace(built, true) {
+            false => encoder.rebuild(vdom),
+            true => encoder.rebuild_in_place(vdom),
+        };
+
+        // send the initial render to the client
+        ws.send(edits).await?;
+
+        // a browser that reconnects starts out with the head of its page, so it gets the latest head right away
+        while let Ok(Some(_)) = head_rx.try_next() {}
+        let latest = head.borrow().clone();
+        if let Some(latest) = latest {
+            ws.send(latest).await?;
+        }
+
+        // and so do the listeners on its document and window
+        while let Ok(Some(_)) = listeners_rx.try_next() {}
+        ws.send(listeners.message()).await?;
+
+        // the files of an event are streamed in chunks right before the event
+        let mut pending_files = SerializedFileEngine::new();
+        // and the files of a streamed upload right after it
+        let mut uploads = Uploads::default();
+
+        loop {
+            tokio::select! {
+                // poll any futures or suspense
+                _ = vdom.wait_for_work() => {}
+
+                Some(command) = history_rx.next() => {
+                    ws.send(serde_json::json!({ "history": command }).to_string()).await?;
+                }
+
+                Some(message) = eval_rx.next() => {
+                    ws.send(message).await?;
+                }
+
+                Some(message) = head_rx.next() => {
+                    ws.send(message).await?;
+                }
+
+                Some(message) = listeners_rx.next() => {
+                    ws.send(message).await?;
+                }
+
+                Some((key, value)) = snapshot_rx.next() => {
+                    ws.send(serde_json::json!({ "snapshot": { key: value } }).to_string()).await?;
+                }
+
+                evt = ws.next() => {
+                    match evt.as_ref().map(|o| o.as_deref()) {
+                        // respond with a pong every ping to keep the websocket alive
+                        Some(Ok("__ping__")) => {
+                            ws.send("__pong__".to_string()).await?;
+                        }
+                        Some(Ok(evt)) => {
+                            match serde_json::from_str::<IpcMessage>(evt) {
+                                Ok(IpcMessage { method, params }) if method == "file_chunk" => {
+                                    if let Ok(chunk) = serde_json::from_value::<FileChunk>(params) {
+                                        _ = pending_files.push_chunk(&chunk);
+                                    }
+                                }
+                                Ok(IpcMessage { method, params }) if method == "file_start" => {
+                                    if let Ok(file) = serde_json::from_value(params) {
+                                        uploads.start(file);
+                                    }
+                                }
+                                Ok(IpcMessage { method, params }) if method == "file_data" => {
+                                    if let Ok(data) = serde_json::from_value(params) {
+                                        uploads.push(data);
+                                    }
+                                }
+                                Ok(IpcMessage { method, params }) if method == "eval_send" || method == "eval_result" => {
+                                    evals.handle_message(&method, &params);
+                                }
+                                Ok(IpcMessage { method, params }) if method == "global_event" => {
+                                    listeners.handle_message(&params);
+                                }
+                                Ok(IpcMessage { method, params }) if method == "history" => {
+                                    if let Ok(BrowserPath { path }) = serde_json::from_value(params) {
+                                        history.follow(path);
+                                    }
+                                }
+                                Ok(IpcMessage { method, params }) if method == "browser_open" => {
+                                    if let Some(href) = params.get("href").and_then(|href| href.as_str()) {
+                                        ws.send(serde_json::json!({ "open": href }).to_string()).await?;
+                                    }
+                                }
+                                Ok(IpcMessage { params, .. }) => {
+                                    let href = params.get("href").and_then(|href| href.as_str()).map(str::to_string);
+                                    if let Ok(mut params) = serde_json::from_value::<HtmlEvent>(params) {
+                                        if let Some(files) = uploads.take_announced() {
+                                            params.data.set_files(Arc::new(files));
+                                        } else if !pending_files.is_empty() {
+                                            params.data.set_files(Arc::new(std::mem::take(&mut pending_files)));
+                                        }
+                                        let prevented = vdom.handle_event(&params.name, params.data.into_any(), params.element, params.bubbles);
+
+                                        // the browser follows the link once the listeners had their chance to prevent it
+                                        if let (false, Some(href)) = (prevented, href) {
+                                            ws.send(serde_json::json!({ "open": href }).to_string()).await?;
+                                        }
+                                    }
+                                }
+                                Err(_) => {}
+                            }
+                        }
+                        // log this I guess? when would we get an error here?
+                        Some(Err(_e)) => {},
+                        None => return Ok(()),
+                    }
+                }
+            }
+
+            let edits = encoder
+                .render(vdom, tokio::time::sleep(Duration::from_millis(10)))
+                .await;
+
+            ws.send(edits).await?;
+        }
+    }
+}
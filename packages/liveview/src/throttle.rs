@@ -0,0 +1,72 @@
+use dioxus_core::VirtualDom;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// How often the browser sends the events that can fire many times a second
+///
+/// An event of a throttled kind is sent right away, and the ones that follow it on the same element within the interval
+/// are coalesced into the last of them, which is sent once the interval is over. Any other event sends the coalesced
+/// ones first, so a `submit` always comes after the last `input`.
+///
+/// By default the events the VirtualDom says may be deferred (see `VirtualDom::set_event_dispatch`) are sent at most
+/// every 50ms. Out of the box those are the moves of the mouse, pointer and touches, drags, scrolling and input.
+/// Intervals set for an event with [`EventThrottle::with`] take priority over its dispatch policy.
+///
+/// ```rust, ignore
+/// let throttle = EventThrottle::default()
+///     .with("input", Duration::ZERO)
+///     .with("keydown", Duration::from_millis(100));
+/// let view = LiveViewPool::new().with_event_throttle(throttle);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EventThrottle {
+    deferred: Option<Duration>,
+    intervals: HashMap<String, Duration>,
+}
+
+impl Default for EventThrottle {
+    fn default() -> Self {
+        Self::none().with_deferred(Duration::from_millis(50))
+    }
+}
+
+impl EventThrottle {
+    /// Send every event as soon as it fires
+    pub fn none() -> Self {
+        Self {
+            deferred: None,
+            intervals: HashMap::new(),
+        }
+    }
+
+    /// Send the events the VirtualDom may defer at most once every `interval`, or as soon as they fire if it's zero
+    pub fn with_deferred(mut self, interval: Duration) -> Self {
+        self.deferred = (!interval.is_zero()).then_some(interval);
+        self
+    }
+
+    /// Send events of a kind at most once every `interval`, or as soon as they fire if it's zero
+    pub fn with(mut self, event: impl ToString, interval: Duration) -> Self {
+        self.intervals.insert(event.to_string(), interval);
+        self
+    }
+
+    /// The intervals in milliseconds by the name of the event, like the browser reads them
+    pub(crate) fn to_json(&self, dom: &VirtualDom) -> serde_json::Value {
+        let deferred = self.deferred.into_iter().flat_map(|interval| {
+            dom.deferred_events()
+                .into_iter()
+                .map(move |event| (event, interval))
+        });
+        let intervals: HashMap<_, _> = deferred
+            .chain(self.intervals.clone())
+            .filter(|(_, interval)| !interval.is_zero())
+            .collect();
+
+        intervals
+            .into_iter()
+            .map(|(event, interval)| (event, (interval.as_millis() as u64).into()))
+            .collect::<serde_json::Map<_, _>>()
+            .into()
+    }
+}
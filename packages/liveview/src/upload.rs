@@ -0,0 +1,67 @@
+use dioxus_html::{FileChunk, StreamedFileEngine};
+use std::collections::HashMap;
+
+/// A file the browser is about to stream for the event after it
+#[derive(serde::Deserialize)]
+pub(crate) struct FileStart {
+    upload: u64,
+    name: String,
+    size: usize,
+}
+
+/// A chunk of a file the browser is streaming
+#[derive(serde::Deserialize)]
+pub(crate) struct FileData {
+    upload: u64,
+    #[serde(flatten)]
+    chunk: FileChunk,
+}
+
+/// The files a browser is streaming after the events they belong to, by the upload the browser numbered them with
+///
+/// The uploads belong to the connection, so whatever is left of them is aborted when it closes.
+#[derive(Default)]
+pub(crate) struct Uploads {
+    streaming: HashMap<u64, StreamedFileEngine>,
+    announced: Option<StreamedFileEngine>,
+}
+
+impl Uploads {
+    /// Expect a file of the next event
+    pub(crate) fn start(&mut self, FileStart { upload, name, size }: FileStart) {
+        let files = self.streaming.entry(upload).or_default();
+        files.start(&name, size);
+        self.announced = Some(files.clone());
+    }
+
+    /// The files that were announced for the event the browser sent right after them
+    pub(crate) fn take_announced(&mut self) -> Option<StreamedFileEngine> {
+        self.announced.take()
+    }
+
+    /// Add a chunk to its file, forgetting the upload once every file of it arrived
+    ///
+    /// A chunk that isn't base64 aborts its upload, since the file couldn't be put back together anyway.
+    pub(crate) fn push(&mut self, FileData { upload, chunk }: FileData) {
+        let files = match self.streaming.get(&upload) {
+            Some(files) => files,
+            None => return,
+        };
+
+        let failed = files.push_chunk(&chunk).is_err();
+        if failed {
+            files.abort();
+        }
+        if failed || files.is_complete() {
+            self.streaming.remove(&upload);
+        }
+    }
+}
+
+impl Drop for Uploads {
+    fn drop(&mut self) {
+        for files in self.streaming.values() {
+            files.abort();
+        }
+    }
+}
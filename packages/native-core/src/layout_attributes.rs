@@ -1,6 +1,6 @@
 /*
-- [ ] pub display: Display, ----> taffy doesnt support all display types
-- [x] pub position_type: PositionType,  --> taffy doesnt support everything
+- [x] pub display: Display, ----> flex, grid and none
+- [x] pub position: Position,  --> taffy doesnt support everything
 - [x] pub direction: Direction,
 
 - [x] pub flex_direction: FlexDirection,
@@ -15,34 +15,52 @@
 - [x] pub align_self: AlignSelf,
 - [x] pub align_content: AlignContent,
 
-- [x] pub margin: Rect<Dimension>,
-- [x] pub padding: Rect<Dimension>,
+- [x] pub justify_items: Option<JustifyItems>,
+- [x] pub justify_self: Option<JustifySelf>,
+
+- [x] pub margin: Rect<LengthPercentageAuto>,
+- [x] pub padding: Rect<LengthPercentage>,
 
 - [x] pub justify_content: JustifyContent,
-- [x] pub position: Rect<Dimension>,
-- [x] pub border: Rect<Dimension>,
+- [x] pub inset: Rect<LengthPercentageAuto>,
+- [x] pub border: Rect<LengthPercentage>,
+- [x] pub gap: Size<LengthPercentage>,
+
+- [x] pub size: Size<Dimension>,
+- [x] pub min_size: Size<Dimension>,
+- [x] pub max_size: Size<Dimension>,
 
-- [ ] pub size: Size<Dimension>, ----> seems to only be relevant for input?
-- [ ] pub min_size: Size<Dimension>,
-- [ ] pub max_size: Size<Dimension>,
+- [x] pub grid_template_rows: GridTrackVec<TrackSizingFunction>,
+- [x] pub grid_template_columns: GridTrackVec<TrackSizingFunction>,
+- [x] pub grid_auto_rows: GridTrackVec<NonRepeatedTrackSizingFunction>,
+- [x] pub grid_auto_columns: GridTrackVec<NonRepeatedTrackSizingFunction>,
+- [x] pub grid_auto_flow: GridAutoFlow,
+- [x] pub grid_row: Line<GridPlacement>, ----> named lines and areas aren't supported
+- [x] pub grid_column: Line<GridPlacement>,
 
 - [ ] pub aspect_ratio: Number, ----> parsing is done, but taffy doesnt support it
 */
 
-use lightningcss::properties::{align, display, flex, position, size};
+use lightningcss::properties::{align, display, flex, grid, position, size};
 use lightningcss::{
     properties::{align::GapValue, border::BorderSideWidth, Property, PropertyId},
     stylesheet::ParserOptions,
     traits::Parse,
     values::{
-        length::{Length, LengthPercentageOrAuto, LengthValue},
+        length::{
+            Length, LengthPercentage as CssLengthPercentage, LengthPercentageOrAuto, LengthValue,
+        },
         percentage::DimensionPercentage,
         ratio::Ratio,
     },
 };
 use taffy::{
     prelude::*,
-    style::{FlexDirection, PositionType},
+    style::{
+        FlexDirection, GridAutoFlow, GridPlacement, GridTrackRepetition, MaxTrackSizingFunction,
+        MinTrackSizingFunction, NonRepeatedTrackSizingFunction, Position, TrackSizingFunction,
+    },
+    style_helpers::{line, span},
 };
 
 /// applies the entire html namespace defined in dioxus-html
@@ -55,33 +73,31 @@ pub fn apply_layout_attributes(name: &str, value: &str, style: &mut Style) {
                 display::Display::Keyword(display::DisplayKeyword::None) => {
                     style.display = Display::None
                 }
-                display::Display::Pair(pair) => {
-                    if let display::DisplayInside::Flex(_) = pair.inside {
-                        style.display = Display::Flex
-                    }
-                }
+                display::Display::Pair(pair) => match pair.inside {
+                    display::DisplayInside::Flex(_) => style.display = Display::Flex,
+                    display::DisplayInside::Grid => style.display = Display::Grid,
+                    _ => (),
+                },
                 _ => (),
             },
             Property::Position(position) => {
-                style.position_type = match position {
-                    position::Position::Relative => PositionType::Relative,
-                    position::Position::Absolute => PositionType::Absolute,
+                style.position = match position {
+                    position::Position::Relative => Position::Relative,
+                    position::Position::Absolute => Position::Absolute,
                     _ => return,
                 }
             }
-            Property::Top(top) => style.position.top = convert_length_percentage_or_auto(top),
+            Property::Top(top) => style.inset.top = convert_length_percentage_or_auto(top),
             Property::Bottom(bottom) => {
-                style.position.bottom = convert_length_percentage_or_auto(bottom)
-            }
-            Property::Left(left) => style.position.left = convert_length_percentage_or_auto(left),
-            Property::Right(right) => {
-                style.position.right = convert_length_percentage_or_auto(right)
+                style.inset.bottom = convert_length_percentage_or_auto(bottom)
             }
+            Property::Left(left) => style.inset.left = convert_length_percentage_or_auto(left),
+            Property::Right(right) => style.inset.right = convert_length_percentage_or_auto(right),
             Property::Inset(inset) => {
-                style.position.top = convert_length_percentage_or_auto(inset.top);
-                style.position.bottom = convert_length_percentage_or_auto(inset.bottom);
-                style.position.left = convert_length_percentage_or_auto(inset.left);
-                style.position.right = convert_length_percentage_or_auto(inset.right);
+                style.inset.top = convert_length_percentage_or_auto(inset.top);
+                style.inset.bottom = convert_length_percentage_or_auto(inset.bottom);
+                style.inset.left = convert_length_percentage_or_auto(inset.left);
+                style.inset.right = convert_length_percentage_or_auto(inset.right);
             }
             Property::BorderTopWidth(width) => {
                 style.border.top = convert_border_side_width(width);
@@ -144,105 +160,92 @@ pub fn apply_layout_attributes(name: &str, value: &str, style: &mut Style) {
                 style.flex_shrink = shrink;
             }
             Property::FlexBasis(basis, _) => {
-                style.flex_basis = convert_length_percentage_or_auto(basis);
+                style.flex_basis = convert_length_percentage_or_auto_dimension(basis);
             }
             Property::Flex(flex, _) => {
                 style.flex_grow = flex.grow;
                 style.flex_shrink = flex.shrink;
-                style.flex_basis = convert_length_percentage_or_auto(flex.basis);
+                style.flex_basis = convert_length_percentage_or_auto_dimension(flex.basis);
             }
             Property::AlignContent(align, _) => {
-                use AlignContent::*;
-                style.align_content = match align {
-                    align::AlignContent::ContentDistribution(distribution) => match distribution {
-                        align::ContentDistribution::SpaceBetween => SpaceBetween,
-                        align::ContentDistribution::SpaceAround => SpaceAround,
-                        align::ContentDistribution::SpaceEvenly => SpaceEvenly,
-                        align::ContentDistribution::Stretch => Stretch,
-                    },
-                    align::AlignContent::ContentPosition {
-                        value: position, ..
-                    } => match position {
-                        align::ContentPosition::Center => Center,
-                        align::ContentPosition::Start | align::ContentPosition::FlexStart => {
-                            FlexStart
-                        }
-                        align::ContentPosition::End | align::ContentPosition::FlexEnd => FlexEnd,
-                    },
-                    _ => return,
-                };
+                style.align_content = convert_align_content(align);
             }
             Property::JustifyContent(justify, _) => {
                 use JustifyContent::*;
                 style.justify_content = match justify {
                     align::JustifyContent::ContentDistribution(distribution) => {
                         match distribution {
-                            align::ContentDistribution::SpaceBetween => SpaceBetween,
-                            align::ContentDistribution::SpaceAround => SpaceAround,
-                            align::ContentDistribution::SpaceEvenly => SpaceEvenly,
-                            _ => return,
+                            align::ContentDistribution::SpaceBetween => Some(SpaceBetween),
+                            align::ContentDistribution::SpaceAround => Some(SpaceAround),
+                            align::ContentDistribution::SpaceEvenly => Some(SpaceEvenly),
+                            align::ContentDistribution::Stretch => Some(Stretch),
                         }
                     }
                     align::JustifyContent::ContentPosition {
                         value: position, ..
-                    } => match position {
+                    } => Some(match position {
                         align::ContentPosition::Center => Center,
-                        // start ignores -reverse flex-direction but there is no way to specify that in Taffy
-                        align::ContentPosition::Start | align::ContentPosition::FlexStart => {
-                            FlexStart
-                        }
-                        // end ignores -reverse flex-direction but there is no way to specify that in Taffy
-                        align::ContentPosition::End | align::ContentPosition::FlexEnd => FlexEnd,
-                    },
+                        align::ContentPosition::Start => Start,
+                        align::ContentPosition::FlexStart => FlexStart,
+                        align::ContentPosition::End => End,
+                        align::ContentPosition::FlexEnd => FlexEnd,
+                    }),
                     _ => return,
                 };
             }
             Property::AlignSelf(align, _) => {
-                use AlignSelf::*;
                 style.align_self = match align {
-                    align::AlignSelf::Auto => Auto,
-                    align::AlignSelf::Stretch => Stretch,
-                    align::AlignSelf::BaselinePosition(_) => Baseline,
+                    align::AlignSelf::Auto => None,
+                    align::AlignSelf::Stretch => Some(AlignSelf::Stretch),
+                    align::AlignSelf::BaselinePosition(_) => Some(AlignSelf::Baseline),
                     align::AlignSelf::SelfPosition {
                         value: position, ..
-                    } => match position {
-                        align::SelfPosition::Center => Center,
-                        align::SelfPosition::Start
-                        | align::SelfPosition::SelfStart
-                        | align::SelfPosition::FlexStart => FlexStart,
-                        align::SelfPosition::End
-                        | align::SelfPosition::SelfEnd
-                        | align::SelfPosition::FlexEnd => FlexEnd,
-                    },
+                    } => Some(convert_self_position(position)),
                     _ => return,
                 };
             }
             Property::AlignItems(align, _) => {
-                use AlignItems::*;
                 style.align_items = match align {
-                    align::AlignItems::BaselinePosition(_) => Baseline,
-                    align::AlignItems::Stretch => Stretch,
+                    align::AlignItems::BaselinePosition(_) => Some(AlignItems::Baseline),
+                    align::AlignItems::Stretch => Some(AlignItems::Stretch),
                     align::AlignItems::SelfPosition {
                         value: position, ..
-                    } => match position {
-                        align::SelfPosition::Center => Center,
-                        align::SelfPosition::FlexStart => FlexStart,
-                        align::SelfPosition::FlexEnd => FlexEnd,
-                        _ => return,
-                    },
+                    } => Some(convert_self_position(position)),
+                    _ => return,
+                };
+            }
+            Property::JustifySelf(justify) => {
+                style.justify_self = match justify {
+                    align::JustifySelf::Auto => None,
+                    align::JustifySelf::Stretch => Some(AlignSelf::Stretch),
+                    align::JustifySelf::BaselinePosition(_) => Some(AlignSelf::Baseline),
+                    align::JustifySelf::SelfPosition {
+                        value: position, ..
+                    } => Some(convert_self_position(position)),
                     _ => return,
                 };
             }
+            Property::JustifyItems(justify) => {
+                style.justify_items = match justify {
+                    align::JustifyItems::Stretch => Some(AlignItems::Stretch),
+                    align::JustifyItems::BaselinePosition(_) => Some(AlignItems::Baseline),
+                    align::JustifyItems::SelfPosition {
+                        value: position, ..
+                    } => Some(convert_self_position(position)),
+                    _ => return,
+                };
+            }
+            // the gap between rows is vertical, so it's the height of the gap
             Property::RowGap(row_gap) => {
-                style.gap.width = convert_gap_value(row_gap);
+                style.gap.height = convert_gap_value(row_gap);
             }
             Property::ColumnGap(column_gap) => {
-                style.gap.height = convert_gap_value(column_gap);
+                style.gap.width = convert_gap_value(column_gap);
             }
             Property::Gap(gap) => {
                 style.gap = Size {
-                    width: convert_gap_value(gap.row),
-                    height: convert_gap_value(gap.column),
+                    width: convert_gap_value(gap.column),
+                    height: convert_gap_value(gap.row),
                 };
             }
             Property::MarginTop(margin) => {
@@ -266,23 +269,23 @@ pub fn apply_layout_attributes(name: &str, value: &str, style: &mut Style) {
                 };
             }
             Property::PaddingTop(padding) => {
-                style.padding.top = convert_length_percentage_or_auto(padding);
+                style.padding.top = convert_padding(padding);
             }
             Property::PaddingBottom(padding) => {
-                style.padding.bottom = convert_length_percentage_or_auto(padding);
+                style.padding.bottom = convert_padding(padding);
             }
             Property::PaddingLeft(padding) => {
-                style.padding.left = convert_length_percentage_or_auto(padding);
+                style.padding.left = convert_padding(padding);
             }
             Property::PaddingRight(padding) => {
-                style.padding.right = convert_length_percentage_or_auto(padding);
+                style.padding.right = convert_padding(padding);
             }
             Property::Padding(padding) => {
                 style.padding = Rect {
-                    top: convert_length_percentage_or_auto(padding.top),
-                    bottom: convert_length_percentage_or_auto(padding.bottom),
-                    left: convert_length_percentage_or_auto(padding.left),
-                    right: convert_length_percentage_or_auto(padding.right),
+                    top: convert_padding(padding.top),
+                    bottom: convert_padding(padding.bottom),
+                    left: convert_padding(padding.left),
+                    right: convert_padding(padding.right),
                 };
             }
             Property::Width(width) => {
@@ -291,6 +294,60 @@ pub fn apply_layout_attributes(name: &str, value: &str, style: &mut Style) {
             Property::Height(height) => {
                 style.size.height = convert_size(height);
             }
+            Property::MinWidth(width) => {
+                style.min_size.width = convert_size(width);
+            }
+            Property::MinHeight(height) => {
+                style.min_size.height = convert_size(height);
+            }
+            Property::MaxWidth(width) => {
+                style.max_size.width = convert_max_size(width);
+            }
+            Property::MaxHeight(height) => {
+                style.max_size.height = convert_max_size(height);
+            }
+            Property::GridTemplateColumns(columns) => {
+                style.grid_template_columns = convert_grid_track_sizing(columns);
+            }
+            Property::GridTemplateRows(rows) => {
+                style.grid_template_rows = convert_grid_track_sizing(rows);
+            }
+            Property::GridAutoColumns(columns) => {
+                style.grid_auto_columns =
+                    columns.0.into_iter().map(convert_grid_track_size).collect();
+            }
+            Property::GridAutoRows(rows) => {
+                style.grid_auto_rows = rows.0.into_iter().map(convert_grid_track_size).collect();
+            }
+            Property::GridAutoFlow(flow) => {
+                let dense = flow.contains(grid::GridAutoFlow::Dense);
+                style.grid_auto_flow = match (flow.contains(grid::GridAutoFlow::Column), dense) {
+                    (false, false) => GridAutoFlow::Row,
+                    (false, true) => GridAutoFlow::RowDense,
+                    (true, false) => GridAutoFlow::Column,
+                    (true, true) => GridAutoFlow::ColumnDense,
+                };
+            }
+            Property::GridColumnStart(start) => {
+                style.grid_column.start = convert_grid_placement(start);
+            }
+            Property::GridColumnEnd(end) => {
+                style.grid_column.end = convert_grid_placement(end);
+            }
+            Property::GridColumn(column) => {
+                style.grid_column.start = convert_grid_placement(column.start);
+                style.grid_column.end = convert_grid_placement(column.end);
+            }
+            Property::GridRowStart(start) => {
+                style.grid_row.start = convert_grid_placement(start);
+            }
+            Property::GridRowEnd(end) => {
+                style.grid_row.end = convert_grid_placement(end);
+            }
+            Property::GridRow(row) => {
+                style.grid_row.start = convert_grid_placement(row.start);
+                style.grid_row.end = convert_grid_placement(row.end);
+            }
             _ => (),
         }
         // currently not implemented in lightningcss
@@ -302,56 +359,226 @@ pub fn apply_layout_attributes(name: &str, value: &str, style: &mut Style) {
     }
 }
 
-fn convert_length_value(length_value: LengthValue) -> Dimension {
+fn convert_length_value(length_value: LengthValue) -> f32 {
     match length_value {
-        LengthValue::Px(value) => Dimension::Points(value),
+        LengthValue::Px(value) => value,
         _ => todo!(),
     }
 }
 
-fn convert_dimension_percentage(
-    dimension_percentage: DimensionPercentage<LengthValue>,
-) -> Dimension {
+fn convert_length_percentage(dimension_percentage: CssLengthPercentage) -> LengthPercentage {
     match dimension_percentage {
-        DimensionPercentage::Dimension(value) => convert_length_value(value),
-        DimensionPercentage::Percentage(percentage) => Dimension::Percent(percentage.0),
+        DimensionPercentage::Dimension(value) => {
+            LengthPercentage::Points(convert_length_value(value))
+        }
+        DimensionPercentage::Percentage(percentage) => LengthPercentage::Percent(percentage.0),
         _ => todo!(),
     }
 }
 
+fn convert_dimension(dimension_percentage: CssLengthPercentage) -> Dimension {
+    match convert_length_percentage(dimension_percentage) {
+        LengthPercentage::Points(points) => Dimension::Points(points),
+        LengthPercentage::Percent(percent) => Dimension::Percent(percent),
+    }
+}
+
 fn convert_length_percentage_or_auto(
     length_percentage_or_auto: LengthPercentageOrAuto,
+) -> LengthPercentageAuto {
+    match length_percentage_or_auto {
+        LengthPercentageOrAuto::Auto => LengthPercentageAuto::Auto,
+        LengthPercentageOrAuto::LengthPercentage(percentage) => {
+            match convert_length_percentage(percentage) {
+                LengthPercentage::Points(points) => LengthPercentageAuto::Points(points),
+                LengthPercentage::Percent(percent) => LengthPercentageAuto::Percent(percent),
+            }
+        }
+    }
+}
+
+fn convert_length_percentage_or_auto_dimension(
+    length_percentage_or_auto: LengthPercentageOrAuto,
 ) -> Dimension {
     match length_percentage_or_auto {
         LengthPercentageOrAuto::Auto => Dimension::Auto,
+        LengthPercentageOrAuto::LengthPercentage(percentage) => convert_dimension(percentage),
+    }
+}
+
+// padding can't be auto, so auto padding is none at all
+fn convert_padding(padding: LengthPercentageOrAuto) -> LengthPercentage {
+    match padding {
+        LengthPercentageOrAuto::Auto => LengthPercentage::Points(0.0),
         LengthPercentageOrAuto::LengthPercentage(percentage) => {
-            convert_dimension_percentage(percentage)
+            convert_length_percentage(percentage)
         }
     }
 }
 
-fn convert_border_side_width(border_side_width: BorderSideWidth) -> Dimension {
+fn convert_border_side_width(border_side_width: BorderSideWidth) -> LengthPercentage {
     match border_side_width {
-        BorderSideWidth::Length(Length::Value(value)) => convert_length_value(value),
-        BorderSideWidth::Thick => Dimension::Points(5.0),
-        BorderSideWidth::Medium => Dimension::Points(3.0),
-        BorderSideWidth::Thin => Dimension::Points(1.0),
+        BorderSideWidth::Length(Length::Value(value)) => {
+            LengthPercentage::Points(convert_length_value(value))
+        }
+        BorderSideWidth::Thick => LengthPercentage::Points(5.0),
+        BorderSideWidth::Medium => LengthPercentage::Points(3.0),
+        BorderSideWidth::Thin => LengthPercentage::Points(1.0),
         _ => todo!(),
     }
 }
 
-fn convert_gap_value(gap_value: GapValue) -> Dimension {
+fn convert_gap_value(gap_value: GapValue) -> LengthPercentage {
     match gap_value {
-        GapValue::LengthPercentage(dim) => convert_dimension_percentage(dim),
-        GapValue::Normal => Dimension::Auto,
+        GapValue::LengthPercentage(dim) => convert_length_percentage(dim),
+        GapValue::Normal => LengthPercentage::Points(0.0),
     }
 }
 
 fn convert_size(size: size::Size) -> Dimension {
     match size {
         size::Size::Auto => Dimension::Auto,
-        size::Size::LengthPercentage(length) => convert_dimension_percentage(length),
-        _ => todo!(),
+        size::Size::LengthPercentage(length) => convert_dimension(length),
+        size::Size::MinContent(_) => intrinsic_size("min-content"),
+        size::Size::MaxContent(_) => intrinsic_size("max-content"),
+        size::Size::FitContent(_) | size::Size::FitContentFunction(_) => {
+            intrinsic_size("fit-content")
+        }
+        _ => intrinsic_size("stretch"),
+    }
+}
+
+fn convert_max_size(size: size::MaxSize) -> Dimension {
+    match size {
+        size::MaxSize::None => Dimension::Auto,
+        size::MaxSize::LengthPercentage(length) => convert_dimension(length),
+        size::MaxSize::MinContent(_) => intrinsic_size("min-content"),
+        size::MaxSize::MaxContent(_) => intrinsic_size("max-content"),
+        size::MaxSize::FitContent(_) => intrinsic_size("fit-content"),
+        // the content is never sized past the limit, which is as close as taffy gets
+        size::MaxSize::FitContentFunction(length) => convert_dimension(length),
+        _ => intrinsic_size("stretch"),
+    }
+}
+
+// taffy sizes auto nodes to their content, which is the closest it has to the content sizing keywords
+fn intrinsic_size(keyword: &str) -> Dimension {
+    log::warn!(
+        "{} sizes aren't supported by the layout, falling back to auto",
+        keyword
+    );
+    Dimension::Auto
+}
+
+fn convert_align_content(align: align::AlignContent) -> Option<AlignContent> {
+    use AlignContent::*;
+    match align {
+        align::AlignContent::ContentDistribution(distribution) => Some(match distribution {
+            align::ContentDistribution::SpaceBetween => SpaceBetween,
+            align::ContentDistribution::SpaceAround => SpaceAround,
+            align::ContentDistribution::SpaceEvenly => SpaceEvenly,
+            align::ContentDistribution::Stretch => Stretch,
+        }),
+        align::AlignContent::ContentPosition {
+            value: position, ..
+        } => Some(match position {
+            align::ContentPosition::Center => Center,
+            align::ContentPosition::Start => Start,
+            align::ContentPosition::FlexStart => FlexStart,
+            align::ContentPosition::End => End,
+            align::ContentPosition::FlexEnd => FlexEnd,
+        }),
+        _ => None,
+    }
+}
+
+fn convert_self_position(position: align::SelfPosition) -> AlignItems {
+    use AlignItems::*;
+    match position {
+        align::SelfPosition::Center => Center,
+        align::SelfPosition::Start | align::SelfPosition::SelfStart => Start,
+        align::SelfPosition::FlexStart => FlexStart,
+        align::SelfPosition::End | align::SelfPosition::SelfEnd => End,
+        align::SelfPosition::FlexEnd => FlexEnd,
+    }
+}
+
+fn convert_grid_track_sizing(sizing: grid::TrackSizing) -> Vec<TrackSizingFunction> {
+    match sizing {
+        grid::TrackSizing::None => Vec::new(),
+        grid::TrackSizing::TrackList(list) => list
+            .items
+            .into_iter()
+            .map(|item| match item {
+                grid::TrackListItem::TrackSize(size) => {
+                    TrackSizingFunction::Single(convert_grid_track_size(size))
+                }
+                grid::TrackListItem::TrackRepeat(repeat) => TrackSizingFunction::Repeat(
+                    match repeat.count {
+                        grid::RepeatCount::Number(count) => {
+                            GridTrackRepetition::Count(count.max(1) as u16)
+                        }
+                        grid::RepeatCount::AutoFill => GridTrackRepetition::AutoFill,
+                        grid::RepeatCount::AutoFit => GridTrackRepetition::AutoFit,
+                    },
+                    repeat
+                        .track_sizes
+                        .into_iter()
+                        .map(convert_grid_track_size)
+                        .collect(),
+                ),
+            })
+            .collect(),
+    }
+}
+
+fn convert_grid_track_size(size: grid::TrackSize) -> NonRepeatedTrackSizingFunction {
+    match size {
+        grid::TrackSize::TrackBreadth(breadth) => NonRepeatedTrackSizingFunction {
+            min: convert_min_track_breadth(&breadth),
+            max: convert_max_track_breadth(&breadth),
+        },
+        grid::TrackSize::MinMax { min, max } => NonRepeatedTrackSizingFunction {
+            min: convert_min_track_breadth(&min),
+            max: convert_max_track_breadth(&max),
+        },
+        grid::TrackSize::FitContent(limit) => NonRepeatedTrackSizingFunction {
+            min: MinTrackSizingFunction::Auto,
+            max: MaxTrackSizingFunction::FitContent(convert_length_percentage(limit)),
+        },
+    }
+}
+
+// a flexible track like 1fr is at least as big as its contents, like minmax(auto, 1fr)
+fn convert_min_track_breadth(breadth: &grid::TrackBreadth) -> MinTrackSizingFunction {
+    match breadth {
+        grid::TrackBreadth::Length(length) => {
+            MinTrackSizingFunction::Fixed(convert_length_percentage(length.clone()))
+        }
+        grid::TrackBreadth::MinContent => MinTrackSizingFunction::MinContent,
+        grid::TrackBreadth::MaxContent => MinTrackSizingFunction::MaxContent,
+        grid::TrackBreadth::Auto | grid::TrackBreadth::Flex(_) => MinTrackSizingFunction::Auto,
+    }
+}
+
+fn convert_max_track_breadth(breadth: &grid::TrackBreadth) -> MaxTrackSizingFunction {
+    match breadth {
+        grid::TrackBreadth::Length(length) => {
+            MaxTrackSizingFunction::Fixed(convert_length_percentage(length.clone()))
+        }
+        grid::TrackBreadth::Flex(fraction) => MaxTrackSizingFunction::Flex(*fraction),
+        grid::TrackBreadth::MinContent => MaxTrackSizingFunction::MinContent,
+        grid::TrackBreadth::MaxContent => MaxTrackSizingFunction::MaxContent,
+        grid::TrackBreadth::Auto => MaxTrackSizingFunction::Auto,
+    }
+}
+
+// named lines and areas aren't supported, so they're placed automatically
+fn convert_grid_placement(placement: grid::GridLine) -> GridPlacement {
+    match placement {
+        grid::GridLine::Line { index, .. } => line(index as i16),
+        grid::GridLine::Span { index, .. } => span(index.max(1) as u16),
+        _ => GridPlacement::Auto,
     }
 }
 
@@ -373,3 +600,86 @@ pub fn parse_value(value: &str) -> Option<Dimension> {
         None
     }
 }
+
+#[test]
+fn grid_attributes() {
+    let mut style = Style::default();
+    apply_layout_attributes("display", "grid", &mut style);
+    apply_layout_attributes(
+        "grid-template-columns",
+        "10px 1fr repeat(2, 20%)",
+        &mut style,
+    );
+    apply_layout_attributes("gap", "1px 2px", &mut style);
+    apply_layout_attributes("grid-column", "span 2", &mut style);
+    apply_layout_attributes("min-width", "50%", &mut style);
+    apply_layout_attributes("max-height", "30px", &mut style);
+
+    assert_eq!(style.display, Display::Grid);
+    assert_eq!(
+        style.grid_template_columns,
+        vec![
+            TrackSizingFunction::Single(NonRepeatedTrackSizingFunction {
+                min: MinTrackSizingFunction::Fixed(LengthPercentage::Points(10.0)),
+                max: MaxTrackSizingFunction::Fixed(LengthPercentage::Points(10.0)),
+            }),
+            TrackSizingFunction::Single(NonRepeatedTrackSizingFunction {
+                min: MinTrackSizingFunction::Auto,
+                max: MaxTrackSizingFunction::Flex(1.0),
+            }),
+            TrackSizingFunction::Repeat(
+                GridTrackRepetition::Count(2),
+                vec![NonRepeatedTrackSizingFunction {
+                    min: MinTrackSizingFunction::Fixed(LengthPercentage::Percent(0.2)),
+                    max: MaxTrackSizingFunction::Fixed(LengthPercentage::Percent(0.2)),
+                }]
+            ),
+        ]
+    );
+    assert_eq!(
+        style.gap,
+        Size {
+            width: LengthPercentage::Points(2.0),
+            height: LengthPercentage::Points(1.0),
+        }
+    );
+    assert_eq!(style.grid_column.start, span(2));
+    assert_eq!(style.min_size.width, Dimension::Percent(0.5));
+    assert_eq!(style.max_size.height, Dimension::Points(30.0));
+}
+
+#[test]
+fn min_content_sizes() {
+    let mut style = Style::default();
+    apply_layout_attributes("width", "10px", &mut style);
+    apply_layout_attributes("width", "min-content", &mut style);
+    apply_layout_attributes("max-height", "min-content", &mut style);
+
+    assert_eq!(style.size.width, Dimension::Auto);
+    assert_eq!(style.max_size.height, Dimension::Auto);
+}
+
+#[test]
+fn max_content_sizes() {
+    let mut style = Style::default();
+    apply_layout_attributes("min-width", "10px", &mut style);
+    apply_layout_attributes("min-width", "max-content", &mut style);
+    apply_layout_attributes("max-width", "max-content", &mut style);
+
+    assert_eq!(style.min_size.width, Dimension::Auto);
+    assert_eq!(style.max_size.width, Dimension::Auto);
+}
+
+#[test]
+fn fit_content_sizes() {
+    let mut style = Style::default();
+    apply_layout_attributes("height", "fit-content", &mut style);
+    apply_layout_attributes("width", "fit-content(20px)", &mut style);
+    apply_layout_attributes("max-width", "fit-content(30px)", &mut style);
+    apply_layout_attributes("max-height", "fit-content", &mut style);
+
+    assert_eq!(style.size.height, Dimension::Auto);
+    assert_eq!(style.size.width, Dimension::Auto);
+    assert_eq!(style.max_size.width, Dimension::Points(30.0));
+    assert_eq!(style.max_size.height, Dimension::Auto);
+}
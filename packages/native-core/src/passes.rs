@@ -1,9 +1,9 @@
 use crate::tree::{NodeId, TreeView};
 use crate::{FxDashSet, SendAnyMap};
+use rayon::prelude::*;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::collections::BTreeMap;
 use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign};
-use std::sync::Arc;
 
 #[derive(Default)]
 struct DirtyNodes {
@@ -36,6 +36,14 @@ impl DirtyNodes {
         self.passes_dirty[index] &= !encoded;
         Some(NodeId((index * 64) + node_id as usize))
     }
+
+    fn drain(mut self) -> Vec<NodeId> {
+        let mut nodes = Vec::new();
+        while let Some(node_id) = self.pop() {
+            nodes.push(node_id);
+        }
+        nodes
+    }
 }
 
 #[derive(Default)]
@@ -96,8 +104,46 @@ impl DirtyNodeStates {
 
         Some((height, node_id))
     }
+
+    /// Take every node at the lowest height that is dirty for the pass
+    fn pop_front_height(&mut self, pass_id: PassId) -> Option<(u16, Vec<NodeId>)> {
+        let (&height, values) = self
+            .dirty
+            .iter_mut()
+            .find(|(_, values)| values.contains_key(&pass_id))?;
+        let dirty = values.remove(&pass_id)?;
+        if values.is_empty() {
+            self.dirty.remove(&height);
+        }
+
+        Some((height, dirty.drain()))
+    }
+
+    /// Take every node at the highest height that is dirty for the pass
+    fn pop_back_height(&mut self, pass_id: PassId) -> Option<(u16, Vec<NodeId>)> {
+        let (&height, values) = self
+            .dirty
+            .iter_mut()
+            .rev()
+            .find(|(_, values)| values.contains_key(&pass_id))?;
+        let dirty = values.remove(&pass_id)?;
+        if values.is_empty() {
+            self.dirty.remove(&height);
+        }
+
+        Some((height, dirty.drain()))
+    }
 }
 
+/// A node that is resolved on one of the threads of a parallel pass
+///
+/// Every node in a batch is at the same height, so no two threads get the same node, and the parents or children they
+/// read are never written while the batch is resolved.
+struct SharedNode<T>(*mut T);
+
+unsafe impl<T: Send + Sync> Send for SharedNode<T> {}
+unsafe impl<T: Send + Sync> Sync for SharedNode<T> {}
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
 pub struct PassId(pub u64);
 
@@ -154,7 +200,7 @@ pub trait UpwardPass<T>: Pass {
     fn pass<'a>(
         &self,
         node: &mut T,
-        children: &mut dyn Iterator<Item = &'a mut T>,
+        children: &mut dyn Iterator<Item = &'a T>,
         ctx: &SendAnyMap,
     ) -> PassReturn;
 }
@@ -168,27 +214,76 @@ fn resolve_upward_pass<T, P: UpwardPass<T> + ?Sized>(
 ) {
     let pass_id = pass.pass_id();
     while let Some((height, id)) = dirty_states.pop_back(pass_id) {
-        let (node, mut children) = tree.parent_child_mut(id).unwrap();
+        let (node, children) = tree.parent_child_mut(id).unwrap();
+        let mut children = children.map(|child| &*child);
         let result = pass.pass(node, &mut children, ctx);
         drop(children);
-        if result.progress || result.mark_dirty {
-            nodes_updated.insert(id);
-            if let Some(id) = tree.parent_id(id) {
-                if result.mark_dirty {
-                    for dependant in pass.dependants() {
-                        dirty_states.insert(*dependant, id, height - 1);
-                    }
-                }
-                if result.progress && height > 0 {
-                    dirty_states.insert(pass_id, id, height - 1);
+        mark_upward_dirty(tree, pass, dirty_states, nodes_updated, id, height, result);
+    }
+}
+
+fn resolve_upward_pass_parallel<T: Send + Sync, P: UpwardPass<T> + Sync + ?Sized>(
+    tree: &mut impl TreeView<T>,
+    pass: &P,
+    dirty_states: &mut DirtyNodeStates,
+    nodes_updated: &FxDashSet<NodeId>,
+    ctx: &SendAnyMap,
+) {
+    let pass_id = pass.pass_id();
+    while let Some((height, ids)) = dirty_states.pop_back_height(pass_id) {
+        let mut batch = Vec::with_capacity(ids.len());
+        for id in ids {
+            let children_ids = tree.children_ids(id).unwrap().to_vec();
+            let node = SharedNode(tree.get_ptr(id).unwrap());
+            let children: Vec<_> = children_ids
+                .into_iter()
+                .map(|child| SharedNode(tree.get_ptr(child).unwrap()))
+                .collect();
+            batch.push((id, node, children));
+        }
+
+        let results: Vec<_> = batch
+            .into_par_iter()
+            .map(|(id, node, children)| {
+                // Safety: the node is only in this batch once, and its children are lower in the tree than the batch
+                let node = unsafe { &mut *node.0 };
+                let mut children = children.iter().map(|child| unsafe { &*child.0 });
+                (id, pass.pass(node, &mut children, ctx))
+            })
+            .collect();
+
+        for (id, result) in results {
+            mark_upward_dirty(tree, pass, dirty_states, nodes_updated, id, height, result);
+        }
+    }
+}
+
+fn mark_upward_dirty<T, P: UpwardPass<T> + ?Sized>(
+    tree: &impl TreeView<T>,
+    pass: &P,
+    dirty_states: &mut DirtyNodeStates,
+    nodes_updated: &FxDashSet<NodeId>,
+    id: NodeId,
+    height: u16,
+    result: PassReturn,
+) {
+    if result.progress || result.mark_dirty {
+        nodes_updated.insert(id);
+        if let Some(id) = tree.parent_id(id) {
+            if result.mark_dirty {
+                for dependant in pass.dependants() {
+                    dirty_states.insert(*dependant, id, height - 1);
                 }
             }
+            if result.progress && height > 0 {
+                dirty_states.insert(pass.pass_id(), id, height - 1);
+            }
         }
     }
 }
 
 pub trait DownwardPass<T>: Pass {
-    fn pass(&self, node: &mut T, parent: Option<&mut T>, ctx: &SendAnyMap) -> PassReturn;
+    fn pass(&self, node: &mut T, parent: Option<&T>, ctx: &SendAnyMap) -> PassReturn;
 }
 
 fn resolve_downward_pass<T, P: DownwardPass<T> + ?Sized>(
@@ -201,21 +296,73 @@ fn resolve_downward_pass<T, P: DownwardPass<T> + ?Sized>(
     let pass_id = pass.pass_id();
     while let Some((height, id)) = dirty_states.pop_front(pass_id) {
         let (node, parent) = tree.node_parent_mut(id).unwrap();
-        let result = pass.pass(node, parent, ctx);
-        if result.mark_dirty {
-            nodes_updated.insert(id);
-        }
-        if result.mark_dirty || result.progress {
-            for id in tree.children_ids(id).unwrap() {
-                if result.mark_dirty {
-                    for dependant in pass.dependants() {
-                        dirty_states.insert(*dependant, *id, height + 1);
-                    }
+        let result = pass.pass(node, parent.map(|parent| &*parent), ctx);
+        mark_downward_dirty(tree, pass, dirty_states, nodes_updated, id, height, result);
+    }
+}
+
+fn resolve_downward_pass_parallel<T: Send + Sync, P: DownwardPass<T> + Sync + ?Sized>(
+    tree: &mut impl TreeView<T>,
+    pass: &P,
+    dirty_states: &mut DirtyNodeStates,
+    nodes_updated: &FxDashSet<NodeId>,
+    ctx: &SendAnyMap,
+) {
+    let pass_id = pass.pass_id();
+    while let Some((height, ids)) = dirty_states.pop_front_height(pass_id) {
+        // siblings share their parent, so each parent is only looked up once
+        let mut parents: FxHashMap<NodeId, SharedNode<T>> = FxHashMap::default();
+        let mut batch = Vec::with_capacity(ids.len());
+        for id in ids {
+            let parent_id = tree.parent_id(id);
+            if let Some(parent_id) = parent_id {
+                if !parents.contains_key(&parent_id) {
+                    let parent = SharedNode(tree.get_ptr(parent_id).unwrap());
+                    parents.insert(parent_id, parent);
                 }
-                if result.progress {
-                    dirty_states.insert(pass_id, *id, height + 1);
+            }
+            let node = SharedNode(tree.get_ptr(id).unwrap());
+            batch.push((id, node, parent_id));
+        }
+
+        let results: Vec<_> = batch
+            .into_par_iter()
+            .map(|(id, node, parent_id)| {
+                // Safety: the node is only in this batch once, and its parent is higher in the tree than the batch
+                let node = unsafe { &mut *node.0 };
+                let parent = parent_id.map(|parent_id| unsafe { &*parents[&parent_id].0 });
+                (id, pass.pass(node, parent, ctx))
+            })
+            .collect();
+
+        for (id, result) in results {
+            mark_downward_dirty(tree, pass, dirty_states, nodes_updated, id, height, result);
+        }
+    }
+}
+
+fn mark_downward_dirty<T, P: DownwardPass<T> + ?Sized>(
+    tree: &impl TreeView<T>,
+    pass: &P,
+    dirty_states: &mut DirtyNodeStates,
+    nodes_updated: &FxDashSet<NodeId>,
+    id: NodeId,
+    height: u16,
+    result: PassReturn,
+) {
+    if result.mark_dirty {
+        nodes_updated.insert(id);
+    }
+    if result.mark_dirty || result.progress {
+        for id in tree.children_ids(id).unwrap() {
+            if result.mark_dirty {
+                for dependant in pass.dependants() {
+                    dirty_states.insert(*dependant, *id, height + 1);
                 }
             }
+            if result.progress {
+                dirty_states.insert(pass.pass_id(), *id, height + 1);
+            }
         }
     }
 }
@@ -243,6 +390,41 @@ fn resolve_node_pass<T, P: NodePass<T> + ?Sized>(
     }
 }
 
+fn resolve_node_pass_parallel<T: Send + Sync, P: NodePass<T> + Sync + ?Sized>(
+    tree: &mut impl TreeView<T>,
+    pass: &P,
+    dirty_states: &mut DirtyNodeStates,
+    nodes_updated: &FxDashSet<NodeId>,
+    ctx: &SendAnyMap,
+) {
+    // a node pass only reads the node it updates, so every dirty node can be resolved at once
+    let pass_id = pass.pass_id();
+    let mut batch = Vec::new();
+    while let Some((height, ids)) = dirty_states.pop_back_height(pass_id) {
+        for id in ids {
+            batch.push((id, height, SharedNode(tree.get_ptr(id).unwrap())));
+        }
+    }
+
+    let results: Vec<_> = batch
+        .into_par_iter()
+        .map(|(id, height, node)| {
+            // Safety: every node is only in the batch once
+            let node = unsafe { &mut *node.0 };
+            (id, height, pass.pass(node, ctx))
+        })
+        .collect();
+
+    for (id, height, updated) in results {
+        if updated {
+            nodes_updated.insert(id);
+            for dependant in pass.dependants() {
+                dirty_states.insert(*dependant, id, height);
+            }
+        }
+    }
+}
+
 pub enum AnyPass<T: 'static> {
     Upward(&'static (dyn UpwardPass<T> + Send + Sync + 'static)),
     Downward(&'static (dyn DownwardPass<T> + Send + Sync + 'static)),
@@ -283,94 +465,79 @@ impl<T> AnyPass<T> {
             Self::Node(pass) => resolve_node_pass(tree, *pass, dirty_states, nodes_updated, ctx),
         }
     }
+
+    fn resolve_parallel(
+        &self,
+        tree: &mut impl TreeView<T>,
+        dirty_states: &mut DirtyNodeStates,
+        nodes_updated: &FxDashSet<NodeId>,
+        ctx: &SendAnyMap,
+    ) where
+        T: Send + Sync,
+    {
+        match self {
+            Self::Downward(pass) => {
+                resolve_downward_pass_parallel(tree, *pass, dirty_states, nodes_updated, ctx)
+            }
+            Self::Upward(pass) => {
+                resolve_upward_pass_parallel(tree, *pass, dirty_states, nodes_updated, ctx)
+            }
+            Self::Node(pass) => {
+                resolve_node_pass_parallel(tree, *pass, dirty_states, nodes_updated, ctx)
+            }
+        }
+    }
 }
 
-pub fn resolve_passes<T, Tr: TreeView<T> + Sync + Send>(
+/// Resolve the passes one after the other, each after the passes it depends on
+fn resolve_in_order<T>(mut passes: Vec<&AnyPass<T>>, mut resolve: impl FnMut(&AnyPass<T>)) {
+    let mut resolved_passes: FxHashSet<PassId> = FxHashSet::default();
+    while !passes.is_empty() {
+        let next = passes.iter().position(|pass| {
+            let pass_id = pass.pass_id();
+            pass.dependancies()
+                .iter()
+                .all(|d| resolved_passes.contains(d) || *d == pass_id)
+        });
+        match next {
+            Some(i) => {
+                let pass = passes.remove(i);
+                resolve(pass);
+                resolved_passes.insert(pass.pass_id());
+            }
+            None => panic!("the passes have a dependency cycle"),
+        }
+    }
+}
+
+/// Resolve the passes for the dirty nodes. The dirty nodes at the same height in the tree don't depend on each other for
+/// a pass, so they're resolved in parallel.
+pub fn resolve_passes<T: Send + Sync, Tr: TreeView<T> + Sync + Send>(
     tree: &mut Tr,
     dirty_nodes: DirtyNodeStates,
     passes: Vec<&AnyPass<T>>,
     ctx: SendAnyMap,
 ) -> FxDashSet<NodeId> {
-    resolve_passes_single_threaded(tree, dirty_nodes, passes, ctx)
-    // TODO: multithreadeding has some safety issues currently that need to be resolved before it can be used
-    // let dirty_states = Arc::new(dirty_nodes);
-    // let mut resolved_passes: FxHashSet<PassId> = FxHashSet::default();
-    // let mut resolving = Vec::new();
-    // let nodes_updated = Arc::new(FxDashSet::default());
-    // let ctx = Arc::new(ctx);
-    // while !passes.is_empty() {
-    //     let mut currently_borrowed = MemberMask::default();
-    //     std::thread::scope(|s| {
-    //         let mut i = 0;
-    //         while i < passes.len() {
-    //             let pass = &passes[i];
-    //             let pass_id = pass.pass_id();
-    //             let pass_mask = pass.mask();
-    //             if pass
-    //                 .dependancies()
-    //                 .iter()
-    //                 .all(|d| resolved_passes.contains(d) || *d == pass_id)
-    //                 && !pass_mask.overlaps(currently_borrowed)
-    //             {
-    //                 let pass = passes.remove(i);
-    //                 resolving.push(pass_id);
-    //                 currently_borrowed |= pass_mask;
-    //                 let dirty_states = dirty_states.clone();
-    //                 let nodes_updated = nodes_updated.clone();
-    //                 let ctx = ctx.clone();
-    //                 let mut dirty = DirtyNodes::default();
-    //                 // dirty_states.all_dirty(pass_id, &mut dirty, tree);
-    //                 // this is safe because the member_mask acts as a per-member mutex and we have verified that the pass does not overlap with any other pass
-    //                 let tree_mut_unbounded = unsafe { &mut *(tree as *mut Tr) };
-    //                 s.spawn(move || {
-    //                     pass.resolve(
-    //                         tree_mut_unbounded,
-    //                         dirty,
-    //                         &dirty_states,
-    //                         &nodes_updated,
-    //                         &ctx,
-    //                     );
-    //                 });
-    //             } else {
-    //                 i += 1;
-    //             }
-    //         }
-    //         // all passes are resolved at the end of the scope
-    //     });
-    //     resolved_passes.extend(resolving.iter().copied());
-    //     resolving.clear()
-    // }
-    // std::sync::Arc::try_unwrap(nodes_updated).unwrap()
+    let mut dirty_states = dirty_nodes;
+    let nodes_updated = FxDashSet::default();
+    resolve_in_order(passes, |pass| {
+        pass.resolve_parallel(tree, &mut dirty_states, &nodes_updated, &ctx)
+    });
+    nodes_updated
 }
 
 pub fn resolve_passes_single_threaded<T, Tr: TreeView<T>>(
     tree: &mut Tr,
     dirty_nodes: DirtyNodeStates,
-    mut passes: Vec<&AnyPass<T>>,
+    passes: Vec<&AnyPass<T>>,
     ctx: SendAnyMap,
 ) -> FxDashSet<NodeId> {
     let mut dirty_states = dirty_nodes;
-    let mut resolved_passes: FxHashSet<PassId> = FxHashSet::default();
-    let nodes_updated = Arc::new(FxDashSet::default());
-    let ctx = Arc::new(ctx);
-    while !passes.is_empty() {
-        for (i, pass) in passes.iter().enumerate() {
-            let pass_id = pass.pass_id();
-            if pass
-                .dependancies()
-                .iter()
-                .all(|d| resolved_passes.contains(d) || *d == pass_id)
-            {
-                let pass = passes.remove(i);
-                let nodes_updated = nodes_updated.clone();
-                let ctx = ctx.clone();
-                pass.resolve(tree, &mut dirty_states, &nodes_updated, &ctx);
-                resolved_passes.insert(pass_id);
-                break;
-            }
-        }
-    }
-    std::sync::Arc::try_unwrap(nodes_updated).unwrap()
+    let nodes_updated = FxDashSet::default();
+    resolve_in_order(passes, |pass| {
+        pass.resolve(tree, &mut dirty_states, &nodes_updated, &ctx)
+    });
+    nodes_updated
 }
 
 #[test]
@@ -582,7 +749,7 @@ fn down_pass() {
         }
     }
     impl DownwardPass<i32> for AddPass {
-        fn pass(&self, node: &mut i32, parent: Option<&mut i32>, _: &SendAnyMap) -> PassReturn {
+        fn pass(&self, node: &mut i32, parent: Option<&i32>, _: &SendAnyMap) -> PassReturn {
             if let Some(parent) = parent {
                 *node += *parent;
             }
@@ -644,7 +811,7 @@ fn dependant_down_pass() {
         }
     }
     impl DownwardPass<i32> for AddPass {
-        fn pass(&self, node: &mut i32, parent: Option<&mut i32>, _: &SendAnyMap) -> PassReturn {
+        fn pass(&self, node: &mut i32, parent: Option<&i32>, _: &SendAnyMap) -> PassReturn {
             if let Some(parent) = parent {
                 *node += *parent;
             } else {
@@ -675,7 +842,7 @@ fn dependant_down_pass() {
         }
     }
     impl DownwardPass<i32> for SubtractPass {
-        fn pass(&self, node: &mut i32, parent: Option<&mut i32>, _: &SendAnyMap) -> PassReturn {
+        fn pass(&self, node: &mut i32, parent: Option<&i32>, _: &SendAnyMap) -> PassReturn {
             if let Some(parent) = parent {
                 *node -= *parent;
             } else {
@@ -767,7 +934,7 @@ fn up_pass() {
         fn pass<'a>(
             &self,
             node: &mut i32,
-            children: &mut dyn Iterator<Item = &'a mut i32>,
+            children: &mut dyn Iterator<Item = &'a i32>,
             _: &SendAnyMap,
         ) -> PassReturn {
             *node += children.map(|i| *i).sum::<i32>();
@@ -833,7 +1000,7 @@ fn dependant_up_pass() {
         fn pass<'a>(
             &self,
             node: &mut i32,
-            children: &mut dyn Iterator<Item = &'a mut i32>,
+            children: &mut dyn Iterator<Item = &'a i32>,
             _: &SendAnyMap,
         ) -> PassReturn {
             *node += children.map(|i| *i).sum::<i32>();
@@ -866,7 +1033,7 @@ fn dependant_up_pass() {
         fn pass<'a>(
             &self,
             node: &mut i32,
-            children: &mut dyn Iterator<Item = &'a mut i32>,
+            children: &mut dyn Iterator<Item = &'a i32>,
             _: &SendAnyMap,
         ) -> PassReturn {
             *node -= children.map(|i| *i).sum::<i32>();
@@ -909,3 +1076,81 @@ fn dependant_up_pass() {
     assert_eq!(tree.get(child2).unwrap(), &0);
     assert_eq!(tree.get(grandchild2).unwrap(), &1);
 }
+
+#[test]
+fn wide_up_pass() {
+    use crate::tree::{Tree, TreeLike};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    let mut tree = Tree::new(0);
+    let root = tree.root();
+    let mut dirty_nodes: DirtyNodeStates = DirtyNodeStates::default();
+    let mut children = Vec::new();
+    for _ in 0..100 {
+        let child = tree.create_node(0);
+        tree.add_child(root, child);
+        children.push(child);
+        for _ in 0..10 {
+            let grandchild = tree.create_node(0);
+            tree.add_child(child, grandchild);
+            dirty_nodes.insert(PassId(0), grandchild, 2);
+        }
+    }
+
+    struct CountPass;
+    impl Pass for CountPass {
+        fn pass_id(&self) -> PassId {
+            PassId(0)
+        }
+
+        fn dependancies(&self) -> &'static [PassId] {
+            &[PassId(0)]
+        }
+
+        fn dependants(&self) -> &'static [PassId] {
+            &[]
+        }
+
+        fn mask(&self) -> MemberMask {
+            MemberMask(0)
+        }
+    }
+
+    // every node counts the nodes in its subtree
+    impl UpwardPass<i32> for CountPass {
+        fn pass<'a>(
+            &self,
+            node: &mut i32,
+            children: &mut dyn Iterator<Item = &'a i32>,
+            _: &SendAnyMap,
+        ) -> PassReturn {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            let count = 1 + children.sum::<i32>();
+            let changed = *node != count;
+            *node = count;
+            PassReturn {
+                progress: changed,
+                mark_dirty: changed,
+            }
+        }
+    }
+
+    let count_pass = AnyPass::Upward(&CountPass);
+    resolve_passes(&mut tree, dirty_nodes, vec![&count_pass], SendAnyMap::new());
+    assert_eq!(tree.get(root).unwrap(), &1101);
+    assert_eq!(tree.get(children[0]).unwrap(), &11);
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1101);
+
+    // only the new node and the nodes above it are revisited
+    CALLS.store(0, Ordering::SeqCst);
+    let grandchild = tree.create_node(0);
+    tree.add_child(children[0], grandchild);
+    let mut dirty_nodes: DirtyNodeStates = DirtyNodeStates::default();
+    dirty_nodes.insert(PassId(0), grandchild, 2);
+    resolve_passes(&mut tree, dirty_nodes, vec![&count_pass], SendAnyMap::new());
+    assert_eq!(tree.get(root).unwrap(), &1102);
+    assert_eq!(tree.get(children[0]).unwrap(), &12);
+    assert_eq!(CALLS.load(Ordering::SeqCst), 3);
+}
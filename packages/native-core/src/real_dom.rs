@@ -72,6 +72,11 @@ impl<S: State<V>, V: FromAnyValue> RealDom<S, V> {
         self.node_id_mapping.get(element_id.0).unwrap().unwrap()
     }
 
+    /// The node of an element, or None if the element was never mounted
+    pub fn try_element_to_node_id(&self, element_id: ElementId) -> Option<RealNodeId> {
+        self.node_id_mapping.get(element_id.0).copied().flatten()
+    }
+
     fn set_element_id(&mut self, node_id: NodeId, element_id: ElementId) {
         let node = self.tree.get_mut(node_id).unwrap();
         let node_id = node.node_data.node_id;
@@ -299,7 +304,7 @@ impl<S: State<V>, V: FromAnyValue> RealDom<S, V> {
                     }
                     mark_dirty(node_id, NodeMask::new().with_text(), &mut nodes_updated);
                 }
-                NewEventListener { name, id } => {
+                NewEventListener { name, id, .. } => {
                     let node_id = self.element_to_node_id(id);
                     let node = self.tree.get_mut(node_id).unwrap();
                     if let NodeType::Element { listeners, .. } = &mut node.node_data.node_type {
@@ -428,9 +433,10 @@ impl<S: State<V>, V: FromAnyValue> RealDom<S, V> {
 impl<S: State<V> + Sync, V: FromAnyValue> RealDom<S, V>
 where
     Tree<Node<S, V>>: Sync + Send,
+    Node<S, V>: Sync + Send,
 {
     /// Update the state of the dom, after appling some mutations. This will keep the nodes in the dom up to date with their VNode counterparts.
-    /// This will resolve the state in parallel
+    /// Only the nodes that were changed and the nodes that depend on them are updated, and nodes at the same height in the tree are updated in parallel.
     pub fn update_state(
         &mut self,
         nodes_updated: DirtyNodeStates,
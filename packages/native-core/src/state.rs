@@ -221,7 +221,10 @@ pub trait State<V: FromAnyValue + 'static>: Default + Clone + 'static {
         dirty: DirtyNodeStates,
         tree: &mut T,
         ctx: SendAnyMap,
-    ) -> FxDashSet<RealNodeId> {
+    ) -> FxDashSet<RealNodeId>
+    where
+        Node<Self, V>: Send + Sync,
+    {
         let passes = Self::PASSES.iter().collect();
         resolve_passes(tree, dirty, passes, ctx)
     }
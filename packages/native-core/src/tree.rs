@@ -83,6 +83,10 @@ pub trait TreeView<T>: Sized {
         unsafe { self.get_mut(id).unwrap_unchecked() }
     }
 
+    /// Get a pointer to a node that stays valid until the tree is changed. Getting pointers to other nodes doesn't
+    /// invalidate it, so many nodes can be borrowed at once through them.
+    fn get_ptr(&mut self, id: NodeId) -> Option<*mut T>;
+
     fn children(&self, id: NodeId) -> Option<Self::Iterator<'_>>;
 
     fn children_mut(&mut self, id: NodeId) -> Option<Self::IteratorMut<'_>>;
@@ -222,6 +226,12 @@ impl<T> TreeView<T> for Tree<T> {
         self.nodes.get_mut(id.0).map(|node| &mut node.value)
     }
 
+    fn get_ptr(&mut self, id: NodeId) -> Option<*mut T> {
+        self.nodes
+            .get_ptr(id.0)
+            .map(|node| unsafe { std::ptr::addr_of_mut!((*node).value) })
+    }
+
     fn children(&self, id: NodeId) -> Option<Self::Iterator<'_>> {
         self.children_ids(id).map(|children_ids| ChildNodeIterator {
             nodes: &self.nodes,
@@ -609,6 +619,15 @@ impl<T> Slab<T> {
         self.data.get_mut(id).and_then(|x| x.as_mut())
     }
 
+    fn get_ptr(&mut self, id: usize) -> Option<*mut T> {
+        if id >= self.data.len() {
+            return None;
+        }
+        // the pointer is offset from the start of the data without borrowing the rest of it
+        let ptr = unsafe { self.data.as_mut_ptr().add(id) };
+        unsafe { (*ptr).as_mut().map(|item| item as *mut T) }
+    }
+
     unsafe fn get_unchecked_mut(&mut self, id: usize) -> &mut T {
         self.data.get_unchecked_mut(id).as_mut().unwrap()
     }
@@ -259,9 +259,16 @@ impl Cursor {
                     self.start.right(text);
                 }
             }
+            KeyA if data.modifiers().contains(Modifiers::CONTROL) => {
+                self.select_all(text);
+            }
             _ => {
                 self.start.realize_col(text);
                 if let Key::Character(character) = data.key() {
+                    // shortcuts like ctrl+c don't type the character
+                    if data.modifiers().contains(Modifiers::CONTROL) {
+                        return;
+                    }
                     if text.len() + 1 - self.selection_len(text) <= max_width {
                         self.delete_selection(text);
                         let character = character.chars().next().unwrap();
@@ -273,6 +280,31 @@ impl Cursor {
         }
     }
 
+    /// Select all of the text
+    pub fn select_all(&mut self, text: &str) {
+        let mut end = Pos::new(usize::MAX, text.lines().count().saturating_sub(1));
+        end.realize_col(text);
+        self.start = Pos::new(0, 0);
+        self.end = Some(end);
+    }
+
+    /// The part of the text that is selected
+    pub fn selection<'a>(&self, text: &'a str) -> &'a str {
+        &text[self.first().idx(text)..self.last().idx(text)]
+    }
+
+    /// Replace the selection with some text, or insert it at the cursor if nothing is selected, unless the text would
+    /// get longer than max_width
+    pub fn insert_str(&mut self, text: &mut String, inserted: &str, max_width: usize) {
+        if text.len() + inserted.len() - self.selection_len(text) > max_width {
+            return;
+        }
+        self.start.realize_col(text);
+        self.delete_selection(text);
+        text.insert_str(self.start.idx(text), inserted);
+        self.start.move_col(inserted.len() as i32, text);
+    }
+
     pub fn with_end(&mut self, f: impl FnOnce(&mut Pos)) {
         let mut new = self.end.take().unwrap_or_else(|| self.start.clone());
         f(&mut new);
@@ -482,3 +514,32 @@ fn cursor_input() {
 
     assert_eq!(text.to_string(), goal_text);
 }
+
+#[test]
+fn cursor_selection() {
+    let mut cursor = Cursor::from_start(Pos::new(0, 0));
+    let mut text = "hello world".to_string();
+
+    cursor.handle_input(
+        &dioxus_html::KeyboardData::new(
+            dioxus_html::input_data::keyboard_types::Key::Character("a".to_string()),
+            dioxus_html::input_data::keyboard_types::Code::KeyA,
+            dioxus_html::input_data::keyboard_types::Location::Standard,
+            false,
+            Modifiers::CONTROL,
+        ),
+        &mut text,
+        20,
+    );
+    assert_eq!(cursor.selection(&text), "hello world");
+
+    cursor.insert_str(&mut text, "hi", 20);
+    assert_eq!(text, "hi");
+    assert_eq!(cursor.selection(&text), "");
+
+    // the text can't get longer than the max width
+    cursor.insert_str(&mut text, " there", 5);
+    assert_eq!(text, "hi");
+    cursor.insert_str(&mut text, " there", 20);
+    assert_eq!(text, "hi there");
+}
@@ -0,0 +1,51 @@
+use dioxus::prelude::*;
+
+fn main() {
+    dioxus_native::launch(app);
+}
+
+fn app(cx: Scope) -> Element {
+    let count = use_state(cx, || 0);
+
+    cx.render(rsx! {
+        div {
+            display: "flex",
+            flex_direction: "column",
+            align_items: "center",
+            justify_content: "center",
+            gap: "16px",
+            width: "100%",
+            height: "100%",
+            background_color: "#f0f4f8",
+
+            h1 {
+                font_size: "32px",
+                color: "#1a202c",
+                "High-five counter: {count}"
+            }
+
+            div {
+                display: "flex",
+                gap: "8px",
+                button {
+                    padding: "8px",
+                    border_width: "1px",
+                    border_color: "#2b6cb0",
+                    border_radius: "4px",
+                    background_color: "#bee3f8",
+                    onclick: move |_| count += 1,
+                    "Up high!"
+                }
+                button {
+                    padding: "8px",
+                    border_width: "1px",
+                    border_color: "#2b6cb0",
+                    border_radius: "4px",
+                    background_color: "#bee3f8",
+                    onclick: move |_| count -= 1,
+                    "Down low!"
+                }
+            }
+        }
+    })
+}
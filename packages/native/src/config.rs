@@ -0,0 +1,46 @@
+use tao::window::WindowBuilder;
+use vello::peniko::Color;
+
+/// The configuration of a native app
+pub struct Config {
+    pub(crate) window: WindowBuilder,
+    pub(crate) background: Color,
+    pub(crate) font: Option<Vec<u8>>,
+}
+
+impl Config {
+    /// Create a configuration with a window titled "Dioxus app" and a white background
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the configuration for the window.
+    pub fn with_window(mut self, window: WindowBuilder) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Set the color the window is cleared to, under every element
+    pub fn with_background_color(mut self, (r, g, b, a): (u8, u8, u8, u8)) -> Self {
+        self.background = Color::rgba8(r, g, b, a);
+        self
+    }
+
+    /// Set the font text is drawn with, from the contents of a TrueType or OpenType file
+    ///
+    /// Without one, the sans-serif font of the system is used.
+    pub fn with_font(mut self, font: Vec<u8>) -> Self {
+        self.font = Some(font);
+        self
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            window: WindowBuilder::new().with_title("Dioxus app"),
+            background: Color::WHITE,
+            font: None,
+        }
+    }
+}
@@ -0,0 +1,282 @@
+//! Turn the input of the window into the same events the desktop renderer gets from its webview
+
+use crate::{render::absolute_rect, NativeDom, NativeNode};
+use dioxus_core::ElementId;
+use dioxus_html::{
+    geometry::{ClientPoint, Coordinates, ElementPoint, PagePoint, ScreenPoint, WheelDelta},
+    input_data::keyboard_types::{Code, Key, Location, Modifiers},
+    input_data::{MouseButton, MouseButtonSet},
+    EventData, FocusData, HtmlEvent, KeyboardData, MouseData, WheelData,
+};
+use dioxus_native_core::{tree::TreeView, NodeId};
+use std::str::FromStr;
+use taffy::Taffy;
+use tao::{
+    event::{ElementState, KeyEvent, MouseScrollDelta, WindowEvent},
+    keyboard::{Key as TaoKey, KeyLocation, ModifiersState},
+};
+use vello::kurbo::{Point, Rect};
+
+/// What the window knows about the mouse and keyboard between events
+#[derive(Default)]
+pub(crate) struct InputState {
+    /// Where the cursor is, in logical pixels
+    cursor: Option<Point>,
+    buttons: MouseButtonSet,
+    modifiers: Modifiers,
+    hovered: Option<ElementId>,
+    /// The element the primary button went down on, which is clicked if it goes up on it too
+    pressed: Option<ElementId>,
+    /// The element key presses go to, which is the last one that was pressed
+    focused: Option<ElementId>,
+}
+
+impl InputState {
+    /// The events an event of the window fires, in the order they happen
+    pub(crate) fn translate(
+        &mut self,
+        event: &WindowEvent,
+        scale: f64,
+        frame: Frame,
+    ) -> Vec<HtmlEvent> {
+        let mut events = Vec::new();
+
+        match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                let position = position.to_logical::<f64>(scale);
+                let cursor = Point::new(position.x, position.y);
+                self.cursor = Some(cursor);
+
+                let hovered = frame.hit_test(cursor);
+                if hovered != self.hovered {
+                    if let Some(left) = self.hovered {
+                        events.push(self.mouse_event("mouseleave", left, None, false, frame));
+                    }
+                    if let Some(entered) = hovered {
+                        events.push(self.mouse_event("mouseenter", entered, None, false, frame));
+                    }
+                    self.hovered = hovered;
+                }
+                if let Some(hovered) = hovered {
+                    events.push(self.mouse_event("mousemove", hovered, None, true, frame));
+                }
+            }
+
+            WindowEvent::CursorLeft { .. } => {
+                if let Some(left) = self.hovered.take() {
+                    events.push(self.mouse_event("mouseleave", left, None, false, frame));
+                }
+                self.cursor = None;
+            }
+
+            WindowEvent::MouseInput { state, button, .. } => {
+                let button = match button {
+                    tao::event::MouseButton::Left => MouseButton::Primary,
+                    tao::event::MouseButton::Right => MouseButton::Secondary,
+                    tao::event::MouseButton::Middle => MouseButton::Auxiliary,
+                    _ => MouseButton::Unknown,
+                };
+                let target = match self.hovered {
+                    Some(target) => target,
+                    None => return events,
+                };
+
+                match state {
+                    ElementState::Pressed => {
+                        self.buttons.insert(button);
+                        if self.focused != Some(target) {
+                            if let Some(blurred) = self.focused {
+                                events.push(focus_event("blur", blurred));
+                            }
+                            events.push(focus_event("focus", target));
+                            self.focused = Some(target);
+                        }
+                        if button == MouseButton::Primary {
+                            self.pressed = Some(target);
+                        }
+                        events.push(self.mouse_event(
+                            "mousedown",
+                            target,
+                            Some(button),
+                            true,
+                            frame,
+                        ));
+                    }
+                    _ => {
+                        self.buttons.remove(button);
+                        events.push(self.mouse_event("mouseup", target, Some(button), true, frame));
+                        let clicked = self.pressed.take() == Some(target);
+                        if button == MouseButton::Primary && clicked {
+                            events.push(self.mouse_event(
+                                "click",
+                                target,
+                                Some(button),
+                                true,
+                                frame,
+                            ));
+                        }
+                    }
+                }
+            }
+
+            WindowEvent::MouseWheel { delta, .. } => {
+                if let Some(target) = self.hovered {
+                    // the wheel moves the content the other way than the page scrolls
+                    let delta = match delta {
+                        MouseScrollDelta::LineDelta(x, y) => {
+                            WheelDelta::lines(-*x as f64, -*y as f64, 0.0)
+                        }
+                        MouseScrollDelta::PixelDelta(position) => {
+                            let position = position.to_logical::<f64>(scale);
+                            WheelDelta::pixels(-position.x, -position.y, 0.0)
+                        }
+                        _ => return events,
+                    };
+                    events.push(HtmlEvent {
+                        element: target,
+                        name: "wheel".to_string(),
+                        bubbles: true,
+                        data: EventData::Wheel(WheelData::new(delta)),
+                    });
+                }
+            }
+
+            WindowEvent::ModifiersChanged(state) => self.modifiers = modifiers(*state),
+
+            WindowEvent::KeyboardInput { event, .. } => {
+                let target = self.focused.unwrap_or(ElementId(0));
+                let name = match event.state {
+                    ElementState::Pressed => "keydown",
+                    _ => "keyup",
+                };
+                events.push(HtmlEvent {
+                    element: target,
+                    name: name.to_string(),
+                    bubbles: true,
+                    data: EventData::Keyboard(self.keyboard_data(event)),
+                });
+            }
+
+            _ => {}
+        }
+
+        events
+    }
+
+    fn mouse_event(
+        &self,
+        name: &str,
+        target: ElementId,
+        button: Option<MouseButton>,
+        bubbles: bool,
+        frame: Frame,
+    ) -> HtmlEvent {
+        let cursor = self.cursor.unwrap_or(Point::ZERO);
+        let origin = frame
+            .element_rect(target)
+            .map_or(Point::ZERO, |rect| rect.origin());
+        // the window is the page and the screen as far as the app can tell
+        let coordinates = Coordinates::new(
+            ScreenPoint::new(cursor.x, cursor.y),
+            ClientPoint::new(cursor.x, cursor.y),
+            ElementPoint::new(cursor.x - origin.x, cursor.y - origin.y),
+            PagePoint::new(cursor.x, cursor.y),
+        );
+
+        HtmlEvent {
+            element: target,
+            name: name.to_string(),
+            bubbles,
+            data: EventData::Mouse(MouseData::new(
+                coordinates,
+                button,
+                self.buttons,
+                self.modifiers,
+            )),
+        }
+    }
+
+    fn keyboard_data(&self, event: &KeyEvent) -> KeyboardData {
+        let key = match &event.logical_key {
+            TaoKey::Character(text) => Key::Character(text.to_string()),
+            TaoKey::Space => Key::Character(" ".to_string()),
+            // tao names the rest of its keys after the same W3C list keyboard-types does
+            named => Key::from_str(&format!("{:?}", named)).unwrap_or(Key::Unidentified),
+        };
+        let code =
+            Code::from_str(&format!("{:?}", event.physical_key)).unwrap_or(Code::Unidentified);
+        let location = match event.location {
+            KeyLocation::Left => Location::Left,
+            KeyLocation::Right => Location::Right,
+            KeyLocation::Numpad => Location::Numpad,
+            _ => Location::Standard,
+        };
+
+        KeyboardData::new(key, code, location, event.repeat, self.modifiers)
+    }
+}
+
+fn focus_event(name: &str, target: ElementId) -> HtmlEvent {
+    HtmlEvent {
+        element: target,
+        name: name.to_string(),
+        bubbles: false,
+        data: EventData::Focus(FocusData {}),
+    }
+}
+
+fn modifiers(state: ModifiersState) -> Modifiers {
+    let mut modifiers = Modifiers::empty();
+    modifiers.set(Modifiers::ALT, state.alt_key());
+    modifiers.set(Modifiers::CONTROL, state.control_key());
+    modifiers.set(Modifiers::META, state.super_key());
+    modifiers.set(Modifiers::SHIFT, state.shift_key());
+    modifiers
+}
+
+/// The dom with the layout it was last painted with, which is what the input of the window lands on
+#[derive(Clone, Copy)]
+pub(crate) struct Frame<'a> {
+    pub rdom: &'a NativeDom,
+    pub taffy: &'a Taffy,
+}
+
+impl Frame<'_> {
+    /// The deepest element under a point, which is the one painted on top
+    fn hit_test(&self, point: Point) -> Option<ElementId> {
+        let mut hit = None;
+        self.visit(NodeId(0), Point::ZERO, &mut |node, rect| {
+            if rect.contains(point) {
+                if let Some(element) = node.mounted_id() {
+                    hit = Some(element);
+                }
+            }
+        });
+        hit
+    }
+
+    /// Where an element is in the window
+    fn element_rect(&self, element: ElementId) -> Option<Rect> {
+        let mut found = None;
+        self.visit(NodeId(0), Point::ZERO, &mut |node, rect| {
+            if node.mounted_id() == Some(element) {
+                found = Some(rect);
+            }
+        });
+        found
+    }
+
+    /// Call `f` with the box of every node, parents before their children
+    fn visit(&self, id: NodeId, parent_location: Point, f: &mut impl FnMut(&NativeNode, Rect)) {
+        let node = &self.rdom[id];
+        let layout = match node.state.layout.node {
+            Some(layout) => layout,
+            None => return,
+        };
+        let rect = absolute_rect(self.taffy.layout(layout).unwrap(), parent_location);
+        f(node, rect);
+        for child in self.rdom.children_ids(id).unwrap() {
+            self.visit(*child, rect.origin(), f);
+        }
+    }
+}
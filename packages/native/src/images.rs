@@ -0,0 +1,30 @@
+use rustc_hash::FxHashMap;
+use std::sync::Arc;
+use vello::peniko::{Blob, Format, Image};
+
+/// The images `img` elements point to, decoded the first time they're drawn
+#[derive(Default)]
+pub(crate) struct Images {
+    decoded: FxHashMap<String, Option<Image>>,
+}
+
+impl Images {
+    /// The image at a path, or `None` if it can't be read
+    pub(crate) fn get(&mut self, src: &str) -> Option<&Image> {
+        self.decoded
+            .entry(src.to_string())
+            .or_insert_with(|| match image::open(src) {
+                Ok(decoded) => {
+                    let decoded = decoded.into_rgba8();
+                    let (width, height) = decoded.dimensions();
+                    let data = Blob::new(Arc::new(decoded.into_raw()));
+                    Some(Image::new(data, Format::Rgba8, width, height))
+                }
+                Err(err) => {
+                    log::warn!("Failed to load the image {}: {}", src, err);
+                    None
+                }
+            })
+            .as_ref()
+    }
+}
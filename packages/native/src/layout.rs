@@ -0,0 +1,191 @@
+use crate::{text::Fonts, NativeDom};
+use dioxus_native_core::layout_attributes::apply_layout_attributes;
+use dioxus_native_core::node::{NodeType, OwnedAttributeView};
+use dioxus_native_core::node_ref::{AttributeMask, NodeMask, NodeView};
+use dioxus_native_core::state::ChildDepState;
+use dioxus_native_core::tree::TreeView;
+use dioxus_native_core::NodeId;
+use dioxus_native_core_macro::sorted_str_slice;
+use std::sync::{Arc, Mutex};
+use taffy::node::MeasureFunc;
+use taffy::prelude::*;
+
+#[derive(Clone, PartialEq, Default, Debug)]
+pub(crate) struct TaffyLayout {
+    pub style: Style,
+    pub node: Option<Node>,
+}
+
+impl ChildDepState for TaffyLayout {
+    type Ctx = Arc<Mutex<Taffy>>;
+    type DepState = (Self,);
+    // use tag to force this to be called when a node is built
+    const NODE_MASK: NodeMask =
+        NodeMask::new_with_attrs(AttributeMask::Static(SORTED_LAYOUT_ATTRS))
+            .with_text()
+            .with_tag();
+
+    /// Setup the layout
+    fn reduce<'a>(
+        &mut self,
+        node: NodeView,
+        children: impl Iterator<Item = (&'a Self,)>,
+        ctx: &Self::Ctx,
+    ) -> bool
+    where
+        Self::DepState: 'a,
+    {
+        let mut taffy = ctx.lock().expect("poisoned taffy");
+
+        // text is measured once the font size it inherits is known, see `measure_text`
+        if node.text().is_some() {
+            return match self.node {
+                Some(_) => false,
+                None => {
+                    self.node = Some(taffy.new_leaf(Style::default()).unwrap());
+                    true
+                }
+            };
+        }
+
+        // gather up all the styles from the attribute list
+        let mut style = Style::default();
+        if let Some(attributes) = node.attributes() {
+            for OwnedAttributeView {
+                attribute, value, ..
+            } in attributes
+            {
+                if let Some(text) = value.as_text() {
+                    apply_layout_attributes(&attribute.name, text, &mut style);
+                }
+            }
+        }
+
+        // Set all direct nodes as our children
+        let child_layout: Vec<_> = children.filter_map(|(l,)| l.node).collect();
+
+        let mut changed = false;
+        match self.node {
+            Some(n) => {
+                if self.style != style {
+                    taffy.set_style(n, style.clone()).unwrap();
+                }
+                if taffy.children(n).unwrap() != child_layout {
+                    taffy.set_children(n, &child_layout).unwrap();
+                }
+            }
+            None => {
+                self.node = Some(
+                    taffy
+                        .new_with_children(style.clone(), &child_layout)
+                        .unwrap(),
+                );
+                changed = true;
+            }
+        }
+        if self.style != style {
+            changed = true;
+            self.style = style;
+        }
+        changed
+    }
+}
+
+/// Size a text node to the lines it wraps into, with the font size it inherits
+pub(crate) fn measure_text(rdom: &NativeDom, taffy: &mut Taffy, fonts: &Arc<Fonts>, id: NodeId) {
+    let node = match rdom.get(id) {
+        Some(node) => node,
+        None => return,
+    };
+    let (text, layout) = match (&node.node_data.node_type, node.state.layout.node) {
+        (NodeType::Text { text }, Some(layout)) => (text.clone(), layout),
+        _ => return,
+    };
+    let size = node.state.text.font_size;
+    let fonts = fonts.clone();
+
+    let measure = move |known: Size<Option<f32>>, available: Size<AvailableSpace>| {
+        let max_width = known.width.or(match available.width {
+            AvailableSpace::Definite(width) => Some(width),
+            AvailableSpace::MinContent => Some(0.0),
+            AvailableSpace::MaxContent => None,
+        });
+        let lines = fonts.layout(&text, size, max_width);
+        Size {
+            width: known.width.unwrap_or_else(|| lines.width()),
+            height: known.height.unwrap_or_else(|| lines.height()),
+        }
+    };
+    taffy
+        .set_measure(layout, Some(MeasureFunc::Boxed(Box::new(measure))))
+        .unwrap();
+}
+
+// these are the attributes in layout_attiributes in native-core
+const SORTED_LAYOUT_ATTRS: &[&str] = &sorted_str_slice!([
+    "align-content",
+    "align-items",
+    "align-self",
+    "aspect-ratio",
+    "border",
+    "border-bottom",
+    "border-bottom-width",
+    "border-left",
+    "border-left-width",
+    "border-right",
+    "border-right-width",
+    "border-top",
+    "border-top-width",
+    "border-width",
+    "bottom",
+    "column-gap",
+    "display",
+    "flex",
+    "flex-basis",
+    "flex-direction",
+    "flex-flow",
+    "flex-grow",
+    "flex-shrink",
+    "flex-wrap",
+    "gap",
+    "grid",
+    "grid-area",
+    "grid-auto-columns",
+    "grid-auto-flow",
+    "grid-auto-rows",
+    "grid-column",
+    "grid-column-end",
+    "grid-column-start",
+    "grid-row",
+    "grid-row-end",
+    "grid-row-start",
+    "grid-template",
+    "grid-template-areas",
+    "grid-template-columns",
+    "grid-template-rows",
+    "height",
+    "inset",
+    "justify-content",
+    "justify-items",
+    "justify-self",
+    "left",
+    "margin",
+    "margin-bottom",
+    "margin-left",
+    "margin-right",
+    "margin-top",
+    "max-height",
+    "max-width",
+    "min-height",
+    "min-width",
+    "padding",
+    "padding-bottom",
+    "padding-left",
+    "padding-right",
+    "padding-top",
+    "position",
+    "right",
+    "row-gap",
+    "top",
+    "width"
+]);
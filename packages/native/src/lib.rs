@@ -0,0 +1,299 @@
+#![doc = include_str!("../README.md")]
+#![doc(html_logo_url = "https://avatars.githubusercontent.com/u/79236386")]
+#![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
+
+mod config;
+mod events;
+mod images;
+mod layout;
+mod node;
+mod render;
+mod surface;
+mod text;
+
+pub use config::Config;
+pub use tao;
+pub use tao::dpi::{LogicalSize, PhysicalSize};
+pub use tao::window::WindowBuilder;
+
+use dioxus_core::*;
+use dioxus_native_core::{NodeId, NodeMask, SendAnyMap};
+use events::{Frame, InputState};
+use images::Images;
+pub(crate) use node::*;
+use render::Painter;
+use std::sync::{Arc, Mutex};
+use std::task::Waker;
+use surface::SurfaceWindow;
+use taffy::prelude::*;
+use tao::{
+    event::{Event, StartCause, WindowEvent},
+    event_loop::{ControlFlow, EventLoop, EventLoopWindowTarget},
+    window::Window,
+};
+use text::Fonts;
+use vello::{
+    peniko::Color,
+    util::{RenderContext, RenderSurface},
+    AaConfig, AaSupport, RenderParams, Renderer, RendererOptions, Scene,
+};
+
+/// The events the app sends its own event loop
+#[derive(Debug)]
+pub(crate) enum UserEvent {
+    /// The VirtualDom has work to do
+    Poll,
+}
+
+/// Launch the app in a window that's painted by the GPU
+///
+/// This function will start a multithreaded Tokio runtime as well the window event loop.
+///
+/// ```rust, ignore
+/// use dioxus::prelude::*;
+///
+/// fn main() {
+///     dioxus_native::launch(app);
+/// }
+///
+/// fn app(cx: Scope) -> Element {
+///     cx.render(rsx!{
+///         h1 { "Hello, world!" }
+///     })
+/// }
+/// ```
+pub fn launch(root: Component) {
+    launch_cfg(root, Config::default())
+}
+
+/// Launch the app in a window that's painted by the GPU, with the window and fonts set up by the config
+pub fn launch_cfg(root: Component, cfg: Config) {
+    launch_with_props(root, (), cfg)
+}
+
+/// Launch the app with props, in a window that's painted by the GPU
+pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, cfg: Config) {
+    let event_loop = EventLoop::<UserEvent>::with_user_event();
+
+    // We start the tokio runtime *on this thread*
+    // Any future we poll later will use this runtime to spawn tasks and for IO
+    let rt = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    // We enter the runtime but we poll futures manually, circumventing the per-task runtime budget
+    let _guard = rt.enter();
+
+    let mut app = App::new(VirtualDom::new_with_props(root, props), cfg, &event_loop);
+
+    event_loop.run(move |window_event, _event_loop, control_flow| {
+        app.handle_event(window_event, control_flow)
+    })
+}
+
+/// The window of a running app, the dom it paints and everything it's painted with
+struct App {
+    dom: VirtualDom,
+    rdom: NativeDom,
+    taffy: Arc<Mutex<Taffy>>,
+    fonts: Arc<Fonts>,
+    images: Images,
+    input: InputState,
+    waker: Waker,
+    render_cx: RenderContext,
+    surface: RenderSurface<'static>,
+    renderer: Renderer,
+    // the window is dropped after the surface that paints it
+    window: Window,
+    background: Color,
+}
+
+impl App {
+    fn new(dom: VirtualDom, cfg: Config, event_loop: &EventLoopWindowTarget<UserEvent>) -> Self {
+        let window = cfg.window.build(event_loop).unwrap();
+        let size = window.inner_size();
+
+        let mut render_cx = RenderContext::new().unwrap();
+        let target =
+            SurfaceWindow::new(&window).expect("the window can't be painted on this platform");
+        let surface = pollster::block_on(render_cx.create_surface(target, size.width, size.height))
+            .expect("failed to create the surface of the window");
+        let renderer = Renderer::new(
+            &render_cx.devices[surface.dev_id].device,
+            RendererOptions {
+                surface_format: Some(surface.format),
+                use_cpu: false,
+                antialiasing_support: AaSupport::area_only(),
+                num_init_threads: None,
+            },
+        )
+        .unwrap();
+
+        let mut app = Self {
+            dom,
+            rdom: NativeDom::new(),
+            taffy: Arc::new(Mutex::new(Taffy::new())),
+            fonts: Arc::new(Fonts::new(cfg.font)),
+            images: Images::default(),
+            input: InputState::default(),
+            waker: dioxus_event_loop::tao_waker(&event_loop.create_proxy(), || UserEvent::Poll),
+            render_cx,
+            surface,
+            renderer,
+            window,
+            background: cfg.background,
+        };
+
+        let mutations = app.dom.rebuild();
+        app.apply(mutations);
+        app
+    }
+
+    fn handle_event(&mut self, event: Event<'_, UserEvent>, control_flow: &mut ControlFlow) {
+        *control_flow = ControlFlow::Wait;
+
+        match event {
+            Event::NewEvents(StartCause::Init) | Event::UserEvent(UserEvent::Poll) => {
+                self.poll_vdom()
+            }
+
+            Event::RedrawRequested(_) => self.redraw(),
+
+            Event::WindowEvent { event, .. } => match event {
+                WindowEvent::CloseRequested => *control_flow = ControlFlow::Exit,
+
+                WindowEvent::Resized(size) => {
+                    self.render_cx
+                        .resize_surface(&mut self.surface, size.width, size.height);
+                    self.window.request_redraw();
+                }
+
+                WindowEvent::ScaleFactorChanged { new_inner_size, .. } => {
+                    self.render_cx.resize_surface(
+                        &mut self.surface,
+                        new_inner_size.width,
+                        new_inner_size.height,
+                    );
+                    self.window.request_redraw();
+                }
+
+                event => {
+                    let events = {
+                        let taffy = self.taffy.lock().expect("taffy lock poisoned");
+                        let scale = self.window.scale_factor();
+                        let frame = Frame {
+                            rdom: &self.rdom,
+                            taffy: &taffy,
+                        };
+                        self.input.translate(&event, scale, frame)
+                    };
+                    if events.is_empty() {
+                        return;
+                    }
+
+                    // the events are dispatched the same way the desktop renderer dispatches the ones of its webview
+                    for evt in events {
+                        self.dom.handle_event(
+                            &evt.name,
+                            evt.data.into_any(),
+                            evt.element,
+                            evt.bubbles,
+                        );
+                    }
+                    let mutations = self.dom.render_immediate();
+                    self.apply(mutations);
+                }
+            },
+
+            _ => {}
+        }
+    }
+
+    /// Poll the virtualdom until it's pending
+    ///
+    /// The waker we give it is connected to the event loop, so it will wake up the event loop when it's ready to be polled again
+    fn poll_vdom(&mut self) {
+        while dioxus_event_loop::poll_work(&mut self.dom, &self.waker) {
+            let mutations = self.dom.render_immediate();
+            self.apply(mutations);
+        }
+    }
+
+    /// Apply the mutations of a render to the dom, and update the styles and layout of the nodes they changed
+    fn apply(&mut self, mutations: Mutations) {
+        let (to_update, dirty) = self.rdom.apply_mutations(mutations);
+        let mut ctx = SendAnyMap::new();
+        ctx.insert(self.taffy.clone());
+        let updated = self.rdom.update_state(to_update, ctx);
+
+        // text is measured again when it changes, or when the size of the font it inherits does
+        let mut taffy = self.taffy.lock().expect("taffy lock poisoned");
+        let text_changed = dirty
+            .iter()
+            .filter(|(_, mask)| mask.overlaps(&NodeMask::new().with_text()))
+            .map(|(id, _)| *id);
+        for id in updated.iter().map(|id| *id).chain(text_changed) {
+            layout::measure_text(&self.rdom, &mut taffy, &self.fonts, id);
+        }
+
+        self.window.request_redraw();
+    }
+
+    /// Lay out the dom to fill the window, and paint it
+    fn redraw(&mut self) {
+        let scale = self.window.scale_factor();
+        let size = self.window.inner_size();
+
+        let mut taffy = self.taffy.lock().expect("taffy lock poisoned");
+
+        // the root node fills the entire window
+        let logical = size.to_logical::<f32>(scale);
+        let root = self.rdom[NodeId(0)].state.layout.node.unwrap();
+        let mut style = taffy.style(root).unwrap().clone();
+        style.size = Size {
+            width: Dimension::Points(logical.width),
+            height: Dimension::Points(logical.height),
+        };
+        taffy.set_style(root, style).unwrap();
+        let available = Size {
+            width: AvailableSpace::Definite(logical.width),
+            height: AvailableSpace::Definite(logical.height),
+        };
+        taffy.compute_layout(root, available).unwrap();
+
+        let mut scene = Scene::new();
+        Painter {
+            rdom: &self.rdom,
+            taffy: &taffy,
+            fonts: &self.fonts,
+            images: &mut self.images,
+        }
+        .paint(&mut scene, scale);
+
+        let texture = match self.surface.surface.get_current_texture() {
+            Ok(texture) => texture,
+            Err(err) => {
+                log::warn!("Failed to get the surface of the window: {}", err);
+                return;
+            }
+        };
+        let device = &self.render_cx.devices[self.surface.dev_id];
+        let params = RenderParams {
+            base_color: self.background,
+            width: size.width,
+            height: size.height,
+            antialiasing_method: AaConfig::Area,
+        };
+        if let Err(err) = self.renderer.render_to_surface(
+            &device.device,
+            &device.queue,
+            &scene,
+            &texture,
+            &params,
+        ) {
+            log::error!("Failed to paint the window: {}", err);
+        }
+        texture.present();
+    }
+}
@@ -0,0 +1,17 @@
+use crate::layout::TaffyLayout;
+use crate::style::{Paint, TextStyle};
+use dioxus_native_core::{real_dom::RealDom, state::*};
+use dioxus_native_core_macro::State;
+
+pub(crate) type NativeDom = RealDom<NodeState>;
+pub(crate) type NativeNode = dioxus_native_core::node::Node<NodeState>;
+
+#[derive(Debug, Clone, State, Default)]
+pub(crate) struct NodeState {
+    #[child_dep_state(layout, Mutex<Taffy>)]
+    pub layout: TaffyLayout,
+    #[parent_dep_state(text)]
+    pub text: TextStyle,
+    #[node_dep_state()]
+    pub paint: Paint,
+}
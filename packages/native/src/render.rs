@@ -0,0 +1,133 @@
+use crate::{images::Images, text::Fonts, NativeDom, NativeNode};
+use dioxus_native_core::node::NodeType;
+use dioxus_native_core::tree::TreeView;
+use dioxus_native_core::NodeId;
+use taffy::prelude::{Layout, LengthPercentage, Taffy};
+use vello::kurbo::{Affine, Insets, Point, Rect, RoundedRect, Shape};
+use vello::peniko::{Fill, Mix};
+use vello::Scene;
+
+/// How closely curves are flattened when the border around a rounded element is cut out, in pixels
+const TOLERANCE: f64 = 0.1;
+
+/// Everything painting the dom needs
+pub(crate) struct Painter<'a> {
+    pub rdom: &'a NativeDom,
+    pub taffy: &'a Taffy,
+    pub fonts: &'a Fonts,
+    pub images: &'a mut Images,
+}
+
+impl Painter<'_> {
+    /// Paint the whole dom into the scene, with everything scaled from logical to physical pixels
+    pub(crate) fn paint(&mut self, scene: &mut Scene, scale: f64) {
+        let rdom = self.rdom;
+        let root = &rdom[NodeId(0)];
+        self.paint_node(scene, root, Affine::scale(scale), Point::ZERO);
+    }
+
+    fn paint_node(
+        &mut self,
+        scene: &mut Scene,
+        node: &NativeNode,
+        transform: Affine,
+        parent_location: Point,
+    ) {
+        if let NodeType::Placeholder = &node.node_data.node_type {
+            return;
+        }
+        let rect = match node.state.layout.node {
+            Some(layout) => absolute_rect(self.taffy.layout(layout).unwrap(), parent_location),
+            None => return,
+        };
+
+        match &node.node_data.node_type {
+            NodeType::Text { text } => {
+                let style = node.state.text;
+                // the box was measured with the same font, so the text wraps the way it did then
+                let layout =
+                    self.fonts
+                        .layout(text, style.font_size, Some(rect.width() as f32 + 0.5));
+                let outlines = self.fonts.outlines(&layout);
+                let transform = transform * Affine::translate(rect.origin().to_vec2());
+                scene.fill(Fill::NonZero, transform, style.color, None, &outlines);
+            }
+            NodeType::Element {
+                tag, attributes, ..
+            } => {
+                let paint = node.state.paint;
+                let shape = RoundedRect::from_rect(rect, paint.border_radius as f64);
+
+                if let Some(opacity) = paint.opacity {
+                    scene.push_layer(Mix::Normal, opacity, transform, &shape);
+                }
+
+                if let Some(background) = paint.background {
+                    scene.fill(Fill::NonZero, transform, background, None, &shape);
+                }
+
+                if tag == "img" {
+                    let src = attributes
+                        .iter()
+                        .find(|(attribute, _)| attribute.name == "src")
+                        .and_then(|(_, value)| value.as_text());
+                    if let Some(image) = src.and_then(|src| self.images.get(src)) {
+                        // images are stretched over the box of the element
+                        let image_transform = transform
+                            * Affine::translate(rect.origin().to_vec2())
+                            * Affine::scale_non_uniform(
+                                rect.width() / image.width as f64,
+                                rect.height() / image.height as f64,
+                            );
+                        scene.push_layer(Mix::Clip, 1.0, transform, &shape);
+                        scene.draw_image(image, image_transform);
+                        scene.pop_layer();
+                    }
+                }
+
+                // the border is the part of the box outside of where the padding starts
+                let border = &node.state.layout.style.border;
+                let width = |side: LengthPercentage| match side {
+                    LengthPercentage::Points(width) => width as f64,
+                    LengthPercentage::Percent(percent) => percent as f64 * rect.width(),
+                };
+                let insets = Insets::new(
+                    width(border.left),
+                    width(border.top),
+                    width(border.right),
+                    width(border.bottom),
+                );
+                if insets.x0 + insets.y0 + insets.x1 + insets.y1 > 0.0 {
+                    let color = paint.border_color.unwrap_or(node.state.text.color);
+                    let inner_radius =
+                        (paint.border_radius as f64 - insets.x0.max(insets.y0)).max(0.0);
+                    let inner = RoundedRect::from_rect(rect - insets, inner_radius);
+                    let mut path = shape.to_path(TOLERANCE);
+                    path.extend(inner.path_elements(TOLERANCE));
+                    scene.fill(Fill::EvenOdd, transform, color, None, &path);
+                }
+
+                let rdom = self.rdom;
+                for child in rdom.children_ids(node.node_data.node_id).unwrap() {
+                    self.paint_node(scene, &rdom[*child], transform, rect.origin());
+                }
+
+                if paint.opacity.is_some() {
+                    scene.pop_layer();
+                }
+            }
+            NodeType::Placeholder => unreachable!(),
+        }
+    }
+}
+
+/// The box of a node in the window, from the location of its parent
+pub(crate) fn absolute_rect(layout: &Layout, parent_location: Point) -> Rect {
+    Rect::from_origin_size(
+        (
+            parent_location.x + layout.location.x as f64,
+            parent_location.y + layout.location.y as f64,
+        ),
+        (layout.size.width as f64, layout.size.height as f64),
+    )
+}
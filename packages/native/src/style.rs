@@ -0,0 +1,151 @@
+use dioxus_native_core::{
+    layout_attributes::parse_value,
+    node_ref::{AttributeMask, NodeMask, NodeView},
+    state::{NodeDepState, ParentDepState},
+};
+use dioxus_native_core_macro::sorted_str_slice;
+use lightningcss::{traits::Parse, values::color::CssColor};
+use taffy::prelude::*;
+use vello::peniko::Color;
+
+/// The size of text that doesn't set one, in pixels
+const DEFAULT_FONT_SIZE: f32 = 16.0;
+
+/// The styles of text, which elements pass down to their children
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) struct TextStyle {
+    pub color: Color,
+    pub font_size: f32,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self {
+            color: Color::BLACK,
+            font_size: DEFAULT_FONT_SIZE,
+        }
+    }
+}
+
+impl ParentDepState for TextStyle {
+    type Ctx = ();
+    type DepState = (Self,);
+    const NODE_MASK: NodeMask =
+        NodeMask::new_with_attrs(AttributeMask::Static(&sorted_str_slice!([
+            "color",
+            "font-size"
+        ])))
+        .with_element();
+
+    fn reduce(&mut self, node: NodeView, parent: Option<(&Self,)>, _: &Self::Ctx) -> bool {
+        let mut new = parent.map(|(parent,)| *parent).unwrap_or_default();
+
+        if let Some(attributes) = node.attributes() {
+            for attribute in attributes {
+                let value = match attribute.value.as_text() {
+                    Some(value) => value,
+                    None => continue,
+                };
+                match attribute.attribute.name.as_str() {
+                    "color" => {
+                        if let Some(color) = parse_color(value) {
+                            new.color = color;
+                        }
+                    }
+                    "font-size" => {
+                        if let Some(Dimension::Points(size)) = parse_value(value) {
+                            new.font_size = size;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if new != *self {
+            *self = new;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// What is painted under the children of an element
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub(crate) struct Paint {
+    pub background: Option<Color>,
+    pub border_color: Option<Color>,
+    pub border_radius: f32,
+    pub opacity: Option<f32>,
+}
+
+impl NodeDepState for Paint {
+    type Ctx = ();
+    type DepState = ();
+    const NODE_MASK: NodeMask =
+        NodeMask::new_with_attrs(AttributeMask::Static(&sorted_str_slice!([
+            "background",
+            "background-color",
+            "border-color",
+            "border-radius",
+            "opacity"
+        ])))
+        .with_element();
+
+    fn reduce(&mut self, node: NodeView, _: (), _: &Self::Ctx) -> bool {
+        let mut new = Paint::default();
+
+        if let Some(attributes) = node.attributes() {
+            for attribute in attributes {
+                let value = match attribute.value.as_text() {
+                    Some(value) => value,
+                    None => continue,
+                };
+                match attribute.attribute.name.as_str() {
+                    "background" | "background-color" => new.background = parse_color(value),
+                    "border-color" => new.border_color = parse_color(value),
+                    "border-radius" => {
+                        if let Some(Dimension::Points(radius)) = parse_value(value) {
+                            new.border_radius = radius;
+                        }
+                    }
+                    "opacity" => new.opacity = value.trim().parse().ok(),
+                    _ => {}
+                }
+            }
+        }
+
+        if new != *self {
+            *self = new;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Parse any css color, like `red`, `#ff0000` or `hsl(0, 100%, 50%)`
+pub(crate) fn parse_color(value: &str) -> Option<Color> {
+    match CssColor::parse_string(value).ok()?.to_rgb() {
+        CssColor::RGBA(rgba) => Some(Color::rgba8(rgba.red, rgba.green, rgba.blue, rgba.alpha)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_colors() {
+        assert_eq!(parse_color("red"), Some(Color::rgba8(255, 0, 0, 255)));
+        assert_eq!(parse_color("#00ff0080"), Some(Color::rgba8(0, 255, 0, 128)));
+        assert_eq!(
+            parse_color("rgb(0, 0, 255)"),
+            Some(Color::rgba8(0, 0, 255, 255))
+        );
+        assert_eq!(parse_color("currentcolor"), None);
+        assert_eq!(parse_color("not a color"), None);
+    }
+}
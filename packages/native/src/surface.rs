@@ -0,0 +1,91 @@
+//! The window the surface of the app paints, in the handles the wgpu of vello takes
+//!
+//! tao still hands out the handles of `raw-window-handle` 0.5, and wgpu only takes the ones of 0.6, which describe the
+//! same windows with non-null pointers.
+
+use raw_window_handle::{
+    HasRawDisplayHandle, HasRawWindowHandle, RawDisplayHandle, RawWindowHandle,
+};
+use std::num::{NonZeroIsize, NonZeroU32};
+use std::ptr::NonNull;
+use tao::window::Window;
+
+/// The handles of a window, to create the surface that paints it
+pub(crate) struct SurfaceWindow {
+    window: rwh_06::RawWindowHandle,
+    display: rwh_06::RawDisplayHandle,
+}
+
+// The handles only point at the window, which the app keeps around for as long as the surface
+unsafe impl Send for SurfaceWindow {}
+unsafe impl Sync for SurfaceWindow {}
+
+impl SurfaceWindow {
+    /// The handles of a window, or `None` if the platform isn't one the renderer paints on
+    pub(crate) fn new(window: &Window) -> Option<Self> {
+        Some(Self {
+            window: window_handle(window.raw_window_handle())?,
+            display: display_handle(window.raw_display_handle())?,
+        })
+    }
+}
+
+impl rwh_06::HasWindowHandle for SurfaceWindow {
+    fn window_handle(&self) -> Result<rwh_06::WindowHandle<'_>, rwh_06::HandleError> {
+        // safety: the window stays open until the surface is dropped
+        Ok(unsafe { rwh_06::WindowHandle::borrow_raw(self.window) })
+    }
+}
+
+impl rwh_06::HasDisplayHandle for SurfaceWindow {
+    fn display_handle(&self) -> Result<rwh_06::DisplayHandle<'_>, rwh_06::HandleError> {
+        // safety: the display outlives the window
+        Ok(unsafe { rwh_06::DisplayHandle::borrow_raw(self.display) })
+    }
+}
+
+fn window_handle(handle: RawWindowHandle) -> Option<rwh_06::RawWindowHandle> {
+    let handle = match handle {
+        RawWindowHandle::Win32(handle) => {
+            let mut new = rwh_06::Win32WindowHandle::new(NonZeroIsize::new(handle.hwnd as isize)?);
+            new.hinstance = NonZeroIsize::new(handle.hinstance as isize);
+            new.into()
+        }
+        RawWindowHandle::AppKit(handle) => {
+            rwh_06::AppKitWindowHandle::new(NonNull::new(handle.ns_view)?).into()
+        }
+        RawWindowHandle::Xlib(handle) => {
+            let mut new = rwh_06::XlibWindowHandle::new(handle.window);
+            new.visual_id = handle.visual_id;
+            new.into()
+        }
+        RawWindowHandle::Xcb(handle) => {
+            let mut new = rwh_06::XcbWindowHandle::new(NonZeroU32::new(handle.window)?);
+            new.visual_id = NonZeroU32::new(handle.visual_id);
+            new.into()
+        }
+        RawWindowHandle::Wayland(handle) => {
+            rwh_06::WaylandWindowHandle::new(NonNull::new(handle.surface)?).into()
+        }
+        _ => return None,
+    };
+    Some(handle)
+}
+
+fn display_handle(handle: RawDisplayHandle) -> Option<rwh_06::RawDisplayHandle> {
+    let handle = match handle {
+        RawDisplayHandle::Windows(_) => rwh_06::WindowsDisplayHandle::new().into(),
+        RawDisplayHandle::AppKit(_) => rwh_06::AppKitDisplayHandle::new().into(),
+        RawDisplayHandle::Xlib(handle) => {
+            rwh_06::XlibDisplayHandle::new(NonNull::new(handle.display), handle.screen).into()
+        }
+        RawDisplayHandle::Xcb(handle) => {
+            rwh_06::XcbDisplayHandle::new(NonNull::new(handle.connection), handle.screen).into()
+        }
+        RawDisplayHandle::Wayland(handle) => {
+            rwh_06::WaylandDisplayHandle::new(NonNull::new(handle.display)?).into()
+        }
+        _ => return None,
+    };
+    Some(handle)
+}
@@ -0,0 +1,144 @@
+use std::sync::Arc;
+use swash::{
+    scale::ScaleContext,
+    zeno::{Command, PathData},
+    FontRef, GlyphId,
+};
+use vello::kurbo::{BezPath, Point};
+
+/// How tall a line is relative to the size of its font, which is what `line-height: normal` comes to for most fonts
+pub(crate) const LINE_HEIGHT: f32 = 1.2;
+
+/// The font every text node is drawn with
+pub(crate) struct Fonts {
+    data: Arc<Vec<u8>>,
+    index: u32,
+}
+
+impl Fonts {
+    /// Use the font in `data`, or the sans-serif font of the system if there is none
+    pub(crate) fn new(data: Option<Vec<u8>>) -> Self {
+        match data {
+            Some(data) => Self {
+                data: Arc::new(data),
+                index: 0,
+            },
+            None => Self::system(),
+        }
+    }
+
+    fn system() -> Self {
+        let mut db = fontdb::Database::new();
+        db.load_system_fonts();
+        let query = fontdb::Query {
+            families: &[fontdb::Family::SansSerif],
+            ..Default::default()
+        };
+        db.query(&query)
+            .and_then(|id| {
+                db.with_face_data(id, |data, index| Self {
+                    data: Arc::new(data.to_vec()),
+                    index,
+                })
+            })
+            .expect("no sans-serif font is installed, set one with Config::with_font")
+    }
+
+    fn font(&self) -> FontRef<'_> {
+        FontRef::from_index(&self.data, self.index as usize).expect("the font couldn't be read")
+    }
+
+    /// Break `text` into the lines it takes at `size`, wrapping between words once a line is wider than `max_width`
+    pub(crate) fn layout(&self, text: &str, size: f32, max_width: Option<f32>) -> TextLayout {
+        let font = self.font();
+        let charmap = font.charmap();
+        let metrics = font.glyph_metrics(&[]).scale(size);
+        let advance = |c: char| metrics.advance_width(charmap.map(c));
+
+        let mut lines = vec![Line::default()];
+        let space = advance(' ');
+        for word in text.split_whitespace() {
+            let width: f32 = word.chars().map(advance).sum();
+
+            let current = lines.last().unwrap();
+            let fits = max_width.map_or(true, |max| current.width + space + width <= max);
+            if !fits && !current.glyphs.is_empty() {
+                lines.push(Line::default());
+            }
+
+            let line = lines.last_mut().unwrap();
+            if !line.glyphs.is_empty() {
+                line.width += space;
+            }
+            for c in word.chars() {
+                line.glyphs.push((charmap.map(c), line.width));
+                line.width += advance(c);
+            }
+        }
+
+        TextLayout {
+            lines,
+            size,
+            line_height: size * LINE_HEIGHT,
+        }
+    }
+
+    /// The outline of every glyph of a layout, with the top left of the text at the origin
+    pub(crate) fn outlines(&self, layout: &TextLayout) -> BezPath {
+        let font = self.font();
+        let ascent = font.metrics(&[]).scale(layout.size).ascent;
+        let mut context = ScaleContext::new();
+        let mut scaler = context.builder(font).size(layout.size).build();
+
+        // the half of the leading above the line keeps the text centered in it
+        let baseline = (layout.line_height - layout.size) / 2.0 + ascent;
+
+        let mut path = BezPath::new();
+        for (row, line) in layout.lines.iter().enumerate() {
+            let y = row as f32 * layout.line_height + baseline;
+            for (glyph, x) in &line.glyphs {
+                if let Some(outline) = scaler.scale_outline(*glyph) {
+                    // fonts are drawn with y going up
+                    let point =
+                        |p: swash::zeno::Point| Point::new((x + p.x) as f64, (y - p.y) as f64);
+                    for command in outline.path().commands() {
+                        match command {
+                            Command::MoveTo(p) => path.move_to(point(p)),
+                            Command::LineTo(p) => path.line_to(point(p)),
+                            Command::QuadTo(c, p) => path.quad_to(point(c), point(p)),
+                            Command::CurveTo(c1, c2, p) => {
+                                path.curve_to(point(c1), point(c2), point(p))
+                            }
+                            Command::Close => path.close_path(),
+                        }
+                    }
+                }
+            }
+        }
+        path
+    }
+}
+
+/// Text broken into lines
+pub(crate) struct TextLayout {
+    pub lines: Vec<Line>,
+    pub size: f32,
+    pub line_height: f32,
+}
+
+impl TextLayout {
+    pub fn width(&self) -> f32 {
+        self.lines.iter().map(|line| line.width).fold(0.0, f32::max)
+    }
+
+    pub fn height(&self) -> f32 {
+        self.lines.len() as f32 * self.line_height
+    }
+}
+
+/// The glyphs of a line, with how far each starts from the start of the line
+#[derive(Default)]
+pub(crate) struct Line {
+    pub glyphs: Vec<(GlyphId, f32)>,
+    pub width: f32,
+}
@@ -0,0 +1,32 @@
+use proc_macro::TokenStream;
+use syn::parse_macro_input;
+
+mod routable;
+
+/// Derive the typed routes of an app from an enum, with a variant for every route
+///
+/// Every variant has a `#[route("...")]` attribute with the path of the route. Segments that start with a `:` are
+/// parameters, which are the named fields of the variant, parsed with `FromStr` and written with `Display`:
+///
+/// ```rust, ignore
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[route("/")]
+///     Home,
+///     #[route("/user/:id")]
+///     User { id: u32 },
+/// }
+///
+/// assert_eq!(Route::User { id: 7 }.to_string(), "/user/7");
+/// assert_eq!("/user/7".parse::<Route>(), Ok(Route::User { id: 7 }));
+/// ```
+///
+/// A parameter without a field, a field without a parameter, and two variants with the same path are compile errors.
+#[proc_macro_derive(Routable, attributes(route))]
+pub fn derive_routable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    match routable::expand(&input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
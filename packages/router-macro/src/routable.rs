@@ -0,0 +1,317 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Ident, LitStr, Type, Variant};
+
+/// A segment of the path of a route
+enum Segment {
+    Static(String),
+    Param(Ident),
+}
+
+/// A variant of the enum and the path it's declared with
+struct Route {
+    variant: Ident,
+    pattern: LitStr,
+    segments: Vec<Segment>,
+    /// the fields of the variant and their types, which are its parameters
+    fields: Option<Vec<(Ident, Type)>>,
+}
+
+impl Route {
+    fn parse(variant: &Variant) -> syn::Result<Self> {
+        let mut patterns = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("route"));
+        let pattern: LitStr = match (patterns.next(), patterns.next()) {
+            (Some(attr), None) => attr.parse_args()?,
+            (None, _) => {
+                return Err(syn::Error::new_spanned(
+                    &variant.ident,
+                    "every variant of a Routable enum needs a path, like #[route(\"/about\")]",
+                ))
+            }
+            (Some(_), Some(extra)) => {
+                return Err(syn::Error::new_spanned(
+                    extra,
+                    "a variant can only have one route",
+                ))
+            }
+        };
+
+        let segments = parse_pattern(&pattern)?;
+
+        let fields = match &variant.fields {
+            Fields::Unit => None,
+            Fields::Named(fields) => Some(
+                fields
+                    .named
+                    .iter()
+                    .map(|field| (field.ident.clone().unwrap(), field.ty.clone()))
+                    .collect::<Vec<_>>(),
+            ),
+            Fields::Unnamed(fields) => {
+                return Err(syn::Error::new_spanned(
+                    fields,
+                    "the parameters of a route are named fields, like User { id: u32 }",
+                ))
+            }
+        };
+
+        // every parameter of the path is a field, and every field is a parameter
+        let params: Vec<&Ident> = segments
+            .iter()
+            .filter_map(|segment| match segment {
+                Segment::Param(param) => Some(param),
+                Segment::Static(_) => None,
+            })
+            .collect();
+        let field_names: Vec<&Ident> = fields.iter().flatten().map(|(name, _)| name).collect();
+        for param in &params {
+            if !field_names.contains(param) {
+                return Err(syn::Error::new_spanned(
+                    &pattern,
+                    format!(
+                        "the parameter :{} of the route isn't a field of {}",
+                        param, variant.ident
+                    ),
+                ));
+            }
+        }
+        for field in &field_names {
+            if !params.contains(field) {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    format!(
+                        "the field {} isn't a parameter of the route {}",
+                        field,
+                        pattern.value()
+                    ),
+                ));
+            }
+        }
+
+        Ok(Self {
+            variant: variant.ident.clone(),
+            pattern,
+            segments,
+            fields,
+        })
+    }
+
+    /// Whether both routes match the same paths
+    fn overlaps(&self, other: &Route) -> bool {
+        self.segments.len() == other.segments.len()
+            && self
+                .segments
+                .iter()
+                .zip(&other.segments)
+                .all(|pair| match pair {
+                    (Segment::Static(a), Segment::Static(b)) => a == b,
+                    (Segment::Param(_), Segment::Param(_)) => true,
+                    _ => false,
+                })
+    }
+
+    /// Routes with static segments are tried before routes with parameters in their place
+    fn specificity(&self) -> Vec<bool> {
+        self.segments
+            .iter()
+            .map(|segment| matches!(segment, Segment::Param(_)))
+            .collect()
+    }
+
+    fn pattern_of(&self) -> TokenStream2 {
+        let variant = &self.variant;
+        let pattern = &self.pattern;
+        match self.fields {
+            Some(_) => quote! { Self::#variant { .. } => #pattern, },
+            None => quote! { Self::#variant => #pattern, },
+        }
+    }
+
+    /// Write the path of the variant
+    ///
+    /// Its fields are bound by their own names, so the formatter is `__f` to stay out of their way.
+    fn display(&self) -> TokenStream2 {
+        let variant = &self.variant;
+
+        let writes = self.segments.iter().map(|segment| match segment {
+            Segment::Static(segment) => {
+                let segment = format!("/{}", segment);
+                quote! { __f.write_str(#segment)?; }
+            }
+            Segment::Param(param) => quote! {
+                __f.write_str("/")?;
+                __f.write_str(&::dioxus_router::routable::encode_segment(&#param.to_string()))?;
+            },
+        });
+        let root = match self.segments.is_empty() {
+            true => quote! { __f.write_str("/")?; },
+            false => quote! {},
+        };
+
+        let bindings = match &self.fields {
+            Some(fields) => {
+                let names = fields.iter().map(|(name, _)| name);
+                quote! { { #(#names),* } }
+            }
+            None => quote! {},
+        };
+
+        quote! {
+            Self::#variant #bindings => {
+                #root
+                #(#writes)*
+            }
+        }
+    }
+
+    fn from_str(&self) -> TokenStream2 {
+        let variant = &self.variant;
+
+        let patterns = self.segments.iter().map(|segment| match segment {
+            Segment::Static(segment) => quote! { #segment },
+            Segment::Param(param) => quote! { #param },
+        });
+
+        match &self.fields {
+            Some(fields) => {
+                let names: Vec<_> = fields.iter().map(|(name, _)| name).collect();
+                let types = fields.iter().map(|(_, ty)| ty);
+                quote! {
+                    if let [#(#patterns),*] = segments[..] {
+                        if let (#(Ok(#names),)*) = (#(<#types as ::core::str::FromStr>::from_str(#names),)*) {
+                            return Ok(Self::#variant { #(#names),* });
+                        }
+                    }
+                }
+            }
+            None => quote! {
+                if let [#(#patterns),*] = segments[..] {
+                    return Ok(Self::#variant);
+                }
+            },
+        }
+    }
+}
+
+/// Split a path like `/user/:id` into its segments
+fn parse_pattern(pattern: &LitStr) -> syn::Result<Vec<Segment>> {
+    let value = pattern.value();
+    let error = |message: &str| syn::Error::new_spanned(pattern, message);
+
+    let path = value
+        .strip_prefix('/')
+        .ok_or_else(|| error("the path of a route starts with a /"))?;
+    if path.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    path.split('/')
+        .map(|segment| {
+            if segment.is_empty() {
+                return Err(error(
+                    "the path of a route can't have empty segments, or end with a /",
+                ));
+            }
+            if segment.contains(|c| c == '?' || c == '#') {
+                return Err(error(
+                    "the path of a route can't have a query or a fragment",
+                ));
+            }
+            match segment.strip_prefix(':') {
+                Some(param) => syn::parse_str::<Ident>(param)
+                    .map(Segment::Param)
+                    .map_err(|_| {
+                        error(&format!(
+                            "the parameter :{} of the route isn't an identifier",
+                            param
+                        ))
+                    }),
+                None => Ok(Segment::Static(segment.to_string())),
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "Routable can only be derived for enums",
+            ))
+        }
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "a Routable enum can't be generic",
+        ));
+    }
+
+    let routes = variants
+        .iter()
+        .map(Route::parse)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    for (idx, route) in routes.iter().enumerate() {
+        if let Some(earlier) = routes[..idx].iter().find(|earlier| earlier.overlaps(route)) {
+            return Err(syn::Error::new_spanned(
+                &route.pattern,
+                format!(
+                    "the route of {} matches the same paths as the route of {}",
+                    route.variant, earlier.variant
+                ),
+            ));
+        }
+    }
+
+    let patterns = routes.iter().map(|route| &route.pattern);
+    let pattern_of = routes.iter().map(Route::pattern_of);
+    let display = routes.iter().map(Route::display);
+
+    let mut by_specificity: Vec<&Route> = routes.iter().collect();
+    by_specificity.sort_by_key(|route| route.specificity());
+    let from_str = by_specificity.iter().map(|route| route.from_str());
+
+    Ok(quote! {
+        impl ::dioxus_router::Routable for #name {
+            const ROUTES: &'static [&'static str] = &[#(#patterns),*];
+
+            fn pattern(&self) -> &'static str {
+                match self {
+                    #(#pattern_of)*
+                }
+            }
+        }
+
+        impl ::core::fmt::Display for #name {
+            fn fmt(&self, __f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    #(#display)*
+                }
+                Ok(())
+            }
+        }
+
+        impl ::core::str::FromStr for #name {
+            type Err = ::dioxus_router::RouteParseError;
+
+            fn from_str(path: &str) -> ::core::result::Result<Self, Self::Err> {
+                let segments = ::dioxus_router::routable::split_path(path);
+                let segments: ::std::vec::Vec<&str> = segments.iter().map(|segment| segment.as_str()).collect();
+
+                #(#from_str)*
+
+                Err(::dioxus_router::RouteParseError {
+                    path: path.to_string(),
+                })
+            }
+        }
+    })
+}
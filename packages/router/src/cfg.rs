@@ -1,6 +1,10 @@
+use dioxus_history::History;
+use std::rc::Rc;
+
 #[derive(Default)]
 pub struct RouterCfg {
     pub base_url: Option<String>,
     pub active_class: Option<String>,
     pub initial_url: Option<String>,
+    pub history: Option<Rc<dyn History>>,
 }
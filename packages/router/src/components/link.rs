@@ -1,6 +1,26 @@
 use crate::{use_route, RouterContext};
 use dioxus::prelude::*;
 
+/// When a [`Link`] loads what its route needs ahead of time, with the handlers of
+/// [`use_prefetch`](crate::use_prefetch)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prefetch {
+    /// Only once the link is followed
+    Never,
+
+    /// When the pointer moves over the link, or it's focused
+    Hover,
+
+    /// When the link scrolls into view, on renderers that can tell
+    Visible,
+}
+
+impl Default for Prefetch {
+    fn default() -> Self {
+        Prefetch::Never
+    }
+}
+
 /// Props for the [`Link`](struct.Link.html) component.
 #[derive(Props)]
 pub struct LinkProps<'a> {
@@ -29,6 +49,19 @@ pub struct LinkProps<'a> {
     #[props(default, strip_option)]
     pub active_class: Option<&'a str>,
 
+    /// Only be active when the current route is exactly the "to" route.
+    ///
+    /// Links that aren't exact are also active on the routes nested in theirs, like a link to `/blog` on
+    /// `/blog/7`. By default set to `true`.
+    #[props(default = true)]
+    pub exact: bool,
+
+    /// Load what the route needs before the link is followed, so navigating to it is instant.
+    ///
+    /// By default set to [`Prefetch::Never`].
+    #[props(default)]
+    pub prefetch: Prefetch,
+
     /// Set the ID of the inner link ['a'](https://www.w3schools.com/tags/tag_a.asp) element.
     ///
     /// This can be useful when styling the inner link element.
@@ -90,6 +123,8 @@ pub fn Link<'a>(cx: Scope<'a, LinkProps<'a>>) -> Element {
         new_tab,
         children,
         active_class,
+        exact,
+        prefetch,
         ..
     } = cx.props;
 
@@ -111,9 +146,23 @@ pub fn Link<'a>(cx: Scope<'a, LinkProps<'a>>) -> Element {
     let route = use_route(cx);
     let url = route.url();
     let path = url.path();
-    let active = path == cx.props.to;
+    let active = is_active(path, to, *exact);
     let active_class = if active { active_class_name } else { "".into() };
 
+    let visibility = use_intersection_observer(cx);
+    let prefetch_route = move || {
+        if let (false, Some(service)) = (outerlink, svc) {
+            service.prefetch(to);
+        }
+    };
+    if *prefetch == Prefetch::Visible
+        && visibility
+            .entry()
+            .map_or(false, |entry| entry.is_intersecting)
+    {
+        prefetch_route();
+    }
+
     cx.render(rsx! {
         a {
             href: "{to}",
@@ -122,6 +171,21 @@ pub fn Link<'a>(cx: Scope<'a, LinkProps<'a>>) -> Element {
             title: format_args!("{}", title.unwrap_or("")),
             prevent_default: "{prevent_default}",
             target: format_args!("{}", if * new_tab { "_blank" } else { "" }),
+            onmounted: move |evt| {
+                if *prefetch == Prefetch::Visible {
+                    let _ = visibility.observe(&evt);
+                }
+            },
+            onmouseenter: move |_| {
+                if *prefetch == Prefetch::Hover {
+                    prefetch_route();
+                }
+            },
+            onfocus: move |_| {
+                if *prefetch == Prefetch::Hover {
+                    prefetch_route();
+                }
+            },
             onclick: move |evt| {
                 log::trace!("Clicked link to {}", to);
 
@@ -130,8 +194,9 @@ pub fn Link<'a>(cx: Scope<'a, LinkProps<'a>>) -> Element {
                         log::trace!("Pushing route to {}", to);
                         service.push_route(to, cx.props.title.map(|f| f.to_string()), None);
 
+                        // the router scrolls to the element a fragment names instead
                         #[cfg(feature = "web")]
-                        {
+                        if !to.contains('#') {
                             web_sys::window().unwrap().scroll_to_with_x_and_y(0.0, 0.0);
                         }
                     } else {
@@ -150,3 +215,15 @@ pub fn Link<'a>(cx: Scope<'a, LinkProps<'a>>) -> Element {
         }
     })
 }
+
+/// Whether a link to `to` is active on `path`, which it is on the routes nested in `to` too unless it's exact
+fn is_active(path: &str, to: &str, exact: bool) -> bool {
+    if path == to {
+        return true;
+    }
+    let to = to.trim_end_matches('/');
+    !exact
+        && path
+            .strip_prefix(to)
+            .map_or(false, |rest| rest.starts_with('/'))
+}
@@ -0,0 +1,37 @@
+use dioxus::prelude::*;
+
+/// The props of a [`Layout`](crate::Layout), which it passes on to its [`Outlet`]
+#[derive(Props)]
+pub struct LayoutProps<'a> {
+    pub(crate) outlet: &'a Element<'a>,
+}
+
+/// Props for the [`Outlet`](struct.Outlet.html) component.
+#[derive(Props)]
+pub struct OutletProps<'a> {
+    /// The props of the layout the outlet is in.
+    pub layout: &'a LayoutProps<'a>,
+}
+
+/// A component that renders the route nested in the route of a layout that matches the current location.
+///
+/// The routes nested in the route see its parameters as well as their own, with [`use_route`](crate::use_route).
+///
+/// # Example
+///
+/// ```rust, ignore
+/// fn BlogLayout<'a>(cx: Scope<'a, LayoutProps<'a>>) -> Element<'a> {
+///     cx.render(rsx! {
+///         nav {
+///             Link { to: "/blog", "All posts" }
+///         }
+///         main {
+///             Outlet { layout: cx.props }
+///         }
+///     })
+/// }
+/// ```
+pub fn Outlet<'a>(cx: Scope<'a, OutletProps<'a>>) -> Element {
+    let outlet: &Element = cx.props.layout.outlet;
+    cx.render(rsx!(&*outlet))
+}
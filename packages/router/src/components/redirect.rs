@@ -1,6 +1,8 @@
 use dioxus::prelude::*;
 
-use crate::use_router;
+use crate::service::join_routes;
+use crate::{use_router, RouteContext};
+use url::Url;
 
 /// The props for the [`Router`](fn.Router.html) component.
 #[derive(Props)]
@@ -30,22 +32,57 @@ pub struct RedirectProps<'a> {
 
 /// If this component is rendered, it will redirect the user to the given route.
 ///
-/// It will replace the current route rather than pushing the current one to the stack.
+/// It will replace the current route rather than pushing the current one to the stack. A redirect with a `from` route
+/// is one of the routes of the router, and fills the parameters of `to` with the ones of `from` by their names:
+///
+/// ```rust, ignore
+/// Redirect { from: "/posts/:id", to: "/blog/:id" }
+/// ```
 pub fn Redirect<'a>(cx: Scope<'a, RedirectProps<'a>>) -> Element {
     let router = use_router(cx);
 
-    let immediate_redirect = cx.use_hook(|| {
-        if let Some(from) = cx.props.from {
-            router.register_total_route(from.to_string(), cx.scope_id());
-            false
-        } else {
-            true
-        }
+    let from = cx.use_hook(|| {
+        let from = cx.props.from?;
+
+        // a redirect nested in a route is one of its nested routes
+        let (from, parent) = match cx.consume_context::<RouteContext>() {
+            Some(ctx) => (join_routes(&ctx.total_route, from), Some(ctx.scope)),
+            None => (from.to_string(), None),
+        };
+        router.register_total_route(from.clone(), cx.scope_id(), parent, false);
+        Some(from)
     });
 
-    if *immediate_redirect || router.should_render(cx.scope_id()) {
-        router.replace_route(cx.props.to, None, None);
+    match from {
+        None => router.replace_route(cx.props.to, None, None),
+        Some(from) if router.should_render(cx.scope_id()) => {
+            let to = fill_params(cx.props.to, from, &router.current_location().url);
+            router.replace_route(&to, None, None);
+        }
+        Some(_) => {}
     }
 
     cx.render(rsx!(()))
 }
+
+/// Fill the parameters of `to` with the segments of the path that are in the place of the same parameters in `from`
+fn fill_params(to: &str, from: &str, url: &Url) -> String {
+    let segments: Vec<&str> = url
+        .path_segments()
+        .map(|segments| segments.collect())
+        .unwrap_or_default();
+    let params: Vec<&str> = from.trim_start_matches('/').split('/').collect();
+
+    to.split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(name) => params
+                .iter()
+                .position(|param| param.strip_prefix(':') == Some(name))
+                .and_then(|idx| segments.get(idx))
+                .copied()
+                .unwrap_or(segment),
+            None => segment,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
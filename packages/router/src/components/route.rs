@@ -1,12 +1,25 @@
-use crate::{RouteContext, RouterContext};
+use crate::service::join_routes;
+use crate::{LayoutProps, RouteContext, RouterContext};
 use dioxus::prelude::*;
 
+/// A component that renders the routes nested in a [`Route`] wherever it places an [`Outlet`]
+pub type Layout = for<'a> fn(Scope<'a, LayoutProps<'a>>) -> Element<'a>;
+
 /// Props for the [`Route`](struct.Route.html) component.
 #[derive(Props)]
 pub struct RouteProps<'a> {
     /// The path to match.
+    ///
+    /// The path of a route nested in another one is relative to it.
     pub to: &'a str,
 
+    /// The layout to render around the routes nested in this one.
+    ///
+    /// A route with a layout matches every path that starts with its own, and renders the layout with the first of its
+    /// nested routes that matches the rest in its [`Outlet`](crate::Outlet).
+    #[props(default, strip_option)]
+    pub layout: Option<Layout>,
+
     /// The component to render when the path matches.
     pub children: Element<'a>,
 }
@@ -20,7 +33,10 @@ pub struct RouteProps<'a> {
 ///     Router {
 ///         Route { to: "/home", Home {} }
 ///         Route { to: "/about", About {} }
-///         Route { to: "/Blog", Blog {} }
+///         Route { to: "/blog", layout: BlogLayout,
+///             Route { to: "/", BlogList {} }
+///             Route { to: ":id", BlogPost {} }
+///         }
 ///     }
 /// )
 /// ```
@@ -30,26 +46,38 @@ pub fn Route<'a>(cx: Scope<'a, RouteProps<'a>>) -> Element {
 
     cx.use_hook(|| {
         // create a bigger, better, longer route if one above us exists
-        let total_route = match root_context {
-            Some(ctx) => ctx.total_route.clone(),
-            None => cx.props.to.to_string(),
+        let (total_route, parent) = match root_context {
+            Some(ctx) => (join_routes(&ctx.total_route, cx.props.to), Some(ctx.scope)),
+            None => (cx.props.to.to_string(), None),
         };
 
         // provide our route context
         let route_context = cx.provide_context(RouteContext {
             declared_route: cx.props.to.to_string(),
             total_route,
+            scope: cx.scope_id(),
         });
 
         // submit our rout
-        router_root.register_total_route(route_context.total_route, cx.scope_id());
+        router_root.register_total_route(
+            route_context.total_route,
+            cx.scope_id(),
+            parent,
+            cx.props.layout.is_some(),
+        );
     });
 
     log::debug!("Checking Route: {:?}", cx.props.to);
 
     if router_root.should_render(cx.scope_id()) {
         log::debug!("Route should render: {:?}", cx.scope_id());
-        cx.render(rsx!(&cx.props.children))
+        match cx.props.layout {
+            Some(layout) => {
+                let outlet = &cx.props.children;
+                cx.render(rsx!(cx.component(layout, LayoutProps { outlet }, "Layout")))
+            }
+            None => cx.render(rsx!(&cx.props.children)),
+        }
     } else {
         log::debug!("Route should *not* render: {:?}", cx.scope_id());
         cx.render(rsx!(()))
@@ -1,5 +1,7 @@
 use crate::{cfg::RouterCfg, RouterContext, RouterService};
 use dioxus::prelude::*;
+use dioxus_history::History;
+use std::rc::Rc;
 
 /// The props for the [`Router`](fn.Router.html) component.
 #[derive(Props)]
@@ -30,13 +32,17 @@ pub struct RouterProps<'a> {
 
     /// Set the initial url.
     pub initial_url: Option<String>,
+
+    /// The history to keep the routes in, instead of the default one of the platform.
+    ///
+    /// A [`HashHistory`](dioxus_history::HashHistory) keeps the routes in the fragment of the url, and a
+    /// [`MemoryHistory`](dioxus_history::MemoryHistory) is handy for tests.
+    pub history: Option<Rc<dyn History>>,
 }
 
 /// A component that conditionally renders children based on the current location of the app.
 ///
-/// Uses BrowserRouter in the browser and HashRouter everywhere else.
-///
-/// Will fallback to HashRouter is BrowserRouter is not available, or through configuration.
+/// Uses a BrowserHistory in the browser and a MemoryHistory everywhere else, unless a history is passed in.
 #[allow(non_snake_case)]
 pub fn Router<'a>(cx: Scope<'a, RouterProps<'a>>) -> Element {
     let svc = cx.use_hook(|| {
@@ -46,14 +52,41 @@ pub fn Router<'a>(cx: Scope<'a, RouterProps<'a>>) -> Element {
                 base_url: cx.props.base_url.map(|s| s.to_string()),
                 active_class: cx.props.active_class.map(|s| s.to_string()),
                 initial_url: cx.props.initial_url.clone(),
+                history: cx.props.history.clone(),
             },
         ))
     });
 
     // next time we run the rout_found will be filled
-    if svc.route_found.get().is_none() {
+    if svc.route_found.borrow().is_empty() {
         cx.props.onchange.call(svc.clone());
     }
 
+    // the page goes to the element the fragment of the url names, once the route rendered it
+    #[cfg(feature = "web")]
+    {
+        let location = svc.current_location();
+        let scrolled = cx.use_hook(|| std::cell::RefCell::new(None));
+        if scrolled.borrow().as_ref() != Some(&location.url) {
+            scrolled.replace(Some(location.url.clone()));
+            if let Some(fragment) = location.url.fragment() {
+                let id = percent_encoding::percent_decode_str(fragment)
+                    .decode_utf8_lossy()
+                    .into_owned();
+                cx.push_on_mount(move || scroll_to_anchor(&id));
+            }
+        }
+    }
+
     cx.render(rsx!(&cx.props.children))
 }
+
+#[cfg(feature = "web")]
+fn scroll_to_anchor(id: &str) {
+    let element = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(id));
+    if let Some(element) = element {
+        element.scroll_into_view();
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{ParsedRoute, RouterContext};
+use futures_channel::mpsc::UnboundedReceiver;
+use futures_util::future::{self, Either};
+use futures_util::StreamExt;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// The most redirects a navigation can go through before the router gives up on it
+const MAX_REDIRECTS: usize = 16;
+
+/// A navigation the guards of the router get to decide on before it happens
+#[derive(Debug, Clone)]
+pub struct Navigation {
+    /// The route the router is on
+    pub from: Arc<ParsedRoute>,
+
+    /// The route the router is about to go to
+    pub to: Arc<ParsedRoute>,
+}
+
+/// What a guard decided to do with a [`Navigation`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NavigationDecision {
+    /// Let the navigation go on to the next guard, and happen once every guard allowed it
+    Allow,
+
+    /// Stay on the route the router is on
+    Cancel,
+
+    /// Go to another route instead, which goes through the guards as well
+    Redirect(String),
+}
+
+pub(crate) type Guard = Rc<dyn Fn(Navigation) -> Pin<Box<dyn Future<Output = NavigationDecision>>>>;
+
+/// How a navigation reaches the history once it's allowed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NavigationKind {
+    Push,
+    Replace,
+    // the history is already on the route, since it moved on its own
+    History,
+}
+
+pub(crate) struct PendingNavigation {
+    pub(crate) kind: NavigationKind,
+    pub(crate) to: Arc<ParsedRoute>,
+}
+
+/// Run the navigations of the router through its guards, one after the other
+///
+/// A navigation that comes in while the guards are still deciding on the last one takes its place, so a slow check
+/// can't send the app to a route the user already left.
+pub(crate) async fn resolve_navigations(
+    router: RouterContext,
+    mut navigations: UnboundedReceiver<PendingNavigation>,
+) {
+    let mut next = None;
+    loop {
+        let navigation = match next.take() {
+            Some(navigation) => navigation,
+            None => match navigations.next().await {
+                Some(navigation) => navigation,
+                None => return,
+            },
+        };
+
+        let resolving = resolve(&router, navigation);
+        futures_util::pin_mut!(resolving);
+        match future::select(resolving, navigations.next()).await {
+            Either::Left(_) => {}
+            Either::Right((Some(newer), _)) => next = Some(newer),
+            Either::Right((None, _)) => return,
+        }
+    }
+}
+
+async fn resolve(router: &RouterContext, mut navigation: PendingNavigation) {
+    // the guards that are added while the navigation is checked only see the next one
+    let guards: Vec<Guard> = router
+        .guards
+        .borrow()
+        .iter()
+        .map(|(_, guard)| guard.clone())
+        .collect();
+
+    for _ in 0..=MAX_REDIRECTS {
+        let mut redirect = None;
+        for guard in &guards {
+            let decision = guard(Navigation {
+                from: router.current_location(),
+                to: navigation.to.clone(),
+            })
+            .await;
+
+            match decision {
+                NavigationDecision::Allow => {}
+                NavigationDecision::Cancel => {
+                    router.cancel();
+                    return;
+                }
+                NavigationDecision::Redirect(route) => {
+                    redirect = Some(route);
+                    break;
+                }
+            }
+        }
+
+        let route = match redirect {
+            Some(route) => route,
+            None => {
+                router.commit(navigation);
+                return;
+            }
+        };
+
+        let to = match router.parse_route(&route, None, None) {
+            Some(to) => to,
+            None => {
+                log::error!(
+                    "A navigation guard redirected to {}, which isn't a route",
+                    route
+                );
+                router.cancel();
+                return;
+            }
+        };
+
+        // the entry the history moved to is taken over by the route it's redirected to
+        let kind = match navigation.kind {
+            NavigationKind::History => NavigationKind::Replace,
+            kind => kind,
+        };
+        navigation = PendingNavigation { kind, to };
+    }
+
+    log::error!(
+        "The navigation guards redirected more than {} times, so the router stays where it is",
+        MAX_REDIRECTS
+    );
+    router.cancel();
+}
@@ -0,0 +1,36 @@
+use crate::{use_router, Navigation, NavigationDecision};
+use dioxus::core::ScopeState;
+use std::future::Future;
+
+/// Decide on every navigation of the router for as long as this component is around
+///
+/// The guard can cancel the navigation, redirect it to another route, or await whatever it needs to know first, like
+/// whether the user is signed in. The router stays on its route until every guard allowed the navigation. The guard is
+/// added the first time the component renders, and also decides on the route the router is on at that moment.
+///
+/// ```rust, ignore
+/// fn App(cx: Scope) -> Element {
+///     let user = use_shared_state::<User>(cx).unwrap().clone();
+///     use_navigation_guard(cx, move |navigation| {
+///         let signed_in = user.read().signed_in;
+///         async move {
+///             match navigation.to.url.path().starts_with("/admin") && !signed_in {
+///                 true => NavigationDecision::Redirect("/login".into()),
+///                 false => NavigationDecision::Allow,
+///             }
+///         }
+///     });
+///     // ...
+/// }
+/// ```
+pub fn use_navigation_guard<F>(cx: &ScopeState, guard: impl Fn(Navigation) -> F + 'static)
+where
+    F: Future<Output = NavigationDecision> + 'static,
+{
+    let router = use_router(cx);
+    cx.use_hook(|| {
+        let id = router.add_guard(guard);
+        let router = router.clone();
+        cx.push_on_drop(move || router.remove_guard(id));
+    });
+}
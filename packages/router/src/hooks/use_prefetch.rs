@@ -0,0 +1,29 @@
+use crate::use_router;
+use dioxus::core::ScopeState;
+use url::Url;
+
+/// Load what a route needs ahead of time whenever a [`Link`](crate::Link) to it asks for it, for as long as this
+/// component is around
+///
+/// The handler is called once for every url a link prefetches, so it can start the query the route renders with, or
+/// warm up a cache.
+///
+/// ```rust, ignore
+/// fn App(cx: Scope) -> Element {
+///     let posts = use_shared_state::<PostCache>(cx).unwrap().clone();
+///     use_prefetch(cx, move |url| {
+///         if let Some(id) = url.path().strip_prefix("/blog/") {
+///             posts.write().load(id);
+///         }
+///     });
+///     // ...
+/// }
+/// ```
+pub fn use_prefetch(cx: &ScopeState, prefetcher: impl Fn(&Url) + 'static) {
+    let router = use_router(cx);
+    cx.use_hook(|| {
+        let id = router.add_prefetcher(prefetcher);
+        let router = router.clone();
+        cx.push_on_drop(move || router.remove_prefetcher(id));
+    });
+}
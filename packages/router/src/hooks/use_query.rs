@@ -0,0 +1,38 @@
+use crate::use_route;
+use dioxus::core::ScopeState;
+use serde::de::DeserializeOwned;
+
+/// A query string that doesn't deserialize into the type it was parsed as
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("the query {query:?} doesn't deserialize: {message}")]
+pub struct QueryError {
+    /// The query string, without the `?`
+    pub query: String,
+
+    /// Why it doesn't deserialize, like a field that's missing
+    pub message: String,
+}
+
+/// Deserialize the query string of the current route into a `T`, rendering the component again when the route changes
+///
+/// A route without a query deserializes like an empty one, so structs with only optional or defaulted fields are
+/// always there.
+///
+/// ```rust, ignore
+/// #[derive(Deserialize)]
+/// struct Search {
+///     q: String,
+///     #[serde(default)]
+///     page: usize,
+/// }
+///
+/// fn Results(cx: Scope) -> Element {
+///     match use_query::<Search>(cx) {
+///         Ok(search) => render! { "results for {search.q}, page {search.page}" },
+///         Err(err) => render! { "invalid search: {err}" },
+///     }
+/// }
+/// ```
+pub fn use_query<T: DeserializeOwned>(cx: &ScopeState) -> Result<T, QueryError> {
+    use_route(cx).parse_query()
+}
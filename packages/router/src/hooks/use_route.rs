@@ -1,4 +1,4 @@
-use crate::{ParsedRoute, RouteContext, RouterContext};
+use crate::{ParsedRoute, Routable, RouteContext, RouterContext};
 use dioxus::core::{ScopeId, ScopeState};
 use std::{borrow::Cow, str::FromStr, sync::Arc};
 use url::Url;
@@ -54,6 +54,18 @@ impl UseRoute {
         serde_urlencoded::from_str(query).ok()
     }
 
+    /// Deserialize the query string into a `T`, with the reason it doesn't fit if it doesn't.
+    ///
+    /// A route without a query deserializes like an empty one.
+    #[cfg(feature = "query")]
+    pub fn parse_query<T: serde::de::DeserializeOwned>(&self) -> Result<T, crate::QueryError> {
+        let query = self.url().query().unwrap_or_default();
+        serde_urlencoded::from_str(query).map_err(|err| crate::QueryError {
+            query: query.to_string(),
+            message: err.to_string(),
+        })
+    }
+
     /// Get the first query parameter given the parameter name.
     ///
     /// If you need to get more than one parameter, use [`query_pairs`] on the [`Url`] instead.
@@ -65,6 +77,14 @@ impl UseRoute {
             .map(|(_, v)| v)
     }
 
+    /// Get the fragment of the url, without the `#` and with its escapes decoded.
+    ///
+    /// The [`Router`](crate::Router) scrolls to the element with the fragment as its id once the route rendered.
+    pub fn fragment(&self) -> Option<Cow<str>> {
+        let fragment = self.route.url.fragment()?;
+        Some(percent_encoding::percent_decode_str(fragment).decode_utf8_lossy())
+    }
+
     /// Returns the nth segment in the path. Paths that end with a slash have
     /// the slash removed before determining the segments. If the path has
     /// fewer segments than `n` then this method returns `None`.
@@ -108,6 +128,20 @@ impl UseRoute {
         self.segment(name).map(|value| value.parse::<T>())
     }
 
+    /// Parse the current path into a typed route. This method returns `None`
+    /// if the path doesn't match any of the routes of `R`.
+    ///
+    /// ```rust, ignore
+    /// match use_route(cx).parse_route::<Route>() {
+    ///     Some(Route::Home) => render! { Home {} },
+    ///     Some(Route::User { id }) => render! { User { id: id } },
+    ///     None => render! { NotFound {} },
+    /// }
+    /// ```
+    pub fn parse_route<R: Routable>(&self) -> Option<R> {
+        self.route.url.path().parse().ok()
+    }
+
     /// Get the named parameter from the path, as defined in your router. The
     /// value will be parsed into the type specified by `T` by calling
     /// `value.parse::<T>()`. This method returns `None` if the named
@@ -2,8 +2,16 @@
 #![warn(missing_docs)]
 
 mod hooks {
+    mod use_navigation_guard;
+    mod use_prefetch;
+    #[cfg(feature = "query")]
+    mod use_query;
     mod use_route;
     mod use_router;
+    pub use use_navigation_guard::*;
+    pub use use_prefetch::*;
+    #[cfg(feature = "query")]
+    pub use use_query::*;
     pub use use_route::*;
     pub use use_router::*;
 }
@@ -13,11 +21,13 @@ mod components {
     #![allow(non_snake_case)]
 
     mod link;
+    mod outlet;
     mod redirect;
     mod route;
     mod router;
 
     pub use link::*;
+    pub use outlet::*;
     pub use redirect::*;
     pub use route::*;
     pub use router::*;
@@ -25,9 +35,15 @@ mod components {
 pub use components::*;
 
 mod cfg;
+mod guard;
+pub mod routable;
 mod routecontext;
 mod service;
 
+pub use dioxus_history as history;
+pub use dioxus_router_macro::Routable;
+pub use guard::{Navigation, NavigationDecision};
+pub use routable::{Routable, RouteParseError};
 pub use routecontext::*;
 pub use service::*;
 
@@ -0,0 +1,79 @@
+//! Routes as the variants of an enum, instead of strings
+//!
+//! ```rust, ignore
+//! #[derive(Clone, Debug, PartialEq, Routable)]
+//! enum Route {
+//!     #[route("/")]
+//!     Home,
+//!     #[route("/user/:id")]
+//!     User { id: u32 },
+//! }
+//!
+//! fn Nav(cx: Scope) -> Element {
+//!     let user = Route::User { id: 7 };
+//!     render! { Link { to: "{user}", "Profile" } }
+//! }
+//! ```
+//!
+//! Links are made from the variants, so renaming or removing a route breaks the links to it at compile time instead
+//! of at runtime, and matching on the current route has to handle every route there is.
+
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A route of an app, usually derived with [`macro@crate::Routable`]
+///
+/// Its `Display` writes the path of the route, and its `FromStr` parses a path back into the route.
+pub trait Routable: FromStr<Err = RouteParseError> + Display + Sized {
+    /// The paths of the routes, like `/user/:id`, in the order they're declared in
+    const ROUTES: &'static [&'static str];
+
+    /// The path this route was declared with, like `/user/:id`
+    fn pattern(&self) -> &'static str;
+}
+
+/// A path that doesn't match any of the routes of a [`Routable`], or where a parameter doesn't parse
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("no route matches the path {path:?}")]
+pub struct RouteParseError {
+    /// The path that was parsed
+    pub path: String,
+}
+
+/// The characters that are escaped in a segment of a path
+const SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// Escape a parameter of a route, so it's a single segment of the path
+#[doc(hidden)]
+pub fn encode_segment(segment: &str) -> String {
+    utf8_percent_encode(segment, SEGMENT).to_string()
+}
+
+/// Split a path into its unescaped segments, without its query, its fragment, or a slash at its end
+#[doc(hidden)]
+pub fn split_path(path: &str) -> Vec<String> {
+    let path = path
+        .split(|c| c == '?' || c == '#')
+        .next()
+        .unwrap_or_default();
+    let path = path.trim_start_matches('/').trim_end_matches('/');
+    if path.is_empty() {
+        return Vec::new();
+    }
+
+    path.split('/')
+        .map(|segment| percent_decode_str(segment).decode_utf8_lossy().into_owned())
+        .collect()
+}
@@ -1,3 +1,5 @@
+use dioxus::core::ScopeId;
+
 /// A `RouteContext` is a context that is provided by [`Route`](fn.Route.html) components.
 ///
 /// This signals to all child [`Route`] and [`Link`] components that they are
@@ -21,4 +23,7 @@ pub struct RouteContext {
     /// "/level0/level1/:id"
     /// ```
     pub total_route: String,
+
+    // the Route that provides the context, which the routes nested in it are registered under
+    pub(crate) scope: ScopeId,
 }
@@ -2,17 +2,20 @@
 // does each window have its own router? probably, lol
 
 use crate::cfg::RouterCfg;
+use crate::guard::{resolve_navigations, Guard, NavigationKind, PendingNavigation};
+use crate::{Navigation, NavigationDecision};
 use dioxus::core::{ScopeId, ScopeState, VirtualDom};
-use std::any::Any;
-use std::rc::Weak;
+use dioxus_history::History;
+use futures_channel::mpsc::UnboundedSender;
 use std::{
     cell::{Cell, RefCell},
     collections::{HashMap, HashSet},
+    future::Future,
     rc::Rc,
     str::FromStr,
     sync::Arc,
 };
-use url::Url;
+use url::{Position, Url};
 
 /// A clonable handle to the router
 pub type RouterContext = Rc<RouterService>;
@@ -41,21 +44,41 @@ pub type RouterContext = Rc<RouterService>;
 ///
 /// # Platform Specific
 ///
-/// - On the web, this is a [`BrowserHistory`](https://docs.rs/gloo/0.3.0/gloo/history/struct.BrowserHistory.html).
-/// - On desktop, mobile, and SSR, this is just a Vec of Strings. Currently on
-///   desktop, there is no way to tap into forward/back for the app unless explicitly set.
+/// The locations are kept in a [`History`], which is the one passed to the [`Router`](crate::Router), or the one
+/// provided as a `Rc<dyn History>` context, like liveview does. Otherwise it's a
+/// [`BrowserHistory`](dioxus_history::BrowserHistory) with the `web` feature and a
+/// [`MemoryHistory`](dioxus_history::MemoryHistory) everywhere else.
 pub struct RouterService {
-    pub(crate) route_found: Cell<Option<ScopeId>>,
+    // the route that rendered at every level of nesting, by the route it's nested in
+    pub(crate) route_found: RefCell<HashMap<Option<ScopeId>, ScopeId>>,
 
-    pub(crate) stack: RefCell<Vec<Arc<ParsedRoute>>>,
+    pub(crate) current: RefCell<Arc<ParsedRoute>>,
+
+    // the routes are joined onto this, since histories only know paths
+    pub(crate) base: Url,
 
     pub(crate) slots: Rc<RefCell<HashMap<ScopeId, String>>>,
 
     pub(crate) ordering: Rc<RefCell<Vec<ScopeId>>>,
 
+    // the route every route is nested in, and whether it's a layout that matches the paths it starts
+    pub(crate) nesting: RefCell<HashMap<ScopeId, (Option<ScopeId>, bool)>>,
+
     pub(crate) onchange_listeners: Rc<RefCell<HashSet<ScopeId>>>,
 
-    pub(crate) history: Box<dyn RouterProvider>,
+    pub(crate) guards: RefCell<Vec<(usize, Guard)>>,
+
+    pub(crate) next_handler: Cell<usize>,
+
+    pub(crate) prefetchers: RefCell<Vec<(usize, Rc<dyn Fn(&Url)>)>>,
+
+    // the urls that were prefetched already, so every link to them doesn't load them again
+    pub(crate) prefetched: RefCell<HashSet<Url>>,
+
+    // the navigations that wait for the guards, which the router resolves in a task of its own
+    pub(crate) navigations: UnboundedSender<PendingNavigation>,
+
+    pub(crate) history: Rc<dyn History>,
 
     pub(crate) regen_any_route: Arc<dyn Fn(ScopeId)>,
 
@@ -74,44 +97,71 @@ pub struct ParsedRoute {
     pub title: Option<String>,
 
     /// The serialized state of the route.
+    ///
+    /// The state only stays with the route while it's current. It isn't kept in the history.
     pub serialized_state: Option<String>,
 }
 
 impl RouterService {
-    pub(crate) fn new(cx: &ScopeState, cfg: RouterCfg) -> RouterContext {
-        #[cfg(feature = "web")]
-        let history = Box::new(web::new());
-
-        #[cfg(not(feature = "web"))]
-        let history = Box::new(hash::new());
-
-        let route = match &cfg.initial_url {
-            Some(url) => Arc::new(ParsedRoute {
-                url: Url::from_str(url).unwrap_or_else(|_|
-                    panic!(
-                        "RouterCfg expects a valid initial_url, but got '{}'. Example: '{{scheme}}://{{?authority}}/{{?path}}'",
-                        &url
-                    )
-                ),
-                title: None,
-                serialized_state: None,
-            }),
-            None => Arc::new(history.init_location()),
+    pub(crate) fn new(cx: &ScopeState, mut cfg: RouterCfg) -> RouterContext {
+        let history = cfg
+            .history
+            .take()
+            .or_else(|| cx.consume_context::<Rc<dyn History>>())
+            .unwrap_or_else(default_history);
+
+        let initial_url = cfg.initial_url.as_ref().map(|url| {
+            Url::from_str(url).unwrap_or_else(|_|
+                panic!(
+                    "RouterCfg expects a valid initial_url, but got '{}'. Example: '{{scheme}}://{{?authority}}/{{?path}}'",
+                    &url
+                )
+            )
+        });
+        let base = match &initial_url {
+            Some(url) => url.join("/").unwrap(),
+            None => Url::parse("app:///").unwrap(),
         };
 
+        let route = Arc::new(ParsedRoute {
+            url: initial_url.unwrap_or_else(|| base.join(&history.current_path()).unwrap()),
+            title: None,
+            serialized_state: None,
+        });
+
+        let (navigations, pending) = futures_channel::mpsc::unbounded();
+
         let svc = Rc::new(Self {
             cfg,
             regen_any_route: cx.schedule_update_any(),
             router_id: cx.scope_id(),
-            route_found: Cell::new(None),
-            stack: RefCell::new(vec![route]),
+            route_found: Default::default(),
+            current: RefCell::new(route),
+            base,
             ordering: Default::default(),
             slots: Default::default(),
+            nesting: Default::default(),
             onchange_listeners: Default::default(),
+            guards: Default::default(),
+            next_handler: Default::default(),
+            prefetchers: Default::default(),
+            prefetched: Default::default(),
+            navigations,
             history,
         });
 
-        svc.history.attach_listeners(Rc::downgrade(&svc));
+        cx.spawn(resolve_navigations(svc.clone(), pending));
+
+        if let Some(collector) = cx.consume_context::<RouterCollector>() {
+            collector.0.borrow_mut().push(svc.clone());
+        }
+
+        let weak = Rc::downgrade(&svc);
+        svc.history.subscribe(Box::new(move || {
+            if let Some(svc) = weak.upgrade() {
+                svc.follow_history();
+            }
+        }));
 
         svc
     }
@@ -125,20 +175,12 @@ impl RouterService {
 
     /// Push a new route to the history.
     ///
-    /// This will trigger a route change event.
+    /// This will trigger a route change event, once the navigation guards allowed it.
     ///
     /// This does not modify the current route
     pub fn push_route(&self, route: &str, title: Option<String>, serialized_state: Option<String>) {
-        let new_route = Arc::new(ParsedRoute {
-            url: self.current_location().url.join(route).ok().unwrap(),
-            title,
-            serialized_state,
-        });
-
-        self.history.push(&new_route);
-        self.stack.borrow_mut().push(new_route);
-
-        self.regen_routes();
+        let new_route = self.parse_route(route, title, serialized_state).unwrap();
+        self.navigate(NavigationKind::Push, new_route);
     }
 
     /// Instead of pushing a new route, replaces the current route.
@@ -148,34 +190,164 @@ impl RouterService {
         title: Option<String>,
         serialized_state: Option<String>,
     ) {
-        let new_route = Arc::new(ParsedRoute {
-            url: self.current_location().url.join(route).ok().unwrap(),
-            title,
-            serialized_state,
+        let new_route = self.parse_route(route, title, serialized_state).unwrap();
+        self.navigate(NavigationKind::Replace, new_route);
+    }
+
+    /// Set a parameter of the query string of the current route, keeping the others where they are.
+    ///
+    /// The route is replaced rather than pushed, like the filters of a list would be. A parameter that's in the query
+    /// more than once is left with only the new value.
+    pub fn set_query_param(&self, name: &str, value: &str) {
+        self.update_query(|pairs| {
+            match pairs.iter().position(|(key, _)| key == name) {
+                Some(idx) => pairs[idx].1 = value.to_string(),
+                None => pairs.push((name.to_string(), value.to_string())),
+            }
+            let mut seen = false;
+            pairs.retain(|(key, _)| key != name || !std::mem::replace(&mut seen, true));
         });
+    }
 
-        self.history.replace(&new_route);
-        *self.stack.borrow_mut().last_mut().unwrap() = new_route;
+    /// Remove a parameter from the query string of the current route, keeping the others.
+    pub fn remove_query_param(&self, name: &str) {
+        self.update_query(|pairs| pairs.retain(|(key, _)| key != name));
+    }
 
-        self.regen_routes();
+    /// Replace the query string of the current route with a serialized `T`.
+    #[cfg(feature = "query")]
+    pub fn set_query<T: serde::Serialize>(
+        &self,
+        query: &T,
+    ) -> Result<(), serde_urlencoded::ser::Error> {
+        let query = serde_urlencoded::to_string(query)?;
+        let mut url = self.current_location().url.clone();
+        url.set_query(Some(query.as_str()).filter(|query| !query.is_empty()));
+        self.replace_route(&url[Position::BeforePath..], None, None);
+        Ok(())
     }
 
-    /// Pop the current route from the history.
+    fn update_query(&self, update: impl FnOnce(&mut Vec<(String, String)>)) {
+        let mut url = self.current_location().url.clone();
+        let mut pairs: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        update(&mut pairs);
+
+        match pairs.is_empty() {
+            true => url.set_query(None),
+            false => {
+                url.query_pairs_mut().clear().extend_pairs(&pairs);
+            }
+        }
+        self.replace_route(&url[Position::BeforePath..], None, None);
+    }
+
+    /// Go back to the previous route in the history.
+    ///
+    /// The route changes once the history moves, which is right away for a
+    /// [`MemoryHistory`](dioxus_history::MemoryHistory) and after the browser catches up on the web.
     pub fn pop_route(&self) {
-        let mut stack = self.stack.borrow_mut();
+        self.history.go(-1);
+    }
 
-        if stack.len() > 1 {
-            stack.pop();
+    /// Go forward to the next route in the history, if a route was popped.
+    pub fn forward_route(&self) {
+        self.history.go(1);
+    }
+
+    /// Take the current route from the history after it moved on its own
+    fn follow_history(&self) {
+        let url = match self.base.join(&self.history.current_path()) {
+            Ok(url) => url,
+            Err(_) => return,
+        };
+        let route = Arc::new(ParsedRoute {
+            url,
+            title: None,
+            serialized_state: None,
+        });
+
+        self.navigate(NavigationKind::History, route);
+    }
+
+    pub(crate) fn parse_route(
+        &self,
+        route: &str,
+        title: Option<String>,
+        serialized_state: Option<String>,
+    ) -> Option<Arc<ParsedRoute>> {
+        Some(Arc::new(ParsedRoute {
+            url: self.current_location().url.join(route).ok()?,
+            title,
+            serialized_state,
+        }))
+    }
+
+    /// Go to the route right away if there are no guards, or once the guards allowed it
+    fn navigate(&self, kind: NavigationKind, to: Arc<ParsedRoute>) {
+        if self.guards.borrow().is_empty() {
+            return self.commit(PendingNavigation { kind, to });
+        }
+
+        if self
+            .navigations
+            .unbounded_send(PendingNavigation { kind, to })
+            .is_err()
+        {
+            log::error!("The router is gone, so it can't navigate anymore");
+        }
+    }
+
+    pub(crate) fn commit(&self, navigation: PendingNavigation) {
+        let path = &navigation.to.url[Position::BeforePath..];
+        match navigation.kind {
+            NavigationKind::Push => self.history.push(path),
+            NavigationKind::Replace => self.history.replace(path),
+            NavigationKind::History => {}
         }
+        self.current.replace(navigation.to);
 
         self.regen_routes();
     }
 
+    /// Stay on the current route, moving the history back to it if it moved on its own
+    pub(crate) fn cancel(&self) {
+        let current = self.current_location();
+        let path = &current.url[Position::BeforePath..];
+        if self.history.current_path() != path {
+            self.history.replace(path);
+        }
+    }
+
+    /// Add a guard that decides on every navigation of the router from now on, returning the id it can be removed with
+    ///
+    /// The guards run in the order they were added, and a navigation only happens once all of them allowed it. A guard
+    /// also decides on the route the router is on when it's added, like it's being navigated to.
+    ///
+    /// [`use_navigation_guard`](crate::use_navigation_guard) adds a guard for as long as a component is around.
+    pub fn add_guard<F>(&self, guard: impl Fn(Navigation) -> F + 'static) -> usize
+    where
+        F: Future<Output = NavigationDecision> + 'static,
+    {
+        let id = self.next_handler.get();
+        self.next_handler.set(id + 1);
+
+        let guard: Guard = Rc::new(move |navigation| Box::pin(guard(navigation)));
+        self.guards.borrow_mut().push((id, guard));
+
+        self.navigate(NavigationKind::History, self.current_location());
+        id
+    }
+
+    /// Remove a guard that was added with [`add_guard`](RouterService::add_guard)
+    pub fn remove_guard(&self, id: usize) {
+        self.guards.borrow_mut().retain(|(guard, _)| *guard != id);
+    }
+
     /// Regenerate any routes that need to be regenerated, discarding the currently found route
     ///
     /// You probably don't need this method
     pub fn regen_routes(&self) {
-        self.route_found.set(None);
+        self.route_found.borrow_mut().clear();
 
         (self.regen_any_route)(self.router_id);
 
@@ -191,12 +363,12 @@ impl RouterService {
 
     /// Get the current location of the Router
     pub fn current_location(&self) -> Arc<ParsedRoute> {
-        self.stack.borrow().last().unwrap().clone()
+        self.current.borrow().clone()
     }
 
-    /// Get the current native location of the Router
-    pub fn native_location<T: 'static>(&self) -> Option<Box<T>> {
-        self.history.native_location().downcast::<T>().ok()
+    /// Get the history the Router keeps its routes in
+    pub fn history(&self) -> &Rc<dyn History> {
+        &self.history
     }
 
     /// Registers a scope to regenerate on route change.
@@ -213,15 +385,91 @@ impl RouterService {
         self.onchange_listeners.borrow_mut().remove(&id);
     }
 
-    pub(crate) fn register_total_route(&self, route: String, scope: ScopeId) {
+    /// Add a handler that loads what a route needs ahead of time, like the data it queries, returning the id it can be
+    /// removed with
+    ///
+    /// [`Link`](crate::Link)s with a [`Prefetch`](crate::Prefetch) mode call the handlers with the url they link to
+    /// once, when they're hovered or scrolled into view. [`use_prefetch`](crate::use_prefetch) adds a handler for as
+    /// long as a component is around.
+    pub fn add_prefetcher(&self, prefetcher: impl Fn(&Url) + 'static) -> usize {
+        let id = self.next_handler.get();
+        self.next_handler.set(id + 1);
+        self.prefetchers
+            .borrow_mut()
+            .push((id, Rc::new(prefetcher)));
+        id
+    }
+
+    /// Remove a handler that was added with [`add_prefetcher`](RouterService::add_prefetcher)
+    pub fn remove_prefetcher(&self, id: usize) {
+        self.prefetchers
+            .borrow_mut()
+            .retain(|(prefetcher, _)| *prefetcher != id);
+    }
+
+    /// Load what a route needs ahead of time with the prefetch handlers, unless it was prefetched already
+    pub fn prefetch(&self, route: &str) {
+        let url = match self.current_location().url.join(route) {
+            Ok(url) => url,
+            Err(_) => return,
+        };
+        if !self.prefetched.borrow_mut().insert(url.clone()) {
+            return;
+        }
+
+        // the handlers can add and remove handlers themselves
+        let prefetchers: Vec<_> = self
+            .prefetchers
+            .borrow()
+            .iter()
+            .map(|(_, prefetcher)| prefetcher.clone())
+            .collect();
+        for prefetcher in prefetchers {
+            prefetcher(&url);
+        }
+    }
+
+    /// The routes of the [`Route`](crate::Route)s that rendered so far, in the order they were declared in
+    ///
+    /// Only the routes that rendered are known, so the routes nested in a route only show up once it's matched.
+    pub fn routes(&self) -> Vec<String> {
+        let slots = self.slots.borrow();
+        let mut routes = Vec::new();
+        for route in self.ordering.borrow().iter().filter_map(|id| slots.get(id)) {
+            if !routes.contains(route) {
+                routes.push(route.clone());
+            }
+        }
+        routes
+    }
+
+    /// Register a route of the router, nested in the route of `parent`
+    ///
+    /// Only one of the routes nested in the same route renders, which is the first one that matches. Layouts match the
+    /// paths that start with their route, so the routes nested in them can match the rest.
+    pub(crate) fn register_total_route(
+        &self,
+        route: String,
+        scope: ScopeId,
+        parent: Option<ScopeId>,
+        layout: bool,
+    ) {
         let clean = clean_route(route);
         self.slots.borrow_mut().insert(scope, clean);
+        self.nesting.borrow_mut().insert(scope, (parent, layout));
         self.ordering.borrow_mut().push(scope);
     }
 
     pub(crate) fn should_render(&self, scope: ScopeId) -> bool {
-        if let Some(root_id) = self.route_found.get() {
-            return root_id == scope;
+        let (parent, layout) = self
+            .nesting
+            .borrow()
+            .get(&scope)
+            .copied()
+            .unwrap_or((None, false));
+
+        if let Some(root_id) = self.route_found.borrow().get(&parent) {
+            return *root_id == scope;
         }
 
         let roots = self.slots.borrow();
@@ -230,8 +478,10 @@ impl RouterService {
             let cur = &self.current_location().url;
             log::trace!("Checking if {} matches {}", cur, route);
 
-            if route_matches_path(cur, route, self.cfg.base_url.as_ref()) || route.is_empty() {
-                self.route_found.set(Some(scope));
+            if route_matches_path(cur, route, self.cfg.base_url.as_ref(), layout)
+                || route.is_empty()
+            {
+                self.route_found.borrow_mut().insert(parent, scope);
                 true
             } else {
                 false
@@ -242,6 +492,31 @@ impl RouterService {
     }
 }
 
+/// Collects the routers of a VirtualDom, for tools that look at the routes of an app from the outside
+///
+/// Provide it as a context of the root scope before the VirtualDom is built, and every [`Router`](crate::Router)
+/// below it adds itself.
+///
+/// ```rust, ignore
+/// let collector = RouterCollector::default();
+/// let mut dom = VirtualDom::new(app);
+/// dom.base_scope().provide_context(collector.clone());
+/// _ = dom.rebuild();
+///
+/// for router in collector.routers() {
+///     println!("{:?}", router.routes());
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct RouterCollector(Rc<RefCell<Vec<RouterContext>>>);
+
+impl RouterCollector {
+    /// The routers that were created so far
+    pub fn routers(&self) -> Vec<RouterContext> {
+        self.0.borrow().clone()
+    }
+}
+
 /// Get the router service from an existing VirtualDom.
 ///
 /// Takes an optional target_scope parameter to specify the scope to use if ScopeId is not the component
@@ -273,7 +548,16 @@ fn clean_path(path: &str) -> &str {
     }
 }
 
-fn route_matches_path(cur: &Url, attempt: &str, base_url: Option<&String>) -> bool {
+/// The route of a route nested in `parent`, which is relative to it
+pub(crate) fn join_routes(parent: &str, route: &str) -> String {
+    let route = route.trim_start_matches('/');
+    if route.is_empty() {
+        return parent.to_string();
+    }
+    format!("{}/{}", parent.trim_end_matches('/'), route)
+}
+
+fn route_matches_path(cur: &Url, attempt: &str, base_url: Option<&String>, prefix: bool) -> bool {
     let cur_piece_iter = cur.path_segments().unwrap();
 
     let mut cur_pieces = match base_url {
@@ -282,7 +566,7 @@ fn route_matches_path(cur: &Url, attempt: &str, base_url: Option<&String>) -> bo
         None => cur_piece_iter.collect::<Vec<_>>(),
     };
 
-    if attempt == "/" && cur_pieces.len() == 1 && cur_pieces[0].is_empty() {
+    if attempt == "/" && (prefix || (cur_pieces.len() == 1 && cur_pieces[0].is_empty())) {
         return true;
     }
 
@@ -293,7 +577,10 @@ fn route_matches_path(cur: &Url, attempt: &str, base_url: Option<&String>) -> bo
 
     let attempt_pieces = clean_path(attempt).split('/').collect::<Vec<_>>();
 
-    if attempt_pieces.len() != cur_pieces.len() {
+    // layouts match the paths that start with their route
+    if attempt_pieces.len() > cur_pieces.len()
+        || (!prefix && attempt_pieces.len() < cur_pieces.len())
+    {
         return false;
     }
 
@@ -312,128 +599,10 @@ fn route_matches_path(cur: &Url, attempt: &str, base_url: Option<&String>) -> bo
     true
 }
 
-pub(crate) trait RouterProvider {
-    fn push(&self, route: &ParsedRoute);
-    fn replace(&self, route: &ParsedRoute);
-    fn native_location(&self) -> Box<dyn Any>;
-    fn init_location(&self) -> ParsedRoute;
-    fn attach_listeners(&self, svc: Weak<RouterService>);
-}
-
-#[cfg(not(feature = "web"))]
-mod hash {
-    use super::*;
+fn default_history() -> Rc<dyn History> {
+    #[cfg(feature = "web")]
+    return Rc::new(dioxus_history::BrowserHistory::new());
 
-    pub fn new() -> HashRouter {
-        HashRouter {}
-    }
-
-    /// a simple cross-platform hash-based router
-    pub struct HashRouter {}
-
-    impl RouterProvider for HashRouter {
-        fn push(&self, _route: &ParsedRoute) {}
-
-        fn native_location(&self) -> Box<dyn Any> {
-            Box::new(())
-        }
-
-        fn init_location(&self) -> ParsedRoute {
-            ParsedRoute {
-                url: Url::parse("app:///").unwrap(),
-                title: None,
-                serialized_state: None,
-            }
-        }
-
-        fn replace(&self, _route: &ParsedRoute) {}
-
-        fn attach_listeners(&self, _svc: Weak<RouterService>) {}
-    }
-}
-
-#[cfg(feature = "web")]
-mod web {
-    use super::RouterProvider;
-    use crate::ParsedRoute;
-
-    use gloo_events::EventListener;
-    use std::{any::Any, cell::Cell};
-    use web_sys::History;
-
-    pub struct WebRouter {
-        // keep it around so it drops when the router is dropped
-        _listener: Cell<Option<gloo_events::EventListener>>,
-
-        window: web_sys::Window,
-        history: History,
-    }
-
-    impl RouterProvider for WebRouter {
-        fn push(&self, route: &ParsedRoute) {
-            let ParsedRoute {
-                url,
-                title,
-                serialized_state,
-            } = route;
-
-            let _ = self.history.push_state_with_url(
-                &wasm_bindgen::JsValue::from_str(serialized_state.as_deref().unwrap_or("")),
-                title.as_deref().unwrap_or(""),
-                Some(url.as_str()),
-            );
-        }
-
-        fn replace(&self, route: &ParsedRoute) {
-            let ParsedRoute {
-                url,
-                title,
-                serialized_state,
-            } = route;
-
-            let _ = self.history.replace_state_with_url(
-                &wasm_bindgen::JsValue::from_str(serialized_state.as_deref().unwrap_or("")),
-                title.as_deref().unwrap_or(""),
-                Some(url.as_str()),
-            );
-        }
-
-        fn native_location(&self) -> Box<dyn Any> {
-            Box::new(self.window.location())
-        }
-
-        fn init_location(&self) -> ParsedRoute {
-            ParsedRoute {
-                url: url::Url::parse(&web_sys::window().unwrap().location().href().unwrap())
-                    .unwrap(),
-                title: web_sys::window()
-                    .unwrap()
-                    .document()
-                    .unwrap()
-                    .title()
-                    .into(),
-                serialized_state: None,
-            }
-        }
-
-        fn attach_listeners(&self, svc: std::rc::Weak<crate::RouterService>) {
-            self._listener.set(Some(EventListener::new(
-                &web_sys::window().unwrap(),
-                "popstate",
-                move |_| {
-                    if let Some(svc) = svc.upgrade() {
-                        svc.pop_route();
-                    }
-                },
-            )));
-        }
-    }
-
-    pub(crate) fn new() -> WebRouter {
-        WebRouter {
-            history: web_sys::window().unwrap().history().unwrap(),
-            window: web_sys::window().unwrap(),
-            _listener: Cell::new(None),
-        }
-    }
+    #[cfg(not(feature = "web"))]
+    return Rc::new(dioxus_history::MemoryHistory::new());
 }
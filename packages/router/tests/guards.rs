@@ -0,0 +1,77 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_router::history::{History, MemoryHistory};
+use dioxus_router::*;
+use std::rc::Rc;
+use std::time::Duration;
+
+fn App(cx: Scope) -> Element {
+    render! {
+        Router {
+            Guard {}
+            Route { to: "/", "home" }
+            Route { to: "/login", "login" }
+            Route { to: "/secret", "secret" }
+            Route { to: "/blog/:id", Post {} }
+            Redirect { from: "/posts/:id", to: "/blog/:id" }
+        }
+    }
+}
+
+fn Guard(cx: Scope) -> Element {
+    use_navigation_guard(cx, |navigation| async move {
+        match navigation.to.url.path() {
+            "/admin" => NavigationDecision::Redirect("/login".into()),
+            "/secret" => NavigationDecision::Cancel,
+            _ => NavigationDecision::Allow,
+        }
+    });
+    cx.render(rsx!(()))
+}
+
+fn Post(cx: Scope) -> Element {
+    let id = use_route(cx).segment("id").unwrap_or_default();
+    render! { "post {id}" }
+}
+
+/// Render until the router is done navigating
+async fn settle(dom: &mut VirtualDom) {
+    for _ in 0..4 {
+        let work = tokio::time::timeout(Duration::from_millis(50), dom.wait_for_work());
+        if work.await.is_err() {
+            break;
+        }
+        _ = dom.render_immediate();
+    }
+}
+
+#[tokio::test]
+async fn guards_decide_on_navigations() {
+    let history = Rc::new(MemoryHistory::with_initial_path("/"));
+    let collector = RouterCollector::default();
+    let mut dom = VirtualDom::new(App);
+    dom.base_scope()
+        .provide_context::<Rc<dyn History>>(history.clone());
+    dom.base_scope().provide_context(collector.clone());
+    _ = dom.rebuild();
+    settle(&mut dom).await;
+    assert_eq!(dioxus_ssr::render(&dom), "home");
+
+    let router = collector.routers().remove(0);
+
+    router.navigate_to("/admin");
+    settle(&mut dom).await;
+    assert_eq!(dioxus_ssr::render(&dom), "login");
+    assert_eq!(history.current_path(), "/login");
+
+    router.navigate_to("/secret");
+    settle(&mut dom).await;
+    assert_eq!(dioxus_ssr::render(&dom), "login");
+    assert_eq!(history.current_path(), "/login");
+
+    router.navigate_to("/posts/3");
+    settle(&mut dom).await;
+    assert_eq!(dioxus_ssr::render(&dom), "post 3");
+    assert_eq!(history.current_path(), "/blog/3");
+}
@@ -0,0 +1,61 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_router::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn render_at(path: &str) -> String {
+    fn App(cx: Scope) -> Element {
+        render! {
+            Router {
+                Link { to: "/blog", "exact" }
+                Link { to: "/blog", exact: false, "nested" }
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(App);
+    dioxus_ssr::provide_request_path(&dom, path);
+    _ = dom.rebuild();
+    dioxus_ssr::render(&dom)
+}
+
+#[test]
+fn links_are_active_on_their_routes() {
+    let active = |html: &str| html.matches("class=\" active\"").count();
+
+    assert_eq!(active(&render_at("/blog")), 2);
+    assert_eq!(active(&render_at("/blog/7")), 1);
+    assert_eq!(active(&render_at("/blogs")), 0);
+}
+
+#[test]
+fn prefetches_every_url_once() {
+    fn App(cx: Scope) -> Element {
+        render! {
+            Router { "app" }
+        }
+    }
+
+    let collector = RouterCollector::default();
+    let mut dom = VirtualDom::new(App);
+    dom.base_scope().provide_context(collector.clone());
+    _ = dom.rebuild();
+
+    let router = collector.routers().remove(0);
+    let prefetched = Rc::new(RefCell::new(Vec::new()));
+    let id = router.add_prefetcher({
+        let prefetched = prefetched.clone();
+        move |url| prefetched.borrow_mut().push(url.path().to_string())
+    });
+
+    router.prefetch("/blog/7");
+    router.prefetch("/blog/7");
+    router.prefetch("/blog/8");
+    assert_eq!(*prefetched.borrow(), ["/blog/7", "/blog/8"]);
+
+    router.remove_prefetcher(id);
+    router.prefetch("/about");
+    assert_eq!(prefetched.borrow().len(), 2);
+}
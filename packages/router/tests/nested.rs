@@ -0,0 +1,46 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_router::history::{History, MemoryHistory};
+use dioxus_router::*;
+use std::rc::Rc;
+
+fn render_at(path: &str) -> String {
+    fn App(cx: Scope) -> Element {
+        render! {
+            Router {
+                Route { to: "/", "home" }
+                Route { to: "/blog", layout: BlogLayout,
+                    Route { to: "/", "all posts" }
+                    Route { to: ":id", Post {} }
+                }
+            }
+        }
+    }
+
+    fn BlogLayout<'a>(cx: Scope<'a, LayoutProps<'a>>) -> Element<'a> {
+        render! {
+            nav { "blog" }
+            main { Outlet { layout: cx.props } }
+        }
+    }
+
+    fn Post(cx: Scope) -> Element {
+        let id = use_route(cx).segment("id").unwrap_or_default();
+        render! { "post {id}" }
+    }
+
+    let mut dom = VirtualDom::new(App);
+    dom.base_scope()
+        .provide_context::<Rc<dyn History>>(Rc::new(MemoryHistory::with_initial_path(path)));
+    _ = dom.rebuild();
+
+    dioxus_ssr::render(&dom)
+}
+
+#[test]
+fn renders_nested_routes_in_layouts() {
+    assert_eq!(render_at("/"), "home");
+    assert_eq!(render_at("/blog"), "<nav>blog</nav><main>all posts</main>");
+    assert_eq!(render_at("/blog/7"), "<nav>blog</nav><main>post 7</main>");
+}
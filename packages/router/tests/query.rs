@@ -0,0 +1,59 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_router::history::{History, MemoryHistory};
+use dioxus_router::*;
+use serde::Deserialize;
+use std::rc::Rc;
+
+#[derive(Deserialize)]
+struct Search {
+    q: String,
+    #[serde(default)]
+    page: usize,
+}
+
+fn App(cx: Scope) -> Element {
+    render! {
+        Router { Results {} }
+    }
+}
+
+fn Results(cx: Scope) -> Element {
+    let fragment = use_route(cx).fragment().unwrap_or_default().into_owned();
+    match use_query::<Search>(cx) {
+        Ok(search) => render! { "{search.q} {search.page} {fragment}" },
+        Err(_) => render! { "invalid" },
+    }
+}
+
+#[test]
+fn parses_and_updates_the_query() {
+    let history = Rc::new(MemoryHistory::with_initial_path(
+        "/search?q=rust&sort=new#top%20hits",
+    ));
+    let collector = RouterCollector::default();
+    let mut dom = VirtualDom::new(App);
+    dom.base_scope()
+        .provide_context::<Rc<dyn History>>(history.clone());
+    dom.base_scope().provide_context(collector.clone());
+    _ = dom.rebuild();
+    assert_eq!(dioxus_ssr::render(&dom), "rust 0 top hits");
+
+    let router = collector.routers().remove(0);
+
+    router.set_query_param("page", "2");
+    dom.process_events();
+    _ = dom.render_immediate();
+    assert_eq!(dioxus_ssr::render(&dom), "rust 2 top hits");
+    assert_eq!(
+        history.current_path(),
+        "/search?q=rust&sort=new&page=2#top%20hits"
+    );
+
+    router.remove_query_param("q");
+    dom.process_events();
+    _ = dom.render_immediate();
+    assert_eq!(dioxus_ssr::render(&dom), "invalid");
+    assert_eq!(history.current_path(), "/search?sort=new&page=2#top%20hits");
+}
@@ -0,0 +1,63 @@
+use dioxus_router::{Routable, RouteParseError};
+
+#[derive(Clone, Debug, PartialEq, Routable)]
+enum Route {
+    #[route("/")]
+    Home,
+    #[route("/user/:id")]
+    User { id: u32 },
+    #[route("/user/new")]
+    NewUser,
+    #[route("/blog/:slug/comments/:page")]
+    Comments { slug: String, page: usize },
+}
+
+#[test]
+fn writes_paths() {
+    assert_eq!(Route::Home.to_string(), "/");
+    assert_eq!(Route::User { id: 7 }.to_string(), "/user/7");
+    assert_eq!(Route::NewUser.to_string(), "/user/new");
+    assert_eq!(
+        Route::Comments {
+            slug: "hello world/2".into(),
+            page: 2
+        }
+        .to_string(),
+        "/blog/hello%20world%2F2/comments/2"
+    );
+}
+
+#[test]
+fn parses_paths() {
+    assert_eq!("/".parse(), Ok(Route::Home));
+    assert_eq!("/user/7/".parse(), Ok(Route::User { id: 7 }));
+    assert_eq!("/user/7?tab=posts#top".parse(), Ok(Route::User { id: 7 }));
+    assert_eq!(
+        "/blog/hello%20world%2F2/comments/2".parse(),
+        Ok(Route::Comments {
+            slug: "hello world/2".into(),
+            page: 2
+        })
+    );
+
+    // static segments win over parameters, wherever the routes are declared
+    assert_eq!("/user/new".parse(), Ok(Route::NewUser));
+
+    // a parameter that doesn't parse doesn't match
+    assert_eq!(
+        "/user/someone".parse::<Route>(),
+        Err(RouteParseError {
+            path: "/user/someone".into()
+        })
+    );
+    assert!("/nowhere".parse::<Route>().is_err());
+}
+
+#[test]
+fn lists_routes() {
+    assert_eq!(
+        Route::ROUTES,
+        ["/", "/user/:id", "/user/new", "/blog/:slug/comments/:page"]
+    );
+    assert_eq!(Route::User { id: 1 }.pattern(), "/user/:id");
+}
@@ -7,14 +7,15 @@
 //! - [x] Fields
 //! - [x] Componentbuilder synax
 //! - [x] Optional commas
-//! - [ ] Children
+//! - [x] Children
+//! - [x] Named slots with `name: rsx! { .. }` syntax
 //! - [ ] Keys
-//! - [ ] Properties spreading with with `..` syntax
+//! - [x] Properties spreading with with `..` syntax
 
 use super::*;
 
 use proc_macro2::TokenStream as TokenStream2;
-use quote::{quote, ToTokens, TokenStreamExt};
+use quote::{quote, quote_spanned, ToTokens, TokenStreamExt};
 use syn::{
     ext::IdentExt,
     parse::{Parse, ParseBuffer, ParseStream},
@@ -142,6 +143,15 @@ impl ToTokens for Component {
                         });
                     }
                 }
+                if !self.children.is_empty() {
+                    let renderer: TemplateRenderer = TemplateRenderer {
+                        roots: &self.children,
+                    };
+
+                    toks.append_all(quote! {
+                        __manual_props.children = Some({ #renderer });
+                    });
+                }
                 toks.append_all(quote! {
                     __manual_props
                 });
@@ -150,15 +160,31 @@ impl ToTokens for Component {
                 }}
             }
             None => {
+                let fields: Vec<_> = self.fields.iter().filter(|f| f.name != "key").collect();
+
+                // Check the names of the props against the markers of the builder first, so a misspelled prop is
+                // reported on its name with the props that are close to it
+                let checks = fields.iter().map(|field| {
+                    let name = &field.name;
+                    let check = quote_spanned! { name.span() => .#name() };
+                    quote! { __fields #check; }
+                });
+
                 let mut toks = match prop_gen_args {
                     Some(gen_args) => quote! { fc_to_builder(#name #gen_args) },
                     None => quote! { fc_to_builder(#name) },
                 };
-                for field in &self.fields {
-                    match field.name.to_string().as_str() {
-                        "key" => {}
-                        _ => toks.append_all(quote! {#field}),
-                    }
+                if !fields.is_empty() {
+                    toks = quote! {{
+                        let __builder = #toks;
+                        let __fields = __builder.__dioxus_fields();
+                        #( #checks )*
+                        __builder
+                    }};
+                }
+
+                for field in fields {
+                    toks.append_all(quote! {#field});
                 }
 
                 if !self.children.is_empty() {
@@ -173,7 +199,9 @@ impl ToTokens for Component {
                     });
                 }
 
-                toks.append_all(quote! {
+                // Missing props are reported on the name of the component
+                let span = self.name.segments.last().unwrap().ident.span();
+                toks.append_all(quote_spanned! { span =>
                     .build()
                 });
                 toks
@@ -209,18 +237,27 @@ pub enum ContentField {
     ManExpr(Expr),
     Formatted(IfmtInput),
     OnHandlerRaw(Expr),
+
+    /// An `Element` prop written inline as `name: rsx! { .. }`, which is rendered like children are
+    Slot {
+        mac: Ident,
+        nodes: Vec<BodyNode>,
+    },
 }
 
 impl ToTokens for ContentField {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
         match self {
-            ContentField::ManExpr(e) => e.to_tokens(tokens),
+            ContentField::ManExpr(e) => tokens.append_all(crate::element::optional_value(e)),
             ContentField::Formatted(s) => tokens.append_all(quote! {
                 __cx.raw_text(#s)
             }),
             ContentField::OnHandlerRaw(e) => tokens.append_all(quote! {
                 __cx.event_handler(#e)
             }),
+            ContentField::Slot { nodes, .. } => {
+                tokens.append_all(crate::node::render_branch(nodes))
+            }
         }
     }
 }
@@ -253,15 +290,48 @@ impl Parse for ComponentField {
             missing_trailing_comma!(input.span());
         }
 
+        if let Some(content) = parse_slot(input)? {
+            return Ok(Self { name, content });
+        }
+
         let content = ContentField::ManExpr(input.parse()?);
         Ok(Self { name, content })
     }
 }
 
+/// Parse a `rsx! { .. }` or `render! { .. }` value directly into the nodes of a slot
+fn parse_slot(input: ParseStream) -> Result<Option<ContentField>> {
+    let is_slot = input.peek(Ident) && input.peek2(Token![!]) && {
+        let mac = input.fork().parse::<Ident>()?;
+        mac == "rsx" || mac == "render"
+    };
+    if !is_slot {
+        return Ok(None);
+    }
+
+    let mac = input.parse::<syn::Macro>()?;
+    let nodes = mac.parse_body_with(|content: ParseStream| {
+        let mut nodes = Vec::new();
+        while !content.is_empty() {
+            nodes.push(content.parse::<BodyNode>()?);
+
+            if content.peek(Token![,]) {
+                let _ = content.parse::<Token![,]>();
+            }
+        }
+        Ok(nodes)
+    })?;
+
+    Ok(Some(ContentField::Slot {
+        mac: mac.path.get_ident().unwrap().clone(),
+        nodes,
+    }))
+}
+
 impl ToTokens for ComponentField {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
         let ComponentField { name, content, .. } = self;
-        tokens.append_all(quote! {
+        tokens.append_all(quote_spanned! { name.span() =>
             .#name(#content)
         })
     }
@@ -4,7 +4,8 @@ use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::{quote, ToTokens, TokenStreamExt};
 use syn::{
     parse::{Parse, ParseBuffer, ParseStream},
-    Error, Expr, Ident, LitStr, Result, Token,
+    spanned::Spanned,
+    Error, Expr, ExprIf, Ident, LitStr, Result, Token,
 };
 
 // =======================================
@@ -39,6 +40,30 @@ impl Parse for Element {
         // "def": 456,
         // abc: 123,
         loop {
+            // Spread a list of attributes onto the element
+            if content.peek(Token![..]) {
+                let dots = content.parse::<Token![..]>()?;
+                let value = content.parse::<Expr>()?;
+
+                attributes.push(ElementAttrNamed {
+                    el_name: el_name.clone(),
+                    attr: ElementAttr::Spread {
+                        dots,
+                        value,
+                        written: Vec::new(),
+                    },
+                });
+
+                if content.is_empty() {
+                    break;
+                }
+
+                if content.parse::<Token![,]>().is_err() {
+                    missing_trailing_comma!(dots.span());
+                }
+                continue;
+            }
+
             // Parse the raw literal fields
             if content.peek(LitStr) && content.peek2(Token![:]) && !content.peek3(Token![:]) {
                 let name = content.parse::<LitStr>()?;
@@ -131,6 +156,27 @@ impl Parse for Element {
             break;
         }
 
+        // The attributes written on the element take precedence over the spread ones
+        let written: Vec<AttrName> = attributes
+            .iter()
+            .filter_map(|attr| match &attr.attr {
+                ElementAttr::AttrText { name, .. } | ElementAttr::AttrExpression { name, .. } => {
+                    Some(AttrName::Builtin(name.clone()))
+                }
+                ElementAttr::CustomAttrExpression { name, .. } if is_custom_listener(name) => None,
+                ElementAttr::CustomAttrText { name, .. }
+                | ElementAttr::CustomAttrExpression { name, .. } => {
+                    Some(AttrName::Custom(name.clone()))
+                }
+                ElementAttr::EventTokens { .. } | ElementAttr::Spread { .. } => None,
+            })
+            .collect();
+        for attr in &mut attributes {
+            if let ElementAttr::Spread { written: names, .. } = &mut attr.attr {
+                *names = written.clone();
+            }
+        }
+
         while !content.is_empty() {
             if (content.peek(LitStr) && content.peek2(Token![:])) && !content.peek3(Token![:]) {
                 attr_after_element!(content.span());
@@ -202,13 +248,30 @@ pub enum ElementAttr {
     /// `"attribute": "value"`
     CustomAttrText { name: LitStr, value: IfmtInput },
 
-    /// `"attribute": true`
+    /// `"attribute": true`, or `"onevent": handler` to listen for an event that isn't part of html
     CustomAttrExpression { name: LitStr, value: Expr },
 
     // /// onclick: move |_| {}
     // EventClosure { name: Ident, closure: ExprClosure },
     /// onclick: {}
     EventTokens { name: Ident, tokens: Expr },
+
+    /// `..attributes`, along with the names of the attributes written on the same element
+    Spread {
+        dots: Token![..],
+        value: Expr,
+        written: Vec<AttrName>,
+    },
+}
+
+/// The name of an attribute written on an element
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub enum AttrName {
+    /// `attribute`, which is looked up in `dioxus_elements`
+    Builtin(Ident),
+
+    /// `"attribute"`
+    Custom(LitStr),
 }
 
 impl ElementAttr {
@@ -219,6 +282,7 @@ impl ElementAttr {
             ElementAttr::CustomAttrText { name, .. } => name.span(),
             ElementAttr::CustomAttrExpression { name, .. } => name.span(),
             ElementAttr::EventTokens { name, .. } => name.span(),
+            ElementAttr::Spread { dots, .. } => dots.span(),
         }
     }
 
@@ -228,6 +292,7 @@ impl ElementAttr {
             ElementAttr::AttrExpression { .. }
                 | ElementAttr::CustomAttrExpression { .. }
                 | ElementAttr::EventTokens { .. }
+                | ElementAttr::Spread { .. }
         )
     }
 }
@@ -254,6 +319,7 @@ impl ToTokens for ElementAttrNamed {
                 }
             }
             ElementAttr::AttrExpression { name, value } => {
+                let value = optional_value(value);
                 quote! {
                     __cx.attr(
                         dioxus_elements::#el_name::#name.0,
@@ -273,7 +339,13 @@ impl ToTokens for ElementAttrNamed {
                     )
                 }
             }
+            ElementAttr::CustomAttrExpression { name, value } if is_custom_listener(name) => {
+                quote! {
+                    dioxus_elements::events::custom_event(__cx, #name, #value)
+                }
+            }
             ElementAttr::CustomAttrExpression { name, value } => {
+                let value = optional_value(value);
                 quote! {
                     __cx.attr(
                         #name,
@@ -288,10 +360,71 @@ impl ToTokens for ElementAttrNamed {
                     dioxus_elements::events::#name(__cx, #tokens)
                 }
             }
+            ElementAttr::Spread { value, written, .. } => {
+                let written = written.iter().map(|name| match name {
+                    AttrName::Builtin(name) => quote! { dioxus_elements::#el_name::#name.0 },
+                    AttrName::Custom(name) => quote! { #name },
+                });
+                quote! {
+                    __cx.attr(
+                        "..",
+                        __cx.spread(#value, &[ #(#written),* ]),
+                        None,
+                        false
+                    )
+                }
+            }
         });
     }
 }
 
+/// Custom attributes named `on...` listen for events that aren't part of html, like those of web components
+fn is_custom_listener(name: &LitStr) -> bool {
+    name.value().starts_with("on")
+}
+
+/// An `if` without an `else` is turned into an [`Option`], which leaves the attribute or prop out when no branch is taken
+///
+/// ```rust, ignore
+/// div { class: if active { "selected" } }
+/// ```
+pub(crate) fn optional_value(value: &Expr) -> TokenStream2 {
+    let chain = match value {
+        Expr::If(chain) => chain,
+        _ => return value.to_token_stream(),
+    };
+
+    let mut body = TokenStream2::new();
+    let mut current = chain;
+    loop {
+        let ExprIf {
+            cond,
+            then_branch,
+            else_branch,
+            ..
+        } = current;
+
+        body.append_all(quote! {
+            if #cond { Some(#then_branch) }
+        });
+
+        match else_branch.as_ref().map(|(_, branch)| branch.as_ref()) {
+            Some(Expr::If(next)) => {
+                body.append_all(quote! { else });
+                current = next;
+            }
+            // A terminated chain is used as it is
+            Some(_) => return value.to_token_stream(),
+            None => break,
+        }
+    }
+
+    body.append_all(quote! {
+        else { None }
+    });
+    body
+}
+
 // ::dioxus::core::Attribute {
 //     name: stringify!(#name),
 //     namespace: None,
@@ -18,7 +18,8 @@ pub enum UpdateResult {
 }
 
 pub struct FileMap<Ctx: HotReloadingContext> {
-    pub map: HashMap<PathBuf, (String, Option<Template<'static>>)>,
+    /// The source of each file as it was compiled, and the templates that were sent for it since
+    pub map: HashMap<PathBuf, (String, Vec<Template<'static>>)>,
     phantom: std::marker::PhantomData<Ctx>,
 }
 
@@ -27,7 +28,7 @@ impl<Ctx: HotReloadingContext> FileMap<Ctx> {
     pub fn new(path: PathBuf) -> Self {
         fn find_rs_files(
             root: PathBuf,
-        ) -> io::Result<HashMap<PathBuf, (String, Option<Template<'static>>)>> {
+        ) -> io::Result<HashMap<PathBuf, (String, Vec<Template<'static>>)>> {
             let mut files = HashMap::new();
             if root.is_dir() {
                 for entry in (fs::read_dir(root)?).flatten() {
@@ -38,7 +39,7 @@ impl<Ctx: HotReloadingContext> FileMap<Ctx> {
                 if let Ok(mut file) = File::open(root.clone()) {
                     let mut src = String::new();
                     file.read_to_string(&mut src).expect("Unable to read file");
-                    files.insert(root, (src, None));
+                    files.insert(root, (src, Vec::new()));
                 }
             }
             Ok(files)
@@ -57,11 +58,11 @@ impl<Ctx: HotReloadingContext> FileMap<Ctx> {
         let mut src = String::new();
         file.read_to_string(&mut src).expect("Unable to read file");
         if let Ok(syntax) = syn::parse_file(&src) {
-            if let Some((old_src, template_slot)) = self.map.get_mut(file_path) {
+            if let Some((old_src, sent_templates)) = self.map.get_mut(file_path) {
                 if let Ok(old) = syn::parse_file(old_src) {
                     match find_rsx(&syntax, &old) {
                         DiffResult::CodeChanged => {
-                            self.map.insert(file_path.to_path_buf(), (src, None));
+                            self.map.insert(file_path.to_path_buf(), (src, Vec::new()));
                         }
                         DiffResult::RsxChanged(changed) => {
                             let mut messages: Vec<Template<'static>> = Vec::new();
@@ -76,34 +77,27 @@ impl<Ctx: HotReloadingContext> FileMap<Ctx> {
                                         let line = old_start.line;
                                         let column = old_start.column + 1;
                                         let location = file.display().to_string()
-                                        + ":"
-                                        + &line.to_string()
-                                        + ":"
-                                        + &column.to_string()
-                                        // the byte index doesn't matter, but dioxus needs it
-                                        + ":0";
+                                            + ":"
+                                            + &line.to_string()
+                                            + ":"
+                                            + &column.to_string();
 
-                                        if let Some(template) = new_call_body
-                                            .update_template::<Ctx>(
-                                                Some(old_call_body),
-                                                Box::leak(location.into_boxed_str()),
-                                            )
+                                        match new_call_body
+                                            .update_templates::<Ctx>(old_call_body, &location)
                                         {
-                                            // dioxus cannot handle empty templates
-                                            if template.roots.is_empty() {
-                                                return UpdateResult::NeedsRebuild;
-                                            } else {
-                                                // if the template is the same, don't send it
-                                                if let Some(old_template) = template_slot {
-                                                    if old_template == &template {
+                                            Some(templates) => {
+                                                for template in templates {
+                                                    // if the template is the same, don't send it
+                                                    if sent_templates.contains(&template) {
                                                         continue;
                                                     }
+                                                    sent_templates
+                                                        .retain(|sent| sent.name != template.name);
+                                                    sent_templates.push(template);
+                                                    messages.push(template);
                                                 }
-                                                *template_slot = Some(template);
-                                                messages.push(template);
                                             }
-                                        } else {
-                                            return UpdateResult::NeedsRebuild;
+                                            None => return UpdateResult::NeedsRebuild,
                                         }
                                     }
                                 }
@@ -19,7 +19,7 @@ pub mod hot_reload;
 mod ifmt;
 mod node;
 
-use std::{collections::HashMap, fmt::Debug, hash::Hash};
+use std::{cell::Cell, collections::HashMap, fmt::Debug, hash::Hash};
 
 // Re-export the namespaces into each other
 pub use component::*;
@@ -43,6 +43,15 @@ fn intern<T: Eq + Hash + Send + Sync + ?Sized + 'static>(s: impl Into<Intern<T>>
     s.into().as_ref()
 }
 
+thread_local! {
+    /// The index of the next template in the `rsx!` call that's being expanded
+    ///
+    /// Templates are numbered in the order they're expanded: the body itself first, then the bodies nested in each of
+    /// its dynamic nodes, depth first. The hot reloader numbers the templates of the old source the same way, so it can
+    /// replace the nested templates too.
+    static TEMPLATE_INDEX: Cell<usize> = Cell::new(0);
+}
+
 /// Fundametnally, every CallBody is a template
 #[derive(Default, Debug)]
 pub struct CallBody {
@@ -60,10 +69,113 @@ impl CallBody {
         location: &'static str,
     ) -> Option<Template<'static>> {
         let mut renderer: TemplateRenderer = TemplateRenderer { roots: &self.roots };
-        renderer.update_template::<Ctx>(template, location)
+        let mut mapping = template.map(|call| DynamicMapping::from(call.roots));
+        renderer.update_template::<Ctx>(&mut mapping, location)
+    }
+
+    /// Create the templates that hot reload this body in place of the `old` body it was compiled from
+    ///
+    /// Unlike [`CallBody::update_template`], loops, conditionals and components only need to keep their rust code. The
+    /// bodies nested in them get templates of their own, so elements, attributes and text can change anywhere in the
+    /// call. `location` is the `file:line:column` of the `rsx!` call.
+    ///
+    /// Returns `None` if any rust code changed, which can only be applied with a rebuild. The template of the body itself
+    /// is the first one.
+    pub fn update_templates<Ctx: HotReloadingContext>(
+        &self,
+        old: CallBody,
+        location: &str,
+    ) -> Option<Vec<Template<'static>>> {
+        let mut templates = Vec::new();
+        update_body::<Ctx>(&self.roots, &old.roots, location, &mut 0, &mut templates)?;
+        Some(templates)
     }
 }
 
+/// Create the template of a body and the templates nested in it, numbering them like the expansion of `old` did
+fn update_body<Ctx: HotReloadingContext>(
+    new: &[BodyNode],
+    old: &[BodyNode],
+    location: &str,
+    next_index: &mut usize,
+    templates: &mut Vec<Template<'static>>,
+) -> Option<()> {
+    let index = *next_index;
+    *next_index += 1;
+
+    // The key was compiled into the code that renders the body
+    if (TemplateRenderer { roots: new }).key() != (TemplateRenderer { roots: old }).key() {
+        return None;
+    }
+
+    let name = intern(format!("{}:{}", location, index).as_str());
+    let mut mapping = Some(DynamicMapping::matching_bodies(old.to_vec()));
+    let template = TemplateRenderer { roots: new }.update_template::<Ctx>(&mut mapping, name)?;
+    if template.roots.is_empty() {
+        return None;
+    }
+    templates.push(template);
+
+    let mut matched = mapping.unwrap().matched;
+    for (idx, old_node) in dynamic_nodes(old).into_iter().enumerate() {
+        match matched.remove(&idx) {
+            Some(new_node) => {
+                let old_bodies = old_node.nested_bodies();
+                for (new_body, old_body) in new_node.nested_bodies().into_iter().zip(old_bodies) {
+                    match (new_body.is_empty(), old_body.is_empty()) {
+                        // Empty bodies aren't rendered, so they don't have a template
+                        (true, true) => {}
+                        (false, false) => {
+                            update_body::<Ctx>(new_body, old_body, location, next_index, templates)?
+                        }
+                        _ => return None,
+                    }
+                }
+            }
+
+            // The node was removed, but its templates were still numbered when it was expanded
+            None => *next_index += count_templates(old_node),
+        }
+    }
+
+    Some(())
+}
+
+/// The dynamic nodes of a body, in the order of their indices in its template
+fn dynamic_nodes(roots: &[BodyNode]) -> Vec<&BodyNode> {
+    fn collect<'a>(node: &'a BodyNode, nodes: &mut Vec<&'a BodyNode>) {
+        match node {
+            BodyNode::Element(el) => {
+                for child in &el.children {
+                    collect(child, nodes);
+                }
+            }
+            BodyNode::Text(text) if text.is_static() => {}
+            _ => nodes.push(node),
+        }
+    }
+
+    let mut nodes = Vec::new();
+    for root in roots {
+        collect(root, &mut nodes);
+    }
+    nodes
+}
+
+/// The number of templates that are nested in a dynamic node
+fn count_templates(node: &BodyNode) -> usize {
+    node.nested_bodies()
+        .into_iter()
+        .filter(|body| !body.is_empty())
+        .map(|body| {
+            1 + dynamic_nodes(body)
+                .into_iter()
+                .map(count_templates)
+                .sum::<usize>()
+        })
+        .sum()
+}
+
 impl Parse for CallBody {
     fn parse(input: ParseStream) -> Result<Self> {
         let mut roots = Vec::new();
@@ -85,6 +197,7 @@ impl Parse for CallBody {
 /// Serialize the same way, regardless of flavor
 impl ToTokens for CallBody {
     fn to_tokens(&self, out_tokens: &mut TokenStream2) {
+        TEMPLATE_INDEX.with(|index| index.set(0));
         let body = TemplateRenderer { roots: &self.roots };
 
         out_tokens.append_all(quote! {
@@ -100,6 +213,7 @@ pub struct RenderCallBody(pub CallBody);
 
 impl ToTokens for RenderCallBody {
     fn to_tokens(&self, out_tokens: &mut TokenStream2) {
+        TEMPLATE_INDEX.with(|index| index.set(0));
         let body: TemplateRenderer = TemplateRenderer {
             roots: &self.0.roots,
         };
@@ -120,17 +234,15 @@ pub struct TemplateRenderer<'a> {
 impl<'a> TemplateRenderer<'a> {
     fn update_template<Ctx: HotReloadingContext>(
         &mut self,
-        previous_call: Option<CallBody>,
+        mapping: &mut Option<DynamicMapping>,
         location: &'static str,
     ) -> Option<Template<'static>> {
-        let mut mapping = previous_call.map(|call| DynamicMapping::from(call.roots));
-
         let mut context = DynamicContext::default();
 
         let mut roots = Vec::new();
         for (idx, root) in self.roots.iter().enumerate() {
             context.current_path.push(idx as u8);
-            roots.push(context.update_node::<Ctx>(root, &mut mapping)?);
+            roots.push(context.update_node::<Ctx>(root, mapping)?);
             context.current_path.pop();
         }
 
@@ -157,26 +269,30 @@ impl<'a> TemplateRenderer<'a> {
     }
 }
 
+impl<'a> TemplateRenderer<'a> {
+    /// The key of the template, which is the key of its root if it has a single one
+    fn key(&self) -> Option<&'a IfmtInput> {
+        match self.roots.get(0) {
+            Some(BodyNode::Element(el)) if self.roots.len() == 1 => el.key.as_ref(),
+            Some(BodyNode::Component(comp)) if self.roots.len() == 1 => comp.key(),
+            _ => None,
+        }
+    }
+}
+
 impl<'a> ToTokens for TemplateRenderer<'a> {
     fn to_tokens(&self, out_tokens: &mut TokenStream2) {
         let mut context = DynamicContext::default();
 
-        let key = match self.roots.get(0) {
-            Some(BodyNode::Element(el)) if self.roots.len() == 1 => el.key.clone(),
-            Some(BodyNode::Component(comp)) if self.roots.len() == 1 => comp.key().cloned(),
-            _ => None,
-        };
-
-        let key_tokens = match key {
+        let key_tokens = match self.key() {
             Some(tok) => quote! { Some( __cx.raw_text(#tok) ) },
             None => quote! { None },
         };
 
-        let spndbg = format!("{:?}", self.roots[0].span());
-        let root_col = spndbg
-            .rsplit_once("..")
-            .and_then(|(_, after)| after.split_once(')').map(|(before, _)| before))
-            .unwrap_or_default();
+        // The templates nested in this one are expanded after it, so they take the indices that follow
+        let index = TEMPLATE_INDEX
+            .with(|index| index.replace(index.get() + 1))
+            .to_string();
 
         let root_printer = self.roots.iter().enumerate().map(|(idx, root)| {
             context.current_path.push(idx as u8);
@@ -201,7 +317,7 @@ impl<'a> ToTokens for TemplateRenderer<'a> {
                     ":",
                     column!(),
                     ":",
-                    #root_col
+                    #index
                 ),
                 roots: &[ #roots ],
                 node_paths: &[ #(#node_paths),* ],
@@ -225,6 +341,12 @@ struct DynamicMapping {
     last_attribute_idx: usize,
     node_to_idx: HashMap<BodyNode, Vec<usize>>,
     last_element_idx: usize,
+
+    /// Match dynamic nodes that only differ in their nested bodies, which are hot reloaded with templates of their own
+    match_bodies: bool,
+
+    /// The new nodes that were matched with each index, if bodies are matched
+    matched: HashMap<usize, BodyNode>,
 }
 
 impl DynamicMapping {
@@ -236,6 +358,24 @@ impl DynamicMapping {
         new
     }
 
+    fn matching_bodies(nodes: Vec<BodyNode>) -> Self {
+        let mut new = Self {
+            match_bodies: true,
+            ..Default::default()
+        };
+        for node in nodes {
+            new.add_node(node);
+        }
+        new
+    }
+
+    fn node_key(&self, node: BodyNode) -> BodyNode {
+        match self.match_bodies {
+            true => node.without_bodies(),
+            false => node,
+        }
+    }
+
     fn get_attribute_idx(&mut self, attr: &ElementAttr) -> Option<usize> {
         self.attribute_to_idx
             .get_mut(attr)
@@ -243,7 +383,14 @@ impl DynamicMapping {
     }
 
     fn get_node_idx(&mut self, node: &BodyNode) -> Option<usize> {
-        self.node_to_idx.get_mut(node).and_then(|idxs| idxs.pop())
+        if !self.match_bodies {
+            return self.node_to_idx.get_mut(node).and_then(|idxs| idxs.pop());
+        }
+
+        let key = self.node_key(node.clone());
+        let idx = self.node_to_idx.get_mut(&key).and_then(|idxs| idxs.pop())?;
+        self.matched.insert(idx, node.clone());
+        Some(idx)
     }
 
     fn insert_attribute(&mut self, attr: ElementAttr) -> usize {
@@ -262,8 +409,9 @@ impl DynamicMapping {
         let idx = self.last_element_idx;
         self.last_element_idx += 1;
 
+        let key = self.node_key(node);
         self.node_to_idx
-            .entry(node)
+            .entry(key)
             .or_insert_with(Vec::new)
             .push(idx);
 
@@ -283,7 +431,8 @@ impl DynamicMapping {
                         | ElementAttr::AttrText { .. }
                         | ElementAttr::CustomAttrText { .. }
                         | ElementAttr::CustomAttrExpression { .. }
-                        | ElementAttr::EventTokens { .. } => {
+                        | ElementAttr::EventTokens { .. }
+                        | ElementAttr::Spread { .. } => {
                             self.insert_attribute(attr.attr);
                         }
                     }
@@ -300,6 +449,7 @@ impl DynamicMapping {
             | BodyNode::Text(_)
             | BodyNode::ForLoop(_)
             | BodyNode::IfChain(_)
+            | BodyNode::Match(_)
             | BodyNode::Component(_) => {
                 self.insert_node(node);
             }
@@ -358,7 +508,8 @@ impl<'a> DynamicContext<'a> {
                         | ElementAttr::AttrText { .. }
                         | ElementAttr::CustomAttrText { .. }
                         | ElementAttr::CustomAttrExpression { .. }
-                        | ElementAttr::EventTokens { .. } => {
+                        | ElementAttr::EventTokens { .. }
+                        | ElementAttr::Spread { .. } => {
                             let idx = match mapping {
                                 Some(mapping) => mapping.get_attribute_idx(&attr.attr)?,
                                 None => self.dynamic_attributes.len(),
@@ -402,6 +553,7 @@ impl<'a> DynamicContext<'a> {
             | BodyNode::Text(_)
             | BodyNode::ForLoop(_)
             | BodyNode::IfChain(_)
+            | BodyNode::Match(_)
             | BodyNode::Component(_) => {
                 let idx = match mapping {
                     Some(mapping) => mapping.get_node_idx(root)?,
@@ -459,7 +611,8 @@ impl<'a> DynamicContext<'a> {
                     | ElementAttr::AttrText { .. }
                     | ElementAttr::CustomAttrText { .. }
                     | ElementAttr::CustomAttrExpression { .. }
-                    | ElementAttr::EventTokens { .. } => {
+                    | ElementAttr::EventTokens { .. }
+                    | ElementAttr::Spread { .. } => {
                         let ct = self.dynamic_attributes.len();
                         self.dynamic_attributes.push(attr);
                         self.attr_paths.push(self.current_path.clone());
@@ -498,6 +651,7 @@ impl<'a> DynamicContext<'a> {
             | BodyNode::Text(_)
             | BodyNode::ForLoop(_)
             | BodyNode::IfChain(_)
+            | BodyNode::Match(_)
             | BodyNode::Component(_) => {
                 let ct = self.dynamic_nodes.len();
                 self.dynamic_nodes.push(root);
@@ -719,3 +873,127 @@ fn diff_template() {
         },
     )
 }
+
+#[test]
+fn control_flow_branches() {
+    let input = quote! {
+        div {
+            if a > 10 {
+                span {}
+            } else if a > 5 {
+                "medium"
+            }
+            match value {
+                Some(x) if x > 1 => "{x}",
+                Some(_) | None => {}
+            }
+        }
+    };
+
+    struct Mock;
+
+    impl HotReloadingContext for Mock {
+        fn map_attribute(_: &str, _: &str) -> Option<(&'static str, Option<&'static str>)> {
+            None
+        }
+
+        fn map_element(_: &str) -> Option<(&'static str, Option<&'static str>)> {
+            None
+        }
+    }
+
+    let call_body: CallBody = syn::parse2(input).unwrap();
+
+    // the branches are dynamic nodes of the template around them
+    let template = call_body.update_template::<Mock>(None, "testing").unwrap();
+    assert_eq!(
+        template.roots,
+        &[TemplateNode::Element {
+            tag: "div",
+            namespace: None,
+            attrs: &[],
+            children: &[
+                TemplateNode::Dynamic { id: 0 },
+                TemplateNode::Dynamic { id: 1 }
+            ],
+        }]
+    );
+
+    // and every branch that isn't empty gets a template of its own
+    let tokens = call_body.to_token_stream().to_string();
+    assert_eq!(tokens.matches("static TEMPLATE").count(), 4);
+}
+
+#[test]
+fn for_loops_need_keys() {
+    let keyed: CallBody = syn::parse2(quote! {
+        for item in items {
+            li { key: "{item.id}", "{item.name}" }
+        }
+    })
+    .unwrap();
+    assert!(matches!(&keyed.roots[0], BodyNode::ForLoop(f) if f.is_keyed()));
+    assert!(!keyed.to_token_stream().to_string().contains("deprecated"));
+
+    let unkeyed: CallBody = syn::parse2(quote! {
+        for item in items {
+            li { "{item.name}" }
+        }
+    })
+    .unwrap();
+    assert!(matches!(&unkeyed.roots[0], BodyNode::ForLoop(f) if !f.is_keyed()));
+    assert!(unkeyed.to_token_stream().to_string().contains("deprecated"));
+}
+
+#[test]
+fn hot_reload_nested_bodies() {
+    use hot_reload::Empty;
+
+    let old = || -> CallBody {
+        syn::parse2(quote! {
+            div {
+                if show { span { "{a}" } }
+                for item in items { li { key: "{item}", "{item}" } }
+            }
+        })
+        .unwrap()
+    };
+
+    // the templates are numbered in the order they're expanded
+    let tokens = old().to_token_stream().to_string();
+    for index in ["\"0\"", "\"1\"", "\"2\""] {
+        assert!(tokens.contains(index));
+    }
+    assert!(!tokens.contains("\"3\""));
+
+    // the bodies can change as long as the loops and conditionals around them stay the same
+    let new: CallBody = syn::parse2(quote! {
+        div {
+            for item in items { li { key: "{item}", class: "item", "{item}" } }
+            if show { p { "{a}" } }
+        }
+    })
+    .unwrap();
+    let templates = new.update_templates::<Empty>(old(), "testing").unwrap();
+    let names: Vec<_> = templates.iter().map(|t| t.name).collect();
+    assert_eq!(names, ["testing:0", "testing:1", "testing:2"]);
+    assert_eq!(
+        templates[1].roots,
+        &[TemplateNode::Element {
+            tag: "p",
+            namespace: None,
+            attrs: &[],
+            children: &[TemplateNode::DynamicText { id: 0 }],
+        }]
+    );
+
+    // but changing the code of a loop needs a rebuild
+    let new: CallBody = syn::parse2(quote! {
+        div {
+            if show { span { "{a}" } }
+            for item in other_items { li { key: "{item}", "{item}" } }
+        }
+    })
+    .unwrap();
+    assert!(new.update_templates::<Empty>(old(), "testing").is_none());
+}
@@ -1,12 +1,12 @@
 use super::*;
 
 use proc_macro2::{Span, TokenStream as TokenStream2};
-use quote::{quote, ToTokens, TokenStreamExt};
+use quote::{quote, quote_spanned, ToTokens, TokenStreamExt};
 use syn::{
     braced,
     parse::{Parse, ParseStream},
     spanned::Spanned,
-    token, Expr, ExprIf, LitStr, Pat, Result,
+    token, Expr, LitStr, Pat, Result,
 };
 
 /*
@@ -16,13 +16,16 @@ Parse
 -> component()
 -> "text {with_args}"
 -> (0..10).map(|f| rsx!("asd")),  // <--- notice the comma - must be a complete expr
+-> if cond { div {} } else { span {} }
+-> match value { Some(x) => "{x}", None => {} }
 */
 #[derive(PartialEq, Eq, Clone, Debug, Hash)]
 pub enum BodyNode {
     Element(Element),
     Component(Component),
     ForLoop(ForLoop),
-    IfChain(ExprIf),
+    IfChain(IfChain),
+    Match(MatchExpr),
     Text(IfmtInput),
     RawExpr(Expr),
 }
@@ -40,8 +43,73 @@ impl BodyNode {
             BodyNode::RawExpr(exp) => exp.span(),
             BodyNode::ForLoop(fl) => fl.for_token.span(),
             BodyNode::IfChain(f) => f.if_token.span(),
+            BodyNode::Match(m) => m.match_token.span(),
         }
     }
+
+    /// The rsx bodies nested in this node, which are rendered as templates of their own
+    ///
+    /// They're in the order their templates are expanded in.
+    pub fn nested_bodies(&self) -> Vec<&[BodyNode]> {
+        match self {
+            BodyNode::ForLoop(exp) => vec![exp.body.as_slice()],
+            BodyNode::IfChain(chain) => {
+                let mut bodies = vec![];
+                let mut elif = Some(chain);
+                while let Some(chain) = elif {
+                    bodies.push(chain.then_branch.as_slice());
+                    if let Some(else_branch) = &chain.else_branch {
+                        bodies.push(else_branch);
+                    }
+                    elif = chain.else_if_branch.as_deref();
+                }
+                bodies
+            }
+            BodyNode::Match(exp) => exp.arms.iter().map(|arm| arm.body.as_slice()).collect(),
+            BodyNode::Component(comp) => comp
+                .fields
+                .iter()
+                .filter_map(|field| match &field.content {
+                    ContentField::Slot { nodes, .. } => Some(nodes.as_slice()),
+                    _ => None,
+                })
+                .chain(std::iter::once(comp.children.as_slice()))
+                .collect(),
+            BodyNode::Element(_) | BodyNode::Text(_) | BodyNode::RawExpr(_) => vec![],
+        }
+    }
+
+    /// This node with its nested bodies left empty, which is only the rust code of the node
+    pub fn without_bodies(mut self) -> Self {
+        match &mut self {
+            BodyNode::ForLoop(exp) => exp.body.clear(),
+            BodyNode::IfChain(chain) => {
+                let mut elif = Some(chain);
+                while let Some(chain) = elif {
+                    chain.then_branch.clear();
+                    if let Some(else_branch) = &mut chain.else_branch {
+                        else_branch.clear();
+                    }
+                    elif = chain.else_if_branch.as_deref_mut();
+                }
+            }
+            BodyNode::Match(exp) => {
+                for arm in &mut exp.arms {
+                    arm.body.clear();
+                }
+            }
+            BodyNode::Component(comp) => {
+                for field in &mut comp.fields {
+                    if let ContentField::Slot { nodes, .. } = &mut field.content {
+                        nodes.clear();
+                    }
+                }
+                comp.children.clear();
+            }
+            BodyNode::Element(_) | BodyNode::Text(_) | BodyNode::RawExpr(_) => {}
+        }
+        self
+    }
 }
 
 impl Parse for BodyNode {
@@ -103,6 +171,10 @@ impl Parse for BodyNode {
             return Ok(BodyNode::IfChain(stream.parse()?));
         }
 
+        if stream.peek(Token![match]) {
+            return Ok(BodyNode::Match(stream.parse()?));
+        }
+
         Ok(BodyNode::RawExpr(stream.parse::<Expr>()?))
     }
 }
@@ -120,78 +192,42 @@ impl ToTokens for BodyNode {
             }),
             BodyNode::ForLoop(exp) => {
                 let ForLoop {
-                    pat, expr, body, ..
+                    for_token,
+                    pat,
+                    expr,
+                    body,
+                    ..
                 } = exp;
 
-                let renderer: TemplateRenderer = TemplateRenderer { roots: body };
+                let body = render_branch(body);
+
+                // Stable proc macros can't emit warnings, so a deprecated item is used where the key is missing
+                let missing_key = (!exp.is_keyed()).then(|| {
+                    quote_spanned! { for_token.span() =>
+                        #[deprecated(
+                            note = "every item of a `for` loop in rsx needs a key, so it can be moved instead of recreated when the list changes\n  = help: add a `key` to the element or component in the body of the loop"
+                        )]
+                        #[allow(non_upper_case_globals)]
+                        const missing_key: () = ();
+                        #[allow(clippy::let_unit_value)]
+                        let _ = missing_key;
+                    }
+                });
 
+                // The items are rendered as a fragment, keyed by the single root of the body
                 tokens.append_all(quote! {
-                     __cx.make_node(
-                        (#expr).into_iter().map(|#pat| { #renderer })
-                     )
+                     __cx.make_node({
+                        #missing_key
+                        (#expr).into_iter().map(|#pat| #body)
+                     })
                 })
             }
-            BodyNode::IfChain(chain) => {
-                if is_if_chain_terminated(chain) {
-                    tokens.append_all(quote! {
-                         __cx.make_node(#chain)
-                    });
-                } else {
-                    let ExprIf {
-                        cond,
-                        then_branch,
-                        else_branch,
-                        ..
-                    } = chain;
-
-                    let mut body = TokenStream2::new();
-
-                    body.append_all(quote! {
-                        if #cond {
-                            Some(#then_branch)
-                        }
-                    });
-
-                    let mut elif = else_branch;
-
-                    while let Some((_, ref branch)) = elif {
-                        match branch.as_ref() {
-                            Expr::If(ref eelif) => {
-                                let ExprIf {
-                                    cond,
-                                    then_branch,
-                                    else_branch,
-                                    ..
-                                } = eelif;
-
-                                body.append_all(quote! {
-                                    else if #cond {
-                                        Some(#then_branch)
-                                    }
-                                });
-
-                                elif = else_branch;
-                            }
-                            _ => {
-                                body.append_all(quote! {
-                                    else {
-                                        #branch
-                                    }
-                                });
-                                break;
-                            }
-                        }
-                    }
-
-                    body.append_all(quote! {
-                        else { None }
-                    });
-
-                    tokens.append_all(quote! {
-                        __cx.make_node(#body)
-                    });
-                }
-            }
+            BodyNode::IfChain(chain) => tokens.append_all(quote! {
+                __cx.make_node(#chain)
+            }),
+            BodyNode::Match(expr) => tokens.append_all(quote! {
+                __cx.make_node(#expr)
+            }),
         }
     }
 }
@@ -222,6 +258,10 @@ impl Parse for ForLoop {
 
         while !content.is_empty() {
             children.push(content.parse()?);
+
+            if content.peek(Token![,]) {
+                let _ = content.parse::<Token![,]>();
+            }
         }
 
         Ok(Self {
@@ -235,17 +275,249 @@ impl Parse for ForLoop {
     }
 }
 
-fn is_if_chain_terminated(chain: &ExprIf) -> bool {
-    let mut current = chain;
-    loop {
-        if let Some((_, else_block)) = &current.else_branch {
-            if let Expr::If(else_if) = else_block.as_ref() {
-                current = else_if;
+impl ForLoop {
+    /// Whether the body is a single element or component with a key
+    ///
+    /// An empty body renders nothing, so it doesn't need a key either.
+    pub fn is_keyed(&self) -> bool {
+        match self.body.as_slice() {
+            [] => true,
+            [BodyNode::Element(el)] => el.key.is_some(),
+            [BodyNode::Component(comp)] => comp.key().is_some(),
+            _ => false,
+        }
+    }
+}
+
+/// An `if` whose branches are rsx bodies. Each branch is rendered as its own template.
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct IfChain {
+    pub if_token: Token![if],
+    pub cond: Box<Expr>,
+    pub then_branch: Vec<BodyNode>,
+    pub else_if_branch: Option<Box<IfChain>>,
+    pub else_branch: Option<Vec<BodyNode>>,
+}
+
+impl Parse for IfChain {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let if_token: Token![if] = input.parse()?;
+
+        // `if let` patterns are parsed as a let expression
+        let cond = input.call(Expr::parse_without_eager_brace)?;
+
+        let then_branch = parse_branch(input)?;
+
+        let mut else_if_branch = None;
+        let mut else_branch = None;
+        if input.parse::<Option<Token![else]>>()?.is_some() {
+            if input.peek(Token![if]) {
+                else_if_branch = Some(Box::new(input.parse()?));
             } else {
-                return true;
+                else_branch = Some(parse_branch(input)?);
             }
+        }
+
+        Ok(Self {
+            if_token,
+            cond: Box::new(cond),
+            then_branch,
+            else_if_branch,
+            else_branch,
+        })
+    }
+}
+
+/// Renders as an `Option<VNode>` that's `None` when no branch is taken
+impl ToTokens for IfChain {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let mut body = TokenStream2::new();
+        let mut terminated = false;
+
+        let mut elif = Some(self);
+        while let Some(chain) = elif {
+            let IfChain {
+                if_token,
+                cond,
+                then_branch,
+                else_if_branch,
+                else_branch,
+            } = chain;
+
+            let then_branch = render_branch(then_branch);
+            body.append_all(quote! {
+                #if_token #cond { #then_branch }
+            });
+
+            elif = else_if_branch.as_deref();
+            if elif.is_some() {
+                body.append_all(quote! { else });
+            } else if let Some(else_branch) = else_branch {
+                let else_branch = render_branch(else_branch);
+                body.append_all(quote! {
+                    else { #else_branch }
+                });
+                terminated = true;
+            }
+        }
+
+        if !terminated {
+            body.append_all(quote! {
+                else { None }
+            });
+        }
+
+        tokens.append_all(body);
+    }
+}
+
+/// A `match` whose arms are rsx bodies. Each arm is rendered as its own template.
+///
+/// An arm is either a braced body or a single node followed by an optional comma.
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct MatchExpr {
+    pub match_token: Token![match],
+    pub expr: Box<Expr>,
+    pub brace_token: token::Brace,
+    pub arms: Vec<MatchArm>,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct MatchArm {
+    pub pat: Pat,
+    pub guard: Option<(Token![if], Box<Expr>)>,
+    pub fat_arrow_token: Token![=>],
+    pub body: Vec<BodyNode>,
+}
+
+impl Parse for MatchExpr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let match_token: Token![match] = input.parse()?;
+        let expr = input.call(Expr::parse_without_eager_brace)?;
+
+        let content;
+        let brace_token = braced!(content in input);
+
+        let mut arms = vec![];
+        while !content.is_empty() {
+            arms.push(content.parse()?);
+        }
+
+        Ok(Self {
+            match_token,
+            expr: Box::new(expr),
+            brace_token,
+            arms,
+        })
+    }
+}
+
+impl Parse for MatchArm {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let pat = parse_arm_pattern(input)?;
+
+        let guard = if input.peek(Token![if]) {
+            let if_token: Token![if] = input.parse()?;
+            let cond: Expr = input.parse()?;
+            Some((if_token, Box::new(cond)))
         } else {
-            return false;
+            None
+        };
+
+        let fat_arrow_token: Token![=>] = input.parse()?;
+
+        let body = if input.peek(token::Brace) {
+            parse_branch(input)?
+        } else {
+            vec![input.parse()?]
+        };
+
+        if input.peek(Token![,]) {
+            let _ = input.parse::<Token![,]>();
         }
+
+        Ok(Self {
+            pat,
+            guard,
+            fat_arrow_token,
+            body,
+        })
+    }
+}
+
+/// Renders as an `Option<VNode>` that's `None` for arms with an empty body
+impl ToTokens for MatchExpr {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let expr = &self.expr;
+        let arms = self.arms.iter().map(|arm| {
+            let MatchArm {
+                pat,
+                guard,
+                fat_arrow_token,
+                body,
+            } = arm;
+
+            let guard = guard
+                .as_ref()
+                .map(|(if_token, cond)| quote! { #if_token #cond });
+            let body = render_branch(body);
+            quote! { #pat #guard #fat_arrow_token { #body } }
+        });
+
+        tokens.append_all(quote! {
+            match #expr {
+                #( #arms )*
+            }
+        });
+    }
+}
+
+/// Patterns of match arms can have alternatives, like `Some(1) | Some(2)`
+fn parse_arm_pattern(input: ParseStream) -> Result<Pat> {
+    let leading_vert: Option<Token![|]> = input.parse()?;
+    let pat: Pat = input.parse()?;
+
+    if leading_vert.is_none() && !input.peek(Token![|]) {
+        return Ok(pat);
     }
+
+    let mut cases = syn::punctuated::Punctuated::new();
+    cases.push_value(pat);
+    while input.peek(Token![|]) && !input.peek(Token![||]) {
+        cases.push_punct(input.parse()?);
+        cases.push_value(input.parse()?);
+    }
+
+    Ok(Pat::Or(syn::PatOr {
+        attrs: Vec::new(),
+        leading_vert,
+        cases,
+    }))
+}
+
+/// Parse the braced body of a branch, where the nodes may be separated by commas
+fn parse_branch(input: ParseStream) -> Result<Vec<BodyNode>> {
+    let content;
+    braced!(content in input);
+
+    let mut children = vec![];
+    while !content.is_empty() {
+        children.push(content.parse()?);
+
+        if content.peek(Token![,]) {
+            let _ = content.parse::<Token![,]>();
+        }
+    }
+
+    Ok(children)
+}
+
+/// Render the body of a branch as its own template, or `None` if the branch is empty
+pub(crate) fn render_branch(body: &[BodyNode]) -> TokenStream2 {
+    if body.is_empty() {
+        return quote! { None };
+    }
+
+    let renderer = TemplateRenderer { roots: body };
+    quote! { Some({ #renderer }) }
 }
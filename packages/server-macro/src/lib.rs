@@ -0,0 +1,33 @@
+use proc_macro::TokenStream;
+use syn::parse_macro_input;
+
+mod server;
+
+/// Turn an async function into a server function, which runs on the server wherever it's called from
+///
+/// With the `ssr` feature of the crate it's declared in, the function is compiled as it is, and registered as an HTTP
+/// endpoint that [`register_server_fns`] serves. Without it, the body is left out, and the function sends its
+/// arguments to that endpoint instead and waits for what the server returned:
+///
+/// ```rust, ignore
+/// #[server]
+/// async fn get_user(id: u32) -> Result<User, ServerFnError> {
+///     Ok(database().await.user(id).await?)
+/// }
+/// ```
+///
+/// The arguments are serialized with serde, so they're owned types, and the function returns a `Result` with an ok
+/// value and an error that serialize too. The error can be any type that converts from a `ServerFnError`, which is what
+/// the client gets when it can't reach the server. The endpoint is named after the function, unless it's given a name
+/// with `#[server("name")]`.
+///
+/// [`register_server_fns`]: https://docs.rs/dioxus-fullstack
+#[proc_macro_attribute]
+pub fn server(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as server::ServerArgs);
+    let input = parse_macro_input!(input as syn::ItemFn);
+    match server::expand(args, input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
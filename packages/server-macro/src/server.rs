@@ -0,0 +1,152 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{FnArg, Ident, ItemFn, LitStr, Pat, ReturnType, Type};
+
+/// The arguments of `#[server]`, which are the name of the endpoint if it isn't named after the function
+pub(crate) struct ServerArgs {
+    endpoint: Option<LitStr>,
+}
+
+impl Parse for ServerArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Ok(Self { endpoint: None });
+        }
+        let endpoint: LitStr = input.parse()?;
+        let valid = !endpoint.value().is_empty()
+            && endpoint
+                .value()
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid {
+            return Err(syn::Error::new_spanned(
+                &endpoint,
+                "the name of a server function can only have letters, digits, _ and -",
+            ));
+        }
+        Ok(Self {
+            endpoint: Some(endpoint),
+        })
+    }
+}
+
+pub(crate) fn expand(args: ServerArgs, input: ItemFn) -> syn::Result<TokenStream2> {
+    let sig = &input.sig;
+    let name = &sig.ident;
+
+    if sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(
+            sig.fn_token,
+            "server functions are async, since the client waits for the server",
+        ));
+    }
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &sig.generics,
+            "server functions can't be generic, since the server needs to know what to call",
+        ));
+    }
+    if let ReturnType::Default = sig.output {
+        return Err(syn::Error::new_spanned(
+            sig,
+            "server functions return a Result, since the server might not be reachable",
+        ));
+    }
+
+    let mut params: Vec<(Ident, Type)> = Vec::new();
+    for arg in &sig.inputs {
+        match arg {
+            FnArg::Receiver(receiver) => {
+                return Err(syn::Error::new_spanned(
+                    receiver,
+                    "server functions can't take self",
+                ))
+            }
+            FnArg::Typed(arg) => match &*arg.pat {
+                Pat::Ident(pat) if pat.by_ref.is_none() && pat.subpat.is_none() => {
+                    params.push((pat.ident.clone(), (*arg.ty).clone()))
+                }
+                pat => {
+                    return Err(syn::Error::new_spanned(
+                        pat,
+                        "the arguments of a server function are sent by their names, so they can't be patterns",
+                    ))
+                }
+            },
+        }
+    }
+
+    let endpoint = match args.endpoint {
+        Some(endpoint) => endpoint,
+        None => LitStr::new(&name.to_string(), name.span()),
+    };
+
+    let names: Vec<&Ident> = params.iter().map(|(name, _)| name).collect();
+    let types: Vec<&Type> = params.iter().map(|(_, ty)| ty).collect();
+    let args_struct = format_ident!("__ServerFnArgs_{}", name);
+    let handler = format_ident!("__server_fn_handler_{}", name);
+
+    // the client only passes the arguments on, so it doesn't need them to be mutable
+    let mut client_sig = sig.clone();
+    for arg in client_sig.inputs.iter_mut() {
+        if let FnArg::Typed(arg) = arg {
+            if let Pat::Ident(pat) = &mut *arg.pat {
+                pat.mutability = None;
+            }
+        }
+    }
+    let lifetime = match params.is_empty() {
+        true => quote! {},
+        false => quote! { <'a> },
+    };
+
+    let attrs = &input.attrs;
+    let vis = &input.vis;
+    let block = &input.block;
+
+    Ok(quote! {
+        #[cfg(feature = "ssr")]
+        #(#attrs)*
+        #vis #sig #block
+
+        #[cfg(feature = "ssr")]
+        const _: () = {
+            #[derive(::dioxus_fullstack::__private::serde::Deserialize)]
+            #[serde(crate = "::dioxus_fullstack::__private::serde")]
+            #[allow(non_camel_case_types)]
+            struct #args_struct {
+                #(#names: #types,)*
+            }
+
+            #[allow(non_snake_case)]
+            fn #handler(body: ::std::string::String) -> ::dioxus_fullstack::ServerFnFuture {
+                ::std::boxed::Box::pin(async move {
+                    let args: #args_struct = match ::dioxus_fullstack::__private::serde_json::from_str(&body) {
+                        Ok(args) => args,
+                        Err(err) => return ::dioxus_fullstack::ServerFnResponse::bad_arguments(err),
+                    };
+                    let #args_struct { #(#names,)* } = args;
+                    ::dioxus_fullstack::ServerFnResponse::from_result(&#name(#(#names),*).await)
+                })
+            }
+
+            ::dioxus_fullstack::__private::inventory::submit! {
+                ::dioxus_fullstack::ServerFnEntry::new(#endpoint, #handler)
+            }
+        };
+
+        #[cfg(not(feature = "ssr"))]
+        #(#attrs)*
+        #vis #client_sig {
+            #[derive(::dioxus_fullstack::__private::serde::Serialize)]
+            #[serde(crate = "::dioxus_fullstack::__private::serde")]
+            #[allow(non_camel_case_types)]
+            struct #args_struct #lifetime {
+                #(#names: &'a #types,)*
+            }
+
+            ::dioxus_fullstack::call_server_fn(#endpoint, &#args_struct { #(#names: &#names,)* }).await
+        }
+    })
+}
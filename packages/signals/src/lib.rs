@@ -7,21 +7,38 @@ use std::{
 
 mod rt;
 
-use dioxus_core::ScopeState;
+use dioxus_core::{
+    exports::bumpalo::Bump, AttributeValue, DynamicNode, IntoAttributeValue, IntoDynNode, Live,
+    RenderReason, ScopeState,
+};
 pub use rt::*;
 
 pub fn use_init_signal_rt(cx: &ScopeState) {
     cx.use_hook(|| {
-        let rt = crate::rt::claim_rt(cx.schedule_update_any());
+        let rt = crate::rt::claim_rt(
+            cx.schedule_update_any_with_reason(RenderReason::Signal),
+            cx.schedule_live_update(),
+        );
         cx.provide_context(rt);
     });
 }
 
+/// Create a signal that lives as long as the component
+///
+/// Components that read the signal while they render, like by formatting it into `"{count}"`, render again when it's
+/// written. Rendering the signal itself as text or as an attribute value doesn't subscribe anything: the text or the
+/// attribute is patched in place instead.
+///
+/// ```rust, ignore
+/// let mut count = use_signal(cx, || 0);
+///
+/// // Clicking only patches the text, the component renders once
+/// cx.render(rsx!{ button { onclick: move |_| count += 1, count } })
+/// ```
 pub fn use_signal<T: 'static>(cx: &ScopeState, f: impl FnOnce() -> T) -> Signal<T> {
     cx.use_hook(|| {
         let rt: &'static SignalRt = cx.consume_context().unwrap();
         let id = rt.init(f());
-        rt.subscribe(id, cx.scope_id());
 
         struct SignalHook<T> {
             signal: Signal<T>,
@@ -44,6 +61,102 @@ pub fn use_signal<T: 'static>(cx: &ScopeState, f: impl FnOnce() -> T) -> Signal<
     .signal
 }
 
+/// Compute a value from other signals, only computing it again when one of the signals it read changes
+///
+/// The signals are tracked automatically: whatever `f` reads becomes a dependency, and dependencies that are no longer
+/// read stop counting once it runs again. The component only re-renders when the new value is different from the old.
+///
+/// ```rust, ignore
+/// let count = use_signal(cx, || 0);
+/// let doubled = use_memo(cx, move || count.get() * 2);
+///
+/// cx.render(rsx!{ "{doubled}" })
+/// ```
+pub fn use_memo<T: PartialEq + 'static>(
+    cx: &ScopeState,
+    f: impl Fn() -> T + 'static,
+) -> Computed<T> {
+    cx.use_hook(|| {
+        let rt: &'static SignalRt = cx.consume_context().unwrap();
+        let id = rt.init_computed(f);
+
+        struct ComputedHook<T> {
+            computed: Computed<T>,
+        }
+
+        impl<T> Drop for ComputedHook<T> {
+            fn drop(&mut self) {
+                self.computed.rt.remove(self.computed.id);
+            }
+        }
+
+        ComputedHook {
+            computed: Computed {
+                id,
+                rt,
+                t: PhantomData,
+            },
+        }
+    })
+    .computed
+}
+
+/// A value derived from other signals, created with [`use_memo`]
+///
+/// Reading it from another computed value makes that value depend on it too.
+pub struct Computed<T> {
+    id: usize,
+    rt: &'static SignalRt,
+    t: PhantomData<T>,
+}
+
+impl<T: 'static> Computed<T> {
+    pub fn read(&self) -> Ref<T> {
+        self.rt.read(self.id)
+    }
+
+    pub fn with<O>(&self, f: impl FnOnce(&T) -> O) -> O {
+        let read = self.read();
+        f(&*read)
+    }
+}
+
+impl<T: Clone + 'static> Computed<T> {
+    pub fn get(&self) -> T {
+        self.rt.get(self.id)
+    }
+}
+
+impl<T> std::clone::Clone for Computed<T> {
+    fn clone(&self) -> Self {
+        Self {
+            t: PhantomData,
+            id: self.id,
+            rt: self.rt,
+        }
+    }
+}
+
+impl<T> Copy for Computed<T> {}
+
+impl<T: Display + 'static> Display for Computed<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.rt.with::<T, _>(self.id, |v| T::fmt(v, f))
+    }
+}
+
+impl<'a, T: Display + 'static> IntoDynNode<'a> for Computed<T> {
+    fn into_vnode(self, cx: &'a ScopeState) -> DynamicNode<'a> {
+        live::<T>(self.rt, self.id).into_vnode(cx)
+    }
+}
+
+impl<'a, T: Display + 'static> IntoAttributeValue<'a> for Computed<T> {
+    fn into_value(self, bump: &'a Bump) -> AttributeValue<'a> {
+        live::<T>(self.rt, self.id).into_value(bump)
+    }
+}
+
 pub struct Signal<T> {
     id: usize,
     rt: &'static SignalRt,
@@ -106,6 +219,42 @@ impl<T: Display + 'static> Display for Signal<T> {
     }
 }
 
+impl<'a, T: Display + 'static> IntoDynNode<'a> for Signal<T> {
+    fn into_vnode(self, cx: &'a ScopeState) -> DynamicNode<'a> {
+        live::<T>(self.rt, self.id).into_vnode(cx)
+    }
+}
+
+impl<'a, T: Display + 'static> IntoAttributeValue<'a> for Signal<T> {
+    fn into_value(self, bump: &'a Bump) -> AttributeValue<'a> {
+        live::<T>(self.rt, self.id).into_value(bump)
+    }
+}
+
+// Display a signal so it's patched in place when it changes, without subscribing the component
+fn live<T: Display + 'static>(rt: &'static SignalRt, id: usize) -> Live<Peek<T>> {
+    Live {
+        id: rt.live::<T>(id),
+        value: Peek {
+            id,
+            rt,
+            t: PhantomData,
+        },
+    }
+}
+
+struct Peek<T> {
+    id: usize,
+    rt: &'static SignalRt,
+    t: PhantomData<T>,
+}
+
+impl<T: Display + 'static> Display for Peek<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.rt.peek::<T, _>(self.id, |v| T::fmt(v, f))
+    }
+}
+
 impl<T: Add<Output = T> + Copy + 'static> std::ops::AddAssign<T> for Signal<T> {
     fn add_assign(&mut self, rhs: T) {
         self.set(self.get() + rhs);
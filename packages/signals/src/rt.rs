@@ -1,6 +1,6 @@
-use std::{any::Any, cell::RefCell, sync::Arc};
+use std::{any::Any, cell::RefCell, fmt::Display, rc::Rc, sync::Arc};
 
-use dioxus_core::ScopeId;
+use dioxus_core::{rendering_scope, LiveId, ScopeId};
 use slab::Slab;
 
 thread_local! {
@@ -11,7 +11,10 @@ thread_local! {
 /// Provide the runtime for signals
 ///
 /// This will reuse dead runtimes
-pub fn claim_rt(update_any: Arc<dyn Fn(ScopeId)>) -> &'static SignalRt {
+pub fn claim_rt(
+    update_any: Arc<dyn Fn(ScopeId)>,
+    live_update: Arc<dyn Fn(LiveId, String)>,
+) -> &'static SignalRt {
     RUNTIMES.with(|runtimes| {
         if let Some(rt) = runtimes.borrow_mut().pop() {
             return rt;
@@ -19,7 +22,9 @@ pub fn claim_rt(update_any: Arc<dyn Fn(ScopeId)>) -> &'static SignalRt {
 
         Box::leak(Box::new(SignalRt {
             signals: RefCell::new(Slab::new()),
+            tracking: RefCell::new(Vec::new()),
             update_any,
+            live_update,
         }))
     })
 }
@@ -34,6 +39,10 @@ pub fn reclam_rt(_rt: &'static SignalRt) {
 pub struct SignalRt {
     pub(crate) signals: RefCell<Slab<Inner>>,
     pub(crate) update_any: Arc<dyn Fn(ScopeId)>,
+    pub(crate) live_update: Arc<dyn Fn(LiveId, String)>,
+
+    // the signals read by each computed value that's being evaluated, innermost last
+    pub(crate) tracking: RefCell<Vec<Vec<usize>>>,
 }
 
 impl SignalRt {
@@ -42,14 +51,220 @@ impl SignalRt {
             value: Box::new(val),
             subscribers: Vec::new(),
             getter: None,
+            live: None,
+            dependents: Vec::new(),
+            computed: None,
         })
     }
 
+    /// Create a value that's computed from other signals
+    ///
+    /// Every signal read while `f` runs becomes a dependency. When one of them is set, `f` runs again, and if the new
+    /// value is different, the subscribers of the computed value are updated.
+    pub fn init_computed<T: PartialEq + 'static>(
+        &'static self,
+        f: impl Fn() -> T + 'static,
+    ) -> usize {
+        let compute: Rc<dyn Fn() -> Box<dyn Any>> = Rc::new(move || Box::new(f()) as Box<dyn Any>);
+        let (value, dependencies) = self.track(&*compute);
+
+        let id = self.signals.borrow_mut().insert(Inner {
+            value,
+            subscribers: Vec::new(),
+            getter: None,
+            live: None,
+            dependents: Vec::new(),
+            computed: Some(Computation {
+                compute,
+                eq: |a, b| a.downcast_ref::<T>() == b.downcast_ref::<T>(),
+                dependencies: Vec::new(),
+            }),
+        });
+
+        self.set_dependencies(id, dependencies);
+        id
+    }
+
+    /// Run `f`, collecting the signals it reads
+    fn track(&self, f: &dyn Fn() -> Box<dyn Any>) -> (Box<dyn Any>, Vec<usize>) {
+        self.tracking.borrow_mut().push(Vec::new());
+        let value = f();
+        let dependencies = self.tracking.borrow_mut().pop().unwrap_or_default();
+        (value, dependencies)
+    }
+
+    /// Note that a signal was read by the computed value that's being evaluated, or else by the component that's
+    /// rendering
+    fn track_read(&self, id: usize) {
+        if let Some(reads) = self.tracking.borrow_mut().last_mut() {
+            if !reads.contains(&id) {
+                reads.push(id);
+            }
+            return;
+        }
+
+        if let Some(scope) = rendering_scope() {
+            self.subscribe(id, scope);
+        }
+    }
+
+    fn set_dependencies(&self, id: usize, dependencies: Vec<usize>) {
+        let mut signals = self.signals.borrow_mut();
+
+        let old = match signals
+            .get_mut(id)
+            .and_then(|inner| inner.computed.as_mut())
+        {
+            Some(computed) => std::mem::replace(&mut computed.dependencies, dependencies.clone()),
+            None => return,
+        };
+
+        for dependency in old {
+            if let Some(inner) = signals.get_mut(dependency) {
+                inner.dependents.retain(|dependent| *dependent != id);
+            }
+        }
+        for dependency in dependencies {
+            if let Some(inner) = signals.get_mut(dependency) {
+                inner.dependents.push(id);
+            }
+        }
+    }
+
+    /// Update the subscribers of a signal that changed, and compute the values that depend on it again
+    ///
+    /// Each computed value runs at most once, after every value it reads was brought up to date, even when it reads
+    /// the signal through several other computed values.
+    fn changed(&self, id: usize) {
+        self.notify(id);
+
+        let mut changed = vec![id];
+        for dependent in self.dependents_in_order(id) {
+            let stale = match self
+                .signals
+                .borrow()
+                .get(dependent)
+                .and_then(|inner| inner.computed.as_ref())
+            {
+                Some(computed) => computed
+                    .dependencies
+                    .iter()
+                    .any(|dependency| changed.contains(dependency)),
+                None => false,
+            };
+
+            if stale && self.recompute(dependent) {
+                self.notify(dependent);
+                changed.push(dependent);
+            }
+        }
+    }
+
+    /// Update the components that read a signal and the text and attributes that display it
+    fn notify(&self, id: usize) {
+        let (subscribers, live) = match self.signals.borrow().get(id) {
+            Some(inner) => (
+                inner.subscribers.clone(),
+                inner
+                    .live
+                    .map(|(live, display)| (live, display(&*inner.value))),
+            ),
+            None => return,
+        };
+
+        for subscriber in subscribers {
+            (self.update_any)(subscriber);
+        }
+
+        if let Some((live, value)) = live {
+            (self.live_update)(live, value);
+        }
+    }
+
+    /// Every computed value that depends on a signal, directly or not, each one after the values it reads
+    fn dependents_in_order(&self, id: usize) -> Vec<usize> {
+        fn visit(
+            signals: &Slab<Inner>,
+            id: usize,
+            visited: &mut Vec<usize>,
+            order: &mut Vec<usize>,
+        ) {
+            let dependents = match signals.get(id) {
+                Some(inner) => &inner.dependents,
+                None => return,
+            };
+
+            for dependent in dependents {
+                if !visited.contains(dependent) {
+                    visited.push(*dependent);
+                    visit(signals, *dependent, visited, order);
+                    order.push(*dependent);
+                }
+            }
+        }
+
+        let signals = self.signals.borrow();
+        let mut visited = Vec::new();
+        let mut order = Vec::new();
+        visit(&signals, id, &mut visited, &mut order);
+
+        // a value is pushed after everything that depends on it
+        order.reverse();
+        order
+    }
+
+    /// Compute a value again, returning whether it changed
+    fn recompute(&self, id: usize) -> bool {
+        let (compute, eq) = match self
+            .signals
+            .borrow()
+            .get(id)
+            .and_then(|inner| inner.computed.as_ref())
+        {
+            Some(computed) => (computed.compute.clone(), computed.eq),
+            None => return false,
+        };
+
+        let (value, dependencies) = self.track(&*compute);
+        self.set_dependencies(id, dependencies);
+
+        let mut signals = self.signals.borrow_mut();
+        let inner = match signals.get_mut(id) {
+            Some(inner) => inner,
+            None => return false,
+        };
+
+        if eq(&*inner.value, &*value) {
+            return false;
+        }
+        inner.value = value;
+        true
+    }
+
     pub fn subscribe(&self, id: usize, subscriber: ScopeId) {
-        self.signals.borrow_mut()[id].subscribers.push(subscriber);
+        let subscribers = &mut self.signals.borrow_mut()[id].subscribers;
+        if !subscribers.contains(&subscriber) {
+            subscribers.push(subscriber);
+        }
+    }
+
+    /// The id the signal is displayed with when it's rendered as text or as an attribute value
+    ///
+    /// Every write patches the text and attributes displaying it with the new value.
+    pub(crate) fn live<T: Display + 'static>(&self, id: usize) -> LiveId {
+        let mut signals = self.signals.borrow_mut();
+        signals[id]
+            .live
+            .get_or_insert_with(|| {
+                let display: fn(&dyn Any) -> String =
+                    |value| value.downcast_ref::<T>().unwrap().to_string();
+                (LiveId::new(), display)
+            })
+            .0
     }
 
     pub fn get<T: Clone + 'static>(&self, id: usize) -> T {
+        self.track_read(id);
         self.signals.borrow()[id]
             .value
             .downcast_ref::<T>()
@@ -58,20 +273,23 @@ impl SignalRt {
     }
 
     pub fn set<T: 'static>(&self, id: usize, value: T) {
-        let mut signals = self.signals.borrow_mut();
-        let inner = &mut signals[id];
-        inner.value = Box::new(value);
-
-        for subscriber in inner.subscribers.iter() {
-            (self.update_any)(*subscriber);
-        }
+        self.signals.borrow_mut()[id].value = Box::new(value);
+        self.changed(id);
     }
 
     pub fn remove(&self, id: usize) {
+        // forget about the signals a computed value read, so they don't keep computing it
+        self.set_dependencies(id, Vec::new());
         self.signals.borrow_mut().remove(id);
     }
 
     pub fn with<T: 'static, O>(&self, id: usize, f: impl FnOnce(&T) -> O) -> O {
+        self.track_read(id);
+        self.peek(id, f)
+    }
+
+    /// Read the value without subscribing anything to it
+    pub fn peek<T: 'static, O>(&self, id: usize, f: impl FnOnce(&T) -> O) -> O {
         let signals = self.signals.borrow();
         let inner = &signals[id];
         let inner = inner.value.downcast_ref::<T>().unwrap();
@@ -79,6 +297,7 @@ impl SignalRt {
     }
 
     pub(crate) fn read<T: 'static>(&self, id: usize) -> std::cell::Ref<T> {
+        self.track_read(id);
         let signals = self.signals.borrow();
         std::cell::Ref::map(signals, |signals| {
             signals[id].value.downcast_ref::<T>().unwrap()
@@ -118,4 +337,96 @@ pub(crate) struct Inner {
 
     // todo: this has a soundness hole in it that you might not run into
     pub getter: Option<Box<dyn Fn()>>,
+
+    // the id the value is displayed with, and how to display it
+    pub live: Option<(LiveId, fn(&dyn Any) -> String)>,
+
+    // the computed values that read this signal
+    pub dependents: Vec<usize>,
+
+    pub computed: Option<Computation>,
+}
+
+pub(crate) struct Computation {
+    pub compute: Rc<dyn Fn() -> Box<dyn Any>>,
+    pub eq: fn(&dyn Any, &dyn Any) -> bool,
+    pub dependencies: Vec<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn rt() -> &'static SignalRt {
+        claim_rt(Arc::new(|_| {}), Arc::new(|_, _| {}))
+    }
+
+    #[test]
+    fn recomputes_only_when_an_input_changes() {
+        let rt = rt();
+        let runs = Rc::new(Cell::new(0));
+
+        let input = rt.init(1);
+        let other = rt.init(1);
+        let doubled = rt.init_computed({
+            let runs = runs.clone();
+            move || {
+                runs.set(runs.get() + 1);
+                rt.get::<i32>(input) * 2
+            }
+        });
+        assert_eq!(runs.get(), 1);
+
+        rt.set(input, 2);
+        assert_eq!(runs.get(), 2);
+        assert_eq!(rt.get::<i32>(doubled), 4);
+
+        // writing a signal it doesn't read leaves it alone
+        rt.set(other, 2);
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn diamond_recomputes_once() {
+        let rt = rt();
+        let runs = Rc::new(Cell::new(0));
+
+        let input = rt.init(1);
+        let left = rt.init_computed(move || rt.get::<i32>(input) + 1);
+        let right = rt.init_computed(move || rt.get::<i32>(input) * 2);
+        let sum = rt.init_computed({
+            let runs = runs.clone();
+            move || {
+                runs.set(runs.get() + 1);
+                rt.get::<i32>(left) + rt.get::<i32>(right)
+            }
+        });
+        assert_eq!(runs.get(), 1);
+
+        rt.set(input, 2);
+        assert_eq!(runs.get(), 2);
+        assert_eq!(rt.get::<i32>(sum), 7);
+    }
+
+    #[test]
+    fn removed_computed_unsubscribes() {
+        let rt = rt();
+        let runs = Rc::new(Cell::new(0));
+
+        let input = rt.init(1);
+        let doubled = rt.init_computed({
+            let runs = runs.clone();
+            move || {
+                runs.set(runs.get() + 1);
+                rt.get::<i32>(input) * 2
+            }
+        });
+
+        rt.remove(doubled);
+        assert!(rt.signals.borrow()[input].dependents.is_empty());
+
+        rt.set(input, 2);
+        assert_eq!(runs.get(), 1);
+    }
 }
@@ -0,0 +1,108 @@
+//! Signals rendered as text or attribute values should be patched in place without running the component again
+
+use dioxus::core::{BorrowedAttributeValue, ElementId, Mutation::*};
+use dioxus::prelude::*;
+use dioxus_signals::*;
+use std::cell::Cell;
+use std::rc::Rc;
+
+thread_local! {
+    static RENDERS: Cell<usize> = Cell::new(0);
+}
+
+fn renders() -> usize {
+    RENDERS.with(|renders| renders.get())
+}
+
+fn text_id(edits: &[Mutation]) -> ElementId {
+    edits
+        .iter()
+        .find_map(|edit| match edit {
+            HydrateText { id, .. } => Some(*id),
+            _ => None,
+        })
+        .unwrap()
+}
+
+#[test]
+fn text_and_attributes_patched_in_place() {
+    fn app(cx: Scope) -> Element {
+        RENDERS.with(|renders| renders.set(renders.get() + 1));
+        use_init_signal_rt(cx);
+
+        let mut count = use_signal(cx, || 0);
+
+        cx.render(rsx! {
+            button { title: count, onclick: move |_| count += 1, count }
+        })
+    }
+
+    RENDERS.with(|renders| renders.set(0));
+    let mut dom = VirtualDom::new(app);
+    let text = text_id(&dom.rebuild().edits);
+
+    for value in ["1", "2"] {
+        dom.handle_event("click", Rc::new(MouseData::default()), ElementId(1), true);
+        assert_eq!(
+            dom.render_immediate().edits,
+            [
+                SetAttribute {
+                    name: "title",
+                    value: BorrowedAttributeValue::Text(value),
+                    id: ElementId(1),
+                    ns: None,
+                },
+                SetText { value, id: text },
+            ]
+        );
+    }
+    assert_eq!(renders(), 1);
+
+    // Rendering again diffs against the text the component last rendered, not the patched text
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        dom.render_immediate().edits,
+        [
+            SetAttribute {
+                name: "title",
+                value: BorrowedAttributeValue::Text("2"),
+                id: ElementId(1),
+                ns: None,
+            },
+            SetText {
+                value: "2",
+                id: text
+            },
+        ]
+    );
+    assert_eq!(renders(), 2);
+}
+
+#[test]
+fn reads_subscribe() {
+    fn app(cx: Scope) -> Element {
+        RENDERS.with(|renders| renders.set(renders.get() + 1));
+        use_init_signal_rt(cx);
+
+        let mut count = use_signal(cx, || 0);
+        let doubled = count() * 2;
+
+        cx.render(rsx! {
+            button { onclick: move |_| count.set(1), "{doubled}" }
+        })
+    }
+
+    RENDERS.with(|renders| renders.set(0));
+    let mut dom = VirtualDom::new(app);
+    let text = text_id(&dom.rebuild().edits);
+
+    dom.handle_event("click", Rc::new(MouseData::default()), ElementId(1), true);
+    assert_eq!(
+        dom.render_immediate().edits,
+        [SetText {
+            value: "2",
+            id: text
+        }]
+    );
+    assert_eq!(renders(), 2);
+}
@@ -0,0 +1,55 @@
+use dioxus_core::ScopeState;
+use std::any::Any;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// The page a data loader loads the data of
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticRoute {
+    /// The path of the page, like `/blog/hello`
+    pub path: String,
+
+    /// The route of the [`Route`](dioxus_router::Route) the page is for, like `/blog/:slug`
+    pub pattern: String,
+
+    /// The values of the parameters of the route, like `slug`
+    pub params: HashMap<String, String>,
+}
+
+impl StaticRoute {
+    pub(crate) fn new(pattern: &str, path: &str) -> Self {
+        let params = pattern
+            .split('/')
+            .zip(path.split('/'))
+            .filter_map(|(pattern, value)| {
+                let name = pattern.strip_prefix(':')?;
+                Some((name.to_string(), value.to_string()))
+            })
+            .collect();
+
+        Self {
+            path: path.to_string(),
+            pattern: pattern.to_string(),
+            params,
+        }
+    }
+}
+
+/// The data the loader of a page loaded, provided to the app while the page renders
+#[derive(Clone)]
+pub(crate) struct PageData(pub(crate) Rc<dyn Any>);
+
+/// The data the loader of the page loaded for it, if it has a loader that loads a `T`
+///
+/// The same components can run in the browser, where there's no loader, so they should handle `None`.
+///
+/// ```rust, ignore
+/// fn BlogPost(cx: Scope) -> Element {
+///     let post = use_page_data::<Post>(cx)?;
+///     render! { article { h1 { "{post.title}" } } }
+/// }
+/// ```
+pub fn use_page_data<T: 'static>(cx: &ScopeState) -> Option<&T> {
+    let data = cx.use_hook(|| cx.consume_context::<PageData>());
+    data.as_ref()?.0.downcast_ref()
+}
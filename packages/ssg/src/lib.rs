@@ -0,0 +1,25 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+
+mod data;
+mod site;
+
+pub use data::{use_page_data, StaticRoute};
+pub use site::StaticSite;
+
+/// The site couldn't be built
+#[derive(Debug, thiserror::Error)]
+pub enum SsgError {
+    /// A file of the site couldn't be written, or an asset couldn't be copied
+    #[error("the site couldn't be written: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The loader of a page failed
+    #[error("the data of {path} couldn't be loaded: {message}")]
+    Loader {
+        /// The path of the page
+        path: String,
+        /// What the loader failed with
+        message: String,
+    },
+}
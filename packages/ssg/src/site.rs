@@ -0,0 +1,256 @@
+use crate::data::{PageData, StaticRoute};
+use crate::SsgError;
+use dioxus_core::{Component, VirtualDom};
+use dioxus_router::RouterCollector;
+use dioxus_ssr::Renderer;
+use futures_util::future::{FutureExt, LocalBoxFuture};
+use std::any::Any;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Display;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+type Layout = Box<dyn Fn(&StaticRoute, &str) -> String>;
+type Loader = Box<dyn Fn(StaticRoute) -> LocalBoxFuture<'static, Result<Rc<dyn Any>, String>>>;
+
+/// A site of static pages, rendered from the routes of an app
+///
+/// The pages are found by rendering the app: the routes of the [`Router`](dioxus_router::Router) that render on the
+/// index page become pages, and so do the routes nested in them once those pages render. Routes with parameters, like
+/// `/blog/:slug`, only become pages for the paths listed with [`StaticSite::paths`].
+///
+/// ```rust, ignore
+/// let pages = StaticSite::new(app)
+///     .paths("/blog/:slug", ["/blog/hello", "/blog/goodbye"])
+///     .loader("/blog/:slug", |route| async move { load_post(&route.params["slug"]).await })
+///     .assets("public")
+///     .build("dist")
+///     .await?;
+/// ```
+pub struct StaticSite {
+    app: Component,
+    layout: Layout,
+    paths: HashMap<String, Vec<String>>,
+    loaders: HashMap<String, Loader>,
+    assets: Vec<PathBuf>,
+}
+
+impl StaticSite {
+    /// A site of the pages of an app, in the default layout
+    pub fn new(app: Component) -> Self {
+        Self {
+            app,
+            layout: Box::new(default_layout),
+            paths: HashMap::new(),
+            loaders: HashMap::new(),
+            assets: Vec::new(),
+        }
+    }
+
+    /// Wrap the html of every page in a layout, like the doctype and the head of the page
+    ///
+    /// The default layout puts the page in the body of a plain document. The elements a page adds to its head, like
+    /// its `Title`, go at the end of the `<head>` of the layout.
+    pub fn layout(mut self, layout: impl Fn(&StaticRoute, &str) -> String + 'static) -> Self {
+        self.layout = Box::new(layout);
+        self
+    }
+
+    /// Render a route with parameters as these paths
+    pub fn paths(
+        mut self,
+        pattern: &str,
+        paths: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.paths
+            .entry(pattern.to_string())
+            .or_default()
+            .extend(paths.into_iter().map(Into::into));
+        self
+    }
+
+    /// Load the data of the pages of a route before they render, for [`use_page_data`](crate::use_page_data)
+    ///
+    /// The site fails to build if the loader of any page fails.
+    pub fn loader<T, E, F, Fut>(mut self, pattern: &str, loader: F) -> Self
+    where
+        T: 'static,
+        E: Display,
+        F: Fn(StaticRoute) -> Fut + 'static,
+        Fut: Future<Output = Result<T, E>> + 'static,
+    {
+        let loader = move |route| {
+            loader(route)
+                .map(|data| match data {
+                    Ok(data) => Ok(Rc::new(data) as Rc<dyn Any>),
+                    Err(err) => Err(err.to_string()),
+                })
+                .boxed_local()
+        };
+        self.loaders.insert(pattern.to_string(), Box::new(loader));
+        self
+    }
+
+    /// Copy the files in a directory, like images and styles, into the root of the site
+    pub fn assets(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.assets.push(dir.into());
+        self
+    }
+
+    /// Render every page of the site into a directory, returning the files of the pages
+    ///
+    /// A page is the `index.html` of the directory of its path, so `/blog/hello` is `blog/hello/index.html`. A path
+    /// that ends in a file name, like `/404.html`, is written as that file.
+    pub async fn build(&self, out: impl AsRef<Path>) -> Result<Vec<PathBuf>, SsgError> {
+        let out = out.as_ref();
+        std::fs::create_dir_all(out)?;
+        for dir in &self.assets {
+            copy_dir(dir, out)?;
+        }
+
+        let mut queue = Queue::default();
+        queue.push("/", "/");
+        for (pattern, paths) in &self.paths {
+            queue.patterns.insert(pattern.clone());
+            for path in paths {
+                queue.push(pattern, path);
+            }
+        }
+
+        let mut renderer = Renderer::new();
+        let mut pages = Vec::new();
+        while let Some(route) = queue.routes.pop_front() {
+            let data = match self.loaders.get(&route.pattern) {
+                Some(loader) => {
+                    Some(
+                        loader(route.clone())
+                            .await
+                            .map_err(|message| SsgError::Loader {
+                                path: route.path.clone(),
+                                message,
+                            })?,
+                    )
+                }
+                None => None,
+            };
+
+            let (html, head, routes) = self.render_page(&mut renderer, &route, data).await;
+
+            let file = out.join(file_path(&route.path));
+            if let Some(dir) = file.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(&file, insert_head((self.layout)(&route, &html), &head))?;
+            pages.push(file);
+
+            for pattern in routes {
+                // a route without a path renders on every page
+                if pattern.is_empty() || !queue.patterns.insert(pattern.clone()) {
+                    continue;
+                }
+                if pattern.split('/').any(|segment| segment.starts_with(':')) {
+                    log::warn!(
+                        "Skipping the route {}, which has parameters but no paths to render",
+                        pattern
+                    );
+                    continue;
+                }
+                queue.push(&pattern, &pattern);
+            }
+        }
+
+        Ok(pages)
+    }
+
+    /// Render the app at the path of a page, waiting for everything that's suspended
+    ///
+    /// Returns the html of the page, the elements the page added to its head and the routes the routers of the page
+    /// declared.
+    async fn render_page(
+        &self,
+        renderer: &mut Renderer,
+        route: &StaticRoute,
+        data: Option<Rc<dyn Any>>,
+    ) -> (String, String, Vec<String>) {
+        let mut dom = VirtualDom::new(self.app);
+
+        dioxus_ssr::provide_request_path(&dom, &route.path);
+        dioxus_ssr::provide_head(&dom);
+        let collector = RouterCollector::default();
+        dom.base_scope().provide_context(collector.clone());
+        if let Some(data) = data {
+            dom.base_scope().provide_context(PageData(data));
+        }
+
+        _ = dom.rebuild();
+        while dom.has_suspended_work() {
+            dom.wait_for_work().await;
+            _ = dom.render_immediate();
+        }
+
+        let routes = collector
+            .routers()
+            .iter()
+            .flat_map(|router| router.routes())
+            .collect();
+        (renderer.render(&dom), dioxus_ssr::render_head(&dom), routes)
+    }
+}
+
+/// The pages left to render, with every route and path that was seen so a page is only rendered once
+#[derive(Default)]
+struct Queue {
+    routes: VecDeque<StaticRoute>,
+    patterns: HashSet<String>,
+    paths: HashSet<String>,
+}
+
+impl Queue {
+    fn push(&mut self, pattern: &str, path: &str) {
+        self.patterns.insert(pattern.to_string());
+        if self.paths.insert(path.to_string()) {
+            self.routes.push_back(StaticRoute::new(pattern, path));
+        }
+    }
+}
+
+fn default_layout(_: &StaticRoute, html: &str) -> String {
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"></head><body><div id=\"main\">{}</div></body></html>",
+        html
+    )
+}
+
+/// Put the elements a page added to its head at the end of the head of its layout
+///
+/// A layout without a head is left alone.
+fn insert_head(mut page: String, head: &str) -> String {
+    if let Some(end) = page.find("</head>") {
+        page.insert_str(end, head);
+    }
+    page
+}
+
+/// The file of the page at a path, relative to the root of the site
+fn file_path(path: &str) -> PathBuf {
+    let path = path.trim_matches('/');
+    match path.rsplit('/').next() {
+        Some(name) if name.contains('.') => PathBuf::from(path),
+        _ => Path::new(path).join("index.html"),
+    }
+}
+
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            std::fs::create_dir_all(&target)?;
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
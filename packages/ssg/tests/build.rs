@@ -0,0 +1,52 @@
+use dioxus::prelude::*;
+use dioxus_router::{Route, Router};
+use dioxus_ssg::{use_page_data, StaticSite};
+
+fn app(cx: Scope) -> Element {
+    render! {
+        Router {
+            Route { to: "/", h1 { "Home" } }
+            Route { to: "/about", h1 { "About" } }
+            Route { to: "/blog/:slug", post {} }
+            Route { to: "/drafts/:slug", h1 { "Draft" } }
+        }
+    }
+}
+
+fn post(cx: Scope) -> Element {
+    let post = use_page_data::<String>(cx)?;
+    render! { article { "{post}" } }
+}
+
+#[test]
+fn renders_every_route() {
+    let out = std::env::temp_dir().join(format!("dioxus-ssg-{}", std::process::id()));
+
+    let pages = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(
+            StaticSite::new(app)
+                .paths("/blog/:slug", ["/blog/hello"])
+                .loader("/blog/:slug", |route| async move {
+                    Ok::<_, std::io::Error>(format!("post {}", route.params["slug"]))
+                })
+                .layout(|_, html| format!("<body>{}</body>", html))
+                .build(&out),
+        )
+        .unwrap();
+
+    let read = |path: &str| std::fs::read_to_string(out.join(path)).unwrap();
+    assert_eq!(read("index.html"), "<body><h1>Home</h1></body>");
+    assert_eq!(read("about/index.html"), "<body><h1>About</h1></body>");
+    assert_eq!(
+        read("blog/hello/index.html"),
+        "<body><article>post hello</article></body>"
+    );
+
+    // the drafts have parameters but no paths, so they aren't rendered
+    assert_eq!(pages.len(), 3);
+    assert!(!out.join("drafts").exists());
+
+    std::fs::remove_dir_all(out).unwrap();
+}
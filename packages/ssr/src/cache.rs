@@ -1,10 +1,18 @@
-use dioxus_core::prelude::*;
+use crate::config::{SsrConfig, Whitespace};
+use dioxus_core::{prelude::*, Attribute, AttributeValue};
 use std::fmt::Write;
 
+const MATHML_NAMESPACE: &str = "http://www.w3.org/1998/Math/MathML";
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
 #[derive(Debug)]
 pub struct StringCache {
     pub segments: Vec<Segment>,
     pub template: Template<'static>,
+
+    /// Where the contents of the root element start and end, as a segment and an offset into it, if the template
+    /// renders a single element that isn't void
+    pub contents: Option<((usize, usize), (usize, usize))>,
 }
 
 #[derive(Default)]
@@ -15,8 +23,57 @@ pub struct StringChain {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Segment {
     Attr(usize),
+    /// The contents of an element, if the dynamic attribute is its `dangerous_inner_html`
+    InnerHtml(usize),
     Node(usize),
     PreRendered(String),
+    /// The attributes of an element in [deterministic](crate::SsrConfig::deterministic) html, which are sorted by name
+    /// once the dynamic ones are known. The static attributes are kept with their name and html.
+    SortedAttrs {
+        statics: Vec<(String, String)>,
+        dynamic: Vec<usize>,
+    },
+    /// Start a new line, indented by this many levels more than where the template is rendered
+    Newline(usize),
+    /// A dynamic node of a template that isn't rendered with [`Whitespace::Preserve`], and where it is in the template
+    FormattedNode {
+        idx: usize,
+        depth: usize,
+        preformatted: bool,
+    },
+}
+
+/// Where a node of a template is, for the whitespace around it
+#[derive(Clone, Copy)]
+struct Layout<'a> {
+    config: &'a SsrConfig,
+    depth: usize,
+    /// whitespace inside of this node is written as it is
+    preformatted: bool,
+    /// the node is text on the line of its element
+    inline: bool,
+}
+
+impl Layout<'_> {
+    fn pretty(&self) -> bool {
+        self.config.whitespace == Whitespace::Pretty && !self.preformatted && !self.inline
+    }
+
+    fn newline(&self, chain: &mut StringChain) {
+        if self.pretty() {
+            chain.segments.push(Segment::Newline(self.depth));
+        }
+    }
+}
+
+impl StringChain {
+    /// The segment and offset the next write goes to
+    fn position(&self) -> (usize, usize) {
+        match self.segments.last() {
+            Some(Segment::PreRendered(s)) => (self.segments.len() - 1, s.len()),
+            _ => (self.segments.len(), 0),
+        }
+    }
 }
 
 impl std::fmt::Write for StringChain {
@@ -31,18 +88,46 @@ impl std::fmt::Write for StringChain {
 }
 
 impl StringCache {
-    pub fn from_template(template: &VNode) -> Result<Self, std::fmt::Error> {
+    /// Cache the html of a template, for a renderer with this config
+    ///
+    /// A template that's rendered inside of an element like `pre` is cached separately, since its whitespace is kept.
+    pub fn from_template(
+        template: &VNode,
+        config: &SsrConfig,
+        preformatted: bool,
+    ) -> Result<Self, std::fmt::Error> {
         let mut chain = StringChain::default();
 
         let mut cur_path = vec![];
+        let layout = Layout {
+            config,
+            depth: 0,
+            preformatted,
+            inline: false,
+        };
+        let roots = template.template.get().roots;
+        let mut contents = None;
 
-        for (root_idx, root) in template.template.get().roots.iter().enumerate() {
-            Self::recurse(root, &mut cur_path, root_idx, &mut chain)?;
+        for (root_idx, root) in roots.iter().enumerate() {
+            let contents = match roots.len() {
+                1 => Some(&mut contents),
+                _ => None,
+            };
+            Self::recurse(
+                root,
+                &mut cur_path,
+                root_idx,
+                &mut chain,
+                template.dynamic_attrs,
+                layout,
+                contents,
+            )?;
         }
 
         Ok(Self {
             segments: chain.segments,
             template: template.template.get(),
+            contents,
         })
     }
 
@@ -51,40 +136,174 @@ impl StringCache {
         cur_path: &mut Vec<usize>,
         root_idx: usize,
         chain: &mut StringChain,
+        dynamic_attrs: &[Attribute],
+        layout: Layout,
+        contents: Option<&mut Option<((usize, usize), (usize, usize))>>,
     ) -> Result<(), std::fmt::Error> {
         match root {
             TemplateNode::Element {
                 tag,
+                namespace,
                 attrs,
                 children,
-                ..
             } => {
                 cur_path.push(root_idx);
+                layout.newline(chain);
                 write!(chain, "<{}", tag)?;
+                // html parsers know where MathML starts, but xml parsers need to be told
+                if *tag == "math"
+                    && *namespace == Some(MATHML_NAMESPACE)
+                    && !attrs
+                        .iter()
+                        .any(|attr| matches!(attr, TemplateAttribute::Static { name: "xmlns", .. }))
+                {
+                    write!(chain, " xmlns=\"{}\"", MATHML_NAMESPACE)?;
+                }
+                let mut inner_html = None;
+                let mut sorted = layout
+                    .config
+                    .deterministic
+                    .then(|| (Vec::new(), Vec::new()));
                 for attr in *attrs {
                     match attr {
+                        TemplateAttribute::Static {
+                            name: "dangerous_inner_html",
+                            value,
+                            ..
+                        } => inner_html = Some(*value),
                         TemplateAttribute::Static { name, value, .. } => {
-                            write!(chain, " {}=\"{}\"", name, value)?;
-                        }
-                        TemplateAttribute::Dynamic { id: index } => {
-                            chain.segments.push(Segment::Attr(*index))
+                            let mut html = String::new();
+                            // browsers only look at whether boolean attributes are there, so "false" leaves it out
+                            if namespace.is_none() && is_boolean_attribute(name) {
+                                if *value != "false" {
+                                    write!(html, " {}", name)?;
+                                }
+                            } else {
+                                write!(html, " {}=\"{}\"", name, value)?;
+                            }
+                            match &mut sorted {
+                                Some((statics, _)) => statics.push((name.to_string(), html)),
+                                None => write!(chain, "{}", html)?,
+                            }
                         }
+                        TemplateAttribute::Dynamic { id: index } => match &mut sorted {
+                            Some((_, dynamic)) => dynamic.push(*index),
+                            None => chain.segments.push(Segment::Attr(*index)),
+                        },
                     }
                 }
-                if children.is_empty() && tag_is_self_closing(tag) {
+                if let Some((statics, dynamic)) = sorted {
+                    if !statics.is_empty() || !dynamic.is_empty() {
+                        chain
+                            .segments
+                            .push(Segment::SortedAttrs { statics, dynamic });
+                    }
+                }
+
+                // void elements can't have any contents, and xml can close any element that's empty
+                let void = namespace.is_none() && tag_is_self_closing(tag);
+                let empty_svg = layout.config.self_close_svg
+                    && *namespace == Some(SVG_NAMESPACE)
+                    && children.is_empty()
+                    && inner_html.is_none()
+                    // the inner html of a dynamic attribute would make it not empty after all
+                    && !attrs.iter().any(|attr| match attr {
+                        TemplateAttribute::Dynamic { id } => {
+                            let attr = &dynamic_attrs[*id];
+                            attr.name == "dangerous_inner_html"
+                                || matches!(attr.value, AttributeValue::Spread(_))
+                        }
+                        _ => false,
+                    });
+
+                if void || empty_svg {
                     write!(chain, "/>")?;
                 } else {
                     write!(chain, ">")?;
+
+                    let preformatted = layout.preformatted || is_preformatted(tag);
+                    // html parsers drop the newline right after the start of these, so it takes another one to keep it
+                    if matches!(*tag, "pre" | "textarea" | "listing") {
+                        if let Some(TemplateNode::Text { text }) = children.first() {
+                            if text.starts_with('\n') {
+                                writeln!(chain)?;
+                            }
+                        }
+                    }
+                    let start = chain.position();
+
+                    if let Some(inner_html) = inner_html {
+                        write!(chain, "{}", inner_html)?;
+                    }
+                    // any of the dynamic attributes might turn out to be the inner html
+                    for attr in *attrs {
+                        if let TemplateAttribute::Dynamic { id: index } = attr {
+                            chain.segments.push(Segment::InnerHtml(*index))
+                        }
+                    }
+
+                    // elements with nothing but text keep it on their line
+                    let inline = children.iter().all(|child| {
+                        matches!(
+                            child,
+                            TemplateNode::Text { .. } | TemplateNode::DynamicText { .. }
+                        )
+                    });
+                    let child_layout = Layout {
+                        depth: layout.depth + 1,
+                        preformatted,
+                        inline,
+                        ..layout
+                    };
                     for child in *children {
-                        Self::recurse(child, cur_path, root_idx, chain)?;
+                        Self::recurse(
+                            child,
+                            cur_path,
+                            root_idx,
+                            chain,
+                            dynamic_attrs,
+                            child_layout,
+                            None,
+                        )?;
+                    }
+
+                    if !inline {
+                        Layout {
+                            preformatted,
+                            ..layout
+                        }
+                        .newline(chain);
+                    }
+                    if let Some(contents) = contents {
+                        *contents = Some((start, chain.position()));
                     }
                     write!(chain, "</{}>", tag)?;
                 }
                 cur_path.pop();
             }
-            TemplateNode::Text { text } => write!(chain, "{}", text)?,
+            TemplateNode::Text { text } => {
+                layout.newline(chain);
+                if layout.config.collapses_whitespace() && !layout.preformatted {
+                    write!(chain, "{}", collapse_whitespace(text))?
+                } else {
+                    write!(chain, "{}", text)?
+                }
+            }
             TemplateNode::Dynamic { id: idx } | TemplateNode::DynamicText { id: idx } => {
-                chain.segments.push(Segment::Node(*idx))
+                if let TemplateNode::DynamicText { .. } = root {
+                    layout.newline(chain);
+                }
+                // collapsing the whitespace of dynamic text depends on whether it's inside of an element like `pre`
+                match layout.config.whitespace {
+                    Whitespace::Preserve if !layout.config.deterministic => {
+                        chain.segments.push(Segment::Node(*idx))
+                    }
+                    _ => chain.segments.push(Segment::FormattedNode {
+                        idx: *idx,
+                        depth: layout.depth,
+                        preformatted: layout.preformatted,
+                    }),
+                }
             }
         }
 
@@ -111,3 +330,60 @@ fn tag_is_self_closing(tag: &str) -> bool {
             | "wbr"
     )
 }
+
+/// The elements whose whitespace is part of what they show or run
+pub(crate) fn is_preformatted(tag: &str) -> bool {
+    matches!(tag, "pre" | "textarea" | "listing" | "script" | "style")
+}
+
+/// The attributes that are true when they're there and false when they're not, whatever their value is
+pub(crate) fn is_boolean_attribute(name: &str) -> bool {
+    matches!(
+        name,
+        "allowfullscreen"
+            | "allowpaymentrequest"
+            | "async"
+            | "autofocus"
+            | "autoplay"
+            | "checked"
+            | "controls"
+            | "default"
+            | "defer"
+            | "disabled"
+            | "formnovalidate"
+            | "hidden"
+            | "inert"
+            | "ismap"
+            | "itemscope"
+            | "loop"
+            | "multiple"
+            | "muted"
+            | "nomodule"
+            | "novalidate"
+            | "open"
+            | "playsinline"
+            | "readonly"
+            | "required"
+            | "reversed"
+            | "selected"
+            | "truespeed"
+    )
+}
+
+/// Replace every run of whitespace with a single space
+pub(crate) fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut in_whitespace = false;
+    for c in text.chars() {
+        if c.is_ascii_whitespace() {
+            if !in_whitespace {
+                collapsed.push(' ');
+            }
+            in_whitespace = true;
+        } else {
+            collapsed.push(c);
+            in_whitespace = false;
+        }
+    }
+    collapsed
+}
@@ -1 +1,103 @@
+//! How the [`Renderer`](crate::Renderer) lays out the html it writes
 
+/// What the renderer does with the whitespace between and inside the nodes of the app
+///
+/// Whitespace inside of `pre`, `textarea`, `listing`, `script` and `style` elements is always written as it is, since
+/// it's part of what they show or run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Whitespace {
+    /// Write the whitespace the app rendered, without adding any
+    Preserve,
+
+    /// Start every element and text node on a line of its own, indented by how deep it is
+    ///
+    /// Elements that only contain text keep it on their line. Browsers show the extra whitespace between inline
+    /// elements, so this is for reading the html rather than serving it.
+    Pretty,
+
+    /// Collapse every run of whitespace in text into a single space
+    Minified,
+}
+
+impl Default for Whitespace {
+    fn default() -> Self {
+        Whitespace::Preserve
+    }
+}
+
+/// The options of a [`Renderer`](crate::Renderer)
+///
+/// ```rust, ignore
+/// let mut renderer = Renderer::with_config(
+///     SsrConfig::new()
+///         .whitespace(Whitespace::Pretty)
+///         .self_close_svg(true),
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SsrConfig {
+    pub(crate) whitespace: Whitespace,
+    pub(crate) indent: usize,
+    pub(crate) self_close_svg: bool,
+    pub(crate) fragment: bool,
+    pub(crate) deterministic: bool,
+}
+
+impl Default for SsrConfig {
+    fn default() -> Self {
+        Self {
+            whitespace: Whitespace::Preserve,
+            indent: 2,
+            self_close_svg: false,
+            fragment: false,
+            deterministic: false,
+        }
+    }
+}
+
+impl SsrConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set what happens to the whitespace of the app. Defaults to [`Whitespace::Preserve`].
+    pub fn whitespace(mut self, whitespace: Whitespace) -> Self {
+        self.whitespace = whitespace;
+        self
+    }
+
+    /// Set how many spaces every level of [`Whitespace::Pretty`] is indented by. Defaults to 2.
+    pub fn indent(mut self, spaces: usize) -> Self {
+        self.indent = spaces;
+        self
+    }
+
+    /// Write SVG elements without children like `<path d="..."/>` instead of `<path d="..."></path>`, like XHTML
+    /// and SVG files do. Defaults to false.
+    pub fn self_close_svg(mut self, self_close_svg: bool) -> Self {
+        self.self_close_svg = self_close_svg;
+        self
+    }
+
+    /// Only write what's inside the element the app renders at its root, for pages that already have the element to
+    /// put it in. Apps that render more than one node at their root are written whole. Defaults to false.
+    pub fn fragment(mut self, fragment: bool) -> Self {
+        self.fragment = fragment;
+        self
+    }
+
+    /// Write the same html for the same tree every time, for snapshot tests. Defaults to false.
+    ///
+    /// The attributes of every element are sorted by name, whitespace in text is collapsed like
+    /// [`Whitespace::Minified`] does, and the element and scope ids of the VirtualDom are left out, even when
+    /// pre-rendering. Those depend on the order the app was rendered and diffed in, not on what it shows.
+    pub fn deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    /// Whether runs of whitespace in text become a single space
+    pub(crate) fn collapses_whitespace(&self) -> bool {
+        self.whitespace == Whitespace::Minified || self.deterministic
+    }
+}
@@ -3,11 +3,19 @@
 mod cache;
 pub mod config;
 pub mod renderer;
+mod stream;
 pub mod template;
 use dioxus_core::{Element, LazyNodes, Scope, VirtualDom};
+use dioxus_history::{History, MemoryHistory};
+use dioxus_hooks::HeadContext;
 use std::cell::Cell;
+use std::rc::Rc;
 
+pub use crate::config::{SsrConfig, Whitespace};
 pub use crate::renderer::Renderer;
+#[cfg(feature = "tokio")]
+pub use crate::stream::render_stream;
+pub use crate::stream::Shell;
 
 /// A convenience function to render an `rsx!` call to a string
 ///
@@ -45,6 +53,95 @@ pub fn render(dom: &VirtualDom) -> String {
     Renderer::new().render(dom)
 }
 
+/// A convenience function to render an existing VirtualDom to the same string every time, for snapshot tests
+///
+/// The attributes are sorted, the whitespace in text is collapsed and the ids of the VirtualDom are left out, so the
+/// html only changes when what the app shows does. See [`SsrConfig::deterministic`].
+///
+/// ```rust, ignore
+/// let mut dom = VirtualDom::new(app);
+/// _ = dom.rebuild();
+///
+/// insta::assert_snapshot!(dioxus_ssr::render_to_string_deterministic(&dom));
+/// ```
+pub fn render_to_string_deterministic(dom: &VirtualDom) -> String {
+    Renderer::with_config(SsrConfig::new().deterministic(true)).render(dom)
+}
+
+/// Route the app to the path of the request it renders for, before the VirtualDom is rebuilt
+///
+/// The [`Router`](https://docs.rs/dioxus-router) of the app renders the routes that match the path, like it does in
+/// the browser, so a page that's pre-rendered for a path hydrates into the same routes. The path can have a query and a
+/// fragment.
+///
+/// ```rust, ignore
+/// let mut dom = VirtualDom::new(app);
+/// dioxus_ssr::provide_request_path(&dom, "/blog/7?comments=all");
+/// _ = dom.rebuild();
+///
+/// let html = dioxus_ssr::pre_render(&dom);
+/// ```
+pub fn provide_request_path(dom: &VirtualDom, path: &str) {
+    let history: Rc<dyn History> = Rc::new(MemoryHistory::with_initial_path(path));
+    dom.base_scope().provide_context(history);
+}
+
+/// Collect the elements that components add to the `<head>` of the page, before the VirtualDom is rebuilt
+///
+/// The `Title`, `Meta`, `Link` and `Script` components of `dioxus_hooks::head`, and anything else that calls
+/// `use_head`, add their elements to it as they render. Once the app is rendered, [`render_head`] renders them.
+///
+/// ```rust, ignore
+/// let mut dom = VirtualDom::new(app);
+/// dioxus_ssr::provide_head(&dom);
+/// _ = dom.rebuild();
+///
+/// let body = dioxus_ssr::pre_render(&dom);
+/// let page = format!(
+///     "<!DOCTYPE html><html><head>{}</head><body><div id=\"main\">{}</div></body></html>",
+///     dioxus_ssr::render_head(&dom),
+///     body,
+/// );
+/// ```
+pub fn provide_head(dom: &VirtualDom) {
+    dom.base_scope().provide_context(HeadContext::default());
+}
+
+/// Render the elements the app added to the `<head>` of the page, deduplicated, for the head of the page
+///
+/// Returns nothing unless [`provide_head`] was called before the VirtualDom was rebuilt. Everything but the title
+/// carries its dedupe key in `data-dioxus-head`, so the web renderer takes the elements over when the page hydrates
+/// instead of adding them again.
+pub fn render_head(dom: &VirtualDom) -> String {
+    dom.base_scope()
+        .has_context::<HeadContext>()
+        .map(|head| head.to_html())
+        .unwrap_or_default()
+}
+
+/// The attribute that marks the subtrees of a page the client makes interactive, naming the island they belong to
+///
+/// `dioxus_web::mount_islands` only boots VirtualDoms for the elements with this attribute. Everything else on the page
+/// is left as the static html the server rendered. Besides [`render_island`], server components can mark islands
+/// themselves with `div { "data-dioxus-island": "cart", ... }`.
+pub const ISLAND_ATTRIBUTE: &str = "data-dioxus-island";
+
+/// A convenience function to render an existing VirtualDom as an island of a mostly static page
+///
+/// The VirtualDom is usually made from the same component the client boots for the island's name.
+///
+/// ```rust, ignore
+/// let mut cart = VirtualDom::new(Cart);
+/// _ = cart.rebuild();
+///
+/// let page = format!("<main>{}{}</main>", article_html, dioxus_ssr::render_island("cart", &cart));
+/// ```
+pub fn render_island(name: &str, dom: &VirtualDom) -> String {
+    let mut buf = String::new();
+    Renderer::new().render_island(&mut buf, name, dom).unwrap();
+    buf
+}
+
 /// A convenience function to pre-render an existing VirtualDom to a string
 ///
 /// We generally recommend creating a new `Renderer` to take advantage of template caching.
@@ -53,3 +150,37 @@ pub fn pre_render(dom: &VirtualDom) -> String {
     renderer.pre_render = true;
     renderer.render(dom)
 }
+
+/// The attribute that pre-rendered elements with dynamic attributes or listeners carry their element id in
+///
+/// Pre-rendering also marks dynamic text with `<!--node-id{id}-->` before it and `<!--#-->` after it, placeholders with
+/// `<!--placeholder{id}-->`, and every component with `<!--dx-scope{id}-->` and `<!--/dx-scope{id}-->` around its
+/// html, so the client can find the nodes of the VirtualDom in the page instead of assuming the page is laid out
+/// exactly like its own render.
+pub const HYDRATION_ATTRIBUTE: &str = "data-node-hydration";
+
+/// The id of the script element [`render_hydration_state`] writes
+pub const HYDRATION_STATE_ID: &str = "dioxus-hydration-state";
+
+/// Serialize the props or state the server rendered the page with into a script element, for the client to start from
+///
+/// The client reads it back with `dioxus_web::hydration_state`, so it renders from the same data instead of fetching
+/// it again and hoping it didn't change in the meantime.
+///
+/// ```rust, ignore
+/// let page = format!(
+///     "<div id=\"main\">{}</div>{}",
+///     dioxus_ssr::pre_render(&dom),
+///     dioxus_ssr::render_hydration_state(&props)?,
+/// );
+/// ```
+pub fn render_hydration_state<T: serde::Serialize>(state: &T) -> serde_json::Result<String> {
+    let json = serde_json::to_string(state)?;
+
+    // the json can't end the script early, and `<\/` is still `</` to a json parser
+    Ok(format!(
+        "<script type=\"application/json\" id=\"{}\">{}</script>",
+        HYDRATION_STATE_ID,
+        json.replace("</", "<\\/")
+    ))
+}
@@ -1,6 +1,9 @@
 use super::cache::Segment;
-use crate::cache::StringCache;
-use dioxus_core::{prelude::*, AttributeValue, DynamicNode, RenderReturn};
+use crate::cache::{collapse_whitespace, is_boolean_attribute, StringCache};
+use crate::config::{SsrConfig, Whitespace};
+use dioxus_core::{
+    prelude::*, Attribute, AttributeValue, DynamicNode, RenderReturn, SuspenseContext,
+};
 use std::collections::HashMap;
 use std::fmt::Write;
 use std::rc::Rc;
@@ -8,11 +11,8 @@ use std::rc::Rc;
 /// A virtualdom renderer that caches the templates it has seen for faster rendering
 #[derive(Default)]
 pub struct Renderer {
-    /// should we do our best to prettify the output?
-    pub pretty: bool,
-
-    /// Control if elements are written onto a new line
-    pub newline: bool,
+    /// How the html is laid out
+    config: SsrConfig,
 
     /// Should we sanitize text nodes? (escape HTML)
     pub sanitize: bool,
@@ -24,8 +24,23 @@ pub struct Renderer {
     // Don't proceed onto new components. Instead, put the name of the component.
     pub skip_components: bool,
 
-    /// A cache of templates that have been rendered
-    template_cache: HashMap<&'static str, Rc<StringCache>>,
+    /// A cache of templates that have been rendered, and whether they were rendered inside of an element like `pre`
+    template_cache: HashMap<(&'static str, bool), Rc<StringCache>>,
+
+    /// How deep the template that's being rendered is, for pretty html
+    depth: usize,
+
+    /// Whether the template that's being rendered is inside of an element that keeps its whitespace
+    preformatted: bool,
+
+    /// Whether nothing was written yet, so pretty html doesn't start with an empty line
+    fresh: bool,
+
+    /// Whether the next template is the root of the app, which fragment mode leaves out
+    skip_root: bool,
+
+    /// The suspense boundaries that were written with their fallback while streaming, to be filled in later
+    pub(crate) suspended: Option<Vec<ScopeId>>,
 }
 
 impl Renderer {
@@ -33,14 +48,58 @@ impl Renderer {
         Self::default()
     }
 
+    /// Create a renderer that lays out its html like the config says
+    pub fn with_config(config: SsrConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    pub fn config(&self) -> &SsrConfig {
+        &self.config
+    }
+
+    /// Change how the html is laid out, which throws away the templates cached for the old config
+    pub fn set_config(&mut self, config: SsrConfig) {
+        self.config = config;
+        self.template_cache.clear();
+    }
+
     pub fn render(&mut self, dom: &VirtualDom) -> String {
         let mut buf = String::new();
         self.render_to(&mut buf, dom).unwrap();
         buf
     }
 
+    /// Render the VirtualDom into `buf`
+    ///
+    /// Suspense boundaries that are still suspended are rendered with their fallback. To send their children once they
+    /// resolve, stream the page with [`Renderer::render_stream`] instead.
     pub fn render_to(&mut self, buf: &mut impl Write, dom: &VirtualDom) -> std::fmt::Result {
-        self.render_scope(buf, dom, ScopeId(0))
+        self.fresh = true;
+        self.skip_root = self.config.fragment;
+        self.render_component(buf, dom, ScopeId(0))
+    }
+
+    /// Render the VirtualDom of an island, wrapped in the element that marks it for `dioxus_web::mount_islands`
+    ///
+    /// The name picks the component the client boots for the island. Names are plain identifiers, like `cart`.
+    pub fn render_island(
+        &mut self,
+        buf: &mut impl Write,
+        name: &str,
+        dom: &VirtualDom,
+    ) -> std::fmt::Result {
+        debug_assert!(
+            !name.contains(|c: char| c == '"' || c == '<' || c == '>' || c == '&'),
+            "the name of an island can't contain html syntax, found {:?}",
+            name
+        );
+
+        write!(buf, "<div {}=\"{}\">", crate::ISLAND_ATTRIBUTE, name)?;
+        self.render_to(buf, dom)?;
+        write!(buf, "</div>")
     }
 
     pub fn render_scope(
@@ -58,78 +117,287 @@ impl Renderer {
         Ok(())
     }
 
+    /// Render a component, or the fallback of a suspense boundary that's still suspended
+    ///
+    /// While streaming, the fallback is wrapped in markers so the children can take its place once they resolve.
+    pub(crate) fn render_component(
+        &mut self,
+        buf: &mut impl Write,
+        dom: &VirtualDom,
+        id: ScopeId,
+    ) -> std::fmt::Result {
+        let scope = dom.get_scope(id).unwrap();
+
+        let boundary = scope
+            .has_context::<Rc<SuspenseContext>>()
+            .filter(|boundary| boundary.is_suspended());
+        if let Some(boundary) = boundary {
+            match self.suspended.as_mut() {
+                Some(suspended) => {
+                    suspended.push(id);
+                    write!(buf, "<!--ds:{}-->", id.0)?;
+                    if let Some(fallback) = boundary.fallback_scope() {
+                        self.render_component(buf, dom, fallback)?;
+                    }
+                    return write!(buf, "<!--/ds:{}-->", id.0);
+                }
+                // Without streaming the fallback is rendered in place of the boundary for good. A boundary without
+                // a fallback renders its children below, where the suspended ones leave nothing behind
+                None => {
+                    if let Some(fallback) = boundary.fallback_scope() {
+                        return self.render_component(buf, dom, fallback);
+                    }
+                }
+            }
+        }
+
+        if self.writes_ids() {
+            write!(buf, "<!--dx-scope{}-->", id.0)?;
+            self.render_root(buf, dom, scope.root_node())?;
+            return write!(buf, "<!--/dx-scope{}-->", id.0);
+        }
+
+        self.render_root(buf, dom, scope.root_node())
+    }
+
+    fn render_root(
+        &mut self,
+        buf: &mut impl Write,
+        dom: &VirtualDom,
+        node: &RenderReturn,
+    ) -> std::fmt::Result {
+        match node {
+            RenderReturn::Ready(node) => self.render_template(buf, dom, node),
+            // a component that errored or is still suspended leaves a placeholder
+            RenderReturn::Aborted(placeholder) => {
+                if self.writes_ids() {
+                    write!(buf, "<!--placeholder{}-->", element_id(&placeholder.id))?;
+                }
+                Ok(())
+            }
+            RenderReturn::Pending(_) => Ok(()),
+        }
+    }
+
     fn render_template(
         &mut self,
         buf: &mut impl Write,
         dom: &VirtualDom,
         template: &VNode,
     ) -> std::fmt::Result {
+        let config = &self.config;
+        let preformatted = self.preformatted;
         let entry = self
             .template_cache
-            .entry(template.template.get().name)
-            .or_insert_with(|| Rc::new(StringCache::from_template(template).unwrap()))
+            .entry((template.template.get().name, preformatted))
+            .or_insert_with(|| {
+                Rc::new(StringCache::from_template(template, config, preformatted).unwrap())
+            })
             .clone();
 
-        for segment in entry.segments.iter() {
+        // in fragment mode, the root element of the app is left out and only its contents are written
+        let skip_root = std::mem::take(&mut self.skip_root);
+        let ((first, start), (last, end)) = match entry.contents {
+            Some(contents) if skip_root => contents,
+            _ => ((0, 0), (entry.segments.len(), 0)),
+        };
+        // an app that only renders a component has the root element of that component
+        if let [TemplateNode::Dynamic { id }] = template.template.get().roots {
+            if let DynamicNode::Component(_) = template.dynamic_nodes[*id] {
+                self.skip_root = skip_root;
+            }
+        }
+
+        // the dynamic attributes of an element are next to each other, and it only needs its id once
+        let mut in_dynamic_attrs = false;
+
+        for (position, segment) in entry.segments.iter().enumerate() {
+            if position < first || position > last || (position == last && end == 0) {
+                continue;
+            }
+
+            let was_in_dynamic_attrs =
+                std::mem::replace(&mut in_dynamic_attrs, matches!(segment, Segment::Attr(_)));
             match segment {
                 Segment::Attr(idx) => {
                     let attr = &template.dynamic_attrs[*idx];
-                    match attr.value {
-                        AttributeValue::Text(value) => write!(buf, " {}=\"{}\"", attr.name, value)?,
-                        AttributeValue::Bool(value) => write!(buf, " {}={}", attr.name, value)?,
-                        _ => {}
-                    };
+                    if self.writes_ids() && !was_in_dynamic_attrs {
+                        write!(
+                            buf,
+                            " {}=\"{}\"",
+                            crate::HYDRATION_ATTRIBUTE,
+                            attr.mounted_element.get().0
+                        )?;
+                    }
+                    for attr in spread(attr) {
+                        write_attribute(buf, attr)?;
+                    }
                 }
-                Segment::Node(idx) => match &template.dynamic_nodes[*idx] {
-                    DynamicNode::Component(node) => {
-                        if self.skip_components {
-                            write!(buf, "<{}><{}/>", node.name, node.name)?;
-                        } else {
-                            let id = node.scope.get().unwrap();
-                            let scope = dom.get_scope(id).unwrap();
-                            let node = scope.root_node();
-                            match node {
-                                RenderReturn::Ready(node) => {
-                                    self.render_template(buf, dom, node)?
-                                }
-                                _ => todo!(
-                                    "generally, scopes should be sync, only if being traversed"
-                                ),
-                            }
+                Segment::SortedAttrs { statics, dynamic } => {
+                    let mut attrs: Vec<(&str, String)> = statics
+                        .iter()
+                        .map(|(name, html)| (name.as_str(), html.clone()))
+                        .collect();
+                    for idx in dynamic {
+                        for attr in spread(&template.dynamic_attrs[*idx]) {
+                            let mut html = String::new();
+                            write_attribute(&mut html, attr)?;
+                            attrs.push((attr.name, html));
                         }
                     }
-                    DynamicNode::Text(text) => {
-                        // in SSR, we are concerned that we can't hunt down the right text node since they might get merged
-                        if self.pre_render {
-                            write!(buf, "<!--#-->")?;
-                        }
-
-                        // todo: escape the text
-                        write!(buf, "{}", text.value)?;
-
-                        if self.pre_render {
-                            write!(buf, "<!--#-->")?;
-                        }
+                    // attributes with the same name stay in the order the app gave them in
+                    attrs.sort_by(|a, b| a.0.cmp(b.0));
+                    for (_, html) in attrs {
+                        buf.write_str(&html)?;
                     }
-                    DynamicNode::Fragment(nodes) => {
-                        for child in *nodes {
-                            self.render_template(buf, dom, child)?;
+                }
+                Segment::InnerHtml(idx) => {
+                    for attr in spread(&template.dynamic_attrs[*idx]) {
+                        if let ("dangerous_inner_html", AttributeValue::Text(value)) =
+                            (attr.name, &attr.value)
+                        {
+                            write!(buf, "{}", value)?;
                         }
                     }
+                }
+                Segment::Node(idx) => self.render_node(buf, dom, template, *idx)?,
+                Segment::FormattedNode {
+                    idx,
+                    depth,
+                    preformatted,
+                } => {
+                    // the templates of the node are laid out from where it is in this one
+                    let depth = std::mem::replace(&mut self.depth, self.depth + depth);
+                    let was_preformatted = std::mem::replace(
+                        &mut self.preformatted,
+                        self.preformatted || *preformatted,
+                    );
+                    let rendered = self.render_node(buf, dom, template, *idx);
+                    self.depth = depth;
+                    self.preformatted = was_preformatted;
+                    rendered?
+                }
 
-                    DynamicNode::Placeholder(_el) => {
-                        if self.pre_render {
-                            write!(buf, "<pre><pre/>")?;
-                        }
-                    }
-                },
+                Segment::Newline(depth) => self.newline(buf, *depth)?,
+
+                Segment::PreRendered(contents) => {
+                    let from = if position == first { start } else { 0 };
+                    let to = if position == last {
+                        end
+                    } else {
+                        contents.len()
+                    };
+                    write!(buf, "{}", &contents[from..to])?
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_node(
+        &mut self,
+        buf: &mut impl Write,
+        dom: &VirtualDom,
+        template: &VNode,
+        idx: usize,
+    ) -> std::fmt::Result {
+        match &template.dynamic_nodes[idx] {
+            DynamicNode::Component(node) => {
+                if self.skip_components {
+                    write!(buf, "<{}><{}/>", node.name, node.name)?;
+                } else {
+                    self.render_component(buf, dom, node.scope.get().unwrap())?;
+                }
+            }
+            DynamicNode::Text(text) => {
+                // in SSR, we are concerned that we can't hunt down the right text node since they might get merged
+                if self.writes_ids() {
+                    write!(buf, "<!--node-id{}-->", element_id(&text.id))?;
+                }
+
+                // todo: escape the text
+                if self.config.collapses_whitespace() && !self.preformatted {
+                    write!(buf, "{}", collapse_whitespace(text.value))?
+                } else {
+                    write!(buf, "{}", text.value)?
+                }
 
-                Segment::PreRendered(contents) => write!(buf, "{}", contents)?,
+                if self.writes_ids() {
+                    write!(buf, "<!--#-->")?;
+                }
+            }
+            DynamicNode::Fragment(nodes) => {
+                for child in *nodes {
+                    self.render_template(buf, dom, child)?;
+                }
+            }
+
+            DynamicNode::Placeholder(placeholder) => {
+                if self.writes_ids() {
+                    if self.config.whitespace == Whitespace::Pretty {
+                        self.newline(buf, 0)?;
+                    }
+                    write!(buf, "<!--placeholder{}-->", element_id(&placeholder.id))?;
+                }
             }
         }
 
         Ok(())
     }
+
+    /// Whether the ids of the VirtualDom are written into the html for hydration
+    fn writes_ids(&self) -> bool {
+        self.pre_render && !self.config.deterministic
+    }
+
+    /// Start a new line for pretty html, indented by this many levels more than the template that's being rendered
+    fn newline(&mut self, buf: &mut impl Write, depth: usize) -> std::fmt::Result {
+        if self.preformatted {
+            return Ok(());
+        }
+
+        // the html starts on the first line
+        if !std::mem::take(&mut self.fresh) {
+            writeln!(buf)?;
+        }
+        let indent = (self.depth + depth) * self.config.indent;
+        write!(buf, "{:indent$}", "", indent = indent)
+    }
+}
+
+/// The attributes a dynamic attribute stands for, which are more than one if it's spread
+fn spread<'a>(attr: &'a Attribute<'a>) -> &'a [Attribute<'a>] {
+    match attr.value {
+        AttributeValue::Spread(spread) => spread,
+        _ => std::slice::from_ref(attr),
+    }
+}
+
+fn write_attribute(buf: &mut impl Write, attr: &Attribute) -> std::fmt::Result {
+    match attr.value {
+        // the inner html is written as the contents of the element instead
+        _ if attr.name == "dangerous_inner_html" => Ok(()),
+        // browsers only look at whether boolean attributes are there
+        AttributeValue::Bool(value) if is_boolean_attribute(attr.name) => match value {
+            true => write!(buf, " {}", attr.name),
+            false => Ok(()),
+        },
+        AttributeValue::Text(value) if is_boolean_attribute(attr.name) => match value {
+            "false" => Ok(()),
+            _ => write!(buf, " {}", attr.name),
+        },
+        AttributeValue::Text(value) => write!(buf, " {}=\"{}\"", attr.name, value),
+        AttributeValue::Bool(value) => write!(buf, " {}=\"{}\"", attr.name, value),
+        AttributeValue::Int(value) => write!(buf, " {}=\"{}\"", attr.name, value),
+        AttributeValue::Float(value) => write!(buf, " {}=\"{}\"", attr.name, value),
+        _ => Ok(()),
+    }
+}
+
+/// The id of a node for the hydration markers, which is 0 for nodes that were never mounted
+fn element_id(id: &std::cell::Cell<Option<ElementId>>) -> usize {
+    id.get().map_or(0, |id| id.0)
 }
 
 #[test]
@@ -165,7 +433,9 @@ fn to_string_works() {
                 vec![
                     PreRendered("<div class=\"asdasdasd\" class=\"asdasdasd\"".into(),),
                     Attr(0,),
-                    PreRendered(">Hello world 1 -->".into(),),
+                    PreRendered(">".into(),),
+                    InnerHtml(0,),
+                    PreRendered("Hello world 1 -->".into(),),
                     Node(0,),
                     PreRendered(
                         "<-- Hello world 2<div>nest 1</div><div></div><div>nest 2</div>".into(),
@@ -0,0 +1,163 @@
+use crate::Renderer;
+use dioxus_core::{ScopeId, SuspenseContext, VirtualDom};
+use futures_util::io::{AsyncWrite, AsyncWriteExt};
+use std::io;
+use std::rc::Rc;
+#[cfg(feature = "tokio")]
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Swaps the contents of a resolved suspense boundary in for the fallback between its markers
+const RESOLVE_SCRIPT: &str = r#"<script>window.__dioxusResolve=function(id){var t=document.querySelector('template[data-dioxus-resolved="'+id+'"]'),w=document.createTreeWalker(document,NodeFilter.SHOW_COMMENT),s=null;while(w.nextNode()){if(w.currentNode.data==="ds:"+id){s=w.currentNode;break}}if(t&&s){while(s.nextSibling&&!(s.nextSibling.nodeType===8&&s.nextSibling.data==="/ds:"+id)){s.nextSibling.remove()}if(s.nextSibling){s.nextSibling.remove()}s.replaceWith(t.content);t.remove()}}</script>"#;
+
+/// The html around the app in a streamed page
+///
+/// ```rust, ignore
+/// let shell = Shell::new(
+///     "<!DOCTYPE html><html><head><title>Dioxus</title></head><body><div id=\"main\">",
+///     "</div></body></html>",
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Shell {
+    /// Everything before the app, like the doctype, the head and the opening tags of the body
+    pub head: String,
+
+    /// Everything after the app
+    pub tail: String,
+}
+
+impl Shell {
+    pub fn new(head: impl Into<String>, tail: impl Into<String>) -> Self {
+        Self {
+            head: head.into(),
+            tail: tail.into(),
+        }
+    }
+}
+
+impl Renderer {
+    /// Render a VirtualDom that wasn't rebuilt yet into `out`, flushing the html as soon as each part of it is ready
+    ///
+    /// The head of the shell is flushed before the app runs, so the browser can start on the styles and scripts of
+    /// the page, and the app follows as soon as it's rebuilt. Suspense boundaries that are still suspended are written
+    /// with their fallback, and their contents are streamed after the rest of the app as they resolve, along with a
+    /// script that swaps them in. The tail of the shell is written once nothing is suspended anymore.
+    pub async fn render_stream<W: AsyncWrite + Unpin>(
+        &mut self,
+        dom: &mut VirtualDom,
+        shell: &Shell,
+        out: &mut W,
+    ) -> io::Result<()> {
+        out.write_all(shell.head.as_bytes()).await?;
+        out.flush().await?;
+
+        _ = dom.rebuild();
+
+        self.suspended = Some(Vec::new());
+        let mut buf = String::new();
+        self.render_component(&mut buf, dom, ScopeId(0)).unwrap();
+        let mut suspended = self.suspended.replace(Vec::new()).unwrap_or_default();
+        if !suspended.is_empty() {
+            buf.push_str(RESOLVE_SCRIPT);
+        }
+        out.write_all(buf.as_bytes()).await?;
+        out.flush().await?;
+
+        while !suspended.is_empty() && dom.has_suspended_work() {
+            dom.wait_for_work().await;
+            _ = dom.render_immediate();
+
+            let mut waiting = Vec::new();
+            for id in suspended.drain(..) {
+                let boundary = match dom.get_scope(id) {
+                    Some(scope) => scope.has_context::<Rc<SuspenseContext>>(),
+                    // the boundary was removed before it resolved
+                    None => continue,
+                };
+                if boundary.map_or(false, |boundary| boundary.is_suspended()) {
+                    waiting.push(id);
+                    continue;
+                }
+
+                // the boundary resolved, so its contents take the place of its fallback
+                buf.clear();
+                buf.push_str(&format!("<template data-dioxus-resolved=\"{}\">", id.0));
+                self.render_scope(&mut buf, dom, id).unwrap();
+                buf.push_str(&format!(
+                    "</template><script>__dioxusResolve({})</script>",
+                    id.0
+                ));
+                out.write_all(buf.as_bytes()).await?;
+                out.flush().await?;
+            }
+
+            // boundaries inside the resolved contents might still be suspended themselves
+            waiting.extend(self.suspended.replace(Vec::new()).unwrap_or_default());
+            suspended = waiting;
+        }
+        self.suspended = None;
+
+        out.write_all(shell.tail.as_bytes()).await?;
+        out.flush().await
+    }
+}
+
+/// Render an app into a stream of html that's ready to be the body of a response, like
+/// `axum::body::StreamBody::new(dioxus_ssr::render_stream(app, (), shell))`
+///
+/// VirtualDoms can't move between threads, so the app renders on a blocking thread of the tokio runtime that runs
+/// its tasks on the side. Rendering stops when the stream is dropped, like when the client goes away.
+#[cfg(feature = "tokio")]
+pub fn render_stream<P: Send + 'static>(
+    app: dioxus_core::Component<P>,
+    props: P,
+    shell: Shell,
+) -> impl futures_util::Stream<Item = io::Result<Vec<u8>>> + Send + 'static {
+    let (chunks, rx) = futures_channel::mpsc::unbounded();
+    let runtime = tokio::runtime::Handle::current();
+
+    tokio::task::spawn_blocking(move || {
+        let local = tokio::task::LocalSet::new();
+        runtime.block_on(local.run_until(async move {
+            let mut dom = VirtualDom::new_with_props(app, props);
+            let mut out = ChunkWriter(chunks.clone());
+            if let Err(err) = Renderer::new()
+                .render_stream(&mut dom, &shell, &mut out)
+                .await
+            {
+                let _ = chunks.unbounded_send(Err(err));
+            }
+        }));
+    });
+
+    rx
+}
+
+/// Sends every write as a chunk of the stream of [`render_stream`]
+#[cfg(feature = "tokio")]
+struct ChunkWriter(futures_channel::mpsc::UnboundedSender<io::Result<Vec<u8>>>);
+
+#[cfg(feature = "tokio")]
+impl AsyncWrite for ChunkWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(match self.0.unbounded_send(Ok(buf.to_vec())) {
+            Ok(()) => Ok(buf.len()),
+            Err(_) => Err(io::ErrorKind::BrokenPipe.into()),
+        })
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
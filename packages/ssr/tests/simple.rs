@@ -74,3 +74,354 @@ fn fragments() {
         "<div></div>"
     );
 }
+
+#[test]
+fn inner_html() {
+    let html = "<b>bold</b>";
+    assert_eq!(
+        dioxus_ssr::render_lazy(rsx! {
+            div { dangerous_inner_html: "<i>static</i>" }
+            div { dangerous_inner_html: "{html}" }
+            article { RawHtml::new(html) }
+        }),
+        "<div><i>static</i></div><div><b>bold</b></div><article><div style=\"display: contents\"><b>bold</b></div></article>"
+    );
+}
+
+#[test]
+fn shadow_roots_render_their_children_in_place() {
+    fn widget(cx: Scope) -> Element {
+        render! {
+            ShadowRoot::new(render! { b { "bold" } }).stylesheet("b { color: red; }")
+        }
+    }
+
+    let mut dom = VirtualDom::new(widget);
+    _ = dom.rebuild();
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<div dioxus-shadow-root=\"open\" style=\"display: contents\" dioxus-shadow-styles=\"b { color: red; }\"><b>bold</b></div>"
+    );
+}
+
+#[test]
+fn islands() {
+    fn cart(cx: Scope) -> Element {
+        render! { button { "checkout" } }
+    }
+
+    let mut dom = VirtualDom::new(cart);
+    _ = dom.rebuild();
+
+    assert_eq!(
+        dioxus_ssr::render_island("cart", &dom),
+        "<div data-dioxus-island=\"cart\"><button>checkout</button></div>"
+    );
+}
+
+#[test]
+fn mathml() {
+    assert_eq!(
+        dioxus_ssr::render_lazy(rsx! {
+            math { display: "block",
+                msup {
+                    mi { "x" }
+                    mn { "2" }
+                }
+            }
+        }),
+        r#"<math xmlns="http://www.w3.org/1998/Math/MathML" display="block"><msup><mi>x</mi><mn>2</mn></msup></math>"#
+    );
+}
+
+#[test]
+fn streams_suspended_boundaries() {
+    fn app(cx: Scope) -> Element {
+        render! {
+            div {
+                SuspenseBoundary {
+                    fallback: |cx| cx.render(rsx!( "Loading..." )),
+                    async_child {}
+                }
+            }
+        }
+    }
+
+    async fn async_child(cx: Scope<'_>) -> Element {
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        cx.render(rsx!( p { "Done!" } ))
+    }
+
+    let html = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let mut dom = VirtualDom::new(app);
+            let shell = dioxus_ssr::Shell::new("<body>", "</body>");
+            let mut out = Vec::new();
+            dioxus_ssr::Renderer::new()
+                .render_stream(&mut dom, &shell, &mut out)
+                .await
+                .unwrap();
+            String::from_utf8(out).unwrap()
+        });
+
+    // the fallback goes out with the rest of the page, and the children follow once they resolve
+    assert!(html.starts_with("<body><div><!--ds:1-->Loading...<!--/ds:1--></div><script>"));
+    assert!(html.ends_with(
+        "<template data-dioxus-resolved=\"1\"><p>Done!</p></template><script>__dioxusResolve(1)</script></body>"
+    ));
+}
+
+#[test]
+fn renders_fallbacks_of_suspended_boundaries() {
+    fn app(cx: Scope) -> Element {
+        render! {
+            div {
+                SuspenseBoundary {
+                    fallback: |cx| cx.render(rsx!( "Loading..." )),
+                    async_child {}
+                }
+                "ready"
+            }
+        }
+    }
+
+    async fn async_child(cx: Scope<'_>) -> Element {
+        std::future::pending::<()>().await;
+        cx.render(rsx!( p { "Done!" } ))
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(dioxus_ssr::render(&dom), "<div>Loading...ready</div>");
+}
+
+#[test]
+fn pre_renders_hydration_markers() {
+    fn app(cx: Scope) -> Element {
+        let name = "world";
+        render! {
+            div { class: "{name}",
+                "hello {name}"
+                if false {
+                    rsx!( "goodbye" )
+                }
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let html = dioxus_ssr::pre_render(&dom);
+
+    // the element with the dynamic attribute carries its id, and every dynamic node is marked with its own
+    assert!(html.starts_with("<!--dx-scope0--><div data-node-hydration=\"1\" class=\"world\">"));
+    assert!(html.contains("<!--node-id2-->hello world<!--#-->"));
+    assert!(html.contains("<!--placeholder3-->"));
+    assert!(html.ends_with("</div><!--/dx-scope0-->"));
+
+    // without pre-rendering there are no markers
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<div class=\"world\">hello world</div>"
+    );
+}
+
+#[test]
+fn renders_hydration_state() {
+    let state = vec!["</script>", "done"];
+
+    assert_eq!(
+        dioxus_ssr::render_hydration_state(&state).unwrap(),
+        "<script type=\"application/json\" id=\"dioxus-hydration-state\">[\"<\\/script>\",\"done\"]</script>"
+    );
+}
+
+#[test]
+fn pretty_prints() {
+    fn app(cx: Scope) -> Element {
+        render! {
+            div {
+                h1 { "Title" }
+                pre { "\n  keep\n  this" }
+                input { r#type: "checkbox", disabled: true }
+                ul { (0..2).map(|i| rsx!( li { "{i}" } )) }
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let config = dioxus_ssr::SsrConfig::new().whitespace(dioxus_ssr::Whitespace::Pretty);
+    let html = dioxus_ssr::Renderer::with_config(config).render(&dom);
+
+    // the contents of the pre are left alone, along with the newline it starts with
+    assert_eq!(
+        html,
+        "<div>\n  <h1>Title</h1>\n  <pre>\n\n  keep\n  this</pre>\n  <input type=\"checkbox\" disabled/>\n  <ul>\n    <li>0</li>\n    <li>1</li>\n  </ul>\n</div>"
+    );
+}
+
+#[test]
+fn minifies() {
+    fn app(cx: Scope) -> Element {
+        let name = "the   world";
+        render! {
+            div {
+                "  hello   {name}  "
+                pre { "  a  b  " }
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let config = dioxus_ssr::SsrConfig::new().whitespace(dioxus_ssr::Whitespace::Minified);
+    let html = dioxus_ssr::Renderer::with_config(config).render(&dom);
+
+    assert_eq!(html, "<div> hello the world <pre>  a  b  </pre></div>");
+}
+
+#[test]
+fn boolean_attributes() {
+    fn app(cx: Scope) -> Element {
+        render! {
+            input {
+                r#type: "checkbox",
+                hidden: "false",
+                checked: false,
+                disabled: true,
+                draggable: true,
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<input type=\"checkbox\" disabled draggable=\"true\"/>"
+    );
+}
+
+#[test]
+fn self_closes_svg() {
+    fn app(cx: Scope) -> Element {
+        render! {
+            svg { path { d: "M 0 0 L 10 10" } }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<svg><path d=\"M 0 0 L 10 10\"></path></svg>"
+    );
+
+    let config = dioxus_ssr::SsrConfig::new().self_close_svg(true);
+    assert_eq!(
+        dioxus_ssr::Renderer::with_config(config).render(&dom),
+        "<svg><path d=\"M 0 0 L 10 10\"/></svg>"
+    );
+}
+
+#[test]
+fn fragments_skip_the_root_element() {
+    fn app(cx: Scope) -> Element {
+        render! { child {} }
+    }
+
+    fn child(cx: Scope) -> Element {
+        let count = 1;
+        render! {
+            div { id: "root",
+                "count: {count}"
+                p { "hello" }
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let config = dioxus_ssr::SsrConfig::new().fragment(true);
+    assert_eq!(
+        dioxus_ssr::Renderer::with_config(config).render(&dom),
+        "count: 1<p>hello</p>"
+    );
+}
+
+#[test]
+fn deterministic_snapshots() {
+    fn app(cx: Scope) -> Element {
+        let class = "card";
+        render! {
+            div { title: "hi", id: "main", class: "{class}", onclick: |_| {},
+                "  some\n   text  "
+                pre { "  kept\n" }
+                input { r#type: "checkbox", disabled: false, checked: true }
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let expected = "<div class=\"card\" id=\"main\" title=\"hi\"> some text <pre>  kept\n</pre><input checked type=\"checkbox\"/></div>";
+    assert_eq!(dioxus_ssr::render_to_string_deterministic(&dom), expected);
+
+    // pre-rendering doesn't add the ids of the VirtualDom
+    let mut renderer =
+        dioxus_ssr::Renderer::with_config(dioxus_ssr::SsrConfig::new().deterministic(true));
+    renderer.pre_render = true;
+    assert_eq!(renderer.render(&dom), expected);
+}
+
+#[test]
+fn head_elements() {
+    use dioxus::hooks::head::*;
+
+    fn app(cx: Scope) -> Element {
+        render! {
+            Title { "App" }
+            Meta { name: "description", content: "An app" }
+            Link { rel: "stylesheet", href: "/app.css" }
+            post {}
+        }
+    }
+
+    fn post(cx: Scope) -> Element {
+        let title = "Fish & chips";
+        render! {
+            Title { "{title} - App" }
+            Meta { name: "description", content: "A post" }
+            Script { src: "/comments.js", defer: true }
+            article { "A post" }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dioxus_ssr::provide_head(&dom);
+    _ = dom.rebuild();
+
+    assert_eq!(
+        dioxus_ssr::render_head(&dom),
+        concat!(
+            "<title>Fish &amp; chips - App</title>",
+            "<meta name=\"description\" content=\"A post\" data-dioxus-head=\"meta:name=description\">",
+            "<link rel=\"stylesheet\" href=\"/app.css\" data-dioxus-head=\"link:stylesheet:/app.css\">",
+            "<script src=\"/comments.js\" defer=\"\" data-dioxus-head=\"script:/comments.js\"></script>",
+        )
+    );
+    assert!(dioxus_ssr::render(&dom).contains("<article>A post</article>"));
+}
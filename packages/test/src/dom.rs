@@ -0,0 +1,479 @@
+//! A copy of the tree the VirtualDom rendered, kept up to date with the mutations it sends out
+
+use dioxus_core::{
+    BorrowedAttributeValue, ElementId, Template, TemplateAttribute, TemplateNode, WriteMutations,
+};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// A node in the tree of a [`crate::Tester`]
+///
+/// The id stays the same for as long as the node is in the tree. Once the node is removed, the id doesn't point at any
+/// node again, even if the VirtualDom reuses the [`ElementId`] of the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(pub(crate) usize);
+
+#[derive(Debug, Clone)]
+pub(crate) enum NodeKind {
+    Element {
+        tag: String,
+        namespace: Option<String>,
+        /// The name, namespace and value of every attribute, in the order they were set
+        attributes: Vec<(String, Option<String>, String)>,
+        listeners: Vec<String>,
+    },
+    Text(String),
+    Placeholder,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct DomNode {
+    pub(crate) kind: NodeKind,
+    pub(crate) parent: Option<NodeId>,
+    pub(crate) children: Vec<NodeId>,
+    pub(crate) element: Option<ElementId>,
+}
+
+pub(crate) struct TestDom {
+    // removed nodes leave a hole behind, so old ids never point at new nodes
+    nodes: Vec<Option<DomNode>>,
+    elements: Vec<Option<NodeId>>,
+    templates: HashMap<String, Vec<NodeId>>,
+    stack: Vec<NodeId>,
+}
+
+impl TestDom {
+    pub(crate) fn new() -> Self {
+        let mut dom = Self {
+            nodes: Vec::new(),
+            elements: Vec::new(),
+            templates: HashMap::new(),
+            stack: Vec::new(),
+        };
+
+        // the VirtualDom mounts the app into the element with the first id
+        let root = dom.create_node(NodeKind::Element {
+            tag: "div".to_string(),
+            namespace: None,
+            attributes: Vec::new(),
+            listeners: Vec::new(),
+        });
+        dom.set_element(root, ElementId(0));
+        dom
+    }
+
+    pub(crate) fn root(&self) -> NodeId {
+        NodeId(0)
+    }
+
+    pub(crate) fn get(&self, id: NodeId) -> Option<&DomNode> {
+        self.nodes.get(id.0).and_then(Option::as_ref)
+    }
+
+    fn get_mut(&mut self, id: NodeId) -> &mut DomNode {
+        self.nodes[id.0]
+            .as_mut()
+            .expect("the mutations refer to a node that was removed")
+    }
+
+    /// The node the VirtualDom knows by the id
+    fn node(&self, id: ElementId) -> NodeId {
+        self.elements
+            .get(id.0)
+            .copied()
+            .flatten()
+            .expect("the mutations refer to an element that doesn't exist")
+    }
+
+    /// Every node in the tree, in the order they appear in
+    pub(crate) fn descendants(&self, id: NodeId) -> Vec<NodeId> {
+        let mut nodes = Vec::new();
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            nodes.push(id);
+            if let Some(node) = self.get(id) {
+                stack.extend(node.children.iter().rev());
+            }
+        }
+        nodes
+    }
+
+    /// The node or the closest element above it that the VirtualDom can send events to
+    pub(crate) fn event_target(&self, id: NodeId) -> Option<ElementId> {
+        let mut current = Some(id);
+        while let Some(id) = current {
+            let node = self.get(id)?;
+            if let (NodeKind::Element { .. }, Some(element)) = (&node.kind, node.element) {
+                if id != self.root() {
+                    return Some(element);
+                }
+            }
+            current = node.parent;
+        }
+        None
+    }
+
+    /// All of the text inside of the node
+    pub(crate) fn text(&self, id: NodeId) -> String {
+        let mut text = String::new();
+        for id in self.descendants(id) {
+            if let Some(DomNode {
+                kind: NodeKind::Text(value),
+                ..
+            }) = self.get(id)
+            {
+                text.push_str(value);
+            }
+        }
+        text
+    }
+
+    /// The html of the node, including the node itself
+    pub(crate) fn html(&self, id: NodeId) -> String {
+        let mut html = String::new();
+        self.write_html(&mut html, id);
+        html
+    }
+
+    /// The html of the children of the node
+    pub(crate) fn inner_html(&self, id: NodeId) -> String {
+        let mut html = String::new();
+        if let Some(node) = self.get(id) {
+            for child in &node.children {
+                self.write_html(&mut html, *child);
+            }
+        }
+        html
+    }
+
+    fn write_html(&self, html: &mut String, id: NodeId) {
+        let node = match self.get(id) {
+            Some(node) => node,
+            None => return,
+        };
+        match &node.kind {
+            NodeKind::Element {
+                tag, attributes, ..
+            } => {
+                html.push('<');
+                html.push_str(tag);
+
+                // attributes in a namespace like style are written the way the browser puts them together
+                let mut style = String::new();
+                for (name, namespace, value) in attributes {
+                    match namespace.as_deref() {
+                        Some("style") => {
+                            let _ = write!(style, "{}:{};", name, value);
+                        }
+                        _ if name == "dangerous_inner_html" => {}
+                        _ => {
+                            let _ = write!(html, " {}=\"{}\"", name, escape(value));
+                        }
+                    }
+                }
+                if !style.is_empty() {
+                    let _ = write!(html, " style=\"{}\"", escape(&style));
+                }
+                html.push('>');
+
+                for child in &node.children {
+                    self.write_html(html, *child);
+                }
+
+                let _ = write!(html, "</{}>", tag);
+            }
+            NodeKind::Text(text) => html.push_str(&escape(text)),
+            NodeKind::Placeholder => {}
+        }
+    }
+
+    /// Change the value attribute of an element, like typing into it does
+    pub(crate) fn set_value(&mut self, id: NodeId, value: &str) {
+        if let NodeKind::Element { attributes, .. } = &mut self.get_mut(id).kind {
+            match attributes
+                .iter_mut()
+                .find(|(name, namespace, _)| name == "value" && namespace.is_none())
+            {
+                Some(attribute) => attribute.2 = value.to_string(),
+                None => attributes.push(("value".to_string(), None, value.to_string())),
+            }
+        }
+    }
+
+    fn create_node(&mut self, kind: NodeKind) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(Some(DomNode {
+            kind,
+            parent: None,
+            children: Vec::new(),
+            element: None,
+        }));
+        id
+    }
+
+    fn set_element(&mut self, id: NodeId, element: ElementId) {
+        self.get_mut(id).element = Some(element);
+        if self.elements.len() <= element.0 {
+            self.elements.resize(element.0 + 1, None);
+        }
+        self.elements[element.0] = Some(id);
+    }
+
+    fn create_template_node(&mut self, node: &TemplateNode) -> NodeId {
+        match node {
+            TemplateNode::Element {
+                tag,
+                namespace,
+                attrs,
+                children,
+            } => {
+                let attributes = attrs
+                    .iter()
+                    .filter_map(|attr| match attr {
+                        TemplateAttribute::Static {
+                            name,
+                            value,
+                            namespace,
+                        } => Some((
+                            name.to_string(),
+                            namespace.map(str::to_string),
+                            value.to_string(),
+                        )),
+                        TemplateAttribute::Dynamic { .. } => None,
+                    })
+                    .collect();
+                let id = self.create_node(NodeKind::Element {
+                    tag: tag.to_string(),
+                    namespace: namespace.map(str::to_string),
+                    attributes,
+                    listeners: Vec::new(),
+                });
+                for child in *children {
+                    let child = self.create_template_node(child);
+                    self.add_child(id, child);
+                }
+                id
+            }
+            TemplateNode::Text { text } => self.create_node(NodeKind::Text(text.to_string())),
+            TemplateNode::Dynamic { .. } => self.create_node(NodeKind::Placeholder),
+            TemplateNode::DynamicText { .. } => self.create_node(NodeKind::Text(String::new())),
+        }
+    }
+
+    fn clone_node(&mut self, id: NodeId) -> NodeId {
+        let node = self.get_mut(id);
+        let kind = node.kind.clone();
+        let children = node.children.clone();
+        let new = self.create_node(kind);
+        for child in children {
+            let child = self.clone_node(child);
+            self.add_child(new, child);
+        }
+        new
+    }
+
+    fn add_child(&mut self, parent: NodeId, child: NodeId) {
+        self.get_mut(child).parent = Some(parent);
+        self.get_mut(parent).children.push(child);
+    }
+
+    fn insert(&mut self, anchor: NodeId, new: Vec<NodeId>, after: bool) {
+        let parent = self
+            .get_mut(anchor)
+            .parent
+            .expect("the mutations insert next to the root");
+        for new in &new {
+            self.get_mut(*new).parent = Some(parent);
+        }
+        let children = &mut self.get_mut(parent).children;
+        let mut index = children.iter().position(|child| *child == anchor).unwrap();
+        if after {
+            index += 1;
+        }
+        children.splice(index..index, new);
+    }
+
+    fn remove_node(&mut self, id: NodeId) {
+        if let Some(parent) = self.get_mut(id).parent {
+            self.get_mut(parent).children.retain(|child| *child != id);
+        }
+        self.drop_node(id);
+    }
+
+    fn drop_node(&mut self, id: NodeId) {
+        if let Some(node) = self.nodes[id.0].take() {
+            // the VirtualDom may have already handed the id of the element to another node
+            if let Some(element) = node.element {
+                if self.elements[element.0] == Some(id) {
+                    self.elements[element.0] = None;
+                }
+            }
+            for child in node.children {
+                self.drop_node(child);
+            }
+        }
+    }
+
+    fn load_child(&self, path: &[u8]) -> NodeId {
+        let mut current = *self.stack.last().unwrap();
+        for index in path {
+            current = self.get(current).unwrap().children[*index as usize];
+        }
+        current
+    }
+
+    fn pop(&mut self, m: usize) -> Vec<NodeId> {
+        self.stack.split_off(self.stack.len() - m)
+    }
+}
+
+impl WriteMutations<'_> for TestDom {
+    fn register_template(&mut self, template: Template<'static>) {
+        let roots = template
+            .roots
+            .iter()
+            .map(|root| self.create_template_node(root))
+            .collect();
+        self.templates.insert(template.name.to_string(), roots);
+    }
+
+    fn append_children(&mut self, id: ElementId, m: usize) {
+        let parent = self.node(id);
+        for child in self.pop(m) {
+            self.add_child(parent, child);
+        }
+    }
+
+    fn assign_id(&mut self, path: &'static [u8], id: ElementId) {
+        let node = self.load_child(path);
+        self.set_element(node, id);
+    }
+
+    fn create_placeholder(&mut self, id: ElementId) {
+        let node = self.create_node(NodeKind::Placeholder);
+        self.set_element(node, id);
+        self.stack.push(node);
+    }
+
+    fn create_text_node(&mut self, value: &str, id: ElementId) {
+        let node = self.create_node(NodeKind::Text(value.to_string()));
+        self.set_element(node, id);
+        self.stack.push(node);
+    }
+
+    fn hydrate_text(&mut self, path: &'static [u8], value: &str, id: ElementId) {
+        let node = self.load_child(path);
+        self.set_element(node, id);
+        self.get_mut(node).kind = NodeKind::Text(value.to_string());
+    }
+
+    fn load_template(&mut self, name: &'static str, index: usize, id: ElementId) {
+        let template = self.templates[name][index];
+        let node = self.clone_node(template);
+        self.set_element(node, id);
+        self.stack.push(node);
+    }
+
+    fn replace_with(&mut self, id: ElementId, m: usize) {
+        let old = self.node(id);
+        let new = self.pop(m);
+        self.insert(old, new, false);
+        self.remove_node(old);
+    }
+
+    fn replace_placeholder(&mut self, path: &'static [u8], m: usize) {
+        let new = self.pop(m);
+        let old = self.load_child(path);
+        self.insert(old, new, false);
+        self.remove_node(old);
+    }
+
+    fn insert_after(&mut self, id: ElementId, m: usize) {
+        let anchor = self.node(id);
+        let new = self.pop(m);
+        self.insert(anchor, new, true);
+    }
+
+    fn insert_before(&mut self, id: ElementId, m: usize) {
+        let anchor = self.node(id);
+        let new = self.pop(m);
+        self.insert(anchor, new, false);
+    }
+
+    fn set_attribute(
+        &mut self,
+        name: &str,
+        ns: Option<&str>,
+        value: BorrowedAttributeValue,
+        id: ElementId,
+    ) {
+        let node = self.node(id);
+        if let NodeKind::Element { attributes, .. } = &mut self.get_mut(node).kind {
+            let value = match value {
+                BorrowedAttributeValue::Text(text) => Some(text.to_string()),
+                BorrowedAttributeValue::Float(f) => Some(f.to_string()),
+                BorrowedAttributeValue::Int(i) => Some(i.to_string()),
+                BorrowedAttributeValue::Bool(b) => Some(b.to_string()),
+                BorrowedAttributeValue::Any(_) | BorrowedAttributeValue::None => None,
+            };
+            let existing = attributes
+                .iter()
+                .position(|(n, namespace, _)| n == name && namespace.as_deref() == ns);
+            match (existing, value) {
+                (Some(index), Some(value)) => attributes[index].2 = value,
+                (Some(index), None) => {
+                    attributes.remove(index);
+                }
+                (None, Some(value)) => {
+                    attributes.push((name.to_string(), ns.map(str::to_string), value))
+                }
+                (None, None) => {}
+            }
+        }
+    }
+
+    fn set_text(&mut self, value: &str, id: ElementId) {
+        let node = self.node(id);
+        self.get_mut(node).kind = NodeKind::Text(value.to_string());
+    }
+
+    fn new_event_listener(&mut self, name: &str, id: ElementId, _passive: bool) {
+        let node = self.node(id);
+        if let NodeKind::Element { listeners, .. } = &mut self.get_mut(node).kind {
+            if !listeners.iter().any(|listener| listener == name) {
+                listeners.push(name.to_string());
+            }
+        }
+    }
+
+    fn remove_event_listener(&mut self, name: &str, id: ElementId) {
+        let node = self.node(id);
+        if let NodeKind::Element { listeners, .. } = &mut self.get_mut(node).kind {
+            listeners.retain(|listener| listener != name);
+        }
+    }
+
+    fn remove(&mut self, id: ElementId) {
+        let node = self.node(id);
+        self.remove_node(node);
+    }
+
+    fn push_root(&mut self, id: ElementId) {
+        let node = self.node(id);
+        self.stack.push(node);
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
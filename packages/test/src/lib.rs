@@ -0,0 +1,505 @@
+#![doc = include_str!("../README.md")]
+
+mod dom;
+
+pub use dom::NodeId;
+
+use dioxus_core::{Element, ElementId, Scope, VirtualDom};
+use dioxus_html::{FormData, MouseData};
+use dom::{NodeKind, TestDom};
+use futures_util::FutureExt;
+use std::any::Any;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// How many times [`Tester::flush`] renders before it decides the app will never settle down
+const MAX_FLUSHES: usize = 1000;
+
+/// A component mounted into a VirtualDom without a renderer, for tests
+///
+/// The tester keeps a copy of the tree the component rendered, which can be searched with the `get_by`, `find_by` and
+/// `query_by` methods, and sent events with [`Tester::click`], [`Tester::input`] and [`Tester::submit`]. The events go
+/// through [`VirtualDom::handle_event`] like they do in a renderer, and the tester renders all of the work they cause
+/// before the methods return.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_test::Tester;
+///
+/// fn app(cx: Scope) -> Element {
+///     let count = use_state(cx, || 0);
+///     cx.render(rsx! {
+///         button { onclick: move |_| count += 1, "clicked {count} times" }
+///     })
+/// }
+///
+/// let mut tester = Tester::new(app);
+/// let button = tester.get_by_role("button");
+/// tester.click(button);
+/// assert_eq!(tester.node(button).text(), "clicked 1 times");
+/// ```
+pub struct Tester {
+    vdom: VirtualDom,
+    dom: TestDom,
+}
+
+impl Tester {
+    /// Mount a component that doesn't take any props
+    pub fn new(app: fn(Scope) -> Element) -> Self {
+        Self::from_vdom(VirtualDom::new(app))
+    }
+
+    /// Mount a component with some props
+    pub fn new_with_props<P: 'static>(app: fn(Scope<P>) -> Element, props: P) -> Self {
+        Self::from_vdom(VirtualDom::new_with_props(app, props))
+    }
+
+    /// Take over a VirtualDom that wasn't built yet, for apps that need root contexts or other setup first
+    pub fn from_vdom(mut vdom: VirtualDom) -> Self {
+        let mut dom = TestDom::new();
+        vdom.rebuild_to(&mut dom);
+        let mut tester = Self { vdom, dom };
+        tester.flush();
+        tester
+    }
+
+    /// The VirtualDom the component is mounted in
+    pub fn vdom(&mut self) -> &mut VirtualDom {
+        &mut self.vdom
+    }
+
+    /// Render all of the work the VirtualDom has ready
+    ///
+    /// Tasks that wake each other up and state that changes from inside of a render are rendered until the app
+    /// settles down. Work that waits on something outside of the app, like a timer or a server, is left for
+    /// [`Tester::wait_for_work`].
+    ///
+    /// # Panics
+    ///
+    /// If the app keeps rendering forever.
+    pub fn flush(&mut self) {
+        for _ in 0..MAX_FLUSHES {
+            self.vdom.process_events();
+            self.vdom.render_immediate_to(&mut self.dom);
+            if self.vdom.wait_for_work().now_or_never().is_none() {
+                return;
+            }
+        }
+        panic!(
+            "the app was still rendering after {} flushes, does it mark itself dirty every time it renders?",
+            MAX_FLUSHES
+        );
+    }
+
+    /// Wait for a task or suspended component to have work, and then render it
+    pub async fn wait_for_work(&mut self) {
+        self.vdom.wait_for_work().await;
+        self.flush();
+    }
+
+    /// The html of everything the app rendered
+    pub fn html(&self) -> String {
+        self.dom.inner_html(self.dom.root())
+    }
+
+    /// A view of a node in the tree
+    ///
+    /// # Panics
+    ///
+    /// If the node was removed.
+    pub fn node(&self, id: NodeId) -> TestNode<'_> {
+        assert!(
+            self.dom.get(id).is_some(),
+            "the node {:?} isn't in the tree anymore",
+            id
+        );
+        TestNode { tester: self, id }
+    }
+
+    /// Whether the node is still in the tree
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.dom.get(id).is_some()
+    }
+
+    /// The elements whose own text, with the whitespace around it trimmed, is the text
+    pub fn query_all_by_text(&self, text: &str) -> Vec<NodeId> {
+        self.query_all(|tester, id| tester.own_text(id).trim() == text)
+    }
+
+    /// The elements with the role, which is the `role` attribute or the role the tag has on its own, like `button`
+    /// for a `button` or `checkbox` for an `input` with the type `checkbox`
+    pub fn query_all_by_role(&self, role: &str) -> Vec<NodeId> {
+        self.query_all(|tester, id| tester.role(id).as_deref() == Some(role))
+    }
+
+    /// The elements with the `data-testid` attribute
+    pub fn query_all_by_test_id(&self, test_id: &str) -> Vec<NodeId> {
+        self.query_all(|tester, id| tester.attribute(id, "data-testid") == Some(test_id))
+    }
+
+    /// The element with the text, if there is exactly one
+    ///
+    /// # Panics
+    ///
+    /// If more than one element has the text.
+    pub fn query_by_text(&self, text: &str) -> Option<NodeId> {
+        at_most_one(self.query_all_by_text(text), "the text", text)
+    }
+
+    /// The element with the role, if there is exactly one
+    ///
+    /// # Panics
+    ///
+    /// If more than one element has the role.
+    pub fn query_by_role(&self, role: &str) -> Option<NodeId> {
+        at_most_one(self.query_all_by_role(role), "the role", role)
+    }
+
+    /// The element with the test id, if there is exactly one
+    ///
+    /// # Panics
+    ///
+    /// If more than one element has the test id.
+    pub fn query_by_test_id(&self, test_id: &str) -> Option<NodeId> {
+        at_most_one(self.query_all_by_test_id(test_id), "the test id", test_id)
+    }
+
+    /// The element with the text
+    ///
+    /// # Panics
+    ///
+    /// If no element or more than one element has the text.
+    pub fn get_by_text(&self, text: &str) -> NodeId {
+        self.expect_one(self.query_by_text(text), "the text", text)
+    }
+
+    /// The element with the role
+    ///
+    /// # Panics
+    ///
+    /// If no element or more than one element has the role.
+    pub fn get_by_role(&self, role: &str) -> NodeId {
+        self.expect_one(self.query_by_role(role), "the role", role)
+    }
+
+    /// The element with the test id
+    ///
+    /// # Panics
+    ///
+    /// If no element or more than one element has the test id.
+    pub fn get_by_test_id(&self, test_id: &str) -> NodeId {
+        self.expect_one(self.query_by_test_id(test_id), "the test id", test_id)
+    }
+
+    /// Wait until an element has the text, rendering the work of the app in between
+    ///
+    /// This is for text that shows up after a task finishes, which may take some time. The future never finishes if
+    /// the text doesn't show up, so tests usually put a timeout around it.
+    pub async fn find_by_text(&mut self, text: &str) -> NodeId {
+        loop {
+            if let Some(id) = self.query_by_text(text) {
+                return id;
+            }
+            self.wait_for_work().await;
+        }
+    }
+
+    /// Wait until an element has the role, rendering the work of the app in between
+    pub async fn find_by_role(&mut self, role: &str) -> NodeId {
+        loop {
+            if let Some(id) = self.query_by_role(role) {
+                return id;
+            }
+            self.wait_for_work().await;
+        }
+    }
+
+    /// Wait until an element has the test id, rendering the work of the app in between
+    pub async fn find_by_test_id(&mut self, test_id: &str) -> NodeId {
+        loop {
+            if let Some(id) = self.query_by_test_id(test_id) {
+                return id;
+            }
+            self.wait_for_work().await;
+        }
+    }
+
+    /// Click on the node
+    ///
+    /// Returns true if a listener prevented the default action of the click.
+    pub fn click(&mut self, id: NodeId) -> bool {
+        self.fire(id, "click", Rc::new(MouseData::default()))
+    }
+
+    /// Type a new value into an input, textarea or select
+    ///
+    /// The value of the element is changed before the event, like it is in a browser, so the values of the form the
+    /// element is in include it. Returns true if a listener prevented the default action of the input.
+    pub fn input(&mut self, id: NodeId, value: &str) -> bool {
+        self.dom.set_value(id, value);
+        let values = match self.closest(id, "form") {
+            Some(form) => self.form_values(form),
+            None => HashMap::new(),
+        };
+        self.fire(
+            id,
+            "input",
+            Rc::new(FormData {
+                value: value.to_string(),
+                values,
+                files: None,
+            }),
+        )
+    }
+
+    /// Submit a form with the values of its named fields
+    ///
+    /// Returns true if a listener prevented the default action of the submit.
+    pub fn submit(&mut self, form: NodeId) -> bool {
+        let values = self.form_values(form);
+        self.fire(
+            form,
+            "submit",
+            Rc::new(FormData {
+                value: String::new(),
+                values,
+                files: None,
+            }),
+        )
+    }
+
+    /// Send any event to the node, with the data the listeners for it expect
+    ///
+    /// The event goes to the closest element the VirtualDom knows about, and bubbles if the event bubbles in a browser.
+    /// Returns true if a listener prevented the default action of the event.
+    ///
+    /// ```rust, ignore
+    /// tester.fire(input, "keydown", Rc::new(KeyboardData::new(/* ... */)));
+    /// ```
+    pub fn fire(&mut self, id: NodeId, name: &str, data: Rc<dyn Any>) -> bool {
+        let element = self.target(id);
+        let prevented =
+            self.vdom
+                .handle_event(name, data, element, dioxus_html::event_bubbles(name));
+        self.flush();
+        prevented
+    }
+
+    fn target(&self, id: NodeId) -> ElementId {
+        self.node(id);
+        self.dom
+            .event_target(id)
+            .expect("the node isn't inside of an element of the app")
+    }
+
+    fn query_all(&self, matches: impl Fn(&Self, NodeId) -> bool) -> Vec<NodeId> {
+        let root = self.dom.root();
+        self.dom
+            .descendants(root)
+            .into_iter()
+            .filter(|id| *id != root && self.tag(*id).is_some() && matches(self, *id))
+            .collect()
+    }
+
+    fn expect_one(&self, id: Option<NodeId>, what: &str, value: &str) -> NodeId {
+        match id {
+            Some(id) => id,
+            None => panic!(
+                "no element has {} {:?}, the app rendered:\n{}",
+                what,
+                value,
+                self.html()
+            ),
+        }
+    }
+
+    fn tag(&self, id: NodeId) -> Option<&str> {
+        match &self.dom.get(id)?.kind {
+            NodeKind::Element { tag, .. } => Some(tag),
+            _ => None,
+        }
+    }
+
+    fn attribute(&self, id: NodeId, name: &str) -> Option<&str> {
+        match &self.dom.get(id)?.kind {
+            NodeKind::Element { attributes, .. } => attributes
+                .iter()
+                .find(|(n, namespace, _)| n == name && namespace.is_none())
+                .map(|(_, _, value)| value.as_str()),
+            _ => None,
+        }
+    }
+
+    fn is_true(&self, id: NodeId, name: &str) -> bool {
+        matches!(self.attribute(id, name), Some(value) if value != "false")
+    }
+
+    /// The text nodes right inside of the element, without the text of the elements inside of it
+    fn own_text(&self, id: NodeId) -> String {
+        let mut text = String::new();
+        if let Some(node) = self.dom.get(id) {
+            for child in &node.children {
+                if let Some(NodeKind::Text(value)) = self.dom.get(*child).map(|node| &node.kind) {
+                    text.push_str(value);
+                }
+            }
+        }
+        text
+    }
+
+    fn role(&self, id: NodeId) -> Option<String> {
+        if let Some(role) = self.attribute(id, "role") {
+            return Some(role.to_string());
+        }
+        dioxus_core::implicit_role(self.tag(id)?, self.attribute(id, "type")).map(str::to_string)
+    }
+
+    fn closest(&self, id: NodeId, tag: &str) -> Option<NodeId> {
+        let mut current = Some(id);
+        while let Some(id) = current {
+            if self.tag(id) == Some(tag) {
+                return Some(id);
+            }
+            current = self.dom.get(id)?.parent;
+        }
+        None
+    }
+
+    /// The values of the named fields in the form, the way a browser submits them
+    fn form_values(&self, form: NodeId) -> HashMap<String, Vec<String>> {
+        let mut values: HashMap<String, Vec<String>> = HashMap::new();
+        for id in self.dom.descendants(form) {
+            let name = match self.attribute(id, "name") {
+                Some(name) => name,
+                None => continue,
+            };
+            let value = match self.tag(id) {
+                Some("input") => match self.attribute(id, "type") {
+                    Some("checkbox") => {
+                        match (self.attribute(id, "value"), self.is_true(id, "checked")) {
+                            (Some(value), true) => value.to_string(),
+                            (Some(_), false) => continue,
+                            (None, checked) => checked.to_string(),
+                        }
+                    }
+                    Some("radio") if !self.is_true(id, "checked") => continue,
+                    _ => self.attribute(id, "value").unwrap_or_default().to_string(),
+                },
+                Some("textarea") => match self.attribute(id, "value") {
+                    Some(value) => value.to_string(),
+                    None => self.dom.text(id),
+                },
+                Some("select") => match self.attribute(id, "value") {
+                    Some(value) => value.to_string(),
+                    None => self.selected_option(id).unwrap_or_default(),
+                },
+                _ => continue,
+            };
+            values.entry(name.to_string()).or_default().push(value);
+        }
+        values
+    }
+
+    /// The value of the selected option of a select, or its first option if none are selected
+    fn selected_option(&self, select: NodeId) -> Option<String> {
+        let options: Vec<NodeId> = self
+            .dom
+            .descendants(select)
+            .into_iter()
+            .filter(|id| self.tag(*id) == Some("option"))
+            .collect();
+        let option = options
+            .iter()
+            .find(|id| self.is_true(**id, "selected"))
+            .or_else(|| options.first())?;
+        Some(match self.attribute(*option, "value") {
+            Some(value) => value.to_string(),
+            None => self.dom.text(*option).trim().to_string(),
+        })
+    }
+}
+
+fn at_most_one(mut ids: Vec<NodeId>, what: &str, value: &str) -> Option<NodeId> {
+    if ids.len() > 1 {
+        panic!(
+            "{} elements have {} {:?}, use the query_all methods to find all of them",
+            ids.len(),
+            what,
+            value
+        );
+    }
+    ids.pop()
+}
+
+/// A node in the tree of a [`Tester`]
+#[derive(Clone, Copy)]
+pub struct TestNode<'a> {
+    tester: &'a Tester,
+    id: NodeId,
+}
+
+impl<'a> TestNode<'a> {
+    /// The id of the node
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    /// The tag of the element, or None for text
+    pub fn tag(&self) -> Option<&'a str> {
+        self.tester.tag(self.id)
+    }
+
+    /// A attribute of the element that isn't in a namespace
+    ///
+    /// Attributes that were set to a bool are `"true"` or `"false"`.
+    pub fn attribute(&self, name: &str) -> Option<&'a str> {
+        self.tester.attribute(self.id, name)
+    }
+
+    /// The names of the events the element listens to, like `click`
+    pub fn listeners(&self) -> Vec<&'a str> {
+        match &self.tester.dom.get(self.id).unwrap().kind {
+            NodeKind::Element { listeners, .. } => listeners.iter().map(String::as_str).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// All of the text inside of the node
+    pub fn text(&self) -> String {
+        self.tester.dom.text(self.id)
+    }
+
+    /// The html of the node
+    pub fn html(&self) -> String {
+        self.tester.dom.html(self.id)
+    }
+
+    /// The html of the children of the node
+    pub fn inner_html(&self) -> String {
+        self.tester.dom.inner_html(self.id)
+    }
+
+    /// The nodes right inside of the node, without placeholders
+    pub fn children(&self) -> Vec<NodeId> {
+        self.tester
+            .dom
+            .get(self.id)
+            .unwrap()
+            .children
+            .iter()
+            .copied()
+            .filter(|id| {
+                !matches!(
+                    self.tester.dom.get(*id).map(|node| &node.kind),
+                    Some(NodeKind::Placeholder)
+                )
+            })
+            .collect()
+    }
+
+    /// The element the node is in
+    pub fn parent(&self) -> Option<NodeId> {
+        let parent = self.tester.dom.get(self.id).unwrap().parent?;
+        (parent != self.tester.dom.root()).then(|| parent)
+    }
+}
@@ -0,0 +1,168 @@
+use dioxus::prelude::*;
+use dioxus_test::Tester;
+use std::time::Duration;
+
+#[test]
+fn renders_markup() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! {
+            div { class: "card", "data-testid": "card",
+                h1 { "Title" }
+                p { "a < b" }
+            }
+        })
+    }
+
+    let tester = Tester::new(app);
+    assert_eq!(
+        tester.html(),
+        "<div class=\"card\" data-testid=\"card\"><h1>Title</h1><p>a &lt; b</p></div>"
+    );
+
+    let card = tester.get_by_test_id("card");
+    assert_eq!(tester.node(card).tag(), Some("div"));
+    assert_eq!(tester.node(card).attribute("class"), Some("card"));
+    assert_eq!(tester.node(card).text(), "Titlea < b");
+    assert_eq!(tester.node(card).children().len(), 2);
+}
+
+#[test]
+fn clicks_update_the_tree() {
+    fn app(cx: Scope) -> Element {
+        let count = use_state(cx, || 0);
+        cx.render(rsx! {
+            button { onclick: move |_| count += 1, "add" }
+            p { "count is {count}" }
+            if **count > 1 {
+                span { "more than one" }
+            }
+        })
+    }
+
+    let mut tester = Tester::new(app);
+    let button = tester.get_by_role("button");
+    assert!(tester.query_by_text("count is 0").is_some());
+
+    tester.click(button);
+    assert!(tester.query_by_text("count is 1").is_some());
+    assert!(tester.query_by_text("more than one").is_none());
+
+    tester.click(button);
+    assert!(tester.query_by_text("count is 2").is_some());
+    assert!(tester.query_by_text("more than one").is_some());
+}
+
+#[test]
+fn events_bubble_from_children() {
+    fn app(cx: Scope) -> Element {
+        let clicked = use_state(cx, || false);
+        cx.render(rsx! {
+            div { onclick: move |_| clicked.set(true),
+                span { "inside" }
+            }
+            p { "clicked: {clicked}" }
+        })
+    }
+
+    let mut tester = Tester::new(app);
+    let span = tester.get_by_text("inside");
+    tester.click(span);
+    assert!(tester.query_by_text("clicked: true").is_some());
+}
+
+#[test]
+fn input_sends_the_value() {
+    fn app(cx: Scope) -> Element {
+        let name = use_state(cx, String::new);
+        cx.render(rsx! {
+            input { "data-testid": "name", oninput: move |evt| name.set(evt.value.clone()) }
+            p { "hello {name}" }
+        })
+    }
+
+    let mut tester = Tester::new(app);
+    let input = tester.get_by_test_id("name");
+    tester.input(input, "world");
+    assert_eq!(tester.node(input).attribute("value"), Some("world"));
+    assert!(tester.query_by_text("hello world").is_some());
+}
+
+#[test]
+fn submit_collects_the_form() {
+    fn app(cx: Scope) -> Element {
+        let submitted = use_state(cx, String::new);
+        cx.render(rsx! {
+            form { "data-testid": "form",
+                prevent_default: "onsubmit",
+                onsubmit: move |evt| {
+                    let mut values: Vec<_> = evt.values.iter().map(|(k, v)| format!("{}={}", k, v.join(","))).collect();
+                    values.sort();
+                    submitted.set(values.join("&"));
+                },
+                input { name: "user", value: "ada" }
+                input { r#type: "checkbox", name: "remember" }
+                input { r#type: "checkbox", name: "tags", value: "a", checked: true }
+                input { r#type: "checkbox", name: "tags", value: "b" }
+                select { name: "color",
+                    option { value: "red", "Red" }
+                    option { value: "blue", selected: true, "Blue" }
+                }
+            }
+            p { "data-testid": "result", "{submitted}" }
+        })
+    }
+
+    let mut tester = Tester::new(app);
+    let form = tester.get_by_test_id("form");
+    tester.submit(form);
+
+    let result = tester.get_by_test_id("result");
+    assert_eq!(
+        tester.node(result).text(),
+        "color=blue&remember=false&tags=a&user=ada"
+    );
+}
+
+#[test]
+#[should_panic(expected = "no element has the text")]
+fn get_panics_when_missing() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! { div { "something" } })
+    }
+
+    Tester::new(app).get_by_text("nothing");
+}
+
+#[test]
+#[should_panic(expected = "2 elements have the role")]
+fn get_panics_when_ambiguous() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! {
+            button { "one" }
+            button { "two" }
+        })
+    }
+
+    Tester::new(app).get_by_role("button");
+}
+
+#[tokio::test]
+async fn waits_for_tasks() {
+    fn app(cx: Scope) -> Element {
+        let data = use_future(cx, (), |_| async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            "loaded"
+        });
+        match data.value() {
+            Some(data) => cx.render(rsx! { p { "{data}" } }),
+            None => cx.render(rsx! { p { "loading" } }),
+        }
+    }
+
+    let mut tester = Tester::new(app);
+    assert!(tester.query_by_text("loading").is_some());
+
+    let loaded = tester.find_by_text("loaded").await;
+    assert_eq!(tester.node(loaded).tag(), Some("p"));
+    assert!(tester.query_by_text("loading").is_none());
+}
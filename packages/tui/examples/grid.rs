@@ -0,0 +1,42 @@
+use dioxus::prelude::*;
+
+fn main() {
+    dioxus_tui::launch(app);
+}
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx! {
+        div {
+            width: "100%",
+            height: "100%",
+            display: "grid",
+            grid_template_columns: "20px 1fr 2fr",
+            grid_template_rows: "3px 1fr 3px",
+            gap: "1px 2px",
+
+            div {
+                grid_column: "1 / 4",
+                background_color: "red",
+                "header"
+            }
+            div {
+                grid_row: "2 / 4",
+                background_color: "blue",
+                "sidebar"
+            }
+            div {
+                background_color: "green",
+                "one"
+            }
+            div {
+                background_color: "yellow",
+                "two"
+            }
+            div {
+                grid_column: "span 2",
+                background_color: "cyan",
+                "footer"
+            }
+        }
+    })
+}
@@ -0,0 +1,70 @@
+use std::{
+    cell::RefCell,
+    io::{stdout, Write},
+    rc::Rc,
+};
+
+/// The text that was last copied or cut out of a text input
+///
+/// Terminals don't let apps read the clipboard of the system, so ctrl+v pastes what was copied inside of the app, and
+/// pasting with the terminal types the text into the focused input instead. Copied text is also sent to the clipboard
+/// of the system with an OSC 52 escape sequence, which most terminals support.
+#[derive(Clone, Default)]
+pub(crate) struct Clipboard {
+    text: Rc<RefCell<String>>,
+    // headless apps don't write to the terminal
+    system: bool,
+}
+
+impl Clipboard {
+    pub fn new(system: bool) -> Self {
+        Self {
+            text: Rc::default(),
+            system,
+        }
+    }
+
+    pub fn get(&self) -> String {
+        self.text.borrow().clone()
+    }
+
+    pub fn set(&self, text: &str) {
+        *self.text.borrow_mut() = text.to_string();
+        if self.system {
+            let mut stdout = stdout();
+            let _ = write!(stdout, "\x1b]52;c;{}\x07", base64(text.as_bytes()));
+            let _ = stdout.flush();
+        }
+    }
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (u32::from(bytes[0]) << 16) | (u32::from(bytes[1]) << 8) | u32::from(bytes[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[test]
+fn encodes_base64() {
+    assert_eq!(base64(b""), "");
+    assert_eq!(base64(b"f"), "Zg==");
+    assert_eq!(base64(b"fo"), "Zm8=");
+    assert_eq!(base64(b"foo"), "Zm9v");
+    assert_eq!(base64(b"hello world"), "aGVsbG8gd29ybGQ=");
+}
@@ -1,4 +1,4 @@
-use crate::{node::PreventDefault, TuiDom};
+use crate::{node::PreventDefault, scroll::Overflow, TuiDom};
 
 use dioxus_native_core::{
     tree::TreeView,
@@ -65,12 +65,12 @@ pub(crate) struct Focus {
 }
 
 impl NodeDepState for Focus {
-    type DepState = ();
+    type DepState = (Overflow,);
     type Ctx = ();
     const NODE_MASK: NodeMask =
         NodeMask::new_with_attrs(AttributeMask::Static(FOCUS_ATTRIBUTES)).with_listeners();
 
-    fn reduce(&mut self, node: NodeView<'_>, _sibling: (), _: &Self::Ctx) -> bool {
+    fn reduce(&mut self, node: NodeView<'_>, (overflow,): (&Overflow,), _: &Self::Ctx) -> bool {
         let new = Focus {
             level: if let Some(a) = node
                 .attributes()
@@ -99,6 +99,8 @@ impl NodeDepState for Focus {
                         .then_some(())
                 })
                 .is_some()
+                // scroll containers can be focused to scroll them with the keyboard
+                || overflow.scrolls()
             {
                 FocusLevel::Focusable
             } else {
@@ -117,6 +119,14 @@ impl NodeDepState for Focus {
 const FOCUS_EVENTS: &[&str] = &sorted_str_slice!(["keydown", "keypress", "keyup"]);
 const FOCUS_ATTRIBUTES: &[&str] = &sorted_str_slice!(["tabindex"]);
 
+/// A change of focus the app asked for through the [`TuiContext`](crate::TuiContext)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum FocusRequest {
+    Next,
+    Prev,
+    Element(dioxus_core::ElementId),
+}
+
 #[derive(Default)]
 pub(crate) struct FocusState {
     pub(crate) focus_iter: PersistantElementIter,
@@ -133,6 +143,36 @@ impl FocusState {
                 return false;
             }
         }
+        self.step(rdom, forward)
+    }
+
+    /// Apply a change of focus the app asked for. Returns true if the focus has changed.
+    ///
+    /// Unlike tabbing, this ignores the focused element preventing the default of its key presses, and elements that
+    /// can't be focused stay unfocused.
+    pub(crate) fn request(&mut self, rdom: &mut TuiDom, request: FocusRequest) -> bool {
+        match request {
+            FocusRequest::Next => self.step(rdom, true),
+            FocusRequest::Prev => self.step(rdom, false),
+            FocusRequest::Element(id) => {
+                let id = match rdom.try_element_to_node_id(id) {
+                    Some(id) => id,
+                    None => return false,
+                };
+                let focusable = rdom
+                    .tree
+                    .get(id)
+                    .map_or(false, |node| node.state.focus.level.focusable());
+                if !focusable || self.last_focused_id == Some(id) {
+                    return false;
+                }
+                self.set_focus(rdom, id);
+                true
+            }
+        }
+    }
+
+    fn step(&mut self, rdom: &mut TuiDom, forward: bool) -> bool {
         // the id that started focused to track when a loop has happened
         let mut loop_marker_id = self.last_focused_id;
         let focus_level = &mut self.focus_level;
@@ -3,7 +3,7 @@ use crossterm::event::{
 };
 use dioxus_core::*;
 use dioxus_native_core::tree::TreeView;
-use dioxus_native_core::NodeId;
+use dioxus_native_core::{NodeId, RealNodeId};
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use dioxus_html::geometry::euclid::{Point2D, Rect, Size2D};
@@ -17,14 +17,17 @@ use dioxus_html::{event_bubbles, FocusData, KeyboardData, MouseData, WheelData};
 use std::{
     any::Any,
     cell::{RefCell, RefMut},
+    collections::VecDeque,
     rc::Rc,
     time::{Duration, Instant},
 };
 use taffy::geometry::{Point, Size};
 use taffy::{prelude::Layout, Taffy};
 
-use crate::{layout_to_screen_space, FocusState};
-use crate::{TuiDom, TuiNode};
+use crate::focus::{FocusRequest, FocusState};
+use crate::scroll::{scroll_key, scroll_wheel};
+use crate::{layout_to_screen_space, screen_to_layout_space};
+use crate::{PreventDefault, TuiDom, TuiNode};
 
 pub(crate) struct Event {
     pub id: ElementId,
@@ -96,6 +99,12 @@ pub struct InnerInputState {
     last_key_pressed: Option<(KeyboardData, Instant)>,
     screen: Option<(u16, u16)>,
     pub(crate) focus_state: FocusState,
+    /// the changes of focus the app asked for since the last update
+    pub(crate) focus_requests: Vec<FocusRequest>,
+    // if an element scrolled since the last time the screen was rendered
+    scrolled: bool,
+    // the keys pressed since the last update, which go to the focused element one at a time
+    keys: VecDeque<KeyboardData>,
     // subscribers: Vec<Rc<dyn Fn() + 'static>>,
 }
 
@@ -108,9 +117,18 @@ impl InnerInputState {
             screen: None,
             // subscribers: Vec::new(),
             focus_state: FocusState::default(),
+            focus_requests: Vec::new(),
+            scrolled: false,
+            keys: VecDeque::new(),
         }
     }
 
+    /// Returns true if the focus moved or an element scrolled since the last time this was called
+    pub(crate) fn clean(&mut self) -> bool {
+        let scrolled = std::mem::take(&mut self.scrolled);
+        self.focus_state.clean() || scrolled
+    }
+
     // stores current input state and transforms events based on that state
     fn apply_event(&mut self, evt: &mut EventCore) {
         match evt.1 {
@@ -181,22 +199,65 @@ impl InnerInputState {
 
         let old_focus = self.focus_state.last_focused_id;
 
-        evts.retain(|e| match &e.1 {
-            EventData::Keyboard(k) => match k.code() {
-                Code::Tab => !self
-                    .focus_state
-                    .progress(dom, !k.modifiers().contains(Modifiers::SHIFT)),
-                _ => true,
-            },
-            _ => true,
-        });
+        for request in self.focus_requests.drain(..) {
+            self.focus_state.request(dom, request);
+        }
 
         for e in evts.iter_mut() {
             self.apply_event(e);
         }
 
+        // the default of a key can move the focus, so the next key goes to the element focused after it
+        let keys = &mut self.keys;
+        evts.retain(|e| match &e.1 {
+            EventData::Keyboard(k) => {
+                keys.push_back(k.clone());
+                false
+            }
+            _ => true,
+        });
+
         self.resolve_mouse_events(previous_mouse, resolved_events, layout, dom);
 
+        self.focus_events(old_focus, resolved_events, dom);
+
+        // for s in &self.subscribers {
+        //     s();
+        // }
+    }
+
+    /// Do what a key does unless a listener prevented it: tab moves the focus, and the keys that scroll an element
+    /// scroll the focused one, unless it prevents the default of its key presses
+    fn key_default(
+        &mut self,
+        key: &KeyboardData,
+        resolved_events: &mut Vec<Event>,
+        layout: &Taffy,
+        dom: &mut TuiDom,
+    ) {
+        let old_focus = self.focus_state.last_focused_id;
+
+        if key.code() == Code::Tab {
+            self.focus_state
+                .progress(dom, !key.modifiers().contains(Modifiers::SHIFT));
+        } else if let Some(focused) = old_focus {
+            let node = &dom[focused];
+            if node.state.overflow.scrolls()
+                && node.state.prevent_default != PreventDefault::KeyDown
+            {
+                self.scrolled |= scroll_key(dom, layout, focused, key);
+            }
+        }
+
+        self.focus_events(old_focus, resolved_events, dom);
+    }
+
+    fn focus_events(
+        &self,
+        old_focus: Option<RealNodeId>,
+        resolved_events: &mut Vec<Event>,
+        dom: &TuiDom,
+    ) {
         if old_focus != self.focus_state.last_focused_id {
             // elements with listeners will always have a element id
             if let Some(id) = self.focus_state.last_focused_id {
@@ -228,10 +289,6 @@ impl InnerInputState {
                 }
             }
         }
-
-        // for s in &self.subscribers {
-        //     s();
-        // }
     }
 
     fn resolve_mouse_events(
@@ -510,6 +567,25 @@ impl InnerInputState {
                                 );
                             }
                         }
+
+                        // the wheel scrolls the innermost element under the mouse that scrolls, unless it prevents it
+                        let mut scrolled_id = None;
+                        dom.traverse_depth_first(|node| {
+                            if node.state.overflow.scrolls()
+                                && layout_contains_point(
+                                    &get_abs_layout(node, dom, layout),
+                                    new_pos,
+                                )
+                            {
+                                scrolled_id = Some(node.node_data.node_id);
+                            }
+                        });
+                        if let Some(id) = scrolled_id {
+                            if dom[id].state.prevent_default != PreventDefault::Wheel {
+                                let delta = w.delta().strip_units();
+                                self.scrolled |= scroll_wheel(dom, layout, id, delta.y);
+                            }
+                        }
                     }
                 }
             }
@@ -564,8 +640,8 @@ impl InnerInputState {
             if was_released {
                 let mut focus_id = None;
                 dom.traverse_depth_first(|node| {
-                    let node_layout = layout.layout(node.state.layout.node.unwrap()).unwrap();
-                    let currently_contains = layout_contains_point(node_layout, new_pos);
+                    let node_layout = get_abs_layout(node, dom, layout);
+                    let currently_contains = layout_contains_point(&node_layout, new_pos);
 
                     if currently_contains && node.state.focus.level.focusable() {
                         focus_id = Some(node.node_data.node_id);
@@ -590,8 +666,9 @@ fn get_abs_layout(node: &TuiNode, dom: &TuiDom, taffy: &Taffy) -> Layout {
     while let Some(parent) = dom.parent(current.node_data.node_id) {
         current = parent;
         let parent_layout = taffy.layout(parent.state.layout.node.unwrap()).unwrap();
-        node_layout.location.x += parent_layout.location.x;
-        node_layout.location.y += parent_layout.location.y;
+        let scroll = parent.state.scroll;
+        node_layout.location.x += parent_layout.location.x - screen_to_layout_space(scroll.x);
+        node_layout.location.y += parent_layout.location.y - screen_to_layout_space(scroll.y);
     }
     node_layout
 }
@@ -697,6 +774,39 @@ impl RinkInputHandler {
         resolved_events
     }
 
+    /// The next key pressed, with the events it fires at the focused element
+    pub(crate) fn next_key(&self, dom: &TuiDom) -> Option<(KeyboardData, Vec<Event>)> {
+        let key = self.state.borrow_mut().keys.pop_front()?;
+        let data: Rc<dyn Any> = Rc::new(key.clone());
+        let events = dom
+            .get_listening_sorted("keydown")
+            .into_iter()
+            .filter(|node| node.state.focused)
+            .filter_map(|node| node.mounted_id())
+            .map(|id| Event {
+                name: "keydown",
+                id,
+                data: data.clone(),
+                bubbles: event_bubbles("keydown"),
+            })
+            .collect();
+        Some((key, events))
+    }
+
+    /// Do what a key does now that no listener prevented it, returning the focus events it fires
+    pub(crate) fn key_default(
+        &self,
+        key: &KeyboardData,
+        layout: &Taffy,
+        dom: &mut TuiDom,
+    ) -> Vec<Event> {
+        let mut resolved_events = Vec::new();
+        self.state
+            .borrow_mut()
+            .key_default(key, &mut resolved_events, layout, dom);
+        resolved_events
+    }
+
     pub(crate) fn state(&self) -> RefMut<InnerInputState> {
         self.state.borrow_mut()
     }
@@ -781,7 +891,11 @@ fn translate_key_event(event: crossterm::event::KeyEvent) -> Option<EventData> {
     // crossterm does not provide code. we make a guess as to which key might have been pressed
     // this is probably garbage if the user has a custom keyboard layout
     let code = guess_code_from_crossterm_key_code(event.code)?;
-    let modifiers = modifiers_from_crossterm_modifiers(event.modifiers);
+    let mut modifiers = modifiers_from_crossterm_modifiers(event.modifiers);
+    // some terminals send BackTab for shift+tab without the shift modifier
+    if event.code == TermKeyCode::BackTab {
+        modifiers |= Modifiers::SHIFT;
+    }
 
     Some(EventData::Keyboard(KeyboardData::new(
         key,
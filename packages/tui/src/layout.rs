@@ -6,6 +6,10 @@ use dioxus_native_core::node_ref::{AttributeMask, NodeMask, NodeView};
 use dioxus_native_core::state::ChildDepState;
 use dioxus_native_core_macro::sorted_str_slice;
 use taffy::prelude::*;
+use taffy::style::{
+    MaxTrackSizingFunction, MinTrackSizingFunction, NonRepeatedTrackSizingFunction,
+    TrackSizingFunction,
+};
 
 use crate::{screen_to_layout_space, unit_to_layout_space};
 
@@ -77,10 +81,11 @@ impl ChildDepState for TaffyLayout {
             };
             if let PossiblyUninitalized::Initialized(n) = self.node {
                 if self.style != style {
-                    taffy.set_style(n, style).unwrap();
+                    taffy.set_style(n, style.clone()).unwrap();
                 }
             } else {
-                self.node = PossiblyUninitalized::Initialized(taffy.new_leaf(style).unwrap());
+                self.node =
+                    PossiblyUninitalized::Initialized(taffy.new_leaf(style.clone()).unwrap());
                 changed = true;
             }
         } else {
@@ -105,42 +110,34 @@ impl ChildDepState for TaffyLayout {
                 child_layout.push(l.node.unwrap());
             }
 
-            fn scale_dimention(d: Dimension) -> Dimension {
-                match d {
-                    Dimension::Points(p) => Dimension::Points(unit_to_layout_space(p)),
-                    Dimension::Percent(p) => Dimension::Percent(p),
-                    Dimension::Auto => Dimension::Auto,
-                    Dimension::Undefined => Dimension::Undefined,
-                }
-            }
             let style = Style {
-                position: Rect {
-                    left: scale_dimention(style.position.left),
-                    right: scale_dimention(style.position.right),
-                    top: scale_dimention(style.position.top),
-                    bottom: scale_dimention(style.position.bottom),
+                inset: Rect {
+                    left: scale_length_percentage_auto(style.inset.left),
+                    right: scale_length_percentage_auto(style.inset.right),
+                    top: scale_length_percentage_auto(style.inset.top),
+                    bottom: scale_length_percentage_auto(style.inset.bottom),
                 },
                 margin: Rect {
-                    left: scale_dimention(style.margin.left),
-                    right: scale_dimention(style.margin.right),
-                    top: scale_dimention(style.margin.top),
-                    bottom: scale_dimention(style.margin.bottom),
+                    left: scale_length_percentage_auto(style.margin.left),
+                    right: scale_length_percentage_auto(style.margin.right),
+                    top: scale_length_percentage_auto(style.margin.top),
+                    bottom: scale_length_percentage_auto(style.margin.bottom),
                 },
                 padding: Rect {
-                    left: scale_dimention(style.padding.left),
-                    right: scale_dimention(style.padding.right),
-                    top: scale_dimention(style.padding.top),
-                    bottom: scale_dimention(style.padding.bottom),
+                    left: scale_length_percentage(style.padding.left),
+                    right: scale_length_percentage(style.padding.right),
+                    top: scale_length_percentage(style.padding.top),
+                    bottom: scale_length_percentage(style.padding.bottom),
                 },
                 border: Rect {
-                    left: scale_dimention(style.border.left),
-                    right: scale_dimention(style.border.right),
-                    top: scale_dimention(style.border.top),
-                    bottom: scale_dimention(style.border.bottom),
+                    left: scale_length_percentage(style.border.left),
+                    right: scale_length_percentage(style.border.right),
+                    top: scale_length_percentage(style.border.top),
+                    bottom: scale_length_percentage(style.border.bottom),
                 },
                 gap: Size {
-                    width: scale_dimention(style.gap.width),
-                    height: scale_dimention(style.gap.height),
+                    width: scale_length_percentage(style.gap.width),
+                    height: scale_length_percentage(style.gap.height),
                 },
                 flex_basis: scale_dimention(style.flex_basis),
                 size: Size {
@@ -155,19 +152,25 @@ impl ChildDepState for TaffyLayout {
                     width: scale_dimention(style.max_size.width),
                     height: scale_dimention(style.max_size.height),
                 },
+                grid_template_rows: scale_tracks(&style.grid_template_rows),
+                grid_template_columns: scale_tracks(&style.grid_template_columns),
+                grid_auto_rows: style.grid_auto_rows.iter().map(scale_track).collect(),
+                grid_auto_columns: style.grid_auto_columns.iter().map(scale_track).collect(),
                 ..style
             };
 
             if let PossiblyUninitalized::Initialized(n) = self.node {
                 if self.style != style {
-                    taffy.set_style(n, style).unwrap();
+                    taffy.set_style(n, style.clone()).unwrap();
                 }
                 if taffy.children(n).unwrap() != child_layout {
                     taffy.set_children(n, &child_layout).unwrap();
                 }
             } else {
                 self.node = PossiblyUninitalized::Initialized(
-                    taffy.new_with_children(style, &child_layout).unwrap(),
+                    taffy
+                        .new_with_children(style.clone(), &child_layout)
+                        .unwrap(),
                 );
                 changed = true;
             }
@@ -180,6 +183,61 @@ impl ChildDepState for TaffyLayout {
     }
 }
 
+fn scale_length_percentage(l: LengthPercentage) -> LengthPercentage {
+    match l {
+        LengthPercentage::Points(p) => LengthPercentage::Points(unit_to_layout_space(p)),
+        LengthPercentage::Percent(p) => LengthPercentage::Percent(p),
+    }
+}
+
+fn scale_length_percentage_auto(l: LengthPercentageAuto) -> LengthPercentageAuto {
+    match l {
+        LengthPercentageAuto::Points(p) => LengthPercentageAuto::Points(unit_to_layout_space(p)),
+        LengthPercentageAuto::Percent(p) => LengthPercentageAuto::Percent(p),
+        LengthPercentageAuto::Auto => LengthPercentageAuto::Auto,
+    }
+}
+
+fn scale_dimention(d: Dimension) -> Dimension {
+    match d {
+        Dimension::Points(p) => Dimension::Points(unit_to_layout_space(p)),
+        Dimension::Percent(p) => Dimension::Percent(p),
+        Dimension::Auto => Dimension::Auto,
+    }
+}
+
+fn scale_track(track: &NonRepeatedTrackSizingFunction) -> NonRepeatedTrackSizingFunction {
+    NonRepeatedTrackSizingFunction {
+        min: match track.min {
+            MinTrackSizingFunction::Fixed(l) => {
+                MinTrackSizingFunction::Fixed(scale_length_percentage(l))
+            }
+            min => min,
+        },
+        max: match track.max {
+            MaxTrackSizingFunction::Fixed(l) => {
+                MaxTrackSizingFunction::Fixed(scale_length_percentage(l))
+            }
+            MaxTrackSizingFunction::FitContent(l) => {
+                MaxTrackSizingFunction::FitContent(scale_length_percentage(l))
+            }
+            max => max,
+        },
+    }
+}
+
+fn scale_tracks(tracks: &[TrackSizingFunction]) -> Vec<TrackSizingFunction> {
+    tracks
+        .iter()
+        .map(|track| match track {
+            TrackSizingFunction::Single(track) => TrackSizingFunction::Single(scale_track(track)),
+            TrackSizingFunction::Repeat(repetition, tracks) => {
+                TrackSizingFunction::Repeat(*repetition, tracks.iter().map(scale_track).collect())
+            }
+        })
+        .collect()
+}
+
 // these are the attributes in layout_attiributes in native-core
 const SORTED_LAYOUT_ATTRS: &[&str] = &sorted_str_slice!([
     "align-content",
@@ -333,5 +391,26 @@ const SORTED_LAYOUT_ATTRS: &[&str] = &sorted_str_slice!([
     "word-break",
     "word-spacing",
     "word-wrap",
+    "gap",
+    "grid",
+    "grid-area",
+    "grid-auto-columns",
+    "grid-auto-flow",
+    "grid-auto-rows",
+    "grid-column",
+    "grid-column-end",
+    "grid-column-start",
+    "grid-row",
+    "grid-row-end",
+    "grid-row-start",
+    "grid-template",
+    "grid-template-areas",
+    "grid-template-columns",
+    "grid-template-rows",
+    "inset",
+    "justify-items",
+    "justify-self",
+    "row-gap",
+    "aspect-ratio",
     "z-index"
 ]);
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use clipboard::Clipboard;
 use crossterm::{
     cursor::{MoveTo, RestorePosition, SavePosition, Show},
     event::{DisableMouseCapture, EnableMouseCapture, Event as TermEvent, KeyCode, KeyModifiers},
@@ -7,7 +8,7 @@ use crossterm::{
 };
 use dioxus_core::*;
 use dioxus_native_core::{real_dom::RealDom, FxDashSet, NodeId, NodeMask, SendAnyMap};
-use focus::FocusState;
+use focus::FocusRequest;
 use futures::{
     channel::mpsc::{UnboundedReceiver, UnboundedSender},
     pin_mut, StreamExt,
@@ -24,6 +25,7 @@ use taffy::Taffy;
 pub use taffy::{geometry::Point, prelude::*};
 use tui::{backend::CrosstermBackend, layout::Rect, Terminal};
 
+mod clipboard;
 mod config;
 mod focus;
 mod hooks;
@@ -32,6 +34,7 @@ mod node;
 pub mod prelude;
 pub mod query;
 mod render;
+mod scroll;
 mod style;
 mod style_attributes;
 mod widget;
@@ -68,6 +71,27 @@ impl TuiContext {
             .unbounded_send(InputEvent::UserInput(event))
             .unwrap();
     }
+
+    /// Move the focus to the next focusable element, like pressing tab does
+    pub fn focus_next(&self) {
+        self.tx
+            .unbounded_send(InputEvent::Focus(FocusRequest::Next))
+            .unwrap();
+    }
+
+    /// Move the focus to the previous focusable element, like pressing shift+tab does
+    pub fn focus_prev(&self) {
+        self.tx
+            .unbounded_send(InputEvent::Focus(FocusRequest::Prev))
+            .unwrap();
+    }
+
+    /// Focus an element, if it can be focused because it has a `tabindex` of 0 or more, or listens to key presses
+    pub fn focus(&self, id: ElementId) {
+        self.tx
+            .unbounded_send(InputEvent::Focus(FocusRequest::Element(id)))
+            .unwrap();
+    }
 }
 
 pub fn launch(app: Component<()>) {
@@ -105,6 +129,7 @@ pub fn launch_cfg_with_props<Props: 'static>(app: Component<Props>, props: Props
     let taffy = Arc::new(Mutex::new(Taffy::new()));
     cx.provide_context(state);
     cx.provide_context(TuiContext { tx: event_tx_clone });
+    cx.provide_context(Clipboard::new(!cfg.headless));
     cx.provide_context(Query {
         rdom: rdom.clone(),
         stretch: taffy.clone(),
@@ -184,7 +209,7 @@ fn render_vdom(
 
                         // the root node fills the entire area
 
-                        let mut style = *taffy.style(root_node).unwrap();
+                        let mut style = taffy.style(root_node).unwrap().clone();
                         style.size = Size {
                             width: Dimension::Points(width),
                             height: Dimension::Points(height),
@@ -203,9 +228,18 @@ fn render_vdom(
                             let rdom = rdom.borrow();
                             let mut taffy = taffy.lock().expect("taffy lock poisoned");
                             // size is guaranteed to not change when rendering
-                            resize(frame.size(), &mut taffy, &rdom);
+                            let size = frame.size();
+                            resize(size, &mut taffy, &rdom);
                             let root = &rdom[NodeId(0)];
-                            render::render_vnode(frame, &taffy, &rdom, root, cfg, Point::ZERO);
+                            render::render_vnode(
+                                frame,
+                                &taffy,
+                                &rdom,
+                                root,
+                                cfg,
+                                Point::ZERO,
+                                size,
+                            );
                         })?;
                         execute!(terminal.backend_mut(), RestorePosition, Show).unwrap();
                     } else {
@@ -246,6 +280,9 @@ fn render_vdom(
                                     TermEvent::Resize(_, _) => updated = true,
                                     TermEvent::Mouse(_) => {}
                                 },
+                                InputEvent::Focus(request) => {
+                                    handler.state().focus_requests.push(*request)
+                                }
                                 InputEvent::Close => break,
                             };
 
@@ -261,11 +298,34 @@ fn render_vdom(
                         let mut rdom = rdom.borrow_mut();
                         handler.get_events(&taffy.lock().expect("taffy lock poisoned"), &mut rdom)
                     };
-                    {
-                        updated |= handler.state().focus_state.clean();
-                    }
                     for e in evts {
-                        vdom.handle_event(e.name, e.data, e.id, e.bubbles)
+                        vdom.handle_event(e.name, e.data, e.id, e.bubbles);
+                    }
+                    // each key goes to the element focused when it's pressed, and only does its default, like
+                    // moving the focus with tab, if none of its listeners prevented it
+                    loop {
+                        let key = handler.next_key(&rdom.borrow());
+                        let (key, evts) = match key {
+                            Some(key) => key,
+                            None => break,
+                        };
+                        let mut prevented = false;
+                        for e in evts {
+                            prevented |= vdom.handle_event(e.name, e.data, e.id, e.bubbles);
+                        }
+                        if !prevented {
+                            let evts = handler.key_default(
+                                &key,
+                                &taffy.lock().expect("taffy lock poisoned"),
+                                &mut rdom.borrow_mut(),
+                            );
+                            for e in evts {
+                                vdom.handle_event(e.name, e.data, e.id, e.bubbles);
+                            }
+                        }
+                    }
+                    {
+                        updated |= handler.state().clean();
                     }
                     let mut rdom = rdom.borrow_mut();
                     let mutations = vdom.render_immediate();
@@ -301,5 +361,6 @@ fn render_vdom(
 #[derive(Debug)]
 enum InputEvent {
     UserInput(TermEvent),
+    Focus(FocusRequest),
     Close,
 }
@@ -1,5 +1,6 @@
 use crate::focus::Focus;
 use crate::layout::TaffyLayout;
+use crate::scroll::{Overflow, ScrollOffset};
 use crate::style_attributes::StyleModifier;
 use dioxus_native_core::{real_dom::RealDom, state::*};
 use dioxus_native_core_macro::{sorted_str_slice, State};
@@ -16,8 +17,11 @@ pub(crate) struct NodeState {
     #[node_dep_state()]
     pub prevent_default: PreventDefault,
     #[node_dep_state()]
+    pub overflow: Overflow,
+    #[node_dep_state(overflow)]
     pub focus: Focus,
     pub focused: bool,
+    pub scroll: ScrollOffset,
 }
 
 #[derive(PartialEq, Debug, Clone)]
@@ -8,10 +8,11 @@ use taffy::{
 use tui::{backend::CrosstermBackend, layout::Rect, style::Color};
 
 use crate::{
-    layout_to_screen_space,
+    layout_to_screen_space, screen_to_layout_space,
+    scroll::max_scroll,
     style::{RinkColor, RinkStyle},
     style_attributes::{BorderEdge, BorderStyle},
-    widget::{RinkBuffer, RinkCell, RinkWidget, WidgetWithContext},
+    widget::{intersect, RinkBuffer, RinkCell, RinkWidget, WidgetWithContext},
     Config, TuiDom, TuiNode,
 };
 
@@ -24,6 +25,7 @@ pub(crate) fn render_vnode(
     node: &TuiNode,
     cfg: Config,
     parent_location: Point<f32>,
+    clip: Rect,
 ) {
     use dioxus_native_core::node::NodeType;
 
@@ -38,11 +40,16 @@ pub(crate) fn render_vnode(
     location.y += parent_location.y;
 
     let Point { x: fx, y: fy } = location;
-    let x = layout_to_screen_space(fx).round() as u16;
-    let y = layout_to_screen_space(fy).round() as u16;
+    // nodes inside of a scrolled element can start above or left of the screen
+    let (start_x, start_y) = (
+        layout_to_screen_space(fx).round() as i32,
+        layout_to_screen_space(fy).round() as i32,
+    );
+    let x = start_x.max(0) as u16;
+    let y = start_y.max(0) as u16;
     let Size { width, height } = *size;
-    let width = layout_to_screen_space(fx + width).round() as u16 - x;
-    let height = layout_to_screen_space(fy + height).round() as u16 - y;
+    let width = (layout_to_screen_space(fx + width).round() as i32 - x as i32).max(0) as u16;
+    let height = (layout_to_screen_space(fy + height).round() as i32 - y as i32).max(0) as u16;
 
     match &node.node_data.node_type {
         NodeType::Text { text } => {
@@ -50,11 +57,13 @@ pub(crate) fn render_vnode(
             struct Label<'a> {
                 text: &'a str,
                 style: RinkStyle,
+                // the characters that are left of the screen
+                skip: usize,
             }
 
             impl<'a> RinkWidget for Label<'a> {
                 fn render(self, area: Rect, mut buf: RinkBuffer) {
-                    for (i, c) in self.text.char_indices() {
+                    for (i, c) in self.text.chars().skip(self.skip).enumerate() {
                         let mut new_cell = RinkCell::default();
                         new_cell.set_style(self.style);
                         new_cell.symbol = c.to_string();
@@ -63,27 +72,71 @@ pub(crate) fn render_vnode(
                 }
             }
 
+            // text is one line tall, so it's hidden once it's above the screen
+            if start_y < 0 {
+                return;
+            }
             let label = Label {
                 text,
                 style: node.state.style.core,
+                skip: (-start_x).max(0) as usize,
             };
             let area = Rect::new(x, y, width, height);
 
             // the renderer will panic if a node is rendered out of range even if the size is zero
-            if area.width > 0 && area.height > 0 {
-                frame.render_widget(WidgetWithContext::new(label, cfg), area);
+            if area.width > 0 && area.height > 0 && intersect(area, clip).area() > 0 {
+                frame.render_widget(WidgetWithContext::new(label, cfg, clip), area);
             }
         }
         NodeType::Element { .. } => {
             let area = Rect::new(x, y, width, height);
 
             // the renderer will panic if a node is rendered out of range even if the size is zero
-            if area.width > 0 && area.height > 0 {
-                frame.render_widget(WidgetWithContext::new(node, cfg), area);
+            if area.width > 0 && area.height > 0 && intersect(area, clip).area() > 0 {
+                frame.render_widget(WidgetWithContext::new(node, cfg, clip), area);
             }
 
-            for c in rdom.children_ids(node.node_data.node_id).unwrap() {
-                render_vnode(frame, layout, rdom, &rdom[*c], cfg, location);
+            // the children of an element that hides what overflows it are cut off inside of its borders
+            let overflow = node.state.overflow;
+            let borders = &node.state.style.modifier.borders;
+            let border = |edge: &BorderEdge| u16::from(edge.style.symbol_set().is_some());
+            let inner = Rect {
+                x: area.x + border(&borders.left),
+                y: area.y + border(&borders.top),
+                width: area
+                    .width
+                    .saturating_sub(border(&borders.left) + border(&borders.right)),
+                height: area
+                    .height
+                    .saturating_sub(border(&borders.top) + border(&borders.bottom)),
+            };
+            let (clip_x, clip_width) = match overflow.x.clips() {
+                true => (inner.x, inner.width),
+                false => (clip.x, clip.width),
+            };
+            let (clip_y, clip_height) = match overflow.y.clips() {
+                true => (inner.y, inner.height),
+                false => (clip.y, clip.height),
+            };
+            let child_clip = intersect(clip, Rect::new(clip_x, clip_y, clip_width, clip_height));
+
+            // the contents shrinking can leave an element scrolled further than it can go now
+            let id = node.node_data.node_id;
+            let max = max_scroll(rdom, layout, id);
+            let mut child_location = location;
+            child_location.x -= screen_to_layout_space(node.state.scroll.x.min(max.x));
+            child_location.y -= screen_to_layout_space(node.state.scroll.y.min(max.y));
+
+            for c in rdom.children_ids(id).unwrap() {
+                render_vnode(
+                    frame,
+                    layout,
+                    rdom,
+                    &rdom[*c],
+                    cfg,
+                    child_location,
+                    child_clip,
+                );
             }
         }
         NodeType::Placeholder => unreachable!(),
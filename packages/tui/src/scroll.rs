@@ -0,0 +1,201 @@
+use dioxus_html::input_data::keyboard_types::Code;
+use dioxus_html::KeyboardData;
+use dioxus_native_core::{
+    node_ref::{AttributeMask, NodeMask, NodeView},
+    state::NodeDepState,
+    tree::TreeView,
+    RealNodeId,
+};
+use dioxus_native_core_macro::sorted_str_slice;
+use taffy::Taffy;
+
+use crate::{layout_to_screen_space, TuiDom};
+
+/// How many rows a tick of the mouse wheel scrolls
+const WHEEL_STEP: i32 = 3;
+
+/// What an element does with the children that don't fit inside of it, along one axis
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum OverflowKind {
+    Visible,
+    Hidden,
+    Scroll,
+}
+
+impl OverflowKind {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "visible" => Some(OverflowKind::Visible),
+            "hidden" | "clip" => Some(OverflowKind::Hidden),
+            "scroll" | "auto" => Some(OverflowKind::Scroll),
+            _ => None,
+        }
+    }
+
+    pub fn clips(&self) -> bool {
+        *self != OverflowKind::Visible
+    }
+}
+
+impl Default for OverflowKind {
+    fn default() -> Self {
+        OverflowKind::Visible
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub(crate) struct Overflow {
+    pub x: OverflowKind,
+    pub y: OverflowKind,
+}
+
+impl Overflow {
+    pub fn scrolls(&self) -> bool {
+        self.x == OverflowKind::Scroll || self.y == OverflowKind::Scroll
+    }
+}
+
+impl NodeDepState for Overflow {
+    type DepState = ();
+    type Ctx = ();
+    const NODE_MASK: NodeMask =
+        NodeMask::new_with_attrs(AttributeMask::Static(OVERFLOW_ATTRIBUTES));
+
+    fn reduce(&mut self, node: NodeView<'_>, _sibling: (), _: &Self::Ctx) -> bool {
+        let mut new = Overflow::default();
+        if let Some(attributes) = node.attributes() {
+            for a in attributes {
+                let value = match a.value.as_text() {
+                    Some(value) => value,
+                    None => continue,
+                };
+                match a.attribute.name.as_str() {
+                    // overflow: hidden scroll sets both axes, and a single value sets both to it
+                    "overflow" => {
+                        let mut values = value.split_whitespace().filter_map(OverflowKind::parse);
+                        if let Some(x) = values.next() {
+                            new.x = x;
+                            new.y = values.next().unwrap_or(x);
+                        }
+                    }
+                    "overflow-x" => new.x = OverflowKind::parse(value).unwrap_or(new.x),
+                    "overflow-y" => new.y = OverflowKind::parse(value).unwrap_or(new.y),
+                    _ => {}
+                }
+            }
+        }
+        if *self != new {
+            *self = new;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+const OVERFLOW_ATTRIBUTES: &[&str] = &sorted_str_slice!(["overflow", "overflow-x", "overflow-y"]);
+
+/// How far the contents of an element are scrolled, in cells
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub(crate) struct ScrollOffset {
+    pub x: u16,
+    pub y: u16,
+}
+
+/// How far the contents of an element can be scrolled before the end of them comes into view
+pub(crate) fn max_scroll(rdom: &TuiDom, taffy: &Taffy, id: RealNodeId) -> ScrollOffset {
+    let node = &rdom[id];
+    let size = match node.state.layout.node.ok() {
+        Some(n) => taffy.layout(n).unwrap().size,
+        None => return ScrollOffset::default(),
+    };
+
+    // the contents end at the far edge of the child that reaches the furthest
+    let (mut right, mut bottom) = (0.0f32, 0.0f32);
+    for child in rdom.children_ids(id).into_iter().flatten() {
+        if let Some(n) = rdom[*child].state.layout.node.ok() {
+            let layout = taffy.layout(n).unwrap();
+            right = right.max(layout.location.x + layout.size.width);
+            bottom = bottom.max(layout.location.y + layout.size.height);
+        }
+    }
+
+    // the contents can't go under the border on the far side
+    let borders = &node.state.style.modifier.borders;
+    let border_right = u16::from(borders.right.style.symbol_set().is_some());
+    let border_bottom = u16::from(borders.bottom.style.symbol_set().is_some());
+    let overflow = node.state.overflow;
+
+    let max = |end: f32, size: f32, border: u16, kind: OverflowKind| {
+        if kind != OverflowKind::Scroll {
+            return 0;
+        }
+        let end = layout_to_screen_space(end).round() as i32;
+        let size = layout_to_screen_space(size).round() as i32 - border as i32;
+        (end - size).max(0) as u16
+    };
+
+    ScrollOffset {
+        x: max(right, size.width, border_right, overflow.x),
+        y: max(bottom, size.height, border_bottom, overflow.y),
+    }
+}
+
+/// Scroll an element by some cells, as far as it can go. Returns true if it scrolled.
+pub(crate) fn scroll_by(
+    rdom: &mut TuiDom,
+    taffy: &Taffy,
+    id: RealNodeId,
+    dx: i32,
+    dy: i32,
+) -> bool {
+    let max = max_scroll(rdom, taffy, id);
+    let scroll = &mut rdom[id].state.scroll;
+    let new = ScrollOffset {
+        x: (scroll.x as i32 + dx).clamp(0, max.x as i32) as u16,
+        y: (scroll.y as i32 + dy).clamp(0, max.y as i32) as u16,
+    };
+    if *scroll != new {
+        *scroll = new;
+        true
+    } else {
+        false
+    }
+}
+
+/// Scroll an element with a tick of the mouse wheel. Returns true if it scrolled.
+pub(crate) fn scroll_wheel(rdom: &mut TuiDom, taffy: &Taffy, id: RealNodeId, delta_y: f64) -> bool {
+    let dy = if delta_y < 0.0 {
+        -WHEEL_STEP
+    } else {
+        WHEEL_STEP
+    };
+    scroll_by(rdom, taffy, id, 0, dy)
+}
+
+/// Scroll an element with the arrows, page up, page down, home and end keys. Returns true if it scrolled.
+pub(crate) fn scroll_key(
+    rdom: &mut TuiDom,
+    taffy: &Taffy,
+    id: RealNodeId,
+    key: &KeyboardData,
+) -> bool {
+    let page = match rdom[id].state.layout.node.ok() {
+        Some(n) => layout_to_screen_space(taffy.layout(n).unwrap().size.height) as i32,
+        None => 0,
+    };
+    // far enough to reach the end of any element
+    let all = i32::from(u16::MAX);
+    let (dx, dy) = match key.code() {
+        Code::ArrowUp => (0, -1),
+        Code::ArrowDown => (0, 1),
+        Code::ArrowLeft => (-1, 0),
+        Code::ArrowRight => (1, 0),
+        Code::PageUp => (0, -page.max(1)),
+        Code::PageDown => (0, page.max(1)),
+        Code::Home => (0, -all),
+        Code::End => (0, all),
+        _ => return false,
+    };
+    scroll_by(rdom, taffy, id, dx, dy)
+}
@@ -13,15 +13,18 @@ use crate::{
 pub struct RinkBuffer<'a> {
     buf: &'a mut Buffer,
     cfg: Config,
+    // the part of the buffer the widget can draw in, outside of which an element hides what overflows it
+    clip: Rect,
 }
 
 impl<'a> RinkBuffer<'a> {
-    fn new(buf: &'a mut Buffer, cfg: Config) -> RinkBuffer<'a> {
-        Self { buf, cfg }
+    fn new(buf: &'a mut Buffer, cfg: Config, clip: Rect) -> RinkBuffer<'a> {
+        let clip = intersect(clip, *buf.area());
+        Self { buf, cfg, clip }
     }
 
     pub fn set(&mut self, x: u16, y: u16, new: RinkCell) {
-        let area = self.buf.area();
+        let area = self.clip;
         if x < area.x || x >= area.width + area.x || y < area.y || y >= area.height + area.y {
             // panic!("({x}, {y}) is not in {area:?}");
             return;
@@ -41,6 +44,18 @@ impl<'a> RinkBuffer<'a> {
     }
 }
 
+/// The part of two rects that is in both of them, which is empty if they don't overlap
+pub(crate) fn intersect(a: Rect, b: Rect) -> Rect {
+    let x = a.x.max(b.x);
+    let y = a.y.max(b.y);
+    Rect {
+        x,
+        y,
+        width: a.right().min(b.right()).saturating_sub(x),
+        height: a.bottom().min(b.bottom()).saturating_sub(y),
+    }
+}
+
 pub trait RinkWidget {
     fn render(self, area: Rect, buf: RinkBuffer);
 }
@@ -48,17 +63,23 @@ pub trait RinkWidget {
 pub struct WidgetWithContext<T: RinkWidget> {
     widget: T,
     config: Config,
+    clip: Rect,
 }
 
 impl<T: RinkWidget> WidgetWithContext<T> {
-    pub fn new(widget: T, config: Config) -> WidgetWithContext<T> {
-        WidgetWithContext { widget, config }
+    pub fn new(widget: T, config: Config, clip: Rect) -> WidgetWithContext<T> {
+        WidgetWithContext {
+            widget,
+            config,
+            clip,
+        }
     }
 }
 
 impl<T: RinkWidget> Widget for WidgetWithContext<T> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        self.widget.render(area, RinkBuffer::new(buf, self.config));
+        self.widget
+            .render(area, RinkBuffer::new(buf, self.config, self.clip));
     }
 }
 
@@ -1,8 +1,9 @@
+use crate::clipboard::Clipboard;
 use crate::widgets::get_root_id;
 use crate::Query;
 use crossterm::{cursor::*, execute};
 use dioxus::prelude::*;
-use dioxus_elements::input_data::keyboard_types::Key;
+use dioxus_elements::input_data::keyboard_types::{Code, Key, Modifiers};
 use dioxus_html as dioxus_elements;
 use dioxus_html::FormData;
 use dioxus_native_core::utils::cursor::{Cursor, Pos};
@@ -28,6 +29,7 @@ pub(crate) struct TextBoxProps<'a> {
 pub(crate) fn TextBox<'a>(cx: Scope<'a, TextBoxProps>) -> Element<'a> {
     let tui_query: Query = cx.consume_context().unwrap();
     let tui_query_clone = tui_query.clone();
+    let clipboard: Clipboard = cx.consume_context().unwrap();
 
     let text_ref = use_ref(cx, || {
         if let Some(intial_text) = cx.props.value {
@@ -84,13 +86,27 @@ pub(crate) fn TextBox<'a>(cx: Scope<'a, TextBoxProps>) -> Element<'a> {
             width: "{width}",
             height: "{height}",
             border_style: "{border}",
+            overflow: "hidden",
 
             onkeydown: move |k| {
                 if k.key() == Key::Enter {
                     return;
                 }
                 let mut text = text_ref.write();
-                cursor.write().handle_input(&k, &mut text, max_len);
+                let ctrl = k.modifiers().contains(Modifiers::CONTROL);
+                match k.code() {
+                    Code::KeyC if ctrl => clipboard.set(cursor.read().selection(&text)),
+                    Code::KeyX if ctrl => {
+                        clipboard.set(cursor.read().selection(&text));
+                        cursor.write().insert_str(&mut text, "", max_len);
+                    }
+                    Code::KeyV if ctrl => {
+                        // textboxs are only one line tall
+                        let pasted = clipboard.get().replace('\n', " ");
+                        cursor.write().insert_str(&mut text, &pasted, max_len);
+                    }
+                    _ => cursor.write().handle_input(&k, &mut text, max_len),
+                }
                 if let Some(input_handler) = &cx.props.raw_oninput{
                     input_handler.call(FormData{
                         value: text.clone(),
@@ -372,3 +372,84 @@ fn context_menu() {
         })
     }
 }
+
+#[test]
+fn focus_next() {
+    dioxus_tui::launch_cfg(app, dioxus_tui::Config::new().with_headless());
+
+    fn app(cx: Scope) -> Element {
+        let render_count = use_state(cx, || 0);
+        let tui_ctx: TuiContext = cx.consume_context().unwrap();
+        let render_count_handle = render_count.clone();
+        cx.spawn(async move {
+            PollN::new(3).await;
+            render_count_handle.modify(|x| *x + 1);
+        });
+        if *render_count.get() > 2 {
+            panic!("Event was not received");
+        }
+        // focus the first element, then the second one
+        tui_ctx.focus_next();
+        tui_ctx.focus_next();
+        cx.render(rsx! {
+            div {
+                width: "100%",
+                height: "50%",
+                tabindex: "0",
+                onfocusout: move |_| {
+                    tui_ctx.quit();
+                },
+            }
+            div {
+                width: "100%",
+                height: "50%",
+                tabindex: "0",
+            }
+        })
+    }
+}
+
+#[test]
+fn prevent_default_keeps_the_focus() {
+    dioxus_tui::launch_cfg(app, dioxus_tui::Config::new().with_headless());
+
+    fn app(cx: Scope) -> Element {
+        let render_count = use_state(cx, || 0);
+        let tui_ctx: TuiContext = cx.consume_context().unwrap();
+        let render_count_handle = render_count.clone();
+        cx.spawn(async move {
+            PollN::new(3).await;
+            render_count_handle.modify(|x| *x + 1);
+        });
+        if *render_count.get() > 2 {
+            panic!("Event was not received");
+        }
+        // focus the first element, then try to tab away from it
+        for code in [KeyCode::Tab, KeyCode::Tab, KeyCode::Char('a')] {
+            tui_ctx.inject_event(Event::Key(KeyEvent {
+                code,
+                modifiers: KeyModifiers::NONE,
+            }));
+        }
+        cx.render(rsx! {
+            div {
+                width: "100%",
+                height: "50%",
+                tabindex: "0",
+                onkeydown: move |evt| {
+                    if evt.data.code() == Code::Tab {
+                        evt.prevent_default();
+                    } else {
+                        tui_ctx.quit();
+                    }
+                },
+            }
+            div {
+                width: "100%",
+                height: "50%",
+                tabindex: "0",
+                onfocus: move |_| panic!("the focus moved"),
+            }
+        })
+    }
+}
@@ -1,3 +1,5 @@
+use dioxus_core::{EventDispatch, ScopeState};
+
 ///  Configuration for the WebSys renderer for the Dioxus VirtualDOM.
 ///
 /// This struct helps configure the specifics of hydration and render destination for WebSys.
@@ -10,8 +12,17 @@
 pub struct Config {
     pub(crate) hydrate: bool,
     pub(crate) rootname: String,
+    pub(crate) root_selector: Option<String>,
+    pub(crate) root_element: Option<web_sys::Element>,
+    pub(crate) shadow_root: Option<Vec<String>>,
     pub(crate) cached_strings: Vec<String>,
     pub(crate) default_panic_hook: bool,
+    pub(crate) error_overlay: bool,
+    pub(crate) render_stats_overlay: bool,
+    pub(crate) root_contexts: Vec<Box<dyn Fn(&ScopeState)>>,
+    pub(crate) event_dispatch: Vec<(String, EventDispatch)>,
+    #[cfg(feature = "inspector")]
+    pub(crate) inspector: Option<String>,
 }
 
 impl Default for Config {
@@ -19,8 +30,17 @@ impl Default for Config {
         Self {
             hydrate: false,
             rootname: "main".to_string(),
+            root_selector: None,
+            root_element: None,
+            shadow_root: None,
             cached_strings: Vec::new(),
             default_panic_hook: true,
+            error_overlay: true,
+            render_stats_overlay: false,
+            root_contexts: Vec::new(),
+            event_dispatch: Vec::new(),
+            #[cfg(feature = "inspector")]
+            inspector: None,
         }
     }
 }
@@ -52,6 +72,34 @@ impl Config {
         self
     }
 
+    /// Mount the app under the first element matching a css selector, as one of possibly many apps on the page.
+    ///
+    /// Apps launched on a selector can coexist with each other, but not with an app launched on the [`Config::rootname`].
+    /// This takes priority over the rootname.
+    pub fn root_selector(mut self, selector: impl Into<String>) -> Self {
+        self.root_selector = Some(selector.into());
+        self
+    }
+
+    /// Mount the app in a shadow root attached to its root element, which adopts the stylesheets
+    ///
+    /// The styles of the page don't reach the elements of the app and the stylesheets don't reach the page, so the app
+    /// can be embedded in pages it doesn't control. Use the `ShadowRoot` node of `dioxus-html` to isolate just a part of
+    /// the app.
+    ///
+    /// ```rust, ignore
+    /// dioxus_web::launch_cfg(
+    ///     App,
+    ///     Config::new()
+    ///         .root_selector("#widget")
+    ///         .with_shadow_root(vec![include_str!("widget.css").to_string()]),
+    /// );
+    /// ```
+    pub fn with_shadow_root(mut self, stylesheets: Vec<String>) -> Self {
+        self.shadow_root = Some(stylesheets);
+        self
+    }
+
     /// Set the name of the element that Dioxus will use as the root.
     ///
     /// This is akint to calling React.render() on the element with the specified name.
@@ -67,4 +115,63 @@ impl Config {
         self.default_panic_hook = f;
         self
     }
+
+    /// Set whether or not debug builds show panics and uncaught errors in an overlay on the page.
+    ///
+    /// The overlay is on by default. Release builds never show it.
+    pub fn with_error_overlay(mut self, f: bool) -> Self {
+        self.error_overlay = f;
+        self
+    }
+
+    /// Set whether or not debug builds show how each component renders in a panel on the page.
+    ///
+    /// The panel lists the components that rendered the most without changing anything, which are the ones worth
+    /// memoizing. It's off by default, and release builds never show it.
+    pub fn with_render_stats_overlay(mut self, f: bool) -> Self {
+        self.render_stats_overlay = f;
+        self
+    }
+
+    /// Provide a context to the root of the app before it's built, for the global services every component can reach
+    /// with `use_context`, like a database handle or the theme.
+    ///
+    /// The renderer's own contexts are provided first, so a context of the same type replaces them.
+    ///
+    /// ```rust, ignore
+    /// dioxus_web::launch_cfg(App, Config::new().with_context(Theme::Dark));
+    /// ```
+    pub fn with_context<T: Clone + 'static>(mut self, context: T) -> Self {
+        self.root_contexts.push(Box::new(move |scope| {
+            scope.provide_context(context.clone());
+        }));
+        self
+    }
+
+    /// Set whether the app handles an event type while the browser dispatches it, or once per animation frame
+    ///
+    /// Listeners of synchronous events can call `prevent_default`. Deferred events are merged, so a listener only sees
+    /// the last one that fired on its element before each frame. By default the events that fire many times a frame,
+    /// like `mousemove`, `scroll` and `input`, are deferred and the rest are synchronous.
+    ///
+    /// ```rust, ignore
+    /// dioxus_web::launch_cfg(App, Config::new().with_event_dispatch("wheel", EventDispatch::Synchronous));
+    /// ```
+    pub fn with_event_dispatch(
+        mut self,
+        event: impl Into<String>,
+        dispatch: EventDispatch,
+    ) -> Self {
+        self.event_dispatch.push((event.into(), dispatch));
+        self
+    }
+
+    /// Connect to the inspector listening on a WebSocket url, like `ws://localhost:8890`.
+    ///
+    /// The inspector sends the requests of the devtools protocol, and the app answers them with what's mounted in it.
+    #[cfg(feature = "inspector")]
+    pub fn with_inspector(mut self, url: impl Into<String>) -> Self {
+        self.inspector = Some(url.into());
+        self
+    }
 }
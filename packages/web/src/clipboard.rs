@@ -0,0 +1,72 @@
+use dioxus_hooks::{ClipboardBackend, ClipboardError, ClipboardFuture};
+use js_sys::{Function, Promise, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+/// Backs `use_clipboard` with the async Clipboard API of the browser
+///
+/// The API is only exposed on secure pages, and it's looked up on every call since `web-sys` only binds it behind its
+/// unstable APIs.
+pub(crate) struct WebClipboard;
+
+impl WebClipboard {
+    /// Call a method of `navigator.clipboard` and wait on the promise it returns
+    async fn call(method: &str, args: &[JsValue]) -> Result<JsValue, ClipboardError> {
+        let navigator = web_sys::window()
+            .ok_or(ClipboardError::Unavailable)?
+            .navigator();
+
+        let clipboard = Reflect::get(&navigator, &"clipboard".into())
+            .ok()
+            .filter(|clipboard| clipboard.is_object())
+            .ok_or(ClipboardError::Unavailable)?;
+
+        let function = Reflect::get(&clipboard, &method.into())
+            .ok()
+            .and_then(|f| f.dyn_into::<Function>().ok())
+            .ok_or(ClipboardError::Unavailable)?;
+
+        let args = args.iter().collect::<js_sys::Array>();
+        let promise = function
+            .apply(&clipboard, &args)
+            .map_err(to_error)?
+            .dyn_into::<Promise>()
+            .map_err(|_| ClipboardError::Other(format!("{} didn't return a promise", method)))?;
+
+        JsFuture::from(promise).await.map_err(to_error)
+    }
+}
+
+impl ClipboardBackend for WebClipboard {
+    fn read(&self) -> ClipboardFuture<String> {
+        Box::pin(async {
+            let text = Self::call("readText", &[]).await?;
+            Ok(text.as_string().unwrap_or_default())
+        })
+    }
+
+    fn write(&self, text: String) -> ClipboardFuture<()> {
+        Box::pin(async move {
+            Self::call("writeText", &[JsValue::from_str(&text)]).await?;
+            Ok(())
+        })
+    }
+}
+
+/// The browser rejects with a `NotAllowedError` when the user or the page's permissions don't allow the access
+fn to_error(err: JsValue) -> ClipboardError {
+    let name = Reflect::get(&err, &"name".into())
+        .ok()
+        .and_then(|name| name.as_string());
+    let message = Reflect::get(&err, &"message".into())
+        .ok()
+        .and_then(|message| message.as_string())
+        .unwrap_or_else(|| format!("{:?}", err));
+
+    match name.as_deref() {
+        Some("NotAllowedError") | Some("SecurityError") => {
+            ClipboardError::PermissionDenied(message)
+        }
+        _ => ClipboardError::Other(message),
+    }
+}
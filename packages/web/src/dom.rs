@@ -10,21 +10,263 @@
 use dioxus_core::{
     BorrowedAttributeValue, ElementId, Mutation, Template, TemplateAttribute, TemplateNode,
 };
-use dioxus_html::{event_bubbles, CompositionData, FormData};
-use dioxus_interpreter_js::{save_template, Channel};
-use futures_channel::mpsc;
+use dioxus_html::{event_bubbles, FormData, MountedData};
+#[cfg(feature = "extended_events")]
+use dioxus_html::{CompositionData, CustomData, MediaData};
+use dioxus_interpreter_js::{attach_shadow, get_node, initilize, save_template, set_node, Channel};
 use rustc_hash::FxHashMap;
-use std::{any::Any, rc::Rc};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 use wasm_bindgen::{closure::Closure, JsCast};
-use web_sys::{Document, Element, Event, HtmlElement};
+use web_sys::{Document, Element, Event, EventTarget, HtmlElement, Node};
 
-use crate::Config;
+use crate::{file_engine::WebFileEngine, queue::EventQueue, Config};
 
 pub struct WebsysDom {
     document: Document,
     templates: FxHashMap<String, u32>,
-    max_template_id: u32,
     interpreter: Channel,
+    root_id: ElementId,
+    event_queue: EventQueue,
+
+    // Elements with an onmounted listener, waiting for their edits to be flushed
+    queued_mounted_events: Vec<ElementId>,
+
+    // The values and checked states the app gave form elements, which they go back to when the app doesn't accept an
+    // edit to them
+    controlled_values: js_sys::WeakMap,
+    controlled_checked: js_sys::WeakMap,
+}
+
+/// An input event of a form element the app handled, and the state the element was in when it did
+pub struct ControlledInput {
+    element: Element,
+    state: Option<String>,
+}
+
+impl ControlledInput {
+    pub fn new(event: &UiEvent) -> Option<Self> {
+        let element: Element = event.origin.clone()?.dyn_into().ok()?;
+        let state = control_state(&element);
+        Some(Self { element, state })
+    }
+}
+
+/// The value of a form element, or if it's checked for checkboxes and radio buttons
+fn control_state(element: &Element) -> Option<String> {
+    if let Some(input) = element.dyn_ref::<web_sys::HtmlInputElement>() {
+        return Some(match input.type_().as_str() {
+            "checkbox" | "radio" => input.checked().to_string(),
+            _ => input.value(),
+        });
+    }
+    if let Some(textarea) = element.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+        return Some(textarea.value());
+    }
+    element
+        .dyn_ref::<web_sys::HtmlSelectElement>()
+        .map(|select| select.value())
+}
+
+/// How many element ids each app launched on a selector gets to itself
+const ISLAND_IDS: usize = 1 << 20;
+
+thread_local! {
+    // The event queue of every app launched on a selector, in the order they were launched
+    static ISLANDS: RefCell<Vec<EventQueue>> = RefCell::new(Vec::new());
+
+    static NEXT_TEMPLATE_ID: Cell<u32> = Cell::new(0);
+}
+
+/// Give an app launched on a selector its own range of ids in the interpreter, binding its root to the first one
+///
+/// Every island shares the interpreter, which listens for events on the whole document and sends each event to the
+/// island that owns the element.
+fn mount_island(
+    document: &Document,
+    root: Element,
+    cfg: &Config,
+    event_queue: EventQueue,
+) -> ElementId {
+    ISLANDS.with(|islands| {
+        let mut islands = islands.borrow_mut();
+
+        if islands.is_empty() {
+            let handler = event_handler(|event| {
+                ISLANDS.with(|islands| {
+                    let island = (event.element.0 / ISLAND_IDS).checked_sub(1);
+                    if let Some(queue) = island.and_then(|idx| islands.borrow().get(idx).cloned()) {
+                        queue.push(event);
+                    }
+                })
+            });
+            let listen_on = document
+                .document_element()
+                .expect("document has no root element");
+            initilize(listen_on.unchecked_into(), handler.as_ref().unchecked_ref());
+            handler.forget();
+        }
+
+        islands.push(event_queue);
+
+        let root_id = islands.len() * ISLAND_IDS;
+        set_node(root_id as u32, mount_point(root, cfg, true));
+        ElementId(root_id)
+    })
+}
+
+/// The node the app is mounted in: the root element, or a shadow root attached to it if the config asks for one
+///
+/// Apps that listen for events at the document need the shadow root to hand over the events that don't leave it.
+fn mount_point(root: Element, cfg: &Config, listens_at_document: bool) -> Node {
+    match &cfg.shadow_root {
+        Some(stylesheets) => {
+            attach_shadow(root.into(), &stylesheets.join("\n"), listens_at_document)
+        }
+        None => root.into(),
+    }
+}
+
+/// The element with the focus, inside of any shadow roots it's in
+fn active_element(document: &Document) -> Option<Element> {
+    let mut element = document.active_element()?;
+    // the document only sees the host of the shadow root the focus is in
+    while let Some(inner) = element
+        .shadow_root()
+        .and_then(|shadow| shadow.active_element())
+    {
+        element = inner;
+    }
+    Some(element)
+}
+
+/// The element that had the focus before the edits, with the selection of its text
+struct FocusedElement {
+    element: HtmlElement,
+    selection: Option<(u32, u32)>,
+}
+
+impl FocusedElement {
+    fn save(document: &Document) -> Option<Self> {
+        let element: HtmlElement = active_element(document)?.dyn_into().ok()?;
+        if element == document.body()? {
+            return None;
+        }
+
+        let selection = if let Some(input) = element.dyn_ref::<web_sys::HtmlInputElement>() {
+            // inputs like checkboxes don't have a selection
+            input
+                .selection_start()
+                .ok()
+                .flatten()
+                .zip(input.selection_end().ok().flatten())
+        } else if let Some(area) = element.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+            area.selection_start()
+                .ok()
+                .flatten()
+                .zip(area.selection_end().ok().flatten())
+        } else {
+            None
+        };
+
+        Some(Self { element, selection })
+    }
+
+    /// Give the focus back if a move took it, as long as the element is still in the document
+    fn restore(self, document: &Document) {
+        let still_focused =
+            active_element(document).map_or(false, |active| active == *self.element);
+        if still_focused || !self.element.is_connected() {
+            return;
+        }
+
+        let _ = self.element.focus();
+        if let Some((start, end)) = self.selection {
+            if let Some(input) = self.element.dyn_ref::<web_sys::HtmlInputElement>() {
+                let _ = input.set_selection_range(start, end);
+            } else if let Some(area) = self.element.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+                let _ = area.set_selection_range(start, end);
+            }
+        }
+    }
+}
+
+/// Remember the scroll offsets of a node that's about to move, and of the parent it's moving in
+fn save_scroll(scrolled: &mut Vec<(Element, i32, i32)>, id: ElementId) {
+    let node: Element = match get_node(id.0 as u32).dyn_into() {
+        Ok(node) => node,
+        Err(_) => return,
+    };
+    for element in std::iter::once(node.clone()).chain(node.parent_element()) {
+        let (top, left) = (element.scroll_top(), element.scroll_left());
+        if (top, left) != (0, 0) && !scrolled.iter().any(|(saved, _, _)| *saved == element) {
+            scrolled.push((element, top, left));
+        }
+    }
+}
+
+/// Focus the first new element with the `autofocus` attribute, returning whether there was one
+fn focus_autofocus(created: &[ElementId]) -> bool {
+    let autofocus = created.iter().find_map(|id| {
+        let node: Element = get_node(id.0 as u32).dyn_into().ok()?;
+        let element = match node.has_attribute("autofocus") {
+            true => Some(node),
+            false => node.query_selector("[autofocus]").ok().flatten(),
+        };
+        element.filter(|element| element.is_connected())
+    });
+
+    match autofocus.and_then(|element| element.dyn_into::<HtmlElement>().ok()) {
+        Some(element) => element.focus().is_ok(),
+        None => false,
+    }
+}
+
+/// Turn the browser events the interpreter catches into [`UiEvent`]s
+fn event_handler(send: impl Fn(UiEvent) + 'static) -> Closure<dyn FnMut(&Event)> {
+    Closure::wrap(Box::new(move |event: &web_sys::Event| {
+        let name = event.type_();
+
+        // The text isn't final until the composition ends, which sends an input event of its own
+        if name == "input"
+            && event
+                .dyn_ref::<web_sys::InputEvent>()
+                .map_or(false, |event| event.is_composing())
+        {
+            return;
+        }
+
+        let bubbles = dioxus_html::event_bubbles(name.as_str());
+        let element = walk_event_for_id(event, bubbles);
+        if let Some((element, target)) = element {
+            if target
+                .get_attribute("dioxus-prevent-default")
+                .as_deref()
+                .map(|f| f.trim_start_matches("on"))
+                == Some(&name)
+            {
+                event.prevent_default();
+            }
+
+            // Forms handled by dioxus never navigate away, like on desktop
+            if name == "submit" {
+                event.prevent_default();
+            }
+
+            let data = virtual_event_from_websys_event(event.clone(), target);
+            send(UiEvent {
+                name,
+                bubbles,
+                element,
+                data,
+                event: event.clone(),
+                origin: event_origin(event),
+            });
+        }
+    }))
 }
 
 pub struct UiEvent {
@@ -33,55 +275,61 @@ pub struct UiEvent {
     pub element: ElementId,
     pub data: Rc<dyn Any>,
     pub event: Event,
+    // where the event started, which is only known while it's dispatched if it came from a shadow root
+    pub origin: Option<EventTarget>,
 }
 
 impl WebsysDom {
-    pub fn new(cfg: Config, event_channel: mpsc::UnboundedSender<UiEvent>) -> Self {
+    pub fn new(cfg: Config, event_queue: EventQueue) -> Self {
         // eventually, we just want to let the interpreter do all the work of decoding events into our event type
         // a match here in order to avoid some error during runtime browser test
         let document = load_document();
-        let root = match document.get_element_by_id(&cfg.rootname) {
-            Some(root) => root,
-            None => document.create_element("body").ok().unwrap(),
-        };
         let interpreter = Channel::default();
 
-        let handler: Closure<dyn FnMut(&Event)> =
-            Closure::wrap(Box::new(move |event: &web_sys::Event| {
-                let name = event.type_();
-                let element = walk_event_for_id(event);
-                let bubbles = dioxus_html::event_bubbles(name.as_str());
-                if let Some((element, target)) = element {
-                    if target
-                        .get_attribute("dioxus-prevent-default")
-                        .as_deref()
-                        .map(|f| f.trim_start_matches("on"))
-                        == Some(&name)
-                    {
-                        event.prevent_default();
-                    }
-
-                    let data = virtual_event_from_websys_event(event.clone(), target);
-                    let _ = event_channel.unbounded_send(UiEvent {
-                        name,
-                        bubbles,
-                        element,
-                        data,
-                        event: event.clone(),
-                    });
-                }
-            }));
+        let island_queue = event_queue.clone();
+        let root_id = match (cfg.root_element.clone(), &cfg.root_selector) {
+            // islands mounted by `mount_islands` already found their element
+            (Some(root), _) => mount_island(&document, root, &cfg, island_queue),
+            (None, Some(selector)) => {
+                let root = document
+                    .query_selector(selector)
+                    .ok()
+                    .flatten()
+                    .unwrap_or_else(|| panic!("no element matches the selector {:?}", selector));
+                mount_island(&document, root, &cfg, island_queue)
+            }
+            (None, None) => {
+                let root = match document.get_element_by_id(&cfg.rootname) {
+                    Some(root) => root,
+                    None => document.create_element("body").ok().unwrap(),
+                };
+                let handler = event_handler(move |event| island_queue.push(event));
+                initilize(
+                    mount_point(root, &cfg, false),
+                    handler.as_ref().unchecked_ref(),
+                );
+                handler.forget();
+                ElementId(0)
+            }
+        };
 
-        dioxus_interpreter_js::initilize(root.unchecked_into(), handler.as_ref().unchecked_ref());
-        handler.forget();
         Self {
             document,
             interpreter,
+            root_id,
             templates: FxHashMap::default(),
-            max_template_id: 0,
+            event_queue,
+            queued_mounted_events: Vec::new(),
+            controlled_values: js_sys::WeakMap::new(),
+            controlled_checked: js_sys::WeakMap::new(),
         }
     }
 
+    /// The id the root element was given in the interpreter, which the VirtualDom needs to mount under
+    pub fn root_id(&self) -> ElementId {
+        self.root_id
+    }
+
     pub fn mount(&mut self) {
         self.interpreter.mount_to_root();
     }
@@ -94,10 +342,10 @@ impl WebsysDom {
                 roots.push(self.create_template_node(root))
             }
 
-            self.templates
-                .insert(template.name.to_owned(), self.max_template_id);
-            save_template(roots, self.max_template_id);
-            self.max_template_id += 1
+            // The template table is shared by every app on the page, so the ids are too
+            let tmpl_id = NEXT_TEMPLATE_ID.with(|id| id.replace(id.get() + 1));
+            self.templates.insert(template.name.to_owned(), tmpl_id);
+            save_template(roots, tmpl_id);
         }
     }
 
@@ -149,8 +397,36 @@ impl WebsysDom {
 
     pub fn apply_edits(&mut self, mut edits: Vec<Mutation>) {
         use Mutation::*;
+
+        #[cfg(feature = "profiling")]
+        let _span = tracing::trace_span!("apply mutations", edits = edits.len()).entered();
+
+        // Moving a node takes it out of the document for a moment, which blurs it and resets its scroll offsets
+        let focused = FocusedElement::save(&self.document);
+        let mut scrolled = Vec::new();
+        let mut autofocus = Vec::new();
+        let mut controlled = Vec::new();
+
         let i = &mut self.interpreter;
         for edit in &edits {
+            match edit {
+                // the nodes haven't moved yet, so their offsets are still there to read
+                PushRoot { id } => save_scroll(&mut scrolled, *id),
+                SetAttribute {
+                    name: name @ ("value" | "checked"),
+                    value,
+                    id,
+                    ns: None,
+                } => controlled.push((*id, *name, controlled_value(value))),
+                LoadTemplate { id, .. } => autofocus.push(*id),
+                SetAttribute {
+                    name: "autofocus",
+                    id,
+                    ..
+                } => autofocus.push(*id),
+                _ => {}
+            }
+
             match edit {
                 AppendChildren { id, m } => i.append_children(id.0 as u32, *m as u32),
                 AssignId { path, id } => {
@@ -199,18 +475,132 @@ impl WebsysDom {
                     _ => unreachable!(),
                 },
                 SetText { value, id } => i.set_text(id.0 as u32, value),
-                NewEventListener { name, id, .. } => {
-                    i.new_event_listener(name, id.0 as u32, event_bubbles(name) as u8);
+                // Mounting isn't a browser event, so we send it ourselves once the edits are flushed
+                NewEventListener { name, id, .. } if *name == "mounted" => {
+                    self.queued_mounted_events.push(*id);
                 }
-                RemoveEventListener { name, id } => {
-                    i.remove_event_listener(name, id.0 as u32, event_bubbles(name) as u8)
+                NewEventListener { name, id, passive } => {
+                    i.new_event_listener(
+                        name,
+                        id.0 as u32,
+                        event_bubbles(name) as u8,
+                        *passive as u8,
+                    );
                 }
+                RemoveEventListener { name, .. } if *name == "mounted" => {}
+                RemoveEventListener { name, id } => i.remove_event_listener(name, id.0 as u32),
                 Remove { id } => i.remove(id.0 as u32),
                 PushRoot { id } => i.push_root(id.0 as u32),
             }
         }
         edits.clear();
         i.flush();
+
+        // the nodes only exist once the edits are flushed
+        for (id, name, value) in controlled {
+            let node = get_node(id.0 as u32);
+            let is_form_control = node.has_type::<web_sys::HtmlInputElement>()
+                || node.has_type::<web_sys::HtmlTextAreaElement>()
+                || node.has_type::<web_sys::HtmlSelectElement>();
+            if !is_form_control {
+                continue;
+            }
+            let values = match name {
+                "value" => &self.controlled_values,
+                _ => &self.controlled_checked,
+            };
+            match value {
+                Some(value) => _ = values.set(&node, &value.into()),
+                None => _ = values.delete(&node),
+            }
+        }
+
+        for (node, top, left) in scrolled {
+            node.set_scroll_top(top);
+            node.set_scroll_left(left);
+        }
+
+        // Like react, elements created with `autofocus` take the focus. Otherwise the element that had it keeps it.
+        if !focus_autofocus(&autofocus) {
+            if let Some(focused) = focused {
+                focused.restore(&self.document);
+            }
+        }
+
+        for id in self.queued_mounted_events.drain(..) {
+            let node: Element = get_node(id.0 as u32).unchecked_into();
+            let data: Rc<dyn Any> = Rc::new(MountedData::new(node));
+            self.event_queue.push(UiEvent {
+                name: "mounted".to_string(),
+                bubbles: false,
+                element: id,
+                data,
+                event: Event::new("mounted").unwrap(),
+                origin: None,
+            });
+        }
+    }
+
+    /// Put the form elements of the input events the app handled back to the values the app gave them, undoing the
+    /// edits it didn't accept
+    ///
+    /// Elements that changed since the app handled their event are left alone, since their next event is on its way.
+    pub fn restore_controlled(&self, inputs: Vec<ControlledInput>) {
+        for input in inputs {
+            if control_state(&input.element) != input.state {
+                continue;
+            }
+            self.restore_control(&input.element);
+
+            // checking a radio button unchecks the others in its group
+            let radio = input
+                .element
+                .dyn_ref::<web_sys::HtmlInputElement>()
+                .filter(|radio| radio.type_() == "radio" && !radio.name().is_empty());
+            if let Some(radio) = radio {
+                let group = self.document.get_elements_by_name(&radio.name());
+                for idx in 0..group.length() {
+                    if let Some(Ok(other)) = group.item(idx).map(|node| node.dyn_into()) {
+                        self.restore_control(&other);
+                    }
+                }
+            }
+        }
+    }
+
+    fn restore_control(&self, element: &Element) {
+        if let Some(value) = self.controlled_values.get(element).as_string() {
+            if let Some(input) = element.dyn_ref::<web_sys::HtmlInputElement>() {
+                if input.value() != value {
+                    input.set_value(&value);
+                }
+            } else if let Some(textarea) = element.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+                if textarea.value() != value {
+                    textarea.set_value(&value);
+                }
+            } else if let Some(select) = element.dyn_ref::<web_sys::HtmlSelectElement>() {
+                if select.value() != value {
+                    select.set_value(&value);
+                }
+            }
+        }
+
+        if let Some(checked) = self.controlled_checked.get(element).as_string() {
+            if let Some(input) = element.dyn_ref::<web_sys::HtmlInputElement>() {
+                input.set_checked(checked == "true");
+            }
+        }
+    }
+}
+
+/// The text a value or checked attribute is set to, or nothing when it's removed
+fn controlled_value(value: &BorrowedAttributeValue) -> Option<String> {
+    match value {
+        BorrowedAttributeValue::Text(text) => Some(text.to_string()),
+        BorrowedAttributeValue::Float(f) => Some(f.to_string()),
+        BorrowedAttributeValue::Int(n) => Some(n.to_string()),
+        BorrowedAttributeValue::Bool(b) => Some(b.to_string()),
+        _ => None,
     }
 }
 
@@ -221,9 +611,6 @@ pub fn virtual_event_from_websys_event(event: web_sys::Event, target: Element) -
 
     match event.type_().as_str() {
         "copy" | "cut" | "paste" => Rc::new(ClipboardData {}),
-        "compositionend" | "compositionstart" | "compositionupdate" => {
-            make_composition_event(&event)
-        }
         "keydown" | "keypress" | "keyup" => Rc::new(KeyboardData::from(event)),
         "focus" | "blur" | "focusout" | "focusin" => Rc::new(FocusData {}),
 
@@ -236,18 +623,59 @@ pub fn virtual_event_from_websys_event(event: web_sys::Event, target: Element) -
         "drag" | "dragend" | "dragenter" | "dragexit" | "dragleave" | "dragover" | "dragstart"
         | "drop" => {
             let mouse = MouseData::from(event);
-            Rc::new(DragData { mouse })
+            let files = event
+                .dyn_ref::<web_sys::DragEvent>()
+                .and_then(|evt| evt.data_transfer())
+                .and_then(|data| data.files())
+                .and_then(|list| {
+                    let mut files = WebFileEngine::new();
+                    files.extend(&list);
+                    files.into_engine()
+                });
+            Rc::new(DragData { mouse, files })
         }
+        "select" => Rc::new(SelectionData {}),
+        "scroll" => Rc::new(ScrollData {
+            scroll_top: target.scroll_top() as f64,
+            scroll_left: target.scroll_left() as f64,
+            scroll_height: target.scroll_height() as f64,
+            scroll_width: target.scroll_width() as f64,
+            client_height: target.client_height() as f64,
+            client_width: target.client_width() as f64,
+        }),
+        "wheel" => Rc::new(WheelData::from(event)),
+        "toggle" => Rc::new(ToggleData {}),
 
+        #[cfg(feature = "extended_events")]
+        _ => virtual_extended_event(event, target),
+
+        #[cfg(not(feature = "extended_events"))]
+        _ => {
+            // without a decoder the data doesn't match the handler, which is never called
+            #[cfg(debug_assertions)]
+            log::warn!(
+                "the data of {} events needs the extended_events feature of dioxus-web",
+                event.type_()
+            );
+            Rc::new(())
+        }
+    }
+}
+
+/// The data of the events that most apps never listen to, which are only decoded with the `extended_events` feature
+#[cfg(feature = "extended_events")]
+fn virtual_extended_event(event: web_sys::Event, target: Element) -> Rc<dyn Any> {
+    use dioxus_html::events::*;
+
+    match event.type_().as_str() {
+        "compositionend" | "compositionstart" | "compositionupdate" => {
+            make_composition_event(&event)
+        }
         "pointerdown" | "pointermove" | "pointerup" | "pointercancel" | "gotpointercapture"
         | "lostpointercapture" | "pointerenter" | "pointerleave" | "pointerover" | "pointerout" => {
             Rc::new(PointerData::from(event))
         }
-        "select" => Rc::new(SelectionData {}),
         "touchcancel" | "touchend" | "touchmove" | "touchstart" => Rc::new(TouchData::from(event)),
-
-        "scroll" => Rc::new(()),
-        "wheel" => Rc::new(WheelData::from(event)),
         "animationstart" | "animationend" | "animationiteration" => {
             Rc::new(AnimationData::from(event))
         }
@@ -255,13 +683,30 @@ pub fn virtual_event_from_websys_event(event: web_sys::Event, target: Element) -
         "abort" | "canplay" | "canplaythrough" | "durationchange" | "emptied" | "encrypted"
         | "ended" | "error" | "loadeddata" | "loadedmetadata" | "loadstart" | "pause" | "play"
         | "playing" | "progress" | "ratechange" | "seeked" | "seeking" | "stalled" | "suspend"
-        | "timeupdate" | "volumechange" | "waiting" => Rc::new(MediaData {}),
-        "toggle" => Rc::new(ToggleData {}),
+        | "timeupdate" | "volumechange" | "waiting" => make_media_event(&target),
 
-        _ => Rc::new(()),
+        _ => make_custom_event(&event),
     }
 }
 
+#[cfg(feature = "extended_events")]
+fn make_media_event(target: &Element) -> Rc<MediaData> {
+    let media = match target.dyn_ref::<web_sys::HtmlMediaElement>() {
+        Some(media) => media,
+        None => return Rc::new(MediaData::default()),
+    };
+    Rc::new(MediaData {
+        current_time: media.current_time(),
+        duration: Some(media.duration()).filter(|duration| duration.is_finite()),
+        volume: media.volume(),
+        muted: media.muted(),
+        paused: media.paused(),
+        ended: media.ended(),
+        playback_rate: media.playback_rate(),
+    })
+}
+
+#[cfg(feature = "extended_events")]
 fn make_composition_event(event: &Event) -> Rc<CompositionData> {
     let evt: &web_sys::CompositionEvent = event.dyn_ref().unwrap();
     Rc::new(CompositionData {
@@ -269,6 +714,61 @@ fn make_composition_event(event: &Event) -> Rc<CompositionData> {
     })
 }
 
+/// Gestures are recognized by the interpreter, which dispatches them with their data in the detail of a custom event
+#[cfg(feature = "extended_events")]
+fn make_gesture_event(event: &Event) -> Rc<dyn Any> {
+    use dioxus_html::events::*;
+
+    let detail = event
+        .dyn_ref::<web_sys::CustomEvent>()
+        .map(|evt| evt.detail())
+        .unwrap_or(wasm_bindgen::JsValue::UNDEFINED);
+    let field = |name: &str| js_sys::Reflect::get(&detail, &name.into()).unwrap_or_default();
+    let number = |name: &str| field(name).as_f64().unwrap_or_default();
+    let string = |name: &str| field(name).as_string().unwrap_or_default();
+
+    match event.type_().as_str() {
+        "swipe" => Rc::new(SwipeData {
+            direction: match string("direction").as_str() {
+                "left" => SwipeDirection::Left,
+                "right" => SwipeDirection::Right,
+                "up" => SwipeDirection::Up,
+                _ => SwipeDirection::Down,
+            },
+            delta_x: number("delta_x"),
+            delta_y: number("delta_y"),
+            velocity: number("velocity"),
+            duration: number("duration"),
+            pointer_type: string("pointer_type"),
+        }),
+        "pinch" => Rc::new(PinchData {
+            phase: match string("phase").as_str() {
+                "start" => GesturePhase::Start,
+                "move" => GesturePhase::Move,
+                _ => GesturePhase::End,
+            },
+            scale: number("scale"),
+            center_x: number("center_x"),
+            center_y: number("center_y"),
+        }),
+        _ => Rc::new(LongPressData {
+            client_x: number("client_x"),
+            client_y: number("client_y"),
+            pointer_type: string("pointer_type"),
+        }),
+    }
+}
+
+#[cfg(feature = "extended_events")]
+fn make_custom_event(event: &Event) -> Rc<CustomData> {
+    let detail = event
+        .dyn_ref::<web_sys::CustomEvent>()
+        .and_then(|evt| js_sys::JSON::stringify(&evt.detail()).ok())
+        .and_then(|detail| detail.as_string())
+        .unwrap_or_default();
+    Rc::new(CustomData { detail })
+}
+
 pub(crate) fn load_document() -> Document {
     web_sys::window()
         .expect("should have access to the Window")
@@ -315,7 +815,15 @@ fn read_input_to_data(target: Element) -> Rc<FormData> {
         })
         .expect("only an InputElement or TextAreaElement or an element with contenteditable=true can have an oninput event listener");
 
-    let mut values = std::collections::HashMap::new();
+    let mut values = std::collections::HashMap::<String, Vec<String>>::new();
+    let mut files = WebFileEngine::new();
+
+    if let Some(list) = target
+        .dyn_ref::<web_sys::HtmlInputElement>()
+        .and_then(|input| input.files())
+    {
+        files.extend(&list);
+    }
 
     // try to fill in form values
     if let Some(form) = target.dyn_ref::<web_sys::HtmlFormElement>() {
@@ -323,31 +831,51 @@ fn read_input_to_data(target: Element) -> Rc<FormData> {
         for x in 0..elements.length() {
             let element = elements.item(x).unwrap();
             if let Some(name) = element.get_attribute("name") {
-                let value: Option<String> = element
-                    .dyn_ref()
-                    .map(|input: &web_sys::HtmlInputElement| {
-                        match input.type_().as_str() {
-                            "checkbox" => {
-                                match input.checked() {
-                                    true => Some("true".to_string()),
-                                    false => Some("false".to_string()),
-                                }
-                            },
-                            "radio" => {
-                                match input.checked() {
-                                    true => Some(input.value()),
-                                    false => None,
-                                }
+                let field = values.entry(name).or_default();
+
+                if let Some(input) = element.dyn_ref::<web_sys::HtmlInputElement>() {
+                    match input.type_().as_str() {
+                        // Like html, a group of checkboxes has the values of the checked ones
+                        "checkbox" if input.has_attribute("value") => {
+                            if input.checked() {
+                                field.push(input.value());
                             }
-                            _ => Some(input.value())
                         }
-                    })
-                    .or_else(|| element.dyn_ref().map(|input: &web_sys::HtmlTextAreaElement| Some(input.value())))
-                    .or_else(|| element.dyn_ref().map(|input: &web_sys::HtmlSelectElement| Some(input.value())))
-                    .or_else(|| Some(element.dyn_ref::<web_sys::HtmlElement>().unwrap().text_content()))
-                    .expect("only an InputElement or TextAreaElement or an element with contenteditable=true can have an oninput event listener");
-                if let Some(value) = value {
-                    values.insert(name, value);
+                        "checkbox" => field.push(input.checked().to_string()),
+                        "radio" => {
+                            if input.checked() {
+                                field.push(input.value());
+                            }
+                        }
+                        "file" => {
+                            if let Some(list) = input.files() {
+                                field.extend(
+                                    (0..list.length())
+                                        .filter_map(|idx| list.item(idx))
+                                        .map(|file| file.name()),
+                                );
+                                files.extend(&list);
+                            }
+                        }
+                        _ => field.push(input.value()),
+                    }
+                } else if let Some(input) = element.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+                    field.push(input.value());
+                } else if let Some(select) = element.dyn_ref::<web_sys::HtmlSelectElement>() {
+                    let options = select.selected_options();
+                    field.extend(
+                        (0..options.length())
+                            .filter_map(|idx| options.item(idx))
+                            .filter_map(|option| {
+                                option.dyn_into::<web_sys::HtmlOptionElement>().ok()
+                            })
+                            .map(|option| option.value()),
+                    );
+                } else if let Some(text) = element
+                    .dyn_ref::<web_sys::HtmlElement>()
+                    .and_then(|element| element.text_content())
+                {
+                    field.push(text);
                 }
             }
         }
@@ -356,13 +884,20 @@ fn read_input_to_data(target: Element) -> Rc<FormData> {
     Rc::new(FormData {
         value,
         values,
-        files: None,
+        files: files.into_engine(),
     })
 }
 
-fn walk_event_for_id(event: &web_sys::Event) -> Option<(ElementId, web_sys::Element)> {
-    let mut target = event
-        .target()
+fn walk_event_for_id(
+    event: &web_sys::Event,
+    bubbles: bool,
+) -> Option<(ElementId, web_sys::Element)> {
+    // Non bubbling events listen at the element itself, which might not be the element the event started at
+    let start = match bubbles {
+        true => event_origin(event),
+        false => event.current_target(),
+    };
+    let mut target = start
         .expect("missing target")
         .dyn_into::<web_sys::Element>()
         .expect("not a valid element");
@@ -373,10 +908,31 @@ fn walk_event_for_id(event: &web_sys::Event) -> Option<(ElementId, web_sys::Elem
             Some(Err(_)) => return None,
 
             // walk the tree upwards until we actually find an event target
-            None => match target.parent_element() {
+            None => match parent_element(&target) {
                 Some(parent) => target = parent,
                 None => return None,
             },
         }
     }
 }
+
+/// The node an event started at
+///
+/// Listeners outside of a shadow root see the events from inside of it as if they came from its host, so the origin
+/// is the first node of the path the event took instead of its target.
+fn event_origin(event: &web_sys::Event) -> Option<EventTarget> {
+    event
+        .composed_path()
+        .get(0)
+        .dyn_into()
+        .ok()
+        .or_else(|| event.target())
+}
+
+/// The parent of an element, going from the top of a shadow root to its host
+fn parent_element(element: &Element) -> Option<Element> {
+    element.parent_element().or_else(|| {
+        let shadow: web_sys::ShadowRoot = element.parent_node()?.dyn_into().ok()?;
+        Some(shadow.host())
+    })
+}
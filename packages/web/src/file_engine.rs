@@ -0,0 +1,62 @@
+use dioxus_html::FileEngine;
+#[cfg(feature = "file_engine")]
+use js_sys::Uint8Array;
+use std::sync::Arc;
+#[cfg(feature = "file_engine")]
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{File, FileList};
+
+/// Reads the files picked in file inputs with the Blob API of the browser
+pub(crate) struct WebFileEngine {
+    files: Vec<File>,
+}
+
+impl WebFileEngine {
+    pub(crate) fn new() -> Self {
+        Self { files: Vec::new() }
+    }
+
+    /// Add the files picked in one file input
+    pub(crate) fn extend(&mut self, list: &FileList) {
+        self.files
+            .extend((0..list.length()).filter_map(|idx| list.item(idx)));
+    }
+
+    /// The files of the event, or none if no files were picked
+    ///
+    /// Without the `file_engine` feature events never have files.
+    pub(crate) fn into_engine(self) -> Option<Arc<dyn FileEngine>> {
+        if self.files.is_empty() {
+            return None;
+        }
+        #[cfg(feature = "file_engine")]
+        return Some(Arc::new(self));
+        #[cfg(not(feature = "file_engine"))]
+        None
+    }
+
+    #[cfg(feature = "file_engine")]
+    fn find(&self, name: &str) -> Option<&File> {
+        self.files.iter().find(|file| file.name() == name)
+    }
+}
+
+#[cfg(feature = "file_engine")]
+#[async_trait::async_trait(?Send)]
+impl FileEngine for WebFileEngine {
+    fn files(&self) -> Vec<String> {
+        self.files.iter().map(File::name).collect()
+    }
+
+    async fn read_file(&self, file: &str) -> Option<Vec<u8>> {
+        let buffer = JsFuture::from(self.find(file)?.array_buffer()).await.ok()?;
+        Some(Uint8Array::new(&buffer).to_vec())
+    }
+
+    async fn read_file_to_string(&self, file: &str) -> Option<String> {
+        JsFuture::from(self.find(file)?.text())
+            .await
+            .ok()?
+            .as_string()
+    }
+}
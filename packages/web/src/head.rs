@@ -0,0 +1,85 @@
+use dioxus_hooks::{HeadBackend, HeadElement, HEAD_ATTRIBUTE};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use wasm_bindgen::JsCast;
+use web_sys::{Document, Element};
+
+/// Backs `use_head` with the head of the document
+///
+/// The elements that didn't change are left alone, so their styles aren't loaded and their scripts aren't run again.
+/// Elements the page was served with that carry [`HEAD_ATTRIBUTE`], like the ones `dioxus_ssr::render_head` renders,
+/// are taken over.
+#[derive(Default)]
+pub(crate) struct WebHead {
+    // the title of the page before the app set one, which comes back when no component sets one
+    initial_title: RefCell<Option<String>>,
+}
+
+impl HeadBackend for WebHead {
+    fn update(&self, elements: &[HeadElement]) {
+        let document = match web_sys::window().and_then(|window| window.document()) {
+            Some(document) => document,
+            None => return,
+        };
+        let head = match document.head() {
+            Some(head) => head,
+            None => return,
+        };
+
+        let initial_title = self
+            .initial_title
+            .borrow_mut()
+            .get_or_insert_with(|| document.title())
+            .clone();
+
+        let mut managed = HashMap::new();
+        if let Ok(nodes) = head.query_selector_all(&format!("[{}]", HEAD_ATTRIBUTE)) {
+            for node in (0..nodes.length()).filter_map(|i| nodes.get(i)) {
+                if let Ok(element) = node.dyn_into::<Element>() {
+                    if let Some(key) = element.get_attribute(HEAD_ATTRIBUTE) {
+                        managed.insert(key, element);
+                    }
+                }
+            }
+        }
+
+        let mut title = initial_title;
+        for element in elements {
+            if element.tag() == "title" {
+                title = element.text().to_string();
+                continue;
+            }
+
+            let key = element.dedupe_key();
+            let node = match create(&document, element, &key) {
+                Some(node) => node,
+                None => continue,
+            };
+            match managed.remove(&key) {
+                Some(existing) if existing.is_equal_node(Some(&node)) => {}
+                Some(existing) => _ = existing.replace_with_with_node_1(&node),
+                None => _ = head.append_child(&node),
+            }
+        }
+
+        for (_, element) in managed {
+            element.remove();
+        }
+
+        if document.title() != title {
+            document.set_title(&title);
+        }
+    }
+}
+
+fn create(document: &Document, element: &HeadElement, key: &str) -> Option<Element> {
+    let node = document.create_element(element.tag()).ok()?;
+    for (name, value) in element.attributes() {
+        node.set_attribute(name, value).ok()?;
+    }
+    node.set_attribute(HEAD_ATTRIBUTE, key).ok()?;
+    if !element.text().is_empty() {
+        node.set_text_content(Some(element.text()));
+    }
+    Some(node)
+}
@@ -7,7 +7,7 @@ use wasm_bindgen::closure::Closure;
 use wasm_bindgen::JsCast;
 use web_sys::{MessageEvent, WebSocket};
 
-#[cfg(not(debug_assertions))]
+#[cfg(not(all(debug_assertions, feature = "hot_reload")))]
 pub(crate) fn init() -> UnboundedReceiver<Template<'static>> {
     let (tx, rx) = futures_channel::mpsc::unbounded();
 
@@ -16,7 +16,7 @@ pub(crate) fn init() -> UnboundedReceiver<Template<'static>> {
     rx
 }
 
-#[cfg(debug_assertions)]
+#[cfg(all(debug_assertions, feature = "hot_reload"))]
 pub(crate) fn init() -> UnboundedReceiver<Template<'static>> {
     use std::convert::TryInto;
 
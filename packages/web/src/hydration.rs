@@ -0,0 +1,30 @@
+/// The id of the script element `dioxus_ssr::render_hydration_state` writes
+const HYDRATION_STATE_ID: &str = "dioxus-hydration-state";
+
+/// The props or state the server rendered the page with, if it wrote them with `dioxus_ssr::render_hydration_state`
+///
+/// Starting from the same data the server had keeps the first render of the client the same as the page, so it
+/// doesn't have to be fetched again.
+///
+/// ```rust, ignore
+/// fn main() {
+///     let props = dioxus_web::hydration_state::<AppProps>().unwrap_or_default();
+///     dioxus_web::launch_with_props(App, props, Config::new().hydrate(true));
+/// }
+/// ```
+pub fn hydration_state<T: serde::de::DeserializeOwned>() -> Option<T> {
+    let script = web_sys::window()?
+        .document()?
+        .get_element_by_id(HYDRATION_STATE_ID)?;
+
+    match serde_json::from_str(&script.text_content()?) {
+        Ok(state) => Some(state),
+        Err(err) => {
+            log::error!(
+                "the hydration state of the page doesn't match its type: {}",
+                err
+            );
+            None
+        }
+    }
+}
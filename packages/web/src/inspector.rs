@@ -0,0 +1,182 @@
+//! Connects the app to an inspector, which asks about the VirtualDom with the devtools protocol over a WebSocket
+//!
+//! The page can't accept connections, so the app connects to the inspector instead.
+
+use crate::Config;
+use dioxus_core::{DevtoolsRequest, VirtualDom};
+use futures_util::stream::{self, FusedStream};
+use std::pin::Pin;
+#[cfg(feature = "inspector")]
+use web_sys::WebSocket;
+
+pub(crate) struct Inspector {
+    #[cfg(feature = "inspector")]
+    socket: Option<WebSocket>,
+    pub(crate) requests: Pin<Box<dyn FusedStream<Item = DevtoolsRequest>>>,
+}
+
+impl Inspector {
+    // never hears from an inspector
+    fn disconnected() -> Self {
+        Self {
+            #[cfg(feature = "inspector")]
+            socket: None,
+            requests: Box::pin(stream::pending()),
+        }
+    }
+}
+
+#[cfg(not(feature = "inspector"))]
+pub(crate) fn init(_cfg: &Config) -> Inspector {
+    Inspector::disconnected()
+}
+
+#[cfg(not(feature = "inspector"))]
+impl Inspector {
+    pub(crate) fn answer(&self, _dom: &VirtualDom, _request: DevtoolsRequest) {}
+}
+
+#[cfg(feature = "inspector")]
+pub(crate) fn init(cfg: &Config) -> Inspector {
+    use std::convert::TryInto;
+    use wasm_bindgen::{closure::Closure, JsCast};
+    use web_sys::MessageEvent;
+
+    let url = match &cfg.inspector {
+        Some(url) => url,
+        None => return Inspector::disconnected(),
+    };
+    let socket = match WebSocket::new(url) {
+        Ok(socket) => socket,
+        Err(err) => {
+            log::error!("Failed to connect to the inspector at {}: {:?}", url, err);
+            return Inspector::disconnected();
+        }
+    };
+
+    let (tx, rx) = futures_channel::mpsc::unbounded();
+    let onmessage = Closure::wrap(Box::new(move |e: MessageEvent| {
+        let text: Option<String> = e
+            .data()
+            .dyn_into::<js_sys::JsString>()
+            .ok()
+            .and_then(|text| text.try_into().ok());
+        match text.map(|text| serde_json::from_str(&text)) {
+            Some(Ok(request)) => _ = tx.unbounded_send(request),
+            Some(Err(err)) => {
+                log::error!("The inspector sent a message that isn't a request: {}", err)
+            }
+            None => {}
+        }
+    }) as Box<dyn FnMut(MessageEvent)>);
+    socket.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+    onmessage.forget();
+
+    Inspector {
+        socket: Some(socket),
+        requests: Box::pin(rx),
+    }
+}
+
+#[cfg(feature = "inspector")]
+impl Inspector {
+    /// Answer a request with the VirtualDom of the app, outlining components on the page
+    pub(crate) fn answer(&self, dom: &VirtualDom, request: DevtoolsRequest) {
+        use dioxus_core::DevtoolsResponse;
+
+        let response = match request {
+            DevtoolsRequest::Tree => DevtoolsResponse::Tree {
+                scopes: dom.inspect(),
+            },
+            DevtoolsRequest::Highlight { scope } => {
+                let elements = dom.scope_elements(scope);
+                highlight(&elements);
+                DevtoolsResponse::Highlighted { elements }
+            }
+            DevtoolsRequest::ClearHighlight => {
+                highlight(&[]);
+                DevtoolsResponse::Cleared
+            }
+        };
+
+        if let Some(socket) = &self.socket {
+            _ = socket.send_with_str(&serde_json::to_string(&response).unwrap());
+        }
+    }
+}
+
+#[cfg(feature = "inspector")]
+const HIGHLIGHT_ID: &str = "dioxus-inspector-highlight";
+
+#[cfg(feature = "inspector")]
+const HIGHLIGHT_STYLE: &str = "position: fixed; pointer-events: none; z-index: 2147483647; \
+    background: rgba(66, 133, 244, 0.25); outline: 2px solid rgb(66, 133, 244);";
+
+/// Outline the nodes on the page, or take the outline away when there are no nodes
+#[cfg(feature = "inspector")]
+fn highlight(elements: &[dioxus_core::ElementId]) {
+    use wasm_bindgen::JsCast;
+    use web_sys::{DomRect, Element, HtmlElement};
+
+    let document = match web_sys::window().and_then(|window| window.document()) {
+        Some(document) => document,
+        None => return,
+    };
+
+    let rects: Vec<DomRect> = elements
+        .iter()
+        .filter_map(|id| {
+            let node = dioxus_interpreter_js::get_node(id.0 as u32);
+            match node.dyn_ref::<Element>() {
+                Some(element) => Some(element.get_bounding_client_rect()),
+                // text nodes only have a size through a range around them
+                None => {
+                    let range = document.create_range().ok()?;
+                    range.select_node(&node).ok()?;
+                    Some(range.get_bounding_client_rect())
+                }
+            }
+        })
+        .filter(|rect| rect.width() > 0.0 || rect.height() > 0.0)
+        .collect();
+
+    let existing = document.get_element_by_id(HIGHLIGHT_ID);
+    if rects.is_empty() {
+        if let Some(overlay) = existing {
+            overlay.remove();
+        }
+        return;
+    }
+
+    let overlay = match existing {
+        Some(overlay) => overlay,
+        None => {
+            let overlay = match document.create_element("div") {
+                Ok(overlay) => overlay,
+                Err(_) => return,
+            };
+            overlay.set_id(HIGHLIGHT_ID);
+            _ = overlay.set_attribute("style", HIGHLIGHT_STYLE);
+            if let Some(body) = document.body() {
+                _ = body.append_child(&overlay);
+            }
+            overlay
+        }
+    };
+
+    // DomRect only has the edges through DomRectReadOnly
+    let (mut left, mut top) = (f64::INFINITY, f64::INFINITY);
+    let (mut right, mut bottom) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for rect in &rects {
+        left = left.min(rect.left());
+        top = top.min(rect.top());
+        right = right.max(rect.right());
+        bottom = bottom.max(rect.bottom());
+    }
+
+    let style = overlay.unchecked_into::<HtmlElement>().style();
+    _ = style.set_property("left", &format!("{}px", left));
+    _ = style.set_property("top", &format!("{}px", top));
+    _ = style.set_property("width", &format!("{}px", right - left));
+    _ = style.set_property("height", &format!("{}px", bottom - top));
+}
@@ -17,6 +17,24 @@
 //!
 //! To purview the examples, check of the root Dioxus crate - the examples in this crate are mostly meant to provide
 //! validation of websys-specific features and not the general use of Dioxus.
+//!
+//! ## Features
+//! ------------
+//! Everything is enabled by default. Apps that count every kilobyte of their bundle can turn off the default features
+//! and only enable what they use:
+//! - `panic_hook`: log panics to the console
+//! - `eval`: running scripts with `use_eval`
+//! - `file_engine`: reading the files of file inputs and drop events
+//! - `hot_reload`: apply the templates of the hot reloading server in debug builds
+//! - `extended_events`: the data of composition, pointer, touch, animation, transition, media and custom events.
+//!   Without it, handlers for these events are never called.
+//! - `worker`: run the VirtualDom in a Web Worker, see `launch_worker`
+//!
+//! `inspector` is off by default. It connects the app to the inspector at `Config::with_inspector`, which can look at
+//! the components, props and hooks of the app and outline components on the page.
+//!
+//! `profiling` is off by default too. It adds `tracing` spans around renders, diffs, events, tasks and applying the
+//! mutations to the page, named after the components and their scopes.
 
 // ## RequestAnimationFrame and RequestIdleCallback
 // ------------------------------------------------
@@ -54,19 +72,43 @@
 //     - Do DOM work in the next requestAnimationFrame callback
 
 pub use crate::cfg::Config;
-pub use crate::util::{use_eval, EvalResult};
+#[cfg(feature = "hydrate")]
+pub use crate::hydration::hydration_state;
+#[cfg(feature = "worker")]
+pub use crate::worker::{
+    launch_in_worker, launch_in_worker_with_props, launch_worker, launch_worker_cfg,
+};
 use dioxus_core::{Element, Scope, VirtualDom};
+pub use dioxus_hooks::{use_eval, Eval, EvalError};
 use futures_util::{pin_mut, FutureExt, StreamExt};
+use std::{cell::RefCell, rc::Rc};
 
 mod cache;
 mod cfg;
+mod clipboard;
 mod dom;
+mod file_engine;
+mod head;
 mod hot_reload;
+#[cfg(feature = "hydrate")]
+mod hydration;
+mod inspector;
+mod listener;
+mod observer;
+#[cfg(debug_assertions)]
+mod overlay;
+mod queue;
+#[cfg(debug_assertions)]
+mod render_stats;
+mod ric_raf;
+mod shortcut;
+mod storage;
+#[cfg(feature = "eval")]
 mod util;
+mod window;
+#[cfg(feature = "worker")]
+mod worker;
 
-// Currently disabled since it actually slows down immediate rendering
-// todo: only schedule non-immediate renders through ric/raf
-// mod ric_raf;
 // mod rehydrate;
 
 /// Launch the VirtualDOM given a root component and a configuration.
@@ -93,6 +135,87 @@ pub fn launch(root_component: fn(Scope) -> Element) {
     launch_with_props(root_component, (), Config::default());
 }
 
+/// Launch your app under the element matching a css selector and run the event loop.
+///
+/// Unlike [`launch`], this can be called several times to embed independent apps into a server-rendered page.
+///
+/// # Example
+///
+/// ```rust, ignore
+/// fn main() {
+///     dioxus_web::launch_on_selector("#cart", Cart);
+///     dioxus_web::launch_on_selector("#search", Search);
+/// }
+/// ```
+pub fn launch_on_selector(selector: &str, root_component: fn(Scope) -> Element) {
+    launch_with_props(root_component, (), Config::new().root_selector(selector));
+}
+
+/// Mount the interactive islands of a server-rendered page, leaving the rest of its html static.
+///
+/// The server marks every island with a [`dioxus_ssr::ISLAND_ATTRIBUTE`] attribute naming it, like
+/// `dioxus_ssr::render_island` does. Each island on the page gets its own VirtualDom, launched from the component with
+/// its name. Islands nested in another island belong to the outer one, and islands without a component are left as
+/// they are.
+///
+/// This doesn't hydrate the islands: the html the server rendered for an island is only shown until the wasm loads,
+/// and is then removed and rendered again by the client. Whatever the user did inside of the island meanwhile, like
+/// focusing an input or scrolling, is lost.
+///
+/// Only the components of the islands have to be compiled into the client.
+///
+/// # Example
+///
+/// ```rust, ignore
+/// fn main() {
+///     dioxus_web::mount_islands(&[("cart", Cart), ("search", Search)]);
+/// }
+/// ```
+pub fn mount_islands(islands: &[(&str, fn(Scope) -> Element)]) {
+    use dioxus_ssr::ISLAND_ATTRIBUTE;
+    use wasm_bindgen::JsCast;
+
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .expect("no document to mount the islands in");
+    let marked = document
+        .query_selector_all(&format!("[{}]", ISLAND_ATTRIBUTE))
+        .expect("the island selector is valid");
+
+    for idx in 0..marked.length() {
+        let root: web_sys::Element = match marked.item(idx).and_then(|node| node.dyn_into().ok()) {
+            Some(root) => root,
+            None => continue,
+        };
+
+        // the outer island renders the islands inside of it
+        let nested = root
+            .parent_element()
+            .and_then(|parent| parent.closest(&format!("[{}]", ISLAND_ATTRIBUTE)).ok())
+            .flatten()
+            .is_some();
+        if nested {
+            continue;
+        }
+
+        let name = root.get_attribute(ISLAND_ATTRIBUTE).unwrap_or_default();
+        let component = match islands.iter().find(|(island, _)| *island == name) {
+            Some((_, component)) => *component,
+            None => {
+                log::warn!("no component for the island {:?}, leaving it static", name);
+                continue;
+            }
+        };
+
+        // the markup of the server is replaced by the render of the client
+        root.set_inner_html("");
+
+        let mut config = Config::new();
+        config.root_element = Some(root);
+        launch_with_props(component, (), config);
+    }
+}
+
 /// Launch your app and run the event loop, with configuration.
 ///
 /// This function will start your web app on the main web thread.
@@ -160,9 +283,14 @@ pub fn launch_with_props<T: 'static>(
 ///     wasm_bindgen_futures::spawn_local(app_fut);
 /// }
 /// ```
-pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_props: T, cfg: Config) {
+pub async fn run_with_props<T: 'static>(
+    root: fn(Scope<T>) -> Element,
+    root_props: T,
+    mut cfg: Config,
+) {
     log::info!("Starting up");
 
+    #[allow(unused_mut)]
     let mut dom = VirtualDom::new_with_props(root, root_props);
 
     #[cfg(feature = "panic_hook")]
@@ -170,7 +298,28 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
         console_error_panic_hook::set_once();
     }
 
+    #[cfg(debug_assertions)]
+    if cfg.error_overlay {
+        overlay::show_panics();
+        dom.set_error_handler(|scope, error| {
+            // the panic hook already showed the panic
+            if error.downcast::<dioxus_core::CapturedPanic>().is_none() {
+                let message = format!("{:#?}", error.error);
+                overlay::show(&format!("error in {:?}", scope), &message, None);
+            }
+        });
+    }
+
+    #[cfg(debug_assertions)]
+    let render_stats_overlay = cfg.render_stats_overlay;
+    #[cfg(debug_assertions)]
+    if render_stats_overlay {
+        dom.track_render_stats(true);
+        dom.set_render_clock(render_stats::now);
+    }
+
     let mut hotreload_rx = hot_reload::init();
+    let mut inspector = inspector::init(&cfg);
 
     for s in crate::cache::BUILTIN_INTERNED_STRINGS {
         wasm_bindgen::intern(s);
@@ -180,17 +329,99 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
     }
 
     let _should_hydrate = cfg.hydrate;
+    let root_contexts = std::mem::take(&mut cfg.root_contexts);
+
+    for (event, dispatch) in std::mem::take(&mut cfg.event_dispatch) {
+        dom.set_event_dispatch(event, dispatch);
+    }
+
+    let event_queue = queue::EventQueue::default();
+
+    let mut websys_dom = dom::WebsysDom::new(cfg, event_queue.clone());
+
+    // Apps launched on a selector share the interpreter, so they each mount under their own range of ids
+    let mut dom = dom.with_root_element(websys_dom.root_id());
+
+    // use_persistent keeps its values in localStorage
+    dom.base_scope()
+        .provide_context(dioxus_hooks::PersistentBackend::new(storage::LocalStorage));
 
-    let (tx, mut rx) = futures_channel::mpsc::unbounded();
+    // use_clipboard goes through the async Clipboard API
+    dom.base_scope()
+        .provide_context(dioxus_hooks::ClipboardContext::new(clipboard::WebClipboard));
 
-    let mut websys_dom = dom::WebsysDom::new(cfg, tx);
+    // use_window_size and use_media_query check the window again whenever it's resized
+    dom.base_scope()
+        .provide_context(dioxus_hooks::WindowContext::new(window::WebWindow));
+
+    // use_head keeps the head of the document up to date
+    dom.base_scope()
+        .provide_context(dioxus_hooks::HeadContext::new(head::WebHead::default()));
+
+    // use_intersection_observer and use_resize_observer attach the browser's observers to mounted elements
+    dom.base_scope()
+        .provide_context(dioxus_hooks::ObserverContext::new(observer::WebObserver));
+
+    // use_document_listener and use_window_listener add their listeners to the document and the window
+    dom.base_scope()
+        .provide_context(dioxus_hooks::GlobalListenerContext::new(
+            listener::WebListeners,
+        ));
+
+    // use_eval runs scripts in the page
+    #[cfg(feature = "eval")]
+    dom.base_scope()
+        .provide_context(dioxus_hooks::EvalContext::new(util::WebEval));
+
+    // use_keyboard_shortcut sees every key pressed on the document
+    shortcut::listen_for_shortcuts();
+
+    // Queries that opted into it are fetched again whenever the page gets focus
+    static FOCUS_LISTENER: std::sync::Once = std::sync::Once::new();
+    FOCUS_LISTENER.call_once(|| {
+        use wasm_bindgen::{closure::Closure, JsCast};
+
+        let listener =
+            Closure::wrap(
+                Box::new(|_: web_sys::Event| dioxus_hooks::revalidate_on_focus())
+                    as Box<dyn FnMut(web_sys::Event)>,
+            );
+        if let Some(window) = web_sys::window() {
+            let _ =
+                window.add_event_listener_with_callback("focus", listener.as_ref().unchecked_ref());
+        }
+        listener.forget();
+    });
+
+    static RESIZE_LISTENER: std::sync::Once = std::sync::Once::new();
+    RESIZE_LISTENER.call_once(|| {
+        use wasm_bindgen::{closure::Closure, JsCast};
+
+        let listener = Closure::wrap(Box::new(|_: web_sys::Event| dioxus_hooks::window_changed())
+            as Box<dyn FnMut(web_sys::Event)>);
+        if let Some(window) = web_sys::window() {
+            let _ = window
+                .add_event_listener_with_callback("resize", listener.as_ref().unchecked_ref());
+        }
+        listener.forget();
+    });
+
+    // The contexts of the config go last, so they can replace the ones above
+    for provide in &root_contexts {
+        provide(dom.base_scope());
+    }
+
+    // Events the app has to handle synchronously are handled by the queue as soon as the browser dispatches them
+    let dom = Rc::new(RefCell::new(dom));
+    event_queue.attach(&dom);
 
     log::info!("rebuilding app");
 
     // if should_hydrate {
     // } else {
     {
-        let edits = dom.rebuild();
+        let mut vdom = dom.borrow_mut();
+        let edits = vdom.rebuild();
 
         websys_dom.load_templates(&edits.templates);
         websys_dom.apply_edits(edits.edits);
@@ -199,54 +430,103 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
     // the mutations come back with nothing - we need to actually mount them
     websys_dom.mount();
 
+    let mut work_loop = ric_raf::RafLoop::new();
+
     loop {
         log::debug!("waiting for work");
 
         // if virtualdom has nothing, wait for it to have something before requesting idle time
         // if there is work then this future resolves immediately.
-        let (mut res, template) = {
-            let work = dom.wait_for_work().fuse();
-            pin_mut!(work);
+        let (template, request) = {
+            // The dom is only borrowed while the future is polled, so the queue can handle events in the meantime
+            let work = futures_util::future::poll_fn(|cx| {
+                let mut dom = dom.borrow_mut();
+                let work = dom.wait_for_work();
+                pin_mut!(work);
+                work.poll_unpin(cx)
+            })
+            .fuse();
+            let events = event_queue.wait().fuse();
+            pin_mut!(work, events);
 
             futures_util::select! {
                 _ = work => (None, None),
-                new_template = hotreload_rx.next() => {
-                    (None, new_template)
-                }
-                evt = rx.next() => (evt, None)
+                new_template = hotreload_rx.next() => (new_template, None),
+                _ = events => (None, None),
+                request = inspector.requests.next() => (None, request),
             }
         };
 
+        // inspecting the app doesn't change it, so there's nothing to render
+        if let Some(request) = request {
+            inspector.answer(&dom.borrow(), request);
+            continue;
+        }
+
+        let replaced_template = template.is_some();
         if let Some(template) = template {
-            dom.replace_template(template);
+            dom.borrow_mut().replace_template(template);
         }
 
-        // Dequeue all of the events from the channel in send order
-        // todo: we should re-order these if possible
-        while let Some(evt) = res {
-            dom.handle_event(evt.name.as_str(), evt.data, evt.element, evt.bubbles);
-            res = rx.try_next().transpose().unwrap().ok();
+        // Handle the events the queue couldn't handle yet, in the order the browser dispatched them
+        let events = event_queue.take(&dom.borrow());
+        let handled_events = !events.is_empty();
+        let mut controlled = Vec::new();
+        for evt in events {
+            if matches!(evt.event.name.as_str(), "input" | "change") {
+                controlled.extend(dom::ControlledInput::new(&evt.event));
+            }
+            if !evt.handled {
+                let evt = evt.event;
+                dom.borrow_mut().handle_event(
+                    evt.name.as_str(),
+                    evt.data,
+                    evt.element,
+                    evt.bubbles,
+                );
+            }
         }
 
-        // Todo: This is currently disabled because it has a negative impact on responce times for events but it could be re-enabled for tasks
         // Jank free rendering
         //
-        // 1. wait for the browser to give us "idle" time
-        // 2. During idle time, diff the dom
-        // 3. Stop diffing if the deadline is exceded
-        // 4. Wait for the animation frame to patch the dom
-
-        // wait for the mainthread to schedule us in
-        // let deadline = work_loop.wait_for_idle_time().await;
-
-        // run the virtualdom work phase until the frame deadline is reached
-        let edits = dom.render_immediate();
+        // Renders for user input and hot reloading are diffed and patched in the next animation frame, so they land in
+        // the frame the user is waiting for. Only the lanes at least as urgent as user input render there; the rest is
+        // left dirty for later.
+        //
+        // Everything else waits for the browser to be idle, diffs until the idle period is over, and is patched in the
+        // animation frame after that. Whatever isn't done by the deadline stays dirty for the next time around.
+        //
+        // The dom is busy from the moment it starts rendering until its edits are applied, so the events that come in
+        // meanwhile wait in the queue for the next round.
+        let idle_deadline = if handled_events || replaced_template {
+            work_loop.wait_for_raf().await;
+            None
+        } else {
+            Some(work_loop.wait_for_idle_time().await)
+        };
 
-        // wait for the animation frame to fire so we can apply our changes
-        // work_loop.wait_for_raf().await;
+        #[allow(clippy::await_holding_refcell_ref)]
+        let mut vdom = dom.borrow_mut();
+        let edits = match idle_deadline {
+            None => vdom.render_with_deadline(std::future::ready(())).await,
+            Some(deadline) => {
+                let edits = vdom.render_with_deadline(deadline).await;
+                work_loop.wait_for_raf().await;
+                edits
+            }
+        };
 
         websys_dom.load_templates(&edits.templates);
         websys_dom.apply_edits(edits.edits);
+
+        #[cfg(debug_assertions)]
+        if render_stats_overlay {
+            render_stats::show(&vdom.render_stats());
+        }
+        drop(vdom);
+
+        // Inputs with a value are controlled by the app, so edits it didn't accept are undone
+        websys_dom.restore_controlled(controlled);
     }
 }
 
@@ -0,0 +1,47 @@
+use crate::dom::virtual_event_from_websys_event;
+use dioxus_hooks::{GlobalHandler, GlobalListenerBackend, ListenerGuard, ListenerTarget};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{Element, Event, EventTarget};
+
+/// Backs `use_document_listener` and `use_window_listener` with listeners on the document and the window
+pub(crate) struct WebListeners;
+
+impl GlobalListenerBackend for WebListeners {
+    fn listen(&self, target: ListenerTarget, event: &str, handler: GlobalHandler) -> ListenerGuard {
+        let window = match web_sys::window() {
+            Some(window) => window,
+            None => return ListenerGuard::new(|| {}),
+        };
+        let document = window.document();
+        let listened: EventTarget = match (target, document.clone()) {
+            (ListenerTarget::Document, Some(document)) => document.into(),
+            (ListenerTarget::Document, None) => return ListenerGuard::new(|| {}),
+            (ListenerTarget::Window, _) => window.into(),
+        };
+
+        let closure = Closure::wrap(Box::new(move |evt: Event| {
+            // Events of the window and the document themselves are decoded as if they happened on the root element
+            let target = evt
+                .target()
+                .and_then(|target| target.dyn_into::<Element>().ok())
+                .or_else(|| document.as_ref().and_then(|d| d.document_element()));
+            let target = match target {
+                Some(target) => target,
+                None => return,
+            };
+
+            if handler(virtual_event_from_websys_event(evt.clone(), target)) {
+                evt.prevent_default();
+            }
+        }) as Box<dyn FnMut(Event)>);
+
+        let event = event.to_string();
+        let _ = listened.add_event_listener_with_callback(&event, closure.as_ref().unchecked_ref());
+
+        ListenerGuard::new(move || {
+            let _ = listened
+                .remove_event_listener_with_callback(&event, closure.as_ref().unchecked_ref());
+            drop(closure);
+        })
+    }
+}
@@ -0,0 +1,132 @@
+use dioxus_hooks::{
+    IntersectionEntry, IntersectionOptions, ObserverBackend, ObserverGuard, ResizeEntry,
+};
+use dioxus_html::{
+    geometry::{euclid, ClientRect},
+    MountedData, MountedError, MountedResult,
+};
+use js_sys::{Array, Function, Reflect};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{DomRectReadOnly, Element, IntersectionObserver, IntersectionObserverEntry};
+
+/// Backs `use_intersection_observer` and `use_resize_observer` with the observers of the browser
+pub(crate) struct WebObserver;
+
+impl ObserverBackend for WebObserver {
+    fn observe_intersection(
+        &self,
+        element: &MountedData,
+        options: &IntersectionOptions,
+        callback: Box<dyn Fn(IntersectionEntry)>,
+    ) -> MountedResult<ObserverGuard> {
+        let element = raw_element(element)?;
+
+        let closure = Closure::wrap(Box::new(move |entries: Array, _: JsValue| {
+            // Only the latest entry matters, since there's a single element
+            if let Some(entry) = entries
+                .iter()
+                .last()
+                .and_then(|e| e.dyn_into::<IntersectionObserverEntry>().ok())
+            {
+                callback(IntersectionEntry {
+                    is_intersecting: entry.is_intersecting(),
+                    intersection_ratio: entry.intersection_ratio(),
+                    bounding_client_rect: to_rect(&entry.bounding_client_rect()),
+                    intersection_rect: to_rect(&entry.intersection_rect()),
+                });
+            }
+        }) as Box<dyn FnMut(Array, JsValue)>);
+
+        let thresholds = options
+            .get_thresholds()
+            .iter()
+            .map(|t| JsValue::from_f64(*t))
+            .collect::<Array>();
+        let mut init = web_sys::IntersectionObserverInit::new();
+        init.root_margin(options.get_root_margin())
+            .threshold(&thresholds);
+
+        let observer =
+            IntersectionObserver::new_with_options(closure.as_ref().unchecked_ref(), &init)
+                .map_err(operation_failed)?;
+        observer.observe(&element);
+
+        Ok(ObserverGuard::new(move || {
+            observer.disconnect();
+            drop(closure);
+        }))
+    }
+
+    fn observe_resize(
+        &self,
+        element: &MountedData,
+        callback: Box<dyn Fn(ResizeEntry)>,
+    ) -> MountedResult<ObserverGuard> {
+        let element = raw_element(element)?;
+
+        let closure = Closure::wrap(Box::new(move |entries: Array, _: JsValue| {
+            if let Some(rect) = entries
+                .iter()
+                .last()
+                .and_then(|entry| Reflect::get(&entry, &"contentRect".into()).ok())
+                .and_then(|rect| rect.dyn_into::<DomRectReadOnly>().ok())
+            {
+                callback(ResizeEntry {
+                    content_rect: to_rect(&rect),
+                });
+            }
+        }) as Box<dyn FnMut(Array, JsValue)>);
+
+        // web-sys only binds ResizeObserver behind its unstable APIs, so it's looked up on the window
+        let constructor = web_sys::window()
+            .and_then(|window| Reflect::get(&window, &"ResizeObserver".into()).ok())
+            .and_then(|constructor| constructor.dyn_into::<Function>().ok())
+            .ok_or(MountedError::NotSupported)?;
+        let observer = Reflect::construct(&constructor, &Array::of1(closure.as_ref()))
+            .map_err(operation_failed)?;
+
+        call(&observer, "observe", &Array::of1(&element))?;
+
+        Ok(ObserverGuard::new(move || {
+            let _ = call(&observer, "disconnect", &Array::new());
+            drop(closure);
+        }))
+    }
+}
+
+fn raw_element(element: &MountedData) -> MountedResult<Element> {
+    element
+        .get_raw_element()?
+        .downcast_ref::<Element>()
+        .cloned()
+        .ok_or(MountedError::NotSupported)
+}
+
+fn call(target: &JsValue, method: &str, args: &Array) -> MountedResult<JsValue> {
+    Reflect::get(target, &method.into())
+        .and_then(|f| f.dyn_into::<Function>())
+        .and_then(|f| f.apply(target, args))
+        .map_err(operation_failed)
+}
+
+fn to_rect(rect: &DomRectReadOnly) -> ClientRect {
+    ClientRect::new(
+        euclid::Point2D::new(rect.x(), rect.y()),
+        euclid::Size2D::new(rect.width(), rect.height()),
+    )
+}
+
+fn operation_failed(err: JsValue) -> MountedError {
+    MountedError::OperationFailed(Box::new(ObserverError(format!("{:?}", err))))
+}
+
+#[derive(Debug)]
+struct ObserverError(String);
+
+impl std::fmt::Display for ObserverError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the observer failed: {}", self.0)
+    }
+}
+
+impl std::error::Error for ObserverError {}
@@ -0,0 +1,104 @@
+//! An overlay for debug builds that shows panics and uncaught errors on the page instead of a blank screen
+
+use wasm_bindgen::prelude::*;
+use web_sys::{Document, Element};
+
+const OVERLAY_ID: &str = "dioxus-error-overlay";
+
+const OVERLAY_STYLE: &str = "position: fixed; inset: 0; z-index: 2147483647; overflow: auto; \
+    padding: 24px; box-sizing: border-box; background: rgba(20, 20, 20, 0.9); color: #e8e8e8; \
+    font-family: ui-monospace, Menlo, Consolas, monospace; font-size: 13px;";
+
+const ENTRY_STYLE: &str = "margin: 0 auto 16px; max-width: 960px; padding: 16px; \
+    border-top: 4px solid #e5484d; background: #1f1f1f;";
+
+const BUTTON_STYLE: &str = "float: right; padding: 4px 12px; cursor: pointer; \
+    border: 1px solid #555; background: #2c2c2c; color: inherit; font: inherit;";
+
+#[wasm_bindgen]
+extern "C" {
+    type Error;
+
+    #[wasm_bindgen(constructor)]
+    fn new() -> Error;
+
+    #[wasm_bindgen(structural, method, getter)]
+    fn stack(error: &Error) -> String;
+}
+
+/// Show panics in the overlay, after the hook that was already set runs
+pub(crate) fn show_panics() {
+    static HOOK: std::sync::Once = std::sync::Once::new();
+    HOOK.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            previous(info);
+            show("panicked", &info.to_string(), Some(&Error::new().stack()));
+        }));
+    });
+}
+
+/// Add an error to the overlay, opening the overlay if it's dismissed
+///
+/// The overlay is built without calling back into wasm, so it can still be dismissed after a panic aborts the app.
+pub(crate) fn show(title: &str, message: &str, stack: Option<&str>) {
+    let document = match web_sys::window().and_then(|window| window.document()) {
+        Some(document) => document,
+        None => return,
+    };
+    let _ = try_show(&document, title, message, stack);
+}
+
+fn try_show(
+    document: &Document,
+    title: &str,
+    message: &str,
+    stack: Option<&str>,
+) -> Result<(), JsValue> {
+    let overlay = match document.get_element_by_id(OVERLAY_ID) {
+        Some(overlay) => overlay,
+        None => {
+            let overlay = document.create_element("div")?;
+            overlay.set_id(OVERLAY_ID);
+            overlay.set_attribute("style", OVERLAY_STYLE)?;
+            overlay.set_attribute("role", "alert")?;
+            let body = document.body().ok_or(JsValue::NULL)?;
+            body.append_child(&overlay)?;
+            overlay
+        }
+    };
+
+    let entry = document.create_element("section")?;
+    entry.set_attribute("style", ENTRY_STYLE)?;
+
+    let dismiss = document.create_element("button")?;
+    dismiss.set_attribute("style", BUTTON_STYLE)?;
+    dismiss.set_attribute(
+        "onclick",
+        &format!("document.getElementById('{}').remove()", OVERLAY_ID),
+    )?;
+    dismiss.set_text_content(Some("Dismiss"));
+    entry.append_child(&dismiss)?;
+
+    append_text(document, &entry, "h2", &format!("Dioxus {}", title))?;
+    append_text(document, &entry, "pre", message)?;
+    if let Some(stack) = stack {
+        let stack = append_text(document, &entry, "pre", stack)?;
+        stack.set_attribute("style", "color: #a0a0a0; white-space: pre-wrap;")?;
+    }
+
+    overlay.append_child(&entry)?;
+    Ok(())
+}
+
+fn append_text(
+    document: &Document,
+    parent: &Element,
+    tag: &str,
+    text: &str,
+) -> Result<Element, JsValue> {
+    let element = document.create_element(tag)?;
+    element.set_text_content(Some(text));
+    parent.append_child(&element)?;
+    Ok(element)
+}
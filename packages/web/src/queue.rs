@@ -0,0 +1,124 @@
+//! The events of an app, from the moment the browser dispatches them until the VirtualDom has handled them
+
+use crate::dom::UiEvent;
+use dioxus_core::{EventDispatch, VirtualDom};
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+    task::{Poll, Waker},
+};
+
+/// The events of an app that the event loop hasn't seen yet
+///
+/// Events the VirtualDom says are [`EventDispatch::Synchronous`] are handled while the browser is still dispatching
+/// them, after the events queued before them, so their listeners can prevent the default action of the browser. While
+/// the event loop is rendering, the VirtualDom is busy and every event waits for the loop.
+#[derive(Clone, Default)]
+pub(crate) struct EventQueue {
+    inner: Rc<RefCell<Queued>>,
+}
+
+#[derive(Default)]
+struct Queued {
+    events: Vec<QueuedEvent>,
+    dom: Weak<RefCell<VirtualDom>>,
+    waker: Option<Waker>,
+}
+
+/// An event, and whether its listeners already ran
+pub(crate) struct QueuedEvent {
+    pub event: UiEvent,
+    pub handled: bool,
+}
+
+impl QueuedEvent {
+    fn handle(&mut self, dom: &mut VirtualDom) -> bool {
+        self.handled = true;
+        let UiEvent {
+            name,
+            data,
+            element,
+            bubbles,
+            ..
+        } = &self.event;
+        dom.handle_event(name, data.clone(), *element, *bubbles)
+    }
+}
+
+impl EventQueue {
+    /// Start handling the synchronous events of the dom as they come in
+    pub fn attach(&self, dom: &Rc<RefCell<VirtualDom>>) {
+        self.inner.borrow_mut().dom = Rc::downgrade(dom);
+    }
+
+    /// Add an event the browser dispatched, handling it right away if it has to be
+    pub fn push(&self, event: UiEvent) {
+        let mut event = QueuedEvent {
+            event,
+            handled: false,
+        };
+
+        let dom = self.inner.borrow().dom.upgrade();
+        if let Some(mut dom) = dom.as_ref().and_then(|dom| dom.try_borrow_mut().ok()) {
+            if dom.event_dispatch(&event.event.name) == EventDispatch::Synchronous {
+                // The queue isn't borrowed while listeners run, since they can make the browser dispatch more events
+                let mut earlier = std::mem::take(&mut self.inner.borrow_mut().events);
+                for earlier in earlier.iter_mut().filter(|earlier| !earlier.handled) {
+                    earlier.handle(&mut dom);
+                }
+                if event.handle(&mut dom) {
+                    event.event.event.prevent_default();
+                }
+
+                let mut queued = self.inner.borrow_mut();
+                earlier.append(&mut queued.events);
+                queued.events = earlier;
+            }
+        }
+
+        let mut queued = self.inner.borrow_mut();
+        queued.events.push(event);
+        if let Some(waker) = queued.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Wait until there's an event in the queue
+    pub async fn wait(&self) {
+        futures_util::future::poll_fn(|cx| {
+            let mut queued = self.inner.borrow_mut();
+            if queued.events.is_empty() {
+                queued.waker = Some(cx.waker().clone());
+                Poll::Pending
+            } else {
+                Poll::Ready(())
+            }
+        })
+        .await
+    }
+
+    /// Take every event out of the queue, in the order they came in
+    ///
+    /// Each run of deferred events of the same type on the same element is merged into its last event, since the
+    /// listeners only need to see where the pointer or the scroll position ended up.
+    pub fn take(&self, dom: &VirtualDom) -> Vec<QueuedEvent> {
+        let events = std::mem::take(&mut self.inner.borrow_mut().events);
+
+        let mut merged: Vec<QueuedEvent> = Vec::with_capacity(events.len());
+        for event in events {
+            if let Some(last) = merged.last_mut() {
+                if !last.handled
+                    && !event.handled
+                    && last.event.name == event.event.name
+                    && last.event.element == event.event.element
+                    && dom.event_dispatch(&event.event.name) == EventDispatch::Deferred
+                {
+                    *last = event;
+                    continue;
+                }
+            }
+            merged.push(event);
+        }
+        merged
+    }
+}
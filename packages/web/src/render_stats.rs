@@ -0,0 +1,64 @@
+//! A panel for debug builds that shows which components render without changing anything
+
+use dioxus_core::ScopeStats;
+use std::time::Duration;
+use wasm_bindgen::JsValue;
+use web_sys::Document;
+
+const PANEL_ID: &str = "dioxus-render-stats";
+
+const PANEL_STYLE: &str = "position: fixed; right: 8px; bottom: 8px; z-index: 2147483646; \
+    margin: 0; padding: 8px 12px; max-height: 40vh; overflow: auto; pointer-events: none; \
+    background: rgba(20, 20, 20, 0.85); color: #e8e8e8; \
+    font-family: ui-monospace, Menlo, Consolas, monospace; font-size: 11px;";
+
+// Only the worst offenders fit in the corner of the page
+const SHOWN: usize = 10;
+
+/// The time since the page loaded, since std has no clock in the browser
+pub(crate) fn now() -> Duration {
+    let millis = web_sys::window()
+        .and_then(|window| window.performance())
+        .map(|performance| performance.now())
+        .unwrap_or_default();
+    Duration::from_secs_f64(millis / 1000.0)
+}
+
+/// Show the components that rendered the most, the ones that wasted the most renders first
+pub(crate) fn show(stats: &[ScopeStats]) {
+    let document = match web_sys::window().and_then(|window| window.document()) {
+        Some(document) => document,
+        None => return,
+    };
+    let _ = try_show(&document, stats);
+}
+
+fn try_show(document: &Document, stats: &[ScopeStats]) -> Result<(), JsValue> {
+    let panel = match document.get_element_by_id(PANEL_ID) {
+        Some(panel) => panel,
+        None => {
+            let panel = document.create_element("pre")?;
+            panel.set_id(PANEL_ID);
+            panel.set_attribute("style", PANEL_STYLE)?;
+            let body = document.body().ok_or(JsValue::NULL)?;
+            body.append_child(&panel)?;
+            panel
+        }
+    };
+
+    let mut text = format!(
+        "{:<24} {:>8} {:>8} {:>10}\n",
+        "component", "renders", "wasted", "diff ms"
+    );
+    for stats in stats.iter().take(SHOWN) {
+        text.push_str(&format!(
+            "{:<24} {:>8} {:>8} {:>10.2}\n",
+            stats.name,
+            stats.renders,
+            stats.wasted_renders,
+            stats.diff_time.as_secs_f64() * 1000.0
+        ));
+    }
+    panel.set_text_content(Some(text.trim_end()));
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::KeyboardEvent;
+
+/// Run the `use_keyboard_shortcut` handler for every chord pressed on the document
+///
+/// The listener is shared by every app on the page, so it's only added once.
+pub(crate) fn listen_for_shortcuts() {
+    static LISTENER: std::sync::Once = std::sync::Once::new();
+    LISTENER.call_once(|| {
+        let listener = Closure::wrap(Box::new(|event: KeyboardEvent| {
+            // Holding down a modifier on its own isn't a shortcut yet
+            if matches!(event.key().as_str(), "Control" | "Alt" | "Shift" | "Meta") {
+                return;
+            }
+
+            let modifiers = [
+                (event.ctrl_key(), "ctrl+"),
+                (event.alt_key(), "alt+"),
+                (event.shift_key(), "shift+"),
+                (event.meta_key(), "meta+"),
+            ];
+            let mut chord = modifiers
+                .iter()
+                .filter(|(held, _)| *held)
+                .map(|(_, name)| *name)
+                .collect::<String>();
+            chord.push_str(&event.code());
+
+            if let Ok(shortcut) = chord.parse() {
+                if dioxus_hooks::trigger_shortcut(&shortcut) {
+                    event.prevent_default();
+                }
+            }
+        }) as Box<dyn FnMut(KeyboardEvent)>);
+
+        if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+            let _ = document
+                .add_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref());
+        }
+        listener.forget();
+    });
+}
@@ -0,0 +1,30 @@
+use dioxus_hooks::PersistentStorage;
+
+/// Backs `use_persistent` with the `localStorage` of the page
+pub(crate) struct LocalStorage;
+
+impl LocalStorage {
+    fn storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+}
+
+impl PersistentStorage for LocalStorage {
+    fn load(&self, key: &str) -> Option<String> {
+        Self::storage()?.get_item(key).ok()?
+    }
+
+    fn store(&self, key: &str, value: &str) {
+        match Self::storage() {
+            Some(storage) => {
+                if storage.set_item(key, value).is_err() {
+                    log::error!("Failed to write {:?} to localStorage", key);
+                }
+            }
+            None => log::warn!(
+                "localStorage is not available, {:?} won't be persisted",
+                key
+            ),
+        }
+    }
+}
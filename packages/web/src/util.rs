@@ -1,72 +1,115 @@
-//! Utilities specific to websys
+//! Runs the scripts of `use_eval` in the page
+//!
+//! # Safety
+//!
+//! Please be very careful with scripts. A script with too many dynamic parts is practically asking for a hacker to
+//! find an XSS vulnerability in it. **This applies especially to web targets, where the JavaScript context has access
+//! to most, if not all of your application data.**
 
-use std::{
-    future::{IntoFuture, Ready},
-    str::FromStr,
+use dioxus_hooks::{EvalBackend, EvalChannel, EvalError, Evaluator};
+use serde_json::Value;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+// Runs the script as the body of an async function with a `dioxus` object, returning the function that delivers the
+// values from Rust and the promise of what the script returns as JSON
+const EVAL_JS: &str = r#"
+const received = [];
+const waiting = [];
+const dioxus = {
+    send: (value) => onSend(JSON.stringify(value === undefined ? null : value)),
+    recv: () => new Promise((resolve) => received.length ? resolve(received.shift()) : waiting.push(resolve)),
+};
+const deliver = (json) => {
+    const value = JSON.parse(json);
+    waiting.length ? waiting.shift()(value) : received.push(value);
 };
+const AsyncFunction = Object.getPrototypeOf(async function () {}).constructor;
+const result = new Promise((resolve) => resolve(new AsyncFunction("dioxus", script)(dioxus)))
+    .then((value) => JSON.stringify(value === undefined ? null : value));
+return [deliver, result];
+"#;
 
-use dioxus_core::*;
-use serde::de::Error;
-use serde_json::Value;
+pub(crate) struct WebEval;
+
+impl EvalBackend for WebEval {
+    fn eval(&self, script: &str) -> Result<Rc<dyn Evaluator>, EvalError> {
+        let channel = Rc::new(EvalChannel::default());
 
-/// Get a closure that executes any JavaScript in the webpage.
-///
-/// # Safety
-///
-/// Please be very careful with this function. A script with too many dynamic
-/// parts is practically asking for a hacker to find an XSS vulnerability in
-/// it. **This applies especially to web targets, where the JavaScript context
-/// has access to most, if not all of your application data.**
-///
-/// # Panics
-///
-/// The closure will panic if the provided script is not valid JavaScript code
-/// or if it returns an uncaught error.
-pub fn use_eval<S: std::string::ToString>(cx: &ScopeState) -> &dyn Fn(S) -> EvalResult {
-    cx.use_hook(|| {
-        |script: S| {
-            let body = script.to_string();
-            EvalResult {
-                value: if let Ok(value) =
-                    js_sys::Function::new_no_args(&body).call0(&wasm_bindgen::JsValue::NULL)
-                {
-                    if let Ok(stringified) = js_sys::JSON::stringify(&value) {
-                        if !stringified.is_undefined() && stringified.is_valid_utf16() {
-                            let string: String = stringified.into();
-                            Value::from_str(&string)
-                        } else {
-                            Err(serde_json::Error::custom("Failed to stringify result"))
-                        }
-                    } else {
-                        Err(serde_json::Error::custom("Failed to stringify result"))
-                    }
-                } else {
-                    Err(serde_json::Error::custom("Failed to execute script"))
-                },
+        let on_send = {
+            let channel = channel.clone();
+            Closure::wrap(Box::new(move |json: String| {
+                if let Ok(value) = serde_json::from_str(&json) {
+                    channel.receive(value);
+                }
+            }) as Box<dyn FnMut(String)>)
+        };
+
+        let started = js_sys::Function::new_with_args("script, onSend", EVAL_JS)
+            .call2(
+                &JsValue::NULL,
+                &JsValue::from_str(script),
+                on_send.as_ref().unchecked_ref(),
+            )
+            .map_err(js_error)?
+            .unchecked_into::<js_sys::Array>();
+        let deliver = started.get(0).unchecked_into::<js_sys::Function>();
+        let result = started.get(1).unchecked_into::<js_sys::Promise>();
+
+        wasm_bindgen_futures::spawn_local({
+            let channel = channel.clone();
+            async move {
+                let result = match wasm_bindgen_futures::JsFuture::from(result).await {
+                    Ok(json) => json
+                        .as_string()
+                        .and_then(|json| serde_json::from_str(&json).ok())
+                        .ok_or_else(|| {
+                            EvalError::Serialization("the script didn't return JSON".to_string())
+                        }),
+                    Err(err) => Err(js_error(err)),
+                };
+                channel.finish(result);
+
+                // the script can send until it's done
+                drop(on_send);
             }
-        }
-    })
-}
+        });
 
-/// A wrapper around the result of a JavaScript evaluation.
-/// This implements IntoFuture to be compatible with the desktop renderer's EvalResult.
-pub struct EvalResult {
-    value: Result<Value, serde_json::Error>,
+        Ok(Rc::new(WebEvaluator { deliver, channel }))
+    }
 }
 
-impl EvalResult {
-    /// Get the result of the Javascript execution.
-    pub fn get(self) -> Result<Value, serde_json::Error> {
-        self.value
-    }
+struct WebEvaluator {
+    deliver: js_sys::Function,
+    channel: Rc<EvalChannel>,
 }
 
-impl IntoFuture for EvalResult {
-    type Output = Result<Value, serde_json::Error>;
+impl Evaluator for WebEvaluator {
+    fn send(&self, value: Value) -> Result<(), EvalError> {
+        if self.channel.is_finished() {
+            return Err(EvalError::Finished);
+        }
 
-    type IntoFuture = Ready<Result<Value, serde_json::Error>>;
+        self.deliver
+            .call1(&JsValue::NULL, &JsValue::from_str(&value.to_string()))
+            .map(|_| ())
+            .map_err(js_error)
+    }
 
-    fn into_future(self) -> Self::IntoFuture {
-        std::future::ready(self.value)
+    fn poll_recv(&self, cx: &mut Context<'_>) -> Poll<Result<Value, EvalError>> {
+        self.channel.poll_recv(cx)
     }
+
+    fn poll_join(&self, cx: &mut Context<'_>) -> Poll<Result<Value, EvalError>> {
+        self.channel.poll_join(cx)
+    }
+}
+
+fn js_error(err: JsValue) -> EvalError {
+    let message = match err.dyn_ref::<js_sys::Error>() {
+        Some(err) => String::from(err.to_string()),
+        None => err.as_string().unwrap_or_else(|| format!("{:?}", err)),
+    };
+    EvalError::Failed(message)
 }
@@ -0,0 +1,28 @@
+use dioxus_hooks::{WindowBackend, WindowSize};
+
+/// Backs `use_window_size` and `use_media_query` with the browser window
+pub(crate) struct WebWindow;
+
+impl WindowBackend for WebWindow {
+    fn size(&self) -> WindowSize {
+        let window = match web_sys::window() {
+            Some(window) => window,
+            None => return WindowSize::default(),
+        };
+
+        let dimension = |value: Result<wasm_bindgen::JsValue, _>| {
+            value.ok().and_then(|v| v.as_f64()).unwrap_or_default()
+        };
+
+        WindowSize {
+            width: dimension(window.inner_width()),
+            height: dimension(window.inner_height()),
+        }
+    }
+
+    fn matches_media(&self, query: &str) -> bool {
+        web_sys::window()
+            .and_then(|window| window.match_media(query).ok().flatten())
+            .map_or(false, |list| list.matches())
+    }
+}
@@ -0,0 +1,146 @@
+//! Run the VirtualDom in a Web Worker, off the main thread
+//!
+//! The page launches the worker with [`launch_worker`], which loads the interpreter onto the main thread. The worker
+//! runs the app with [`launch_in_worker`], posts the serialized mutations of every render to the page, and gets the
+//! events of the page back in the same messages desktop and liveview use. State that takes a while to compute no
+//! longer holds up scrolling and typing, at the cost of serializing every edit and event.
+//!
+//! The worker script has to start the wasm of the app itself, like this one for the `no-modules` target of
+//! wasm-bindgen:
+//!
+//! ```js
+//! importScripts("./app.js");
+//! wasm_bindgen("./app_bg.wasm").then(() => wasm_bindgen.start_worker());
+//! ```
+
+use crate::Config;
+use dioxus_core::{Element, Mutations, Scope, VirtualDom};
+use dioxus_html::{FileChunk, HtmlEvent, SerializedFileEngine};
+use dioxus_interpreter_js::INTERPRETER_JS;
+use futures_util::{pin_mut, FutureExt, StreamExt};
+use std::sync::Arc;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{DedicatedWorkerGlobalScope, MessageEvent, Worker};
+
+// Runs in the same function as the interpreter, so it sees its classes
+const HOST_JS: &str = r#"
+window.interpreter = new Interpreter(root);
+window.ipc = { postMessage: (message) => worker.postMessage(message) };
+worker.onmessage = (event) => window.interpreter.handleEdits(JSON.parse(event.data));
+"#;
+
+/// Start the worker at `worker_url` and render the app it launches into the `main` element
+///
+/// The worker has to call [`launch_in_worker`] when it starts.
+pub fn launch_worker(worker_url: &str) -> Worker {
+    launch_worker_cfg(worker_url, Config::default())
+}
+
+/// Start the worker at `worker_url` and render the app it launches into the root the config points at
+pub fn launch_worker_cfg(worker_url: &str, cfg: Config) -> Worker {
+    let document = crate::dom::load_document();
+    let root = match (cfg.root_element, &cfg.root_selector) {
+        (Some(root), _) => root,
+        (None, Some(selector)) => document
+            .query_selector(selector)
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| panic!("no element matches the selector {:?}", selector)),
+        (None, None) => document
+            .get_element_by_id(&cfg.rootname)
+            .unwrap_or_else(|| panic!("no element has the id {:?}", cfg.rootname)),
+    };
+
+    let worker = Worker::new(worker_url).expect("the worker script should load");
+    let host = js_sys::Function::new_with_args("root, worker", &[INTERPRETER_JS, HOST_JS].concat());
+    host.call2(&JsValue::NULL, &root, &worker)
+        .expect("the interpreter should start");
+
+    worker
+}
+
+/// Run an app inside the worker that the page started with [`launch_worker`]
+pub fn launch_in_worker(root: fn(Scope) -> Element) {
+    launch_in_worker_with_props(root, ())
+}
+
+/// Run an app with props inside the worker that the page started with [`launch_worker`]
+pub fn launch_in_worker_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_props: T) {
+    wasm_bindgen_futures::spawn_local(run_in_worker(root, root_props));
+}
+
+async fn run_in_worker<T: 'static>(root: fn(Scope<T>) -> Element, root_props: T) {
+    let scope: DedicatedWorkerGlobalScope = js_sys::global().unchecked_into();
+
+    let (tx, mut rx) = futures_channel::mpsc::unbounded::<String>();
+    let onmessage = Closure::wrap(Box::new(move |event: MessageEvent| {
+        if let Some(message) = event.data().as_string() {
+            let _ = tx.unbounded_send(message);
+        }
+    }) as Box<dyn FnMut(MessageEvent)>);
+    scope.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+    onmessage.forget();
+
+    let mut dom = VirtualDom::new_with_props(root, root_props);
+    post(&scope, &dom.rebuild());
+
+    // desktop uses this wrapper struct thing around the actual event itself
+    #[derive(serde::Deserialize)]
+    struct IpcMessage {
+        method: String,
+        params: serde_json::Value,
+    }
+
+    // the files of an event are posted in chunks right before the event
+    let mut pending_files = SerializedFileEngine::new();
+
+    loop {
+        let message = {
+            let work = dom.wait_for_work().fuse();
+            pin_mut!(work);
+
+            futures_util::select! {
+                _ = work => None,
+                message = rx.next() => message,
+            }
+        };
+
+        match message.map(|message| serde_json::from_str::<IpcMessage>(&message)) {
+            Some(Ok(IpcMessage { method, params })) if method == "file_chunk" => {
+                if let Ok(chunk) = serde_json::from_value::<FileChunk>(params) {
+                    let _ = pending_files.push_chunk(&chunk);
+                }
+            }
+            Some(Ok(IpcMessage { method, params })) if method == "user_event" => {
+                if let Ok(mut event) = serde_json::from_value::<HtmlEvent>(params) {
+                    if !pending_files.is_empty() {
+                        event
+                            .data
+                            .set_files(Arc::new(std::mem::take(&mut pending_files)));
+                    }
+                    dom.handle_event(
+                        &event.name,
+                        event.data.into_any(),
+                        event.element,
+                        event.bubbles,
+                    );
+                }
+            }
+            _ => {}
+        }
+
+        let edits = dom
+            .render_with_deadline(gloo_timers::future::TimeoutFuture::new(10))
+            .await;
+        post(&scope, &edits);
+    }
+}
+
+fn post(scope: &DedicatedWorkerGlobalScope, mutations: &Mutations) {
+    if mutations.templates.is_empty() && mutations.edits.is_empty() {
+        return;
+    }
+    if let Ok(message) = serde_json::to_string(mutations) {
+        let _ = scope.post_message(&JsValue::from_str(&message));
+    }
+}